@@ -1,34 +1,567 @@
-//! Low-order (quadratic and cubic) Bézier curves.
+//! Low-order (quadratic and cubic) Bézier curves, and bicubic Bézier patches.
 // NOTE: Most info from https://pomax.github.io/bezierinfo
 
-use num_traits::{Zero, real::Real};
+use num_traits::{Zero, One, real::Real};
+use approx::RelativeEq;
 use crate::ops::*;
 use std::ops::*;
 use std::ops::Add;
+use std::fmt::{self, Display, Formatter};
 use crate::vec::repr_c::{
     Vec3 as CVec3,
     Vec4 as CVec4,
 };
+#[cfg(feature="std")]
+use alloc::vec::Vec;
+
+/// The number of samples of a curve before refining, in `project_point()`.
+const PROJECT_POINT_STEPS: usize = 32;
+
+/// The number of intervals in the arc length table of a curve, see `CubicBezier2::frame_at_length()`.
+const ARC_LENGTH_INTERVALS: usize = 64;
+
+/// (node, weight) pairs of the 5-point Gauss-Legendre quadrature over [-1, 1].
+const GAUSS_LEGENDRE_5: [(f64, f64); 5] = [
+    (0., 0.568_888_888_888_888_9),
+    (-0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+    (0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+    (-0.906_179_845_938_664, 0.236_926_885_056_189_1),
+    (0.906_179_845_938_664, 0.236_926_885_056_189_1),
+];
+/// (node, weight) pairs of the 8-point Gauss-Legendre quadrature over [-1, 1].
+const GAUSS_LEGENDRE_8: [(f64, f64); 8] = [
+    (-0.183_434_642_495_649_8, 0.362_683_783_378_362),
+    (0.183_434_642_495_649_8, 0.362_683_783_378_362),
+    (-0.525_532_409_916_329, 0.313_706_645_877_887_3),
+    (0.525_532_409_916_329, 0.313_706_645_877_887_3),
+    (-0.796_666_477_413_626_7, 0.222_381_034_453_374_5),
+    (0.796_666_477_413_626_7, 0.222_381_034_453_374_5),
+    (-0.960_289_856_497_536_3, 0.101_228_536_290_376_3),
+    (0.960_289_856_497_536_3, 0.101_228_536_290_376_3),
+];
+/// The maximum number of times `integrate_adaptive()` halves an interval, and of geometrically
+/// smaller intervals `integrate_speed()` makes around a dip.
+const MAX_QUADRATURE_DEPTH: u32 = 16;
+
+/// Integrates `f` between `t0` and `t1` with the Gauss-Legendre quadrature given by `rule`.
+fn gauss_legendre<T: Real, F: Fn(T) -> T>(f: &F, t0: T, t1: T, rule: &[(f64, f64)]) -> T {
+    let half = (t1 - t0) / (T::one() + T::one());
+    let mid = t0 + half;
+    rule.iter().fold(T::zero(), |sum, &(x, w)| sum + T::from(w).unwrap() * f(mid + half * T::from(x).unwrap())) * half
+}
+
+/// Integrates `f` between `t0` and `t1` with the Gauss-Legendre quadrature given by `rule`,
+/// halving intervals wherever that changes the result by more than `tolerance` (which is itself
+/// halved along with the intervals), e.g around kinks of `f`.
+fn integrate_adaptive<T: Real, F: Fn(T) -> T>(f: &F, t0: T, t1: T, tolerance: T, rule: &[(f64, f64)]) -> T {
+    fn recurse<T: Real, F: Fn(T) -> T>(f: &F, rule: &[(f64, f64)], t0: T, t1: T, whole: T, tolerance: T, depth: u32) -> T {
+        let two = T::one() + T::one();
+        let mid = (t0 + t1) / two;
+        let (left, right) = (gauss_legendre(f, t0, mid, rule), gauss_legendre(f, mid, t1, rule));
+        if depth >= MAX_QUADRATURE_DEPTH || (left + right - whole).abs() <= tolerance {
+            return left + right;
+        }
+        recurse(f, rule, t0, mid, left, tolerance / two, depth + 1) + recurse(f, rule, mid, t1, right, tolerance / two, depth + 1)
+    }
+    recurse(f, rule, t0, t1, gauss_legendre(f, t0, t1, rule), tolerance, 0)
+}
+
+/// Finds a local minimum of `f` between `a` and `b`, by golden-section search.
+fn golden_section_minimum<T: Real, F: Fn(T) -> T>(f: &F, mut a: T, mut b: T) -> T {
+    let r = T::from(0.618_033_988_749_895).unwrap();
+    let (mut c, mut d) = (b - (b - a) * r, a + (b - a) * r);
+    let (mut fc, mut fd) = (f(c), f(d));
+    for _ in 0..64 {
+        if fc < fd {
+            b = d;
+            d = c;
+            fd = fc;
+            c = b - (b - a) * r;
+            fc = f(c);
+        } else {
+            a = c;
+            c = d;
+            fc = fd;
+            d = a + (b - a) * r;
+            fd = f(d);
+        }
+    }
+    (a + b) / (T::one() + T::one())
+}
+
+/// Finds the roots between 0 and 1 (inclusive) of the polynomial given by its Bernstein
+/// coefficients, which must be of degree 2 or 3. The roots are returned in increasing order,
+/// along with their count.
+///
+/// The interval is split at the polynomial's critical points, so that it is monotonic over each
+/// piece, and a root is searched by bisection in each piece whose ends have opposite signs.
+/// Values within rounding error of zero count as zero: a critical point or an end of the interval
+/// where the polynomial is zero is reported as a root, exactly once. A polynomial which is zero
+/// everywhere has no roots.
+fn bernstein_roots_01<T: Real>(bernstein: &[T]) -> ([T; 4], usize) {
+    let (zero, one) = (T::zero(), T::one());
+    let (two, three) = (one + one, one + one + one);
+    // Coefficients in the power basis, lowest degree first.
+    let c = match *bernstein {
+        [d0, d1, d2] => [d0, two * (d1 - d0), d0 - two * d1 + d2, zero],
+        [d0, d1, d2, d3] => [d0, three * (d1 - d0), three * (d0 - two * d1 + d2), d3 - d0 + three * (d1 - d2)],
+        _ => unreachable!(),
+    };
+    let mut roots = [zero; 4];
+    let mut count = 0;
+    let scale = c.iter().fold(zero, |m, x| m.max(x.abs()));
+    if scale == zero {
+        return (roots, count);
+    }
+    let f = |t: T| ((c[3] * t + c[2]) * t + c[1]) * t + c[0];
+    let tolerance = scale * T::epsilon() * T::from(8).unwrap();
+    let is_zero = |y: T| y.abs() <= tolerance;
+
+    // The critical points are the roots of 3*c3*t² + 2*c2*t + c1, which are computed in a way
+    // that avoids cancellation.
+    let mut knots = [zero, one, one, one];
+    let mut knot_count = 1;
+    {
+        let (a, b, c) = (three * c[3], two * c[2], c[1]);
+        let mut critical = [None, None];
+        if a == zero {
+            if b != zero {
+                critical[0] = Some(-c / b);
+            }
+        } else {
+            let discriminant = b * b - T::from(4).unwrap() * a * c;
+            if discriminant >= zero {
+                let q = -(b + b.signum() * discriminant.sqrt()) / two;
+                critical[0] = Some(q / a);
+                if q != zero {
+                    critical[1] = Some(c / q);
+                }
+            }
+        }
+        if let (Some(x), Some(y)) = (critical[0], critical[1]) {
+            if y < x {
+                critical.swap(0, 1);
+            }
+        }
+        for t in critical.iter().filter_map(|&t| t) {
+            if t > zero && t < one && t > knots[knot_count - 1] {
+                knots[knot_count] = t;
+                knot_count += 1;
+            }
+        }
+        knots[knot_count] = one;
+        knot_count += 1;
+    }
+
+    let mut push = |t: T, roots: &mut [T; 4]| {
+        if count == 0 || roots[count - 1] < t {
+            roots[count] = t;
+            count += 1;
+        }
+    };
+    for i in 0..knot_count {
+        let (lo, f_lo) = (knots[i], f(knots[i]));
+        if is_zero(f_lo) {
+            push(lo, &mut roots);
+            continue;
+        }
+        if i + 1 == knot_count {
+            break;
+        }
+        let hi = knots[i + 1];
+        let f_hi = f(hi);
+        if is_zero(f_hi) || (f_lo < zero) == (f_hi < zero) {
+            continue;
+        }
+        // Bisection, until the interval can't be split any further.
+        let (mut lo, mut hi) = (lo, hi);
+        loop {
+            let mid = (lo + hi) / two;
+            if mid <= lo || mid >= hi {
+                break;
+            }
+            let f_mid = f(mid);
+            if f_mid == zero {
+                lo = mid;
+                hi = mid;
+                break;
+            }
+            if (f_mid < zero) == (f_lo < zero) {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        push(if f(lo).abs() <= f(hi).abs() { lo } else { hi }, &mut roots);
+    }
+    (roots, count)
+}
+
+/// The maximum number of refinements of the factor in `ArcLengths::factor_at()`, which is enough for
+/// bisection alone to reach the precision of `f64`.
+const MAX_ARC_LENGTH_ITERATIONS: u32 = 64;
+
+/// The number of intervals over which the speed of a curve is sampled, see `integrate_speed()`.
+const SPEED_SAMPLE_INTERVALS: usize = 32;
+/// The maximum number of dips in the speed of a curve which `integrate_speed()` refines around.
+const MAX_SPEED_DIPS: usize = 4;
+
+/// The maximum number of breakpoints in `ArcLengths`.
+const MAX_SPEED_BREAKS: usize = 2 + MAX_SPEED_DIPS * (1 + 2 * MAX_QUADRATURE_DEPTH as usize);
+
+/// Integrates the speed of a curve (i.e the magnitude of its derivative) between 0 and 1, which
+/// gives its length, to a relative precision of about `epsilon^(3/4)`.
+fn integrate_speed<T: Real, F: Fn(T) -> T>(speed: F, rule: &[(f64, f64)]) -> T {
+    ArcLengths::new(&speed, rule).total()
+}
+
+/// The arc lengths of a curve from its start to sorted breakpoints of its interpolation factor,
+/// the first being 0 and the last being 1.
+struct ArcLengths<T> {
+    breaks: [T; MAX_SPEED_BREAKS],
+    lengths: [T; MAX_SPEED_BREAKS],
+    count: usize,
+}
+
+impl<T: Real> ArcLengths<T> {
+    /// Splits [0, 1] at breakpoints, and integrates the speed of a curve from 0 to each of them, to
+    /// a relative precision of about `epsilon^(3/4)`.
+    ///
+    /// Adaptive quadrature alone isn't enough: where a curve almost has a cusp, its speed has a dip
+    /// which is so narrow that quadratures over larger intervals miss it all the same, so halving
+    /// them changes nothing. Such dips are located first, and intervals are made geometrically
+    /// smaller towards them, down to their width.
+    fn new<F: Fn(T) -> T>(speed: &F, rule: &[(f64, f64)]) -> Self {
+        let n = T::from(SPEED_SAMPLE_INTERVALS).unwrap();
+        let mut samples = [T::zero(); SPEED_SAMPLE_INTERVALS + 1];
+        for (i, sample) in samples.iter_mut().enumerate() {
+            *sample = speed(T::from(i).unwrap() / n);
+        }
+        let max_speed = samples.iter().fold(T::zero(), |max, &s| max.max(s));
+
+        let mut breaks = [T::zero(); MAX_SPEED_BREAKS];
+        breaks[1] = T::one();
+        let mut break_count = 2;
+        let mut dip_count = 0;
+        for i in 0..=SPEED_SAMPLE_INTERVALS {
+            let is_minimum = (i == 0 || samples[i] <= samples[i - 1]) && (i == SPEED_SAMPLE_INTERVALS || samples[i] < samples[i + 1]);
+            // Shallow dips are wide enough for the adaptive quadrature.
+            let is_deep = samples[i] * T::from(8).unwrap() < max_speed;
+            if !is_minimum || !is_deep || dip_count == MAX_SPEED_DIPS {
+                continue;
+            }
+            dip_count += 1;
+            let lo = T::from(i.saturating_sub(1)).unwrap() / n;
+            let hi = T::from((i + 1).min(SPEED_SAMPLE_INTERVALS)).unwrap() / n;
+            let t = golden_section_minimum(speed, lo, hi);
+            // The speed is about `min + |B''| * |t - dip|` around the dip, so this estimates its width.
+            let width = speed(t) / max_speed;
+            breaks[break_count] = t;
+            break_count += 1;
+            let mut offset = T::one();
+            for _ in 0..MAX_QUADRATURE_DEPTH {
+                offset = offset / (T::one() + T::one());
+                if offset * T::from(4).unwrap() < width {
+                    break;
+                }
+                for &b in &[t - offset, t + offset] {
+                    if b > T::zero() && b < T::one() {
+                        breaks[break_count] = b;
+                        break_count += 1;
+                    }
+                }
+            }
+        }
+        breaks[..break_count].sort_unstable_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+        let estimate = breaks[..break_count].windows(2).fold(T::zero(), |sum, w| sum + gauss_legendre(speed, w[0], w[1], rule));
+        let sqrt_epsilon = T::epsilon().sqrt();
+        let tolerance = estimate.abs() * sqrt_epsilon * sqrt_epsilon.sqrt();
+        let mut lengths = [T::zero(); MAX_SPEED_BREAKS];
+        for i in 1..break_count {
+            let (t0, t1) = (breaks[i - 1], breaks[i]);
+            let length = if t0 < t1 { integrate_adaptive(speed, t0, t1, tolerance * (t1 - t0), rule) } else { T::zero() };
+            lengths[i] = lengths[i - 1] + length;
+        }
+        Self { breaks, lengths, count: break_count }
+    }
+    /// Gets the length of the whole curve.
+    fn total(&self) -> T {
+        self.lengths[self.count - 1]
+    }
+    /// Finds the factor at which the arc length of the curve, given its `speed`, is `s` (clamped
+    /// between zero and the length of the curve), to within `epsilon`, or to about the precision of
+    /// `total()` if `epsilon` is `None`.
+    ///
+    /// The breakpoints give an interval which contains the factor and no dip of the speed.
+    /// Within it, Newton iterations on the arc length are used, falling back to bisection wherever
+    /// they would leave the interval known to contain the factor.
+    fn factor_at<F: Fn(T) -> T>(&self, speed: &F, s: T, epsilon: Option<T>, rule: &[(f64, f64)]) -> T {
+        let (breaks, lengths, count) = (&self.breaks, &self.lengths, self.count);
+        let total = self.total();
+        if s <= T::zero() || total <= T::zero() {
+            return T::zero();
+        }
+        if s >= total {
+            return T::one();
+        }
+        let epsilon = epsilon.unwrap_or_else(|| {
+            let sqrt_epsilon = T::epsilon().sqrt();
+            total * sqrt_epsilon * sqrt_epsilon.sqrt()
+        });
+        let two = T::one() + T::one();
+        let i = lengths[..count].partition_point(|&l| l <= s);
+        // The factor is always between `lo` and `hi`, and the arc length at `lo` is known.
+        let (mut lo, mut lo_length, mut hi) = (breaks[i - 1], lengths[i - 1], breaks[i]);
+        let mut t = lo + (hi - lo) * (s - lo_length) / (lengths[i] - lo_length);
+        for _ in 0..MAX_ARC_LENGTH_ITERATIONS {
+            let error = lo_length + integrate_adaptive(speed, lo, t, epsilon / (two * two), rule) - s;
+            if error.abs() <= epsilon {
+                break;
+            }
+            if error < T::zero() {
+                lo = t;
+                lo_length = s + error;
+            } else {
+                hi = t;
+            }
+            let next = t - error / speed(t);
+            t = if next > lo && next < hi { next } else { (lo + hi) / two };
+            if hi - lo <= T::epsilon() {
+                break;
+            }
+        }
+        t
+    }
+}
+
+/// The shape drawn at the open ends of a stroked curve.
+///
+/// See `CubicBezier2::stroke_outline()`.
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+pub enum LineCap {
+    /// The stroke ends exactly at the end point.
+    Butt,
+    /// The stroke ends with a half-disk centered on the end point.
+    Round,
+    /// The stroke is extended past the end point by half the stroke's width.
+    Square,
+}
+
+/// The shape drawn where two parts of a stroked path meet at an angle.
+///
+/// See `CubicBezier2::stroke_outline_of_path()`.
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+pub enum LineJoin<T> {
+    /// The outer edges are extended until they meet, unless the ratio of the miter's length
+    /// to the stroke's width exceeds the given limit, in which case a bevel is used instead
+    /// (like SVG's `stroke-miterlimit`).
+    Miter(T),
+    /// The outer edges are connected by a circular arc centered on the joint.
+    Round,
+    /// The outer edges are connected by a straight line.
+    Bevel,
+}
+
+/// Error returned by `CubicBezier2::intersections()` when the curves overlap along a stretch,
+/// which makes their intersections infinitely many.
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+pub struct CurvesOverlapError;
+
+impl Display for CurvesOverlapError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "curves overlap, so their intersections can't be enumerated")
+    }
+}
+
+/// The intermediate points of De Casteljau's algorithm on a quadratic Bézier curve.
+///
+/// See `QuadraticBezier2::de_casteljau()`.
+#[derive(Debug, Default, Copy, Clone, Hash, PartialEq, Eq)]
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+pub struct QuadraticDeCasteljau<P> {
+    /// The points interpolated between `start` and `ctrl`, then between `ctrl` and `end`.
+    ///
+    /// They are the control points of the first and second halves of the split curve, and
+    /// define the tangent at `point`.
+    pub first: [P; 2],
+    /// The point on the curve.
+    pub point: P,
+}
+
+/// The intermediate points of De Casteljau's algorithm on a cubic Bézier curve.
+///
+/// See `CubicBezier2::de_casteljau()`.
+#[derive(Debug, Default, Copy, Clone, Hash, PartialEq, Eq)]
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+pub struct CubicDeCasteljau<P> {
+    /// The points interpolated between consecutive points of the curve,
+    /// i.e `start`, `ctrl0`, `ctrl1` and `end`.
+    pub first: [P; 3],
+    /// The points interpolated between consecutive points of `first`.
+    ///
+    /// They define the tangent at `point`.
+    pub second: [P; 2],
+    /// The point on the curve.
+    pub point: P,
+}
+
+/// The maximum number of pairs of pieces examined by `CubicBezier2::intersections()`, past which the
+/// curves are considered to overlap.
+#[cfg(feature="std")]
+const MAX_INTERSECTION_PAIRS: usize = 1 << 16;
 
 // WISH: OOBBs from beziers
-// WISH: "Tracing a curve at fixed distance intervals"
-// WISH: Line-curve intersection (Especially straight horizontal and straight vertical)
 
 macro_rules! bezier_impl_any {
-    (3 $Bezier:ident $Point:ident) => {
+    (3 $Bezier:ident $Point:ident $LineSegment:ident) => {
 
-        bezier_impl_any!{$Bezier $Point}
+        bezier_impl_any!{$Bezier $Point $LineSegment}
 
         impl<T: Real> $Bezier<T> {
-            /// Gets the Axis-Aligned Bounding Box for this curve.
+            /// Gets the tight Axis-Aligned Bounding Box for this curve.
+            ///
+            /// See `aabr()`, which this extends to the `z` axis.
             pub fn aabb(self) -> Aabb<T> {
                 let (min_x, max_x) = self.x_bounds();
                 let (min_y, max_y) = self.y_bounds();
                 let (min_z, max_z) = self.z_bounds();
                 Aabb {
-                    min: Vec3::new(min_x, min_y, min_z),
-                    max: Vec3::new(max_x, max_y, max_z),
+                    min: Vec3::new(self.evaluate(min_x).x, self.evaluate(min_y).y, self.evaluate(min_z).z),
+                    max: Vec3::new(self.evaluate(max_x).x, self.evaluate(max_y).y, self.evaluate(max_z).z),
+                }
+            }
+            /// Gets the curvature at interpolation factor `t`, i.e the inverse of the radius of the
+            /// osculating circle.
+            ///
+            /// This is `|B' × B''| / |B'|³`, computed from the first and second derivatives. Unlike
+            /// the curvature of 2D curves, it has no sign. It is `None` where it is undefined, i.e
+            /// where the first derivative is zero (e.g at a cusp), rather than infinite or NaN.
+            pub fn curvature(self, t: T) -> Option<T> {
+                let d1 = self.evaluate_derivative(t);
+                let d2 = self.evaluate_second_derivative(t);
+                let speed_squared = d1.magnitude_squared();
+                let denominator = speed_squared * speed_squared.sqrt();
+                if denominator == T::zero() {
+                    return None;
+                }
+                Some(d1.cross(d2).magnitude() / denominator)
+            }
+            /// Gets a unit normal at interpolation factor `t`, given a reference `up` vector: this is
+            /// the normalized cross product of `up` and the tangent.
+            ///
+            /// The result is orthogonal to both, so e.g a ribbon extruded along it faces `up` as much
+            /// as the curve allows. For a curve in the XY plane and `up` being `Vec3::unit_z()`, this
+            /// is the same as the normal of the 2D curve.
+            ///
+            /// It is `None` where the first derivative is zero (e.g at a cusp), or parallel to `up`,
+            /// rather than NaN.
+            pub fn normal(self, t: T, up: Vec3<T>) -> Option<Vec3<T>> {
+                let n = up.cross(self.evaluate_derivative(t));
+                if n.is_zero() {
+                    return None;
+                }
+                Some(n.normalized())
+            }
+            /// Gets the Frenet frame at interpolation factor `t`, i.e the unit tangent, normal and
+            /// binormal, in that order.
+            ///
+            /// The normal points towards the center of curvature, and the binormal is the cross
+            /// product of the tangent and the normal. The frame is `None` where it is undefined, i.e
+            /// where the curve is locally straight (including everywhere along a straight curve) or
+            /// the first derivative is zero.
+            ///
+            /// It may also suddenly flip around inflection points; `rotation_minimizing_frames()`
+            /// is usually better suited to sweeping geometry along the curve.
+            pub fn frenet_frame(self, t: T) -> Option<(Vec3<T>, Vec3<T>, Vec3<T>)> {
+                let d1 = self.evaluate_derivative(t);
+                let binormal = d1.cross(self.evaluate_second_derivative(t));
+                if d1.is_zero() || binormal.is_zero() {
+                    return None;
+                }
+                let (tangent, binormal) = (d1.normalized(), binormal.normalized());
+                Some((tangent, binormal.cross(tangent), binormal))
+            }
+            /// Samples this curve at `samples` evenly spaced interpolation factors, from 0 to 1, and
+            /// gets the point and the rotation-minimizing frame at each of them.
+            ///
+            /// The frame is a rotation matrix whose columns are the unit tangent, normal and binormal,
+            /// so it maps the X axis to the tangent; it twists as little as possible from one
+            /// sample to the next, which is what sweeping e.g a tube along the curve needs.
+            /// Frames are propagated with the double reflection method of Wang et al.,
+            /// _"Computation of Rotation Minimizing Frames"_, starting from the Frenet frame at the
+            /// start of the curve, or from an arbitrary normal if it is undefined (e.g if the curve is straight).
+            ///
+            /// Where the derivative is zero (e.g at coincident control points), the tangent is
+            /// the limit of the tangents around it.
+            ///
+            /// # Panics
+            /// If `samples` is less than 2.
+            #[cfg(feature="std")]
+            pub fn rotation_minimizing_frames(self, samples: usize) -> Vec<(Vec3<T>, Cols3<T>)> {
+                assert!(samples >= 2, "at least the start and end of the curve must be sampled");
+                let two = T::one() + T::one();
+                let tangent_at = |t: T| {
+                    let d1 = self.evaluate_derivative(t);
+                    if !d1.is_zero() {
+                        return d1.normalized();
+                    }
+                    // Near a zero of the derivative, the derivative points along the second one,
+                    // backwards before the zero.
+                    let d2 = self.evaluate_second_derivative(t);
+                    let d2 = if t + t < T::one() { d2 } else { -d2 };
+                    if !d2.is_zero() {
+                        return d2.normalized();
+                    }
+                    let chord = self.end - self.start;
+                    if chord.is_zero() { Vec3::unit_x() } else { chord.normalized() }
+                };
+                let frame = |tangent: Vec3<T>, normal: Vec3<T>| {
+                    Cols3::from_col_arrays([tangent.into_array(), normal.into_array(), tangent.cross(normal).into_array()])
+                };
+
+                let mut tangent = tangent_at(T::zero());
+                let mut normal = match self.frenet_frame(T::zero()) {
+                    Some((_, normal, _)) => normal,
+                    None => {
+                        // Any unit vector orthogonal to the tangent.
+                        let a = tangent.map(|x| x.abs());
+                        let axis = if a.x <= a.y && a.x <= a.z { Vec3::unit_x() } else if a.y <= a.z { Vec3::unit_y() } else { Vec3::unit_z() };
+                        tangent.cross(axis).normalized()
+                    },
+                };
+                let mut point = self.start;
+                let mut frames = Vec::with_capacity(samples);
+                frames.push((point, frame(tangent, normal)));
+                let last = T::from(samples - 1).unwrap();
+                for i in 1..samples {
+                    let t = T::from(i).unwrap() / last;
+                    let (next_point, next_tangent) = (self.evaluate(t), tangent_at(t));
+                    // Reflect the frame across the plane bisecting the two points, then across the
+                    // plane bisecting the reflected tangent and the next one.
+                    let v1 = next_point - point;
+                    let c1 = v1.dot(v1);
+                    let (reflected_normal, reflected_tangent) = if c1 == T::zero() {
+                        (normal, tangent)
+                    } else {
+                        (normal - v1 * (two * v1.dot(normal) / c1), tangent - v1 * (two * v1.dot(tangent) / c1))
+                    };
+                    let v2 = next_tangent - reflected_tangent;
+                    let c2 = v2.dot(v2);
+                    let next_normal = if c2 == T::zero() {
+                        reflected_normal
+                    } else {
+                        reflected_normal - v2 * (two * v2.dot(reflected_normal) / c2)
+                    };
+                    // Counter the drift of rounding errors.
+                    normal = (next_normal - next_tangent * next_tangent.dot(next_normal)).normalized();
+                    tangent = next_tangent;
+                    point = next_point;
+                    frames.push((point, frame(tangent, normal)));
                 }
+                frames
             }
             /// Returns this curve, flipping the `y` coordinate of each of its points.
             pub fn flipped_z(self) -> Self {
@@ -38,6 +571,19 @@ macro_rules! bezier_impl_any {
             pub fn flip_z(&mut self) {
                 *self = self.flipped_z();
             }
+            /// Returns the mirror image of this curve across the given plane.
+            ///
+            /// Reflection is an affine map, so reflecting the control points exactly reflects the curve.
+            pub fn reflected_across_plane(self, plane: Plane<T>) -> Self {
+                self.into_vector().map(|p| plane.reflected_point(p)).into()
+            }
+            /// Returns the orthogonal projection of this curve onto the given plane.
+            ///
+            /// Projection is an affine map, so projecting the control points exactly projects the curve:
+            /// every point of the result lies on the plane.
+            pub fn projected_onto_plane(self, plane: Plane<T>) -> Self {
+                self.into_vector().map(|p| plane.projected_point(p)).into()
+            }
         }
         impl<T> Mul<$Bezier<T>> for Rows3<T> where T: Real + MulAdd<T,T,Output=T> {
             type Output = $Bezier<T>;
@@ -64,9 +610,159 @@ macro_rules! bezier_impl_any {
             }
         }
     };
-    (2 $Bezier:ident $Point:ident) => {
+    (2 $Bezier:ident $Point:ident $LineSegment:ident) => {
+
+        bezier_impl_any!{$Bezier $Point $LineSegment}
+
+        impl<T: Real> $Bezier<T> {
+            /// Gets the signed curvature at interpolation factor `t`, i.e the inverse of the radius of
+            /// the osculating circle, positive where the curve turns counter-clockwise.
+            ///
+            /// This is `(x'y'' - y'x'') / (x'² + y'²)^(3/2)`, computed from the first and second
+            /// derivatives. It is `None` where it is undefined, i.e where the first derivative is zero
+            /// (e.g at a cusp), rather than infinite or NaN.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{QuadraticBezier2, Vec2};
+            /// # fn main() {
+            /// // The parabola y = x², from x = -1 to x = 1.
+            /// let curve = QuadraticBezier2 {
+            ///     start: Vec2::new(-1_f64, 1.),
+            ///     ctrl: Vec2::new(0., -1.),
+            ///     end: Vec2::new(1., 1.),
+            /// };
+            /// assert_relative_eq!(curve.curvature(0.5).unwrap(), 2.);
+            /// assert_eq!(QuadraticBezier2::from(Vec2::zero() .. Vec2::zero()).curvature(0.5), None);
+            /// # }
+            /// ```
+            pub fn curvature(self, t: T) -> Option<T> {
+                let d1 = self.evaluate_derivative(t);
+                let d2 = self.evaluate_second_derivative(t);
+                let speed_squared = d1.magnitude_squared();
+                let denominator = speed_squared * speed_squared.sqrt();
+                if denominator == T::zero() {
+                    return None;
+                }
+                Some((d1.x*d2.y - d1.y*d2.x) / denominator)
+            }
+            /// Gets the unit normal at interpolation factor `t`, i.e the tangent rotated by 90 degrees
+            /// counter-clockwise (with Y pointing upwards), which is on the left side of the curve.
+            ///
+            /// It is `None` where the first derivative is zero (e.g at a cusp), rather than NaN.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{QuadraticBezier2, Vec2};
+            /// # fn main() {
+            /// let curve = QuadraticBezier2 { start: Vec2::new(0_f32, 0.), ctrl: Vec2::new(1., 1.), end: Vec2::new(2., 0.) };
+            /// assert_relative_eq!(curve.normal(0.5).unwrap(), Vec2::unit_y());
+            /// // Stroking is then a matter of offsetting along the normal.
+            /// let left_edge = curve.evaluate(0.5) + curve.normal(0.5).unwrap() * 0.25;
+            /// assert_relative_eq!(left_edge, Vec2::new(1., 0.75));
+            /// # }
+            /// ```
+            pub fn normal(self, t: T) -> Option<Vec2<T>> {
+                let d = self.evaluate_derivative(t);
+                if d.is_zero() {
+                    return None;
+                }
+                Some(Vec2::new(-d.y, d.x).normalized())
+            }
+            /// Returns the mirror image of this curve across the given line.
+            ///
+            /// This generalizes `flipped_x()` and `flipped_y()` to any line.
+            /// Reflection is an affine map, so reflecting the control points exactly reflects the curve.
+            pub fn reflected_across_line(self, line: Line2<T>) -> Self {
+                self.into_vector().map(|p| line.reflected_point(p)).into()
+            }
+            /// Gets the intersections of this curve with the given line segment, as pairs of the
+            /// interpolation factor along the curve and the intersection point, in increasing order of
+            /// the factor.
+            ///
+            /// This solves the curve's (quadratic or cubic) equation along the normal of the segment,
+            /// and keeps the solutions that are within both the curve and the segment. Both are
+            /// closed: the curve's ends and the segment's ends are included, so where consecutive
+            /// curves of a path meet on the segment, both report the point. Where the curve is
+            /// tangent to the segment, the point of contact is reported once.
+            ///
+            /// There are no intersections if the segment's ends are equal, or if the curve lies along
+            /// the segment's line (there would then be infinitely many of them).
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{QuadraticBezier2, LineSegment2, Vec2};
+            /// # fn main() {
+            /// // The parabola y = x², from x = -2 to x = 2.
+            /// let curve = QuadraticBezier2 {
+            ///     start: Vec2::new(-2_f64, 4.),
+            ///     ctrl: Vec2::new(0., -4.),
+            ///     end: Vec2::new(2., 4.),
+            /// };
+            /// let seg = LineSegment2 { start: Vec2::new(-3., 1.), end: Vec2::new(3., 1.) };
+            /// let hits: Vec<_> = curve.intersect_line_segment(seg).collect();
+            /// assert_eq!(hits.len(), 2);
+            /// assert_relative_eq!(hits[0].0, 0.25);
+            /// assert_relative_eq!(hits[0].1, Vec2::new(-1., 1.));
+            /// assert_relative_eq!(hits[1].1, Vec2::new(1., 1.));
+            ///
+            /// // The bottom of the parabola touches this one.
+            /// let seg = LineSegment2 { start: Vec2::new(-3., 0.), end: Vec2::new(3., 0.) };
+            /// let hits: Vec<_> = curve.intersect_line_segment(seg).collect();
+            /// assert_eq!(hits, vec![(0.5, Vec2::zero())]);
+            /// # }
+            /// ```
+            pub fn intersect_line_segment(self, seg: LineSegment2<T>) -> impl Iterator<Item=(T, $Point<T>)> {
+                let direction = seg.end - seg.start;
+                let normal = Vec2::new(-direction.y, direction.x);
+                let distances = self.into_vector().map(|p| normal.dot(p - seg.start)).into_array();
+                let (roots, count) = bernstein_roots_01(&distances[..]);
+                let length_squared = direction.magnitude_squared();
+                (0..count).map(move |i| (roots[i], self.evaluate(roots[i]))).filter(move |&(_, p)| {
+                    let s = direction.dot(p - seg.start);
+                    s >= T::zero() && s <= length_squared
+                })
+            }
+        }
 
-        bezier_impl_any!{$Bezier $Point}
+        impl<T: Real> $Bezier<T> {
+            /// Returns this curve, rotated counter-clockwise by `angle_radians` about the origin.
+            ///
+            /// See `rotated_z_about()` to rotate about another point.
+            pub fn rotated_z(self, angle_radians: T) -> Self {
+                self.rotated_z_about(Vec2::zero(), angle_radians)
+            }
+            /// Rotates this curve counter-clockwise by `angle_radians` about the origin.
+            pub fn rotate_z(&mut self, angle_radians: T) {
+                *self = self.rotated_z(angle_radians);
+            }
+            /// Returns this curve, rotated counter-clockwise by `angle_radians` about `center`.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{CubicBezier2, Vec2};
+            /// # use std::f32::consts::PI;
+            /// # fn main() {
+            /// let curve = CubicBezier2 {
+            ///     start: Vec2::new(1_f32, 0.), ctrl0: Vec2::new(1., 1.), ctrl1: Vec2::new(2., 1.), end: Vec2::new(2., 0.),
+            /// };
+            /// let rotated = curve.rotated_z_about(Vec2::new(1., 0.), PI / 2.);
+            /// assert_eq!(rotated.start, curve.start);
+            /// assert_relative_eq!(rotated.end, Vec2::new(1., 1.));
+            /// # }
+            /// ```
+            pub fn rotated_z_about(self, center: Vec2<T>, angle_radians: T) -> Self {
+                let (s, c) = (sin(angle_radians), cos(angle_radians));
+                self.into_vector().map(|p| {
+                    let Vec2 { x, y } = p - center;
+                    center + Vec2::new(c * x - s * y, s * x + c * y)
+                }).into()
+            }
+        }
 
         impl<T> Mul<$Bezier<T>> for Rows2<T> where T: Real + MulAdd<T,T,Output=T> {
             type Output = $Bezier<T>;
@@ -93,14 +789,15 @@ macro_rules! bezier_impl_any {
             }
         }
     };
-    ($Bezier:ident $Point:ident) => {
+    ($Bezier:ident $Point:ident $LineSegment:ident) => {
         impl<T: Real> $Bezier<T> {
             /// Evaluates the normalized tangent at interpolation factor `t`.
             pub fn normalized_tangent(self, t: T) -> $Point<T> where T: Add<T, Output=T> {
                 self.evaluate_derivative(t).normalized()
             }
-            // WISH: better length approximation estimations (e.g see https://math.stackexchange.com/a/61796)
             /// Approximates the curve's length by subdividing it into step_count+1 segments.
+            ///
+            /// This converges slowly; `length()` is both faster and much more accurate.
             pub fn length_by_discretization(self, step_count: u16) -> T
                 where T: Add<T, Output=T> + From<u16>
             {
@@ -114,16 +811,262 @@ macro_rules! bezier_impl_any {
                 }
                 length
             }
-
-            /// Gets the Axis-Aligned Bounding Rectangle for this curve.
+            /// Finds the interpolation factor of the point at arc length `s` from the start of this
+            /// curve, i.e `t` such that the length of `subcurve(0, t)` is `s`.
+            ///
+            /// `s` is clamped between zero and `length()`, so this always returns a factor between
+            /// 0 and 1. `epsilon` is the tolerance on the arc length at the returned factor (not on
+            /// the factor itself, which is much less meaningful where the curve goes fast or slow).
+            ///
+            /// This uses Newton iterations on the arc length, falling back to bisection wherever
+            /// they would leave the interval known to contain the factor, e.g around cusps.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{QuadraticBezier2, Vec2};
+            /// # fn main() {
+            /// let curve = QuadraticBezier2 {
+            ///     start: Vec2::new(0_f64, 0.),
+            ///     ctrl: Vec2::new(2., 4.),
+            ///     end: Vec2::new(4., 0.),
+            /// };
+            /// let t = curve.t_at_length(2., 1e-9);
+            /// assert_relative_eq!(curve.subcurve(0., t).length(), 2., epsilon = 1e-9);
+            /// // By symmetry, the middle of the curve is at half its length.
+            /// assert_relative_eq!(curve.t_at_length(curve.length() / 2., 1e-12), 0.5, epsilon = 1e-9);
+            /// assert_eq!(curve.t_at_length(-1., 1e-9), 0.);
+            /// assert_eq!(curve.t_at_length(100., 1e-9), 1.);
+            /// # }
+            /// ```
+            pub fn t_at_length(self, s: T, epsilon: T) -> T {
+                let speed = |t| self.evaluate_derivative(t).magnitude();
+                ArcLengths::new(&speed, &GAUSS_LEGENDRE_8).factor_at(&speed, s, Some(epsilon), &GAUSS_LEGENDRE_8)
+            }
+            /// Evaluates the point at arc length `s` from the start of this curve.
+            ///
+            /// `s` is clamped between zero and `length()`. The tolerance on the arc length is about
+            /// as small as that of `length()`; see `t_at_length()` for a custom tolerance.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{CubicBezier2, Vec2};
+            /// # fn main() {
+            /// let line = CubicBezier2 {
+            ///     start: Vec2::new(0_f64, 0.),
+            ///     ctrl0: Vec2::new(3., 0.),
+            ///     ctrl1: Vec2::new(3.5, 0.),
+            ///     end: Vec2::new(4., 0.),
+            /// };
+            /// assert_relative_eq!(line.evaluate_at_length(1.), Vec2::new(1., 0.), epsilon = 1e-9);
+            /// assert_relative_eq!(line.evaluate_at_length(5.), Vec2::new(4., 0.));
+            /// # }
+            /// ```
+            pub fn evaluate_at_length(self, s: T) -> $Point<T> {
+                let speed = |t| self.evaluate_derivative(t).magnitude();
+                self.evaluate(ArcLengths::new(&speed, &GAUSS_LEGENDRE_8).factor_at(&speed, s, None, &GAUSS_LEGENDRE_8))
+            }
+            /// Gets the points spaced `spacing` apart along this curve (by arc length, not in a
+            /// straight line), from its start, e.g to place dashes or arrowheads along a stroke.
+            ///
+            /// The start is always yielded, and the last point is the one at the largest multiple of
+            /// `spacing` which isn't past the end, so a curve shorter than `spacing` yields only its
+            /// start. If `include_end` is `true`, the end is yielded after it, unless it is that point.
+            ///
+            /// Each point is located from the start of the curve, at a multiple of `spacing`, so
+            /// errors don't accumulate from one point to the next. Where the length of the curve is
+            /// a multiple of `spacing`, up to the precision of `length()`, the last point is exactly
+            /// the end rather than a sliver before it.
+            ///
+            /// If `spacing` is so small that the number of samples doesn't fit in a `usize`, the
+            /// iterator stops after `usize::MAX` of them (so it is, in practice, endless), rather than
+            /// panicking.
+            ///
+            /// # Panics
+            /// Panics if `spacing` is not positive.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{QuadraticBezier2, Vec2};
+            /// # fn main() {
+            /// let line = QuadraticBezier2 {
+            ///     start: Vec2::new(0_f64, 0.),
+            ///     ctrl: Vec2::new(1., 0.),
+            ///     end: Vec2::new(2.5, 0.),
+            /// };
+            /// let points: Vec<_> = line.sample_by_distance(1., false).collect();
+            /// assert_eq!(points.len(), 3);
+            /// assert_relative_eq!(points[2], Vec2::new(2., 0.), epsilon = 1e-9);
+            ///
+            /// let points: Vec<_> = line.sample_by_distance(1., true).collect();
+            /// assert_eq!(points.len(), 4);
+            /// assert_eq!(points[3], line.end);
+            ///
+            /// // Exactly 5 intervals: the end isn't yielded twice.
+            /// assert_eq!(line.sample_by_distance(0.5, true).count(), 6);
+            /// assert_eq!(line.sample_by_distance(0.5, true).last(), Some(line.end));
+            /// # }
+            /// ```
+            pub fn sample_by_distance(self, spacing: T, include_end: bool) -> impl Iterator<Item=$Point<T>> {
+                assert!(spacing > T::zero(), "the spacing between samples must be positive");
+                let speed = move |t| self.evaluate_derivative(t).magnitude();
+                let arc_lengths = ArcLengths::new(&speed, &GAUSS_LEGENDRE_8);
+                let ratio = arc_lengths.total() / spacing;
+                let sqrt_epsilon = T::epsilon().sqrt();
+                let ends_on_sample = (ratio - ratio.round()).abs() <= ratio * sqrt_epsilon * sqrt_epsilon.sqrt();
+                // A spacing that is tiny compared to the length gives more samples than fit in a `usize`.
+                let last = if ends_on_sample { ratio.round() } else { ratio.floor() }.to_usize().unwrap_or(usize::MAX);
+                (0..=last).map(move |i| {
+                    if i == 0 {
+                        self.start
+                    } else if i == last && ends_on_sample {
+                        self.end
+                    } else {
+                        self.evaluate(arc_lengths.factor_at(&speed, spacing * T::from(i).unwrap(), None, &GAUSS_LEGENDRE_8))
+                    }
+                }).chain(if include_end && !ends_on_sample { Some(self.end) } else { None })
+            }
+            /// Splits this curve into three curves, at interpolation factors `t0` and `t1`.
+            ///
+            /// The parts are `subcurve(0, t0)`, `subcurve(t0, t1)` and `subcurve(t1, 1)`, so each
+            /// part ends exactly where the next one starts. `t0` is expected to be at most `t1`.
+            ///
+            /// ```
+            /// # use vek::{CubicBezier2, Vec2};
+            /// let curve = CubicBezier2 {
+            ///     start: Vec2::new(0_f32, 0.),
+            ///     ctrl0: Vec2::new(0., 4.),
+            ///     ctrl1: Vec2::new(4., 4.),
+            ///     end: Vec2::new(4., 0.),
+            /// };
+            /// let [before, middle, after] = curve.split3(0.3, 0.6);
+            /// assert_eq!((before.start, before.end), (curve.start, middle.start));
+            /// assert_eq!((middle.end, after.end), (after.start, curve.end));
+            /// ```
+            pub fn split3(self, t0: T, t1: T) -> [Self; 3] {
+                [self.subcurve(T::zero(), t0), self.subcurve(t0, t1), self.subcurve(t1, T::one())]
+            }
+            /// Splits this curve into `n` curves, covering equal intervals of interpolation factors,
+            /// in order.
+            ///
+            /// The `i`th curve is `subcurve(i/n, (i+1)/n)`: there is no need to rescale factors
+            /// between successive splits, and each curve ends exactly where the next one starts.
+            /// The first one starts exactly at `start`, and the last one ends exactly at `end`.
+            ///
+            /// # Panics
+            /// Panics if `n` is zero.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{QuadraticBezier2, Vec2};
+            /// # fn main() {
+            /// let curve = QuadraticBezier2 {
+            ///     start: Vec2::new(0_f64, 0.),
+            ///     ctrl: Vec2::new(2., 4.),
+            ///     end: Vec2::new(4., 0.),
+            /// };
+            /// let parts: Vec<_> = curve.into_segments(4).collect();
+            /// assert_eq!(parts.len(), 4);
+            /// assert_eq!(parts[0].start, curve.start);
+            /// assert_eq!(parts[1].end, parts[2].start);
+            /// assert_relative_eq!(parts[2].evaluate(0.5), curve.evaluate(0.625));
+            /// assert_eq!(parts[3].end, curve.end);
+            /// # }
+            /// ```
+            pub fn into_segments(self, n: usize) -> impl Iterator<Item=Self> {
+                assert!(n > 0, "a curve can't be split into zero segments");
+                let count = T::from(n).unwrap();
+                (0..n).map(move |i| {
+                    let t0 = T::from(i).unwrap() / count;
+                    let t1 = if i + 1 == n { T::one() } else { T::from(i + 1).unwrap() / count };
+                    self.subcurve(t0, t1)
+                })
+            }
+            /// Approximates this curve by line segments, for rendering or for algorithms which only
+            /// deal with polylines.
+            ///
+            /// Unlike evaluating the curve at regular intervals, this adapts to the curve: it is
+            /// recursively split until each piece is within `tolerance` of its chord, i.e until its
+            /// control points are, which bounds how far the piece is from the segment replacing it.
+            /// Flat stretches thus get few segments, and tight turns get many.
+            ///
+            /// The segments are contiguous, the first one starts at `start` and the last one ends at
+            /// `end`, and none of them has zero length, so a curve whose points are all equal gives
+            /// no segments at all. To bound the output, curves are split at most 32 times in a row.
+            ///
+            /// # Panics
+            /// Panics if `tolerance` is not positive.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # use vek::{CubicBezier2, Vec2};
+            /// # fn main() {
+            /// let curve = CubicBezier2 {
+            ///     start: Vec2::new(0_f32, 0.),
+            ///     ctrl0: Vec2::new(0., 4.),
+            ///     ctrl1: Vec2::new(4., 4.),
+            ///     end: Vec2::new(4., 0.),
+            /// };
+            /// let segments = curve.flatten(0.01);
+            /// assert_eq!(segments.first().unwrap().start, curve.start);
+            /// assert_eq!(segments.last().unwrap().end, curve.end);
+            /// assert!(curve.flatten(0.001).len() > segments.len());
+            /// // A straight curve is a single segment.
+            /// assert_eq!(CubicBezier2::from(Vec2::new(0., 0.) .. Vec2::new(1., 1.)).flatten(0.01).len(), 1);
+            /// # }
+            /// ```
+            #[cfg(feature="std")]
+            pub fn flatten(self, tolerance: T) -> Vec<$LineSegment<T>> {
+                assert!(tolerance > T::zero(), "the tolerance of flattening must be positive");
+                let half = T::one() / (T::one() + T::one());
+                let mut segments = Vec::new();
+                let mut last = self.start;
+                // Pieces yet to be flattened, in reverse order, along with how many times they were split.
+                let mut pieces = alloc::vec![(self, 0)];
+                while let Some((piece, depth)) = pieces.pop() {
+                    let points = piece.into_vector().into_array();
+                    let chord = $LineSegment { start: piece.start, end: piece.end };
+                    let is_flat = points[1..points.len() - 1].iter().all(|&p| chord.distance_to_point_with_epsilon(p, T::zero()) <= tolerance);
+                    if is_flat || depth >= 32 {
+                        if piece.end != last {
+                            segments.push($LineSegment { start: last, end: piece.end });
+                            last = piece.end;
+                        }
+                        continue;
+                    }
+                    let [first, second] = piece.split(half);
+                    pieces.push((second, depth + 1));
+                    pieces.push((first, depth + 1));
+                }
+                segments
+            }
+            /// Gets the tight Axis-Aligned Bounding Rectangle for this curve.
+            ///
+            /// Unlike the bounds of the control points, this only contains the curve itself:
+            /// its extremities and the points where its derivative is zero along an axis
+            /// (for factors between 0 and 1) are its only candidate extrema.
             ///
             /// On 3D curves, this discards the `z` values.
+            ///
+            /// ```
+            /// # use vek::{Aabr, Vec2, CubicBezier2};
+            /// let curve = CubicBezier2 {
+            ///     start: Vec2::new(0_f32, 0.),
+            ///     ctrl0: Vec2::new(0., 4.),
+            ///     ctrl1: Vec2::new(4., 4.),
+            ///     end: Vec2::new(4., 0.),
+            /// };
+            /// assert_eq!(curve.aabr(), Aabr { min: Vec2::zero(), max: Vec2::new(4., 3.) });
+            /// ```
             pub fn aabr(self) -> Aabr<T> {
                 let (min_x, max_x) = self.x_bounds();
                 let (min_y, max_y) = self.y_bounds();
                 Aabr {
-                    min: Vec2::new(min_x, min_y),
-                    max: Vec2::new(max_x, max_y),
+                    min: Vec2::new(self.evaluate(min_x).x, self.evaluate(min_y).y),
+                    max: Vec2::new(self.evaluate(max_x).x, self.evaluate(max_y).y),
                 }
             }
             /// Returns this curve, flipping the `x` coordinate of each of its points.
@@ -142,8 +1085,89 @@ macro_rules! bezier_impl_any {
             pub fn flip_y(&mut self) {
                 *self = self.flipped_y();
             }
+            /// Returns this curve, moved by `offset`.
+            ///
+            /// ```
+            /// # use vek::{QuadraticBezier2, Vec2};
+            /// let curve = QuadraticBezier2 { start: Vec2::new(0_f32, 0.), ctrl: Vec2::new(1., 2.), end: Vec2::new(2., 0.) };
+            /// let moved = curve.translated(Vec2::new(10., 5.));
+            /// assert_eq!(moved.ctrl, Vec2::new(11., 7.));
+            /// assert_eq!(moved.evaluate(0.5), curve.evaluate(0.5) + Vec2::new(10., 5.));
+            /// ```
+            pub fn translated(self, offset: $Point<T>) -> Self {
+                self.into_vector().map(|p| p + offset).into()
+            }
+            /// Moves all points of this curve by `offset`.
+            pub fn translate(&mut self, offset: $Point<T>) {
+                *self = self.translated(offset);
+            }
+            /// Returns this curve, scaled component-wise by `scale` about the origin.
+            pub fn scaled(self, scale: $Point<T>) -> Self {
+                self.into_vector().map(|p| p * scale).into()
+            }
+            /// Scales all points of this curve component-wise by `scale`, about the origin.
+            pub fn scale(&mut self, scale: $Point<T>) {
+                *self = self.scaled(scale);
+            }
+            /// Returns this curve, scaled component-wise by `scale` about `center`, which is left in place.
+            pub fn scaled_about(self, center: $Point<T>, scale: $Point<T>) -> Self {
+                self.into_vector().map(|p| center + (p - center) * scale).into()
+            }
 
-            // TODO: Test this! binary_search_point_by_steps
+            /// Gets the interpolation factor and position of the point of this curve which is closest
+            /// to `p`, e.g to project the mouse cursor onto a curve in an editor.
+            ///
+            /// This samples the curve at 32 evenly spaced factors (plus its end) first, then
+            /// refines around the closest sample; see `project_point_with_steps()` to choose the number
+            /// of samples, e.g for curves which come close to `p` at several places.
+            /// When the closest point is an end of the curve (e.g for points far beyond it), that end
+            /// is returned exactly.
+            ///
+            /// `epsilon` is the precision of the returned factor.
+            ///
+            /// # Panics
+            /// Panics in debug builds if `epsilon` is less than or equal to `T::epsilon()`.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{CubicBezier2, Vec2};
+            /// # fn main() {
+            /// let curve = CubicBezier2 {
+            ///     start: Vec2::new(0_f64, 0.),
+            ///     ctrl0: Vec2::new(0., 4.),
+            ///     ctrl1: Vec2::new(4., 4.),
+            ///     end: Vec2::new(4., 0.),
+            /// };
+            /// // The top of the arch.
+            /// let (t, p) = curve.project_point(Vec2::new(2., 10.), 1e-9);
+            /// assert_relative_eq!(t, 0.5, epsilon = 1e-8);
+            /// assert_relative_eq!(p, Vec2::new(2., 3.), epsilon = 1e-8);
+            /// // Far beyond the end.
+            /// assert_eq!(curve.project_point(Vec2::new(100., -1.), 1e-9), (1., curve.end));
+            /// # }
+            /// ```
+            pub fn project_point(self, p: $Point<T>, epsilon: T) -> (T, $Point<T>) {
+                self.project_point_with_steps(p, PROJECT_POINT_STEPS, epsilon)
+            }
+            /// Same as `project_point()`, but sampling the curve at `steps` evenly spaced factors
+            /// (plus its end) before refining.
+            ///
+            /// More steps make it less likely to settle on a point which is only the closest locally,
+            /// where the curve comes close to `p` at several places.
+            ///
+            /// # Panics
+            /// Panics in debug builds if `epsilon` is less than or equal to `T::epsilon()`.
+            pub fn project_point_with_steps(self, p: $Point<T>, steps: usize, epsilon: T) -> (T, $Point<T>) {
+                let steps = steps.max(1);
+                let n = T::from(steps).unwrap();
+                let coarse = (0..steps).map(|i| {
+                    let t = T::from(i).unwrap() / n;
+                    (t, self.evaluate(t))
+                });
+                // half_interval = 1/(2*steps)
+                self.binary_search_point(p, coarse, (n + n).recip(), epsilon)
+            }
             /// Searches for the point lying on this curve that is closest to `p`.
             ///
             /// `steps` is the number of points to sample in the curve for the "broad phase"
@@ -152,26 +1176,17 @@ macro_rules! bezier_impl_any {
             /// `epsilon` denotes the desired precision for the result. The higher it is, the
             /// sooner the algorithm will finish, but the result would be less satisfactory.
             ///
+            /// This is the same as `project_point_with_steps()`.
+            ///
             /// # Panics
             /// Panics if `epsilon` is less than or equal to `T::epsilon()`.  
             /// `epsilon` must be positive and not approximately equal to zero.
-            pub fn binary_search_point_by_steps(self, p: $Point<T>, steps: u16, epsilon: T) -> (T, $Point<T>) 
-                where T: Add<T, Output=T> + From<u16>
-            {
-                let steps_f = <T as From<u16>>::from(steps);
-                let it = (0..steps).map(|i| {
-                    let i = <T as From<u16>>::from(i);
-                    let t = i / steps_f;
-                    (t, self.evaluate(t))
-                });
-                // half_interval = 1/(2*steps)
-                let h = (steps_f + steps_f).recip();
-                self.binary_search_point(p, it, h, epsilon)
+            pub fn binary_search_point_by_steps(self, p: $Point<T>, steps: u16, epsilon: T) -> (T, $Point<T>) {
+                self.project_point_with_steps(p, steps.into(), epsilon)
             }
-            // TODO: Test this! binary_search_point
             /// Searches for the point lying on this curve that is closest to `p`.
             ///
-            /// For an example usage, see the source code of `binary_search_point_by_steps()`.
+            /// For an example usage, see the source code of `project_point_with_steps()`.
             ///
             /// `coarse` is an iterator over pairs of `(interpolation_value, point)` that are
             /// assumed to, together, represent a discretization of the curve.  
@@ -187,6 +1202,22 @@ macro_rules! bezier_impl_any {
             /// `epsilon` denotes the desired precision for the result. The higher it is, the
             /// sooner the algorithm will finish, but the result would be less satisfactory.
             ///
+            /// The returned interpolation value is always within `[0, 1]`: when `p` is closest to
+            /// an end of the curve, that end is returned rather than a point of the curve's
+            /// extrapolation past it.
+            ///
+            /// ```
+            /// # use vek::{CubicBezier2, Vec2};
+            /// // Evaluates to (t, 0), including past its ends.
+            /// let curve = CubicBezier2::from(Vec2::new(0_f64, 0.) .. Vec2::new(1., 0.));
+            /// let coarse = |t: f64| Some((t, curve.evaluate(t)));
+            /// assert_eq!(curve.binary_search_point(Vec2::new(2., 0.), coarse(0.9), 0.25, 1e-6), (1., curve.end));
+            /// assert_eq!(curve.binary_search_point(Vec2::new(-1., 1.), coarse(0.1), 0.25, 1e-6), (0., curve.start));
+            /// let (t, p) = curve.binary_search_point(Vec2::new(0.25, 1.), coarse(0.5), 0.25, 1e-9);
+            /// assert_eq!(t, 0.25);
+            /// assert!(p.distance(Vec2::new(0.25, 0.)) < 1e-12);
+            /// ```
+            ///
             /// # Panics
             /// Panics if `epsilon` is less than or equal to `T::epsilon()`.  
             /// `epsilon` must be positive and not approximately equal to zero.
@@ -205,13 +1236,15 @@ macro_rules! bezier_impl_any {
                 }
                 let mut h = half_interval;
                 while h >= epsilon {
-                    let (p1, p2) = (self.evaluate(t-h), self.evaluate(t+h));
+                    // Candidates are kept within the curve, i.e `t` stays in `[0, 1]`.
+                    let (t1, t2) = ((t-h).max(T::zero()), (t+h).min(T::one()));
+                    let (p1, p2) = (self.evaluate(t1), self.evaluate(t2));
                     let (d1, d2) = (p.distance_squared(p1), p.distance_squared(p2));
                     if d1 < d || d2 < d {
                         if d1 < d2 {
-                            d = d1; pt = p1; t = t - h;
+                            d = d1; pt = p1; t = t1;
                         } else {
-                            d = d2; pt = p2; t = t + h;
+                            d = d2; pt = p2; t = t2;
                         }
                         continue;
                     }
@@ -224,7 +1257,7 @@ macro_rules! bezier_impl_any {
 }
 
 macro_rules! bezier_impl_quadratic_axis {
-    ($QuadraticBezier:ident $Point:ident ($x_s:expr) $x:ident $x_inflection:ident $x_min:ident $x_max:ident $x_bounds:ident) => {
+    ($QuadraticBezier:ident $Point:ident ($x_s:expr) $x:ident $x_inflection:ident $x_extremum:ident $x_min:ident $x_max:ident $x_bounds:ident) => {
         impl<T: Real> $QuadraticBezier<T> {
             /// Returns the evaluation factor that gives an inflection point along the
             #[doc=$x_s]
@@ -242,11 +1275,28 @@ macro_rules! bezier_impl_quadratic_axis {
                 }
                 return None;
             }
+            /// Returns the evaluation factor, strictly between 0 and 1, at which the derivative
+            /// along the
+            #[doc=$x_s]
+            /// axis is zero, if any.
+            ///
+            /// On either side of it, the curve is monotonic along this axis, so splitting the curve
+            /// there yields monotonic pieces. Factors of exactly 0 or 1 are excluded, since
+            /// splitting there is pointless. If the curve is linear (or constant) along this axis,
+            /// there is no such factor and this returns `None`.
+            pub fn $x_extremum(self) -> Option<T> {
+                let div = self.start.$x - (self.ctrl.$x + self.ctrl.$x) + self.end.$x;
+                if div == T::zero() {
+                    return None;
+                }
+                let t = (self.start.$x - self.ctrl.$x) / div;
+                if T::zero() < t && t < T::one() { Some(t) } else { None }
+            }
             /// Returns the evaluation factor that gives the point on the curve which
             #[doc=$x_s]
             /// coordinate is the minimum.
             pub fn $x_min(self) -> T {
-                if let Some(t) = self.$x_inflection() {
+                if let Some(t) = self.$x_extremum() {
                     let p = self.evaluate(t);
                     if p.$x < self.start.$x && p.$x < self.end.$x {
                         return t;
@@ -258,7 +1308,7 @@ macro_rules! bezier_impl_quadratic_axis {
             #[doc=$x_s]
             /// coordinate is the maximum.
             pub fn $x_max(self) -> T {
-                if let Some(t) = self.$x_inflection() {
+                if let Some(t) = self.$x_extremum() {
                     let p = self.evaluate(t);
                     if p.$x > self.start.$x && p.$x > self.end.$x {
                         return t;
@@ -270,7 +1320,7 @@ macro_rules! bezier_impl_quadratic_axis {
             #[doc=$x_s]
             /// coordinates are the respective minimum and maximum.
             pub fn $x_bounds(self) -> (T, T) {
-                // PERF: We don't need to compute $x_inflections twice!
+                // PERF: We don't need to compute $x_extremum twice!
                 (self.$x_min(), self.$x_max())
             }
         }
@@ -278,13 +1328,19 @@ macro_rules! bezier_impl_quadratic_axis {
 }
 
 macro_rules! bezier_impl_cubic_axis {
-    ($CubicBezier:ident $Point:ident ($x_s:expr) $x:ident $x_inflections:ident $x_min:ident $x_max:ident $x_bounds:ident) => {
+    ($CubicBezier:ident $Point:ident ($x_s:expr) $x:ident $x_inflections:ident $x_extrema:ident $x_min:ident $x_max:ident $x_bounds:ident) => {
         impl<T: Real> $CubicBezier<T> {
             /// Returns the evaluation factor that gives an inflection point along the
             #[doc=$x_s]
             /// axis, if any.
+            ///
+            /// Despite its name, this returns the factors where the derivative along this axis is
+            /// (approximately) zero, i.e the extrema. Prefer the corresponding `*_extrema()` method,
+            /// which finds the same roots without comparing them to an absolute epsilon, and only
+            /// returns factors strictly between 0 and 1 (this one doesn't, where the derivative is linear).
             // Code in part taken from `lyon` crate, geom.
             // Also explained at https://pomax.github.io/bezierinfo/#extremities
+            #[deprecated(note="Use the corresponding `*_extrema()` method instead")]
             pub fn $x_inflections(self) -> Option<(T, Option<T>)> {
                 // See www.faculty.idc.ac.il/arik/quality/appendixa.html for an explanation
                 // The derivative of a cubic bezier curve is a curve representing a second degree polynomial function
@@ -351,54 +1407,98 @@ macro_rules! bezier_impl_cubic_axis {
                     }
                 }
             }
+            /// Returns the evaluation factors, strictly between 0 and 1, at which the derivative
+            /// along the
+            #[doc=$x_s]
+            /// axis is zero, in increasing order.
+            ///
+            /// Between them, the curve is monotonic along this axis, so splitting the curve at
+            /// these factors yields monotonic pieces. Factors of exactly 0 or 1 are excluded, since
+            /// splitting there is pointless. The second factor is only `Some` if the first is.
+            ///
+            /// Where the derivative is a quadratic polynomial, there are up to two factors; a
+            /// double root (where the derivative touches zero without changing sign) is returned
+            /// once. Where the derivative is linear (e.g for elevated quadratic curves), there is
+            /// up to one, and where it is constant, there is none.
+            ///
+            /// ```
+            /// # use vek::{CubicBezier2, Vec2};
+            /// let curve = CubicBezier2 {
+            ///     start: Vec2::new(0_f32, 0.),
+            ///     ctrl0: Vec2::new(0., 4.),
+            ///     ctrl1: Vec2::new(4., -4.),
+            ///     end: Vec2::new(4., 0.),
+            /// };
+            /// assert_eq!(curve.x_extrema(), (None, None));
+            /// let (t0, t1) = curve.y_extrema();
+            /// assert!(t0.unwrap() < 0.5 && 0.5 < t1.unwrap());
+            /// ```
+            pub fn $x_extrema(self) -> (Option<T>, Option<T>) {
+                let two = T::one() + T::one();
+                let three = two + T::one();
+                // The derivative, divided by 3, is a*t² + b*t + c.
+                let a = self.end.$x - self.start.$x + three * (self.ctrl0.$x - self.ctrl1.$x);
+                let b = two * (self.start.$x - two * self.ctrl0.$x + self.ctrl1.$x);
+                let c = self.ctrl0.$x - self.start.$x;
+                let inside = |t: T| if T::zero() < t && t < T::one() { Some(t) } else { None };
+                if a == T::zero() {
+                    return (if b == T::zero() { None } else { inside(-c / b) }, None);
+                }
+                let discriminant = b * b - two * two * a * c;
+                if discriminant < T::zero() {
+                    return (None, None);
+                }
+                // This form avoids the cancellation of the textbook formula.
+                let sqrt = discriminant.sqrt();
+                let q = -(b + if b < T::zero() { -sqrt } else { sqrt }) / two;
+                if q == T::zero() {
+                    // Then b = c = 0, so the only root is t = 0.
+                    return (None, None);
+                }
+                let (t0, t1) = (q / a, c / q);
+                let (t0, t1) = if t0 <= t1 { (t0, t1) } else { (t1, t0) };
+                match (inside(t0), inside(t1)) {
+                    (Some(t0), Some(t1)) if t0 < t1 => (Some(t0), Some(t1)),
+                    (Some(t), _) | (None, Some(t)) => (Some(t), None),
+                    (None, None) => (None, None),
+                }
+            }
             /// Returns the evaluation factor that gives the point on the curve which
             #[doc=$x_s]
             /// coordinate is the minimum.
             pub fn $x_min(self) -> T {
-                if let Some((t1, t2)) = self.$x_inflections() {
-                    let p1 = self.evaluate(t1);
-                    if let Some(t2) = t2 {
-                        let p2 = self.evaluate(t2);
-                        if (p1.$x < self.start.$x && p1.$x < self.end.$x)
-                        || (p2.$x < self.start.$x && p2.$x < self.end.$x)
-                        {
-                            return if p1.$x < p2.$x { t1 } else { t2 };
-                        }
-                    } else {
-                        if p1.$x < self.start.$x && p1.$x < self.end.$x {
-                            return t1;
-                        }
+                let (mut best_t, mut best) = if self.start.$x < self.end.$x { (T::zero(), self.start.$x) } else { (T::one(), self.end.$x) };
+                let (t0, t1) = self.$x_extrema();
+                for &t in [t0, t1].iter().flatten() {
+                    let x = self.evaluate(t).$x;
+                    if x < best {
+                        best_t = t;
+                        best = x;
                     }
                 }
-                if self.start.$x < self.end.$x { T::zero() } else { T::one() }
+                best_t
             }
             /// Returns the evaluation factor that gives the point on the curve which
             #[doc=$x_s]
             /// coordinate is the maximum.
             pub fn $x_max(self) -> T {
-                if let Some((t1, t2)) = self.$x_inflections() {
-                    let p1 = self.evaluate(t1);
-                    if let Some(t2) = t2 {
-                        let p2 = self.evaluate(t2);
-                        if (p1.$x > self.start.$x && p1.$x > self.end.$x)
-                        || (p2.$x > self.start.$x && p2.$x > self.end.$x)
-                        {
-                            return if p1.$x > p2.$x { t1 } else { t2 };
-                        }
-                    } else {
-                        if p1.$x > self.start.$x && p1.$x > self.end.$x {
-                            return t1;
-                        }
+                let (mut best_t, mut best) = if self.start.$x > self.end.$x { (T::zero(), self.start.$x) } else { (T::one(), self.end.$x) };
+                let (t0, t1) = self.$x_extrema();
+                for &t in [t0, t1].iter().flatten() {
+                    let x = self.evaluate(t).$x;
+                    if x > best {
+                        best_t = t;
+                        best = x;
                     }
                 }
-                if self.start.$x > self.end.$x { T::zero() } else { T::one() }
+                best_t
             }
 
             /// Returns the evaluation factors that give the points on the curve which
             #[doc=$x_s]
             /// coordinates are the respective minimum and maximum.
             pub fn $x_bounds(self) -> (T, T) {
-                // PERF: We don't need to compute $x_inflections twice!
+                // PERF: We don't need to compute $x_extrema twice!
                 (self.$x_min(), self.$x_max())
             }
         }
@@ -407,12 +1507,50 @@ macro_rules! bezier_impl_cubic_axis {
 macro_rules! bezier_impl_quadratic {
     ($(#[$attrs:meta])* 3 $QuadraticBezier:ident $CubicBezier:ident $Point:ident $LineSegment:ident) => {
         bezier_impl_quadratic!{$(#[$attrs])* $QuadraticBezier $CubicBezier $Point $LineSegment}
-        bezier_impl_quadratic_axis!{$QuadraticBezier $Point ("Z") z z_inflection min_z max_z z_bounds}
-        bezier_impl_any!(3 $QuadraticBezier $Point);
+        bezier_impl_quadratic_axis!{$QuadraticBezier $Point ("Z") z z_inflection z_extremum min_z max_z z_bounds}
+        bezier_impl_any!(3 $QuadraticBezier $Point $LineSegment);
     };
     ($(#[$attrs:meta])* 2 $QuadraticBezier:ident $CubicBezier:ident $Point:ident $LineSegment:ident) => {
         bezier_impl_quadratic!{$(#[$attrs])* $QuadraticBezier $CubicBezier $Point $LineSegment}
-        bezier_impl_any!(2 $QuadraticBezier $Point);
+        bezier_impl_any!(2 $QuadraticBezier $Point $LineSegment);
+        impl<T: Real> $QuadraticBezier<T> {
+            /// Does this curve's `bounding_triangle()` contain the given point ?
+            ///
+            /// Points on the triangle's edges are contained. Both windings are accepted.
+            /// If the control points are collinear, the triangle is the segment which joins the
+            /// farthest two of them, and if they are the same point, it is that point.
+            ///
+            /// ```
+            /// # use vek::{QuadraticBezier2, Vec2};
+            /// let curve = QuadraticBezier2 { start: Vec2::new(0_f32, 0.), ctrl: Vec2::new(2., 4.), end: Vec2::new(4., 0.) };
+            /// assert!(curve.contains_in_bounding_triangle(Vec2::new(2., 1.)));
+            /// assert!(curve.contains_in_bounding_triangle(Vec2::new(1., 2.)));
+            /// assert!(!curve.contains_in_bounding_triangle(Vec2::new(1., 3.)));
+            /// ```
+            pub fn contains_in_bounding_triangle(self, p: $Point<T>) -> bool {
+                let (a, b, c) = self.bounding_triangle();
+                let cross = |u: $Point<T>, v: $Point<T>| u.x * v.y - u.y * v.x;
+                let area = cross(b - a, c - a);
+                if area != T::zero() {
+                    let edges = [cross(b - a, p - a), cross(c - b, p - b), cross(a - c, p - c)];
+                    return if area > T::zero() {
+                        edges.iter().all(|&e| e >= T::zero())
+                    } else {
+                        edges.iter().all(|&e| e <= T::zero())
+                    };
+                }
+                // Collinear: p must be on the line through the farthest two points, and between them,
+                // i.e within the bounds of the three points.
+                let (origin, direction) = [(a, b - a), (a, c - a), (b, c - b)].iter().copied()
+                    .fold((a, $Point::zero()), |longest, candidate| {
+                        if candidate.1.magnitude_squared() > longest.1.magnitude_squared() { candidate } else { longest }
+                    });
+                let (min, max) = ($Point::<T>::partial_min($Point::<T>::partial_min(a, b), c), $Point::<T>::partial_max($Point::<T>::partial_max(a, b), c));
+                cross(direction, p - origin) == T::zero()
+                    && min.x <= p.x && p.x <= max.x
+                    && min.y <= p.y && p.y <= max.y
+            }
+        }
     };
     ($(#[$attrs:meta])* $QuadraticBezier:ident $CubicBezier:ident $Point:ident $LineSegment:ident) => {
         
@@ -455,6 +1593,13 @@ macro_rules! bezier_impl_quadratic {
                 let n = l+l;
                 (self.ctrl-self.start)*(l-t)*n + (self.end-self.ctrl)*t*n
             }
+            /// Evaluates the second derivative, which is constant for quadratic curves.
+            ///
+            /// `t` is only there for the sake of consistency with cubic curves, e.g in generic code.
+            pub fn evaluate_second_derivative(self, _t: T) -> $Point<T> {
+                let l = T::one();
+                (self.start - self.ctrl*(l+l) + self.end)*(l+l)
+            }
             /// Returns the constant matrix M such that,
             /// given `T = [1, t*t, t*t*t]` and `P` the vector of control points,
             /// `dot(T * M, P)` evalutes the Bezier curve at 't'.
@@ -472,30 +1617,180 @@ macro_rules! bezier_impl_quadratic {
                     )
                 }
             }
-            /// Splits this quadratic Bézier curve into two curves, at interpolation factor `t`.
-            // NOTE that some computations may be reused, but the compiler can
-            // reason about these. Clarity wins here IMO.
-            pub fn split(self, t: T) -> [Self; 2] {
-                let l = T::one();
-                let two = l+l;
-                let first = $QuadraticBezier {
-                    start: self.start,
-                    ctrl:  self.ctrl*t - self.start*(t-l),
-                    end:   self.end*t*t - self.ctrl*two*t*(t-l) + self.start*(t-l)*(t-l),
-                };
-                let second = $QuadraticBezier {
-                    start: self.end*t*t - self.ctrl*two*t*(t-l) + self.start*(t-l)*(t-l),
-                    ctrl:  self.end*t - self.ctrl*(t-l),
-                    end:   self.end,
-                };
-                [first, second]
+            /// Gets the coefficients `[c0, c1, c2]` of this curve in power basis, such that
+            /// `evaluate(t) == c0 + c1*t + c2*t*t`.
+            ///
+            /// They are obtained from `matrix()`, which makes root-finding and other analytic
+            /// queries easier than on the control points.
+            pub fn polynomial_coefficients(self) -> [$Point<T>; 3] {
+                let m = Self::matrix();
+                let points = [self.start, self.ctrl, self.end];
+                let mut coefficients = [$Point::zero(); 3];
+                for (i, c) in coefficients.iter_mut().enumerate() {
+                    for (j, p) in points.iter().enumerate() {
+                        *c = *c + *p * m[(i, j)];
+                    }
+                }
+                coefficients
+            }
+            /// Creates a curve from its coefficients in power basis; the inverse of
+            /// `polynomial_coefficients()`.
+            ///
+            /// Since `matrix()` is lower triangular, this solves it by forward substitution.
+            pub fn from_polynomial_coefficients(coefficients: [$Point<T>; 3]) -> Self {
+                let m = Self::matrix();
+                let mut points = [$Point::zero(); 3];
+                for (i, c) in coefficients.iter().enumerate() {
+                    let sum = points[..i].iter().enumerate().fold(*c, |sum, (j, p)| sum - *p * m[(i, j)]);
+                    points[i] = sum / m[(i, i)];
+                }
+                let [start, ctrl, end] = points;
+                Self { start, ctrl, end }
+            }
+            /// Runs De Casteljau's algorithm at interpolation factor `t`, which gives the point on
+            /// the curve as well as the intermediate points.
+            ///
+            /// The point only involves linear interpolations between nearby points, which is
+            /// more robust than `evaluate()` when control points have extreme magnitudes.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # use vek::{Vec2, QuadraticBezier2};
+            /// let curve = QuadraticBezier2 { start: Vec2::new(0_f32, 0.), ctrl: Vec2::new(2., 2.), end: Vec2::new(4., 0.) };
+            /// let steps = curve.de_casteljau(0.5);
+            /// assert_eq!(steps.point, Vec2::new(2., 1.));
+            /// assert_eq!(steps.point, curve.evaluate(0.5));
+            /// assert_eq!(steps.first, [Vec2::new(1., 1.), Vec2::new(3., 1.)]);
+            /// ```
+            pub fn de_casteljau(self, t: T) -> QuadraticDeCasteljau<$Point<T>> {
+                let l = T::one();
+                let lerp = |a: $Point<T>, b: $Point<T>| a*(l-t) + b*t;
+                let first = [lerp(self.start, self.ctrl), lerp(self.ctrl, self.end)];
+                QuadraticDeCasteljau { first, point: lerp(first[0], first[1]) }
+            }
+            /// Splits this quadratic Bézier curve into two curves, at interpolation factor `t`.
+            ///
+            /// The new points are those of `de_casteljau()`.
+            pub fn split(self, t: T) -> [Self; 2] {
+                let QuadraticDeCasteljau { first: [ctrl0, ctrl1], point } = self.de_casteljau(t);
+                [
+                    $QuadraticBezier { start: self.start, ctrl: ctrl0, end: point },
+                    $QuadraticBezier { start: point, ctrl: ctrl1, end: self.end },
+                ]
+            }
+            /// Gets the length of this curve.
+            ///
+            /// This integrates the magnitude of the derivative with a 5-point Gauss-Legendre quadrature,
+            /// subdividing the curve where needed (e.g if it doubles back on itself). The relative error
+            /// is about `epsilon^(3/4)`, i.e `1e-12` for `f64`.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{QuadraticBezier2, Vec2};
+            /// # fn main() {
+            /// // The parabola y = x², from x = 0 to x = 1.
+            /// let curve = QuadraticBezier2 {
+            ///     start: Vec2::new(0_f64, 0.),
+            ///     ctrl: Vec2::new(0.5, 0.),
+            ///     end: Vec2::new(1., 1.),
+            /// };
+            /// let exact = (2. * 5_f64.sqrt() + (2. + 5_f64.sqrt()).ln()) / 4.;
+            /// assert_relative_eq!(curve.length(), exact, max_relative = 1e-12);
+            /// # }
+            /// ```
+            pub fn length(self) -> T {
+                integrate_speed(|t| self.evaluate_derivative(t).magnitude(), &GAUSS_LEGENDRE_5)
+            }
+            /// Gets cheap lower and upper bounds of this curve's `length()`, respectively the distance
+            /// between its ends and the length of its control polygon.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # use vek::{QuadraticBezier2, Vec2};
+            /// let curve = QuadraticBezier2 {
+            ///     start: Vec2::new(0_f64, 0.),
+            ///     ctrl: Vec2::new(2., 2.),
+            ///     end: Vec2::new(4., 0.),
+            /// };
+            /// let (lower, upper) = curve.length_bounds();
+            /// assert_eq!(lower, 4.);
+            /// assert_eq!(upper, 32_f64.sqrt());
+            /// assert!(lower <= curve.length() && curve.length() <= upper);
+            /// assert!(!curve.is_approximately_linear(1.));
+            /// assert!(curve.is_approximately_linear(2.));
+            /// ```
+            pub fn length_bounds(self) -> (T, T) {
+                let chord = self.start.distance(self.end);
+                let polygon = self.start.distance(self.ctrl) + self.ctrl.distance(self.end);
+                (chord, polygon)
+            }
+            /// Returns whether this curve is straight enough that its length is within `tolerance`
+            /// of the distance between its ends, i.e the gap between the two `length_bounds()` is
+            /// at most `tolerance` (which is a length, not a ratio).
+            ///
+            /// This is cheap, and conservative: a curve that doubles back on itself along a line
+            /// isn't considered linear. Adaptive algorithms can use it to stop subdividing.
+            pub fn is_approximately_linear(self, tolerance: T) -> bool {
+                let (lower, upper) = self.length_bounds();
+                upper - lower <= tolerance
+            }
+            /// Gets the part of this curve between interpolation factors `t0` and `t1`, as a curve of
+            /// its own, whose `t` goes from 0 to 1 between them.
+            ///
+            /// If `t0` is greater than `t1`, the part is reversed. Its control points are computed
+            /// directly rather than by splitting twice, so that there is no rescaling of `t` involved,
+            /// and the ends of parts which share a factor are exactly equal.
+            /// See also `split3()` and `into_segments()`.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{QuadraticBezier2, Vec2};
+            /// # fn main() {
+            /// let curve = QuadraticBezier2 {
+            ///     start: Vec2::new(0_f64, 0.),
+            ///     ctrl: Vec2::new(2., 4.),
+            ///     end: Vec2::new(4., 0.),
+            /// };
+            /// let part = curve.subcurve(0.25, 0.75);
+            /// assert_relative_eq!(part.start, curve.evaluate(0.25));
+            /// assert_relative_eq!(part.evaluate(0.5), curve.evaluate(0.5));
+            /// assert_relative_eq!(part.end, curve.evaluate(0.75));
+            /// # }
+            /// ```
+            pub fn subcurve(self, t0: T, t1: T) -> Self {
+                $QuadraticBezier {
+                    start: self.blossom(t0, t0),
+                    ctrl:  self.blossom(t0, t1),
+                    end:   self.blossom(t1, t1),
+                }
+            }
+            // Evaluates the blossom (i.e polar form) of this curve, which is De Casteljau's algorithm
+            // with a different factor at each level.
+            fn blossom(self, a: T, b: T) -> $Point<T> {
+                let lerp = |p: $Point<T>, q: $Point<T>, t: T| p * (T::one() - t) + q * t;
+                let (p0, p1) = (lerp(self.start, self.ctrl, a), lerp(self.ctrl, self.end, a));
+                lerp(p0, p1, b)
             }
             /// Elevates this curve into a cubic Bézier curve.
+            ///
+            /// Degree elevation is exact: the control points are `start + 2/3 * (ctrl - start)` and
+            /// `end + 2/3 * (ctrl - end)`, so that the cubic curve evaluates to the same points for all
+            /// `t` (up to rounding errors). This is also available as a `From` conversion.
             pub fn into_cubic(self) -> $CubicBezier<T> {
                 self.into()
             }
         }
         impl<T> $QuadraticBezier<T> {
+            /// Gets the triangle formed by `start`, `ctrl` and `end`, in this order, which contains
+            /// the whole curve (it is the curve's convex hull).
+            ///
+            /// This is the triangle which GPU curve rendering techniques (such as Loop-Blinn)
+            /// rasterize. For 3D curves, it lies in the curve's plane.
+            pub fn bounding_triangle(self) -> ($Point<T>, $Point<T>, $Point<T>) {
+                (self.start, self.ctrl, self.end)
+            }
             /// Gets this curve reversed, i.e swaps `start` with `end`.
             pub fn reversed(mut self) -> Self {
                 self.reverse();
@@ -521,6 +1816,49 @@ macro_rules! bezier_impl_quadratic {
             pub fn into_array(self) -> [$Point<T>; 3] {
                 self.into_vec3().into_array()
             }
+            /// Gets a curve whose points are those of this one, transformed by `f`.
+            ///
+            /// ```
+            /// # use vek::{QuadraticBezier2, Vec2};
+            /// let curve = QuadraticBezier2 { start: Vec2::new(0_i32, 0), ctrl: Vec2::new(1, 2), end: Vec2::new(2, 0) };
+            /// let scaled = curve.map(|p| p * 3);
+            /// assert_eq!(scaled, QuadraticBezier2 { start: Vec2::new(0, 0), ctrl: Vec2::new(3, 6), end: Vec2::new(6, 0) });
+            /// assert_eq!(curve.map(|p| p.numcast::<f32>().unwrap()).ctrl, Vec2::new(1., 2.));
+            /// ```
+            pub fn map<D, F>(self, f: F) -> $QuadraticBezier<D> where F: FnMut($Point<T>) -> $Point<D> {
+                self.into_vec3().map(f).into()
+            }
+            /// Iterates over references to this curve's points, in order (`start`, `ctrl`, `end`).
+            pub fn iter(&self) -> std::array::IntoIter<&$Point<T>, 3> {
+                IntoIterator::into_iter([&self.start, &self.ctrl, &self.end])
+            }
+            /// Iterates over mutable references to this curve's points, in order (`start`, `ctrl`, `end`).
+            pub fn iter_mut(&mut self) -> std::array::IntoIter<&mut $Point<T>, 3> {
+                IntoIterator::into_iter([&mut self.start, &mut self.ctrl, &mut self.end])
+            }
+        }
+
+        /// Iterates over the curve's points, in order (`start`, `ctrl`, `end`).
+        impl<T> IntoIterator for $QuadraticBezier<T> {
+            type Item = $Point<T>;
+            type IntoIter = std::array::IntoIter<$Point<T>, 3>;
+            fn into_iter(self) -> Self::IntoIter {
+                IntoIterator::into_iter(self.into_array())
+            }
+        }
+        impl<'a, T> IntoIterator for &'a $QuadraticBezier<T> {
+            type Item = &'a $Point<T>;
+            type IntoIter = std::array::IntoIter<&'a $Point<T>, 3>;
+            fn into_iter(self) -> Self::IntoIter {
+                self.iter()
+            }
+        }
+        impl<'a, T> IntoIterator for &'a mut $QuadraticBezier<T> {
+            type Item = &'a mut $Point<T>;
+            type IntoIter = std::array::IntoIter<&'a mut $Point<T>, 3>;
+            fn into_iter(self) -> Self::IntoIter {
+                self.iter_mut()
+            }
         }
         
         impl<T> From<Vec3<$Point<T>>> for $QuadraticBezier<T> {
@@ -554,8 +1892,8 @@ macro_rules! bezier_impl_quadratic {
             }
         }
         
-        bezier_impl_quadratic_axis!{$QuadraticBezier $Point ("X") x x_inflection min_x max_x x_bounds}
-        bezier_impl_quadratic_axis!{$QuadraticBezier $Point ("Y") y y_inflection min_y max_y y_bounds}
+        bezier_impl_quadratic_axis!{$QuadraticBezier $Point ("X") x x_inflection x_extremum min_x max_x x_bounds}
+        bezier_impl_quadratic_axis!{$QuadraticBezier $Point ("Y") y y_inflection y_extremum min_y max_y y_bounds}
     }
 }
 
@@ -595,12 +1933,12 @@ macro_rules! bezier_impl_3d_into_2d {
 macro_rules! bezier_impl_cubic {
     ($(#[$attrs:meta])* 3 $QuadraticBezier:ident $CubicBezier:ident $Point:ident $LineSegment:ident) => {
         bezier_impl_cubic!{$(#[$attrs])* $QuadraticBezier $CubicBezier $Point $LineSegment}
-        bezier_impl_cubic_axis!{$CubicBezier $Point ("Z") z z_inflections min_z max_z z_bounds}
-        bezier_impl_any!(3 $CubicBezier $Point);
+        bezier_impl_cubic_axis!{$CubicBezier $Point ("Z") z z_inflections z_extrema min_z max_z z_bounds}
+        bezier_impl_any!(3 $CubicBezier $Point $LineSegment);
     };
     ($(#[$attrs:meta])* 2 $QuadraticBezier:ident $CubicBezier:ident $Point:ident $LineSegment:ident) => {
         bezier_impl_cubic!{$(#[$attrs])* $QuadraticBezier $CubicBezier $Point $LineSegment}
-        bezier_impl_any!(2 $CubicBezier $Point);
+        bezier_impl_any!(2 $CubicBezier $Point $LineSegment);
     };
     ($(#[$attrs:meta])* $QuadraticBezier:ident $CubicBezier:ident $Point:ident $LineSegment:ident) => {
         
@@ -642,6 +1980,15 @@ macro_rules! bezier_impl_cubic {
                 let two = l+l;
                 (self.ctrl0-self.start)*(l-t)*(l-t)*n + (self.ctrl1-self.ctrl0)*two*(l-t)*t*n + (self.end-self.ctrl1)*t*t*n
             }
+            /// Evaluates the second derivative at interpolation factor `t`.
+            ///
+            /// This is the rate of change of `evaluate_derivative()`, which matters for curvature
+            /// and inflection points.
+            pub fn evaluate_second_derivative(self, t: T) -> $Point<T> {
+                let l = T::one();
+                let six = (l+l+l)*(l+l);
+                (self.ctrl1 - self.ctrl0*(l+l) + self.start)*six*(l-t) + (self.end - self.ctrl1*(l+l) + self.ctrl0)*six*t
+            }
             /// Returns the constant matrix M such that,
             /// given `T = [1, t*t, t*t*t, t*t*t*t]` and `P` the vector of control points,
             /// `dot(T * M, P)` evalutes the Bezier curve at 't'.
@@ -661,26 +2008,372 @@ macro_rules! bezier_impl_cubic {
                     )
                 }
             }
+            /// Gets the coefficients `[c0, c1, c2, c3]` of this curve in power basis, such that
+            /// `evaluate(t) == c0 + c1*t + c2*t*t + c3*t*t*t`.
+            ///
+            /// They are obtained from `matrix()`, which makes root-finding and other analytic
+            /// queries easier than on the control points.
+            pub fn polynomial_coefficients(self) -> [$Point<T>; 4] {
+                let m = Self::matrix();
+                let points = [self.start, self.ctrl0, self.ctrl1, self.end];
+                let mut coefficients = [$Point::zero(); 4];
+                for (i, c) in coefficients.iter_mut().enumerate() {
+                    for (j, p) in points.iter().enumerate() {
+                        *c = *c + *p * m[(i, j)];
+                    }
+                }
+                coefficients
+            }
+            /// Creates a curve from its coefficients in power basis; the inverse of
+            /// `polynomial_coefficients()`.
+            ///
+            /// Since `matrix()` is lower triangular, this solves it by forward substitution.
+            pub fn from_polynomial_coefficients(coefficients: [$Point<T>; 4]) -> Self {
+                let m = Self::matrix();
+                let mut points = [$Point::zero(); 4];
+                for (i, c) in coefficients.iter().enumerate() {
+                    let sum = points[..i].iter().enumerate().fold(*c, |sum, (j, p)| sum - *p * m[(i, j)]);
+                    points[i] = sum / m[(i, i)];
+                }
+                let [start, ctrl0, ctrl1, end] = points;
+                Self { start, ctrl0, ctrl1, end }
+            }
+            /// Creates the curve going from `p0` to `p1`, with tangents `m0` at `p0` and `m1` at `p1`,
+            /// i.e the cubic Hermite curve defined by these.
+            ///
+            /// The tangents are those of `evaluate_derivative()`, so the control points are a third
+            /// of them away from the ends.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{CubicBezier2, Vec2};
+            /// # fn main() {
+            /// let curve = CubicBezier2::from_hermite(Vec2::new(0_f32, 0.), Vec2::new(3., 0.), Vec2::new(2., 2.), Vec2::new(0., 3.));
+            /// assert_eq!(curve.ctrl0, Vec2::new(1., 0.));
+            /// assert_eq!(curve.ctrl1, Vec2::new(2., 1.));
+            /// assert_relative_eq!(curve.evaluate_derivative(1.), Vec2::new(0., 3.));
+            /// # }
+            /// ```
+            pub fn from_hermite(p0: $Point<T>, m0: $Point<T>, p1: $Point<T>, m1: $Point<T>) -> Self {
+                let three = T::one() + T::one() + T::one();
+                Self {
+                    start: p0,
+                    ctrl0: p0 + m0 / three,
+                    ctrl1: p1 - m1 / three,
+                    end: p1,
+                }
+            }
+            /// Gets this curve as a cubic Hermite curve, i.e `(p0, m0, p1, m1)` where `p0` and `p1`
+            /// are the ends and `m0` and `m1` the tangents there; the inverse of `from_hermite()`.
+            pub fn to_hermite(self) -> ($Point<T>, $Point<T>, $Point<T>, $Point<T>) {
+                let three = T::one() + T::one() + T::one();
+                (self.start, (self.ctrl0 - self.start) * three, self.end, (self.end - self.ctrl1) * three)
+            }
+            /// Creates the curve going from `p1` to `p2` as a segment of a cardinal spline through
+            /// `p0`, `p1`, `p2` and `p3`.
+            ///
+            /// The tangent at `p1` is `(1 - tension) * (p2 - p0) / 2`, and likewise at `p2`, so a
+            /// `tension` of zero gives a Catmull–Rom spline, and a `tension` of one gives
+            /// a straight line.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{CubicBezier2, Vec2};
+            /// # fn main() {
+            /// let (p0, p1, p2, p3) = (Vec2::new(0_f32, 0.), Vec2::new(1., 1.), Vec2::new(2., 1.), Vec2::new(3., 0.));
+            /// let curve = CubicBezier2::from_catmull_rom(p0, p1, p2, p3, 0.);
+            /// assert_eq!((curve.start, curve.end), (p1, p2));
+            /// assert_relative_eq!(curve.evaluate_derivative(0.), (p2 - p0) / 2.);
+            /// # }
+            /// ```
+            pub fn from_catmull_rom(p0: $Point<T>, p1: $Point<T>, p2: $Point<T>, p3: $Point<T>, tension: T) -> Self {
+                // The control points are a third of the tangents away from the ends.
+                let six = T::from(6).unwrap();
+                let k = (T::one() - tension) / six;
+                Self {
+                    start: p1,
+                    ctrl0: p1 + (p2 - p0) * k,
+                    ctrl1: p2 - (p3 - p1) * k,
+                    end: p2,
+                }
+            }
+            /// Creates the curves of the cardinal spline going through all of `points`, in order
+            /// (see `from_catmull_rom()`).
+            ///
+            /// The first and last points are duplicated to get the tangents at the ends, so that
+            /// they point towards their neighbours.
+            /// There is one curve less than there are points, so there are none if there are
+            /// less than two points.
+            ///
+            /// ```
+            /// # use vek::{CubicBezier3, Vec3};
+            /// let points = [Vec3::new(0_f32, 0., 0.), Vec3::new(1., 2., 0.), Vec3::new(3., 2., 1.), Vec3::new(4., 0., 1.)];
+            /// let curves = CubicBezier3::catmull_rom_spline(&points, 0.);
+            /// assert_eq!(curves.len(), 3);
+            /// assert_eq!(curves[1].start, points[1]);
+            /// assert_eq!(curves[1].end, points[2]);
+            /// ```
+            #[cfg(feature="std")]
+            pub fn catmull_rom_spline(points: &[$Point<T>], tension: T) -> Vec<Self> {
+                let last = points.len().saturating_sub(1);
+                (0..last).map(|i| {
+                    let p0 = points[i.saturating_sub(1)];
+                    let p3 = points[(i + 2).min(last)];
+                    Self::from_catmull_rom(p0, points[i], points[i + 1], p3, tension)
+                }).collect()
+            }
+            /// Approximates this curve with a single quadratic one, with the same ends, and gets an
+            /// upper bound of the distance between points of both curves at the same factor.
+            ///
+            /// The control point is the average of the ones that would give the right tangent at
+            /// each end (midpoint degree reduction). The bound is `sqrt(3)/36` times the magnitude of
+            /// the curve's third difference, `end - 3*ctrl1 + 3*ctrl0 - start`, which is zero if and
+            /// only if the curve is actually quadratic.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{QuadraticBezier2, Vec2};
+            /// # fn main() {
+            /// let quadratic = QuadraticBezier2 {
+            ///     start: Vec2::new(0_f64, 0.),
+            ///     ctrl: Vec2::new(2., 4.),
+            ///     end: Vec2::new(4., 0.),
+            /// };
+            /// let (reduced, error) = quadratic.into_cubic().reduce_degree();
+            /// assert_eq!((reduced.start, reduced.end), (quadratic.start, quadratic.end));
+            /// assert_relative_eq!(reduced.ctrl, quadratic.ctrl);
+            /// assert_relative_eq!(error, 0., epsilon = 1e-12);
+            /// # }
+            /// ```
+            pub fn reduce_degree(self) -> ($QuadraticBezier<T>, T) {
+                let three = T::one() + T::one() + T::one();
+                let four = three + T::one();
+                let ctrl = ((self.ctrl0 + self.ctrl1) * three - self.start - self.end) / four;
+                let third_difference = self.end - self.ctrl1 * three + self.ctrl0 * three - self.start;
+                let error = three.sqrt() / (four * three * three) * third_difference.magnitude();
+                ($QuadraticBezier { start: self.start, ctrl, end: self.end }, error)
+            }
+            /// Approximates this curve with consecutive quadratic curves, such that the distance
+            /// between points of this curve and of the approximation is at most `tolerance`.
+            ///
+            /// The curve is split in halves recursively until the error bound of `reduce_degree()` is
+            /// within `tolerance` for each part. Each part ends exactly where the next one starts,
+            /// and the ends of the approximation are exactly those of this curve.
+            ///
+            /// # Panics
+            /// Panics if `tolerance` is not positive.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # use vek::{CubicBezier2, Vec2};
+            /// # fn main() {
+            /// let curve = CubicBezier2 {
+            ///     start: Vec2::new(0_f32, 0.),
+            ///     ctrl0: Vec2::new(0., 4.),
+            ///     ctrl1: Vec2::new(4., 4.),
+            ///     end: Vec2::new(4., 0.),
+            /// };
+            /// let quadratics = curve.to_quadratics(0.01);
+            /// assert_eq!(quadratics.first().unwrap().start, curve.start);
+            /// assert_eq!(quadratics.last().unwrap().end, curve.end);
+            /// assert!(curve.to_quadratics(0.001).len() > quadratics.len());
+            /// # }
+            /// ```
+            #[cfg(feature="std")]
+            pub fn to_quadratics(self, tolerance: T) -> Vec<$QuadraticBezier<T>> {
+                assert!(tolerance > T::zero(), "the tolerance of degree reduction must be positive");
+                let half = T::one() / (T::one() + T::one());
+                let mut quadratics = Vec::new();
+                // Pieces yet to be reduced, in reverse order, along with how many times they were split.
+                let mut pieces = alloc::vec![(self, 0)];
+                while let Some((piece, depth)) = pieces.pop() {
+                    let (quadratic, error) = piece.reduce_degree();
+                    if error <= tolerance || depth >= 32 {
+                        quadratics.push(quadratic);
+                        continue;
+                    }
+                    let [first, second] = piece.split(half);
+                    pieces.push((second, depth + 1));
+                    pieces.push((first, depth + 1));
+                }
+                quadratics
+            }
+            /// Runs De Casteljau's algorithm at interpolation factor `t`, which gives the point on
+            /// the curve as well as the intermediate points.
+            ///
+            /// The point only involves linear interpolations between nearby points, which is
+            /// more robust than `evaluate()` when control points have extreme magnitudes.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # use vek::{Vec2, CubicBezier2};
+            /// let curve = CubicBezier2 {
+            ///     start: Vec2::new(0_f32, 0.), ctrl0: Vec2::new(0., 4.), ctrl1: Vec2::new(4., 4.), end: Vec2::new(4., 0.),
+            /// };
+            /// let steps = curve.de_casteljau(0.5);
+            /// assert_eq!(steps.point, Vec2::new(2., 3.));
+            /// assert_eq!(steps.point, curve.evaluate(0.5));
+            /// // The tangent at the point.
+            /// assert_eq!(steps.second, [Vec2::new(1., 3.), Vec2::new(3., 3.)]);
+            /// ```
+            pub fn de_casteljau(self, t: T) -> CubicDeCasteljau<$Point<T>> {
+                let l = T::one();
+                let lerp = |a: $Point<T>, b: $Point<T>| a*(l-t) + b*t;
+                let first = [lerp(self.start, self.ctrl0), lerp(self.ctrl0, self.ctrl1), lerp(self.ctrl1, self.end)];
+                let second = [lerp(first[0], first[1]), lerp(first[1], first[2])];
+                CubicDeCasteljau { first, second, point: lerp(second[0], second[1]) }
+            }
             /// Splits this cubic Bézier curve into two curves, at interpolation factor `t`.
-            // NOTE that some computations may be reused, but the compiler can
-            // reason about these. Clarity wins here IMO.
+            ///
+            /// The new points are those of `de_casteljau()`.
             pub fn split(self, t: T) -> [Self; 2] {
-                let l = T::one();
-                let two = l+l;
-                let three = l+l+l;
-                let first = $CubicBezier {
-                    start: self.start,
-                    ctrl0: self.ctrl0*t - self.start*(t-l),
-                    ctrl1: self.ctrl1*t*t - self.ctrl0*two*t*(t-l) + self.start*(t-l)*(t-l),
-                    end:   self.end*t*t*t - self.ctrl1*three*t*t*(t-l) + self.ctrl0*three*t*(t-l)*(t-l) - self.start*(t-l)*(t-l)*(t-l),
-                };
-                let second = $CubicBezier {
-                    start: self.end*t*t*t - self.ctrl1*three*t*t*(t-l) + self.ctrl0*three*t*(t-l)*(t-l) - self.start*(t-l)*(t-l)*(t-l),
-                    ctrl0: self.end*t*t - self.ctrl1*two*t*(t-l) + self.ctrl0*(t-l)*(t-l),
-                    ctrl1: self.end*t - self.ctrl1*(t-l),
-                    end:   self.end,
-                };
-                [first, second]
+                let CubicDeCasteljau { first, second, point } = self.de_casteljau(t);
+                [
+                    $CubicBezier { start: self.start, ctrl0: first[0], ctrl1: second[0], end: point },
+                    $CubicBezier { start: point, ctrl0: second[1], ctrl1: first[2], end: self.end },
+                ]
+            }
+            /// Gets the length of this curve.
+            ///
+            /// This integrates the magnitude of the derivative with an 8-point Gauss-Legendre quadrature,
+            /// subdividing the curve where needed (e.g around cusps and sharp turns). The relative error
+            /// is about `epsilon^(3/4)`, i.e `1e-12` for `f64`.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{CubicBezier2, Vec2};
+            /// # fn main() {
+            /// let curve = CubicBezier2 {
+            ///     start: Vec2::new(0_f64, 0.),
+            ///     ctrl0: Vec2::new(1., 1.),
+            ///     ctrl1: Vec2::new(2., 2.),
+            ///     end: Vec2::new(3., 3.),
+            /// };
+            /// assert_relative_eq!(curve.length(), 18_f64.sqrt());
+            /// # }
+            /// ```
+            pub fn length(self) -> T {
+                integrate_speed(|t| self.evaluate_derivative(t).magnitude(), &GAUSS_LEGENDRE_8)
+            }
+            /// Gets cheap lower and upper bounds of this curve's `length()`, respectively the distance
+            /// between its ends and the length of its control polygon.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # use vek::{CubicBezier2, Vec2};
+            /// let curve = CubicBezier2 {
+            ///     start: Vec2::new(0_f64, 0.),
+            ///     ctrl0: Vec2::new(0., 3.),
+            ///     ctrl1: Vec2::new(4., 3.),
+            ///     end: Vec2::new(4., 0.),
+            /// };
+            /// assert_eq!(curve.length_bounds(), (4., 10.));
+            /// let length = curve.length();
+            /// assert!(4. <= length && length <= 10.);
+            /// ```
+            pub fn length_bounds(self) -> (T, T) {
+                let chord = self.start.distance(self.end);
+                let polygon = self.start.distance(self.ctrl0) + self.ctrl0.distance(self.ctrl1) + self.ctrl1.distance(self.end);
+                (chord, polygon)
+            }
+            /// Returns whether this curve is straight enough that its length is within `tolerance`
+            /// of the distance between its ends, i.e the gap between the two `length_bounds()` is
+            /// at most `tolerance` (which is a length, not a ratio).
+            ///
+            /// This is cheap, and conservative: a curve that doubles back on itself along a line
+            /// isn't considered linear. Adaptive algorithms can use it to stop subdividing.
+            pub fn is_approximately_linear(self, tolerance: T) -> bool {
+                let (lower, upper) = self.length_bounds();
+                upper - lower <= tolerance
+            }
+            /// Gets the part of this curve between interpolation factors `t0` and `t1`, as a curve of
+            /// its own, whose `t` goes from 0 to 1 between them.
+            ///
+            /// If `t0` is greater than `t1`, the part is reversed. Its control points are computed
+            /// directly rather than by splitting twice, so that there is no rescaling of `t` involved,
+            /// and the ends of parts which share a factor are exactly equal.
+            /// See also `split3()` and `into_segments()`.
+            pub fn subcurve(self, t0: T, t1: T) -> Self {
+                $CubicBezier {
+                    start: self.blossom(t0, t0, t0),
+                    ctrl0: self.blossom(t0, t0, t1),
+                    ctrl1: self.blossom(t0, t1, t1),
+                    end:   self.blossom(t1, t1, t1),
+                }
+            }
+            // Evaluates the blossom (i.e polar form) of this curve, which is De Casteljau's algorithm
+            // with a different factor at each level.
+            fn blossom(self, a: T, b: T, c: T) -> $Point<T> {
+                let lerp = |p: $Point<T>, q: $Point<T>, t: T| p * (T::one() - t) + q * t;
+                let (p0, p1, p2) = (lerp(self.start, self.ctrl0, a), lerp(self.ctrl0, self.ctrl1, a), lerp(self.ctrl1, self.end, a));
+                let (p0, p1) = (lerp(p0, p1, b), lerp(p1, p2, b));
+                lerp(p0, p1, c)
+            }
+            /// Returns the smallest value of `distance_to` over all points of this curve, where
+            /// `distance_to` gives the distance from a point to some shape.
+            ///
+            /// This works by recursive subdivision, using the fact that the curve lies inside
+            /// the bounding circle (or sphere) of its control points to discard parts of it.
+            /// For this reason, `distance_to` must be an actual distance function, i.e it must
+            /// not change by more than `d` when its input moves by `d` (distances to any set of
+            /// points satisfy this).
+            ///
+            /// The result `r` is guaranteed to satisfy `d <= r <= d + tolerance`, where `d` is the
+            /// exact distance (up to floating-point precision). A non-positive `tolerance` is allowed,
+            /// in which case subdivision stops at a fixed depth.
+            pub fn distance_to_shape<F>(self, mut distance_to: F, tolerance: T) -> T
+                where F: FnMut($Point<T>) -> T
+            {
+                fn search<T: Real, F: FnMut($Point<T>) -> T>(curve: $CubicBezier<T>, distance_to: &mut F, tolerance: T, depth: u32, best: &mut T) {
+                    let l = T::one();
+                    let four = l+l+l+l;
+                    let center = (curve.start + curve.ctrl0 + curve.ctrl1 + curve.end) / four;
+                    let radius = curve.start.distance(center)
+                        .max(curve.ctrl0.distance(center))
+                        .max(curve.ctrl1.distance(center))
+                        .max(curve.end.distance(center));
+                    let lower_bound = distance_to(center) - radius;
+                    if lower_bound >= *best - tolerance {
+                        return;
+                    }
+                    let [first, second] = curve.split((l+l).recip());
+                    *best = best.min(distance_to(first.end));
+                    // The midpoint is within `radius` of `center`, so it is within `2*radius` of the lower bound.
+                    if radius + radius <= tolerance || depth == 0 {
+                        return;
+                    }
+                    search(first, distance_to, tolerance, depth - 1, best);
+                    search(second, distance_to, tolerance, depth - 1, best);
+                }
+                let mut best = distance_to(self.start).min(distance_to(self.end));
+                search(self, &mut distance_to, tolerance, 48, &mut best);
+                best
+            }
+            /// Returns the smallest distance between this curve and a line segment, to within
+            /// `tolerance` (see `distance_to_shape()` for the exact guarantee).
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{CubicBezier2, LineSegment2, Vec2};
+            /// # fn main() {
+            /// let curve = CubicBezier2 {
+            ///     start: Vec2::new(0_f64, 0.),
+            ///     ctrl0: Vec2::new(0., 4.),
+            ///     ctrl1: Vec2::new(4., 4.),
+            ///     end: Vec2::new(4., 0.),
+            /// };
+            /// // The curve's apex is at y = 3.
+            /// let seg = LineSegment2 { start: Vec2::new(-10., 5.), end: Vec2::new(10., 5.) };
+            /// assert_relative_eq!(curve.distance_to_segment(seg, 1e-6), 2., epsilon = 1e-6);
+            /// # }
+            /// ```
+            pub fn distance_to_segment(self, seg: $LineSegment<T>, tolerance: T) -> T where T: RelativeEq {
+                self.distance_to_shape(|p| seg.distance_to_point(p), tolerance)
             }
             /// Gets the cubic Bézier curve that approximates a unit quarter circle.
             ///
@@ -734,6 +2427,52 @@ macro_rules! bezier_impl_cubic {
             pub fn into_array(self) -> [$Point<T>; 4] {
                 self.into_vec4().into_array()
             }
+            /// Gets a curve whose points are those of this one, transformed by `f`.
+            pub fn map<D, F>(self, f: F) -> $CubicBezier<D> where F: FnMut($Point<T>) -> $Point<D> {
+                self.into_vec4().map(f).into()
+            }
+            /// Iterates over references to this curve's points, in order (`start`, `ctrl0`, `ctrl1`, `end`).
+            pub fn iter(&self) -> std::array::IntoIter<&$Point<T>, 4> {
+                IntoIterator::into_iter([&self.start, &self.ctrl0, &self.ctrl1, &self.end])
+            }
+            /// Iterates over mutable references to this curve's points, in order (`start`, `ctrl0`, `ctrl1`, `end`).
+            ///
+            /// ```
+            /// # use vek::{CubicBezier2, Vec2};
+            /// let mut curve = CubicBezier2 {
+            ///     start: Vec2::new(0_i32, 0), ctrl0: Vec2::new(1, 2), ctrl1: Vec2::new(3, 2), end: Vec2::new(4, 0),
+            /// };
+            /// for p in curve.iter_mut() {
+            ///     *p += Vec2::new(10, 20);
+            /// }
+            /// assert_eq!(curve.into_array(), [Vec2::new(10, 20), Vec2::new(11, 22), Vec2::new(13, 22), Vec2::new(14, 20)]);
+            /// ```
+            pub fn iter_mut(&mut self) -> std::array::IntoIter<&mut $Point<T>, 4> {
+                IntoIterator::into_iter([&mut self.start, &mut self.ctrl0, &mut self.ctrl1, &mut self.end])
+            }
+        }
+
+        /// Iterates over the curve's points, in order (`start`, `ctrl0`, `ctrl1`, `end`).
+        impl<T> IntoIterator for $CubicBezier<T> {
+            type Item = $Point<T>;
+            type IntoIter = std::array::IntoIter<$Point<T>, 4>;
+            fn into_iter(self) -> Self::IntoIter {
+                IntoIterator::into_iter(self.into_array())
+            }
+        }
+        impl<'a, T> IntoIterator for &'a $CubicBezier<T> {
+            type Item = &'a $Point<T>;
+            type IntoIter = std::array::IntoIter<&'a $Point<T>, 4>;
+            fn into_iter(self) -> Self::IntoIter {
+                self.iter()
+            }
+        }
+        impl<'a, T> IntoIterator for &'a mut $CubicBezier<T> {
+            type Item = &'a mut $Point<T>;
+            type IntoIter = std::array::IntoIter<&'a mut $Point<T>, 4>;
+            fn into_iter(self) -> Self::IntoIter {
+                self.iter_mut()
+            }
         }
         
         impl<T> From<Vec4<$Point<T>>> for $CubicBezier<T> {
@@ -783,87 +2522,3694 @@ macro_rules! bezier_impl_cubic {
             }
         }
         
-        bezier_impl_cubic_axis!{$CubicBezier $Point ("X") x x_inflections min_x max_x x_bounds}
-        bezier_impl_cubic_axis!{$CubicBezier $Point ("Y") y y_inflections min_y max_y y_bounds}
+        bezier_impl_cubic_axis!{$CubicBezier $Point ("X") x x_inflections x_extrema min_x max_x x_bounds}
+        bezier_impl_cubic_axis!{$CubicBezier $Point ("Y") y y_inflections y_extrema min_y max_y y_bounds}
     }
 }
 
-macro_rules! impl_all_beziers {
-    ($mod:ident) => {
-        use  crate::vec::$mod::{Vec3, Vec4, Vec2};
-        use  crate::mat::$mod::row_major::{Mat2 as Rows2, Mat3 as Rows3, Mat4 as Rows4};
-        use  crate::mat::$mod::column_major::{Mat2 as Cols2, Mat3 as Cols3, Mat4 as Cols4};
-        use crate::geom::$mod::{LineSegment2, LineSegment3, Aabr, Aabb};
-        use self::Rows4 as Mat4;
-        use self::Rows3 as Mat3;
-        bezier_impl_quadratic!{
-            /// A 2D Bézier curve with one control point.
+macro_rules! bezier_impl_cubic2_stroke {
+    ($CubicBezier:ident) => {
+        #[cfg(feature="std")]
+        impl<T: Real> $CubicBezier<T> {
+            /// Computes the outline of this curve, stroked with the given width, as a closed sequence of curves.
             ///
-            /// 2x2 and 3x3 matrices can be multiplied by a Bézier curve to transform all of its points.
-            2 QuadraticBezier2 CubicBezier2 Vec2 LineSegment2
-        }
-        bezier_impl_2d_into_3d!{QuadraticBezier2 QuadraticBezier3}
-        bezier_impl_quadratic!{
-            /// A 3D Bézier curve with one control point.
+            /// Each returned curve starts exactly where the previous one ends, and the last one
+            /// ends exactly where the first one starts. The outline runs clockwise (assuming a Y-up
+            /// coordinate system): first along the left side of the curve, then around the end cap,
+            /// back along the right side, and around the start cap.
+            /// It may overlap itself where the curve is sharply bent, so it should be filled with
+            /// the non-zero winding rule.
             ///
-            /// 3x3 and 4x4 matrices can be multiplied by a Bézier curve to transform all of its points.
-            3 QuadraticBezier3 CubicBezier3 Vec3 LineSegment3
-        }
-        bezier_impl_3d_into_2d!{QuadraticBezier3 QuadraticBezier2}
-        bezier_impl_cubic!{
-            /// A 2D Bézier curve with two control points.
+            /// `tolerance` is the maximum distance between the offset curves and their exact position.
+            /// `join` only applies where the curve has a cusp.
             ///
-            /// 2x2 and 3x3 matrices can be multiplied by a Bézier curve to transform all of its points.
-            2 QuadraticBezier2 CubicBezier2 Vec2 LineSegment2
-        }
-        bezier_impl_2d_into_3d!{CubicBezier2 CubicBezier3}
-        bezier_impl_cubic!{
-            /// A 3D Bézier curve with two control points.
+            /// ```
+            /// # extern crate vek;
+            /// # use vek::{Vec2, CubicBezier2, LineCap, LineJoin};
+            /// let curve = CubicBezier2 {
+            ///     start: Vec2::new(0_f32, 0.),
+            ///     ctrl0: Vec2::new(1., 2.),
+            ///     ctrl1: Vec2::new(3., 2.),
+            ///     end: Vec2::new(4., 0.),
+            /// };
+            /// let outline = curve.stroke_outline(0.5, LineCap::Round, LineJoin::Miter(4.), 0.01);
+            /// assert_eq!(outline.first().unwrap().start, outline.last().unwrap().end);
+            /// for pair in outline.windows(2) {
+            ///     assert_eq!(pair[0].end, pair[1].start);
+            /// }
+            /// ```
+            pub fn stroke_outline(self, width: T, cap: LineCap, join: LineJoin<T>, tolerance: T) -> Vec<Self> {
+                Self::stroke_outline_of_path(&[self], width, cap, join, tolerance)
+            }
+            /// Computes the outline of a path made of consecutive curves, stroked with the given width.
             ///
-            /// 3x3 and 4x4 matrices can be multiplied by a Bézier curve to transform all of its points.
-            3 QuadraticBezier3 CubicBezier3 Vec3 LineSegment3
+            /// Each curve is expected to start where the previous one ends. The path is open, so caps
+            /// are drawn at both of its ends, and `join` is used wherever the path's direction
+            /// changes abruptly.
+            /// See `stroke_outline()` for the properties of the returned outline.
+            /// An empty path yields an empty outline.
+            pub fn stroke_outline_of_path(curves: &[Self], width: T, cap: LineCap, join: LineJoin<T>, tolerance: T) -> Vec<Self> {
+                let mut out = Vec::new();
+                let (first, last) = match (curves.first(), curves.last()) {
+                    (Some(first), Some(last)) => (*first, *last),
+                    _ => return out,
+                };
+                let half_width = width.abs() / (T::one() + T::one());
+                Self::stroke_side(curves.iter().cloned(), half_width, join, tolerance, &mut out);
+                Self::stroke_cap(last.end, last.stroke_tangents().1, half_width, cap, &mut out);
+                Self::stroke_side(curves.iter().rev().map(|c| c.reversed()), half_width, join, tolerance, &mut out);
+                Self::stroke_cap(first.start, -first.stroke_tangents().0, half_width, cap, &mut out);
+                let start = out[0].start;
+                out.last_mut().unwrap().end = start;
+                out
+            }
+            /// Gets the unit tangents at the start and end of the curve, skipping coincident control points.
+            fn stroke_tangents(self) -> (Vec2<T>, Vec2<T>) {
+                let first_non_zero = |candidates: [Vec2<T>; 3]| {
+                    candidates.iter()
+                        .find(|v| v.magnitude_squared() > T::epsilon() * T::epsilon())
+                        .map(|v| v.normalized())
+                        .unwrap_or_else(Vec2::unit_x)
+                };
+                let start = first_non_zero([self.ctrl0 - self.start, self.ctrl1 - self.start, self.end - self.start]);
+                let end = first_non_zero([self.end - self.ctrl1, self.end - self.ctrl0, self.end - self.start]);
+                (start, end)
+            }
+            /// Appends `curve` to `out`, snapping its start to the end of the previous curve.
+            fn stroke_push(mut curve: Self, out: &mut Vec<Self>) {
+                if let Some(last) = out.last() {
+                    curve.start = last.end;
+                }
+                out.push(curve);
+            }
+            /// Appends a straight line from the end of the last curve of `out` to `to`.
+            fn stroke_line_to(to: Vec2<T>, out: &mut Vec<Self>) {
+                let from = out.last().unwrap().end;
+                let three = T::one() + T::one() + T::one();
+                let d = (to - from) / three;
+                Self::stroke_push(Self { start: from, ctrl0: from + d, ctrl1: to - d, end: to }, out);
+            }
+            /// Appends a circular arc around `center`, from the end of the last curve of `out` to `to`,
+            /// sweeping `sweep` radians (counter-clockwise if positive).
+            fn stroke_arc_to(center: Vec2<T>, to: Vec2<T>, sweep: T, out: &mut Vec<Self>) {
+                let from = out.last().unwrap().end;
+                let radius = (from - center).magnitude();
+                let start_angle = atan2(from.y - center.y, from.x - center.x);
+                let quarter = T::from(::std::f64::consts::FRAC_PI_2).unwrap();
+                let count = (sweep.abs() / quarter).ceil().max(T::one());
+                let step = sweep / count;
+                let four = T::one() + T::one() + T::one() + T::one();
+                let k = four / (T::one() + T::one() + T::one()) * tan(step / four) * radius;
+                let mut i = T::zero();
+                while i < count {
+                    let a0 = start_angle + step * i;
+                    let a1 = a0 + step;
+                    let (d0, d1) = (Vec2::new(cos(a0), sin(a0)), Vec2::new(cos(a1), sin(a1)));
+                    let (p0, p1) = (center + d0 * radius, center + d1 * radius);
+                    let (t0, t1) = (Vec2::new(-d0.y, d0.x), Vec2::new(-d1.y, d1.x));
+                    Self::stroke_push(Self { start: p0, ctrl0: p0 + t0 * k, ctrl1: p1 - t1 * k, end: p1 }, out);
+                    i = i + T::one();
+                }
+                out.last_mut().unwrap().end = to;
+            }
+            /// Appends the cap at `p`, where the path ends heading towards `tangent`,
+            /// going from the left side to the right side.
+            fn stroke_cap(p: Vec2<T>, tangent: Vec2<T>, half_width: T, cap: LineCap, out: &mut Vec<Self>) {
+                let normal = Vec2::new(-tangent.y, tangent.x) * half_width;
+                match cap {
+                    LineCap::Butt => Self::stroke_line_to(p - normal, out),
+                    LineCap::Square => {
+                        let extent = tangent * half_width;
+                        Self::stroke_line_to(p + normal + extent, out);
+                        Self::stroke_line_to(p - normal + extent, out);
+                        Self::stroke_line_to(p - normal, out);
+                    },
+                    LineCap::Round => {
+                        let pi = T::from(::std::f64::consts::PI).unwrap();
+                        Self::stroke_arc_to(p, p - normal, -pi, out);
+                    },
+                }
+            }
+            /// Appends the left side of the given path, offset by `half_width`, with joins where needed.
+            fn stroke_side<I: Iterator<Item=Self>>(curves: I, half_width: T, join: LineJoin<T>, tolerance: T, out: &mut Vec<Self>) {
+                let mut prev_tangent = None;
+                for curve in curves {
+                    curve.stroke_offset(half_width, join, tolerance, 0, &mut prev_tangent, out);
+                }
+            }
+            /// Appends the offset of this curve, subdividing it until the offset is within `tolerance`.
+            fn stroke_offset(self, half_width: T, join: LineJoin<T>, tolerance: T, depth: u32, prev_tangent: &mut Option<Vec2<T>>, out: &mut Vec<Self>) {
+                const MAX_DEPTH: u32 = 10;
+                let (t0, t1) = self.stroke_tangents();
+                let (n0, n1) = (Vec2::new(-t0.y, t0.x), Vec2::new(-t1.y, t1.x));
+                let start = self.start + n0 * half_width;
+                let end = self.end + n1 * half_width;
+                // Tiller-Hanson: offset each leg of the control polygon, then intersect adjacent legs.
+                let mid = self.ctrl1 - self.ctrl0;
+                let mid_normal = if mid.magnitude_squared() > T::epsilon() * T::epsilon() {
+                    let mid = mid.normalized();
+                    Some(Vec2::new(-mid.y, mid.x))
+                } else {
+                    None
+                };
+                let intersect = |p: Vec2<T>, d: Vec2<T>, fallback: Vec2<T>| {
+                    let (q, e) = match mid_normal {
+                        Some(n) => (self.ctrl0 + n * half_width, Vec2::new(n.y, -n.x)),
+                        None => return fallback,
+                    };
+                    let denom = d.x * e.y - d.y * e.x;
+                    if denom.abs() <= T::epsilon() {
+                        return fallback;
+                    }
+                    let w = q - p;
+                    p + d * ((w.x * e.y - w.y * e.x) / denom)
+                };
+                let offset = Self {
+                    start,
+                    ctrl0: intersect(start, t0, self.ctrl0 + n0 * half_width),
+                    ctrl1: intersect(end, t1, self.ctrl1 + n1 * half_width),
+                    end,
+                };
+                if depth < MAX_DEPTH {
+                    let quarter = T::one() / (T::one() + T::one() + T::one() + T::one());
+                    let mut t = quarter;
+                    while t < T::one() {
+                        let error = ((offset.evaluate(t) - self.evaluate(t)).magnitude() - half_width).abs();
+                        if error > tolerance {
+                            let [a, b] = self.split(T::one() / (T::one() + T::one()));
+                            a.stroke_offset(half_width, join, tolerance, depth + 1, prev_tangent, out);
+                            b.stroke_offset(half_width, join, tolerance, depth + 1, prev_tangent, out);
+                            return;
+                        }
+                        t = t + quarter;
+                    }
+                }
+                if let (Some(last), Some(tangent)) = (out.last(), *prev_tangent) {
+                    if (last.end - start).magnitude() > tolerance {
+                        Self::stroke_join(self.start, tangent, t0, start, half_width, join, out);
+                    }
+                }
+                Self::stroke_push(offset, out);
+                *prev_tangent = Some(t1);
+            }
+            /// Appends the join at `p` on the left side, between a part of the path that arrives with
+            /// `tangent_in` and one that leaves with `tangent_out`, ending at `to`.
+            #[allow(clippy::too_many_arguments)]
+            fn stroke_join(p: Vec2<T>, tangent_in: Vec2<T>, tangent_out: Vec2<T>, to: Vec2<T>, half_width: T, join: LineJoin<T>, out: &mut Vec<Self>) {
+                let cross = tangent_in.x * tangent_out.y - tangent_in.y * tangent_out.x;
+                if cross >= T::zero() {
+                    // Turning left: the left side is the inner side.
+                    // Going through the joint keeps the outline inside the stroke.
+                    Self::stroke_line_to(p, out);
+                    Self::stroke_line_to(to, out);
+                    return;
+                }
+                match join {
+                    LineJoin::Bevel => Self::stroke_line_to(to, out),
+                    LineJoin::Round => {
+                        let sweep = atan2(cross, tangent_in.dot(tangent_out));
+                        Self::stroke_arc_to(p, to, sweep, out);
+                    },
+                    LineJoin::Miter(limit) => {
+                        let n_in = Vec2::new(-tangent_in.y, tangent_in.x);
+                        let n_out = Vec2::new(-tangent_out.y, tangent_out.x);
+                        let bisector = n_in + n_out;
+                        let cos_half = if bisector.magnitude_squared() > T::epsilon() {
+                            bisector.normalized().dot(n_in)
+                        } else {
+                            T::zero()
+                        };
+                        // The miter's length divided by the stroke's width is 1 / cos(turn_angle / 2).
+                        if cos_half > T::zero() && T::one() <= limit * cos_half {
+                            Self::stroke_line_to(p + bisector.normalized() * (half_width / cos_half), out);
+                        }
+                        Self::stroke_line_to(to, out);
+                    },
+                }
+            }
         }
-        bezier_impl_3d_into_2d!{CubicBezier3 CubicBezier2}
     };
 }
 
-#[cfg(all(nightly, feature="repr_simd"))]
-pub mod repr_simd {
-    //! Bézier curve structs that use `#[repr(simd)]` vectors.
-    use super::*;
-    impl_all_beziers!{repr_simd}
-}
-pub mod repr_c {
-    //! Bézier curve structs that use `#[repr(C)]` vectors.
-    use super::*;
-    impl_all_beziers!{repr_c}
-}
-
-pub use self::repr_c::*;
+macro_rules! bezier_impl_cubic2_inspection {
+    ($CubicBezier:ident $LineSegment:ident) => {
+        impl<T: Real> $CubicBezier<T> {
+            /// Gets the signed curvature at `t`, positive where the curve turns counter-clockwise.
+            /// It is infinite (or NaN) where the derivative is zero.
+            pub(crate) fn signed_curvature_at(self, t: T) -> T {
+                self.curvature(t).unwrap_or_else(|| T::one() / T::zero())
+            }
+            /// Computes the curvature comb of this curve, used by curve design tools to judge its smoothness.
+            ///
+            /// For each of `samples` evenly spaced values of `t` in `[0, 1]`, this returns a segment
+            /// which starts on the curve, is perpendicular to it, and whose length is the curvature at
+            /// that point times `scale`. Segments point away from the center of curvature (i.e they
+            /// stand on the convex side of the curve), so straight parts have zero-length segments.
+            ///
+            /// Points where the derivative is zero (cusps) also get a zero-length segment.
+            ///
+            /// ```
+            /// # use vek::{CubicBezier2, Vec2};
+            /// let curve = CubicBezier2 {
+            ///     start: Vec2::new(0_f64, 0.),
+            ///     ctrl0: Vec2::new(1., 1.),
+            ///     ctrl1: Vec2::new(2., 1.),
+            ///     end: Vec2::new(3., 0.),
+            /// };
+            /// let comb = curve.curvature_comb(9, 1.);
+            /// assert_eq!(comb.len(), 9);
+            /// // The curve bends clockwise, so the comb stands above it.
+            /// assert!(comb[4].end.y > comb[4].start.y);
+            /// ```
+            #[cfg(feature="std")]
+            pub fn curvature_comb(self, samples: usize, scale: T) -> Vec<$LineSegment<T>> {
+                let last = T::from(samples.saturating_sub(1).max(1)).unwrap();
+                (0..samples).map(|i| {
+                    let t = T::from(i).unwrap() / last;
+                    let start = self.evaluate(t);
+                    let tangent = self.evaluate_derivative(t);
+                    let curvature = self.signed_curvature_at(t);
+                    let length = tangent.magnitude();
+                    let end = if length > T::zero() && curvature.abs() < T::max_value() {
+                        let normal = Vec2::new(-tangent.y, tangent.x) / length;
+                        start - normal * (curvature * scale)
+                    } else {
+                        start
+                    };
+                    $LineSegment { start, end }
+                }).collect()
+            }
+            /// Finds where the magnitude of the curvature is the largest, returning `(t, curvature)`
+            /// (the latter being non-negative).
+            ///
+            /// This samples the curve at 65 evenly spaced points, then refines the best one with a
+            /// golden-section search between its neighbours.
+            /// If one of the samples is a cusp (where the derivative is zero), it is returned with an
+            /// infinite curvature.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{CubicBezier2, Vec2};
+            /// # fn main() {
+            /// // Symmetric, so the curvature peaks in the middle.
+            /// let curve = CubicBezier2 {
+            ///     start: Vec2::new(0_f64, 0.),
+            ///     ctrl0: Vec2::new(1., 2.),
+            ///     ctrl1: Vec2::new(2., 2.),
+            ///     end: Vec2::new(3., 0.),
+            /// };
+            /// let (t, _curvature) = curve.max_curvature();
+            /// assert_relative_eq!(t, 0.5, epsilon = 1e-6);
+            /// # }
+            /// ```
+            pub fn max_curvature(self) -> (T, T) {
+                const SAMPLES: usize = 64;
+                let curvature = |t: T| self.signed_curvature_at(t).abs();
+                let n = T::from(SAMPLES).unwrap();
+                let (mut best_t, mut best) = (T::zero(), T::zero());
+                for i in 0..=SAMPLES {
+                    let t = T::from(i).unwrap() / n;
+                    let c = curvature(t);
+                    // Also catches NaN, for cusps where the second derivative is zero too.
+                    if c.partial_cmp(&T::max_value()) != Some(std::cmp::Ordering::Less) {
+                        return (t, T::one() / T::zero());
+                    }
+                    if c > best {
+                        best_t = t;
+                        best = c;
+                    }
+                }
+                // Golden-section search around the best sample.
+                let inv_phi = (T::from(5).unwrap().sqrt() - T::one()) / (T::one() + T::one());
+                let (mut a, mut b) = ((best_t - n.recip()).max(T::zero()), (best_t + n.recip()).min(T::one()));
+                let mut c = b - (b - a) * inv_phi;
+                let mut d = a + (b - a) * inv_phi;
+                let (mut fc, mut fd) = (curvature(c), curvature(d));
+                for _ in 0..48 {
+                    if fc > fd {
+                        b = d; d = c; fd = fc;
+                        c = b - (b - a) * inv_phi;
+                        fc = curvature(c);
+                    } else {
+                        a = c; c = d; fc = fd;
+                        d = a + (b - a) * inv_phi;
+                        fd = curvature(d);
+                    }
+                }
+                let t = (a + b) / (T::one() + T::one());
+                let refined = curvature(t);
+                if refined > best { (t, refined) } else { (best_t, best) }
+            }
+            /// Gets the interpolation factors, in `(0, 1)` and in increasing order, at which this
+            /// curve has an inflection, i.e where its curvature is zero.
+            ///
+            /// These are the roots of the cross product of the first and second derivatives, which is
+            /// a polynomial of degree two, so there are at most two of them.
+            /// Curves which are actually quadratic or straight have no inflection.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{CubicBezier2, Vec2};
+            /// # fn main() {
+            /// let curve = CubicBezier2 {
+            ///     start: Vec2::new(0_f64, 0.),
+            ///     ctrl0: Vec2::new(1., 1.),
+            ///     ctrl1: Vec2::new(2., -1.),
+            ///     end: Vec2::new(3., 0.),
+            /// };
+            /// let (first, second) = curve.inflection_points();
+            /// assert_relative_eq!(first.unwrap(), 0.5);
+            /// assert_eq!(second, None);
+            /// # }
+            /// ```
+            pub fn inflection_points(self) -> (Option<T>, Option<T>) {
+                let two = T::one() + T::one();
+                let four = two + two;
+                let cross = |u: Vec2<T>, v: Vec2<T>| u.x * v.y - u.y * v.x;
+                // B'(t) / 3 = a + 2bt + ct², and B''(t) / 6 = b + ct.
+                let a = self.ctrl0 - self.start;
+                let b = self.ctrl1 - self.ctrl0 * two + self.start;
+                let c = self.end - self.start + (self.ctrl0 - self.ctrl1) * (two + T::one());
+                // Their cross product is qa * t² + qb * t + qc.
+                let (qa, qb, qc) = (cross(b, c), cross(a, c), cross(a, b));
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::vec::{Vec2, Vec3};
+                // Relative to the magnitude of the curve, so that the result doesn't depend on its scale.
+                let scale = qa.abs().max(qb.abs()).max(qc.abs());
+                let norm = a.magnitude_squared() + b.magnitude_squared() + c.magnitude_squared();
+                if scale <= T::epsilon() * norm {
+                    // Straight (or a point).
+                    return (None, None);
+                }
+                let snap = |x: T| if (x / scale).abs() <= T::epsilon() * four { T::zero() } else { x / scale };
+                let (qa, qb, qc) = (snap(qa), snap(qb), snap(qc));
 
-    macro_rules! test {
-        ($Bezier:ident $bezier:ident $Vec:ident) => {
-            mod $bezier {
-                use super::*;
-                #[test] fn lerp_from_line_segment() {
-                    let count = 32;
-                    let t_iter = (0..(count+1)).into_iter().map(|i| i as f32 / (count as f32));
-                    let l = || $Vec::<f32>::unit_x() .. $Vec::<f32>::unit_y();
-                    let c = $Bezier::from(l());
-                    for t in t_iter {
-                        assert_relative_eq!(c.evaluate(t), Lerp::lerp_unclamped_precise(l().start, l().end, t))
-                    }
+                let discriminant = qb * qb - four * qa * qc;
+                if discriminant < T::zero() {
+                    return (None, None);
+                }
+                // Numerically stable form; divisions by zero give infinities or NaNs, which are
+                // rejected below.
+                let q = -(qb + qb.signum() * discriminant.sqrt()) / two;
+                let (mut first, mut second) = (q / qa, qc / q);
+                if second < first {
+                    std::mem::swap(&mut first, &mut second);
+                }
+                let is_between01 = |t: T| T::zero() < t && t < T::one();
+                match (is_between01(first), is_between01(second)) {
+                    (true, true) if first == second => (Some(first), None),
+                    (true, true) => (Some(first), Some(second)),
+                    (true, false) => (Some(first), None),
+                    (false, true) => (Some(second), None),
+                    (false, false) => (None, None),
                 }
             }
-        };
-    }
-    test!{QuadraticBezier2 quadratic2 Vec2}
-    test!{QuadraticBezier3 quadratic3 Vec3}
-    test!{CubicBezier2 cubic2 Vec2}
-    test!{CubicBezier3 cubic3 Vec3}
+            /// Splits this curve at its inflection points (see `inflection_points()`), returning
+            /// one to three curves, in order, none of which changes the direction it turns in.
+            ///
+            /// ```
+            /// # use vek::{CubicBezier2, Vec2};
+            /// let curve = CubicBezier2 {
+            ///     start: Vec2::new(0_f64, 0.),
+            ///     ctrl0: Vec2::new(1., 1.),
+            ///     ctrl1: Vec2::new(2., -1.),
+            ///     end: Vec2::new(3., 0.),
+            /// };
+            /// let pieces = curve.split_at_inflections();
+            /// assert_eq!(pieces.len(), 2);
+            /// assert_eq!(pieces[0].end, pieces[1].start);
+            /// ```
+            #[cfg(feature="std")]
+            pub fn split_at_inflections(self) -> Vec<Self> {
+                match self.inflection_points() {
+                    (None, _) => alloc::vec![self],
+                    (Some(t), None) => self.split(t).to_vec(),
+                    (Some(t0), Some(t1)) => {
+                        let [first, rest] = self.split(t0);
+                        let [second, third] = rest.split((t1 - t0) / (T::one() - t0));
+                        alloc::vec![first, second, third]
+                    },
+                }
+            }
+        }
+    };
+}
+
+macro_rules! bezier_impl_cubic2_intersections {
+    ($CubicBezier:ident $QuadraticBezier:ident $LineSegment:ident $Point:ident) => {
+        impl<T: Real> $CubicBezier<T> {
+            /// Gets the intersections of this curve with another one, as pairs of interpolation
+            /// factors along this curve and along `other`, in increasing order of the former.
+            ///
+            /// Quadratic curves can be given as `other`, as they are elevated to cubic curves
+            /// (which keeps their interpolation factors). Both curves are recursively subdivided,
+            /// discarding pairs of pieces whose control points' bounds don't overlap, until the
+            /// pieces are within `tolerance` of straight line segments, which are then intersected.
+            /// Intersections are refined by Newton's method, so the points of both curves at the
+            /// returned factors are at most `tolerance` apart, and usually much closer.
+            /// Intersections between which the curves stay within `tolerance` of each other are
+            /// merged, so where the curves are tangent, the point of contact is reported once.
+            /// Ends count, so consecutive curves of a path intersect where they meet.
+            ///
+            /// Returns an error if the curves overlap, i.e if a stretch of one curve also lies on
+            /// the other, within `tolerance`, including when they are the same curve. Curves which
+            /// stay so close to each other that intersections can't be isolated in reasonable time
+            /// are reported the same way.
+            ///
+            /// # Panics
+            /// Panics if `tolerance` is not positive.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{CubicBezier2, QuadraticBezier2, Vec2};
+            /// # fn main() {
+            /// let arch = CubicBezier2 {
+            ///     start: Vec2::new(0_f64, 0.),
+            ///     ctrl0: Vec2::new(0., 4.),
+            ///     ctrl1: Vec2::new(4., 4.),
+            ///     end: Vec2::new(4., 0.),
+            /// };
+            /// let valley = QuadraticBezier2 { start: Vec2::new(0., 4.), ctrl: Vec2::new(2., -2.), end: Vec2::new(4., 4.) };
+            /// let hits = arch.intersections(valley, 1e-9).unwrap();
+            /// assert_eq!(hits.len(), 2);
+            /// for (t, u) in hits {
+            ///     assert_relative_eq!(arch.evaluate(t), valley.evaluate(u), epsilon = 1e-9);
+            /// }
+            /// assert!(arch.intersections(arch, 1e-9).is_err());
+            /// # }
+            /// ```
+            #[cfg(feature="std")]
+            pub fn intersections<C: Into<Self>>(self, other: C, tolerance: T) -> Result<Vec<(T, T)>, CurvesOverlapError> {
+                assert!(tolerance > T::zero(), "the tolerance of curve intersections must be positive");
+                let other = other.into();
+                if self.overlaps_with(other, tolerance) {
+                    return Err(CurvesOverlapError);
+                }
+                let (zero, one) = (T::zero(), T::one());
+                let two = one + one;
+                let bounds = |c: Self| -> Aabr<T> {
+                    let [a, b, c, d] = c.into_vector().into_array();
+                    Aabr { min: Vec2::partial_min(Vec2::partial_min(a, b), Vec2::partial_min(c, d)), max: Vec2::partial_max(Vec2::partial_max(a, b), Vec2::partial_max(c, d)) }
+                };
+                // How far the curve may be from its chord.
+                let flatness = |c: Self| {
+                    let chord = $LineSegment { start: c.start, end: c.end };
+                    chord.distance_to_point_with_epsilon(c.ctrl0, T::zero()).max(chord.distance_to_point_with_epsilon(c.ctrl1, T::zero()))
+                };
+
+                // Pairs of pieces, along with the ranges of factors they span.
+                let mut pairs = alloc::vec![((self, zero, one), (other, zero, one))];
+                // The factors of the closest points of pairs of pieces which are close to each other
+                // and flat enough to be taken as line segments.
+                let mut leaves: Vec<(T, T)> = Vec::new();
+                let mut count = 0;
+                while let Some((a, b)) = pairs.pop() {
+                    count += 1;
+                    if count > MAX_INTERSECTION_PAIRS {
+                        return Err(CurvesOverlapError);
+                    }
+                    if !bounds(a.0).intersection(bounds(b.0)).is_valid() {
+                        continue;
+                    }
+                    let can_split = |(_, t0, t1): (Self, T, T), flatness: T| {
+                        let mid = (t0 + t1) / two;
+                        flatness > tolerance && mid > t0 && mid < t1
+                    };
+                    let (flatness_a, flatness_b) = (flatness(a.0), flatness(b.0));
+                    let split_a = can_split(a, flatness_a) && (flatness_a >= flatness_b || !can_split(b, flatness_b));
+                    let split_b = !split_a && can_split(b, flatness_b);
+                    let halves = |(c, t0, t1): (Self, T, T)| {
+                        let [first, second] = c.split(one / two);
+                        let mid = (t0 + t1) / two;
+                        [(first, t0, mid), (second, mid, t1)]
+                    };
+                    if split_a {
+                        for &half in halves(a).iter().rev() {
+                            pairs.push((half, b));
+                        }
+                    } else if split_b {
+                        for &half in halves(b).iter().rev() {
+                            pairs.push((a, half));
+                        }
+                    } else {
+                        // Both curves are within the tolerance of their chords.
+                        let (s, r, d) = Self::closest_chord_factors(a.0, b.0);
+                        if d <= tolerance + tolerance {
+                            leaves.push((a.1 + (a.2 - a.1) * s, b.1 + (b.2 - b.1) * r));
+                        }
+                    }
+                }
+
+                // Refines the leaves to intersections, and merges those between which the curves stay
+                // within the tolerance, e.g around a point of contact: they can't be told apart.
+                let distance = |t: T, u: T| self.evaluate(t).distance(other.evaluate(u));
+                let mut refined: Vec<(T, T, T)> = leaves.into_iter()
+                    .map(|(t, u)| self.refine_intersection(other, t, u, distance(t, u)))
+                    .map(|(t, u)| (t, u, distance(t, u)))
+                    .filter(|&(_, _, d)| d <= tolerance)
+                    .collect();
+                // NaN factors have NaN distances and are filtered out above; should one slip through
+                // anyway, it compares as equal rather than panicking.
+                refined.sort_unstable_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+                let mut intersections: Vec<(T, T, T)> = Vec::with_capacity(refined.len());
+                for (t, u, d) in refined {
+                    if let Some(last) = intersections.last_mut() {
+                        let is_same = (1..4).all(|k| {
+                            let s = T::from(k).unwrap() / T::from(4).unwrap();
+                            distance(last.0 + (t - last.0) * s, last.1 + (u - last.1) * s) <= tolerance
+                        });
+                        if is_same {
+                            if d < last.2 {
+                                *last = (t, u, d);
+                            }
+                            continue;
+                        }
+                    }
+                    intersections.push((t, u, d));
+                }
+                Ok(intersections.into_iter().map(|(t, u, _)| (t, u)).collect())
+            }
+            /// Gets the factors of the closest points of the chords of two curves, and their distance.
+            #[cfg(feature="std")]
+            fn closest_chord_factors(a: Self, b: Self) -> (T, T, T) {
+                let (zero, one) = (T::zero(), T::one());
+                let (da, db) = (a.end - a.start, b.end - b.start);
+                let offset = b.start - a.start;
+                let cross = da.x * db.y - da.y * db.x;
+                if cross != zero {
+                    let s = (offset.x * db.y - offset.y * db.x) / cross;
+                    let r = (offset.x * da.y - offset.y * da.x) / cross;
+                    if s >= zero && s <= one && r >= zero && r <= one {
+                        return (s, r, zero);
+                    }
+                }
+                // Otherwise, one of the closest points is an end.
+                let project = |p: $Point<T>, start: $Point<T>, direction: $Point<T>| {
+                    let length_squared = direction.magnitude_squared();
+                    if length_squared == zero {
+                        zero
+                    } else {
+                        (direction.dot(p - start) / length_squared).max(zero).min(one)
+                    }
+                };
+                let candidates = [
+                    (zero, project(a.start, b.start, db)),
+                    (one, project(a.end, b.start, db)),
+                    (project(b.start, a.start, da), zero),
+                    (project(b.end, a.start, da), one),
+                ];
+                candidates.iter().map(|&(s, r)| (s, r, (a.start + da * s).distance(b.start + db * r)))
+                    .fold((zero, zero, T::max_value()), |best, c| if c.2 < best.2 { c } else { best })
+            }
+            /// Refines an intersection of this curve with `other` with Newton's method, from factors
+            /// whose points are `d` apart, keeping the factors as they are if it doesn't improve them.
+            #[cfg(feature="std")]
+            fn refine_intersection(self, other: Self, t: T, u: T, d: T) -> (T, T) {
+                let (zero, one) = (T::zero(), T::one());
+                let (mut best, mut best_d) = ((t, u), d);
+                let (mut t, mut u) = (t, u);
+                for _ in 0..16 {
+                    let f = self.evaluate(t) - other.evaluate(u);
+                    let (da, db) = (self.evaluate_derivative(t), other.evaluate_derivative(u));
+                    // Solves [da, -db] * (dt, du) = -f.
+                    let det = db.x * da.y - da.x * db.y;
+                    if det == zero {
+                        break;
+                    }
+                    t = (t + (f.x * db.y - f.y * db.x) / det).max(zero).min(one);
+                    u = (u + (da.y * f.x - da.x * f.y) / det).max(zero).min(one);
+                    let d = self.evaluate(t).distance(other.evaluate(u));
+                    if d < best_d {
+                        best = (t, u);
+                        best_d = d;
+                    }
+                }
+                best
+            }
+            /// Tells whether a stretch of this curve also lies on `other`, within `tolerance`.
+            ///
+            /// Such a stretch ends at two of the curves' ends, and since both curves are then pieces
+            /// of the same cubic, their factors along it are proportional.
+            #[cfg(feature="std")]
+            fn overlaps_with(self, other: Self, tolerance: T) -> bool {
+                let (zero, one) = (T::zero(), T::one());
+                let epsilon = T::epsilon() * T::from(16).unwrap();
+                let mut ends = [(zero, zero); 4];
+                let mut count = 0;
+                for &t in &[zero, one] {
+                    let (u, p) = other.project_point(self.evaluate(t), epsilon);
+                    if p.distance(self.evaluate(t)) <= tolerance {
+                        ends[count] = (t, u);
+                        count += 1;
+                    }
+                }
+                for &u in &[zero, one] {
+                    let (t, p) = self.project_point(other.evaluate(u), epsilon);
+                    if p.distance(other.evaluate(u)) <= tolerance {
+                        ends[count] = (t, u);
+                        count += 1;
+                    }
+                }
+                let samples = 16;
+                for i in 0..count {
+                    for j in i + 1..count {
+                        let ((t0, u0), (t1, u1)) = (ends[i], ends[j]);
+                        if self.evaluate(t0).distance(self.evaluate(t1)) <= tolerance {
+                            continue;
+                        }
+                        let is_shared = (1..samples).all(|k| {
+                            let s = T::from(k).unwrap() / T::from(samples).unwrap();
+                            self.evaluate(t0 + (t1 - t0) * s).distance(other.evaluate(u0 + (u1 - u0) * s)) <= tolerance
+                        });
+                        if is_shared {
+                            return true;
+                        }
+                    }
+                }
+                false
+            }
+        }
+
+        impl<T: Real> $QuadraticBezier<T> {
+            /// Gets the intersections of this curve with another one, as pairs of interpolation
+            /// factors along this curve and along `other`, in increasing order of the former.
+            ///
+            /// This elevates this curve to a cubic one, see `CubicBezier2::intersections()`.
+            #[cfg(feature="std")]
+            pub fn intersections<C: Into<$CubicBezier<T>>>(self, other: C, tolerance: T) -> Result<Vec<(T, T)>, CurvesOverlapError> {
+                self.into_cubic().intersections(other, tolerance)
+            }
+        }
+    };
+}
+
+macro_rules! bezier_impl_cubic2_continuity {
+    ($CubicBezier:ident) => {
+        impl<T: Real> $CubicBezier<T> {
+            /// Gets the unit tangents at the start and end of the curve, skipping coincident control
+            /// points, or `None` where all points coincide.
+            fn join_tangents(self) -> (Option<Vec2<T>>, Option<Vec2<T>>) {
+                let first_non_zero = |candidates: [Vec2<T>; 3]| {
+                    candidates.iter()
+                        .find(|v| v.magnitude_squared() > T::zero())
+                        .map(|v| v.normalized())
+                };
+                let start = first_non_zero([self.ctrl0 - self.start, self.ctrl1 - self.start, self.end - self.start]);
+                let end = first_non_zero([self.end - self.ctrl1, self.end - self.ctrl0, self.end - self.start]);
+                (start, end)
+            }
+            /// Is the join between this curve and `next` C1-continuous, i.e does `next` start where this
+            /// curve ends, with the same derivative ?
+            ///
+            /// Both the distance between the end points and the distance between the derivatives
+            /// (divided by 3, i.e the distance between the handles `end - ctrl1` and `next.ctrl0 - next.start`)
+            /// must be at most `epsilon`.
+            ///
+            /// ```
+            /// # use vek::{CubicBezier2, Vec2};
+            /// let a = CubicBezier2 { start: Vec2::new(0_f32, 0.), ctrl0: Vec2::new(1., 1.), ctrl1: Vec2::new(2., 1.), end: Vec2::new(3., 0.) };
+            /// let b = CubicBezier2 { start: Vec2::new(3_f32, 0.), ctrl0: Vec2::new(4., -1.), ctrl1: Vec2::new(5., 1.), end: Vec2::new(6., 0.) };
+            /// assert!(a.is_c1_continuous_with(&b, 1e-6));
+            /// ```
+            pub fn is_c1_continuous_with(&self, next: &Self, epsilon: T) -> bool {
+                self.end.distance(next.start) <= epsilon
+                    && (self.end - self.ctrl1).distance(next.ctrl0 - next.start) <= epsilon
+            }
+            /// Is the join between this curve and `next` G1-continuous, i.e does `next` start where this
+            /// curve ends, in the same direction (the speeds may differ) ?
+            ///
+            /// The distance between the end points, and the distance between the unit tangents
+            /// at the join (which is approximately the angle between them, in radians) must be at most `epsilon`.
+            /// Handles of zero length are skipped, so that the tangent is that of the curve itself.
+            /// A curve whose points all coincide has no tangent, and is considered G1-continuous with
+            /// anything that starts where it ends.
+            ///
+            /// ```
+            /// # use vek::{CubicBezier2, Vec2};
+            /// let a = CubicBezier2 { start: Vec2::new(0_f32, 0.), ctrl0: Vec2::new(1., 1.), ctrl1: Vec2::new(2., 1.), end: Vec2::new(3., 0.) };
+            /// // The handle is twice as long, so this is G1 but not C1.
+            /// let b = CubicBezier2 { start: Vec2::new(3_f32, 0.), ctrl0: Vec2::new(5., -2.), ctrl1: Vec2::new(5., 1.), end: Vec2::new(6., 0.) };
+            /// assert!(a.is_g1_continuous_with(&b, 1e-6));
+            /// assert!(!a.is_c1_continuous_with(&b, 1e-6));
+            /// ```
+            pub fn is_g1_continuous_with(&self, next: &Self, epsilon: T) -> bool {
+                if self.end.distance(next.start) > epsilon {
+                    return false;
+                }
+                match (self.join_tangents().1, next.join_tangents().0) {
+                    (Some(out), Some(in_)) => out.distance(in_) <= epsilon,
+                    _ => true,
+                }
+            }
+            /// Makes the join between this curve and `next` G1-continuous, by rotating the handles
+            /// adjacent to the join (`self.ctrl1` and `next.ctrl0`) onto a shared tangent line, while
+            /// preserving their lengths.
+            ///
+            /// The shared tangent bisects the two original tangents, so that each handle is rotated by
+            /// half of the angle between them. `self.start`, `self.ctrl0`, `self.end`, `next.ctrl1` and
+            /// `next.end` are left untouched; `next.start` is set to `self.end`, which it is expected to
+            /// be (approximately) equal to.
+            ///
+            /// If one of the handles has zero length, the tangent on that side is determined by control
+            /// points which are not moved, so the other handle is aligned with it instead.
+            /// If the tangents are exactly opposite (a cusp), the tangent of `self` is kept.
+            ///
+            /// ```
+            /// # use vek::{CubicBezier2, Vec2};
+            /// let mut a = CubicBezier2 { start: Vec2::new(0_f32, 0.), ctrl0: Vec2::new(1., 1.), ctrl1: Vec2::new(2., 1.), end: Vec2::new(3., 0.) };
+            /// let mut b = CubicBezier2 { start: Vec2::new(3_f32, 0.), ctrl0: Vec2::new(4., 1.), ctrl1: Vec2::new(5., 1.), end: Vec2::new(6., 0.) };
+            /// assert!(!a.is_g1_continuous_with(&b, 1e-4));
+            /// a.make_g1_continuous_with(&mut b);
+            /// assert!(a.is_g1_continuous_with(&b, 1e-4));
+            /// assert_eq!(a.end, Vec2::new(3., 0.));
+            /// ```
+            pub fn make_g1_continuous_with(&mut self, next: &mut Self) {
+                let join = self.end;
+                next.start = join;
+                let out_handle = (join - self.ctrl1).magnitude();
+                let in_handle = (next.ctrl0 - join).magnitude();
+                let tangent = match (self.join_tangents().1, next.join_tangents().0) {
+                    (_, Some(in_)) if in_handle == T::zero() => in_,
+                    (Some(out), _) if out_handle == T::zero() => out,
+                    (Some(out), Some(in_)) => {
+                        let bisector = out + in_;
+                        if bisector.magnitude_squared() > T::zero() { bisector.normalized() } else { out }
+                    },
+                    _ => return,
+                };
+                self.ctrl1 = join - tangent * out_handle;
+                next.ctrl0 = join + tangent * in_handle;
+            }
+        }
+    };
+}
+
+macro_rules! bezier_impl_cubic2_arc_length {
+    ($CubicBezier:ident) => {
+        impl<T: Real> $CubicBezier<T> {
+            /// Integrates the speed (magnitude of the derivative) of this curve between `t0` and `t1`,
+            /// with a 5-point Gauss-Legendre quadrature.
+            fn arc_length_between(self, t0: T, t1: T) -> T {
+                gauss_legendre(&|t| self.evaluate_derivative(t).magnitude(), t0, t1, &GAUSS_LEGENDRE_5)
+            }
+            /// Gets the arc lengths from the start of this curve to `ARC_LENGTH_INTERVALS + 1` evenly
+            /// spaced values of `t`, the last one being the length of the whole curve.
+            fn arc_length_table(self) -> [T; ARC_LENGTH_INTERVALS + 1] {
+                let n = T::from(ARC_LENGTH_INTERVALS).unwrap();
+                let mut table = [T::zero(); ARC_LENGTH_INTERVALS + 1];
+                let mut length = T::zero();
+                for (i, entry) in table.iter_mut().enumerate().skip(1) {
+                    length = length + self.arc_length_between(T::from(i - 1).unwrap() / n, T::from(i).unwrap() / n);
+                    *entry = length;
+                }
+                table
+            }
+            /// Inverts `table` (as returned by `arc_length_table()`), i.e finds `t` such that the arc
+            /// length from the start of this curve to `t` is `length`.
+            ///
+            /// The table is searched for the enclosing interval, then a few Newton iterations refine
+            /// the linear interpolation within it.
+            fn factor_at_length(self, table: &[T; ARC_LENGTH_INTERVALS + 1], length: T) -> T {
+                let n = T::from(ARC_LENGTH_INTERVALS).unwrap();
+                let i = table.partition_point(|&l| l <= length).clamp(1, ARC_LENGTH_INTERVALS) - 1;
+                let (t0, t1) = (T::from(i).unwrap() / n, T::from(i + 1).unwrap() / n);
+                let (l0, l1) = (table[i], table[i + 1]);
+                if l1 <= l0 {
+                    return t0;
+                }
+                let mut t = t0 + (t1 - t0) * (length - l0) / (l1 - l0);
+                for _ in 0..4 {
+                    let speed = self.evaluate_derivative(t).magnitude();
+                    if speed <= T::zero() {
+                        break;
+                    }
+                    t = t - (l0 + self.arc_length_between(t0, t) - length) / speed;
+                    t = if t < t0 { t0 } else if t > t1 { t1 } else { t };
+                }
+                t
+            }
+            /// Gets the position and unit tangent at `t`, falling back to the tangents of
+            /// `join_tangents()` where the derivative is zero (e.g at coincident control points).
+            fn frame_at(self, t: T) -> (Vec2<T>, Vec2<T>) {
+                let derivative = self.evaluate_derivative(t);
+                let tangent = if derivative.magnitude_squared() > T::zero() {
+                    derivative.normalized()
+                } else {
+                    let (start, end) = self.join_tangents();
+                    let fallback = if t + t < T::one() { start.or(end) } else { end.or(start) };
+                    fallback.unwrap_or_else(Vec2::zero)
+                };
+                (self.evaluate(t), tangent)
+            }
+            /// Gets the position and unit tangent of the point at the given arc length from the
+            /// start of this curve.
+            ///
+            /// See `frame_at_length_of_path()`.
+            pub fn frame_at_length(self, length: T) -> (Vec2<T>, Vec2<T>) {
+                Self::frame_at_length_of_path(&[self], length).unwrap()
+            }
+            /// Gets the position and unit tangent of the point at the given arc length from the
+            /// start of a path made of consecutive curves, or `None` if the path is empty.
+            ///
+            /// `length` is clamped between zero and the length of the whole path.
+            /// Arc lengths are computed by numerical integration, and accurate well beyond what
+            /// placing glyphs or dashes along the path requires.
+            ///
+            /// Each curve owns the points from its start (included) to its end (excluded), except for
+            /// the last one which also owns the path's end. Where the path's direction changes
+            /// abruptly, the tangent at the join is therefore the one at the start of the next curve.
+            /// Curves of zero length own no point.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{CubicBezier2, Vec2};
+            /// # fn main() {
+            /// let path = [
+            ///     CubicBezier2::from(Vec2::new(0_f64, 0.) .. Vec2::new(2., 0.)),
+            ///     CubicBezier2::from(Vec2::new(2_f64, 0.) .. Vec2::new(2., 3.)),
+            /// ];
+            /// let (p, tangent) = CubicBezier2::frame_at_length_of_path(&path, 1.).unwrap();
+            /// assert_relative_eq!(p, Vec2::new(1., 0.), epsilon = 1e-9);
+            /// assert_relative_eq!(tangent, Vec2::unit_x(), epsilon = 1e-9);
+            /// // At the corner, the second segment owns the point.
+            /// let (p, tangent) = CubicBezier2::frame_at_length_of_path(&path, 2.).unwrap();
+            /// assert_relative_eq!(p, Vec2::new(2., 0.), epsilon = 1e-9);
+            /// assert_relative_eq!(tangent, Vec2::unit_y(), epsilon = 1e-9);
+            /// # }
+            /// ```
+            pub fn frame_at_length_of_path(curves: &[Self], length: T) -> Option<(Vec2<T>, Vec2<T>)> {
+                let (last, init) = curves.split_last()?;
+                let length = if length > T::zero() { length } else { T::zero() };
+                let mut start = T::zero();
+                for curve in init {
+                    let table = curve.arc_length_table();
+                    let end = start + table[ARC_LENGTH_INTERVALS];
+                    if length < end {
+                        return Some(curve.frame_at(curve.factor_at_length(&table, length - start)));
+                    }
+                    start = end;
+                }
+                let table = last.arc_length_table();
+                let length = if length > start { length - start } else { T::zero() };
+                let length = if length < table[ARC_LENGTH_INTERVALS] { length } else { table[ARC_LENGTH_INTERVALS] };
+                Some(last.frame_at(last.factor_at_length(&table, length)))
+            }
+            /// Gets the positions and unit tangents of `count` points, evenly spaced by arc length
+            /// along this curve, from its start to its end.
+            ///
+            /// See `evenly_spaced_frames_of_path()`.
+            #[cfg(feature="std")]
+            pub fn evenly_spaced_frames(self, count: usize) -> Vec<(Vec2<T>, Vec2<T>)> {
+                Self::evenly_spaced_frames_of_path(&[self], count)
+            }
+            /// Gets the positions and unit tangents of `count` points, evenly spaced by arc length
+            /// along a path made of consecutive curves, from its start to its end.
+            ///
+            /// This is meant for placing glyphs, dashes or other decorations along a path.
+            /// A single point is placed at the start of the path. An empty path yields no points.
+            /// Which curve owns a point at a join, and thus gives its tangent, is documented in
+            /// `frame_at_length_of_path()`.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{CubicBezier2, Vec2};
+            /// # fn main() {
+            /// let path = [CubicBezier2::from(Vec2::new(0_f64, 0.) .. Vec2::new(4., 0.))];
+            /// let frames = CubicBezier2::evenly_spaced_frames_of_path(&path, 5);
+            /// for (i, (p, tangent)) in frames.into_iter().enumerate() {
+            ///     assert_relative_eq!(p, Vec2::new(i as f64, 0.), epsilon = 1e-9);
+            ///     assert_relative_eq!(tangent, Vec2::unit_x(), epsilon = 1e-9);
+            /// }
+            /// # }
+            /// ```
+            #[cfg(feature="std")]
+            pub fn evenly_spaced_frames_of_path(curves: &[Self], count: usize) -> Vec<(Vec2<T>, Vec2<T>)> {
+                let mut frames = Vec::with_capacity(count);
+                if curves.is_empty() || count == 0 {
+                    return frames;
+                }
+                let tables: Vec<_> = curves.iter().map(|c| c.arc_length_table()).collect();
+                let total = tables.iter().fold(T::zero(), |sum, table| sum + table[ARC_LENGTH_INTERVALS]);
+                let last = T::from(count.saturating_sub(1).max(1)).unwrap();
+                let (mut curve, mut start) = (0, T::zero());
+                for i in 0..count {
+                    let length = total * T::from(i).unwrap() / last;
+                    // Lengths are increasing, so the owning curve is found by walking forward.
+                    while curve + 1 < curves.len() && length >= start + tables[curve][ARC_LENGTH_INTERVALS] {
+                        start = start + tables[curve][ARC_LENGTH_INTERVALS];
+                        curve += 1;
+                    }
+                    let length = if length > start { length - start } else { T::zero() };
+                    let table = &tables[curve];
+                    let length = if length < table[ARC_LENGTH_INTERVALS] { length } else { table[ARC_LENGTH_INTERVALS] };
+                    let c = curves[curve];
+                    frames.push(c.frame_at(c.factor_at_length(table, length)));
+                }
+                frames
+            }
+        }
+    };
+}
+
+macro_rules! bezier_impl_rational_quadratic2 {
+    ($RationalQuadraticBezier:ident $QuadraticBezier:ident $QuadraticBezier3:ident $CubicBezier:ident) => {
+        /// A 2D rational Bézier curve with one control point, where each point has a weight.
+        ///
+        /// Unlike polynomial Bézier curves, rational quadratic curves represent conic sections
+        /// (circular and elliptic arcs, parabolas, hyperbolas) exactly.
+        /// This is how SVG arc segments and NURBS edges are usually brought into a Bézier pipeline.
+        ///
+        /// The curve is the perspective projection of a 3D `QuadraticBezier3` whose control points are
+        /// `(x*w, y*w, w)` (see `into_homogeneous()`). Weights are expected to be positive; with all
+        /// weights equal, this is the same curve as the matching `QuadraticBezier2`.
+        ///
+        /// ```
+        /// # extern crate vek;
+        /// # #[macro_use] extern crate approx;
+        /// # use vek::{RationalQuadraticBezier2, Vec2};
+        /// # fn main() {
+        /// let arc = RationalQuadraticBezier2::from_circular_arc(Vec2::zero(), 2_f64, 0., std::f64::consts::FRAC_PI_2);
+        /// for i in 0..=10 {
+        ///     assert_relative_eq!(arc.evaluate(i as f64 / 10.).magnitude(), 2., epsilon = 1e-12);
+        /// }
+        /// # }
+        /// ```
+        #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, /*PartialOrd, Ord*/)]
+        #[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+        pub struct $RationalQuadraticBezier<T> {
+            /// Starting point of the curve.
+            pub start: Vec2<T>,
+            /// Control point of the curve.
+            pub ctrl: Vec2<T>,
+            /// End point of the curve.
+            pub end: Vec2<T>,
+            /// The weights of `start`, `ctrl` and `end`, respectively.
+            pub weights: Vec3<T>,
+        }
+
+        /// The default value is a curve whose points are all zero, with weights of one.
+        impl<T: Zero + One> Default for $RationalQuadraticBezier<T> {
+            fn default() -> Self {
+                Self {
+                    start: Vec2::zero(),
+                    ctrl: Vec2::zero(),
+                    end: Vec2::zero(),
+                    weights: Vec3::one(),
+                }
+            }
+        }
+
+        impl<T: Real> $RationalQuadraticBezier<T> {
+            /// Creates a rational curve from a polynomial one and the weights of its points.
+            pub fn from_quadratic(curve: $QuadraticBezier<T>, weights: Vec3<T>) -> Self {
+                let $QuadraticBezier { start, ctrl, end } = curve;
+                Self { start, ctrl, end, weights }
+            }
+            /// Creates the rational curve which exactly represents a circular arc.
+            ///
+            /// The arc is centered on `center`, starts at angle `start_angle` (in radians,
+            /// counter-clockwise from the X axis) and sweeps `sweep_angle` radians (counter-clockwise
+            /// if positive). The weights are `(1, cos(sweep_angle/2), 1)`.
+            ///
+            /// The absolute value of `sweep_angle` must be less than PI; larger arcs have to be split
+            /// into several curves.
+            pub fn from_circular_arc(center: Vec2<T>, radius: T, start_angle: T, sweep_angle: T) -> Self {
+                let half = sweep_angle / (T::one() + T::one());
+                let w = cos(half);
+                debug_assert!(w > T::zero(), "The sweep angle of a single arc must be less than PI");
+                let at = |angle: T| Vec2::new(cos(angle), sin(angle));
+                Self {
+                    start: center + at(start_angle) * radius,
+                    ctrl: center + at(start_angle + half) * (radius / w),
+                    end: center + at(start_angle + sweep_angle) * radius,
+                    weights: Vec3::new(T::one(), w, T::one()),
+                }
+            }
+            /// Gets the 3D polynomial curve whose perspective projection (i.e dividing `x` and `y`
+            /// by `z`) is this curve. Its control points are `(x*w, y*w, w)`.
+            pub fn into_homogeneous(self) -> $QuadraticBezier3<T> {
+                let lift = |p: Vec2<T>, w: T| Vec3::new(p.x * w, p.y * w, w);
+                $QuadraticBezier3 {
+                    start: lift(self.start, self.weights.x),
+                    ctrl: lift(self.ctrl, self.weights.y),
+                    end: lift(self.end, self.weights.z),
+                }
+            }
+            /// Creates a rational curve from its homogeneous representation (see `into_homogeneous()`).
+            ///
+            /// The `z` elements of the control points become the weights, and must not be zero.
+            pub fn from_homogeneous(curve: $QuadraticBezier3<T>) -> Self {
+                let project = |p: Vec3<T>| Vec2::new(p.x / p.z, p.y / p.z);
+                Self {
+                    start: project(curve.start),
+                    ctrl: project(curve.ctrl),
+                    end: project(curve.end),
+                    weights: Vec3::new(curve.start.z, curve.ctrl.z, curve.end.z),
+                }
+            }
+            /// Evaluates the position of the point lying on the curve at interpolation factor `t`.
+            pub fn evaluate(self, t: T) -> Vec2<T> {
+                let h = self.into_homogeneous().evaluate(t);
+                Vec2::new(h.x / h.z, h.y / h.z)
+            }
+            /// Evaluates the derivative tangent at interpolation factor `t`, which happens to give
+            /// a non-normalized tangent vector.
+            pub fn evaluate_derivative(self, t: T) -> Vec2<T> {
+                let homogeneous = self.into_homogeneous();
+                let h = homogeneous.evaluate(t);
+                let dh = homogeneous.evaluate_derivative(t);
+                // Quotient rule: (N/w)' = (N' - (N/w) * w') / w
+                let p = Vec2::new(h.x / h.z, h.y / h.z);
+                (Vec2::new(dh.x, dh.y) - p * dh.z) / h.z
+            }
+            /// Evaluates the normalized tangent at interpolation factor `t`.
+            pub fn normalized_tangent(self, t: T) -> Vec2<T> {
+                self.evaluate_derivative(t).normalized()
+            }
+            /// Gets the unit normal at interpolation factor `t`, i.e the tangent rotated by 90 degrees
+            /// counter-clockwise, or `None` where the derivative is zero.
+            ///
+            /// See `QuadraticBezier2::normal()`.
+            pub fn normal(self, t: T) -> Option<Vec2<T>> {
+                let d = self.evaluate_derivative(t);
+                if d.is_zero() {
+                    return None;
+                }
+                Some(Vec2::new(-d.y, d.x).normalized())
+            }
+            /// Splits this curve into two curves, at interpolation factor `t`.
+            ///
+            /// The first curve evaluated at `s` gives the same point as this curve evaluated at `s*t`.
+            pub fn split(self, t: T) -> [Self; 2] {
+                let [first, second] = self.into_homogeneous().split(t);
+                [Self::from_homogeneous(first), Self::from_homogeneous(second)]
+            }
+            /// Gets this curve reversed, i.e swaps `start` with `end` (and their weights).
+            pub fn reversed(self) -> Self {
+                Self {
+                    start: self.end,
+                    ctrl: self.ctrl,
+                    end: self.start,
+                    weights: Vec3::new(self.weights.z, self.weights.y, self.weights.x),
+                }
+            }
+            /// Reverses this curve, i.e swaps `start` with `end` (and their weights).
+            pub fn reverse(&mut self) {
+                *self = self.reversed();
+            }
+            /// Approximates this curve by a sequence of polynomial cubic curves, for use by code
+            /// that only handles polynomial curves.
+            ///
+            /// Each cubic curve matches the position and tangent of this curve at both of its ends.
+            /// This curve is subdivided until, at 7 evenly spaced samples, each cubic is no more than
+            /// `tolerance` away from the point of this curve at the same interpolation factor.
+            ///
+            /// Each returned curve starts exactly where the previous one ends.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # use vek::{RationalQuadraticBezier2, Vec2};
+            /// let arc = RationalQuadraticBezier2::from_circular_arc(Vec2::zero(), 1_f64, 0., 3.);
+            /// let cubics = arc.to_cubic_beziers(1e-6);
+            /// assert_eq!(cubics.first().unwrap().start, arc.start);
+            /// assert_eq!(cubics.last().unwrap().end, arc.end);
+            /// for cubic in cubics {
+            ///     for i in 0..=20 {
+            ///         let p = cubic.evaluate(i as f64 / 20.);
+            ///         assert!((p.magnitude() - 1.).abs() <= 1e-6);
+            ///     }
+            /// }
+            /// ```
+            #[cfg(feature="std")]
+            pub fn to_cubic_beziers(self, tolerance: T) -> Vec<$CubicBezier<T>> {
+                let mut out = Vec::new();
+                self.push_cubic_beziers(tolerance, 0, &mut out);
+                out
+            }
+            #[cfg(feature="std")]
+            fn push_cubic_beziers(self, tolerance: T, depth: u32, out: &mut Vec<$CubicBezier<T>>) {
+                const MAX_DEPTH: u32 = 16;
+                let three = T::one() + T::one() + T::one();
+                let cubic = $CubicBezier {
+                    start: self.start,
+                    ctrl0: self.start + self.evaluate_derivative(T::zero()) / three,
+                    ctrl1: self.end - self.evaluate_derivative(T::one()) / three,
+                    end: self.end,
+                };
+                if depth < MAX_DEPTH {
+                    let eighth = (three + three + T::one() + T::one()).recip();
+                    let mut t = eighth;
+                    while t < T::one() {
+                        if cubic.evaluate(t).distance(self.evaluate(t)) > tolerance {
+                            let [a, b] = self.split((T::one() + T::one()).recip());
+                            a.push_cubic_beziers(tolerance, depth + 1, out);
+                            b.push_cubic_beziers(tolerance, depth + 1, out);
+                            return;
+                        }
+                        t = t + eighth;
+                    }
+                }
+                out.push(cubic);
+            }
+        }
+
+        impl<T: Real> From<$QuadraticBezier<T>> for $RationalQuadraticBezier<T> {
+            /// Converts a polynomial curve into a rational one with all weights set to one.
+            fn from(curve: $QuadraticBezier<T>) -> Self {
+                Self::from_quadratic(curve, Vec3::one())
+            }
+        }
+    };
+}
+
+macro_rules! bezier_impl_patch3 {
+    ($BezierPatch:ident $CubicBezier:ident) => {
+        /// A bicubic Bézier surface patch in 3D, defined by a 4x4 grid of control points.
+        ///
+        /// The patch maps `uv`, with both coordinates from 0 to 1, to a point in space. Each row of
+        /// control points is a `CubicBezier3` along `u`, and the rows are blended along `v` by the
+        /// same cubic basis. The edges of the patch are the cubic curves of the boundary rows and
+        /// columns, so patches which share these (e.g the Utah teapot's) join without gaps.
+        ///
+        /// ```
+        /// # extern crate vek;
+        /// # #[macro_use] extern crate approx;
+        /// # use vek::{BezierPatch3, Vec2, Vec3};
+        /// # fn main() {
+        /// let patch = BezierPatch3::from_bilinear(
+        ///     Vec3::new(0_f64, 0., 0.), Vec3::new(2., 0., 0.),
+        ///     Vec3::new(0., 2., 0.), Vec3::new(2., 2., 1.),
+        /// );
+        /// assert_relative_eq!(patch.evaluate(Vec2::new(0.5, 0.5)), Vec3::new(1., 1., 0.25));
+        /// assert_relative_eq!(patch.evaluate(Vec2::new(1., 1.)), Vec3::new(2., 2., 1.));
+        /// assert!(patch.normal(Vec2::zero()).z > 0.);
+        /// # }
+        /// ```
+        #[derive(Debug, Default, Copy, Clone, Hash, PartialEq, Eq)]
+        #[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+        pub struct $BezierPatch<T> {
+            /// The control points, as `ctrl[v][u]`: each row is a curve along `u`, and the rows
+            /// follow each other along `v`. Only the four corners lie on the patch.
+            pub ctrl: [[Vec3<T>; 4]; 4],
+        }
+
+        impl<T: Real> $BezierPatch<T> {
+            /// Creates the flat (if its corners are coplanar) patch which bilinearly interpolates
+            /// the given corners, respectively at `uv` = (0, 0), (1, 0), (0, 1) and (1, 1).
+            pub fn from_bilinear(p00: Vec3<T>, p10: Vec3<T>, p01: Vec3<T>, p11: Vec3<T>) -> Self {
+                let three = T::one() + T::one() + T::one();
+                let mut ctrl = [[Vec3::zero(); 4]; 4];
+                for (j, row) in ctrl.iter_mut().enumerate() {
+                    let v = T::from(j).unwrap() / three;
+                    for (i, p) in row.iter_mut().enumerate() {
+                        let u = T::from(i).unwrap() / three;
+                        *p = (p00 + (p10 - p00) * u) * (T::one() - v) + (p01 + (p11 - p01) * u) * v;
+                    }
+                }
+                Self { ctrl }
+            }
+            /// Gets the curve along `u`, at the given value of `v`.
+            ///
+            /// For `v` equal to 0 or 1, this is the curve of the first or last row of control points.
+            pub fn u_curve(self, v: T) -> $CubicBezier<T> {
+                let p = |i: usize| self.column(i).evaluate(v);
+                $CubicBezier { start: p(0), ctrl0: p(1), ctrl1: p(2), end: p(3) }
+            }
+            /// Gets the curve along `v`, at the given value of `u`.
+            ///
+            /// For `u` equal to 0 or 1, this is the curve of the first or last column of control points.
+            pub fn v_curve(self, u: T) -> $CubicBezier<T> {
+                let p = |j: usize| self.row(j).evaluate(u);
+                $CubicBezier { start: p(0), ctrl0: p(1), ctrl1: p(2), end: p(3) }
+            }
+            /// Evaluates the position of the point of the patch at `uv`.
+            pub fn evaluate(self, uv: Vec2<T>) -> Vec3<T> {
+                self.v_curve(uv.x).evaluate(uv.y)
+            }
+            /// Evaluates the partial derivatives of the patch along `u` and `v`, at `uv`.
+            pub fn derivatives(self, uv: Vec2<T>) -> (Vec3<T>, Vec3<T>) {
+                (self.u_curve(uv.y).evaluate_derivative(uv.x), self.v_curve(uv.x).evaluate_derivative(uv.y))
+            }
+            /// Gets the unit normal of the patch at `uv`, i.e the normalized cross product of the
+            /// derivatives along `u` and `v`.
+            ///
+            /// Where the derivatives are parallel or zero, e.g at a corner collapsed into a single
+            /// point (like at the top of the Utah teapot's lid), the normal is evaluated a tiny step
+            /// towards the center of the patch instead. It is zero if the patch is degenerate there too.
+            pub fn normal(self, uv: Vec2<T>) -> Vec3<T> {
+                // The cross product, or `None` if the derivatives are parallel up to rounding.
+                let cross = |uv| {
+                    let (du, dv) = self.derivatives(uv);
+                    let n = du.cross(dv);
+                    let is_degenerate = n.magnitude_squared() <= du.magnitude_squared() * dv.magnitude_squared() * T::epsilon();
+                    if is_degenerate { None } else { Some(n) }
+                };
+                let half = T::one() / (T::one() + T::one());
+                cross(uv)
+                    .or_else(|| cross(uv + (Vec2::broadcast(half) - uv) * T::epsilon().sqrt()))
+                    .map_or(Vec3::zero(), Vec3::normalized)
+            }
+            /// Splits this patch into two at the given value of `u`, such that the first one spans
+            /// `u` from 0 to `t` and the second one from `t` to 1.
+            pub fn split_u(self, t: T) -> [Self; 2] {
+                let mut halves = [self; 2];
+                for j in 0..4 {
+                    let [first, second] = self.row(j).split(t);
+                    halves[0].ctrl[j] = [first.start, first.ctrl0, first.ctrl1, first.end];
+                    halves[1].ctrl[j] = [second.start, second.ctrl0, second.ctrl1, second.end];
+                }
+                halves
+            }
+            /// Splits this patch into two at the given value of `v`, such that the first one spans
+            /// `v` from 0 to `t` and the second one from `t` to 1.
+            pub fn split_v(self, t: T) -> [Self; 2] {
+                let [first, second] = self.transposed().split_u(t);
+                [first.transposed(), second.transposed()]
+            }
+            /// Gets the Axis-Aligned Bounding Box of the control points, which contains the patch,
+            /// though not tightly.
+            pub fn aabb(self) -> Aabb<T> {
+                let first = self.ctrl[0][0];
+                self.ctrl.iter().flat_map(|row| row.iter()).fold(Aabb { min: first, max: first }, |aabb, &p| {
+                    Aabb { min: Vec3::partial_min(aabb.min, p), max: Vec3::partial_max(aabb.max, p) }
+                })
+            }
+            /// Evaluates the patch over a regular grid of `resolution.w` by `resolution.h` quads,
+            /// respectively along `u` and `v`, giving vertex positions and triangles.
+            ///
+            /// Vertices are in row-major order, i.e the vertex at `uv = (i/w, j/h)` has index
+            /// `j*(w+1) + i`. Each quad is split into two triangles, which are counter-clockwise when
+            /// viewed from the side that `normal()` points to.
+            ///
+            /// # Panics
+            /// Panics if `resolution.w` or `resolution.h` is zero.
+            #[cfg(feature="std")]
+            pub fn tessellate(self, resolution: Extent2<usize>) -> (Vec<Vec3<T>>, Vec<[u32; 3]>) {
+                let (w, h) = (resolution.w, resolution.h);
+                assert!(w > 0 && h > 0, "the resolution of a tessellation must not be zero");
+                let (wt, ht) = (T::from(w).unwrap(), T::from(h).unwrap());
+                let mut positions = Vec::with_capacity((w + 1) * (h + 1));
+                for i in 0..=w {
+                    let curve = self.v_curve(T::from(i).unwrap() / wt);
+                    positions.extend((0..=h).map(|j| curve.evaluate(T::from(j).unwrap() / ht)));
+                }
+                // Evaluated by columns, to reuse each curve along `v`; transposed into rows here.
+                let positions = (0..(w + 1) * (h + 1)).map(|k| positions[(k % (w + 1)) * (h + 1) + k / (w + 1)]).collect();
+                let mut triangles = Vec::with_capacity(2 * w * h);
+                let index = |i: usize, j: usize| (j * (w + 1) + i) as u32;
+                for j in 0..h {
+                    for i in 0..w {
+                        let (a, b, c, d) = (index(i, j), index(i + 1, j), index(i, j + 1), index(i + 1, j + 1));
+                        triangles.push([a, b, d]);
+                        triangles.push([a, d, c]);
+                    }
+                }
+                (positions, triangles)
+            }
+
+            fn row(self, j: usize) -> $CubicBezier<T> {
+                let [start, ctrl0, ctrl1, end] = self.ctrl[j];
+                $CubicBezier { start, ctrl0, ctrl1, end }
+            }
+            fn column(self, i: usize) -> $CubicBezier<T> {
+                $CubicBezier { start: self.ctrl[0][i], ctrl0: self.ctrl[1][i], ctrl1: self.ctrl[2][i], end: self.ctrl[3][i] }
+            }
+            fn transposed(mut self) -> Self {
+                for j in 0..4 {
+                    for i in 0..j {
+                        let (a, b) = (self.ctrl[j][i], self.ctrl[i][j]);
+                        self.ctrl[j][i] = b;
+                        self.ctrl[i][j] = a;
+                    }
+                }
+                self
+            }
+        }
+    };
+}
+
+macro_rules! impl_all_beziers {
+    ($mod:ident) => {
+        use  crate::vec::$mod::{Vec3, Vec4, Vec2};
+        #[cfg(feature="std")]
+        use  crate::vec::$mod::Extent2;
+        use  crate::mat::$mod::row_major::{Mat2 as Rows2, Mat3 as Rows3, Mat4 as Rows4};
+        use  crate::mat::$mod::column_major::{Mat2 as Cols2, Mat3 as Cols3, Mat4 as Cols4};
+        use crate::geom::$mod::{LineSegment2, LineSegment3, Line2, Plane, Aabr, Aabb};
+        use self::Rows4 as Mat4;
+        use self::Rows3 as Mat3;
+        bezier_impl_quadratic!{
+            /// A 2D Bézier curve with one control point.
+            ///
+            /// 2x2 and 3x3 matrices can be multiplied by a Bézier curve to transform all of its points.
+            2 QuadraticBezier2 CubicBezier2 Vec2 LineSegment2
+        }
+        bezier_impl_2d_into_3d!{QuadraticBezier2 QuadraticBezier3}
+        bezier_impl_quadratic!{
+            /// A 3D Bézier curve with one control point.
+            ///
+            /// 3x3 and 4x4 matrices can be multiplied by a Bézier curve to transform all of its points.
+            3 QuadraticBezier3 CubicBezier3 Vec3 LineSegment3
+        }
+        bezier_impl_3d_into_2d!{QuadraticBezier3 QuadraticBezier2}
+        bezier_impl_cubic!{
+            /// A 2D Bézier curve with two control points.
+            ///
+            /// 2x2 and 3x3 matrices can be multiplied by a Bézier curve to transform all of its points.
+            2 QuadraticBezier2 CubicBezier2 Vec2 LineSegment2
+        }
+        bezier_impl_2d_into_3d!{CubicBezier2 CubicBezier3}
+        bezier_impl_cubic2_stroke!{CubicBezier2}
+        bezier_impl_cubic2_inspection!{CubicBezier2 LineSegment2}
+        bezier_impl_cubic2_intersections!{CubicBezier2 QuadraticBezier2 LineSegment2 Vec2}
+        bezier_impl_cubic2_continuity!{CubicBezier2}
+        bezier_impl_cubic2_arc_length!{CubicBezier2}
+        bezier_impl_rational_quadratic2!{RationalQuadraticBezier2 QuadraticBezier2 QuadraticBezier3 CubicBezier2}
+        bezier_impl_cubic!{
+            /// A 3D Bézier curve with two control points.
+            ///
+            /// 3x3 and 4x4 matrices can be multiplied by a Bézier curve to transform all of its points.
+            3 QuadraticBezier3 CubicBezier3 Vec3 LineSegment3
+        }
+        bezier_impl_3d_into_2d!{CubicBezier3 CubicBezier2}
+        bezier_impl_patch3!{BezierPatch3 CubicBezier3}
+    };
+}
+
+#[cfg(all(nightly, feature="repr_simd"))]
+pub mod repr_simd {
+    //! Bézier curve structs that use `#[repr(simd)]` vectors.
+    use super::*;
+    impl_all_beziers!{repr_simd}
+}
+pub mod repr_c {
+    //! Bézier curve structs that use `#[repr(C)]` vectors.
+    use super::*;
+    impl_all_beziers!{repr_c}
+}
+
+pub use self::repr_c::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vec::{Vec2, Vec3};
+
+    macro_rules! test {
+        ($Bezier:ident $bezier:ident $Vec:ident) => {
+            mod $bezier {
+                use super::*;
+                #[test] fn lerp_from_line_segment() {
+                    let count = 32;
+                    let t_iter = (0..(count+1)).into_iter().map(|i| i as f32 / (count as f32));
+                    let l = || $Vec::<f32>::unit_x() .. $Vec::<f32>::unit_y();
+                    let c = $Bezier::from(l());
+                    for t in t_iter {
+                        assert_relative_eq!(c.evaluate(t), Lerp::lerp_unclamped_precise(l().start, l().end, t))
+                    }
+                }
+            }
+        };
+    }
+    macro_rules! test_degree_elevation {
+        ($test:ident $CubicBezier:ident $quadratic:expr) => {
+            #[test] fn $test() {
+                let quadratic = $quadratic;
+                let cubic = quadratic.into_cubic();
+                assert_eq!(cubic, $CubicBezier::from(quadratic));
+                assert_eq!((cubic.start, cubic.end), (quadratic.start, quadratic.end));
+                for &t in &[0., 0.1, 0.25, 1. / 3., 0.5, 0.9, 1.] {
+                    assert_relative_eq!(cubic.evaluate(t), quadratic.evaluate(t), epsilon = 1e-12);
+                    assert_relative_eq!(cubic.evaluate_derivative(t), quadratic.evaluate_derivative(t), epsilon = 1e-12);
+                }
+            }
+        };
+    }
+    test_degree_elevation!{degree_elevation_2d CubicBezier2 QuadraticBezier2 {
+        start: Vec2::new(0_f64, 0.), ctrl: Vec2::new(3., 7.), end: Vec2::new(-2., 1.),
+    }}
+    test_degree_elevation!{degree_elevation_3d CubicBezier3 QuadraticBezier3 {
+        start: Vec3::new(1_f64, -2., 4.), ctrl: Vec3::new(3., 7., -1.), end: Vec3::new(-2., 1., 5.),
+    }}
+
+    macro_rules! test_segments {
+        ($test:ident $curve:expr) => {
+            #[test] fn $test() {
+                let curve = $curve;
+                assert_eq!(curve.subcurve(0., 1.), curve);
+                for &n in &[1, 2, 3, 7, 10] {
+                    let mut parts = curve.into_segments(n).enumerate().peekable();
+                    assert_eq!(curve.into_segments(n).count(), n);
+                    assert_eq!(curve.into_segments(n).next().unwrap().start, curve.start);
+                    assert_eq!(curve.into_segments(n).last().unwrap().end, curve.end);
+                    while let Some((i, part)) = parts.next() {
+                        if let Some((_, next)) = parts.peek() {
+                            assert_eq!(part.end, next.start);
+                        }
+                        // Evaluating the parts one after the other gives back the curve.
+                        for &s in &[0., 0.25, 0.5, 0.75, 1.] {
+                            let t = (i as f64 + s) / n as f64;
+                            assert_relative_eq!(part.evaluate(s), curve.evaluate(t), epsilon = 1e-12);
+                            assert_relative_eq!(part.evaluate_derivative(s) * n as f64, curve.evaluate_derivative(t), epsilon = 1e-11);
+                        }
+                    }
+                }
+                let [before, middle, after] = curve.split3(0.2, 0.7);
+                assert_eq!((before.start, before.end, middle.end, after.end), (curve.start, middle.start, after.start, curve.end));
+                let [first, rest] = curve.split(0.2);
+                assert_relative_eq!(before.into_vector(), first.into_vector(), epsilon = 1e-12);
+                assert_relative_eq!(middle.into_vector(), rest.split(0.5 / 0.8)[0].into_vector(), epsilon = 1e-12);
+                assert_relative_eq!(after.into_vector(), curve.split(0.7)[1].into_vector(), epsilon = 1e-12);
+                assert_relative_eq!(curve.subcurve(0.7, 0.2).into_vector(), middle.reversed().into_vector(), epsilon = 1e-12);
+            }
+        };
+    }
+    mod segments {
+        use super::*;
+        test_segments!{quadratic2 QuadraticBezier2 {
+            start: Vec2::new(0_f64, 0.), ctrl: Vec2::new(3., 7.), end: Vec2::new(-2., 1.),
+        }}
+        test_segments!{quadratic3 QuadraticBezier3 {
+            start: Vec3::new(1_f64, -2., 4.), ctrl: Vec3::new(3., 7., -1.), end: Vec3::new(-2., 1., 5.),
+        }}
+        test_segments!{cubic2 CubicBezier2 {
+            start: Vec2::new(0_f64, 0.), ctrl0: Vec2::new(0., 4.), ctrl1: Vec2::new(5., -3.), end: Vec2::new(4., 1.),
+        }}
+        test_segments!{cubic3 CubicBezier3 {
+            start: Vec3::new(1_f64, -2., 4.), ctrl0: Vec3::new(3., 7., -1.), ctrl1: Vec3::new(0., 0., 0.), end: Vec3::new(-2., 1., 5.),
+        }}
+    }
+
+    mod length {
+        use super::*;
+
+        // Sum of the lengths of many chords, which converges to the length slowly but surely.
+        fn reference<P: Copy, F: Fn(f64) -> P>(evaluate: F, distance: fn(P, P) -> f64) -> f64 {
+            let n = 1 << 20;
+            let mut previous = evaluate(0.);
+            (1..=n).map(|i| {
+                let p = evaluate(i as f64 / n as f64);
+                let d = distance(previous, p);
+                previous = p;
+                d
+            }).sum()
+        }
+        fn cubics() -> [CubicBezier2<f64>; 6] {
+            let c = |a: (f64, f64), b: (f64, f64), c: (f64, f64), d: (f64, f64)| CubicBezier2 {
+                start: Vec2::from(a), ctrl0: Vec2::from(b), ctrl1: Vec2::from(c), end: Vec2::from(d),
+            };
+            [
+                c((0., 0.), (0., 4.), (5., -3.), (4., 1.)),
+                // Cusp at t = 0.5.
+                c((0., 0.), (1., 1.), (0., 1.), (1., 0.)),
+                // Nearly cusps.
+                c((0., 0.), (1., 1.), (0., 1.001), (1., 0.)),
+                c((0., 0.), (1., 1.), (0., 1.000_001), (1., 0.)),
+                // Loop.
+                c((0., 0.), (3., 3.), (-2., 3.), (1., 0.)),
+                // Far away control points, i.e sharp turns.
+                c((0., 0.), (100., 0.), (-100., 1.), (1., 1.)),
+            ]
+        }
+
+        #[test] fn cubic() {
+            for curve in cubics().iter().copied() {
+                let expected = reference(|t| curve.evaluate(t), |a, b| a.distance(b));
+                let length = curve.length();
+                assert_relative_eq!(length, expected, max_relative = 1e-10);
+                let discretized = curve.length_by_discretization(1000);
+                assert!((discretized - expected).abs() > 100. * (length - expected).abs());
+                // The 3D version agrees.
+                let curve3 = CubicBezier3::from(curve);
+                assert_relative_eq!(curve3.length(), length, max_relative = 1e-12);
+            }
+            assert_eq!(CubicBezier2::from(Vec2::new(1_f64, 2.) .. Vec2::new(1., 2.)).length(), 0.);
+            let f32_curve = cubics()[4].into_vector().map(|p| p.as_::<f32>());
+            assert_relative_eq!(CubicBezier2::from(f32_curve).length() as f64, cubics()[4].length(), max_relative = 1e-5);
+        }
+        #[test] fn quadratic() {
+            // Goes to x = 1 and back, with a zero derivative at t = 0.5.
+            let back_and_forth = QuadraticBezier2 { start: Vec2::new(0_f64, 0.), ctrl: Vec2::new(2., 0.), end: Vec2::new(0., 0.) };
+            assert_relative_eq!(back_and_forth.length(), 2., max_relative = 1e-9);
+            let curves = [
+                QuadraticBezier3 { start: Vec3::new(1_f64, -2., 4.), ctrl: Vec3::new(3., 7., -1.), end: Vec3::new(-2., 1., 5.) },
+                QuadraticBezier3 { start: Vec3::new(0_f64, 0., 0.), ctrl: Vec3::new(100., 0., 0.), end: Vec3::new(0., 1., 1.) },
+            ];
+            for curve in curves.iter().copied() {
+                let expected = reference(|t| curve.evaluate(t), |a, b| a.distance(b));
+                assert_relative_eq!(curve.length(), expected, max_relative = 1e-9);
+                // Elevating the degree doesn't change the length.
+                assert_relative_eq!(curve.into_cubic().length(), curve.length(), max_relative = 1e-9);
+            }
+        }
+        #[cfg(feature="std")]
+        #[test] fn bounds() {
+            // Deterministic pseudo-random coordinates in [-10, 10), using a xorshift generator.
+            let mut state = 0x2545_f491_4f6c_dd1d_u64;
+            let mut random = move || {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                (state >> 11) as f64 / (1_u64 << 53) as f64 * 20. - 10.
+            };
+            let mut random3 = move || Vec3::new(random(), random(), random());
+            let mut cubics: Vec<_> = cubics().iter().map(|&c| CubicBezier3::from(c)).collect();
+            cubics.extend((0..50).map(|_| CubicBezier3 { start: random3(), ctrl0: random3(), ctrl1: random3(), end: random3() }));
+            for curve in cubics.iter().copied() {
+                let (lower, upper) = curve.length_bounds();
+                for &length in &[curve.length_by_discretization(1000), curve.length()] {
+                    assert!(lower <= length * (1. + 1e-12) && length <= upper * (1. + 1e-12), "{:?}", curve);
+                }
+                let quadratic = QuadraticBezier3 { start: curve.start, ctrl: curve.ctrl0, end: curve.end };
+                let (lower, upper) = quadratic.length_bounds();
+                for &length in &[quadratic.length_by_discretization(1000), quadratic.length()] {
+                    assert!(lower <= length * (1. + 1e-12) && length <= upper * (1. + 1e-12), "{:?}", quadratic);
+                }
+                // The 2D versions agree.
+                assert_eq!(CubicBezier2::from(curve).length_bounds(), CubicBezier3::from(CubicBezier2::from(curve)).length_bounds());
+            }
+            // Straight lines have equal bounds, whatever the spacing of their control points.
+            let line = CubicBezier2 { start: Vec2::new(0_f64, 0.), ctrl0: Vec2::new(3., 0.), ctrl1: Vec2::new(3.5, 0.), end: Vec2::new(4., 0.) };
+            assert_eq!(line.length_bounds(), (4., 4.));
+            assert!(line.is_approximately_linear(0.));
+            // Doubling back along a line is not linear.
+            let back_and_forth = QuadraticBezier2 { start: Vec2::new(0_f64, 0.), ctrl: Vec2::new(2., 0.), end: Vec2::new(0., 0.) };
+            assert_eq!(back_and_forth.length_bounds(), (0., 4.));
+            assert!(!back_and_forth.is_approximately_linear(1.));
+            // Subdividing tightens the bounds, until the parts are linear enough.
+            let curve = cubics[0];
+            let mut parts = 1;
+            while !(0..parts).all(|i| curve.subcurve(i as f64 / parts as f64, (i + 1) as f64 / parts as f64).is_approximately_linear(1e-3)) {
+                parts *= 2;
+            }
+            assert!(parts > 1 && parts < 256, "{}", parts);
+        }
+        #[cfg(feature="std")]
+        #[test] fn at_length() {
+            // Moving at constant speed along each curve covers equal arc lengths in equal times.
+            let n = 200;
+            for curve in cubics().iter().copied() {
+                let length = curve.length();
+                let ts: Vec<_> = (0..=n).map(|i| curve.t_at_length(length * i as f64 / n as f64, 1e-10)).collect();
+                assert_eq!((ts[0], curve.t_at_length(length, 1e-10)), (0., 1.));
+                for w in ts.windows(2) {
+                    assert!(w[0] <= w[1]);
+                    assert_relative_eq!(curve.subcurve(w[0], w[1]).length(), length / n as f64, epsilon = 1e-9);
+                }
+                // Successive points are as far apart as the curve lets them be, i.e close to
+                // the arc length between them where the curve is smooth.
+                let points: Vec<_> = (0..=n).map(|i| curve.evaluate_at_length(length * i as f64 / n as f64)).collect();
+                for w in points.windows(2) {
+                    assert!(w[0].distance(w[1]) <= length / n as f64 * (1. + 1e-9));
+                }
+                // The tolerance is on the arc length.
+                for &epsilon in &[1e-2, 1e-5] {
+                    let t = curve.t_at_length(length / 3., epsilon);
+                    assert_relative_eq!(curve.subcurve(0., t).length(), length / 3., epsilon = epsilon);
+                }
+            }
+            // A smooth curve, on which the points are equidistant.
+            let curve = QuadraticBezier3 { start: Vec3::new(1_f64, -2., 4.), ctrl: Vec3::new(3., 7., -1.), end: Vec3::new(-2., 1., 5.) };
+            let step = curve.length() / n as f64;
+            let points: Vec<_> = (0..=n).map(|i| curve.evaluate_at_length(step * i as f64)).collect();
+            for w in points.windows(2) {
+                assert_relative_eq!(w[0].distance(w[1]), step, max_relative = 1e-3);
+            }
+            assert_eq!(curve.evaluate_at_length(-1.), curve.start);
+            assert_eq!(curve.evaluate_at_length(1e9), curve.end);
+            // Degenerate curves have a single point.
+            let point = CubicBezier2::from(Vec2::new(1_f64, 2.) .. Vec2::new(1., 2.));
+            assert_eq!(point.t_at_length(1., 1e-9), 0.);
+        }
+        #[cfg(feature="std")]
+        #[test] fn sample_by_distance() {
+            for curve in cubics().iter().copied() {
+                let length = curve.length();
+                let spacing = length / 7.3;
+                let points: Vec<_> = curve.sample_by_distance(spacing, false).collect();
+                assert_eq!(points.len(), 8);
+                assert_eq!(points[0], curve.start);
+                for (i, &p) in points.iter().enumerate() {
+                    assert_relative_eq!(p, curve.evaluate_at_length(spacing * i as f64), epsilon = 1e-9);
+                }
+                let with_end: Vec<_> = curve.sample_by_distance(spacing, true).collect();
+                assert_eq!(with_end[..8], points[..]);
+                assert_eq!(with_end[8..], [curve.end]);
+            }
+            // 0.1 isn't exact, so neither are its multiples, yet the last interval isn't a sliver.
+            let line = CubicBezier2::from(Vec2::new(0_f64, 0.) .. Vec2::new(6., 8.));
+            for &include_end in &[false, true] {
+                let points: Vec<_> = line.sample_by_distance(0.1, include_end).collect();
+                assert_eq!(points.len(), 101);
+                assert_eq!(points[100], line.end);
+                for w in points.windows(2) {
+                    assert_relative_eq!(w[0].distance(w[1]), 0.1, epsilon = 1e-9);
+                }
+            }
+            // Shorter than the spacing.
+            assert_eq!(line.sample_by_distance(11., false).collect::<Vec<_>>(), [line.start]);
+            assert_eq!(line.sample_by_distance(11., true).collect::<Vec<_>>(), [line.start, line.end]);
+            let point = CubicBezier2::from(Vec2::new(1_f64, 2.) .. Vec2::new(1., 2.));
+            assert_eq!(point.sample_by_distance(1., true).collect::<Vec<_>>(), [point.start]);
+        }
+        #[test] fn sample_by_distance_tiny_spacing() {
+            let line = CubicBezier2::from(Vec2::new(0_f64, 0.) .. Vec2::new(6., 8.));
+            // The number of samples overflows a `usize`, then the ratio of length to spacing is infinite.
+            for &spacing in &[f64::MIN_POSITIVE, 5e-324] {
+                let mut points = line.sample_by_distance(spacing, true);
+                assert_eq!(points.size_hint().0, usize::MAX);
+                assert_eq!(points.next(), Some(line.start));
+                assert_relative_eq!(points.next().unwrap(), line.start, epsilon = 1e-300);
+            }
+        }
+    }
+
+    test!{QuadraticBezier2 quadratic2 Vec2}
+    test!{QuadraticBezier3 quadratic3 Vec3}
+    test!{CubicBezier2 cubic2 Vec2}
+    test!{CubicBezier3 cubic3 Vec3}
+
+    mod bounds {
+        #[cfg(feature="std")]
+        use alloc::vec::Vec;
+        use crate::vec::repr_c::{Vec2, Vec3};
+        use crate::geom::repr_c::{Aabr, Aabb};
+        use crate::bezier::repr_c::{QuadraticBezier2, QuadraticBezier3, CubicBezier2, CubicBezier3};
+
+        // Bounds of densely sampled points, which the tight bounds must match closely.
+        fn sampled_aabr<F: Fn(f64) -> Vec2<f64>>(evaluate: F) -> Aabr<f64> {
+            let n = 100_000;
+            (1..=n).fold(Aabr::new_empty(evaluate(0.)), |aabr, i| aabr.expanded_to_contain_point(evaluate(i as f64 / n as f64)))
+        }
+
+        #[test] fn cubic_extrema_inside() {
+            let c = CubicBezier2 {
+                start: Vec2::new(0., 0.), ctrl0: Vec2::new(-3., 5.), ctrl1: Vec2::new(7., -4.), end: Vec2::new(2., 1.),
+            };
+            let aabr = c.aabr();
+            let sampled = sampled_aabr(|t| c.evaluate(t));
+            assert_relative_eq!(aabr.min, sampled.min, epsilon = 1e-6);
+            assert_relative_eq!(aabr.max, sampled.max, epsilon = 1e-6);
+            // Strictly tighter than the control points' bounds.
+            assert!(aabr.min.x > -3. && aabr.max.x < 7. && aabr.min.y > -4. && aabr.max.y < 5.);
+        }
+        #[test] fn quadratic_extrema_inside() {
+            let c = QuadraticBezier2 { start: Vec2::new(0., 0.), ctrl: Vec2::new(1., 10.), end: Vec2::new(2., 0.) };
+            assert_relative_eq!(c.aabr().max, Vec2::new(2., 5.));
+            assert_relative_eq!(c.aabr().min, Vec2::zero());
+        }
+        // Roots of the derivative outside of [0, 1] are ignored.
+        #[test] fn extrema_outside() {
+            let c = CubicBezier2 {
+                start: Vec2::new(0., 0.), ctrl0: Vec2::new(1., 1.), ctrl1: Vec2::new(2., 1.9), end: Vec2::new(3., 2.7),
+            };
+            assert_relative_eq!(c.aabr().min, Vec2::new(0., 0.));
+            assert_relative_eq!(c.aabr().max, Vec2::new(3., 2.7));
+            // x' is linear and its root is at t = 1.5.
+            let c = CubicBezier2 {
+                start: Vec2::new(0., 0.), ctrl0: Vec2::new(1., 0.), ctrl1: Vec2::new(5. / 3., 1.), end: Vec2::new(2., 1.),
+            };
+            let sampled = sampled_aabr(|t| c.evaluate(t));
+            assert_relative_eq!(c.aabr().min, sampled.min, epsilon = 1e-6);
+            assert_relative_eq!(c.aabr().max, sampled.max, epsilon = 1e-6);
+            let c = QuadraticBezier2 { start: Vec2::new(0., 0.), ctrl: Vec2::new(2., 1.), end: Vec2::new(3., 3.) };
+            assert_relative_eq!(c.aabr().max, Vec2::new(3., 3.));
+        }
+        #[test] fn collinear() {
+            // Control points beyond the end: the curve overshoots then comes back.
+            let c = QuadraticBezier2 { start: Vec2::new(0., 0.), ctrl: Vec2::new(2., 4.), end: Vec2::new(1., 2.) };
+            assert_relative_eq!(c.aabr().min, Vec2::zero());
+            assert_relative_eq!(c.aabr().max, Vec2::new(4. / 3., 8. / 3.));
+            let c = CubicBezier2 {
+                start: Vec2::new(1., 1.), ctrl0: Vec2::new(-1., -1.), ctrl1: Vec2::new(3., 3.), end: Vec2::new(2., 2.),
+            };
+            let sampled = sampled_aabr(|t| c.evaluate(t));
+            assert_relative_eq!(c.aabr().min, sampled.min, epsilon = 1e-6);
+            assert_relative_eq!(c.aabr().max, sampled.max, epsilon = 1e-6);
+            // Evenly spaced control points: a straight segment.
+            let c = CubicBezier2::from(Vec2::new(3., -1.) .. Vec2::new(-2., 4.));
+            assert_eq!(c.aabr(), Aabr { min: Vec2::new(-2., -1.), max: Vec2::new(3., 4.) });
+            // All points equal.
+            let c = CubicBezier2 { start: Vec2::<f64>::one(), ctrl0: Vec2::one(), ctrl1: Vec2::one(), end: Vec2::one() };
+            assert_eq!(c.aabr(), Aabr { min: Vec2::one(), max: Vec2::one() });
+        }
+        #[cfg(feature="std")]
+        #[test] fn extrema() {
+            let c = CubicBezier2 {
+                start: Vec2::new(0., 0.), ctrl0: Vec2::new(-3., 5.), ctrl1: Vec2::new(7., -4.), end: Vec2::new(2., 1.),
+            };
+            for &(t0, t1) in &[c.x_extrema(), c.y_extrema()] {
+                let (t0, t1) = (t0.unwrap(), t1.unwrap());
+                assert!(0. < t0 && t0 < t1 && t1 < 1.);
+            }
+            let (x0, x1) = c.x_extrema();
+            assert_relative_eq!(c.evaluate_derivative(x0.unwrap()).x, 0., epsilon = 1e-12);
+            assert_relative_eq!(c.evaluate_derivative(x1.unwrap()).x, 0., epsilon = 1e-12);
+            // Splitting at all extrema yields monotonic pieces.
+            let (y0, y1) = c.y_extrema();
+            let mut ts: Vec<f64> = [0., x0.unwrap(), x1.unwrap(), y0.unwrap(), y1.unwrap(), 1.].to_vec();
+            ts.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+            for w in ts.windows(2) {
+                let points: Vec<_> = (0..=100).map(|i| c.evaluate(w[0] + (w[1] - w[0]) * i as f64 / 100.)).collect();
+                let steps: Vec<_> = points.windows(2).map(|p| p[1] - p[0]).collect();
+                assert!(steps.iter().all(|d| d.x >= -1e-12) || steps.iter().all(|d| d.x <= 1e-12));
+                assert!(steps.iter().all(|d| d.y >= -1e-12) || steps.iter().all(|d| d.y <= 1e-12));
+            }
+        }
+        #[test] fn extrema_edge_cases() {
+            // Roots exactly at 0 and 1 are excluded.
+            let c = CubicBezier2 {
+                start: Vec2::new(0., 0.), ctrl0: Vec2::new(0., 0.), ctrl1: Vec2::new(4., 1.), end: Vec2::new(4., 1.),
+            };
+            assert_eq!(c.x_extrema(), (None, None));
+            assert_eq!(c.y_extrema(), (None, None));
+            // Elevated quadratic curves have linear derivatives.
+            let q = QuadraticBezier2 { start: Vec2::new(0., 0.), ctrl: Vec2::new(4., 2.), end: Vec2::new(1., 3.) };
+            assert_relative_eq!(q.x_extremum().unwrap(), 4. / 7.);
+            assert_eq!(q.y_extremum(), None);
+            let (t, none) = q.into_cubic().x_extrema();
+            assert_relative_eq!(t.unwrap(), 4. / 7., epsilon = 1e-12);
+            assert_eq!(none, None);
+            assert_eq!(q.into_cubic().y_extrema(), (None, None));
+            // Constant axis.
+            let c = CubicBezier2::from(Vec2::new(1., 0.) .. Vec2::new(1., 5.));
+            assert_eq!(c.x_extrema(), (None, None));
+            assert_eq!(QuadraticBezier2::from(Vec2::new(1., 0.) .. Vec2::new(1., 5.)).x_extremum(), None);
+            // Double root, where the curve stays monotonic: x = (2t - 1)³.
+            let c = CubicBezier2 {
+                start: Vec2::new(-1., 0.), ctrl0: Vec2::new(1., 0.), ctrl1: Vec2::new(-1., 0.), end: Vec2::new(1., 0.),
+            };
+            assert_eq!(c.x_extrema(), (Some(0.5), None));
+            // Only one root inside: x' is proportional to (t - 0.5)(t - 1.5).
+            let c = CubicBezier2 {
+                start: Vec2::new(0., 0.), ctrl0: Vec2::new(0.75, 0.), ctrl1: Vec2::new(0.5, 0.), end: Vec2::new(0.25, 0.),
+            };
+            assert_eq!(c.x_extrema(), (Some(0.5), None));
+        }
+        #[test] fn three_d() {
+            let c = CubicBezier3 {
+                start: Vec3::new(0., 0., 0.), ctrl0: Vec3::new(0., 4., -4.), ctrl1: Vec3::new(4., 4., 4.), end: Vec3::new(4., 0., 0.),
+            };
+            let aabb = c.aabb();
+            let n = 100_000;
+            let sampled = (1..=n).fold(Aabb::new_empty(c.start), |aabb, i| aabb.expanded_to_contain_point(c.evaluate(i as f64 / n as f64)));
+            assert_relative_eq!(aabb.min, sampled.min, epsilon = 1e-6);
+            assert_relative_eq!(aabb.max, sampled.max, epsilon = 1e-6);
+            let c = QuadraticBezier3 { start: Vec3::zero(), ctrl: Vec3::new(1., -2., 6.), end: Vec3::new(2., 0., 0.) };
+            assert_relative_eq!(c.aabb().min, Vec3::new(0., -1., 0.));
+            assert_relative_eq!(c.aabb().max, Vec3::new(2., 0., 3.));
+        }
+    }
+
+    mod curvature {
+        use crate::vec::repr_c::{Vec2, Vec3};
+        use crate::mat::repr_c::Mat3;
+        use crate::bezier::repr_c::{QuadraticBezier2, QuadraticBezier3, CubicBezier2, CubicBezier3};
+
+        #[test] fn circle() {
+            // The cubic approximation of a circle has a nearly constant curvature.
+            let c = CubicBezier2::<f64>::unit_quarter_circle();
+            for i in 0..=10 {
+                let t = i as f64 / 10.;
+                assert_relative_eq!(c.curvature(t).unwrap(), 1., epsilon = 0.025);
+                let scaled = CubicBezier2 { start: c.start * 4., ctrl0: c.ctrl0 * 4., ctrl1: c.ctrl1 * 4., end: c.end * 4. };
+                assert_relative_eq!(scaled.curvature(t).unwrap(), c.curvature(t).unwrap() / 4., epsilon = 1e-12);
+                // Clockwise turns have a negative curvature.
+                assert_relative_eq!(c.reversed().curvature(1. - t).unwrap(), -c.curvature(t).unwrap(), epsilon = 1e-12);
+            }
+        }
+        #[test] fn parabola() {
+            // y = x², whose curvature is 2 / (1 + 4x²)^(3/2).
+            let q = QuadraticBezier2 { start: Vec2::new(-1_f64, 1.), ctrl: Vec2::new(0., -1.), end: Vec2::new(1., 1.) };
+            for i in 0..=10 {
+                let t = i as f64 / 10.;
+                let x = 2. * t - 1.;
+                let expected = 2. / (1. + 4. * x * x).powf(1.5);
+                assert_relative_eq!(q.curvature(t).unwrap(), expected, epsilon = 1e-12);
+                assert_relative_eq!(q.into_cubic().curvature(t).unwrap(), expected, epsilon = 1e-12);
+                assert_relative_eq!(q.into_3d().curvature(t).unwrap(), expected, epsilon = 1e-12);
+                assert_relative_eq!(q.into_cubic().into_3d().curvature(t).unwrap(), expected, epsilon = 1e-12);
+            }
+        }
+        #[test] fn straight() {
+            let c = CubicBezier2::from(Vec2::new(1_f64, 2.) .. Vec2::new(-3., 4.));
+            assert_relative_eq!(c.curvature(0.3).unwrap(), 0., epsilon = 1e-12);
+            let c = CubicBezier3::from(Vec3::new(1_f64, 2., 3.) .. Vec3::new(-3., 4., 0.));
+            assert_relative_eq!(c.curvature(0.7).unwrap(), 0., epsilon = 1e-12);
+        }
+        #[test] fn three_d_matches_2d() {
+            let c = CubicBezier2 {
+                start: Vec2::new(0_f64, 0.), ctrl0: Vec2::new(1., 2.), ctrl1: Vec2::new(3., -1.), end: Vec2::new(4., 1.),
+            };
+            let rotation = Mat3::rotation_3d(0.7, Vec3::new(1., 2., 3.));
+            let c3 = c.into_3d();
+            let rotated = CubicBezier3 { start: rotation * c3.start, ctrl0: rotation * c3.ctrl0, ctrl1: rotation * c3.ctrl1, end: rotation * c3.end };
+            for i in 0..=10 {
+                let t = i as f64 / 10.;
+                assert_relative_eq!(rotated.curvature(t).unwrap(), c.curvature(t).unwrap().abs(), epsilon = 1e-9);
+            }
+        }
+        #[test] fn cusp() {
+            let c = CubicBezier2 {
+                start: Vec2::new(0_f64, 0.), ctrl0: Vec2::new(1., 1.), ctrl1: Vec2::new(0., 1.), end: Vec2::new(1., 0.),
+            };
+            assert_eq!(c.curvature(0.5), None);
+            assert_eq!(c.into_3d().curvature(0.5), None);
+            assert!(c.curvature(0.4).unwrap().is_finite());
+            // Coincident control points.
+            let q = QuadraticBezier3::from(Vec3::<f64>::one() .. Vec3::one());
+            assert_eq!(q.curvature(0.), None);
+        }
+    }
+
+    #[cfg(feature="std")]
+    mod arc_length {
+        use alloc::vec::Vec;
+        use crate::vec::repr_c::Vec2;
+        use crate::bezier::repr_c::CubicBezier2;
+
+        // A unit straight segment along X, then a quarter circle turning counter-clockwise,
+        // so the tangent jumps from +X to +Y at the join.
+        fn path() -> [CubicBezier2<f64>; 2] {
+            [CubicBezier2::from(Vec2::zero() .. Vec2::unit_x()), CubicBezier2::unit_quarter_circle()]
+        }
+        fn quarter_circle_length() -> f64 {
+            let c = CubicBezier2::<f64>::unit_quarter_circle();
+            let n = 100_000;
+            (1..=n).map(|i| (c.evaluate(i as f64 / n as f64) - c.evaluate((i - 1) as f64 / n as f64)).magnitude()).sum()
+        }
+        // The exact point and tangent at arc length `s` along the ideal path.
+        fn ideal_frame(s: f64) -> (Vec2<f64>, Vec2<f64>) {
+            if s < 1. {
+                (Vec2::new(s, 0.), Vec2::unit_x())
+            } else {
+                let angle = (s - 1.) * ::std::f64::consts::FRAC_PI_2 / quarter_circle_length();
+                (Vec2::new(angle.cos(), angle.sin()), Vec2::new(-angle.sin(), angle.cos()))
+            }
+        }
+
+        #[test] fn evenly_spaced() {
+            let total = 1. + quarter_circle_length();
+            let count = 41;
+            let frames = CubicBezier2::evenly_spaced_frames_of_path(&path(), count);
+            assert_eq!(frames.len(), count);
+            for (i, &(p, tangent)) in frames.iter().enumerate() {
+                let (ideal_p, ideal_tangent) = ideal_frame(total * i as f64 / (count - 1) as f64);
+                // The cubic only approximates the circle, hence the tolerance.
+                assert_relative_eq!(p, ideal_p, epsilon = 1e-3);
+                assert_relative_eq!(tangent, ideal_tangent, epsilon = 2e-3);
+                assert_relative_eq!(tangent.magnitude(), 1., epsilon = 1e-12);
+            }
+            // The arc length between consecutive points is uniform: chords on the straight part
+            // match exactly, and chords on the arc match each other.
+            let step = total / (count - 1) as f64;
+            let chords: Vec<_> = frames.windows(2).map(|w| (w[1].0 - w[0].0).magnitude()).collect();
+            for (i, &chord) in chords.iter().enumerate() {
+                let (s0, s1) = (step * i as f64, step * (i + 1) as f64);
+                if s1 <= 1. {
+                    assert_relative_eq!(chord, step, epsilon = 1e-9);
+                } else if s0 >= 1. {
+                    assert_relative_eq!(chord, 2. * (step / 2.).sin(), epsilon = 1e-4);
+                }
+            }
+            assert_eq!(frames[0], (Vec2::zero(), Vec2::unit_x()));
+            assert_relative_eq!(frames[count - 1].0, Vec2::unit_y(), epsilon = 1e-12);
+        }
+        #[test] fn segment_boundaries() {
+            let path = path();
+            let frame = |s| CubicBezier2::frame_at_length_of_path(&path, s).unwrap();
+            let (p, tangent) = frame(1. - 1e-9);
+            assert_relative_eq!(p, Vec2::new(1., 0.), epsilon = 1e-8);
+            assert_relative_eq!(tangent, Vec2::unit_x());
+            // The join is owned by the quarter circle.
+            let (p, tangent) = frame(1.);
+            assert_relative_eq!(p, Vec2::new(1., 0.), epsilon = 1e-12);
+            assert_relative_eq!(tangent, Vec2::unit_y(), epsilon = 1e-12);
+            // Lengths are clamped to the path.
+            assert_eq!(frame(-1.), (Vec2::zero(), Vec2::unit_x()));
+            let (p, tangent) = frame(10.);
+            assert_relative_eq!(p, Vec2::unit_y(), epsilon = 1e-12);
+            assert_relative_eq!(tangent, -Vec2::unit_x(), epsilon = 1e-12);
+            // Single curves.
+            let (p, _) = path[1].frame_at_length(quarter_circle_length() / 2.);
+            assert_relative_eq!(p, Vec2::broadcast(0.5_f64.sqrt()), epsilon = 1e-6);
+            assert_eq!(path[0].evenly_spaced_frames(1), [(Vec2::zero(), Vec2::unit_x())]);
+        }
+        #[test] fn degenerate() {
+            assert_eq!(CubicBezier2::<f64>::frame_at_length_of_path(&[], 1.), None);
+            assert!(CubicBezier2::<f64>::evenly_spaced_frames_of_path(&[], 4).is_empty());
+            assert!(CubicBezier2::<f64>::unit_quarter_circle().evenly_spaced_frames(0).is_empty());
+            // Zero-length curves own no point.
+            let point = CubicBezier2::from(Vec2::unit_x() .. Vec2::unit_x());
+            let path = [CubicBezier2::from(Vec2::zero() .. Vec2::unit_x()), point, CubicBezier2::unit_quarter_circle()];
+            let (_, tangent) = CubicBezier2::frame_at_length_of_path(&path, 1.).unwrap();
+            assert_relative_eq!(tangent, Vec2::unit_y(), epsilon = 1e-12);
+            // Coincident control points at the ends still give a tangent there.
+            let c = CubicBezier2 { start: Vec2::zero(), ctrl0: Vec2::zero(), ctrl1: Vec2::new(3., 0.), end: Vec2::new(3., 0.) };
+            let frames = c.evenly_spaced_frames(4);
+            for (i, (p, tangent)) in frames.into_iter().enumerate() {
+                assert_relative_eq!(p, Vec2::new(i as f64, 0.), epsilon = 1e-9);
+                assert_relative_eq!(tangent, Vec2::unit_x());
+            }
+        }
+    }
+
+    #[cfg(feature="std")]
+    mod project_point {
+        use crate::vec::repr_c::{Vec2, Vec3};
+        use crate::bezier::repr_c::{QuadraticBezier3, CubicBezier2};
+
+        fn arch() -> CubicBezier2<f64> {
+            CubicBezier2 { start: Vec2::new(0., 0.), ctrl0: Vec2::new(0., 4.), ctrl1: Vec2::new(4., 4.), end: Vec2::new(4., 0.) }
+        }
+
+        #[test] fn point_on_curve() {
+            let quadratic = QuadraticBezier3 { start: Vec3::new(1_f64, -2., 4.), ctrl: Vec3::new(3., 7., -1.), end: Vec3::new(-2., 1., 5.) };
+            for i in 0..=50 {
+                let t = i as f64 / 50.;
+                let (found_t, found) = arch().project_point(arch().evaluate(t), 1e-10);
+                assert_relative_eq!(found_t, t, epsilon = 1e-9);
+                assert_relative_eq!(found, arch().evaluate(t), epsilon = 1e-9);
+                let (found_t, found) = quadratic.project_point(quadratic.evaluate(t), 1e-10);
+                assert_relative_eq!(found_t, t, epsilon = 1e-9);
+                assert_relative_eq!(found, quadratic.evaluate(t), epsilon = 1e-9);
+            }
+        }
+        #[test] fn matches_brute_force() {
+            let curve = arch();
+            let n = 100_000;
+            for &p in &[Vec2::new(1., 1.), Vec2::new(3., 5.), Vec2::new(-1., 2.), Vec2::new(2.5, -3.)] {
+                let brute = (0..=n).map(|i| curve.evaluate(i as f64 / n as f64).distance(p)).fold(f64::MAX, f64::min);
+                let (_, found) = curve.project_point(p, 1e-10);
+                assert_relative_eq!(found.distance(p), brute, epsilon = 1e-8);
+                assert!(found.distance(p) <= brute);
+            }
+        }
+        #[test] fn far_away_points_give_the_nearest_end() {
+            let curve = arch();
+            assert_eq!(curve.project_point(Vec2::new(-100., -3.), 1e-9), (0., curve.start));
+            assert_eq!(curve.project_point(Vec2::new(100., -3.), 1e-9), (1., curve.end));
+            assert_eq!(curve.project_point_with_steps(Vec2::new(0., -1e6), 3, 1e-9), (0., curve.start));
+            // Straight above, the top of the arch is closer than either end.
+            let (t, _) = curve.project_point(Vec2::new(2., 1e6), 1e-9);
+            assert_relative_eq!(t, 0.5, epsilon = 1e-8);
+        }
+        #[test] fn symmetric_curve_gives_symmetric_results() {
+            // The arch is symmetric about x = 2.
+            let curve = arch();
+            for &p in &[Vec2::new(0.5, 1.), Vec2::new(1., 3.5), Vec2::new(-2., 7.), Vec2::new(1.9, -0.5)] {
+                let mirrored = Vec2::new(4. - p.x, p.y);
+                let (t, found) = curve.project_point(p, 1e-10);
+                let (mirrored_t, mirrored_found) = curve.project_point(mirrored, 1e-10);
+                assert_relative_eq!(mirrored_t, 1. - t, epsilon = 1e-8);
+                assert_relative_eq!(mirrored_found, Vec2::new(4. - found.x, found.y), epsilon = 1e-8);
+            }
+        }
+        #[test] fn steps() {
+            // A loop, which comes close to `p` at several places; in f32.
+            let curve = CubicBezier2 { start: Vec2::new(0_f32, 0.), ctrl0: Vec2::new(10., 10.), ctrl1: Vec2::new(-6., 10.), end: Vec2::new(4., 0.) };
+            let p = curve.evaluate(0.8) + Vec2::new(0., -0.01);
+            let (t, _) = curve.project_point_with_steps(p, 64, 1e-6);
+            assert_relative_eq!(t, 0.8, epsilon = 1e-3);
+            assert_eq!(curve.binary_search_point_by_steps(p, 64, 1e-6), curve.project_point_with_steps(p, 64, 1e-6));
+        }
+    }
+    mod distance_to_segment {
+        use crate::vec::repr_c::{Vec2, Vec3};
+        use crate::geom::repr_c::{LineSegment2, Ray};
+        use crate::bezier::repr_c::{CubicBezier2, CubicBezier3};
+
+        fn brute_force<F: Fn(f64) -> f64>(dist_at: F) -> f64 {
+            let n = 200_000;
+            (0..=n).map(|i| dist_at(i as f64 / n as f64)).fold(f64::MAX, f64::min)
+        }
+
+        // A curve which loops back on itself, passing twice near the segment.
+        fn loop_curve() -> CubicBezier2<f64> {
+            CubicBezier2 {
+                start: Vec2::new(0., 0.),
+                ctrl0: Vec2::new(10., 10.),
+                ctrl1: Vec2::new(-6., 10.),
+                end: Vec2::new(4., 0.),
+            }
+        }
+
+        #[test]
+        fn matches_brute_force() {
+            let curve = loop_curve();
+            let segments = [
+                LineSegment2 { start: Vec2::new(-5., 8.), end: Vec2::new(10., 8.) },
+                LineSegment2 { start: Vec2::new(1.9, 3.), end: Vec2::new(2.1, 3.2) },
+                LineSegment2 { start: Vec2::new(-3., -1.), end: Vec2::new(7., -1.5) },
+                LineSegment2 { start: Vec2::new(12., 0.), end: Vec2::new(12., 12.) },
+                LineSegment2 { start: Vec2::new(2., 20.), end: Vec2::new(2., 20.) },
+                // Crosses the curve.
+                LineSegment2 { start: Vec2::new(-5., 2.), end: Vec2::new(10., 2.) },
+            ];
+            for tolerance in [1e-3, 1e-6].iter().cloned() {
+                for seg in segments.iter().cloned() {
+                    let expected = brute_force(|t| seg.distance_to_point(curve.evaluate(t)));
+                    let d = curve.distance_to_segment(seg, tolerance);
+                    // Dense sampling is itself only an upper bound.
+                    assert!(d >= expected - 1e-4 && d <= expected + tolerance, "{} vs {}", d, expected);
+                }
+            }
+        }
+        #[test]
+        fn degenerate_curve() {
+            let p = Vec2::new(1., 1.);
+            let curve = CubicBezier2 { start: p, ctrl0: p, ctrl1: p, end: p };
+            let seg = LineSegment2 { start: Vec2::new(0., 0.), end: Vec2::new(4., 0.) };
+            assert_relative_eq!(curve.distance_to_segment(seg, 0.), 1.);
+        }
+        #[test]
+        fn ray_matches_brute_force() {
+            let curve = CubicBezier3 {
+                start: Vec3::new(0., 0., 0.),
+                ctrl0: Vec3::new(10., 10., 5.),
+                ctrl1: Vec3::new(-6., 10., -3.),
+                end: Vec3::new(4., 0., 2.),
+            };
+            let rays = [
+                Ray::new(Vec3::new(2., 5., -10.), Vec3::unit_z()),
+                Ray::new(Vec3::new(-4., 0., 0.), Vec3::new(1., 1., 0.).normalized()),
+                Ray::new(Vec3::new(20., 3., 3.), Vec3::unit_x()),
+            ];
+            for ray in rays.iter().cloned() {
+                let expected = brute_force(|t| ray.distance_to_point(curve.evaluate(t)));
+                let d = ray.distance_to_curve(curve, 1e-5);
+                assert!(d >= expected - 1e-4 && d <= expected + 1e-5, "{} vs {}", d, expected);
+            }
+        }
+    }
+    mod patch {
+        use crate::vec::repr_c::{Vec2, Vec3};
+        use crate::bezier::repr_c::{BezierPatch3, CubicBezier3};
+
+        // A curved, non-symmetric patch.
+        fn patch() -> BezierPatch3<f64> {
+            let mut ctrl = [[Vec3::zero(); 4]; 4];
+            for (j, row) in ctrl.iter_mut().enumerate() {
+                for (i, p) in row.iter_mut().enumerate() {
+                    let (x, y) = (i as f64, j as f64);
+                    *p = Vec3::new(x + 0.3 * y * y, y - 0.2 * x, (x - 1.5) * (y - 1.) + 0.5 * x * x * y / 3.);
+                }
+            }
+            BezierPatch3 { ctrl }
+        }
+        fn uvs() -> impl Iterator<Item=Vec2<f64>> {
+            (0..=6).flat_map(|j| (0..=6).map(move |i| Vec2::new(i as f64 / 6., j as f64 / 6.)))
+        }
+
+        #[test]
+        fn boundaries_are_cubic_curves() {
+            let patch = patch();
+            let c = patch.ctrl;
+            let edges = [
+                (CubicBezier3 { start: c[0][0], ctrl0: c[0][1], ctrl1: c[0][2], end: c[0][3] }, Vec2::new(1., 0.), Vec2::new(0., 0.)),
+                (CubicBezier3 { start: c[3][0], ctrl0: c[3][1], ctrl1: c[3][2], end: c[3][3] }, Vec2::new(1., 0.), Vec2::new(0., 1.)),
+                (CubicBezier3 { start: c[0][0], ctrl0: c[1][0], ctrl1: c[2][0], end: c[3][0] }, Vec2::new(0., 1.), Vec2::new(0., 0.)),
+                (CubicBezier3 { start: c[0][3], ctrl0: c[1][3], ctrl1: c[2][3], end: c[3][3] }, Vec2::new(0., 1.), Vec2::new(1., 0.)),
+            ];
+            assert_eq!(patch.u_curve(0.), edges[0].0);
+            assert_eq!(patch.u_curve(1.), edges[1].0);
+            assert_eq!(patch.v_curve(0.), edges[2].0);
+            assert_eq!(patch.v_curve(1.), edges[3].0);
+            for &(curve, direction, origin) in &edges {
+                for i in 0..=10 {
+                    let t = i as f64 / 10.;
+                    assert_relative_eq!(patch.evaluate(origin + direction * t), curve.evaluate(t), epsilon = 1e-12);
+                }
+            }
+        }
+        #[test]
+        fn derivatives_and_normals_match_finite_differences() {
+            let patch = patch();
+            let h = 1e-6;
+            for uv in uvs() {
+                let du = (patch.evaluate(uv + Vec2::new(h, 0.)) - patch.evaluate(uv - Vec2::new(h, 0.))) / (2. * h);
+                let dv = (patch.evaluate(uv + Vec2::new(0., h)) - patch.evaluate(uv - Vec2::new(0., h))) / (2. * h);
+                let derivatives = patch.derivatives(uv);
+                assert_relative_eq!(derivatives.0, du, epsilon = 1e-6);
+                assert_relative_eq!(derivatives.1, dv, epsilon = 1e-6);
+                assert_relative_eq!(patch.normal(uv), du.cross(dv).normalized(), epsilon = 1e-6);
+            }
+        }
+        #[test]
+        fn collapsed_corner() {
+            // A triangle-like patch, whose first row is a single point.
+            let mut patch = patch();
+            patch.ctrl[0] = [Vec3::new(1., -1., 2.); 4];
+            let n = patch.normal(Vec2::new(0.3, 0.));
+            assert_relative_eq!(n.magnitude(), 1., epsilon = 1e-12);
+            assert_relative_eq!(n, patch.normal(Vec2::new(0.3, 1e-4)), epsilon = 1e-3);
+            // Entirely degenerate.
+            let point = BezierPatch3 { ctrl: [[Vec3::new(1., 2., 3.); 4]; 4] };
+            assert_eq!(point.normal(Vec2::new(0.5, 0.5)), Vec3::zero());
+        }
+        #[test]
+        fn split() {
+            let patch = patch();
+            let [left, right] = patch.split_u(0.4);
+            let [bottom, top] = patch.split_v(0.7);
+            for uv in uvs() {
+                assert_relative_eq!(left.evaluate(uv), patch.evaluate(Vec2::new(uv.x * 0.4, uv.y)), epsilon = 1e-12);
+                assert_relative_eq!(right.evaluate(uv), patch.evaluate(Vec2::new(0.4 + uv.x * 0.6, uv.y)), epsilon = 1e-12);
+                assert_relative_eq!(bottom.evaluate(uv), patch.evaluate(Vec2::new(uv.x, uv.y * 0.7)), epsilon = 1e-12);
+                assert_relative_eq!(top.evaluate(uv), patch.evaluate(Vec2::new(uv.x, 0.7 + uv.y * 0.3)), epsilon = 1e-12);
+            }
+        }
+        #[test]
+        fn aabb_contains_patch() {
+            let patch = patch();
+            let aabb = patch.aabb();
+            for uv in uvs() {
+                assert!(aabb.contains_point(patch.evaluate(uv)));
+            }
+            for row in patch.ctrl.iter() {
+                for &p in row.iter() {
+                    assert!(aabb.contains_point(p));
+                }
+            }
+        }
+        #[test]
+        fn bilinear() {
+            let (p00, p10, p01, p11) = (Vec3::new(0., 0., 1.), Vec3::new(3., 0., 0.), Vec3::new(0., 2., 0.), Vec3::new(4., 3., 2.));
+            let patch = BezierPatch3::from_bilinear(p00, p10, p01, p11);
+            for uv in uvs() {
+                let expected = (p00 * (1. - uv.x) + p10 * uv.x) * (1. - uv.y) + (p01 * (1. - uv.x) + p11 * uv.x) * uv.y;
+                assert_relative_eq!(patch.evaluate(uv), expected, epsilon = 1e-12);
+            }
+        }
+        #[cfg(feature="std")]
+        #[test]
+        fn tessellate() {
+            use crate::vec::repr_c::Extent2;
+            let patch = patch();
+            let (positions, triangles) = patch.tessellate(Extent2::new(4, 3));
+            assert_eq!(positions.len(), 5 * 4);
+            assert_eq!(triangles.len(), 2 * 4 * 3);
+            for j in 0..=3 {
+                for i in 0..=4 {
+                    let uv = Vec2::new(i as f64 / 4., j as f64 / 3.);
+                    assert_relative_eq!(positions[j * 5 + i], patch.evaluate(uv), epsilon = 1e-12);
+                }
+            }
+            // Counter-clockwise around the normal at the centroid.
+            let uv = |k: u32| Vec2::new((k % 5) as f64 / 4., (k / 5) as f64 / 3.);
+            for &[a, b, c] in triangles.iter() {
+                let normal = patch.normal((uv(a) + uv(b) + uv(c)) / 3.);
+                let (a, b, c) = (positions[a as usize], positions[b as usize], positions[c as usize]);
+                assert!((b - a).cross(c - a).dot(normal) > 0.);
+            }
+        }
+        #[test]
+        #[should_panic]
+        #[cfg(feature="std")]
+        fn tessellate_zero_resolution() {
+            use crate::vec::repr_c::Extent2;
+            let _ = patch().tessellate(Extent2::new(0, 3));
+        }
+    }
+    mod polynomial {
+        use crate::vec::repr_c::{Vec2, Vec3};
+        use crate::bezier::repr_c::{QuadraticBezier2, QuadraticBezier3, CubicBezier2, CubicBezier3};
+
+        #[test]
+        fn matches_evaluate() {
+            let cubic = CubicBezier2 { start: Vec2::new(0.3, -1.2), ctrl0: Vec2::new(2.7, 4.1), ctrl1: Vec2::new(-3.3, 0.9), end: Vec2::new(1.9, -0.4) };
+            let quadratic = QuadraticBezier3 { start: Vec3::new(0.3, -1.2, 5.), ctrl: Vec3::new(2.7, 4.1, -0.6), end: Vec3::new(1.9, -0.4, 2.2) };
+            let [c0, c1, c2, c3] = cubic.polynomial_coefficients();
+            let [q0, q1, q2] = quadratic.polynomial_coefficients();
+            for i in 0..=100 {
+                let t = i as f64 / 100.;
+                assert_relative_eq!(c0 + c1*t + c2*t*t + c3*t*t*t, cubic.evaluate(t), epsilon = 1e-12);
+                assert_relative_eq!(q0 + q1*t + q2*t*t, quadratic.evaluate(t), epsilon = 1e-12);
+            }
+        }
+        #[test]
+        fn round_trip() {
+            let cubic = CubicBezier3 { start: Vec3::new(1., -2., 3.), ctrl0: Vec3::new(-4., 5., 0.), ctrl1: Vec3::new(7., 8., -9.), end: Vec3::new(0., 10., 2.) };
+            assert_eq!(CubicBezier3::from_polynomial_coefficients(cubic.polynomial_coefficients()), cubic);
+            let quadratic = QuadraticBezier2 { start: Vec2::new(1_f32, -2.), ctrl: Vec2::new(-4., 5.), end: Vec2::new(7., 8.) };
+            assert_eq!(QuadraticBezier2::from_polynomial_coefficients(quadratic.polynomial_coefficients()), quadratic);
+            // A straight line at constant speed.
+            let line = CubicBezier2::from_polynomial_coefficients([Vec2::new(1., 1.), Vec2::new(3., 6.), Vec2::zero(), Vec2::zero()]);
+            assert_eq!(line, CubicBezier2 { start: Vec2::new(1., 1.), ctrl0: Vec2::new(2., 3.), ctrl1: Vec2::new(3., 5.), end: Vec2::new(4., 7.) });
+        }
+    }
+    mod de_casteljau {
+        use crate::vec::repr_c::{Vec2, Vec3};
+        use crate::bezier::repr_c::{QuadraticBezier2, QuadraticBezier3, CubicBezier2, CubicBezier3};
+
+        #[test]
+        fn matches_evaluate() {
+            let cubic = CubicBezier3 { start: Vec3::new(0.3, -1.2, 5.), ctrl0: Vec3::new(2.7, 4.1, -0.6), ctrl1: Vec3::new(-3.3, 0.9, 1.), end: Vec3::new(1.9, -0.4, 2.2) };
+            let quadratic = QuadraticBezier2 { start: Vec2::new(0.3, -1.2), ctrl: Vec2::new(2.7, 4.1), end: Vec2::new(1.9, -0.4) };
+            for i in 0..=100 {
+                let t = i as f64 / 100.;
+                assert_relative_eq!(cubic.de_casteljau(t).point, cubic.evaluate(t), epsilon = 1e-12);
+                assert_relative_eq!(cubic.into_2d().de_casteljau(t).point, cubic.into_2d().evaluate(t), epsilon = 1e-12);
+                assert_relative_eq!(quadratic.de_casteljau(t).point, quadratic.evaluate(t), epsilon = 1e-12);
+                assert_relative_eq!(quadratic.into_3d().de_casteljau(t).point, quadratic.into_3d().evaluate(t), epsilon = 1e-12);
+            }
+            // The endpoints are exact.
+            assert_eq!(cubic.de_casteljau(0.).point, cubic.start);
+            assert_eq!(cubic.de_casteljau(1.).point, cubic.end);
+            assert_eq!(quadratic.de_casteljau(0.).point, quadratic.start);
+            assert_eq!(quadratic.de_casteljau(1.).point, quadratic.end);
+        }
+        #[test]
+        fn tangent() {
+            let curve = CubicBezier2 { start: Vec2::new(0.3, -1.2), ctrl0: Vec2::new(2.7, 4.1), ctrl1: Vec2::new(-3.3, 0.9), end: Vec2::new(1.9, -0.4) };
+            for &t in &[0., 0.2, 0.5, 0.9, 1.] {
+                let [a, b] = curve.de_casteljau(t).second;
+                assert_relative_eq!((b - a) * 3., curve.evaluate_derivative(t), epsilon = 1e-12);
+            }
+            let curve = QuadraticBezier3 { start: Vec3::new(0.3, -1.2, 5.), ctrl: Vec3::new(2.7, 4.1, -0.6), end: Vec3::new(1.9, -0.4, 2.2) };
+            for &t in &[0., 0.2, 0.5, 0.9, 1.] {
+                let [a, b] = curve.de_casteljau(t).first;
+                assert_relative_eq!((b - a) * 2., curve.evaluate_derivative(t), epsilon = 1e-12);
+            }
+        }
+        #[test]
+        fn split() {
+            let curve = CubicBezier2 { start: Vec2::new(0.3, -1.2), ctrl0: Vec2::new(2.7, 4.1), ctrl1: Vec2::new(-3.3, 0.9), end: Vec2::new(1.9, -0.4) };
+            let [left, right] = curve.split(0.3);
+            assert_eq!((left.start, left.end, right.start, right.end), (curve.start, curve.de_casteljau(0.3).point, left.end, curve.end));
+            for i in 0..=10 {
+                let t = i as f64 / 10.;
+                assert_relative_eq!(left.evaluate(t), curve.evaluate(0.3 * t), epsilon = 1e-12);
+                assert_relative_eq!(right.evaluate(t), curve.evaluate(0.3 + 0.7 * t), epsilon = 1e-12);
+            }
+        }
+        #[test]
+        fn extreme_magnitudes() {
+            // The Bernstein form overflows here, while the interpolations don't.
+            let curve = CubicBezier2 { start: Vec2::new(-3e38_f32, 0.), ctrl0: Vec2::new(3e38, 1.), ctrl1: Vec2::new(-3e38, 2.), end: Vec2::new(3e38, 3.) };
+            let point = curve.de_casteljau(0.5).point;
+            assert_relative_eq!(point, Vec2::new(0., 1.5));
+        }
+    }
+    mod second_derivative {
+        use crate::vec::repr_c::{Vec2, Vec3};
+        use crate::bezier::repr_c::{QuadraticBezier2, QuadraticBezier3, CubicBezier2, CubicBezier3};
+
+        const H: f64 = 1e-5;
+
+        #[test]
+        fn matches_finite_difference() {
+            let cubic = CubicBezier3 { start: Vec3::new(0.3, -1.2, 5.), ctrl0: Vec3::new(2.7, 4.1, -0.6), ctrl1: Vec3::new(-3.3, 0.9, 1.), end: Vec3::new(1.9, -0.4, 2.2) };
+            let quadratic = QuadraticBezier2 { start: Vec2::new(0.3, -1.2), ctrl: Vec2::new(2.7, 4.1), end: Vec2::new(1.9, -0.4) };
+            for i in 0..=20 {
+                let t = i as f64 / 20.;
+                let expected = (cubic.evaluate_derivative(t + H) - cubic.evaluate_derivative(t - H)) / (2. * H);
+                assert_relative_eq!(cubic.evaluate_second_derivative(t), expected, epsilon = 1e-6);
+                let cubic = cubic.into_2d();
+                let expected = (cubic.evaluate_derivative(t + H) - cubic.evaluate_derivative(t - H)) / (2. * H);
+                assert_relative_eq!(cubic.evaluate_second_derivative(t), expected, epsilon = 1e-6);
+                let expected = (quadratic.evaluate_derivative(t + H) - quadratic.evaluate_derivative(t - H)) / (2. * H);
+                assert_relative_eq!(quadratic.evaluate_second_derivative(t), expected, epsilon = 1e-6);
+                let quadratic = quadratic.into_3d();
+                let expected = (quadratic.evaluate_derivative(t + H) - quadratic.evaluate_derivative(t - H)) / (2. * H);
+                assert_relative_eq!(quadratic.evaluate_second_derivative(t), expected, epsilon = 1e-6);
+            }
+        }
+        #[test]
+        fn known_values() {
+            // x(t) = t³, y(t) = 3t²(1-t) + t³.
+            let cubic = CubicBezier2 { start: Vec2::new(0., 0.), ctrl0: Vec2::new(0., 0.), ctrl1: Vec2::new(0., 1.), end: Vec2::new(1., 1.) };
+            assert_eq!(cubic.evaluate_second_derivative(0.), Vec2::new(0., 6.));
+            assert_eq!(cubic.evaluate_second_derivative(0.5), Vec2::new(3., 0.));
+            assert_eq!(cubic.evaluate_second_derivative(1.), Vec2::new(6., -6.));
+            // x(t) = t², y(t) = 2t(1-t).
+            let quadratic = QuadraticBezier3 { start: Vec3::zero(), ctrl: Vec3::new(0., 1., 0.), end: Vec3::new(1., 0., 0.) };
+            assert_eq!(quadratic.evaluate_second_derivative(0.3), Vec3::new(2., -4., 0.));
+        }
+    }
+    mod normal {
+        use crate::vec::repr_c::{Vec2, Vec3};
+        use crate::bezier::repr_c::{QuadraticBezier2, CubicBezier2, CubicBezier3, RationalQuadraticBezier2};
+
+        fn curve() -> CubicBezier2<f64> {
+            CubicBezier2 { start: Vec2::new(0.3, -1.2), ctrl0: Vec2::new(2.7, 4.1), ctrl1: Vec2::new(-3.3, 0.9), end: Vec2::new(1.9, -0.4) }
+        }
+
+        #[test]
+        fn counter_clockwise() {
+            let curve = curve();
+            for i in 0..=20 {
+                let t = i as f64 / 20.;
+                let (tangent, normal) = (curve.normalized_tangent(t), curve.normal(t).unwrap());
+                assert_relative_eq!(normal.magnitude(), 1., epsilon = 1e-12);
+                assert_relative_eq!(normal.dot(tangent), 0., epsilon = 1e-12);
+                // Counter-clockwise from the tangent.
+                assert_relative_eq!(tangent.x * normal.y - tangent.y * normal.x, 1., epsilon = 1e-12);
+                assert_relative_eq!(curve.reversed().normal(1. - t).unwrap(), -normal, epsilon = 1e-12);
+            }
+            // A counter-clockwise arc's normal points at its center.
+            let arc = RationalQuadraticBezier2::from_circular_arc(Vec2::new(1., 2.), 3., 0.2, 1.5);
+            for &t in &[0., 0.3, 1.] {
+                assert_relative_eq!(arc.normal(t).unwrap(), (Vec2::new(1., 2.) - arc.evaluate(t)) / 3., epsilon = 1e-12);
+            }
+        }
+        #[test]
+        fn three_d() {
+            let flat = curve().into_3d();
+            let up = Vec3::new(1., -2., 3.).normalized();
+            let tilted = CubicBezier3 { start: flat.start, ctrl0: flat.ctrl0 + up, ctrl1: flat.ctrl1 - up * 2., end: flat.end + up };
+            for i in 0..=20 {
+                let t = i as f64 / 20.;
+                // Same as 2D in the XY plane.
+                assert_relative_eq!(flat.normal(t, Vec3::unit_z()).unwrap(), Vec3::from(curve().normal(t).unwrap()), epsilon = 1e-12);
+                let normal = tilted.normal(t, up).unwrap();
+                assert_relative_eq!(normal.magnitude(), 1., epsilon = 1e-12);
+                assert_relative_eq!(normal.dot(tilted.evaluate_derivative(t)), 0., epsilon = 1e-12);
+                assert_relative_eq!(normal.dot(up), 0., epsilon = 1e-12);
+            }
+            // Along `up`.
+            let vertical = CubicBezier3 { start: Vec3::zero(), ctrl0: Vec3::unit_z(), ctrl1: Vec3::unit_z() * 2., end: Vec3::unit_z() * 3. };
+            assert_eq!(vertical.normal(0.5, Vec3::unit_z()), None);
+            assert!(vertical.normal(0.5, Vec3::unit_y()).is_some());
+        }
+        #[test]
+        fn cusps() {
+            // The control points coincide with the ends, so the derivative vanishes there.
+            let curve = CubicBezier2 { start: Vec2::new(0_f32, 0.), ctrl0: Vec2::new(0., 0.), ctrl1: Vec2::new(1., 1.), end: Vec2::new(1., 1.) };
+            assert_eq!(curve.normal(0.), None);
+            assert_eq!(curve.normal(1.), None);
+            assert!(curve.normal(0.5).is_some());
+            assert_eq!(curve.into_3d().normal(0., Vec3::unit_z()), None);
+            let point = QuadraticBezier2 { start: Vec2::new(1_f32, 1.), ctrl: Vec2::new(1., 1.), end: Vec2::new(1., 1.) };
+            assert_eq!(point.normal(0.5), None);
+        }
+    }
+    #[cfg(feature="std")]
+    mod frames {
+        use crate::vec::repr_c::{Vec2, Vec3};
+        use crate::mat::repr_c::Mat3;
+        use crate::bezier::repr_c::{CubicBezier2, CubicBezier3};
+
+        // An S shape in the XY plane, with an inflection at t = 0.5.
+        fn s_curve() -> CubicBezier3<f64> {
+            CubicBezier2 { start: Vec2::new(0., 0.), ctrl0: Vec2::new(3., 3.), ctrl1: Vec2::new(1., -3.), end: Vec2::new(4., 0.) }.into_3d()
+        }
+        fn assert_orthonormal(m: Mat3<f64>) {
+            assert_relative_eq!(m.transposed() * m, Mat3::identity(), epsilon = 1e-12);
+            assert_relative_eq!(m.determinant(), 1., epsilon = 1e-12);
+        }
+
+        #[test]
+        fn frenet() {
+            let curve = CubicBezier3 { start: Vec3::new(1., 0., 0.), ctrl0: Vec3::new(1., 1., 0.5), ctrl1: Vec3::new(0., 2., 1.), end: Vec3::new(-1., 1., 1.5) };
+            for i in 0..=10 {
+                let t = i as f64 / 10.;
+                let (tangent, normal, binormal) = curve.frenet_frame(t).unwrap();
+                assert_orthonormal(Mat3::from_col_arrays([tangent.into_array(), normal.into_array(), binormal.into_array()]));
+                assert_relative_eq!(tangent, curve.normalized_tangent(t), epsilon = 1e-12);
+                // Towards the center of curvature.
+                assert!(normal.dot(curve.evaluate_second_derivative(t)) > 0.);
+            }
+            // The normal flips across the inflection of the S curve.
+            let (_, before, _) = s_curve().frenet_frame(0.45).unwrap();
+            let (_, after, _) = s_curve().frenet_frame(0.55).unwrap();
+            assert!(before.dot(after) < -0.9);
+            assert_eq!(s_curve().frenet_frame(0.5), None);
+            let line = CubicBezier3 { start: Vec3::zero(), ctrl0: Vec3::unit_x(), ctrl1: Vec3::unit_x() * 2., end: Vec3::unit_x() * 3. };
+            assert_eq!(line.frenet_frame(0.3), None);
+        }
+        #[test]
+        fn rotation_minimizing() {
+            let curve = s_curve();
+            let frames = curve.rotation_minimizing_frames(64);
+            assert_eq!(frames.len(), 64);
+            assert_eq!(frames[0].0, curve.start);
+            assert_eq!(frames[63].0, curve.end);
+            for (i, &(point, frame)) in frames.iter().enumerate() {
+                let t = i as f64 / 63.;
+                assert_relative_eq!(point, curve.evaluate(t), epsilon = 1e-12);
+                assert_orthonormal(frame);
+                assert_relative_eq!(frame * Vec3::unit_x(), curve.normalized_tangent(t), epsilon = 1e-12);
+                // The curve is planar, so the frame never twists out of its plane.
+                assert_relative_eq!((frame * Vec3::unit_z()).z.abs(), 1., epsilon = 1e-9);
+            }
+            // No sudden flips, unlike the Frenet frame.
+            for pair in frames.windows(2) {
+                assert!((pair[0].1 * Vec3::unit_y()).dot(pair[1].1 * Vec3::unit_y()) > 0.9);
+            }
+            // Starts from the Frenet frame.
+            let (_, normal, _) = curve.frenet_frame(0.).unwrap();
+            assert_relative_eq!(frames[0].1 * Vec3::unit_y(), normal, epsilon = 1e-12);
+        }
+        #[test]
+        fn twisted() {
+            // A non-planar curve: the frames rotate about the tangent as little as possible, which
+            // refining the sampling doesn't change much.
+            let curve = CubicBezier3 { start: Vec3::new(0., 0., 0.), ctrl0: Vec3::new(2., 0., 1.), ctrl1: Vec3::new(0., 2., -1.), end: Vec3::new(2., 2., 0.) };
+            let coarse = curve.rotation_minimizing_frames(33);
+            let fine = curve.rotation_minimizing_frames(257);
+            for (i, &(_, frame)) in coarse.iter().enumerate() {
+                assert_orthonormal(frame);
+                let (_, fine_frame) = fine[i * 8];
+                assert!((frame * Vec3::unit_y()).dot(fine_frame * Vec3::unit_y()) > 0.999);
+            }
+        }
+        #[test]
+        fn degenerate() {
+            // Straight, with control points on the ends: the Frenet frame is undefined everywhere,
+            // and the derivative is zero at both ends.
+            let line = CubicBezier3 { start: Vec3::zero(), ctrl0: Vec3::zero(), ctrl1: Vec3::new(1., 2., 2.), end: Vec3::new(1., 2., 2.) };
+            let frames = line.rotation_minimizing_frames(5);
+            for &(_, frame) in frames.iter() {
+                assert_orthonormal(frame);
+                assert_relative_eq!(frame * Vec3::unit_x(), Vec3::new(1., 2., 2.) / 3., epsilon = 1e-12);
+                assert_relative_eq!(frame, frames[0].1, epsilon = 1e-12);
+            }
+        }
+        #[test]
+        #[should_panic]
+        fn single_sample() {
+            s_curve().rotation_minimizing_frames(1);
+        }
+    }
+    mod matrix_product {
+        use crate::vec::repr_c::{Vec2, Vec3};
+        use crate::mat::repr_c::{row_major, column_major};
+        use crate::bezier::repr_c::{QuadraticBezier2, QuadraticBezier3, CubicBezier2, CubicBezier3};
+
+        // Translation, rotation and non-uniform scale, each alone and combined.
+        fn transforms_3d() -> [column_major::Mat4<f64>; 4] {
+            let translation = column_major::Mat4::translation_3d(Vec3::new(3., -1., 2.));
+            let rotation = column_major::Mat4::rotation_3d(0.8, Vec3::new(1., -2., 0.5));
+            let scale = column_major::Mat4::scaling_3d(Vec3::new(2., 0.5, -3.));
+            [translation, rotation, scale, translation * rotation * scale]
+        }
+        fn transforms_2d() -> [column_major::Mat3<f64>; 4] {
+            let translation = column_major::Mat3::translation_2d(Vec2::new(3., -1.));
+            let rotation = column_major::Mat3::rotation_z(0.8);
+            let scale = column_major::Mat3::scaling_3d(Vec3::new(2., -0.5, 1.));
+            [translation, rotation, scale, translation * rotation * scale]
+        }
+
+        #[test] fn commutes_with_evaluation_3d() {
+            let quadratic = QuadraticBezier3 { start: Vec3::new(0., 1., 2.), ctrl: Vec3::new(4., -2., 1.), end: Vec3::new(-1., 3., 5.) };
+            let cubic = CubicBezier3 { start: Vec3::new(0., 1., 2.), ctrl0: Vec3::new(4., -2., 1.), ctrl1: Vec3::new(2., 2., -2.), end: Vec3::new(-1., 3., 5.) };
+            for &m in transforms_3d().iter() {
+                let rows = row_major::Mat4::from_row_arrays(m.into_row_arrays());
+                for i in 0..=10 {
+                    let t = i as f64 / 10.;
+                    assert_relative_eq!((m * quadratic).evaluate(t), m.mul_point(quadratic.evaluate(t)), epsilon = 1e-12);
+                    assert_relative_eq!((m * cubic).evaluate(t), m.mul_point(cubic.evaluate(t)), epsilon = 1e-12);
+                    assert_relative_eq!((rows * cubic).evaluate(t), m.mul_point(cubic.evaluate(t)), epsilon = 1e-12);
+                }
+                // 3x3 matrices are linear maps.
+                let linear = column_major::Mat3::from(m);
+                assert_relative_eq!((linear * cubic).evaluate(0.3), linear * cubic.evaluate(0.3), epsilon = 1e-12);
+            }
+        }
+        #[test] fn commutes_with_evaluation_2d() {
+            let quadratic = QuadraticBezier2 { start: Vec2::new(0., 1.), ctrl: Vec2::new(4., -2.), end: Vec2::new(-1., 3.) };
+            let cubic = CubicBezier2 { start: Vec2::new(0., 1.), ctrl0: Vec2::new(4., -2.), ctrl1: Vec2::new(2., 2.), end: Vec2::new(-1., 3.) };
+            for &m in transforms_2d().iter() {
+                let rows = row_major::Mat3::from_row_arrays(m.into_row_arrays());
+                for i in 0..=10 {
+                    let t = i as f64 / 10.;
+                    assert_relative_eq!((m * quadratic).evaluate(t), m.mul_point_2d(quadratic.evaluate(t)), epsilon = 1e-12);
+                    assert_relative_eq!((m * cubic).evaluate(t), m.mul_point_2d(cubic.evaluate(t)), epsilon = 1e-12);
+                    assert_relative_eq!((rows * quadratic).evaluate(t), m.mul_point_2d(quadratic.evaluate(t)), epsilon = 1e-12);
+                }
+                // 2x2 matrices are linear maps.
+                let linear = column_major::Mat2::from(m);
+                assert_relative_eq!((linear * quadratic).evaluate(0.3), linear * quadratic.evaluate(0.3), epsilon = 1e-12);
+            }
+        }
+    }
+    mod affine {
+        use crate::vec::repr_c::{Vec2, Vec3};
+        use crate::bezier::repr_c::{QuadraticBezier2, CubicBezier2, CubicBezier3};
+
+        fn cubic2() -> CubicBezier2<f64> {
+            CubicBezier2 { start: Vec2::new(-1., 0.5), ctrl0: Vec2::new(0., 3.), ctrl1: Vec2::new(2., -2.), end: Vec2::new(4., 1.) }
+        }
+        fn ts() -> impl Iterator<Item=f64> {
+            (0..=16).map(|i| i as f64 / 16.)
+        }
+
+        #[test] fn translated() {
+            let (curve, v) = (cubic2(), Vec2::new(3., -7.5));
+            for t in ts() {
+                assert_relative_eq!(curve.translated(v).evaluate(t), curve.evaluate(t) + v, epsilon = 1e-12);
+            }
+            let mut moved = curve;
+            moved.translate(v);
+            assert_eq!(moved, curve.translated(v));
+            let curve3 = CubicBezier3 { start: Vec3::zero(), ctrl0: Vec3::unit_x(), ctrl1: Vec3::unit_y(), end: Vec3::one() };
+            let v3 = Vec3::new(1., 2., 3.);
+            assert_relative_eq!(curve3.translated(v3).evaluate(0.3), curve3.evaluate(0.3) + v3, epsilon = 1e-12);
+        }
+        #[test] fn scaled() {
+            let (curve, scale, center) = (cubic2(), Vec2::new(2., -0.5), Vec2::new(1., 1.));
+            for t in ts() {
+                assert_relative_eq!(curve.scaled(scale).evaluate(t), curve.evaluate(t) * scale, epsilon = 1e-12);
+                let about = curve.scaled_about(center, scale).evaluate(t);
+                assert_relative_eq!(about, center + (curve.evaluate(t) - center) * scale, epsilon = 1e-12);
+            }
+            let mut scaled = curve;
+            scaled.scale(scale);
+            assert_eq!(scaled, curve.scaled(scale));
+            assert_eq!(curve.scaled_about(Vec2::zero(), scale), curve.scaled(scale));
+            assert_eq!(curve.scaled(Vec2::one()), curve);
+        }
+        #[test] fn rotated_z() {
+            let (curve, center) = (cubic2(), Vec2::new(-2., 0.5));
+            for &angle in &[0.3, -1.2, 2.5] {
+                let (rotated, about) = (curve.rotated_z(angle), curve.rotated_z_about(center, angle));
+                for t in ts() {
+                    let p = curve.evaluate(t);
+                    assert_relative_eq!(rotated.evaluate(t), p.rotated_z(angle), epsilon = 1e-12);
+                    assert_relative_eq!(about.evaluate(t), center + (p - center).rotated_z(angle), epsilon = 1e-12);
+                }
+                // Rotation preserves lengths.
+                assert_relative_eq!(rotated.length_by_discretization(64), curve.length_by_discretization(64), epsilon = 1e-9);
+            }
+            let quadratic = QuadraticBezier2 { start: Vec2::new(1_f32, 0.), ctrl: Vec2::new(1., 1.), end: Vec2::new(0., 1.) };
+            let mut rotated = quadratic;
+            rotated.rotate_z(std::f32::consts::PI);
+            assert_relative_eq!(rotated.ctrl, Vec2::new(-1., -1.), epsilon = 1e-6);
+            assert_relative_eq!(rotated.rotated_z(-std::f32::consts::PI).end, quadratic.end, epsilon = 1e-6);
+        }
+    }
+    mod points {
+        use crate::vec::repr_c::{Vec2, Vec3};
+        use crate::bezier::repr_c::{QuadraticBezier2, QuadraticBezier3, CubicBezier2, CubicBezier3};
+
+        #[test] fn quadratic_order() {
+            let mut curve = QuadraticBezier2 { start: Vec2::new(1, 2), ctrl: Vec2::new(3, 4), end: Vec2::new(5, 6) };
+            let expected = [Vec2::new(1, 2), Vec2::new(3, 4), Vec2::new(5, 6)];
+            assert!(curve.iter().copied().eq(expected.iter().copied()));
+            assert!((&curve).into_iter().copied().eq(expected.iter().copied()));
+            assert!(curve.into_iter().eq(expected.iter().copied()));
+            assert_eq!(curve.iter().len(), 3);
+            for (i, p) in (&mut curve).into_iter().enumerate() {
+                p.x = i as i32 * 10;
+            }
+            assert_eq!(curve, QuadraticBezier2 { start: Vec2::new(0, 2), ctrl: Vec2::new(10, 4), end: Vec2::new(20, 6) });
+            // 3D curves too.
+            let mut curve = QuadraticBezier3::from(curve);
+            for p in curve.iter_mut() {
+                *p += Vec3::unit_z();
+            }
+            assert!(curve.into_iter().all(|p| p.z == 1));
+        }
+        #[test] fn cubic_order() {
+            let mut curve = CubicBezier3 {
+                start: Vec3::new(1., 2., 3.), ctrl0: Vec3::new(4., 5., 6.), ctrl1: Vec3::new(7., 8., 9.), end: Vec3::new(10., 11., 12.),
+            };
+            let expected = [curve.start, curve.ctrl0, curve.ctrl1, curve.end];
+            assert!(curve.iter().copied().eq(expected.iter().copied()));
+            assert!(curve.into_iter().eq(expected.iter().copied()));
+            for p in &mut curve {
+                *p = -*p;
+            }
+            assert!(curve.into_iter().eq(expected.iter().map(|&p| -p)));
+            // Iterating by value is the same as converting to an array.
+            let curve2 = CubicBezier2::from(curve);
+            assert!(curve2.into_iter().eq(IntoIterator::into_iter(curve2.into_array())));
+        }
+        #[test] fn map() {
+            let curve = CubicBezier2 { start: Vec2::new(0_f64, 0.), ctrl0: Vec2::new(0., 4.), ctrl1: Vec2::new(4., 4.), end: Vec2::new(4., 0.) };
+            let moved = curve.map(|p| p + Vec2::new(1., -1.));
+            for i in 0..=8 {
+                let t = i as f64 / 8.;
+                assert_eq!(moved.evaluate(t), curve.evaluate(t) + Vec2::new(1., -1.));
+            }
+            let mut iterated = curve;
+            for p in iterated.iter_mut() {
+                *p += Vec2::new(1., -1.);
+            }
+            assert_eq!(iterated, moved);
+            // The element type can change.
+            let rounded: CubicBezier2<i32> = curve.map(|p| p.map(|e| e as i32));
+            assert_eq!(rounded.ctrl1, Vec2::new(4, 4));
+        }
+    }
+    mod bounding_triangle {
+        use crate::vec::repr_c::{Vec2, Vec3};
+        use crate::bezier::repr_c::{QuadraticBezier2, QuadraticBezier3};
+
+        fn curve() -> QuadraticBezier2<f64> {
+            QuadraticBezier2 { start: Vec2::new(-1., 0.5), ctrl: Vec2::new(2., 4.), end: Vec2::new(5., -1.) }
+        }
+
+        #[test] fn contains_curve() {
+            for curve in [curve(), curve().reversed()].iter() {
+                assert_eq!(curve.bounding_triangle(), (curve.start, curve.ctrl, curve.end));
+                for i in 0..=64 {
+                    assert!(curve.contains_in_bounding_triangle(curve.evaluate(i as f64 / 64.)), "{}", i);
+                }
+            }
+        }
+        #[test] fn inside_and_outside() {
+            let (a, b, c) = curve().bounding_triangle();
+            let centroid = (a + b + c) / 3.;
+            // Both windings agree.
+            for curve in [curve(), curve().reversed(), QuadraticBezier2 { start: a, ctrl: c, end: b }].iter() {
+                assert!(curve.contains_in_bounding_triangle(centroid));
+                // Vertices and points on edges.
+                for &p in &[a, b, c, (a + b) / 2., (b + c) / 2., (c + a) / 2.] {
+                    assert!(curve.contains_in_bounding_triangle(p), "{:?}", p);
+                }
+                // Points just outside each edge, and beyond each vertex.
+                for &(u, v) in &[(a, b), (b, c), (c, a)] {
+                    let middle = (u + v) / 2.;
+                    assert!(!curve.contains_in_bounding_triangle(middle + (middle - centroid) * 1e-9));
+                    assert!(!curve.contains_in_bounding_triangle(u + (u - centroid) * 1e-9));
+                }
+                assert!(!curve.contains_in_bounding_triangle(Vec2::new(10., 10.)));
+            }
+        }
+        #[test] fn collinear() {
+            // The control point is beyond the end, so the segment spans from start to ctrl.
+            let curve = QuadraticBezier2 { start: Vec2::new(0_f32, 0.), ctrl: Vec2::new(3., 6.), end: Vec2::new(1., 2.) };
+            for &p in &[Vec2::new(0., 0.), Vec2::new(2., 4.), Vec2::new(3., 6.), Vec2::new(0.5, 1.)] {
+                assert!(curve.contains_in_bounding_triangle(p), "{:?}", p);
+            }
+            for &p in &[Vec2::new(-1., -2.), Vec2::new(4., 8.), Vec2::new(1., 2.5), Vec2::new(2., 3.)] {
+                assert!(!curve.contains_in_bounding_triangle(p), "{:?}", p);
+            }
+            // Along an axis.
+            let curve = QuadraticBezier2 { start: Vec2::new(2_f32, 1.), ctrl: Vec2::new(-1., 1.), end: Vec2::new(5., 1.) };
+            assert!(curve.contains_in_bounding_triangle(Vec2::new(-1., 1.)));
+            assert!(curve.contains_in_bounding_triangle(Vec2::new(4., 1.)));
+            assert!(!curve.contains_in_bounding_triangle(Vec2::new(4., 1.5)));
+            assert!(!curve.contains_in_bounding_triangle(Vec2::new(6., 1.)));
+            // A single point.
+            let p = Vec2::new(1_f32, 2.);
+            let curve = QuadraticBezier2 { start: p, ctrl: p, end: p };
+            assert!(curve.contains_in_bounding_triangle(p));
+            assert!(!curve.contains_in_bounding_triangle(Vec2::new(1., 2.5)));
+        }
+        #[test] fn in_plane_3d() {
+            let curve = QuadraticBezier3 { start: Vec3::new(1_f64, 0., 2.), ctrl: Vec3::new(-2., 3., 1.), end: Vec3::new(4., 2., -1.) };
+            let (a, b, c) = curve.bounding_triangle();
+            let normal = (b - a).cross(c - a);
+            for i in 0..=16 {
+                let p = curve.evaluate(i as f64 / 16.);
+                assert_relative_eq!((p - a).dot(normal), 0., epsilon = 1e-12);
+                // Barycentric coordinates, all non-negative.
+                let area = normal.magnitude_squared();
+                let (u, v, w) = ((c - b).cross(p - b).dot(normal) / area, (a - c).cross(p - c).dot(normal) / area, (b - a).cross(p - a).dot(normal) / area);
+                assert_relative_eq!(u + v + w, 1., epsilon = 1e-12);
+                assert!(u >= -1e-12 && v >= -1e-12 && w >= -1e-12);
+            }
+        }
+    }
+    mod hermite {
+        use crate::vec::repr_c::{Vec2, Vec3};
+        use crate::bezier::repr_c::{CubicBezier2, CubicBezier3};
+
+        // The cubic Hermite basis functions.
+        fn hermite<P>(p0: P, m0: P, p1: P, m1: P, t: f64) -> P
+            where P: core::ops::Mul<f64, Output=P> + core::ops::Add<Output=P>
+        {
+            let (t2, t3) = (t * t, t * t * t);
+            p0 * (2. * t3 - 3. * t2 + 1.) + m0 * (t3 - 2. * t2 + t) + p1 * (-2. * t3 + 3. * t2) + m1 * (t3 - t2)
+        }
+
+        #[test] fn matches_hermite_basis() {
+            let (p0, m0, p1, m1) = (Vec2::new(1., -2.), Vec2::new(4., 3.), Vec2::new(5., 1.), Vec2::new(-2., 6.));
+            let curve = CubicBezier2::from_hermite(p0, m0, p1, m1);
+            let (q0, n0, q1, n1) = (Vec3::new(0., 1., 2.), Vec3::new(3., -1., 0.5), Vec3::new(-1., 4., 2.), Vec3::new(0., 0., -7.));
+            let curve3 = CubicBezier3::from_hermite(q0, n0, q1, n1);
+            for i in 0..=16 {
+                let t = i as f64 / 16.;
+                assert_relative_eq!(curve.evaluate(t), hermite(p0, m0, p1, m1, t), epsilon = 1e-12);
+                assert_relative_eq!(curve3.evaluate(t), hermite(q0, n0, q1, n1, t), epsilon = 1e-12);
+            }
+            assert_relative_eq!(curve.evaluate_derivative(0.), m0, epsilon = 1e-12);
+            assert_relative_eq!(curve.evaluate_derivative(1.), m1, epsilon = 1e-12);
+        }
+        #[test] fn round_trip() {
+            let curve = CubicBezier3 { start: Vec3::new(0.1_f64, 2., -3.), ctrl0: Vec3::new(1.7, 0.3, 4.), ctrl1: Vec3::new(-2.2, 5., 1.1), end: Vec3::new(9., -0.4, 0.) };
+            let (p0, m0, p1, m1) = curve.to_hermite();
+            assert_eq!((p0, p1), (curve.start, curve.end));
+            let back = CubicBezier3::from_hermite(p0, m0, p1, m1);
+            assert_relative_eq!(back.ctrl0, curve.ctrl0, epsilon = 1e-14);
+            assert_relative_eq!(back.ctrl1, curve.ctrl1, epsilon = 1e-14);
+            let hermite = (Vec2::new(1_f32, 2.), Vec2::new(-3., 0.75), Vec2::new(6., 1.), Vec2::new(0., 9.));
+            let (p0, m0, p1, m1) = CubicBezier2::from_hermite(hermite.0, hermite.1, hermite.2, hermite.3).to_hermite();
+            assert_eq!((p0, p1), (hermite.0, hermite.2));
+            assert_relative_eq!(m0, hermite.1, epsilon = 1e-5);
+            assert_relative_eq!(m1, hermite.3, epsilon = 1e-5);
+        }
+    }
+    #[cfg(feature="std")]
+    mod catmull_rom {
+        use crate::vec::repr_c::{Vec2, Vec3};
+        use crate::bezier::repr_c::{CubicBezier2, CubicBezier3};
+
+        fn waypoints() -> [Vec2<f64>; 6] {
+            [Vec2::new(0., 0.), Vec2::new(1., 2.), Vec2::new(3., 3.), Vec2::new(4., 1.), Vec2::new(6., 0.5), Vec2::new(7., 2.)]
+        }
+
+        #[test] fn interpolates_points() {
+            let points = waypoints();
+            for &tension in &[0., 0.5, -0.5] {
+                let curves = CubicBezier2::catmull_rom_spline(&points, tension);
+                assert_eq!(curves.len(), points.len() - 1);
+                for (i, curve) in curves.iter().enumerate() {
+                    assert_eq!(curve.evaluate(0.), points[i]);
+                    assert_relative_eq!(curve.evaluate(1.), points[i + 1], epsilon = 1e-12);
+                }
+            }
+        }
+        #[test] fn c1_continuity() {
+            let points = waypoints();
+            let curves = CubicBezier2::catmull_rom_spline(&points, 0.);
+            for pair in curves.windows(2) {
+                assert_relative_eq!(pair[0].evaluate_derivative(1.), pair[1].evaluate_derivative(0.), epsilon = 1e-12);
+            }
+            // Inner tangents are those of Catmull–Rom.
+            for i in 1..points.len() - 1 {
+                assert_relative_eq!(curves[i].evaluate_derivative(0.), (points[i + 1] - points[i - 1]) / 2., epsilon = 1e-12);
+            }
+            // The ends point towards their neighbours.
+            assert_relative_eq!(curves[0].evaluate_derivative(0.), (points[1] - points[0]) / 2., epsilon = 1e-12);
+            let last = curves[curves.len() - 1];
+            assert_relative_eq!(last.evaluate_derivative(1.), (points[5] - points[4]) / 2., epsilon = 1e-12);
+        }
+        #[test] fn tension() {
+            let (p0, p1, p2, p3) = (Vec3::new(0_f64, 0., 0.), Vec3::new(1., 1., 1.), Vec3::new(2., 1., 3.), Vec3::new(4., 0., 3.));
+            let loose = CubicBezier3::from_catmull_rom(p0, p1, p2, p3, 0.);
+            let tight = CubicBezier3::from_catmull_rom(p0, p1, p2, p3, 0.5);
+            assert_relative_eq!(tight.evaluate_derivative(0.), loose.evaluate_derivative(0.) * 0.5, epsilon = 1e-12);
+            assert_relative_eq!(tight.evaluate_derivative(1.), loose.evaluate_derivative(1.) * 0.5, epsilon = 1e-12);
+            // With a tension of one, the curve is a straight line.
+            let straight = CubicBezier3::from_catmull_rom(p0, p1, p2, p3, 1.);
+            assert_eq!((straight.ctrl0, straight.ctrl1), (p1, p2));
+        }
+        #[test] fn few_points() {
+            assert!(CubicBezier2::<f32>::catmull_rom_spline(&[], 0.).is_empty());
+            assert!(CubicBezier2::catmull_rom_spline(&[Vec2::new(1_f32, 2.)], 0.).is_empty());
+            let (a, b) = (Vec3::new(1_f32, 2., 3.), Vec3::new(4., 6., 3.));
+            let curves = CubicBezier3::catmull_rom_spline(&[a, b], 0.);
+            assert_eq!(curves.len(), 1);
+            assert_eq!((curves[0].start, curves[0].end), (a, b));
+            // A straight line, symmetric about its middle.
+            assert_relative_eq!(curves[0].evaluate(0.5), (a + b) / 2.);
+        }
+    }
+    #[cfg(feature="std")]
+    mod degree_reduction {
+        use crate::vec::repr_c::{Vec2, Vec3};
+        use crate::bezier::repr_c::{QuadraticBezier2, CubicBezier2, CubicBezier3};
+        use alloc::vec::Vec;
+
+        fn curves() -> [CubicBezier2<f64>; 4] {
+            let c = |a: (f64, f64), b: (f64, f64), c: (f64, f64), d: (f64, f64)| CubicBezier2 {
+                start: Vec2::from(a), ctrl0: Vec2::from(b), ctrl1: Vec2::from(c), end: Vec2::from(d),
+            };
+            [
+                c((0., 0.), (0., 4.), (4., 4.), (4., 0.)),
+                // S-shaped.
+                c((0., 0.), (3., 5.), (1., -5.), (4., 0.)),
+                // Loop.
+                c((0., 0.), (10., 10.), (-6., 10.), (4., 0.)),
+                // Cusp.
+                c((0., 0.), (1., 1.), (0., 1.), (1., 0.)),
+            ]
+        }
+
+        #[test] fn error_bound() {
+            for curve in curves().iter().copied() {
+                let (quadratic, error) = curve.reduce_degree();
+                assert_eq!((quadratic.start, quadratic.end), (curve.start, curve.end));
+                let max_distance = (0..=1000).map(|i| {
+                    let t = i as f64 / 1000.;
+                    curve.evaluate(t).distance(quadratic.evaluate(t))
+                }).fold(0., f64::max);
+                assert!(max_distance <= error * (1. + 1e-12), "{} > {}", max_distance, error);
+                // The bound is tight for these curves, whose third difference is the error's direction.
+                assert!(max_distance >= error * 0.5, "{} < {}", max_distance, error);
+            }
+            // Quadratic curves are reduced exactly, also in 3D.
+            let quadratic = QuadraticBezier2 { start: Vec2::new(1_f64, 2.), ctrl: Vec2::new(-3., 5.), end: Vec2::new(4., 4.) };
+            let (reduced, error) = quadratic.into_cubic().reduce_degree();
+            assert_eq!((reduced.start, reduced.end), (quadratic.start, quadratic.end));
+            assert_relative_eq!(reduced.ctrl, quadratic.ctrl, epsilon = 1e-12);
+            assert!(error < 1e-14);
+            let (reduced, error) = CubicBezier3::from(quadratic.into_cubic()).reduce_degree();
+            assert_relative_eq!(reduced.ctrl, Vec3::from(quadratic.ctrl), epsilon = 1e-12);
+            assert!(error < 1e-14);
+        }
+        #[test] fn to_quadratics() {
+            for curve in curves().iter().copied() {
+                for &tolerance in &[1., 0.1, 1e-3, 1e-6] {
+                    let quadratics = curve.to_quadratics(tolerance);
+                    assert_eq!(quadratics[0].start, curve.start);
+                    assert_eq!(quadratics[quadratics.len() - 1].end, curve.end);
+                    for pair in quadratics.windows(2) {
+                        assert_eq!(pair[0].end, pair[1].start);
+                    }
+                    // Each part approximates a piece of the curve between dyadic factors.
+                    let mut t0 = 0_f64;
+                    for quadratic in quadratics.iter() {
+                        let t1 = (0..=32).map(|k| 0.5_f64.powi(k)).filter(|&w| t0 % w == 0. && t0 + w <= 1.).map(|w| t0 + w)
+                            .min_by(|&a, &b| curve.evaluate(a).distance(quadratic.end).partial_cmp(&curve.evaluate(b).distance(quadratic.end)).unwrap())
+                            .unwrap();
+                        for i in 0..=20 {
+                            let t = i as f64 / 20.;
+                            let expected = curve.evaluate(t0 + (t1 - t0) * t);
+                            assert!(quadratic.evaluate(t).distance(expected) <= tolerance * (1. + 1e-6) + 1e-12);
+                        }
+                        t0 = t1;
+                    }
+                    assert_eq!(t0, 1.);
+                }
+                // Halving the tolerance roughly multiplies the number of parts by the cube root of 2.
+                let coarse = curve.to_quadratics(1e-4).len() as f64;
+                let fine = curve.to_quadratics(1e-4 / 64.).len() as f64;
+                assert!(fine >= coarse * 2. && fine <= coarse * 8., "{} {}", coarse, fine);
+            }
+            // Quadratic curves are a single part.
+            let quadratic = QuadraticBezier2 { start: Vec2::new(1_f64, 2.), ctrl: Vec2::new(-3., 5.), end: Vec2::new(4., 4.) };
+            assert_eq!(quadratic.into_cubic().to_quadratics(1e-9).len(), 1);
+            let parts: Vec<_> = curves()[0].to_quadratics(10.);
+            assert_eq!(parts.len(), 1);
+        }
+        #[test]
+        #[should_panic]
+        fn zero_tolerance() {
+            curves()[0].to_quadratics(0.);
+        }
+    }
+    #[cfg(feature="std")]
+    mod flatten {
+        use crate::vec::repr_c::{Vec2, Vec3};
+        use crate::geom::repr_c::{LineSegment2, LineSegment3};
+        use crate::bezier::repr_c::{QuadraticBezier2, CubicBezier2, CubicBezier3};
+        use alloc::vec::Vec;
+
+        fn loop_curve() -> CubicBezier2<f64> {
+            CubicBezier2 { start: Vec2::new(0., 0.), ctrl0: Vec2::new(10., 10.), ctrl1: Vec2::new(-6., 10.), end: Vec2::new(4., 0.) }
+        }
+        fn assert_flattened(curve: CubicBezier2<f64>, segments: &[LineSegment2<f64>], tolerance: f64) {
+            assert_eq!(segments[0].start, curve.start);
+            assert_eq!(segments[segments.len() - 1].end, curve.end);
+            for w in segments.windows(2) {
+                assert_eq!(w[0].end, w[1].start);
+            }
+            assert!(segments.iter().all(|s| s.start != s.end));
+            // Every point of the curve is close to the polyline.
+            for i in 0..=500 {
+                let p = curve.evaluate(i as f64 / 500.);
+                let d = segments.iter().map(|s| s.distance_to_point(p)).fold(f64::MAX, f64::min);
+                assert!(d <= tolerance, "{} > {}", d, tolerance);
+            }
+        }
+
+        #[test]
+        fn within_tolerance() {
+            let s_curve = CubicBezier2 { start: Vec2::new(0., 0.), ctrl0: Vec2::new(6., 8.), ctrl1: Vec2::new(-2., -8.), end: Vec2::new(4., 0.) };
+            // A cusp.
+            let cusp = CubicBezier2 { start: Vec2::new(0., 0.), ctrl0: Vec2::new(4., 4.), ctrl1: Vec2::new(0., 4.), end: Vec2::new(4., 0.) };
+            for &curve in &[loop_curve(), s_curve, cusp] {
+                let mut previous = 0;
+                for &tolerance in &[1., 0.1, 1e-3, 1e-5] {
+                    let segments = curve.flatten(tolerance);
+                    assert_flattened(curve, &segments, tolerance);
+                    assert!(segments.len() > previous);
+                    previous = segments.len();
+                }
+            }
+        }
+        #[test]
+        fn adaptive() {
+            // Nearly straight at the start, tightly turning at the end.
+            let curve = CubicBezier2 { start: Vec2::new(0., 0.), ctrl0: Vec2::new(10., 0.), ctrl1: Vec2::new(10., 1.), end: Vec2::new(9., 1.) };
+            let segments = curve.flatten(1e-3);
+            let lengths: Vec<f64> = segments.iter().map(|s| s.start.distance(s.end)).collect();
+            let (shortest, longest) = lengths.iter().fold((f64::MAX, 0_f64), |(a, b), &l| (a.min(l), b.max(l)));
+            assert!(longest > 10. * shortest, "{} {}", shortest, longest);
+            // The longest segments are along the straight stretch.
+            assert!(lengths[0] > lengths[lengths.len() - 1]);
+            // Quadratic curves grow as the square root of the inverse of the tolerance.
+            let parabola = QuadraticBezier2 { start: Vec2::new(-1., 1.), ctrl: Vec2::new(0., -1.), end: Vec2::new(1., 1.) };
+            let (coarse, fine) = (parabola.flatten(1e-2).len(), parabola.flatten(1e-4).len());
+            assert!(fine >= 5 * coarse && fine <= 20 * coarse, "{} {}", coarse, fine);
+        }
+        #[test]
+        fn degenerate() {
+            let p = Vec2::new(1., 2.);
+            assert!(CubicBezier2 { start: p, ctrl0: p, ctrl1: p, end: p }.flatten(0.1).is_empty());
+            let line = CubicBezier2::from(Vec2::new(0., 0.) .. Vec2::new(3., 4.));
+            assert_eq!(line.flatten(1e-9), [LineSegment2 { start: line.start, end: line.end }]);
+            // A closed curve.
+            let closed = CubicBezier2 { start: p, ctrl0: Vec2::new(5., 5.), ctrl1: Vec2::new(-5., 5.), end: p };
+            let segments = closed.flatten(1e-3);
+            assert_eq!(segments[0].start, p);
+            assert_eq!(segments[segments.len() - 1].end, p);
+        }
+        #[test]
+        fn three_d() {
+            let curve = CubicBezier3 { start: Vec3::new(0., 0., 0.), ctrl0: Vec3::new(2., 0., 3.), ctrl1: Vec3::new(2., 2., -3.), end: Vec3::new(0., 2., 0.) };
+            let segments: Vec<LineSegment3<f64>> = curve.flatten(1e-3);
+            assert_eq!((segments[0].start, segments[segments.len() - 1].end), (curve.start, curve.end));
+            for i in 0..=1000 {
+                let p = curve.evaluate(i as f64 / 1000.);
+                assert!(segments.iter().any(|s| s.distance_to_point(p) <= 1e-3));
+            }
+        }
+        #[test]
+        #[should_panic]
+        fn zero_tolerance() {
+            let _ = loop_curve().flatten(0.);
+        }
+    }
+    #[cfg(feature="std")]
+    mod intersections {
+        use crate::vec::repr_c::Vec2;
+        use crate::geom::repr_c::LineSegment2;
+        use crate::bezier::CurvesOverlapError;
+        use crate::bezier::repr_c::{QuadraticBezier2, CubicBezier2};
+        use alloc::vec::Vec;
+
+        // Intersections with a dense polyline approximating `other`.
+        fn brute_force(curve: CubicBezier2<f64>, other: CubicBezier2<f64>) -> Vec<f64> {
+            let n = 4000;
+            let mut ts: Vec<f64> = (0..n).flat_map(|i| {
+                let seg = LineSegment2 { start: other.evaluate(i as f64 / n as f64), end: other.evaluate((i + 1) as f64 / n as f64) };
+                curve.intersect_line_segment(seg).map(|(t, _)| t).collect::<Vec<_>>()
+            }).collect();
+            ts.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+            // Crossings at the ends of segments are found twice.
+            ts.dedup_by(|b, a| (*b - *a).abs() < 1e-6);
+            ts
+        }
+        fn arch() -> CubicBezier2<f64> {
+            CubicBezier2 { start: Vec2::new(0., 0.), ctrl0: Vec2::new(0., 4.), ctrl1: Vec2::new(4., 4.), end: Vec2::new(4., 0.) }
+        }
+        fn s_curve() -> CubicBezier2<f64> {
+            CubicBezier2 { start: Vec2::new(0., 0.), ctrl0: Vec2::new(6., 8.), ctrl1: Vec2::new(-2., -8.), end: Vec2::new(4., 0.) }
+        }
+        fn loop_curve() -> CubicBezier2<f64> {
+            CubicBezier2 { start: Vec2::new(0., 0.), ctrl0: Vec2::new(10., 10.), ctrl1: Vec2::new(-6., 10.), end: Vec2::new(4., 0.) }
+        }
+        fn check(curve: CubicBezier2<f64>, other: CubicBezier2<f64>, tolerance: f64) -> Vec<(f64, f64)> {
+            let hits = curve.intersections(other, tolerance).unwrap();
+            for &(t, u) in hits.iter() {
+                assert!(curve.evaluate(t).distance(other.evaluate(u)) <= tolerance);
+            }
+            assert!(hits.windows(2).all(|w| w[0].0 <= w[1].0));
+            hits
+        }
+
+        #[test]
+        fn matches_brute_force() {
+            let (sin, cos) = 1_f64.sin_cos();
+            let rotated = |c: CubicBezier2<f64>| -> CubicBezier2<f64> {
+                c.into_vector().map(|p| Vec2::new(2. + (p.x - 2.) * cos - p.y * sin, (p.x - 2.) * sin + p.y * cos)).into()
+            };
+            let pairs = [
+                (arch(), s_curve()),
+                (s_curve(), rotated(s_curve())),
+                (loop_curve(), s_curve()),
+                (loop_curve(), rotated(loop_curve())),
+                (arch(), rotated(arch())),
+                (arch(), CubicBezier2::from(Vec2::new(-1., 1.) .. Vec2::new(5., 1.5))),
+            ];
+            for tolerance in [1e-4, 1e-10].iter().cloned() {
+                for &(a, b) in pairs.iter() {
+                    let hits = check(a, b, tolerance);
+                    let expected = brute_force(a, b);
+                    assert_eq!(hits.len(), expected.len(), "{:?} vs {:?}", hits, expected);
+                    for (&(t, _), &e) in hits.iter().zip(expected.iter()) {
+                        assert_relative_eq!(t, e, epsilon = 1e-5);
+                    }
+                }
+            }
+            assert!(check(s_curve(), rotated(s_curve()), 1e-9).len() >= 3);
+        }
+        #[test]
+        fn tangent() {
+            // Touches the top of the arch, at (2, 3).
+            let valley = CubicBezier2 { start: Vec2::new(0., 6.), ctrl0: Vec2::new(0., 2.), ctrl1: Vec2::new(4., 2.), end: Vec2::new(4., 6.) };
+            for tolerance in [1e-3, 1e-6, 1e-12].iter().cloned() {
+                let hits = check(arch(), valley, tolerance);
+                assert_eq!(hits.len(), 1);
+                assert_relative_eq!(arch().evaluate(hits[0].0), Vec2::new(2., 3.), epsilon = tolerance.sqrt() * 4.);
+            }
+        }
+        #[test]
+        fn ends() {
+            // Consecutive curves of a path, which meet smoothly.
+            let [first, second] = s_curve().split(0.3);
+            let hits = check(first, second, 1e-9);
+            assert_eq!(hits.len(), 1);
+            assert_relative_eq!(first.evaluate(hits[0].0), s_curve().evaluate(0.3), epsilon = 1e-9);
+            // A curve ending on another one.
+            let line = CubicBezier2::from(Vec2::new(2., 5.) .. Vec2::new(2., 3.));
+            let hits = check(arch(), line, 1e-9);
+            assert_eq!(hits.len(), 1);
+            assert_relative_eq!(hits[0].0, 0.5, epsilon = 1e-9);
+            assert_relative_eq!(hits[0].1, 1., epsilon = 1e-9);
+        }
+        #[test]
+        fn misses() {
+            let far: CubicBezier2<f64> = arch().into_vector().map(|p| p + Vec2::new(0., 10.)).into();
+            assert_eq!(arch().intersections(far, 1e-9), Ok(Vec::new()));
+            // Only the control polygons overlap.
+            let inside = CubicBezier2 { start: Vec2::new(1., 0.5), ctrl0: Vec2::new(2., 1.), ctrl1: Vec2::new(3., 0.5), end: Vec2::new(3.5, 0.) };
+            assert_eq!(check(arch(), inside, 1e-9).len(), 0);
+        }
+        #[test]
+        fn overlaps() {
+            let curve = loop_curve();
+            assert_eq!(curve.intersections(curve, 1e-9), Err(CurvesOverlapError));
+            let [first, second] = curve.split(0.6);
+            let [_, middle] = first.split(0.2);
+            assert_eq!(curve.intersections(middle, 1e-9), Err(CurvesOverlapError));
+            assert_eq!(middle.intersections(curve, 1e-9), Err(CurvesOverlapError));
+            assert_eq!(curve.intersections(second.reversed(), 1e-9), Err(CurvesOverlapError));
+            // Overlapping at the middle of both.
+            let [a, _] = curve.split(0.7);
+            let [_, b] = curve.split(0.4);
+            assert_eq!(a.intersections(b, 1e-9), Err(CurvesOverlapError));
+            // Almost the same curve, within the tolerance.
+            let moved: CubicBezier2<f64> = curve.into_vector().map(|p| p + Vec2::new(1e-9, 0.)).into();
+            assert_eq!(curve.intersections(moved, 1e-6), Err(CurvesOverlapError));
+        }
+        #[test]
+        fn quadratic() {
+            let q = QuadraticBezier2 { start: Vec2::new(0., 4.), ctrl: Vec2::new(2., -2.), end: Vec2::new(4., 4.) };
+            let hits = q.intersections(arch(), 1e-9).unwrap();
+            let cubic_hits = q.into_cubic().intersections(arch(), 1e-9).unwrap();
+            assert_eq!(hits, cubic_hits);
+            assert_eq!(hits.len(), 2);
+            let swapped = arch().intersections(q, 1e-9).unwrap();
+            for (&(t, u), &(u2, t2)) in hits.iter().zip(swapped.iter()) {
+                assert_relative_eq!(t, t2, epsilon = 1e-9);
+                assert_relative_eq!(u, u2, epsilon = 1e-9);
+            }
+            assert_eq!(q.intersections(q, 1e-9), Err(CurvesOverlapError));
+        }
+        #[test]
+        fn degenerate() {
+            // A curve collapsed to a point on the other one.
+            let p = arch().evaluate(0.25);
+            let point = CubicBezier2 { start: p, ctrl0: p, ctrl1: p, end: p };
+            let hits = check(arch(), point, 1e-9);
+            assert_eq!(hits.len(), 1);
+            assert_relative_eq!(hits[0].0, 0.25, epsilon = 1e-6);
+            // NaN coordinates must not make sorting the intersections panic.
+            let nan = CubicBezier2 { start: Vec2::new(0., 4.), ctrl0: Vec2::new(f64::NAN, 0.), ctrl1: Vec2::new(2., -2.), end: Vec2::new(4., 4.) };
+            let _ = arch().intersections(nan, 1e-9);
+            let _ = nan.intersections(arch(), 1e-9);
+            let _ = nan.intersections(nan, 1e-9);
+        }
+        #[test]
+        #[should_panic]
+        fn zero_tolerance() {
+            let _ = arch().intersections(s_curve(), 0.);
+        }
+    }
+    #[cfg(feature="std")]
+    mod intersect_line_segment {
+        use crate::vec::repr_c::Vec2;
+        use crate::geom::repr_c::LineSegment2;
+        use crate::bezier::repr_c::{QuadraticBezier2, CubicBezier2};
+        use alloc::vec::Vec;
+
+        // Crossings found by sign changes over a dense sampling, away from the ends of the segment.
+        fn brute_force(curve: CubicBezier2<f64>, seg: LineSegment2<f64>) -> Vec<f64> {
+            let direction = seg.end - seg.start;
+            let side = |t: f64| Vec2::new(-direction.y, direction.x).dot(curve.evaluate(t) - seg.start);
+            let n = 100_000;
+            (0..n).filter_map(|i| {
+                let (t0, t1) = (i as f64 / n as f64, (i + 1) as f64 / n as f64);
+                if (side(t0) < 0.) == (side(t1) < 0.) {
+                    return None;
+                }
+                let s = direction.dot(curve.evaluate(t0) - seg.start) / direction.magnitude_squared();
+                if s > 0. && s < 1. { Some(t0) } else { None }
+            }).collect()
+        }
+        fn loop_curve() -> CubicBezier2<f64> {
+            CubicBezier2 {
+                start: Vec2::new(0., 0.),
+                ctrl0: Vec2::new(10., 10.),
+                ctrl1: Vec2::new(-6., 10.),
+                end: Vec2::new(4., 0.),
+            }
+        }
+        fn s_curve() -> CubicBezier2<f64> {
+            CubicBezier2 { start: Vec2::new(0., 0.), ctrl0: Vec2::new(6., 8.), ctrl1: Vec2::new(-2., -8.), end: Vec2::new(4., 0.) }
+        }
+
+        #[test]
+        fn matches_brute_force() {
+            let segments = [
+                LineSegment2 { start: Vec2::new(-5., 2.), end: Vec2::new(10., 2.) },
+                LineSegment2 { start: Vec2::new(-5., 2.), end: Vec2::new(1.5, 2.) },
+                LineSegment2 { start: Vec2::new(1., -5.), end: Vec2::new(2.5, 9.) },
+                LineSegment2 { start: Vec2::new(-1., -0.7), end: Vec2::new(5., 0.9) },
+                LineSegment2 { start: Vec2::new(-1., 0.1), end: Vec2::new(5., -0.1) },
+                LineSegment2 { start: Vec2::new(-3., -1.), end: Vec2::new(7., -1.5) },
+                LineSegment2 { start: Vec2::new(12., 0.), end: Vec2::new(12., 12.) },
+            ];
+            for curve in [loop_curve(), s_curve()].iter().cloned() {
+                for seg in segments.iter().cloned() {
+                    let hits: Vec<_> = curve.intersect_line_segment(seg).collect();
+                    let expected = brute_force(curve, seg);
+                    assert_eq!(hits.len(), expected.len(), "{:?} vs {:?}", hits, expected);
+                    for (&(t, p), &e) in hits.iter().zip(expected.iter()) {
+                        assert!(t >= e && t <= e + 1e-5, "{} vs {}", t, e);
+                        assert_relative_eq!(p, curve.evaluate(t));
+                        assert!(seg.distance_to_point(p) < 1e-12);
+                    }
+                    assert!(hits.windows(2).all(|w| w[0].0 < w[1].0));
+                }
+            }
+            // Three crossings, through the center of symmetry of the curve.
+            let seg = LineSegment2 { start: Vec2::new(-1., 0.1), end: Vec2::new(5., -0.1) };
+            assert_eq!(s_curve().intersect_line_segment(seg).count(), 3);
+        }
+        #[test]
+        fn quadratic_matches_cubic() {
+            let curve = QuadraticBezier2 { start: Vec2::new(-2., 4.), ctrl: Vec2::new(1., -4.), end: Vec2::new(3., 5.) };
+            let seg = LineSegment2 { start: Vec2::new(-3., 0.5), end: Vec2::new(4., 2.) };
+            let hits: Vec<_> = curve.intersect_line_segment(seg).collect();
+            let cubic_hits: Vec<_> = curve.into_cubic().intersect_line_segment(seg).collect();
+            assert_eq!(hits.len(), 2);
+            assert_eq!(hits.len(), cubic_hits.len());
+            for (a, b) in hits.iter().zip(cubic_hits.iter()) {
+                assert_relative_eq!(a.0, b.0, epsilon = 1e-12);
+                assert_relative_eq!(a.1, b.1, epsilon = 1e-12);
+            }
+        }
+        #[test]
+        fn tangent() {
+            // An arch whose top, at t = 0.5, is at y = 3.
+            let curve = CubicBezier2 { start: Vec2::new(0., 0.), ctrl0: Vec2::new(0., 4.), ctrl1: Vec2::new(4., 4.), end: Vec2::new(4., 0.) };
+            let seg = LineSegment2 { start: Vec2::new(-1., 3.), end: Vec2::new(5., 3.) };
+            let hits: Vec<_> = curve.intersect_line_segment(seg).collect();
+            assert_eq!(hits, [(0.5, Vec2::new(2., 3.))]);
+            // The same, rotated, so that the touch isn't exact any more.
+            let (sin, cos) = 0.3_f64.sin_cos();
+            let rotate = |p: Vec2<f64>| Vec2::new(p.x * cos - p.y * sin, p.x * sin + p.y * cos);
+            let curve: CubicBezier2<f64> = curve.into_vector().map(rotate).into();
+            let seg = LineSegment2 { start: rotate(seg.start), end: rotate(seg.end) };
+            let hits: Vec<_> = curve.intersect_line_segment(seg).collect();
+            assert!(!hits.is_empty() && hits.len() <= 2, "{:?}", hits);
+            for (t, p) in hits {
+                assert_relative_eq!(t, 0.5, epsilon = 1e-6);
+                assert_relative_eq!(p, rotate(Vec2::new(2., 3.)), epsilon = 1e-6);
+            }
+        }
+        #[test]
+        fn ends_are_included() {
+            let curve = QuadraticBezier2 { start: Vec2::new(-2., 4.), ctrl: Vec2::new(0., -4.), end: Vec2::new(2., 4.) };
+            // The curve's ends on the segment.
+            let seg = LineSegment2 { start: Vec2::new(-3., 4.), end: Vec2::new(3., 4.) };
+            let hits: Vec<_> = curve.intersect_line_segment(seg).collect();
+            assert_eq!(hits, [(0., Vec2::new(-2., 4.)), (1., Vec2::new(2., 4.))]);
+            // The segment's ends on the curve.
+            let seg = LineSegment2 { start: Vec2::new(-1., 1.), end: Vec2::new(1., 1.) };
+            let hits: Vec<_> = curve.intersect_line_segment(seg).collect();
+            assert_eq!(hits, [(0.25, Vec2::new(-1., 1.)), (0.75, Vec2::new(1., 1.))]);
+        }
+        #[test]
+        fn misses() {
+            let curve = QuadraticBezier2 { start: Vec2::new(-2., 4.), ctrl: Vec2::new(0., -4.), end: Vec2::new(2., 4.) };
+            // The line crosses the curve, but not the segment.
+            let seg = LineSegment2 { start: Vec2::new(-0.5, 1.), end: Vec2::new(0.5, 1.) };
+            assert_eq!(curve.intersect_line_segment(seg).count(), 0);
+            // The line crosses the parabola beyond the curve's ends.
+            let seg = LineSegment2 { start: Vec2::new(-5., 9.), end: Vec2::new(5., 9.) };
+            assert_eq!(curve.intersect_line_segment(seg).count(), 0);
+            // Below the parabola.
+            let seg = LineSegment2 { start: Vec2::new(-5., -1.), end: Vec2::new(5., -1.) };
+            assert_eq!(curve.intersect_line_segment(seg).count(), 0);
+        }
+        #[test]
+        fn degenerate() {
+            let curve = QuadraticBezier2 { start: Vec2::new(-2., 4.), ctrl: Vec2::new(0., -4.), end: Vec2::new(2., 4.) };
+            let seg = LineSegment2 { start: Vec2::new(0., 0.), end: Vec2::new(0., 0.) };
+            assert_eq!(curve.intersect_line_segment(seg).count(), 0);
+            // Along the segment.
+            let curve = CubicBezier2 { start: Vec2::new(0., 0.), ctrl0: Vec2::new(1., 1.), ctrl1: Vec2::new(2., 2.), end: Vec2::new(3., 3.) };
+            let seg = LineSegment2 { start: Vec2::new(-1., -1.), end: Vec2::new(5., 5.) };
+            assert_eq!(curve.intersect_line_segment(seg).count(), 0);
+            // A point.
+            let p = Vec2::new(1., 1.);
+            let curve = CubicBezier2 { start: p, ctrl0: p, ctrl1: p, end: p };
+            assert_eq!(curve.intersect_line_segment(LineSegment2 { start: Vec2::new(0., 0.), end: Vec2::new(2., 0.) }).count(), 0);
+        }
+    }
+    mod inspection {
+        use crate::vec::repr_c::Vec2;
+        use crate::bezier::repr_c::CubicBezier2;
+
+        fn s_curve() -> CubicBezier2<f64> {
+            CubicBezier2 { start: Vec2::new(0., 0.), ctrl0: Vec2::new(3., 4.), ctrl1: Vec2::new(-1., 4.), end: Vec2::new(5., 1.) }
+        }
+
+        #[cfg(feature="std")]
+        #[test] fn comb_is_perpendicular() {
+            let curve = s_curve();
+            let comb = curve.curvature_comb(33, 2.);
+            assert_eq!(comb.len(), 33);
+            for (i, seg) in comb.iter().enumerate() {
+                let t = i as f64 / 32.;
+                assert_relative_eq!(seg.start, curve.evaluate(t));
+                let d = seg.end - seg.start;
+                assert_relative_eq!(d.dot(curve.normalized_tangent(t)), 0., epsilon = 1e-9);
+                // Length is |curvature| * scale; check it against the osculating circle of 3 close points.
+                let h = 1e-4;
+                let (a, b, c) = (curve.evaluate(t - h), curve.evaluate(t), curve.evaluate(t + h));
+                let area2 = (b - a).x * (c - a).y - (b - a).y * (c - a).x;
+                let curvature = 2. * area2 / ((b - a).magnitude() * (c - b).magnitude() * (c - a).magnitude());
+                assert_relative_eq!(d.magnitude(), curvature.abs() * 2., epsilon = 1e-4, max_relative = 1e-3);
+                // The comb stands on the convex side, away from the center of curvature.
+                let left = Vec2::new(-(c - a).y, (c - a).x);
+                assert!(d.dot(left) * curvature <= 0.);
+            }
+            assert!(curve.curvature_comb(0, 1.).is_empty());
+            assert_eq!(curve.curvature_comb(1, 1.)[0].start, curve.start);
+        }
+        #[cfg(feature="std")]
+        #[test] fn straight_line_has_flat_comb() {
+            let line = CubicBezier2 { start: Vec2::new(1_f64, 1.), ctrl0: Vec2::new(2., 3.), ctrl1: Vec2::new(4., 7.), end: Vec2::new(5., 9.) };
+            for seg in line.curvature_comb(17, 10.) {
+                assert_relative_eq!(seg.start, seg.end, epsilon = 1e-12);
+            }
+            assert_relative_eq!(line.max_curvature().1, 0., epsilon = 1e-12);
+        }
+        #[test] fn max_curvature() {
+            let curve = s_curve();
+            let (t, k) = curve.max_curvature();
+            for i in 0..=10_000 {
+                let c = curve.signed_curvature_at(i as f64 / 10_000.).abs();
+                assert!(c <= k * (1. + 1e-9), "{} > {} (at {})", c, k, t);
+            }
+            assert_relative_eq!(curve.signed_curvature_at(t).abs(), k);
+            // A cusp.
+            let cusp = CubicBezier2 { start: Vec2::new(0_f64, 0.), ctrl0: Vec2::new(2., 1.), ctrl1: Vec2::new(0., 1.), end: Vec2::new(2., 0.) };
+            assert!(cusp.max_curvature().1 > 1e6);
+        }
+        #[test] fn inflection_points() {
+            let cross = |c: CubicBezier2<f64>, t: f64| {
+                let (d1, d2) = (c.evaluate_derivative(t), c.evaluate_second_derivative(t));
+                d1.x * d2.y - d1.y * d2.x
+            };
+            // Symmetric S shape.
+            let symmetric = CubicBezier2 { start: Vec2::new(0., 0.), ctrl0: Vec2::new(1., 1.), ctrl1: Vec2::new(2., -1.), end: Vec2::new(3., 0.) };
+            assert_eq!(symmetric.inflection_points(), (Some(0.5), None));
+            // Two inflections, and the result doesn't depend on the scale.
+            for &scale in &[1e-6, 1., 1e6] {
+                let curve = s_curve();
+                let curve = CubicBezier2 { start: curve.start * scale, ctrl0: curve.ctrl0 * scale, ctrl1: curve.ctrl1 * scale, end: curve.end * scale };
+                let (t0, t1) = curve.inflection_points();
+                let (t0, t1) = (t0.unwrap(), t1.unwrap());
+                assert!(0. < t0 && t0 < t1 && t1 < 1.);
+                for &t in &[t0, t1] {
+                    assert_relative_eq!(cross(curve, t) / (scale * scale), 0., epsilon = 1e-9);
+                    assert!(cross(curve, t - 0.01) * cross(curve, t + 0.01) < 0.);
+                }
+            }
+        }
+        #[test] fn no_inflection_points() {
+            // Elevated from a quadratic curve.
+            let (start, ctrl, end) = (Vec2::new(0_f64, 0.), Vec2::new(1., 3.), Vec2::new(3., 0.3));
+            let elevated = CubicBezier2 { start, ctrl0: start + (ctrl - start) * (2. / 3.), ctrl1: end + (ctrl - end) * (2. / 3.), end };
+            assert_eq!(elevated.inflection_points(), (None, None));
+            assert_eq!(CubicBezier2 { start: Vec2::new(0., 0.), ctrl0: Vec2::new(1., 2.), ctrl1: Vec2::new(3., 2.), end: Vec2::new(4., 0.) }.inflection_points(), (None, None));
+            // Straight lines, including with control points out of order, and a point.
+            let line = CubicBezier2 { start: Vec2::new(1_f64, 1.), ctrl0: Vec2::new(2., 3.), ctrl1: Vec2::new(4., 7.), end: Vec2::new(5., 9.) };
+            assert_eq!(line.inflection_points(), (None, None));
+            let line = CubicBezier2 { start: Vec2::new(0_f64, 0.), ctrl0: Vec2::new(3., 3.), ctrl1: Vec2::new(-1., -1.), end: Vec2::new(2., 2.) };
+            assert_eq!(line.inflection_points(), (None, None));
+            let p = Vec2::new(1_f32, 2.);
+            assert_eq!(CubicBezier2 { start: p, ctrl0: p, ctrl1: p, end: p }.inflection_points(), (None, None));
+        }
+        #[cfg(feature="std")]
+        #[test] fn split_at_inflections() {
+            let curve = s_curve();
+            let (t0, t1) = curve.inflection_points();
+            let pieces = curve.split_at_inflections();
+            assert_eq!(pieces.len(), 3);
+            assert_eq!(pieces[0].start, curve.start);
+            assert_relative_eq!(pieces[0].end, curve.evaluate(t0.unwrap()), epsilon = 1e-12);
+            assert_relative_eq!(pieces[1].end, curve.evaluate(t1.unwrap()), epsilon = 1e-12);
+            assert_relative_eq!(pieces[2].end, curve.end, epsilon = 1e-12);
+            for pair in pieces.windows(2) {
+                assert_eq!(pair[0].end, pair[1].start);
+            }
+            for piece in &pieces {
+                assert_eq!(piece.inflection_points(), (None, None));
+            }
+            let symmetric = CubicBezier2 { start: Vec2::new(0., 0.), ctrl0: Vec2::new(1., 1.), ctrl1: Vec2::new(2., -1.), end: Vec2::new(3., 0.) };
+            assert_eq!(symmetric.split_at_inflections().len(), 2);
+            // A loop has no inflection either.
+            let looped = CubicBezier2 { start: Vec2::new(0., 0.), ctrl0: Vec2::new(4., 1.), ctrl1: Vec2::new(-3., 1.), end: Vec2::new(1., 0.) };
+            assert_eq!(looped.split_at_inflections(), alloc::vec![looped]);
+            let arch = CubicBezier2 { start: Vec2::new(0., 0.), ctrl0: Vec2::new(1., 2.), ctrl1: Vec2::new(3., 2.), end: Vec2::new(4., 0.) };
+            assert_eq!(arch.split_at_inflections(), alloc::vec![arch]);
+        }
+    }
+    mod continuity {
+        use crate::vec::repr_c::Vec2;
+        use crate::bezier::repr_c::CubicBezier2;
+
+        fn first() -> CubicBezier2<f64> {
+            CubicBezier2 { start: Vec2::new(0., 0.), ctrl0: Vec2::new(1., 2.), ctrl1: Vec2::new(3., 3.), end: Vec2::new(4., 2.) }
+        }
+        fn next_with_handle(handle: Vec2<f64>) -> CubicBezier2<f64> {
+            CubicBezier2 { start: Vec2::new(4., 2.), ctrl0: Vec2::new(4., 2.) + handle, ctrl1: Vec2::new(7., 0.), end: Vec2::new(8., 1.) }
+        }
+
+        #[test] fn classification() {
+            let a = first();
+            let c1 = next_with_handle(Vec2::new(1., -1.));
+            assert!(a.is_c1_continuous_with(&c1, 1e-9));
+            assert!(a.is_g1_continuous_with(&c1, 1e-9));
+            let g1 = next_with_handle(Vec2::new(0.25, -0.25));
+            assert!(!a.is_c1_continuous_with(&g1, 1e-9));
+            assert!(a.is_g1_continuous_with(&g1, 1e-9));
+            let kink = next_with_handle(Vec2::new(1., -0.8));
+            assert!(!a.is_c1_continuous_with(&kink, 1e-9));
+            assert!(!a.is_g1_continuous_with(&kink, 1e-9));
+            assert!(a.is_g1_continuous_with(&kink, 0.2));
+            let reversed = next_with_handle(Vec2::new(-1., 1.));
+            assert!(!a.is_g1_continuous_with(&reversed, 1e-9));
+            let mut gap = c1;
+            gap.start.x += 0.01;
+            gap.ctrl0.x += 0.01;
+            assert!(!a.is_c1_continuous_with(&gap, 1e-3));
+            assert!(!a.is_g1_continuous_with(&gap, 1e-3));
+            // A zero-length handle uses the curve's actual tangent.
+            let mut zero_handle = next_with_handle(Vec2::zero());
+            zero_handle.ctrl1 = Vec2::new(5., 1.);
+            assert!(a.is_g1_continuous_with(&zero_handle, 1e-9));
+        }
+        #[test] fn enforcement() {
+            let handles = [Vec2::new(1., -0.8), Vec2::new(0., 3.), Vec2::new(-2., -0.5), Vec2::new(0.25, -0.25), Vec2::zero()];
+            for &handle in handles.iter() {
+                let (mut a, mut b) = (first(), next_with_handle(handle));
+                let (a0, b0) = (a, b);
+                a.make_g1_continuous_with(&mut b);
+                assert!(a.is_g1_continuous_with(&b, 1e-9), "{:?} {:?}", a, b);
+                assert_eq!((a.start, a.ctrl0, a.end), (a0.start, a0.ctrl0, a0.end));
+                assert_eq!((b.start, b.ctrl1, b.end), (b0.start, b0.ctrl1, b0.end));
+                assert_relative_eq!((a.end - a.ctrl1).magnitude(), (a0.end - a0.ctrl1).magnitude(), epsilon = 1e-12);
+                assert_relative_eq!((b.ctrl0 - b.start).magnitude(), (b0.ctrl0 - b0.start).magnitude(), epsilon = 1e-12);
+            }
+            // Already G1 joins are left as they are.
+            let (mut a, mut b) = (first(), next_with_handle(Vec2::new(0.25, -0.25)));
+            let (a0, b0) = (a, b);
+            a.make_g1_continuous_with(&mut b);
+            assert_relative_eq!(a.ctrl1, a0.ctrl1, epsilon = 1e-12);
+            assert_relative_eq!(b.ctrl0, b0.ctrl0, epsilon = 1e-12);
+        }
+    }
+    mod reflection {
+        use crate::vec::repr_c::{Vec2, Vec3};
+        use crate::geom::repr_c::{Line2, Plane};
+        use crate::bezier::repr_c::{CubicBezier2, CubicBezier3, QuadraticBezier2};
+
+        #[test] fn across_line() {
+            let curve = CubicBezier2 { start: Vec2::new(0.3_f64, -1.), ctrl0: Vec2::new(2., 5.), ctrl1: Vec2::new(-4., 1.5), end: Vec2::new(7., 2.) };
+            let line = Line2::from_points(Vec2::new(1., 2.), Vec2::new(-3., 0.5));
+            let mirrored = curve.reflected_across_line(line);
+            let back = mirrored.reflected_across_line(line);
+            for (a, b) in [(back.start, curve.start), (back.ctrl0, curve.ctrl0), (back.ctrl1, curve.ctrl1), (back.end, curve.end)].iter() {
+                assert_relative_eq!(*a, *b, epsilon = 1e-12);
+            }
+            for i in 0..=16 {
+                let t = i as f64 / 16.;
+                assert_relative_eq!(mirrored.evaluate(t), line.reflected_point(curve.evaluate(t)), epsilon = 1e-12);
+                assert_relative_eq!(line.signed_distance_to_point(mirrored.evaluate(t)), -line.signed_distance_to_point(curve.evaluate(t)), epsilon = 1e-12);
+            }
+            // Generalizes flipped_x() and flipped_y().
+            assert_eq!(curve.reflected_across_line(Line2::new(Vec2::unit_x(), 0.)), curve.flipped_x());
+            assert_eq!(curve.reflected_across_line(Line2::new(Vec2::unit_y(), 0.)), curve.flipped_y());
+            let quadratic = QuadraticBezier2 { start: curve.start, ctrl: curve.ctrl0, end: curve.end };
+            assert_eq!(quadratic.reflected_across_line(Line2::new(Vec2::unit_y(), 0.)), quadratic.flipped_y());
+        }
+        #[test] fn across_plane() {
+            let curve = CubicBezier3 {
+                start: Vec3::new(0.3_f64, -1., 2.), ctrl0: Vec3::new(2., 5., -1.),
+                ctrl1: Vec3::new(-4., 1.5, 0.), end: Vec3::new(7., 2., 3.),
+            };
+            let plane = Plane::from_points(Vec3::new(1., 0., 0.), Vec3::new(0., 1., 0.5), Vec3::new(0., 0., 2.));
+            let mirrored = curve.reflected_across_plane(plane);
+            let back = mirrored.reflected_across_plane(plane);
+            for (a, b) in [(back.start, curve.start), (back.ctrl0, curve.ctrl0), (back.ctrl1, curve.ctrl1), (back.end, curve.end)].iter() {
+                assert_relative_eq!(*a, *b, epsilon = 1e-12);
+            }
+            assert_eq!(curve.reflected_across_plane(Plane::new(Vec3::unit_z(), 0.)), curve.flipped_z());
+            let projected = curve.projected_onto_plane(plane);
+            for i in 0..=16 {
+                let t = i as f64 / 16.;
+                assert_relative_eq!(plane.signed_distance_to_point(projected.evaluate(t)), 0., epsilon = 1e-12);
+                assert_relative_eq!(projected.evaluate(t), plane.projected_point(curve.evaluate(t)), epsilon = 1e-12);
+            }
+        }
+    }
+    mod rational {
+        use crate::vec::repr_c::{Vec2, Vec3};
+        use crate::bezier::repr_c::{QuadraticBezier2, CubicBezier2, RationalQuadraticBezier2};
+        use std::f64::consts::FRAC_PI_2;
+
+        fn samples() -> impl Iterator<Item=f64> {
+            (0..=64).map(|i| i as f64 / 64.)
+        }
+
+        #[test] fn quarter_circle_is_exact() {
+            let arc = RationalQuadraticBezier2::from_circular_arc(Vec2::zero(), 1_f64, 0., FRAC_PI_2);
+            assert_relative_eq!(arc.start, Vec2::unit_x());
+            assert_relative_eq!(arc.ctrl, Vec2::one());
+            assert_relative_eq!(arc.end, Vec2::unit_y(), epsilon = 1e-15);
+            let cubic = CubicBezier2::<f64>::unit_quarter_circle();
+            let mut cubic_max_error = 0_f64;
+            for t in samples() {
+                assert_relative_eq!(arc.evaluate(t).magnitude(), 1., epsilon = 1e-15);
+                cubic_max_error = cubic_max_error.max((cubic.evaluate(t).magnitude() - 1.).abs());
+            }
+            // The cubic approximation is off by about 2.7e-4.
+            assert!(cubic_max_error > 1e-4);
+        }
+        #[test] fn arcs_are_exact() {
+            let center = Vec2::new(3_f64, -2.);
+            for &(start, sweep) in [(0.3_f64, 2.5_f64), (-2., -1.), (4., 0.1), (1., -3.)].iter() {
+                let arc = RationalQuadraticBezier2::from_circular_arc(center, 5., start, sweep);
+                for t in samples() {
+                    let p = arc.evaluate(t) - center;
+                    assert_relative_eq!(p.magnitude(), 5., epsilon = 1e-12);
+                    // The tangent is orthogonal to the radius, and turns the right way.
+                    let d = arc.evaluate_derivative(t);
+                    assert_relative_eq!(p.dot(d) / d.magnitude(), 0., epsilon = 1e-9);
+                    assert!((p.x * d.y - p.y * d.x) * sweep > 0.);
+                }
+                assert_relative_eq!(arc.end, center + Vec2::new((start + sweep).cos(), (start + sweep).sin()) * 5., epsilon = 1e-12);
+            }
+        }
+        #[test] fn derivative_and_split() {
+            let curve = RationalQuadraticBezier2 {
+                start: Vec2::new(0_f64, 0.),
+                ctrl: Vec2::new(2., 3.),
+                end: Vec2::new(5., -1.),
+                weights: Vec3::new(0.5, 3., 1.5),
+            };
+            let h = 1e-6;
+            for t in samples().skip(1).take(63) {
+                let numerical = (curve.evaluate(t + h) - curve.evaluate(t - h)) / (2. * h);
+                assert_relative_eq!(curve.evaluate_derivative(t), numerical, epsilon = 1e-6, max_relative = 1e-6);
+            }
+            let [a, b] = curve.split(0.3);
+            for s in samples() {
+                assert_relative_eq!(a.evaluate(s), curve.evaluate(s * 0.3), epsilon = 1e-12);
+                assert_relative_eq!(b.evaluate(s), curve.evaluate(0.3 + s * 0.7), epsilon = 1e-12);
+            }
+            let r = curve.reversed();
+            for t in samples() {
+                assert_relative_eq!(r.evaluate(t), curve.evaluate(1. - t), epsilon = 1e-12);
+            }
+            // Equal weights give back the polynomial curve.
+            let poly = QuadraticBezier2 { start: curve.start, ctrl: curve.ctrl, end: curve.end };
+            let rational = RationalQuadraticBezier2::from(poly);
+            for t in samples() {
+                assert_relative_eq!(rational.evaluate(t), poly.evaluate(t), epsilon = 1e-12);
+                assert_relative_eq!(rational.evaluate_derivative(t), poly.evaluate_derivative(t), epsilon = 1e-12);
+            }
+        }
+        #[cfg(feature="std")]
+        #[test] fn to_cubic_beziers() {
+            let arc = RationalQuadraticBezier2::from_circular_arc(Vec2::new(1_f64, 1.), 10., 0.5, 3.);
+            for &tolerance in [1e-2, 1e-5, 1e-9].iter() {
+                let cubics = arc.to_cubic_beziers(tolerance);
+                assert_eq!(cubics[0].start, arc.start);
+                assert_eq!(cubics.last().unwrap().end, arc.end);
+                for pair in cubics.windows(2) {
+                    assert_eq!(pair[0].end, pair[1].start);
+                }
+                for c in &cubics {
+                    for t in samples() {
+                        let d = (c.evaluate(t) - Vec2::new(1., 1.)).magnitude();
+                        assert!((d - 10.).abs() <= tolerance, "{} for tolerance {}", d, tolerance);
+                    }
+                }
+            }
+        }
+    }
+    mod stroke {
+        use super::*;
+        use crate::vec::repr_c::Vec2;
+        use crate::bezier::repr_c::CubicBezier2;
+
+        fn line(a: Vec2<f64>, b: Vec2<f64>) -> CubicBezier2<f64> {
+            CubicBezier2 { start: a, ctrl0: a + (b - a) / 3., ctrl1: b - (b - a) / 3., end: b }
+        }
+
+        fn assert_closed(outline: &[CubicBezier2<f64>]) {
+            assert!(!outline.is_empty());
+            for pair in outline.windows(2) {
+                assert_eq!(pair[0].end, pair[1].start);
+            }
+            assert_eq!(outline.last().unwrap().end, outline[0].start);
+        }
+
+        // Signed area of the flattened outline (positive when counter-clockwise).
+        fn signed_area(outline: &[CubicBezier2<f64>]) -> f64 {
+            let steps = 64;
+            let mut area = 0.;
+            for c in outline {
+                let mut prev = c.start;
+                for i in 1..=steps {
+                    let p = c.evaluate(i as f64 / steps as f64);
+                    area += prev.x * p.y - p.x * prev.y;
+                    prev = p;
+                }
+            }
+            area / 2.
+        }
+
+        #[test]
+        fn straight_line_area() {
+            use core::f64::consts::PI;
+            let c = line(Vec2::new(1., 2.), Vec2::new(11., 2.));
+            let (length, width) = (10., 2.);
+            for &(cap, expected) in &[
+                (LineCap::Butt, length * width),
+                (LineCap::Square, length * width + width * width),
+                (LineCap::Round, length * width + PI * width * width / 4.),
+            ] {
+                let outline = c.stroke_outline(width, cap, LineJoin::Miter(4.), 1e-3);
+                assert_closed(&outline);
+                assert_relative_eq!(-signed_area(&outline), expected, epsilon = 1e-2);
+            }
+        }
+
+        #[test]
+        fn curved_outline_is_closed_and_clockwise() {
+            let curves = [
+                CubicBezier2 { start: Vec2::new(0., 0.), ctrl0: Vec2::new(1., 2.), ctrl1: Vec2::new(3., 2.), end: Vec2::new(4., 0.) },
+                CubicBezier2 { start: Vec2::new(0., 0.), ctrl0: Vec2::new(4., 3.), ctrl1: Vec2::new(-1., 3.), end: Vec2::new(3., 0.) },
+                CubicBezier2 { start: Vec2::new(0., 0.), ctrl0: Vec2::new(0., 0.), ctrl1: Vec2::new(2., 2.), end: Vec2::new(2., 2.) },
+            ];
+            for &c in &curves {
+                for &cap in &[LineCap::Butt, LineCap::Round, LineCap::Square] {
+                    for &join in &[LineJoin::Miter(4.), LineJoin::Round, LineJoin::Bevel] {
+                        let outline = c.stroke_outline(0.25, cap, join, 1e-3);
+                        assert_closed(&outline);
+                        assert!(signed_area(&outline) < 0.);
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn offset_is_within_tolerance() {
+            let c = CubicBezier2 { start: Vec2::new(0., 0.), ctrl0: Vec2::new(1., 2.), ctrl1: Vec2::new(3., 2.), end: Vec2::new(4., 0.) };
+            let tolerance = 1e-3;
+            let outline = c.stroke_outline(1., LineCap::Butt, LineJoin::Bevel, tolerance);
+            // Every point of the outline (except on the caps) is half the width away from the curve.
+            let sides = outline.iter().filter(|o| (o.start - o.end).magnitude() < 0.99);
+            for o in sides {
+                for i in 0..=8 {
+                    let p = o.evaluate(i as f64 / 8.);
+                    let (_, closest) = c.binary_search_point_by_steps(p, 256, 1e-9);
+                    assert_relative_eq!((p - closest).magnitude(), 0.5, epsilon = 10. * tolerance);
+                }
+            }
+        }
+
+        #[test]
+        fn joins() {
+            // An L-shaped path turning left at (10, 0); the outer corner is at (11, -1).
+            let path = [line(Vec2::new(0., 0.), Vec2::new(10., 0.)), line(Vec2::new(10., 0.), Vec2::new(10., 10.))];
+            let corner = Vec2::new(11., -1.);
+            let has_corner = |outline: &[CubicBezier2<f64>]| outline.iter().any(|c| (c.end - corner).magnitude() < 1e-9);
+            let miter = CubicBezier2::stroke_outline_of_path(&path, 2., LineCap::Butt, LineJoin::Miter(2.), 1e-3);
+            assert_closed(&miter);
+            assert!(has_corner(&miter));
+            // The miter ratio of a right angle is sqrt(2), which exceeds this limit.
+            let limited = CubicBezier2::stroke_outline_of_path(&path, 2., LineCap::Butt, LineJoin::Miter(1.4), 1e-3);
+            assert!(!has_corner(&limited));
+            let bevel = CubicBezier2::stroke_outline_of_path(&path, 2., LineCap::Butt, LineJoin::Bevel, 1e-3);
+            assert_closed(&bevel);
+            assert!(!has_corner(&bevel));
+            assert_eq!(limited, bevel);
+            let round = CubicBezier2::stroke_outline_of_path(&path, 2., LineCap::Butt, LineJoin::Round, 1e-3);
+            assert_closed(&round);
+            let (mut min, mut max) = (round[0].start, round[0].start);
+            for c in &round {
+                for i in 0..=16 {
+                    let p = c.evaluate(i as f64 / 16.);
+                    min = Vec2::partial_min(min, p);
+                    max = Vec2::partial_max(max, p);
+                }
+            }
+            assert_relative_eq!(min, Vec2::new(0., -1.), epsilon = 1e-3);
+            assert_relative_eq!(max, Vec2::new(11., 10.), epsilon = 1e-3);
+            assert!(CubicBezier2::<f64>::stroke_outline_of_path(&[], 2., LineCap::Butt, LineJoin::Round, 1e-3).is_empty());
+        }
+    }
+
+    #[cfg(all(nightly, feature="std"))]
+    mod bench {
+        extern crate test;
+        use self::test::{Bencher, black_box};
+        use crate::vec::Vec2;
+        use crate::bezier::CubicBezier2;
+
+        fn s_curve() -> CubicBezier2<f32> {
+            CubicBezier2 { start: Vec2::new(0., 0.), ctrl0: Vec2::new(600., 800.), ctrl1: Vec2::new(-200., -800.), end: Vec2::new(400., 0.) }
+        }
+
+        #[bench]
+        fn flatten_quarter_pixel(b: &mut Bencher) {
+            let curve = s_curve();
+            b.iter(|| black_box(curve).flatten(black_box(0.25)));
+        }
+        #[bench]
+        fn flatten_hundredth_pixel(b: &mut Bencher) {
+            let curve = s_curve();
+            b.iter(|| black_box(curve).flatten(black_box(0.01)));
+        }
+        // For comparison, evaluating at as many regular intervals as there are segments at a quarter pixel.
+        #[bench]
+        fn evaluate_regular_intervals(b: &mut Bencher) {
+            let curve = s_curve();
+            let n = curve.flatten(0.25).len();
+            b.iter(|| {
+                let curve = black_box(curve);
+                (0..=n).map(|i| curve.evaluate(i as f32 / n as f32)).fold(Vec2::<f32>::zero(), |sum, p| sum + p)
+            });
+        }
+    }
 }
@@ -0,0 +1,324 @@
+//! Bounding volume hierarchies, for fast queries over large sets of curves.
+//!
+//! This module is only available with the `std` feature.
+
+use num_traits::real::Real;
+use std::ops::Range;
+use alloc::vec::Vec;
+use crate::vec::repr_c::Vec2;
+use crate::geom::repr_c::Aabr;
+use crate::bezier::repr_c::CubicBezier2;
+
+/// Maximum number of curves in a leaf node.
+const LEAF_SIZE: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum NodeKind {
+    /// Covers `indices[range]`.
+    Leaf(usize, usize),
+    /// Children are at `nodes[i]` and `nodes[i + 1]`.
+    Inner(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Node<T> {
+    aabr: Aabr<T>,
+    kind: NodeKind,
+}
+
+/// A bounding volume hierarchy over a slice of cubic Bézier curves, which speeds up
+/// hit-testing queries when there are many curves.
+///
+/// Nodes are bounded by the Aabrs of the control points of the curves they contain
+/// (which, unlike the exact bounds of a curve, are cheap to compute, and always contain it).
+/// The hierarchy is built in `O(n log n)` by recursively splitting curves at the median of
+/// their centers, along the longest axis.
+///
+/// ```
+/// # use vek::{CurveBvh, CubicBezier2, Vec2, Aabr};
+/// let curves: Vec<_> = (0..100).map(|i| {
+///     let o = Vec2::new(i as f32 * 10., 0.);
+///     CubicBezier2 { start: o, ctrl0: o + Vec2::new(2., 5.), ctrl1: o + Vec2::new(6., 5.), end: o + Vec2::new(8., 0.) }
+/// }).collect();
+/// let bvh = CurveBvh::new(&curves);
+///
+/// let (i, _t, _point) = bvh.closest_segment_to_point(Vec2::new(424., 10.), 16, 0.0001).unwrap();
+/// assert_eq!(i, 42);
+///
+/// let hits = bvh.segments_intersecting_aabr(Aabr { min: Vec2::new(205., 0.), max: Vec2::new(225., 1.) });
+/// assert_eq!(hits, vec![20, 21, 22]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurveBvh<'a, T: 'a> {
+    curves: &'a [CubicBezier2<T>],
+    indices: Vec<usize>,
+    nodes: Vec<Node<T>>,
+}
+
+fn control_aabr<T: Real>(c: &CubicBezier2<T>) -> Aabr<T> {
+    Aabr::new_empty(c.start)
+        .expanded_to_contain_point(c.ctrl0)
+        .expanded_to_contain_point(c.ctrl1)
+        .expanded_to_contain_point(c.end)
+}
+
+// Unlike `Aabr::collides_with_aabr()`, touching rectangles overlap.
+fn overlaps<T: Real>(a: Aabr<T>, b: Aabr<T>) -> bool {
+    a.min.x <= b.max.x && b.min.x <= a.max.x && a.min.y <= b.max.y && b.min.y <= a.max.y
+}
+
+fn distance_squared_to_aabr<T: Real>(aabr: Aabr<T>, p: Vec2<T>) -> T {
+    let closest = Vec2::new(p.x.max(aabr.min.x).min(aabr.max.x), p.y.max(aabr.min.y).min(aabr.max.y));
+    closest.distance_squared(p)
+}
+
+// Decides whether the curve passes through `aabr` by subdividing it. When `depth` runs out,
+// the (tiny) piece is assumed to intersect.
+fn curve_intersects_aabr<T: Real>(curve: CubicBezier2<T>, aabr: Aabr<T>, depth: u32) -> bool {
+    if !overlaps(control_aabr(&curve), aabr) {
+        return false;
+    }
+    if aabr.contains_point(curve.start) || aabr.contains_point(curve.end) || depth == 0 {
+        return true;
+    }
+    let [a, b] = curve.split((T::one() + T::one()).recip());
+    curve_intersects_aabr(a, aabr, depth - 1) || curve_intersects_aabr(b, aabr, depth - 1)
+}
+
+impl<'a, T: Real> CurveBvh<'a, T> {
+    /// Builds a hierarchy over the given curves.
+    pub fn new(curves: &'a [CubicBezier2<T>]) -> Self {
+        let aabrs: Vec<_> = curves.iter().map(control_aabr).collect();
+        let mut bvh = Self {
+            curves,
+            indices: (0..curves.len()).collect(),
+            nodes: Vec::with_capacity(2 * curves.len() / LEAF_SIZE + 1),
+        };
+        if !curves.is_empty() {
+            bvh.nodes.push(Node { aabr: aabrs[0], kind: NodeKind::Leaf(0, 0) });
+            bvh.build(0, 0..curves.len(), &aabrs);
+        }
+        bvh
+    }
+    fn build(&mut self, node: usize, range: Range<usize>, aabrs: &[Aabr<T>]) {
+        let indices = &mut self.indices[range.clone()];
+        let aabr = indices.iter().skip(1).fold(aabrs[indices[0]], |a, &i| a.union(aabrs[i]));
+        if indices.len() <= LEAF_SIZE {
+            self.nodes[node] = Node { aabr, kind: NodeKind::Leaf(range.start, range.end) };
+            return;
+        }
+        let centers = indices.iter().skip(1).fold(Aabr::new_empty(aabrs[indices[0]].center()), |a, &i| {
+            a.expanded_to_contain_point(aabrs[i].center())
+        });
+        let size = centers.size();
+        let mid = indices.len() / 2;
+        if size.w >= size.h {
+            indices.select_nth_unstable_by(mid, |&a, &b| aabrs[a].center().x.partial_cmp(&aabrs[b].center().x).unwrap_or(std::cmp::Ordering::Equal));
+        } else {
+            indices.select_nth_unstable_by(mid, |&a, &b| aabrs[a].center().y.partial_cmp(&aabrs[b].center().y).unwrap_or(std::cmp::Ordering::Equal));
+        }
+        let children = self.nodes.len();
+        let placeholder = Node { aabr, kind: NodeKind::Leaf(0, 0) };
+        self.nodes.push(placeholder);
+        self.nodes.push(placeholder);
+        self.nodes[node] = Node { aabr, kind: NodeKind::Inner(children) };
+        let mid = range.start + mid;
+        self.build(children, range.start..mid, aabrs);
+        self.build(children + 1, mid..range.end, aabrs);
+    }
+    /// Gets the curves this hierarchy was built over.
+    pub fn curves(&self) -> &'a [CubicBezier2<T>] {
+        self.curves
+    }
+    /// Gets the bounds of all curves, or `None` if there are none.
+    pub fn aabr(&self) -> Option<Aabr<T>> {
+        self.nodes.first().map(|n| n.aabr)
+    }
+
+    /// Finds the curve which is closest to `p`, returning its index, and the interpolation
+    /// factor and position of the closest point found on it.
+    ///
+    /// Points on individual curves are searched for with `CubicBezier2::binary_search_point_by_steps()`,
+    /// using the given `steps` and `epsilon`; Curves whose bounds are farther than the best
+    /// candidate found so far are skipped.
+    ///
+    /// The result is the best of these per-curve searches, which are local: each of them can
+    /// settle on a point which is not the closest one of its curve (e.g if `steps` is too low
+    /// for it to sample the right part of a curve which comes back close to itself). So the
+    /// result is not guaranteed to be the globally closest point, but it is the same as
+    /// searching each curve in turn and keeping the closest result.
+    ///
+    /// Returns `None` if there are no curves.
+    pub fn closest_segment_to_point(&self, p: Vec2<T>, steps: u16, epsilon: T) -> Option<(usize, T, Vec2<T>)> {
+        self.closest_segment_to_point_counting(p, steps, epsilon).0
+    }
+    fn closest_segment_to_point_counting(&self, p: Vec2<T>, steps: u16, epsilon: T) -> (Option<(usize, T, Vec2<T>)>, usize) {
+        let mut best: Option<(usize, T, Vec2<T>)> = None;
+        let mut best_d2 = T::max_value();
+        let mut visited = 0;
+        let mut stack = Vec::with_capacity(64);
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+        while let Some(node) = stack.pop() {
+            let Node { aabr, kind } = self.nodes[node];
+            if distance_squared_to_aabr(aabr, p) > best_d2 {
+                continue;
+            }
+            visited += 1;
+            match kind {
+                NodeKind::Leaf(start, end) => for &i in &self.indices[start..end] {
+                    let curve = self.curves[i];
+                    if distance_squared_to_aabr(control_aabr(&curve), p) > best_d2 {
+                        continue;
+                    }
+                    let (t, point) = curve.binary_search_point_by_steps(p, steps, epsilon);
+                    let d2 = point.distance_squared(p);
+                    let wins_tie = match best { Some(b) => d2 == best_d2 && i < b.0, None => true };
+                    if d2 < best_d2 || wins_tie {
+                        best_d2 = d2;
+                        best = Some((i, t, point));
+                    }
+                },
+                NodeKind::Inner(children) => {
+                    // Visit the nearest child first, so that it tightens the bound for the other.
+                    let (a, b) = (children, children + 1);
+                    if distance_squared_to_aabr(self.nodes[a].aabr, p) <= distance_squared_to_aabr(self.nodes[b].aabr, p) {
+                        stack.push(b);
+                        stack.push(a);
+                    } else {
+                        stack.push(a);
+                        stack.push(b);
+                    }
+                },
+            }
+        }
+        (best, visited)
+    }
+
+    /// Gets the indices, in increasing order, of the curves which pass through `query` (bounds included).
+    ///
+    /// Curves are tested by recursive subdivision, after being culled by the hierarchy.
+    /// Parts of curves which are much smaller than the curve itself (by a factor of about
+    /// a million) are considered to intersect if their bounds do.
+    pub fn segments_intersecting_aabr(&self, query: Aabr<T>) -> Vec<usize> {
+        self.segments_intersecting_aabr_counting(query).0
+    }
+    fn segments_intersecting_aabr_counting(&self, query: Aabr<T>) -> (Vec<usize>, usize) {
+        let mut hits = Vec::new();
+        let mut visited = 0;
+        let mut stack = Vec::with_capacity(64);
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+        while let Some(node) = stack.pop() {
+            let Node { aabr, kind } = self.nodes[node];
+            if !overlaps(aabr, query) {
+                continue;
+            }
+            visited += 1;
+            match kind {
+                NodeKind::Leaf(start, end) => hits.extend(self.indices[start..end].iter().cloned().filter(|&i| {
+                    curve_intersects_aabr(self.curves[i], query, 20)
+                })),
+                NodeKind::Inner(children) => {
+                    stack.push(children);
+                    stack.push(children + 1);
+                },
+            }
+        }
+        hits.sort_unstable();
+        (hits, visited)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Small deterministic generator, so that tests are reproducible.
+    struct Lcg(u64);
+    impl Lcg {
+        fn next(&mut self) -> f64 {
+            self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (self.0 >> 11) as f64 / (1_u64 << 53) as f64
+        }
+        fn point(&mut self, scale: f64) -> Vec2<f64> {
+            Vec2::new(self.next() * scale, self.next() * scale)
+        }
+    }
+
+    fn random_curves(rng: &mut Lcg, n: usize) -> Vec<CubicBezier2<f64>> {
+        (0..n).map(|_| {
+            let o = rng.point(1000.);
+            CubicBezier2 { start: o, ctrl0: o + rng.point(20.), ctrl1: o + rng.point(20.), end: o + rng.point(20.) }
+        }).collect()
+    }
+
+    #[test]
+    fn empty() {
+        let bvh = CurveBvh::<f32>::new(&[]);
+        assert_eq!(bvh.aabr(), None);
+        assert_eq!(bvh.closest_segment_to_point(Vec2::zero(), 8, 0.001), None);
+        assert!(bvh.segments_intersecting_aabr(Aabr { min: Vec2::zero(), max: Vec2::one() }).is_empty());
+    }
+
+    #[test]
+    fn closest_matches_brute_force() {
+        let mut rng = Lcg(7);
+        let curves = random_curves(&mut rng, 1000);
+        let bvh = CurveBvh::new(&curves);
+        let mut total_visited = 0;
+        for _ in 0..50 {
+            let p = rng.point(1000.);
+            let brute = curves.iter().map(|c| c.binary_search_point_by_steps(p, 16, 1e-6).1.distance(p)).fold(f64::MAX, f64::min);
+            let ((i, t, point), visited) = match bvh.closest_segment_to_point_counting(p, 16, 1e-6) {
+                (Some(hit), visited) => (hit, visited),
+                (None, _) => panic!("no hit"),
+            };
+            assert_eq!(point.distance(p), brute);
+            assert_eq!(curves[i].evaluate(t), point);
+            total_visited += visited;
+        }
+        // Only a small fraction of the nodes should be visited.
+        assert!(total_visited / 50 < bvh.nodes.len() / 20, "visited {} nodes on average, out of {}", total_visited / 50, bvh.nodes.len());
+    }
+
+    #[test]
+    fn aabr_query_matches_brute_force() {
+        let mut rng = Lcg(42);
+        let curves = random_curves(&mut rng, 1000);
+        let bvh = CurveBvh::new(&curves);
+        let mut total_visited = 0;
+        for _ in 0..50 {
+            let min = rng.point(1000.);
+            let query = Aabr { min, max: min + rng.point(50.) };
+            let brute: Vec<usize> = (0..curves.len()).filter(|&i| curve_intersects_aabr(curves[i], query, 20)).collect();
+            let (hits, visited) = bvh.segments_intersecting_aabr_counting(query);
+            assert_eq!(hits, brute);
+            // Check against sampling too (which can only miss hits).
+            for (i, c) in curves.iter().enumerate() {
+                if (0..=32).any(|s| query.contains_point(c.evaluate(s as f64 / 32.))) {
+                    assert!(hits.binary_search(&i).is_ok());
+                }
+            }
+            total_visited += visited;
+        }
+        assert!(total_visited / 50 < bvh.nodes.len() / 20, "visited {} nodes on average, out of {}", total_visited / 50, bvh.nodes.len());
+    }
+
+    #[test]
+    fn curves_crossing_query_without_endpoints_inside() {
+        // The curve's endpoints and control points are outside the query, but it passes through it.
+        let curve = CubicBezier2 {
+            start: Vec2::new(-10_f32, 0.),
+            ctrl0: Vec2::new(-10., 10.),
+            ctrl1: Vec2::new(10., 10.),
+            end: Vec2::new(10., 0.),
+        };
+        let curves = [curve];
+        let bvh = CurveBvh::new(&curves);
+        assert_eq!(bvh.segments_intersecting_aabr(Aabr { min: Vec2::new(-1., 7.), max: Vec2::new(1., 8.) }), &[0]);
+        assert!(bvh.segments_intersecting_aabr(Aabr { min: Vec2::new(-1., 8.), max: Vec2::new(1., 9.) }).is_empty());
+    }
+}
@@ -2,16 +2,143 @@
 
 // NOTE: in this module, the type parameters <P,E> usually stand for Position and Extent.
 
-use num_traits::{real::Real, FloatConst, Zero, One, AsPrimitive};
+use num_traits::{real::Real, FloatConst, Zero, One, AsPrimitive, Bounded, CheckedSub, CheckedMul, PrimInt};
 use approx::RelativeEq;
 use std::ops::*;
 use std::ops::Add;
-use crate::ops::Clamp;
+use crate::ops::{Clamp, MulAdd, atan2, tan};
+
+pub mod predicates;
 
 // WISH: add useful impls to this module (inclusing basic conversions from rect to vec pairs)
 // WISH: lerp for all shapes
 // WISH: More intersections (e.g line_segment vs box, etc)
 
+/// A convex shape which can tell its farthest point along any direction, which is all that
+/// GJK-style collision detection algorithms need to know about it.
+///
+/// ```
+/// # use vek::{Aabb, Sphere, Vec3, MinkowskiDifference, SupportMap};
+/// let aabb = Aabb { min: Vec3::new(-1_f32, -2., -3.), max: Vec3::new(1., 2., 3.) };
+/// assert_eq!(aabb.support_point(Vec3::new(1., -1., 0.5)), Vec3::new(1., -2., 3.));
+/// let sphere = Sphere::new(Vec3::new(10_f32, 0., 0.), 2.);
+/// assert_eq!(sphere.support_point(-Vec3::unit_x()), Vec3::new(8., 0., 0.));
+/// // The shapes collide if and only if their Minkowski difference contains the origin.
+/// let difference = MinkowskiDifference { a: aabb, b: sphere };
+/// assert_eq!(difference.support_point(Vec3::unit_x()), Vec3::new(-7., 2., 3.));
+/// ```
+pub trait SupportMap {
+    /// The type of points and directions.
+    type Vector;
+    /// Gets a point of this shape which is the farthest along `direction`, i.e one that
+    /// maximizes `p.dot(direction)`. `direction` doesn't need to be normalized.
+    ///
+    /// When several points qualify (including when `direction` is zero), which one is returned
+    /// is documented by each implementation, and is always the same for the same inputs.
+    fn support_point(&self, direction: Self::Vector) -> Self::Vector;
+}
+
+impl<S: SupportMap + ?Sized> SupportMap for &S {
+    type Vector = S::Vector;
+    fn support_point(&self, direction: Self::Vector) -> Self::Vector {
+        (**self).support_point(direction)
+    }
+}
+
+/// The Minkowski difference of two convex shapes, i.e the set of all `a - b` for all points `a`
+/// of the first shape and `b` of the second.
+///
+/// It contains the origin if and only if both shapes intersect, and its distance to the origin
+/// is the distance between both shapes.
+#[derive(Debug, Default, Clone, Copy, Hash, Eq, PartialEq)]
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+pub struct MinkowskiDifference<A, B> {
+    /// The shape that is subtracted from.
+    pub a: A,
+    /// The shape that is subtracted.
+    pub b: B,
+}
+
+impl<A, B> SupportMap for MinkowskiDifference<A, B>
+    where A: SupportMap, B: SupportMap<Vector=A::Vector>,
+          A::Vector: Copy + Sub<Output=A::Vector> + Neg<Output=A::Vector>
+{
+    type Vector = A::Vector;
+    fn support_point(&self, direction: A::Vector) -> A::Vector {
+        self.a.support_point(direction) - self.b.support_point(-direction)
+    }
+}
+
+/// Clips the parametric line `origin + direction * t`, for `t` in `[t0, t1]`, to the box
+/// `[min, max]` (boundary included) with the Liang–Barsky algorithm, returning the clipped
+/// range of `t`, or `None` if it is empty.
+fn liang_barsky<T: Real>(origin: &[T], direction: &[T], min: &[T], max: &[T], mut t0: T, mut t1: T) -> Option<(T, T)> {
+    for (((&o, &d), &lo), &hi) in origin.iter().zip(direction).zip(min).zip(max) {
+        for &(p, q) in &[(-d, o - lo), (d, hi - o)] {
+            if p == T::zero() {
+                // Parallel to this boundary, and outside of it.
+                if q < T::zero() {
+                    return None;
+                }
+                continue;
+            }
+            let r = q / p;
+            if p < T::zero() {
+                if r > t1 {
+                    return None;
+                }
+                if r > t0 {
+                    t0 = r;
+                }
+            } else {
+                if r < t0 {
+                    return None;
+                }
+                if r < t1 {
+                    t1 = r;
+                }
+            }
+        }
+    }
+    Some((t0, t1))
+}
+
+/// Same as `liang_barsky()` for the 2D segment from `start` to `end`, but with exact integer
+/// arithmetic: the clipped range of `t` is returned as two fractions `(numerator, denominator)`
+/// with positive denominators.
+fn liang_barsky_exact(start: [i128; 2], end: [i128; 2], min: [i128; 2], max: [i128; 2]) -> Option<((i128, i128), (i128, i128))> {
+    let (mut t0, mut t1) = ((0, 1), (1, 1));
+    for i in 0..2 {
+        let d = end[i] - start[i];
+        for &(p, q) in &[(-d, start[i] - min[i]), (d, max[i] - start[i])] {
+            if p == 0 {
+                if q < 0 {
+                    return None;
+                }
+                continue;
+            }
+            // r = q / p, with a positive denominator.
+            let r = if p < 0 { (-q, -p) } else { (q, p) };
+            if p < 0 {
+                if r.0 * t1.1 > t1.0 * r.1 {
+                    return None;
+                }
+                if r.0 * t0.1 > t0.0 * r.1 {
+                    t0 = r;
+                }
+            } else {
+                if r.0 * t0.1 < t0.0 * r.1 {
+                    return None;
+                }
+                if r.0 * t1.1 < t1.0 * r.1 {
+                    t1 = r;
+                }
+            }
+        }
+    }
+    Some((t0, t1))
+}
+
 macro_rules! geom_impl_line_segment {
     ($LineSegment:ident $Vec:ident) => {
         impl<T> From<Range<$Vec<T>>> for $LineSegment<T> {
@@ -47,6 +174,30 @@ macro_rules! geom_impl_line_segment {
                 self.projected_point(p).distance(p)
             }
 
+            /// Project the given point onto the line segment, treating the segment as a single
+            /// point if it is no longer than `epsilon`.
+            ///
+            /// This is `projected_point()` with an explicit tolerance, for use with geometry
+            /// whose scale is far from 1 (see `ops::relative_epsilon_for()`).
+            pub fn projected_point_with_epsilon(self, p: $Vec<T>, epsilon: T) -> $Vec<T> where T: Real + Add<T, Output=T> {
+                let len_sq = self.start.distance_squared(self.end);
+
+                if len_sq <= epsilon * epsilon {
+                    self.start
+                } else {
+                    let t = ((p - self.start).dot(self.end - self.start) / len_sq)
+                        .max(Zero::zero())
+                        .min(One::one());
+                    self.start + (self.end - self.start) * t
+                }
+            }
+
+            /// Get the smallest distance between the line segment and a point, treating the
+            /// segment as a single point if it is no longer than `epsilon`.
+            pub fn distance_to_point_with_epsilon(self, p: $Vec<T>, epsilon: T) -> T where T: Real + Add<T, Output=T> {
+                self.projected_point_with_epsilon(p, epsilon).distance(p)
+            }
+
             /// Converts this line to a line of another type, using the `as` conversion.
             pub fn as_<D>(self) -> $LineSegment<D> where T: AsPrimitive<D>, D: 'static + Copy {
                 let Self { start, end } = self;
@@ -56,6 +207,55 @@ macro_rules! geom_impl_line_segment {
     };
 }
 
+macro_rules! geom_impl_line2_or_plane {
+    ($Shape:ident ($Shape_s:expr) $Vec:ident) => {
+        impl<T: Real> $Shape<T> {
+            /// Creates a new
+            #[doc=$Shape_s]
+            /// from its unit normal and its signed distance from the origin.
+            ///
+            /// This doesn't check if `normal` is normalized.
+            pub fn new(normal: $Vec<T>, distance: T) -> Self {
+                Self { normal, distance }
+            }
+            /// Creates the
+            #[doc=$Shape_s]
+            /// which goes through `point` and is orthogonal to `normal` (which is normalized first).
+            pub fn from_point_and_normal(point: $Vec<T>, normal: $Vec<T>) -> Self {
+                let normal = normal.normalized();
+                Self { normal, distance: normal.dot(point) }
+            }
+            /// Gets the signed distance from this shape to a point; positive on the side the normal
+            /// points to, and negative on the other side.
+            pub fn signed_distance_to_point(self, p: $Vec<T>) -> T {
+                self.normal.dot(p) - self.distance
+            }
+            /// Gets the orthogonal projection of a point onto this shape, i.e the closest point of this
+            /// shape to `p`.
+            pub fn projected_point(self, p: $Vec<T>) -> $Vec<T> {
+                p - self.normal * self.signed_distance_to_point(p)
+            }
+            /// Gets the mirror image of a point across this shape.
+            pub fn reflected_point(self, p: $Vec<T>) -> $Vec<T> {
+                let d = self.signed_distance_to_point(p);
+                p - self.normal * (d + d)
+            }
+            /// Gets the mirror image of a direction across this shape (the position of the shape
+            /// doesn't matter for directions).
+            pub fn reflected_direction(self, v: $Vec<T>) -> $Vec<T> {
+                let d = self.normal.dot(v);
+                v - self.normal * (d + d)
+            }
+            /// Gets the same
+            #[doc=$Shape_s]
+            /// with its normal pointing the other way, which swaps its positive and negative sides.
+            pub fn flipped(self) -> Self {
+                Self { normal: -self.normal, distance: -self.distance }
+            }
+        }
+    };
+}
+
 macro_rules! geom_impl_rect_or_rect3 {
     (
         $Rect:ident $Vec:ident $Extent:ident ($(($p_s:expr) $p:ident $split_at_p:ident)+) ($($e:ident)+)
@@ -111,6 +311,8 @@ macro_rules! geom_impl_rect_or_rect3 {
         }
         impl<T> $Rect<T,T> where T: Copy + Add<T, Output=T> {
             /// Converts this into the matching axis-aligned bounding shape representation.
+            ///
+            /// This wraps (or panics in debug builds) if `position + extent` overflows `T`.
             pub fn $into_aab(self) -> $Aab<T> {
                 self.into()
             }
@@ -215,7 +417,30 @@ macro_rules! geom_impl_aabr_or_aabb {
         collides_with_aab: $collides_with_aab:ident
         collision_vector_with_aab: $collision_vector_with_aab:ident
     ) => {
+        /// The default value is `empty()`.
+        impl<T: Bounded> Default for $Aab<T> {
+            fn default() -> Self {
+                Self::empty()
+            }
+        }
+
         impl<T> $Aab<T> {
+            /// Gets the empty bounding shape, which has `min` set to the greatest value of `T` and
+            /// `max` to the lowest one, on every axis.
+            ///
+            /// This is the identity of `union()` and `expanded_to_contain_point()`, so it is the
+            /// value to start from when computing the bounds of a set of points or shapes.
+            /// It is not valid (see `is_valid()`), and contains no point.
+            ///
+            /// Not to be confused with `new_empty()`, which creates a zero-sized shape at a given point.
+            /// Also note that for floating-point `T`, the bounds are the greatest finite values,
+            /// not the infinities.
+            pub fn empty() -> Self where T: Bounded {
+                Self {
+                    min: $Vec { $($p: T::max_value(),)+ },
+                    max: $Vec { $($p: T::min_value(),)+ },
+                }
+            }
             /// Is this bounding shape valid ?
             /// True only if all elements of `self.min` are less than or equal to those of `self.max`.
             pub fn is_valid(&self) -> bool where T: PartialOrd {
@@ -248,11 +473,21 @@ macro_rules! geom_impl_aabr_or_aabb {
                 (self.min + self.max) / (T::one() + T::one())
             }
             /// Gets this bounding shape's total size.
+            ///
+            /// With unsigned elements, this wraps (or panics in debug builds) if this shape is
+            /// not valid, and with signed elements, if the size doesn't fit in `T`.
+            /// See `checked_size()`.
             pub fn size(self) -> $Extent<T>
                 where T: Copy + Sub<T, Output=T>
             {
                 self.$into_rect().extent()
             }
+            /// Gets this bounding shape's total size, or `None` if it doesn't fit in `T`
+            /// (which includes invalid shapes with unsigned elements).
+            pub fn checked_size(self) -> Option<$Extent<T>> where T: CheckedSub {
+                let size = $Vec { $($p: self.max.$p.checked_sub(&self.min.$p)?,)+ };
+                Some(size.into())
+            }
             /// Gets this bounding shape's half size.
             pub fn half_size(self) -> $Extent<T>
                 where T: Copy + Sub<T, Output=T> + One + Div<T,Output=T> + Add<T, Output=T>
@@ -289,12 +524,49 @@ macro_rules! geom_impl_aabr_or_aabb {
             pub fn expand_to_contain_point(&mut self, p: $Vec<T>) where T: Copy + PartialOrd {
                 *self = self.expanded_to_contain_point(p);
             }
+            /// Gets the bounds swept by this shape when it is translated by `displacement`, i.e the union
+            /// of this shape and the same shape translated by `displacement`.
+            ///
+            /// This is typically used in broad-phase collision detection for moving objects.
+            pub fn expanded_by_displacement(self, displacement: $Vec<T>) -> Self
+                where T: Copy + PartialOrd + Zero + Add<T, Output=T>
+            {
+                let Self { mut min, mut max } = self;
+                $(
+                    if displacement.$p < T::zero() {
+                        min.$p = min.$p + displacement.$p;
+                    } else {
+                        max.$p = max.$p + displacement.$p;
+                    }
+                )+
+                Self { min, max }
+            }
+            /// Gets this shape grown by `radius` along every axis, i.e the bounds of the Minkowski sum of
+            /// this shape with a sphere (or disk, in 2D) of the given radius.
+            pub fn expanded_by_radius(self, radius: T) -> Self
+                where T: Copy + Add<T, Output=T> + Sub<T, Output=T>
+            {
+                let Self { min, max } = self;
+                Self {
+                    min: $Vec { $($p: min.$p - radius),+ },
+                    max: $Vec { $($p: max.$p + radius),+ },
+                }
+            }
             /// Does this bounding shape contain the given point ?
             pub fn contains_point(self, p: $Vec<T>) -> bool
                 where T: PartialOrd
             {
                 true $(&& self.min.$p <= p.$p && p.$p <= self.max.$p)+
             }
+            /// Returns a random point inside this bounding shape (bounds included), uniformly distributed.
+            ///
+            /// Panics if this bounding shape is not valid (see `is_valid()`).
+            #[cfg(feature="rand")]
+            pub fn random_point_inside<R: $crate::rand::Rng + ?Sized>(self, rng: &mut R) -> $Vec<T>
+                where T: $crate::rand::distributions::uniform::SampleUniform + PartialOrd + Copy
+            {
+                $Vec { $($p: rng.gen_range(self.min.$p..=self.max.$p)),+ }
+            }
             /// Does this bounding shape fully contain another ?
             pub fn $contains_aab(self, other: Self) -> bool
                 where T: PartialOrd
@@ -319,6 +591,60 @@ macro_rules! geom_impl_aabr_or_aabb {
                     b1.min.$p - b2.max.$p
                 }),+}
             }
+            /// Gets the smallest axis-aligned vector by which `self` has to be moved so that it
+            /// doesn't collide with `other` anymore, or `None` if they don't collide.
+            ///
+            /// Shapes which merely touch don't collide (see `collides_with_*()`), so the result is
+            /// never a zero vector. When `self` is contained in `other` (or the reverse), it gets
+            /// pushed out through the nearest face.
+            ///
+            /// Ties are broken deterministically: when moving in either direction along an axis
+            /// requires the same distance, the positive direction is chosen, and when several axes
+            /// require the same distance, the first one (in X, Y, Z order) is chosen.
+            ///
+            /// ```
+            /// # use vek::{Aabr, Vec2};
+            /// let a = Aabr { min: Vec2::new(0, 0), max: Vec2::new(4, 4) };
+            /// let b = Aabr { min: Vec2::new(3, 1), max: Vec2::new(9, 2) };
+            /// assert_eq!(a.minimum_translation_vector(b), Some(Vec2::new(-1, 0)));
+            /// assert_eq!(b.minimum_translation_vector(a), Some(Vec2::new(1, 0)));
+            /// let touching = Aabr { min: Vec2::new(4, 0), max: Vec2::new(5, 4) };
+            /// assert_eq!(a.minimum_translation_vector(touching), None);
+            /// ```
+            pub fn minimum_translation_vector(self, other: Self) -> Option<$Vec<T>>
+                where T: Copy + Zero + PartialOrd + Sub<T, Output=T> + Neg<Output=T>
+            {
+                // For each axis, the penetration depth and the matching signed displacement.
+                let axes = [$({
+                    // How far `self` has to move in the negative and positive directions.
+                    let neg = self.max.$p - other.min.$p;
+                    let pos = other.max.$p - self.min.$p;
+                    if neg <= T::zero() || pos <= T::zero() {
+                        return None;
+                    }
+                    if pos <= neg { (pos, pos) } else { (neg, -neg) }
+                }),+];
+                let mut best = 0;
+                for (i, axis) in axes.iter().enumerate() {
+                    if axis.0 < axes[best].0 {
+                        best = i;
+                    }
+                }
+                let mut mtv = $Vec::zero();
+                mtv[best] = axes[best].1;
+                Some(mtv)
+            }
+            /// Tells whether this bounding shape collides with another, and if so, gets the
+            /// `minimum_translation_vector()` in the same pass. The vector is zero when they
+            /// don't collide.
+            pub fn collides_and_mtv(self, other: Self) -> (bool, $Vec<T>)
+                where T: Copy + Zero + PartialOrd + Sub<T, Output=T> + Neg<Output=T>
+            {
+                match self.minimum_translation_vector(other) {
+                    Some(mtv) => (true, mtv),
+                    None => (false, $Vec::zero()),
+                }
+            }
             /// Project the given point into the bounding shape (equivalent to 'snapping' the point
             /// to the closest point in the bounding shape).
             pub fn projected_point(self, p: $Vec<T>) -> $Vec<T>
@@ -330,6 +656,51 @@ macro_rules! geom_impl_aabr_or_aabb {
             pub fn distance_to_point(self, p: $Vec<T>) -> T where T: Clamp + Real + Add<T, Output=T> + RelativeEq {
                 self.projected_point(p).distance(p)
             }
+            /// Gets the signed distance from this bounding shape to a point, which is negative
+            /// inside, zero on the boundary and positive outside.
+            ///
+            /// This is the exact distance, inside and outside (it is not a bound, unlike some
+            /// approximations of it).
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{Aabr, Vec2};
+            /// # fn main() {
+            /// let aabr = Aabr { min: Vec2::new(0_f32, 0.), max: Vec2::new(4., 2.) };
+            /// assert_relative_eq!(aabr.signed_distance(Vec2::new(3., 1.)), -1.);
+            /// assert_relative_eq!(aabr.signed_distance(Vec2::new(4., 1.)), 0.);
+            /// assert_relative_eq!(aabr.signed_distance(Vec2::new(7., 6.)), 5.);
+            /// # }
+            /// ```
+            pub fn signed_distance(self, p: $Vec<T>) -> T where T: Real {
+                let two = T::one() + T::one();
+                // Distances past each pair of faces; positive outside, negative inside.
+                let q = $Vec { $($p: (p.$p - (self.min.$p + self.max.$p) / two).abs() - (self.max.$p - self.min.$p) / two),+ };
+                let outside = (T::zero() $(+ q.$p.max(T::zero()) * q.$p.max(T::zero()))+).sqrt();
+                let inside = q.reduce_partial_max().min(T::zero());
+                outside + inside
+            }
+            /// Gets the gradient of `signed_distance()` at the given point, which is the unit
+            /// direction in which the signed distance increases the fastest.
+            ///
+            /// Outside, it points away from the closest point of the boundary.
+            /// Inside, it is the normal of the closest face (if several faces are equally close,
+            /// the first axis wins).
+            pub fn gradient_at(self, p: $Vec<T>) -> $Vec<T> where T: Real {
+                let two = T::one() + T::one();
+                let sign = |x: T| if x < T::zero() { -T::one() } else { T::one() };
+                let d = $Vec { $($p: p.$p - (self.min.$p + self.max.$p) / two),+ };
+                let q = $Vec { $($p: d.$p.abs() - (self.max.$p - self.min.$p) / two),+ };
+                let outside = $Vec { $($p: q.$p.max(T::zero()) * sign(d.$p)),+ };
+                let m = outside.magnitude();
+                if m > T::zero() {
+                    return outside / m;
+                }
+                let max = q.reduce_partial_max();
+                let mut found = false;
+                $Vec { $($p: if !found && q.$p == max { found = true; sign(d.$p) } else { T::zero() }),+ }
+            }
             $(
             /// Splits this shape in two, by a straight plane along the
             #[doc=$p_s]
@@ -374,6 +745,56 @@ macro_rules! geom_impl_aabr_or_aabb {
         }
     };
 }
+macro_rules! geom_impl_support_map {
+    ($Aab:ident $Shape:ident $LineSegment:ident $Vec:ident ($($p:ident)+)) => {
+        /// The support point of a bounding shape is one of its corners.
+        /// Along axes where `direction` is zero, `max` is chosen.
+        impl<T> SupportMap for $Aab<T> where T: Copy + Zero + PartialOrd {
+            type Vector = $Vec<T>;
+            fn support_point(&self, direction: $Vec<T>) -> $Vec<T> {
+                $Vec { $($p: if direction.$p >= T::zero() { self.max.$p } else { self.min.$p }),+ }
+            }
+        }
+        /// A slice of points is treated as their convex hull. When several points are the farthest
+        /// along `direction`, the first one is chosen.
+        ///
+        /// # Panics
+        /// Panics if the slice is empty.
+        impl<T> SupportMap for [$Vec<T>] where T: Copy + Zero + Mul<Output=T> + Add<Output=T> + PartialOrd {
+            type Vector = $Vec<T>;
+            fn support_point(&self, direction: $Vec<T>) -> $Vec<T> {
+                let (&first, rest) = self.split_first().expect("support point of an empty set of points");
+                let mut best = (first, first.dot(direction));
+                for &p in rest {
+                    let d = p.dot(direction);
+                    if d > best.1 {
+                        best = (p, d);
+                    }
+                }
+                best.0
+            }
+        }
+        /// When `direction` is zero, the center is chosen.
+        impl<T: Real> SupportMap for $Shape<T,T> {
+            type Vector = $Vec<T>;
+            fn support_point(&self, direction: $Vec<T>) -> $Vec<T> {
+                let m = direction.magnitude();
+                if m > T::zero() {
+                    self.center + direction * (self.radius / m)
+                } else {
+                    self.center
+                }
+            }
+        }
+        /// When both ends are equally far along `direction`, `start` is chosen.
+        impl<T> SupportMap for $LineSegment<T> where T: Copy + Zero + Mul<Output=T> + Add<Output=T> + PartialOrd {
+            type Vector = $Vec<T>;
+            fn support_point(&self, direction: $Vec<T>) -> $Vec<T> {
+                if self.end.dot(direction) > self.start.dot(direction) { self.end } else { self.start }
+            }
+        }
+    };
+}
 macro_rules! geom_impl_disk_or_sphere {
     (
         $Shape:ident ($Shape_s:expr) $Vec:ident ($($p:ident)+)
@@ -431,6 +852,31 @@ macro_rules! geom_impl_disk_or_sphere {
             pub fn contains_point(self, p: $Vec<T>) -> bool where T: PartialOrd {
                 self.center.distance(p) <= self.radius
             }
+            /// Gets the signed distance from this shape to a point, which is negative inside,
+            /// zero on the boundary and positive outside.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{Disk, Vec2};
+            /// # fn main() {
+            /// let disk = Disk::new(Vec2::new(1_f32, 1.), 2.);
+            /// assert_relative_eq!(disk.signed_distance(Vec2::new(1., 1.)), -2.);
+            /// assert_relative_eq!(disk.signed_distance(Vec2::new(4., 5.)), 3.);
+            /// # }
+            /// ```
+            pub fn signed_distance(self, p: $Vec<T>) -> T {
+                self.center.distance(p) - self.radius
+            }
+            /// Gets the gradient of `signed_distance()` at the given point, which is the unit
+            /// direction from the center to the point.
+            ///
+            /// At the center, where it is undefined, this returns zero.
+            pub fn gradient_at(self, p: $Vec<T>) -> $Vec<T> {
+                let d = p - self.center;
+                let m = d.magnitude();
+                if m > T::zero() { d / m } else { $Vec::zero() }
+            }
             /// Does this shape collide with another ?
             pub fn $collides_with_other(self, other: Self) -> bool where T: PartialOrd {
                 self.center.distance(other.center) <= (self.radius + other.radius)
@@ -466,6 +912,9 @@ macro_rules! geom_complete_mod {
     ($mod:ident) => {
 
         use crate::vec::$mod::*;
+        use crate::bezier::$mod::{CubicBezier2, CubicBezier3};
+        use crate::mat::$mod::column_major::{Mat3, Mat4};
+        use crate::quaternion::$mod::Quaternion;
 
         // XXX: Beware when using code that assumes that Y points downards.
         // Luckily, our matrix functions (those that receive a viewport) do not!
@@ -515,7 +964,10 @@ macro_rules! geom_complete_mod {
         /// `min` are indeed less than or equal to those of `max`.
         /// The `is_valid()`, `make_valid()` and `made_valid()` methods are designed to help you
         /// with this.
-        #[derive(Debug, Default, Clone, Copy, Hash, Eq, PartialEq, /*Ord, PartialOrd*/)]
+        ///
+        /// The default value is the empty shape (see `empty()`), rather than a zero-sized shape
+        /// at the origin, so that it can be used as the starting point of a union.
+        #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, /*Ord, PartialOrd*/)]
         #[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
         pub struct Aabr<T> {
             /// Minimum coordinates of bounds.
@@ -541,6 +993,529 @@ macro_rules! geom_complete_mod {
             collision_vector_with_aab: collision_vector_with_aabr
         }
 
+        impl<P,E> Rect<P,E> {
+            /// Gets this rectangle's area, or `None` if it overflows `E`.
+            pub fn checked_area(self) -> Option<E> where E: CheckedMul {
+                self.extent().checked_area()
+            }
+            /// Gets this rectangle's area as an `u64`, which can't overflow.
+            ///
+            /// ```
+            /// # use vek::Rect;
+            /// let texture = Rect::new(0_i32, 0, 65536_u32, 65536);
+            /// assert_eq!(texture.area_u64(), 1 << 32);
+            /// assert_eq!(texture.checked_area(), None);
+            /// ```
+            pub fn area_u64(self) -> u64 where E: Into<u32> {
+                self.extent().area_u64()
+            }
+        }
+
+        impl<T> Aabr<T> {
+            /// Gets this rectangle's area, or `None` if it overflows `T` (see also `checked_size()`).
+            pub fn checked_area(self) -> Option<T> where T: CheckedSub + CheckedMul {
+                self.checked_size()?.checked_area()
+            }
+            /// Gets this rectangle's area as an `u64`, which can't overflow for elements that are
+            /// at most 32 bits wide, signed or not.
+            ///
+            /// Axes along which this rectangle is not valid (see `is_valid()`) count as empty.
+            ///
+            /// ```
+            /// # use vek::{Aabr, Vec2};
+            /// let aabr = Aabr { min: Vec2::broadcast(i32::MIN), max: Vec2::broadcast(i32::MAX) };
+            /// assert_eq!(aabr.area_u64(), (u32::MAX as u64).pow(2));
+            /// assert_eq!(aabr.checked_area(), None);
+            /// ```
+            pub fn area_u64(self) -> u64 where T: Into<i64> {
+                let Self { min, max } = self;
+                let w = (max.x.into() - min.x.into()).max(0) as u64;
+                let h = (max.y.into() - min.y.into()).max(0) as u64;
+                w * h
+            }
+        }
+
+        impl<T> Aabr<T> where T: Copy + PartialOrd {
+            /// Subtracts `other` from this rectangle, returning up to 4 rectangles which cover what
+            /// remains of `self`.
+            ///
+            /// Bounds are treated as half-open: a point `p` belongs to an `Aabr` if
+            /// `min <= p < max` on each axis. With integer elements, this means that a cell `(x, y)`
+            /// belongs to the rectangle if `min.x <= x < max.x` and `min.y <= y < max.y`.
+            ///
+            /// The returned rectangles are guaranteed to be:
+            /// - disjoint: they may share edges, but, following the convention above, no point;
+            /// - non-empty: each of them has a strictly positive width and height;
+            /// - complete: together with `self.intersection(other)` (when they collide), they
+            ///   cover exactly `self`.
+            ///
+            /// In order, the low-Y strip (spanning the full width of `self`) comes first, then
+            /// the high-Y strip, then the low-X and high-X strips (spanning the height of the
+            /// intersection only).
+            ///
+            /// `self` and `other` are assumed to be valid (see `is_valid()`). If `self` is empty,
+            /// nothing is returned; if they don't collide, `self` is returned as-is.
+            ///
+            /// ```
+            /// # use vek::{Aabr, Vec2};
+            /// let a = Aabr { min: Vec2::new(0, 0), max: Vec2::new(10, 10) };
+            /// let hole = Aabr { min: Vec2::new(2, 3), max: Vec2::new(5, 10) };
+            /// let parts: Vec<_> = a.subtracted(hole).collect();
+            /// assert_eq!(parts, vec![
+            ///     Aabr { min: Vec2::new(0, 0), max: Vec2::new(10, 3) },
+            ///     Aabr { min: Vec2::new(0, 3), max: Vec2::new(2, 10) },
+            ///     Aabr { min: Vec2::new(5, 3), max: Vec2::new(10, 10) },
+            /// ]);
+            /// ```
+            pub fn subtracted(self, other: Self) -> impl Iterator<Item=Self> {
+                let is_non_empty = |r: &Self| r.min.x < r.max.x && r.min.y < r.max.y;
+                let parts = if !self.collides_with_aabr(other) {
+                    [Some(self), None, None, None]
+                } else {
+                    let i = self.intersection(other);
+                    let low_y  = Self { min: self.min, max: Vec2::new(self.max.x, i.min.y) };
+                    let high_y = Self { min: Vec2::new(self.min.x, i.max.y), max: self.max };
+                    let low_x  = Self { min: Vec2::new(self.min.x, i.min.y), max: Vec2::new(i.min.x, i.max.y) };
+                    let high_x = Self { min: Vec2::new(i.max.x, i.min.y), max: Vec2::new(self.max.x, i.max.y) };
+                    [Some(low_y), Some(high_y), Some(low_x), Some(high_x)]
+                };
+                IntoIterator::into_iter(parts).flatten().filter(is_non_empty)
+            }
+        }
+
+        impl<T: PrimInt> Aabr<T> {
+            /// Iterates over the cells of this rectangle, in row-major order.
+            ///
+            /// Bounds are half-open, as in `subtracted()`: cells `(x, y)` such that
+            /// `min.x <= x < max.x` and `min.y <= y < max.y` are yielded row by row, from low to
+            /// high Y, then from low to high X. This order is guaranteed not to change.
+            ///
+            /// See also `iter_positions_hilbert()`, which has better locality.
+            ///
+            /// ```
+            /// # use vek::{Aabr, Vec2};
+            /// let aabr = Aabr { min: Vec2::new(1_i32, -1), max: Vec2::new(3, 1) };
+            /// let cells: Vec<_> = aabr.iter_positions().collect();
+            /// assert_eq!(cells, [Vec2::new(1, -1), Vec2::new(2, -1), Vec2::new(1, 0), Vec2::new(2, 0)]);
+            /// ```
+            pub fn iter_positions(self) -> impl Iterator<Item=Vec2<T>> {
+                let Self { min, max } = self;
+                let mut p = min;
+                std::iter::from_fn(move || {
+                    if p.y >= max.y || min.x >= max.x {
+                        return None;
+                    }
+                    let cell = p;
+                    p.x = p.x + T::one();
+                    if p.x >= max.x {
+                        p.x = min.x;
+                        p.y = p.y + T::one();
+                    }
+                    Some(cell)
+                })
+            }
+        }
+
+        /// Gets the index of cell `pos` along the Hilbert curve which covers the
+        /// `2^order` by `2^order` grid.
+        ///
+        /// The curve starts at `(0, 0)` and ends at `(2^order - 1, 0)`, and consecutive cells along
+        /// it are always adjacent. Cells which are close along the curve are close in the grid,
+        /// which makes this order more cache-friendly than row-major order. The inverse is
+        /// `hilbert_position()`.
+        ///
+        /// # Panics
+        /// Panics if `order` is greater than 32, or if `pos` is outside of the grid.
+        ///
+        /// ```
+        /// # use vek::{Vec2, hilbert_index, hilbert_position};
+        /// // The curve of order 1 is a "U" shape.
+        /// assert_eq!(hilbert_index(Vec2::new(0, 0), 1), 0);
+        /// assert_eq!(hilbert_index(Vec2::new(0, 1), 1), 1);
+        /// assert_eq!(hilbert_index(Vec2::new(1, 1), 1), 2);
+        /// assert_eq!(hilbert_index(Vec2::new(1, 0), 1), 3);
+        /// assert_eq!(hilbert_position(hilbert_index(Vec2::new(1000, 3), 10), 10), Vec2::new(1000, 3));
+        /// ```
+        pub fn hilbert_index(pos: Vec2<u32>, order: u32) -> u64 {
+            assert!(order <= 32, "the order of a Hilbert curve must be at most 32");
+            let n = 1_u64 << order;
+            let (mut x, mut y) = (pos.x as u64, pos.y as u64);
+            assert!(x < n && y < n, "the position must be within the grid of the Hilbert curve");
+            let mut index = 0;
+            let mut s = n / 2;
+            while s > 0 {
+                let (rx, ry) = ((x & s != 0) as u64, (y & s != 0) as u64);
+                index += s * s * ((3 * rx) ^ ry);
+                hilbert_rotate(n, &mut x, &mut y, rx, ry);
+                s /= 2;
+            }
+            index
+        }
+
+        /// Gets the cell at `index` along the Hilbert curve which covers the
+        /// `2^order` by `2^order` grid.
+        ///
+        /// This is the inverse of `hilbert_index()`.
+        ///
+        /// # Panics
+        /// Panics if `order` is greater than 32, or if `index` is not less than `4^order`.
+        pub fn hilbert_position(index: u64, order: u32) -> Vec2<u32> {
+            assert!(order <= 32, "the order of a Hilbert curve must be at most 32");
+            assert!(order == 32 || index < 1 << (2 * order), "the index must be within the Hilbert curve");
+            let n = 1_u64 << order;
+            let (mut x, mut y) = (0, 0);
+            let mut t = index;
+            let mut s = 1;
+            while s < n {
+                let rx = 1 & (t / 2);
+                let ry = 1 & (t ^ rx);
+                hilbert_rotate(s, &mut x, &mut y, rx, ry);
+                x += s * rx;
+                y += s * ry;
+                t /= 4;
+                s *= 2;
+            }
+            Vec2::new(x as u32, y as u32)
+        }
+
+        // Rotates and flips a quadrant of side `n`, as needed by the Hilbert curve.
+        fn hilbert_rotate(n: u64, x: &mut u64, y: &mut u64, rx: u64, ry: u64) {
+            if ry == 0 {
+                if rx == 1 {
+                    *x = n - 1 - *x;
+                    *y = n - 1 - *y;
+                }
+                std::mem::swap(x, y);
+            }
+        }
+
+        impl Aabr<u32> {
+            /// Iterates over the cells of this rectangle, in the order of the Hilbert curve which
+            /// covers the `2^order` by `2^order` grid (see `hilbert_index()`).
+            ///
+            /// Bounds are half-open, as in `iter_positions()`. The curve is anchored at `(0, 0)`
+            /// rather than at `min`, so that the cells shared by overlapping rectangles are yielded
+            /// in the same relative order. Blocks of the curve which are outside of this rectangle
+            /// are skipped as a whole.
+            ///
+            /// # Panics
+            /// Panics if `order` is greater than 32, or if this rectangle is not within the grid
+            /// (i.e `max.x` or `max.y` is greater than `2^order`).
+            ///
+            /// ```
+            /// # use vek::{Aabr, Vec2};
+            /// let aabr = Aabr { min: Vec2::new(0_u32, 0), max: Vec2::new(2, 2) };
+            /// let cells: Vec<_> = aabr.iter_positions_hilbert(1).collect();
+            /// assert_eq!(cells, [Vec2::new(0, 0), Vec2::new(0, 1), Vec2::new(1, 1), Vec2::new(1, 0)]);
+            ///
+            /// // Only the cells within the rectangle are yielded.
+            /// let aabr = Aabr { min: Vec2::new(1_u32, 0), max: Vec2::new(4, 1) };
+            /// let cells: Vec<_> = aabr.iter_positions_hilbert(2).collect();
+            /// assert_eq!(cells, [Vec2::new(1, 0), Vec2::new(2, 0), Vec2::new(3, 0)]);
+            /// ```
+            pub fn iter_positions_hilbert(self, order: u32) -> HilbertPositions {
+                assert!(order <= 32, "the order of a Hilbert curve must be at most 32");
+                let n = 1_u64 << order;
+                assert!(self.max.x as u64 <= n && self.max.y as u64 <= n, "the rectangle must be within the grid of the Hilbert curve");
+                let is_empty = self.min.x >= self.max.x || self.min.y >= self.max.y;
+                HilbertPositions { aabr: self, order, index: if is_empty { None } else { Some(0) } }
+            }
+        }
+
+        /// Iterator over the cells of an `Aabr<u32>`, in the order of a Hilbert curve.
+        ///
+        /// See `Aabr::iter_positions_hilbert()`.
+        #[derive(Debug, Clone, Hash, Eq, PartialEq)]
+        pub struct HilbertPositions {
+            aabr: Aabr<u32>,
+            order: u32,
+            // The next index along the curve, or `None` once it's over.
+            index: Option<u64>,
+        }
+
+        impl HilbertPositions {
+            fn contains(&self, p: Vec2<u32>) -> bool {
+                let Aabr { min, max } = self.aabr;
+                min.x <= p.x && p.x < max.x && min.y <= p.y && p.y < max.y
+            }
+            // Whether the aligned square of side `2^k` which contains `p` intersects the rectangle.
+            fn block_intersects(&self, p: Vec2<u32>, k: u32) -> bool {
+                let Aabr { min, max } = self.aabr;
+                let lo = p.map(|e| (e as u64) >> k << k);
+                let hi = lo.map(|e| e + (1 << k));
+                lo.x < max.x as u64 && (min.x as u64) < hi.x && lo.y < max.y as u64 && (min.y as u64) < hi.y
+            }
+            fn advance(&mut self, index: u64, step: u64) {
+                let last = if self.order == 32 { u64::MAX } else { (1 << (2 * self.order)) - 1 };
+                self.index = index.checked_add(step).filter(|&i| i <= last);
+            }
+        }
+
+        impl Iterator for HilbertPositions {
+            type Item = Vec2<u32>;
+            fn next(&mut self) -> Option<Vec2<u32>> {
+                while let Some(index) = self.index {
+                    let p = hilbert_position(index, self.order);
+                    if self.contains(p) {
+                        self.advance(index, 1);
+                        return Some(p);
+                    }
+                    // Blocks of 4^k indices which start at a multiple of 4^k cover aligned squares of
+                    // side 2^k; skip the largest one which doesn't intersect the rectangle.
+                    let mut k = 0;
+                    while k < self.order && index.trailing_zeros() >= 2 * (k + 1) && !self.block_intersects(p, k + 1) {
+                        k += 1;
+                    }
+                    self.advance(index, 1 << (2 * k));
+                }
+                None
+            }
+        }
+
+        impl<T> Rect<T,T> where T: Copy + PartialOrd + Sub<T, Output=T> + Add<T, Output=T> {
+            /// Subtracts `other` from this rectangle, returning up to 4 rectangles which cover what
+            /// remains of `self`.
+            ///
+            /// This is the same as `Aabr::subtracted()`, which documents the guarantees and the
+            /// half-open boundary convention.
+            ///
+            /// ```
+            /// # use vek::Rect;
+            /// let a = Rect::new(0, 0, 10, 10);
+            /// let area: i32 = a.subtracted(Rect::new(8, -2, 5, 5)).map(|r| r.w * r.h).sum();
+            /// assert_eq!(area, 100 - 2 * 3);
+            /// ```
+            pub fn subtracted(self, other: Self) -> impl Iterator<Item=Self> {
+                self.into_aabr().subtracted(other.into_aabr()).map(Aabr::into_rect)
+            }
+        }
+
+        impl<T> Rect<T,T> where T: Copy + PartialOrd + Zero + Sub<T, Output=T> + Add<T, Output=T> + Mul<T, Output=T> + Div<T, Output=T> {
+            /// Slices this rectangle into nine pieces for "nine-slice" (a.k.a 9-patch) scaling,
+            /// given the sizes of its borders as `(left, right, top, bottom)`.
+            ///
+            /// Pieces are in rows from bottom to top (i.e by increasing Y), each from left to right,
+            /// so the corners are at indices 0, 2, 6 and 8, and the center is at index 4.
+            /// They tile this rectangle exactly.
+            ///
+            /// If the rectangle is narrower (or shorter) than its left and right (or top and bottom)
+            /// borders, the borders are shrunk in proportion to fill it, and the center is empty.
+            /// Negative borders count as zero. This rectangle is expected to have non-negative extents.
+            ///
+            /// ```
+            /// # use vek::{Rect, Vec4};
+            /// let pieces = Rect::new(0, 0, 100, 50).nine_slice(Vec4::new(10, 20, 5, 8));
+            /// assert_eq!(pieces[0], Rect::new(0, 0, 10, 8));
+            /// assert_eq!(pieces[4], Rect::new(10, 8, 70, 37));
+            /// assert_eq!(pieces[8], Rect::new(80, 45, 20, 5));
+            /// // Too narrow for the borders.
+            /// let pieces = Rect::new(0, 0, 15, 50).nine_slice(Vec4::new(10, 20, 5, 8));
+            /// assert_eq!((pieces[3].w, pieces[4].w, pieces[5].w), (5, 0, 10));
+            /// ```
+            pub fn nine_slice(self, margins: Vec4<T>) -> [Self; 9] {
+                let columns = Self::nine_slice_spans(self.x, self.w, margins.x, margins.y);
+                let rows = Self::nine_slice_spans(self.y, self.h, margins.w, margins.z);
+                Self::nine_slice_pieces(columns, rows)
+            }
+            /// Slices a rectangle of a texture into nine pieces, the counterpart of `nine_slice()`
+            /// for the source of a nine-slice image.
+            ///
+            /// Unlike `nine_slice()`, this expects Y to point downwards, as for image data and
+            /// texture coordinates in most APIs (i.e the top border is at the lowest Y). The pieces
+            /// are in the same order as those of `nine_slice()`, so that each piece of the texture is
+            /// drawn into the destination piece at the same index. For textures whose Y points
+            /// upwards (e.g OpenGL's), use `nine_slice()` instead.
+            ///
+            /// ```
+            /// # use vek::{Rect, Vec4};
+            /// let margins = Vec4::new(4, 4, 2, 6);
+            /// let source = Rect::new(0, 0, 32, 32).nine_slice_uv(margins);
+            /// let destination = Rect::new(0, 0, 100, 50).nine_slice(margins);
+            /// // The bottom-left corners.
+            /// assert_eq!(source[0], Rect::new(0, 26, 4, 6));
+            /// assert_eq!(destination[0], Rect::new(0, 0, 4, 6));
+            /// ```
+            pub fn nine_slice_uv(self, margins: Vec4<T>) -> [Self; 9] {
+                let columns = Self::nine_slice_spans(self.x, self.w, margins.x, margins.y);
+                let [top, middle, bottom] = Self::nine_slice_spans(self.y, self.h, margins.z, margins.w);
+                Self::nine_slice_pieces(columns, [bottom, middle, top])
+            }
+            /// Splits `[start, start + len]` into spans of length `low`, what remains, and `high`,
+            /// as `(start, length)` pairs.
+            fn nine_slice_spans(start: T, len: T, low: T, high: T) -> [(T, T); 3] {
+                let zero = T::zero();
+                let low = if low > zero { low } else { zero };
+                let high = if high > zero { high } else { zero };
+                let (low, high) = if low + high > len {
+                    let low = len * low / (low + high);
+                    (low, len - low)
+                } else {
+                    (low, high)
+                };
+                [(start, low), (start + low, len - low - high), (start + len - high, high)]
+            }
+            fn nine_slice_pieces(columns: [(T, T); 3], rows: [(T, T); 3]) -> [Self; 9] {
+                let piece = |i: usize| {
+                    let ((x, w), (y, h)) = (columns[i % 3], rows[i / 3]);
+                    Self { x, y, w, h }
+                };
+                [piece(0), piece(1), piece(2), piece(3), piece(4), piece(5), piece(6), piece(7), piece(8)]
+            }
+        }
+
+        /// A 2D rectangle with rounded corners, represented by a `Rect` and a radius for each corner.
+        ///
+        /// The radii are stored counter-clockwise (with Y pointing upwards), starting at the
+        /// bottom-left corner: `x` is bottom-left, `y` is bottom-right, `z` is top-right and `w` is top-left.
+        ///
+        /// Radii larger than what the rectangle can fit are allowed; methods use `clamped_radii()`.
+        #[derive(Debug, Default, Clone, Copy, Hash, Eq, PartialEq, /*Ord, PartialOrd*/)]
+        #[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+        pub struct RoundedRect<T> {
+            /// The rectangle, as if its corners were not rounded.
+            pub rect: Rect<T, T>,
+            /// The corner radii: bottom-left, bottom-right, top-right, top-left.
+            pub radii: Vec4<T>,
+        }
+
+        impl<T: Real> RoundedRect<T> {
+            /// Creates a `RoundedRect` from a rectangle and per-corner radii.
+            pub fn new(rect: Rect<T, T>, radii: Vec4<T>) -> Self {
+                Self { rect, radii }
+            }
+            /// Creates a `RoundedRect` whose corners all have the same radius.
+            pub fn with_uniform_radius(rect: Rect<T, T>, radius: T) -> Self {
+                Self::new(rect, Vec4::broadcast(radius))
+            }
+            /// Gets the corner radii, each clamped between zero and half of the rectangle's shortest side.
+            ///
+            /// This guarantees that corners never overlap, and that the rounded rectangle is the
+            /// set of points within some radius of a smaller rectangle, locally at each corner.
+            ///
+            /// ```
+            /// # use vek::{RoundedRect, Rect, Vec4};
+            /// let r = RoundedRect::new(Rect::new(0., 0., 10., 4.), Vec4::new(-1., 1., 3., 2.));
+            /// assert_eq!(r.clamped_radii(), Vec4::new(0., 1., 2., 2.));
+            /// ```
+            pub fn clamped_radii(self) -> Vec4<T> {
+                let two = T::one() + T::one();
+                let max = self.rect.w.abs().min(self.rect.h.abs()) / two;
+                self.radii.map(|r| r.max(T::zero()).min(max))
+            }
+            /// Gets the signed distance from `p` to the outline, which is negative inside and positive outside.
+            ///
+            /// This is exact (not a bound), including around the rounded corners, whatever their radii:
+            /// it is the distance to the closest of the straight edges and corner arcs of the outline.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{RoundedRect, Rect, Vec2};
+            /// # fn main() {
+            /// let r = RoundedRect::with_uniform_radius(Rect::new(0., 0., 10., 4.), 1.);
+            /// assert_relative_eq!(r.signed_distance(Vec2::new(5., 2.)), -2.);
+            /// assert_relative_eq!(r.signed_distance(Vec2::new(5., 6.)), 2.);
+            /// // Near the corners, the distance is to the arc.
+            /// assert_relative_eq!(r.signed_distance(Vec2::new(-1., -1.)), 2_f32.sqrt() * 2. - 1.);
+            /// # }
+            /// ```
+            pub fn signed_distance(self, p: Vec2<T>) -> T {
+                let one = T::one();
+                let r = self.clamped_radii();
+                let aabr = self.rect.into_aabr().made_valid();
+                let (min, max) = (aabr.min, aabr.max);
+                let segment_distance = |a: Vec2<T>, b: Vec2<T>| {
+                    let ab = b - a;
+                    let len2 = ab.magnitude_squared();
+                    let t = if len2 > T::zero() { ((p - a).dot(ab) / len2).max(T::zero()).min(one) } else { T::zero() };
+                    p.distance(a + ab * t)
+                };
+                // The outline is made of four straight edges, between the ends of the corner arcs...
+                let mut distance = segment_distance(Vec2::new(min.x + r.x, min.y), Vec2::new(max.x - r.y, min.y))
+                    .min(segment_distance(Vec2::new(max.x, min.y + r.y), Vec2::new(max.x, max.y - r.z)))
+                    .min(segment_distance(Vec2::new(max.x - r.z, max.y), Vec2::new(min.x + r.w, max.y)))
+                    .min(segment_distance(Vec2::new(min.x, max.y - r.w), Vec2::new(min.x, min.y + r.x)));
+                let mut inside = aabr.contains_point(p);
+                // ...and of the four arcs, each of which spans the quadrant of its center that points
+                // away from the rectangle. Outside of it, the closest point of an arc is one of its ends,
+                // which are also ends of edges.
+                let corners = [
+                    (r.x, Vec2::new(-one, -one), min),
+                    (r.y, Vec2::new(one, -one), Vec2::new(max.x, min.y)),
+                    (r.z, Vec2::new(one, one), max),
+                    (r.w, Vec2::new(-one, one), Vec2::new(min.x, max.y)),
+                ];
+                for &(radius, dir, corner) in corners.iter() {
+                    let d = p - (corner - dir * radius);
+                    if d.x * dir.x >= T::zero() && d.y * dir.y >= T::zero() {
+                        let m = d.magnitude();
+                        // Points in that quadrant, but beyond the arc, are cut out of the rectangle.
+                        inside = inside && m <= radius;
+                        distance = distance.min((m - radius).abs());
+                    }
+                }
+                if inside { -distance } else { distance }
+            }
+            /// Tests if `p` is inside the rounded rectangle or on its outline.
+            pub fn contains_point(self, p: Vec2<T>) -> bool {
+                self.signed_distance(p) <= T::zero()
+            }
+            /// Gets the outline as 8 cubic Bézier curves, running counter-clockwise (with Y pointing upwards).
+            ///
+            /// Edges (which are straight) and corners (which approximate quarter circles) alternate,
+            /// starting with the bottom edge. Each curve ends exactly where the next one starts.
+            /// Corners with a radius of zero become curves whose points all coincide.
+            ///
+            /// ```
+            /// # use vek::{RoundedRect, Rect};
+            /// let r = RoundedRect::with_uniform_radius(Rect::new(0., 0., 10., 4.), 1.);
+            /// let outline = r.to_cubic_beziers();
+            /// for i in 0..8 {
+            ///     assert_eq!(outline[i].end, outline[(i + 1) % 8].start);
+            /// }
+            /// ```
+            pub fn to_cubic_beziers(self) -> [CubicBezier2<T>; 8] {
+                let (two, three) = (T::one() + T::one(), T::one() + T::one() + T::one());
+                // Length of the handles of a cubic Bézier approximating a unit quarter circle.
+                let kappa = (two + two) * (two.sqrt() - T::one()) / three;
+                let aabr = self.rect.into_aabr().made_valid();
+                let (min, max) = (aabr.min, aabr.max);
+                let r = self.clamped_radii();
+                let line = |start: Vec2<T>, end: Vec2<T>| {
+                    let d = (end - start) / three;
+                    CubicBezier2 { start, ctrl0: start + d, ctrl1: end - d, end }
+                };
+                // A corner arc, from `start` going along `start_dir`, to `end` arriving along `end_dir`.
+                let corner = |start: Vec2<T>, start_dir: Vec2<T>, end: Vec2<T>, end_dir: Vec2<T>, radius: T| {
+                    CubicBezier2 {
+                        start,
+                        ctrl0: start + start_dir * (kappa * radius),
+                        ctrl1: end - end_dir * (kappa * radius),
+                        end,
+                    }
+                };
+                let (right, up) = (Vec2::unit_x(), Vec2::unit_y());
+                let bottom_start = Vec2::new(min.x + r.x, min.y);
+                let bottom_end = Vec2::new(max.x - r.y, min.y);
+                let right_start = Vec2::new(max.x, min.y + r.y);
+                let right_end = Vec2::new(max.x, max.y - r.z);
+                let top_start = Vec2::new(max.x - r.z, max.y);
+                let top_end = Vec2::new(min.x + r.w, max.y);
+                let left_start = Vec2::new(min.x, max.y - r.w);
+                let left_end = Vec2::new(min.x, min.y + r.x);
+                [
+                    line(bottom_start, bottom_end),
+                    corner(bottom_end, right, right_start, up, r.y),
+                    line(right_start, right_end),
+                    corner(right_end, up, top_start, -right, r.z),
+                    line(top_start, top_end),
+                    corner(top_end, -right, left_start, -up, r.w),
+                    line(left_start, left_end),
+                    corner(left_end, -up, bottom_start, right, r.x),
+                ]
+            }
+        }
+
 
         /// A `Rect` extended to 3D.
         ///
@@ -583,7 +1558,10 @@ macro_rules! geom_complete_mod {
         /// `min` are indeed less than or equal to those of `max`.
         /// The `is_valid()`, `make_valid()` and `made_valid()` methods are designed to help you
         /// with this.
-        #[derive(Debug, Default, Clone, Copy, Hash, Eq, PartialEq, /*Ord, PartialOrd*/)]
+        ///
+        /// The default value is the empty shape (see `empty()`), rather than a zero-sized shape
+        /// at the origin, so that it can be used as the starting point of a union.
+        #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, /*Ord, PartialOrd*/)]
         #[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
         pub struct Aabb<T> {
             /// Minimum coordinates of bounds.
@@ -600,23 +1578,263 @@ macro_rules! geom_complete_mod {
             collision_vector_with_aab: collision_vector_with_aabb
         }
 
+        impl<P,E> Rect3<P,E> {
+            /// Gets this box's volume, or `None` if it overflows `E`.
+            pub fn checked_volume(self) -> Option<E> where E: CheckedMul {
+                self.extent().checked_volume()
+            }
+            /// Gets this box's volume as an `u128`, which can't overflow.
+            pub fn volume_u128(self) -> u128 where E: Into<u32> {
+                self.extent().volume_u128()
+            }
+        }
 
-        // NOTE: Only implement axis-aligned primitives (a.k.a don't go on a rampage).
-        //
-        // Don't write, e.g a "Disk in 3D-space" structure, because users would rather
-        // represent it with a (Disk, z, orientation) tuple or anything else that suits their particular needs.
-        //
-        // On the other hand, everybody agrees that a minimal "Disk" struct is a position+radius pair.
-        // (even if it's just expressed as a radius with no
-        // position, then fine, just use the radius as-is, without making it it a new struct).
-        //
-        // Any other info, such as fill color, border thickness, etc. are just extras that users can
-        // put on top (see composition over inheritance, etc).
-
-        /// Disk (2D), represented by center and radius.
-        #[derive(Debug, Default, Clone, Copy, Hash, Eq, PartialEq, /*Ord, PartialOrd*/)]
-        #[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
-        #[allow(missing_docs)]
+        impl<T> Aabb<T> {
+            /// Gets this box's volume, or `None` if it overflows `T` (see also `checked_size()`).
+            pub fn checked_volume(self) -> Option<T> where T: CheckedSub + CheckedMul {
+                self.checked_size()?.checked_volume()
+            }
+            /// Gets this box's volume as an `u128`, which can't overflow for elements that are
+            /// at most 32 bits wide, signed or not.
+            ///
+            /// Axes along which this box is not valid (see `is_valid()`) count as empty.
+            ///
+            /// ```
+            /// # use vek::{Aabb, Vec3};
+            /// let aabb = Aabb { min: Vec3::new(0_u32, 0, 0), max: Vec3::new(2048, 2048, 1024) };
+            /// assert_eq!(aabb.volume_u128(), 1 << 32);
+            /// assert_eq!(aabb.checked_volume(), None);
+            /// ```
+            pub fn volume_u128(self) -> u128 where T: Into<i64> {
+                let Self { min, max } = self;
+                let w = (max.x.into() - min.x.into()).max(0) as u128;
+                let h = (max.y.into() - min.y.into()).max(0) as u128;
+                let d = (max.z.into() - min.z.into()).max(0) as u128;
+                w * h * d
+            }
+        }
+
+        impl<T: PrimInt> Aabb<T> {
+            /// Iterates over the cells of this box, in row-major order.
+            ///
+            /// Bounds are half-open: cells `(x, y, z)` such that `min <= (x, y, z) < max` on each
+            /// axis are yielded slice by slice, from low to high Z, then row by row, from low to
+            /// high Y, then from low to high X. This order is guaranteed not to change.
+            ///
+            /// ```
+            /// # use vek::{Aabb, Vec3};
+            /// let aabb = Aabb { min: Vec3::new(0_u8, 0, 0), max: Vec3::new(2, 1, 2) };
+            /// let cells: Vec<_> = aabb.iter_positions().collect();
+            /// assert_eq!(cells, [
+            ///     Vec3::new(0, 0, 0), Vec3::new(1, 0, 0),
+            ///     Vec3::new(0, 0, 1), Vec3::new(1, 0, 1),
+            /// ]);
+            /// ```
+            pub fn iter_positions(self) -> impl Iterator<Item=Vec3<T>> {
+                let Self { min, max } = self;
+                let mut p = min;
+                std::iter::from_fn(move || {
+                    if p.z >= max.z || min.x >= max.x || min.y >= max.y {
+                        return None;
+                    }
+                    let cell = p;
+                    p.x = p.x + T::one();
+                    if p.x >= max.x {
+                        p.x = min.x;
+                        p.y = p.y + T::one();
+                        if p.y >= max.y {
+                            p.y = min.y;
+                            p.z = p.z + T::one();
+                        }
+                    }
+                    Some(cell)
+                })
+            }
+        }
+
+        impl<T> Aabb<T> where T: Real + MulAdd<T,T,Output=T> {
+            /// Projects this box with a view-projection matrix using OpenGL-style clip space
+            /// (i.e the near plane is at `z = -w`), and gets the bounding rectangle of its image
+            /// in viewport (pixel) coordinates, with `(0, 0)` at the bottom-left corner.
+            ///
+            /// The box is clipped against the near plane first, so the result stays correct when
+            /// the camera is close to or inside the box. Returns `None` when the box lies
+            /// entirely behind the near plane.
+            /// The result is not clamped to the viewport.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{Aabb, Mat4, Vec2, Vec3, Extent2};
+            /// # fn main() {
+            /// let view = Mat4::look_at_rh(Vec3::new(0., 0., 5.), Vec3::zero(), Vec3::unit_y());
+            /// let proj = Mat4::perspective_rh_no(90_f32.to_radians(), 1., 0.1, 100.);
+            /// let aabb = Aabb { min: Vec3::new(-1., -1., -1.), max: Vec3::new(1., 1., 1.) };
+            /// let aabr = aabb.projected_aabr_no(proj * view, Extent2::new(800, 800)).unwrap();
+            /// // The front face is 4 units away, so it spans a quarter of each side of the viewport.
+            /// assert_relative_eq!(aabr.min, Vec2::new(300., 300.), epsilon = 1e-3);
+            /// assert_relative_eq!(aabr.max, Vec2::new(500., 500.), epsilon = 1e-3);
+            /// // Behind the camera:
+            /// let behind = Aabb { min: Vec3::new(-1., -1., 6.), max: Vec3::new(1., 1., 7.) };
+            /// assert_eq!(behind.projected_aabr_no(proj * view, Extent2::new(800, 800)), None);
+            /// # }
+            /// ```
+            pub fn projected_aabr_no(self, view_proj: Mat4<T>, viewport: Extent2<u32>) -> Option<Aabr<T>> {
+                self.projected_aabr_with_near_plane(view_proj, viewport, |v| v.z + v.w)
+            }
+            /// Same as `projected_aabr_no()`, but using Direct3D-style clip space
+            /// (i.e the near plane is at `z = 0`).
+            pub fn projected_aabr_zo(self, view_proj: Mat4<T>, viewport: Extent2<u32>) -> Option<Aabr<T>> {
+                self.projected_aabr_with_near_plane(view_proj, viewport, |v| v.z)
+            }
+            /// Gets the bounds of `point` as it rotates about `center`, by every orientation from
+            /// `from` to `to`, i.e along the shortest arc that `Quaternion::slerp()` (or its normalized
+            /// lerp) follows. Both orientations are expected to be normalized.
+            ///
+            /// The point moves along an arc of circle, whose exact bounds are given, up to rounding.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{Aabb, Quaternion, Vec3};
+            /// # fn main() {
+            /// let quarter = Quaternion::rotation_z(std::f64::consts::FRAC_PI_2);
+            /// let aabb = Aabb::point_rotation_bounds(Vec3::unit_x(), Vec3::zero(), Quaternion::identity(), quarter);
+            /// assert_relative_eq!(aabb.min, Vec3::zero());
+            /// assert_relative_eq!(aabb.max, Vec3::new(1., 1., 0.));
+            /// # }
+            /// ```
+            pub fn point_rotation_bounds(point: Vec3<T>, center: Vec3<T>, from: Quaternion<T>, to: Quaternion<T>) -> Self where T: FloatConst {
+                let start = from * (point - center);
+                let end = to * (point - center);
+                let mut bounds = Self { min: Vec3::partial_min(start, end), max: Vec3::partial_max(start, end) };
+                // The rotation from `from` to `to`, of `angle` (at most half a turn) around `axis`.
+                let delta = to.delta_from(from);
+                let sin_half = Vec3::new(delta.x, delta.y, delta.z).magnitude();
+                if sin_half > T::zero() {
+                    let axis = Vec3::new(delta.x, delta.y, delta.z) / sin_half;
+                    let angle = (T::one() + T::one()) * atan2(sin_half, delta.w);
+                    // The arc is `middle + u*cos(phi) + v*sin(phi)`, for `phi` from 0 to `angle`.
+                    let middle = axis * axis.dot(start);
+                    let u = start - middle;
+                    let v = axis.cross(u);
+                    let turn = T::PI() + T::PI();
+                    for i in 0..3 {
+                        if u[i] == T::zero() && v[i] == T::zero() {
+                            continue;
+                        }
+                        let radius = u[i].hypot(v[i]);
+                        let at_max = atan2(v[i], u[i]);
+                        let at_max = if at_max < T::zero() { at_max + turn } else { at_max };
+                        let at_min = if at_max < T::PI() { at_max + T::PI() } else { at_max - T::PI() };
+                        if at_max <= angle {
+                            bounds.max[i] = middle[i] + radius;
+                        }
+                        if at_min <= angle {
+                            bounds.min[i] = middle[i] - radius;
+                        }
+                    }
+                }
+                Self { min: bounds.min + center, max: bounds.max + center }
+            }
+            /// Gets a box which contains this box at every orientation from `from` to `to`, as it
+            /// rotates about `center`, for bounding animated geometry (e.g for culling).
+            ///
+            /// See `point_rotation_bounds()` for the interpolation of orientations. Since the bounds
+            /// of a rotated box are the bounds of its rotated corners, this is the union of their
+            /// bounds, which is tight: at most orientations, the box reaches none of its faces,
+            /// but each face is reached at some orientation.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{Aabb, Quaternion, Vec3};
+            /// # fn main() {
+            /// let aabb = Aabb { min: Vec3::new(-1_f64, -1., -1.), max: Vec3::new(1., 1., 1.) };
+            /// let swept = aabb.swept_rotation_bounds(Vec3::zero(), Quaternion::identity(), Quaternion::rotation_z(std::f64::consts::FRAC_PI_4));
+            /// // The corners sweep to the diagonals.
+            /// let r = std::f64::consts::SQRT_2;
+            /// assert_relative_eq!(swept.min, Vec3::new(-r, -r, -1.));
+            /// assert_relative_eq!(swept.max, Vec3::new(r, r, 1.));
+            /// # }
+            /// ```
+            pub fn swept_rotation_bounds(self, center: Vec3<T>, from: Quaternion<T>, to: Quaternion<T>) -> Self where T: FloatConst {
+                let (min, max) = (self.min, self.max);
+                let corner = |i: usize| Vec3::new(
+                    if i & 1 == 0 { min.x } else { max.x },
+                    if i & 2 == 0 { min.y } else { max.y },
+                    if i & 4 == 0 { min.z } else { max.z },
+                );
+                (1..8).fold(Self::point_rotation_bounds(corner(0), center, from, to), |bounds, i| {
+                    bounds.union(Self::point_rotation_bounds(corner(i), center, from, to))
+                })
+            }
+            /// `near_distance` returns a clip-space point's signed distance to the near plane,
+            /// which is positive in front of it.
+            fn projected_aabr_with_near_plane<F>(self, view_proj: Mat4<T>, viewport: Extent2<u32>, near_distance: F) -> Option<Aabr<T>>
+                where F: Fn(Vec4<T>) -> T
+            {
+                let (min, max) = (self.min, self.max);
+                let corner = |i: usize| Vec3::new(
+                    if i & 1 == 0 { min.x } else { max.x },
+                    if i & 2 == 0 { min.y } else { max.y },
+                    if i & 4 == 0 { min.z } else { max.z },
+                );
+                let mut clip = [Vec4::zero(); 8];
+                for (i, c) in clip.iter_mut().enumerate() {
+                    *c = view_proj * Vec4::from_point(corner(i));
+                }
+                let two = T::one() + T::one();
+                let size = Vec2::new(T::from(viewport.w).unwrap(), T::from(viewport.h).unwrap());
+                let mut bounds: Option<Aabr<T>> = None;
+                let mut add = |v: Vec4<T>| {
+                    let p = (Vec2::new(v.x, v.y) / v.w + T::one()) / two * size;
+                    bounds = Some(match bounds {
+                        None => Aabr { min: p, max: p },
+                        Some(b) => Aabr { min: Vec2::partial_min(b.min, p), max: Vec2::partial_max(b.max, p) },
+                    });
+                };
+                for &v in clip.iter() {
+                    if near_distance(v) >= T::zero() {
+                        add(v);
+                    }
+                }
+                // Edges crossing the near plane contribute their intersection with it.
+                // Corners are indexed by bits, so edges connect corners that differ by one bit.
+                for a in 0..8 {
+                    for &bit in &[1, 2, 4] {
+                        let b = a | bit;
+                        if a == b {
+                            continue;
+                        }
+                        let (da, db) = (near_distance(clip[a]), near_distance(clip[b]));
+                        if (da >= T::zero()) != (db >= T::zero()) {
+                            let t = da / (da - db);
+                            add(clip[a] + (clip[b] - clip[a]) * t);
+                        }
+                    }
+                }
+                bounds
+            }
+        }
+
+
+        // NOTE: Only implement axis-aligned primitives (a.k.a don't go on a rampage).
+        //
+        // Don't write, e.g a "Disk in 3D-space" structure, because users would rather
+        // represent it with a (Disk, z, orientation) tuple or anything else that suits their particular needs.
+        //
+        // On the other hand, everybody agrees that a minimal "Disk" struct is a position+radius pair.
+        // (even if it's just expressed as a radius with no
+        // position, then fine, just use the radius as-is, without making it it a new struct).
+        //
+        // Any other info, such as fill color, border thickness, etc. are just extras that users can
+        // put on top (see composition over inheritance, etc).
+
+        /// Disk (2D), represented by center and radius.
+        #[derive(Debug, Default, Clone, Copy, Hash, Eq, PartialEq, /*Ord, PartialOrd*/)]
+        #[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+        #[allow(missing_docs)]
         pub struct Disk<P,E> {
             pub center: Vec2<P>,
             pub radius: E,
@@ -676,6 +1894,270 @@ macro_rules! geom_complete_mod {
             collision_vector_with_other: collision_vector_with_sphere
         }
 
+        /// Capsule (3D), i.e the set of points within `radius` of a line segment.
+        #[derive(Debug, Default, Clone, Copy, Hash, Eq, PartialEq, /*Ord, PartialOrd*/)]
+        #[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+        pub struct Capsule<T> {
+            /// The capsule's axis, which joins the centers of its hemispherical caps.
+            pub segment: LineSegment3<T>,
+            /// The capsule's radius.
+            pub radius: T,
+        }
+
+        impl<T> Capsule<T> {
+            /// Creates a capsule from the centers of its caps and its radius.
+            pub fn new(start: Vec3<T>, end: Vec3<T>, radius: T) -> Self {
+                Self { segment: LineSegment3 { start, end }, radius }
+            }
+            /// Does this capsule contain the given point ?
+            pub fn contains_point(self, p: Vec3<T>) -> bool where T: Real + Add<T, Output=T> + RelativeEq {
+                self.segment.distance_to_point(p) <= self.radius
+            }
+        }
+
+        /// When both caps are equally far along `direction`, the one at `segment.start` is chosen.
+        /// When `direction` is zero, `segment.start` is returned.
+        impl<T: Real> SupportMap for Capsule<T> {
+            type Vector = Vec3<T>;
+            fn support_point(&self, direction: Vec3<T>) -> Vec3<T> {
+                let cap = Sphere::new(self.segment.support_point(direction), self.radius);
+                cap.support_point(direction)
+            }
+        }
+
+        /// Cylinder (3D), i.e the set of points within `radius` of a line segment, and between
+        /// the planes perpendicular to it at its ends.
+        #[derive(Debug, Default, Clone, Copy, Hash, Eq, PartialEq, /*Ord, PartialOrd*/)]
+        #[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+        pub struct Cylinder<T> {
+            /// The cylinder's axis, which joins the centers of its caps.
+            pub segment: LineSegment3<T>,
+            /// The cylinder's radius.
+            pub radius: T,
+        }
+
+        impl<T> Cylinder<T> {
+            /// Creates a cylinder from the centers of its caps and its radius.
+            pub fn new(start: Vec3<T>, end: Vec3<T>, radius: T) -> Self {
+                Self { segment: LineSegment3 { start, end }, radius }
+            }
+            /// Does this cylinder contain the given point ?
+            pub fn contains_point(self, p: Vec3<T>) -> bool where T: Real {
+                let LineSegment3 { start, end } = self.segment;
+                let axis = end - start;
+                let along = (p - start).dot(axis);
+                let length_squared = axis.magnitude_squared();
+                if along < T::zero() || along > length_squared {
+                    return false;
+                }
+                let distance_squared = if length_squared > T::zero() {
+                    (p - start).magnitude_squared() - along * along / length_squared
+                } else {
+                    (p - start).magnitude_squared()
+                };
+                distance_squared <= self.radius * self.radius
+            }
+        }
+
+        geom_impl_support_map!{Aabr Disk LineSegment2 Vec2 (x y)}
+        geom_impl_support_map!{Aabb Sphere LineSegment3 Vec3 (x y z)}
+
+        impl<T: Real> Sphere<T, T> {
+            /// Estimates the radius, in pixels, of this sphere's image through a perspective camera
+            /// located at `eye`, with a vertical field of view of `fov_y_radians` and the given viewport.
+            ///
+            /// This uses the exact angular radius of the sphere as seen from `eye`, i.e it is exact
+            /// when the sphere is at the center of the view, and a good estimate elsewhere (off-center
+            /// spheres project to slightly larger ellipses), which is what level-of-detail
+            /// selection usually wants.
+            ///
+            /// - The camera's orientation is not taken into account, so spheres behind the camera
+            ///   get the same size as if the camera were looking at them. Cull them beforehand
+            ///   (e.g with a frustum test) if that matters.
+            /// - If `eye` is inside the sphere (or on its surface), the sphere covers the whole
+            ///   view, and this returns `T::max_value()`.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{Sphere, Vec3, Extent2};
+            /// # fn main() {
+            /// let sphere = Sphere::new(Vec3::new(0., 0., -10.), 1_f32);
+            /// let r = sphere.projected_radius_pixels(Vec3::zero(), 90_f32.to_radians(), Extent2::new(1920, 1080));
+            /// assert_relative_eq!(r, 540. / 99_f32.sqrt());
+            /// assert_eq!(sphere.projected_radius_pixels(Vec3::new(0., 0., -10.5), 1., Extent2::new(1920, 1080)), std::f32::MAX);
+            /// # }
+            /// ```
+            pub fn projected_radius_pixels(self, eye: Vec3<T>, fov_y_radians: T, viewport: Extent2<u32>) -> T {
+                let two = T::one() + T::one();
+                let distance_squared = (self.center - eye).magnitude_squared();
+                let radius_squared = self.radius * self.radius;
+                if distance_squared <= radius_squared {
+                    return T::max_value();
+                }
+                let half_height = T::from(viewport.h).unwrap() / two;
+                half_height * self.radius / ((distance_squared - radius_squared).sqrt() * tan(fov_y_radians / two))
+            }
+        }
+
+
+        impl<T: Real> Sphere<T, T> {
+            /// Gets the inertia tensor of this sphere, as a solid ball of uniform density with the
+            /// given total mass, about its center.
+            ///
+            /// ```
+            /// # use vek::{Sphere, Vec3, Mat3};
+            /// let sphere = Sphere::new(Vec3::new(5_f32, 0., 0.), 2.);
+            /// assert_eq!(sphere.inertia_tensor(10.), Mat3::identity() * 16.);
+            /// ```
+            pub fn inertia_tensor(self, mass: T) -> Mat3<T> {
+                let two = T::one() + T::one();
+                Mat3::identity() * (two * mass * self.radius * self.radius / T::from(5).unwrap())
+            }
+            /// Gets the inertia tensor of this sphere, as a thin shell of uniform density with the
+            /// given total mass, about its center.
+            pub fn hollow_inertia_tensor(self, mass: T) -> Mat3<T> {
+                let two = T::one() + T::one();
+                Mat3::identity() * (two * mass * self.radius * self.radius / (two + T::one()))
+            }
+        }
+        impl<T: Real> Aabb<T> {
+            /// Gets the inertia tensor of this box, as a solid of uniform density with the given
+            /// total mass, about its center.
+            ///
+            /// ```
+            /// # use vek::{Aabb, Vec3, Mat3};
+            /// let aabb = Aabb { min: Vec3::new(0_f32, 0., 0.), max: Vec3::new(1., 2., 3.) };
+            /// // Rotating around the longest axis is the easiest.
+            /// assert_eq!(aabb.inertia_tensor(12.), Mat3::with_diagonal(Vec3::new(13., 10., 5.)));
+            /// ```
+            pub fn inertia_tensor(self, mass: T) -> Mat3<T> {
+                let s = self.max - self.min;
+                let s = s * s;
+                let k = mass / T::from(12).unwrap();
+                Mat3::with_diagonal(Vec3::new(s.y + s.z, s.x + s.z, s.x + s.y) * k)
+            }
+        }
+        impl<T: Real> Capsule<T> {
+            /// Gets the inertia tensor of this capsule, as a solid of uniform density with the
+            /// given total mass, about its center (i.e the middle of its segment).
+            ///
+            /// The tensor is expressed in the same axes as the capsule, which doesn't need to be
+            /// aligned with any of them.
+            pub fn inertia_tensor(self, mass: T) -> Mat3<T> {
+                let (two, three) = (T::one() + T::one(), T::one() + T::one() + T::one());
+                let four = two + two;
+                let (r, axis) = (self.radius, self.segment.end - self.segment.start);
+                let h = axis.magnitude();
+                // Mass is split by volume between the cylinder and both (joined) caps.
+                let cylinder_volume = h * three;
+                let caps_volume = r * four;
+                let cylinder_mass = mass * cylinder_volume / (cylinder_volume + caps_volume);
+                let caps_mass = mass - cylinder_mass;
+                let (r2, h2) = (r * r, h * h);
+                let sphere = two * r2 / T::from(5).unwrap();
+                let axial = cylinder_mass * r2 / two + caps_mass * sphere;
+                // Each cap's center of mass is 3r/8 past the end of the segment.
+                let perpendicular = cylinder_mass * (h2 / T::from(12).unwrap() + r2 / four)
+                    + caps_mass * (sphere + h2 / four + three * h * r / T::from(8).unwrap());
+                axisymmetric_inertia_tensor(axis, axial, perpendicular)
+            }
+        }
+        impl<T: Real> Cylinder<T> {
+            /// Gets the inertia tensor of this cylinder, as a solid of uniform density with the
+            /// given total mass, about its center (i.e the middle of its segment).
+            ///
+            /// The tensor is expressed in the same axes as the cylinder, which doesn't need to be
+            /// aligned with any of them. If the segment has zero length, the cylinder is taken
+            /// to be a disk perpendicular to the Z axis.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{Cylinder, Vec3, Mat3};
+            /// # fn main() {
+            /// let cylinder = Cylinder::new(Vec3::new(0_f32, 0., -1.), Vec3::new(0., 0., 1.), 1.);
+            /// let tensor = cylinder.inertia_tensor(12.);
+            /// assert_relative_eq!(tensor, Mat3::with_diagonal(Vec3::new(7., 7., 6.)));
+            /// # }
+            /// ```
+            pub fn inertia_tensor(self, mass: T) -> Mat3<T> {
+                let two = T::one() + T::one();
+                let (r, axis) = (self.radius, self.segment.end - self.segment.start);
+                let h2 = axis.magnitude_squared();
+                let axial = mass * r * r / two;
+                let perpendicular = mass * (h2 / T::from(12).unwrap() + r * r / (two + two));
+                axisymmetric_inertia_tensor(axis, axial, perpendicular)
+            }
+        }
+
+        // The inertia tensor of a body which is symmetric around `axis`, given its moments of
+        // inertia around that axis and around any axis perpendicular to it.
+        fn axisymmetric_inertia_tensor<T: Real>(axis: Vec3<T>, axial: T, perpendicular: T) -> Mat3<T> {
+            let length = axis.magnitude();
+            let u = if length > T::zero() { axis / length } else { Vec3::unit_z() };
+            let mut tensor = Mat3::identity() * perpendicular;
+            for i in 0..3 {
+                for j in 0..3 {
+                    tensor[(i, j)] = tensor[(i, j)] + (axial - perpendicular) * u[i] * u[j];
+                }
+            }
+            tensor
+        }
+
+        /// Moves an inertia tensor from a body's center of mass to a point at `offset` from it,
+        /// with the parallel axis theorem, i.e adds `mass * (offset·offset * I - offset * offsetᵀ)`.
+        ///
+        /// Tensors can only be moved away from the center of mass, not between arbitrary points.
+        ///
+        /// ```
+        /// # use vek::{Sphere, Vec3, Mat3, parallel_axis_shift};
+        /// let sphere = Sphere::new(Vec3::zero(), 1_f32);
+        /// let shifted = parallel_axis_shift(sphere.inertia_tensor(5.), 5., Vec3::new(0., 0., 2.));
+        /// assert_eq!(shifted, Mat3::with_diagonal(Vec3::new(22., 22., 2.)));
+        /// ```
+        pub fn parallel_axis_shift<T: Real>(tensor: Mat3<T>, mass: T, offset: Vec3<T>) -> Mat3<T> {
+            let d2 = offset.magnitude_squared();
+            let mut shifted = tensor;
+            for i in 0..3 {
+                for j in 0..3 {
+                    let identity = if i == j { d2 } else { T::zero() };
+                    shifted[(i, j)] = shifted[(i, j)] + mass * (identity - offset[i] * offset[j]);
+                }
+            }
+            shifted
+        }
+
+        /// Combines the parts of a composite body, each given as `(mass, center of mass, inertia
+        /// tensor about the center of mass)`, into the same for the whole body.
+        ///
+        /// All tensors must be expressed in the same axes. If the total mass is zero, so are the
+        /// returned center and tensor.
+        ///
+        /// ```
+        /// # extern crate vek;
+        /// # #[macro_use] extern crate approx;
+        /// # use vek::{Aabb, Vec3, combine_inertia};
+        /// # fn main() {
+        /// let left = Aabb { min: Vec3::new(-1_f32, 0., 0.), max: Vec3::new(0., 1., 1.) };
+        /// let right = Aabb { min: Vec3::new(0_f32, 0., 0.), max: Vec3::new(1., 1., 1.) };
+        /// let whole = Aabb { min: left.min, max: right.max };
+        /// let parts = [(2., left.center(), left.inertia_tensor(2.)), (2., right.center(), right.inertia_tensor(2.))];
+        /// let (mass, center, tensor) = combine_inertia(&parts);
+        /// assert_eq!(mass, 4.);
+        /// assert_relative_eq!(center, whole.center());
+        /// assert_relative_eq!(tensor, whole.inertia_tensor(4.));
+        /// # }
+        /// ```
+        pub fn combine_inertia<T: Real>(parts: &[(T, Vec3<T>, Mat3<T>)]) -> (T, Vec3<T>, Mat3<T>) {
+            let mass = parts.iter().fold(T::zero(), |sum, part| sum + part.0);
+            if mass == T::zero() {
+                return (mass, Vec3::zero(), Mat3::zero());
+            }
+            let center = parts.iter().fold(Vec3::zero(), |sum, &(m, c, _)| sum + c * m) / mass;
+            let tensor = parts.iter().fold(Mat3::zero(), |sum, &(m, c, tensor)| sum + parallel_axis_shift(tensor, m, c - center));
+            (mass, center, tensor)
+        }
 
         /// Ellipsis (2D), represented by center and radius in both axii.
         #[derive(Debug, Default, Clone, Copy, Hash, Eq, PartialEq, /*Ord, PartialOrd*/)]
@@ -685,6 +2167,20 @@ macro_rules! geom_complete_mod {
             pub center: Vec2<P>,
             pub radius: Extent2<E>,
         }
+        impl<T> Ellipsis<T,T> where T: Copy + One + Add<T, Output=T> + Sub<T, Output=T> + Div<T, Output=T> {
+            /// Gets the largest ellipsis that fits in the given bounding rectangle, i.e the one
+            /// which touches the middle of each of its sides.
+            ///
+            /// ```
+            /// # use vek::{Aabr, Ellipsis, Extent2, Vec2};
+            /// let aabr = Aabr { min: Vec2::new(0_f32, 2.), max: Vec2::new(4., 3.) };
+            /// let e = Ellipsis::inscribed_in_aabr(aabr);
+            /// assert_eq!(e, Ellipsis { center: Vec2::new(2., 2.5), radius: Extent2::new(2., 0.5) });
+            /// ```
+            pub fn inscribed_in_aabr(aabr: Aabr<T>) -> Self {
+                Self { center: aabr.center(), radius: aabr.half_size() }
+            }
+        }
         /// Nobody can possibly use this ???
         #[derive(Debug, Default, Clone, Copy, Hash, Eq, PartialEq, /*Ord, PartialOrd*/)]
         #[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
@@ -694,6 +2190,164 @@ macro_rules! geom_complete_mod {
             pub radius: Extent3<E>,
         }
 
+        /// Ellipsoid (3D), represented by center, radius along each of its axes, and orientation.
+        ///
+        /// Its axes are the X, Y and Z axes, rotated by `orientation`, which is expected to be
+        /// normalized. Unlike `Potato`, this can represent any ellipsoid.
+        #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, /*Ord, PartialOrd*/)]
+        #[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+        pub struct Ellipsoid<P,E> {
+            /// The ellipsoid's center.
+            pub center: Vec3<P>,
+            /// The ellipsoid's radius (i.e semi-axis length) along each of its axes.
+            pub radius: Extent3<E>,
+            /// The rotation from the X, Y and Z axes to the ellipsoid's axes.
+            pub orientation: Quaternion<E>,
+        }
+
+        /// The default ellipsoid has an identity orientation.
+        impl<P: Default, E: Default + Zero + One> Default for Ellipsoid<P,E> {
+            fn default() -> Self {
+                Self::new(Vec3::default(), Extent3::default(), Quaternion::identity())
+            }
+        }
+
+        impl<P,E> Ellipsoid<P,E> {
+            /// Creates a new `Ellipsoid` from `center`, `radius` and `orientation`.
+            pub fn new(center: Vec3<P>, radius: Extent3<E>, orientation: Quaternion<E>) -> Self {
+                Self { center, radius, orientation }
+            }
+        }
+
+        impl<T: Real + Add<T, Output=T>> Ellipsoid<T,T> {
+            /// Gets the largest axis-aligned ellipsoid that fits in the given bounding box, i.e the one
+            /// which touches the middle of each of its faces.
+            ///
+            /// ```
+            /// # use vek::{Aabb, Ellipsoid, Extent3, Vec3};
+            /// let aabb = Aabb { min: Vec3::new(0_f32, 2., -1.), max: Vec3::new(4., 3., 1.) };
+            /// let e = Ellipsoid::inscribed_in_aabb(aabb);
+            /// assert_eq!(e.center, Vec3::new(2., 2.5, 0.));
+            /// assert_eq!(e.radius, Extent3::new(2., 0.5, 1.));
+            /// assert_eq!(e.bounding_aabb(), aabb);
+            /// ```
+            pub fn inscribed_in_aabb(aabb: Aabb<T>) -> Self {
+                Self::new(aabb.center(), aabb.half_size(), Quaternion::identity())
+            }
+            /// Gets the confidence ellipsoid of a 3D normal distribution, given its `mean` and
+            /// `covariance` matrix (which must be symmetric and positive semi-definite), that
+            /// extends `n_std` standard deviations along each of its axes.
+            ///
+            /// The axes are the eigenvectors of the covariance matrix, sorted by decreasing
+            /// variance, and each radius is `n_std` times the square root of the matching
+            /// eigenvalue (see `Mat3::symmetric_eigen()`). Slightly negative eigenvalues, caused
+            /// by rounding errors, are treated as zero.
+            ///
+            /// Note that the probability for a sample to lie within this ellipsoid is _not_ the
+            /// same as in 1D: for instance, about 97.1% of samples are within 3 standard deviations
+            /// (as opposed to 99.7%).
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{Ellipsoid, Extent3, Mat3, Vec3};
+            /// # fn main() {
+            /// // Independent variables, whose standard deviations are 1, 4 and 0.5.
+            /// let covariance = Mat3::new(
+            ///     1_f64, 0., 0.,
+            ///     0., 16., 0.,
+            ///     0., 0., 0.25,
+            /// );
+            /// let e = Ellipsoid::from_covariance(Vec3::new(1., 2., 3.), covariance, 2.);
+            /// assert_eq!(e.center, Vec3::new(1., 2., 3.));
+            /// assert_relative_eq!(e.radius, Extent3::new(8., 2., 1.));
+            /// // The largest axis is along Y.
+            /// assert_relative_eq!(e.orientation * Vec3::unit_x(), Vec3::unit_y());
+            /// assert!(e.contains_point(Vec3::new(1., 9.9, 3.)));
+            /// assert!(!e.contains_point(Vec3::new(1., 2., 4.1)));
+            /// # }
+            /// ```
+            pub fn from_covariance(mean: Vec3<T>, covariance: Mat3<T>, n_std: T) -> Self {
+                let (variances, axes) = covariance.symmetric_eigen();
+                let radius = variances.map(|v| v.max(T::zero()).sqrt() * n_std);
+                // Converts the rotation matrix, whose columns are the axes, to a quaternion
+                // (Shepperd's method, which picks the most precise of four formulas).
+                let m = |i, j| axes[(i, j)];
+                let (one, two) = (T::one(), T::one() + T::one());
+                let four = two + two;
+                let trace = m(0, 0) + m(1, 1) + m(2, 2);
+                let (x, y, z, w) = if trace > T::zero() {
+                    let s = (trace + one).sqrt() * two;
+                    ((m(2, 1) - m(1, 2)) / s, (m(0, 2) - m(2, 0)) / s, (m(1, 0) - m(0, 1)) / s, s / four)
+                } else if m(0, 0) > m(1, 1) && m(0, 0) > m(2, 2) {
+                    let s = (one + m(0, 0) - m(1, 1) - m(2, 2)).sqrt() * two;
+                    (s / four, (m(0, 1) + m(1, 0)) / s, (m(0, 2) + m(2, 0)) / s, (m(2, 1) - m(1, 2)) / s)
+                } else if m(1, 1) > m(2, 2) {
+                    let s = (one + m(1, 1) - m(0, 0) - m(2, 2)).sqrt() * two;
+                    ((m(0, 1) + m(1, 0)) / s, s / four, (m(1, 2) + m(2, 1)) / s, (m(0, 2) - m(2, 0)) / s)
+                } else {
+                    let s = (one + m(2, 2) - m(0, 0) - m(1, 1)).sqrt() * two;
+                    ((m(0, 2) + m(2, 0)) / s, (m(1, 2) + m(2, 1)) / s, s / four, (m(1, 0) - m(0, 1)) / s)
+                };
+                let orientation = Quaternion::from_xyzw(x, y, z, w).normalized();
+                Self::new(mean, Extent3::new(radius.x, radius.y, radius.z), orientation)
+            }
+            /// Gets the sum of the squared coordinates of `p`, in the ellipsoid's frame where it
+            /// becomes the unit sphere, or `None` if `p` lies outside of a flat ellipsoid's plane.
+            fn unit_sphere_magnitude_squared(self, p: Vec3<T>) -> Option<T> {
+                let local = self.orientation.conjugate() * (p - self.center);
+                let mut sum = T::zero();
+                for (l, r) in local.into_iter().zip(Vec3::<T>::from(self.radius)) {
+                    if r > T::zero() {
+                        sum = sum + (l / r) * (l / r);
+                    } else if l != T::zero() {
+                        return None;
+                    }
+                }
+                Some(sum)
+            }
+            /// Does this ellipsoid contain the given point ?
+            ///
+            /// Points on the surface are contained. Flat ellipsoids (with a zero radius) contain
+            /// no point outside of their plane.
+            pub fn contains_point(self, p: Vec3<T>) -> bool {
+                self.unit_sphere_magnitude_squared(p).is_some_and(|m| m <= T::one())
+            }
+            /// Gets the smallest axis-aligned bounding box which contains this ellipsoid.
+            pub fn bounding_aabb(self) -> Aabb<T> {
+                let r = Vec3::<T>::from(self.radius);
+                let axes = [
+                    self.orientation * Vec3::unit_x() * r.x,
+                    self.orientation * Vec3::unit_y() * r.y,
+                    self.orientation * Vec3::unit_z() * r.z,
+                ];
+                let half_size = (axes[0] * axes[0] + axes[1] * axes[1] + axes[2] * axes[2]).map(Real::sqrt);
+                Aabb { min: self.center - half_size, max: self.center + half_size }
+            }
+            /// Gets the point of the surface which lies in the given direction from the center,
+            /// which doesn't need to be normalized.
+            ///
+            /// This returns the center if `direction` is zero, or if the ellipsoid is flat along it.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{Ellipsoid, Extent3, Quaternion, Vec3};
+            /// # fn main() {
+            /// let e = Ellipsoid::new(Vec3::zero(), Extent3::new(3_f32, 1., 2.), Quaternion::rotation_z(std::f32::consts::FRAC_PI_2));
+            /// assert_relative_eq!(e.surface_point(Vec3::new(0., 5., 0.)), Vec3::new(0., 3., 0.));
+            /// assert_relative_eq!(e.surface_point(Vec3::new(-1., 0., 0.)), Vec3::new(-1., 0., 0.));
+            /// assert_relative_eq!(e.surface_point(Vec3::new(1., 0., 1.)), Vec3::new(1., 0., 1.) * (0.8_f32).sqrt());
+            /// # }
+            /// ```
+            pub fn surface_point(self, direction: Vec3<T>) -> Vec3<T> {
+                match self.unit_sphere_magnitude_squared(self.center + direction) {
+                    Some(m) if m > T::zero() => self.center + direction / m.sqrt(),
+                    _ => self.center,
+                }
+            }
+        }
+
         /// 2D Line segment, represented by two points, `start` and `end`.
         #[derive(Debug, Default, Clone, Copy, Hash, Eq, PartialEq, /*Ord, PartialOrd*/)]
         #[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
@@ -714,6 +2368,451 @@ macro_rules! geom_complete_mod {
         geom_impl_line_segment!{LineSegment2 Vec2}
         geom_impl_line_segment!{LineSegment3 Vec3}
 
+        impl<T: Real> LineSegment2<T> {
+            /// Clips this line segment to the given bounding rectangle (boundary included), using the
+            /// Liang–Barsky algorithm, or returns `None` if they don't intersect.
+            ///
+            /// The result has the same direction as this segment. Endpoints which are inside the
+            /// rectangle are kept as is, and the others are moved onto its boundary (and clamped to it,
+            /// so that rounding errors never put them outside).
+            /// A segment which only touches the rectangle, at a corner or along an edge, is clipped to
+            /// the touching part, which is a single point (i.e `start == end`) in the former case.
+            ///
+            /// ```
+            /// # use vek::{Aabr, LineSegment2, Vec2};
+            /// let aabr = Aabr { min: Vec2::new(0_f32, 0.), max: Vec2::new(4., 4.) };
+            /// let segment = LineSegment2 { start: Vec2::new(-2., 1.), end: Vec2::new(2., 3.) };
+            /// assert_eq!(segment.clipped_to_aabr(aabr), Some(LineSegment2 { start: Vec2::new(0., 2.), end: Vec2::new(2., 3.) }));
+            /// let segment = LineSegment2 { start: Vec2::new(-2., 5.), end: Vec2::new(6., 5.) };
+            /// assert_eq!(segment.clipped_to_aabr(aabr), None);
+            /// ```
+            pub fn clipped_to_aabr(self, aabr: Aabr<T>) -> Option<Self> {
+                let direction = self.end - self.start;
+                let (t0, t1) = liang_barsky(self.start.as_slice(), direction.as_slice(), aabr.min.as_slice(), aabr.max.as_slice(), T::zero(), T::one())?;
+                let at = |t: T| Vec2::partial_min(Vec2::partial_max(self.start + direction * t, aabr.min), aabr.max);
+                Some(Self {
+                    start: if t0 == T::zero() { self.start } else { at(t0) },
+                    end: if t1 == T::one() { self.end } else { at(t1) },
+                })
+            }
+        }
+
+        impl<T: PrimInt> LineSegment2<T> {
+            /// Same as `clipped_to_aabr()`, for integer segments and rectangles.
+            ///
+            /// The intersection is computed exactly, without any floating-point arithmetic, then
+            /// the endpoints which moved onto the rectangle's boundary are rounded to the nearest
+            /// integer point (halves being rounded up), which stays within the rectangle.
+            /// A segment which misses the rectangle by any amount, however small, is rejected.
+            ///
+            /// Computations use 128-bit integers, so they are exact for all element types of up to
+            /// 32 bits. With 64-bit elements, the differences between coordinates must fit in an `i64`.
+            ///
+            /// ```
+            /// # use vek::{Aabr, LineSegment2, Vec2};
+            /// let aabr = Aabr { min: Vec2::new(0_i32, 0), max: Vec2::new(10, 10) };
+            /// let segment = LineSegment2 { start: Vec2::new(-5, 0), end: Vec2::new(5, 3) };
+            /// // The exact intersection with the left edge is at (0, 1.5).
+            /// assert_eq!(segment.clipped_to_aabr_integer(aabr), Some(LineSegment2 { start: Vec2::new(0, 2), end: Vec2::new(5, 3) }));
+            /// ```
+            pub fn clipped_to_aabr_integer(self, aabr: Aabr<T>) -> Option<Self> {
+                let wide = |v: Vec2<T>| [v.x.to_i128().unwrap(), v.y.to_i128().unwrap()];
+                let (start, end) = (wide(self.start), wide(self.end));
+                let (t0, t1) = liang_barsky_exact(start, end, wide(aabr.min), wide(aabr.max))?;
+                // Rounds `start + (end - start) * t` to the nearest integer point.
+                let at = |(n, d): (i128, i128)| {
+                    let round = |i: usize| {
+                        let offset = (end[i] - start[i]) * n;
+                        let q = offset.div_euclid(d);
+                        start[i] + if 2 * offset.rem_euclid(d) >= d { q + 1 } else { q }
+                    };
+                    Vec2::new(T::from(round(0)).unwrap(), T::from(round(1)).unwrap())
+                };
+                Some(Self {
+                    start: if t0.0 == 0 { self.start } else { at(t0) },
+                    end: if t1.0 == t1.1 { self.end } else { at(t1) },
+                })
+            }
+        }
+
+        impl<T: Real> LineSegment3<T> {
+            /// Clips this line segment to the given bounding box (boundary included), using the
+            /// Liang–Barsky algorithm, or returns `None` if they don't intersect.
+            ///
+            /// See `LineSegment2::clipped_to_aabr()` for details.
+            pub fn clipped_to_aabb(self, aabb: Aabb<T>) -> Option<Self> {
+                let direction = self.end - self.start;
+                let (t0, t1) = liang_barsky(self.start.as_slice(), direction.as_slice(), aabb.min.as_slice(), aabb.max.as_slice(), T::zero(), T::one())?;
+                let at = |t: T| Vec3::partial_min(Vec3::partial_max(self.start + direction * t, aabb.min), aabb.max);
+                Some(Self {
+                    start: if t0 == T::zero() { self.start } else { at(t0) },
+                    end: if t1 == T::one() { self.end } else { at(t1) },
+                })
+            }
+        }
+
+        // NOTE: There's never a sane Default for these (the normal must be normalized), so don't implement or derive it!!
+        /// An infinite 2D line, represented by its unit normal and its signed distance from the origin.
+        ///
+        /// The line is the set of points `p` such that `normal.dot(p) == distance`.
+        ///
+        /// ```
+        /// # use vek::{Line2, Vec2};
+        /// let line = Line2::from_point_and_normal(Vec2::new(0_f32, 2.), Vec2::unit_y());
+        /// assert_eq!(line.distance, 2.);
+        /// assert_eq!(line.signed_distance_to_point(Vec2::new(5., -1.)), -3.);
+        /// assert_eq!(line.reflected_point(Vec2::new(5., -1.)), Vec2::new(5., 5.));
+        /// ```
+        #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, /*Ord, PartialOrd*/)]
+        #[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+        pub struct Line2<T> {
+            /// The line's normal. **Methods expect it to be normalized**.
+            pub normal: Vec2<T>,
+            /// The signed distance from the origin to the line, along `normal`.
+            pub distance: T,
+        }
+        /// A 3D plane, represented by its unit normal and its signed distance from the origin.
+        ///
+        /// The plane is the set of points `p` such that `normal.dot(p) == distance`.
+        ///
+        /// ```
+        /// # use vek::{Plane, Vec3};
+        /// let plane = Plane::from_point_and_normal(Vec3::new(1_f32, 2., 3.), Vec3::unit_z() * 4.);
+        /// assert_eq!(plane.signed_distance_to_point(Vec3::new(9., 9., 5.)), 2.);
+        /// assert_eq!(plane.projected_point(Vec3::new(9., 9., 5.)), Vec3::new(9., 9., 3.));
+        /// ```
+        #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, /*Ord, PartialOrd*/)]
+        #[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+        pub struct Plane<T> {
+            /// The plane's normal. **Methods expect it to be normalized**.
+            pub normal: Vec3<T>,
+            /// The signed distance from the origin to the plane, along `normal`.
+            pub distance: T,
+        }
+
+        geom_impl_line2_or_plane!{Line2 ("`Line2`") Vec2}
+        geom_impl_line2_or_plane!{Plane ("`Plane`") Vec3}
+
+        impl<T: Real> Line2<T> {
+            /// Creates the line which goes through `a` and `b`, which must be distinct.
+            ///
+            /// The normal points to the left of the direction from `a` to `b` (with Y pointing upwards).
+            pub fn from_points(a: Vec2<T>, b: Vec2<T>) -> Self {
+                let d = b - a;
+                Self::from_point_and_normal(a, Vec2::new(-d.y, d.x))
+            }
+        }
+        impl<T: Real> Plane<T> {
+            /// Creates the plane which goes through `a`, `b` and `c`, which must not be collinear.
+            ///
+            /// The normal is on the side from which `a`, `b` and `c` appear counter-clockwise.
+            pub fn from_points(a: Vec3<T>, b: Vec3<T>, c: Vec3<T>) -> Self {
+                Self::from_point_and_normal(a, (b - a).cross(c - a))
+            }
+            /// Gets the single point where three planes meet, or `None` if they don't meet in
+            /// a single point (e.g two of them are parallel, or all three share a line).
+            ///
+            /// Planes that are too close to these cases are also rejected, instead of yielding a
+            /// far-off and inaccurate point: see `intersection_of_three_with_epsilon()`, which this
+            /// calls with the square root of `T::epsilon()`.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{Plane, Vec3};
+            /// # fn main() {
+            /// let x = Plane::new(Vec3::unit_x(), 1_f32);
+            /// let y = Plane::new(Vec3::unit_y(), 2.);
+            /// let z = Plane::new(Vec3::unit_z(), 3.);
+            /// assert_relative_eq!(Plane::intersection_of_three(x, y, z).unwrap(), Vec3::new(1., 2., 3.));
+            /// assert_eq!(Plane::intersection_of_three(x, y, x.flipped()), None);
+            /// # }
+            /// ```
+            pub fn intersection_of_three(a: Self, b: Self, c: Self) -> Option<Vec3<T>> {
+                Self::intersection_of_three_with_epsilon(a, b, c, T::epsilon().sqrt())
+            }
+            /// Same as `intersection_of_three()`, but with an explicit threshold for the absolute
+            /// value of the normals' triple product (the determinant of the system), below which
+            /// the planes are considered not to meet in a single point.
+            ///
+            /// The error of the result, relative to the planes' distances from the origin, grows
+            /// like the machine epsilon divided by that triple product.
+            pub fn intersection_of_three_with_epsilon(a: Self, b: Self, c: Self, epsilon: T) -> Option<Vec3<T>> {
+                let (bc, ca, ab) = (b.normal.cross(c.normal), c.normal.cross(a.normal), a.normal.cross(b.normal));
+                let det = a.normal.dot(bc);
+                if det.abs() <= epsilon {
+                    return None;
+                }
+                Some((bc * a.distance + ca * b.distance + ab * c.distance) / det)
+            }
+        }
+
+        impl<T: Real> Aabb<T> {
+            /// Gets the planes of this box's faces, with normals pointing outwards, in this order:
+            /// min X, max X, min Y, max Y, min Z, max Z.
+            ///
+            /// Points inside the box are on the negative side of all of them.
+            ///
+            /// ```
+            /// # use vek::{Aabb, Vec3};
+            /// let aabb = Aabb { min: Vec3::new(0_f32, 1., 2.), max: Vec3::new(3., 4., 5.) };
+            /// let planes = aabb.face_planes();
+            /// assert_eq!(planes[1].normal, Vec3::unit_x());
+            /// assert_eq!(planes[1].signed_distance_to_point(Vec3::new(4., 2., 3.)), 1.);
+            /// assert!(planes.iter().all(|plane| plane.signed_distance_to_point(aabb.center()) < 0.));
+            /// ```
+            pub fn face_planes(self) -> [Plane<T>; 6] {
+                let Self { min, max } = self;
+                [
+                    Plane::new(-Vec3::unit_x(), -min.x),
+                    Plane::new( Vec3::unit_x(),  max.x),
+                    Plane::new(-Vec3::unit_y(), -min.y),
+                    Plane::new( Vec3::unit_y(),  max.y),
+                    Plane::new(-Vec3::unit_z(), -min.z),
+                    Plane::new( Vec3::unit_z(),  max.z),
+                ]
+            }
+        }
+
+        /// 2D triangle, represented by its three vertices.
+        #[derive(Debug, Default, Clone, Copy, Hash, Eq, PartialEq, /*Ord, PartialOrd*/)]
+        #[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+        #[allow(missing_docs)]
+        pub struct Triangle2<T> {
+            pub a: Vec2<T>,
+            pub b: Vec2<T>,
+            pub c: Vec2<T>,
+        }
+
+        impl<T> Triangle2<T> {
+            /// Creates a triangle from its three vertices.
+            pub fn new(a: Vec2<T>, b: Vec2<T>, c: Vec2<T>) -> Self {
+                Self { a, b, c }
+            }
+            /// Gets twice the signed area of this triangle; positive if its vertices are
+            /// counter-clockwise (with Y pointing upwards).
+            pub fn signed_double_area(self) -> T where T: Copy + Sub<Output=T> + Mul<Output=T> {
+                let (ab, ac) = (self.b - self.a, self.c - self.a);
+                ab.x * ac.y - ab.y * ac.x
+            }
+        }
+
+        impl Triangle2<f32> {
+            /// Iterates over the pixels covered by this triangle, along with their barycentric
+            /// weights (relative to `a`, `b` and `c` respectively).
+            ///
+            /// Pixel `(x, y)` is the unit square which spans `x..x+1` and `y..y+1`; it is covered
+            /// if its center is inside the triangle. Centers which lie exactly on an edge are
+            /// resolved with the top-left fill rule (with Y pointing upwards, a "top" edge is a
+            /// horizontal edge above the triangle's interior), so that triangles which share an edge
+            /// never cover the same pixel twice nor leave gaps between them.
+            ///
+            /// To make this guarantee exact, vertices are first snapped to 1/256th of a pixel.
+            /// Both windings are accepted. Degenerate triangles cover nothing.
+            ///
+            /// Pixels are yielded row by row, from low to high Y, then from low to high X.
+            ///
+            /// ```
+            /// # use vek::{Triangle2, Vec2};
+            /// let tri = Triangle2::new(Vec2::new(0_f32, 0.), Vec2::new(4., 0.), Vec2::new(0., 4.));
+            /// let pixels: Vec<_> = tri.covered_pixels_iter().map(|(p, _)| p).collect();
+            /// // The center of (3, 0) lies on the hypotenuse, which is neither a top nor a left edge.
+            /// assert_eq!(pixels, [
+            ///     Vec2::new(0, 0), Vec2::new(1, 0), Vec2::new(2, 0),
+            ///     Vec2::new(0, 1), Vec2::new(1, 1),
+            ///     Vec2::new(0, 2),
+            /// ]);
+            /// ```
+            pub fn covered_pixels_iter(self) -> CoveredPixels {
+                self.covered_pixels_iter_clipped(Aabr { min: Vec2::broadcast(i32::MIN), max: Vec2::broadcast(i32::MAX) })
+            }
+            /// Same as `covered_pixels_iter()`, but only yields the pixels that are within `clip`,
+            /// i.e `(x, y)` such that `clip.min.x <= x < clip.max.x` and `clip.min.y <= y < clip.max.y`.
+            ///
+            /// Rasterizing the same triangle over adjacent tiles yields each of its pixels exactly once.
+            pub fn covered_pixels_iter_clipped(self, clip: Aabr<i32>) -> CoveredPixels {
+                CoveredPixels::new(self, clip)
+            }
+        }
+
+        /// How much of an `Aabr` a shape covers, as given by `Triangle2::classify_aabr()`.
+        #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+        #[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+        pub enum Coverage {
+            /// The whole `Aabr` is covered, including its boundary.
+            FullyInside,
+            /// The `Aabr` is partly covered, or only touched.
+            Partial,
+            /// The `Aabr` isn't covered at all.
+            Outside,
+        }
+
+        impl<T: Real> Triangle2<T> {
+            /// Classifies `aabr` according to how much of it this triangle covers.
+            ///
+            /// This is exact (up to rounding), since it tests all possible separating axes, i.e
+            /// those of the `Aabr` and the normals of the triangle's edges. An `Aabr` which only
+            /// touches the triangle is `Partial`, and degenerate triangles never fully cover an `Aabr`.
+            /// Both windings are accepted.
+            ///
+            /// ```
+            /// # use vek::{Triangle2, Aabr, Coverage, Vec2};
+            /// let tri = Triangle2::new(Vec2::new(0_f32, 0.), Vec2::new(8., 0.), Vec2::new(0., 8.));
+            /// let aabr = |x, y| Aabr { min: Vec2::new(x, y), max: Vec2::new(x + 2., y + 2.) };
+            /// assert_eq!(tri.classify_aabr(aabr(1., 1.)), Coverage::FullyInside);
+            /// assert_eq!(tri.classify_aabr(aabr(3., 3.)), Coverage::Partial);
+            /// assert_eq!(tri.classify_aabr(aabr(5., 5.)), Coverage::Outside);
+            /// ```
+            pub fn classify_aabr(self, aabr: Aabr<T>) -> Coverage {
+                let min = Vec2::<T>::partial_min(self.a, Vec2::partial_min(self.b, self.c));
+                let max = Vec2::<T>::partial_max(self.a, Vec2::partial_max(self.b, self.c));
+                if max.x < aabr.min.x || max.y < aabr.min.y || min.x > aabr.max.x || min.y > aabr.max.y {
+                    return Coverage::Outside;
+                }
+                let corners = [aabr.min, Vec2::new(aabr.max.x, aabr.min.y), aabr.max, Vec2::new(aabr.min.x, aabr.max.y)];
+                let double_area = self.signed_double_area();
+                let sign = if double_area < T::zero() { -T::one() } else { T::one() };
+                let mut is_inside = double_area != T::zero();
+                for &(from, to) in &[(self.a, self.b), (self.b, self.c), (self.c, self.a)] {
+                    // Positive on the side of the interior.
+                    let delta = to - from;
+                    let edge = |p: Vec2<T>| (delta.x * (p.y - from.y) - delta.y * (p.x - from.x)) * sign;
+                    if corners.iter().all(|&p| edge(p) < T::zero()) {
+                        return Coverage::Outside;
+                    }
+                    is_inside &= corners.iter().all(|&p| edge(p) >= T::zero());
+                }
+                if is_inside { Coverage::FullyInside } else { Coverage::Partial }
+            }
+            /// Iterates over the tiles that this triangle covers, even partly or only on their
+            /// boundary, for tiled rendering, light binning, or occlusion culling.
+            ///
+            /// Tile `(x, y)` spans `x*w..(x+1)*w` and `y*h..(y+1)*h`, where `w` and `h` are
+            /// `tile_size`'s dimensions. Unlike `covered_pixels_iter()`, which only yields tiles
+            /// (pixels) whose center is covered, this is conservative: every tile which has any point
+            /// in common with the triangle is yielded (see `classify_aabr()`), so this may yield tiles
+            /// which share only an edge or a corner with it.
+            ///
+            /// Tiles are yielded row by row, from low to high Y, then from low to high X.
+            ///
+            /// # Panics
+            /// Panics if `tile_size` isn't positive, or if the tiles' coordinates don't fit in an `i32`.
+            ///
+            /// ```
+            /// # use vek::{Triangle2, Extent2, Vec2};
+            /// let tri = Triangle2::new(Vec2::new(1_f32, 1.), Vec2::new(30., 2.), Vec2::new(2., 20.));
+            /// let tiles: Vec<_> = tri.covered_tiles_conservative(Extent2::new(16., 16.)).collect();
+            /// assert_eq!(tiles, [Vec2::new(0, 0), Vec2::new(1, 0), Vec2::new(0, 1)]);
+            /// ```
+            pub fn covered_tiles_conservative(self, tile_size: Extent2<T>) -> impl Iterator<Item=Vec2<i32>> {
+                assert!(tile_size.w > T::zero() && tile_size.h > T::zero(), "tiles must have a positive size");
+                let min = Vec2::<T>::partial_min(self.a, Vec2::partial_min(self.b, self.c));
+                let max = Vec2::<T>::partial_max(self.a, Vec2::partial_max(self.b, self.c));
+                let size = Vec2::from(tile_size);
+                let tile = |p: Vec2<T>| (p / size).map(|e| e.floor().to_i32().expect("tile coordinates must fit in an i32"));
+                let (first, last) = (tile(min), tile(max));
+                (first.y..=last.y)
+                    .flat_map(move |y| (first.x..=last.x).map(move |x| Vec2::new(x, y)))
+                    .filter(move |&tile| {
+                        let min = Vec2::new(T::from(tile.x).unwrap(), T::from(tile.y).unwrap()) * size;
+                        self.classify_aabr(Aabr { min, max: min + size }) != Coverage::Outside
+                    })
+            }
+        }
+
+        /// An edge function in sub-pixel fixed point, evaluated at pixel centers.
+        #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+        struct RasterEdge {
+            origin: Vec2<i128>,
+            delta: Vec2<i128>,
+            // 0 if points on the edge are covered, 1 otherwise.
+            bias: i128,
+        }
+
+        /// Iterator over the pixels covered by a `Triangle2`, and their barycentric weights.
+        ///
+        /// See `Triangle2::covered_pixels_iter()`.
+        #[derive(Debug, Clone, Hash, Eq, PartialEq)]
+        pub struct CoveredPixels {
+            // The edge opposite to each of the vertices, in their original order.
+            edges: [RasterEdge; 3],
+            double_area: i128,
+            min_x: i32,
+            max_x: i32,
+            max_y: i32,
+            x: i32,
+            y: i32,
+        }
+
+        impl CoveredPixels {
+            const SUBPIXEL_BITS: u32 = 8;
+
+            fn to_fixed(v: Vec2<f32>) -> Vec2<i128> {
+                let one = (1_u32 << Self::SUBPIXEL_BITS) as f32;
+                v.map(|e| (e * one).round() as i64 as i128)
+            }
+            // Gets the range of pixels whose centers are within `lo..=hi` (in fixed point),
+            // clamped to `clip_lo..clip_hi`, as an inclusive range.
+            fn pixel_range(lo: i128, hi: i128, clip_lo: i32, clip_hi: i32) -> (i32, i32) {
+                let half = 1_i128 << (Self::SUBPIXEL_BITS - 1);
+                // First pixel whose center is >= lo, and last pixel whose center is <= hi.
+                let first = (lo - half + (1 << Self::SUBPIXEL_BITS) - 1) >> Self::SUBPIXEL_BITS;
+                let last = (hi - half) >> Self::SUBPIXEL_BITS;
+                let first = first.max(clip_lo as i128) as i32;
+                let last = last.min(clip_hi as i128 - 1);
+                // `last` may end up below `clip_lo`, in which case the range is empty anyway.
+                (first, last.max(first as i128 - 1) as i32)
+            }
+            fn new(tri: Triangle2<f32>, clip: Aabr<i32>) -> Self {
+                let [a, b, c] = [tri.a, tri.b, tri.c].map(Self::to_fixed);
+                let double_area = Triangle2::new(a, b, c).signed_double_area();
+                let edge = |from: Vec2<i128>, to: Vec2<i128>| {
+                    // Make the interior lie on the positive side of the edge.
+                    let (from, to) = if double_area < 0 { (to, from) } else { (from, to) };
+                    let delta = to - from;
+                    // With counter-clockwise winding, left edges go down and top edges go left.
+                    let is_top_left = delta.y < 0 || (delta.y == 0 && delta.x < 0);
+                    RasterEdge { origin: from, delta, bias: if is_top_left { 0 } else { 1 } }
+                };
+                let edges = [edge(b, c), edge(c, a), edge(a, b)];
+                let (min, max) = (Vec2::partial_min(a, Vec2::partial_min(b, c)), Vec2::partial_max(a, Vec2::partial_max(b, c)));
+                let (min_x, max_x) = Self::pixel_range(min.x, max.x, clip.min.x, clip.max.x);
+                let (min_y, max_y) = Self::pixel_range(min.y, max.y, clip.min.y, clip.max.y);
+                let max_y = if double_area == 0 || min_x > max_x { min_y - 1 } else { max_y };
+                Self { edges, double_area: double_area.abs(), min_x, max_x, max_y, x: min_x, y: min_y }
+            }
+        }
+
+        impl Iterator for CoveredPixels {
+            type Item = (Vec2<i32>, Vec3<f32>);
+            fn next(&mut self) -> Option<Self::Item> {
+                let half = 1_i128 << (Self::SUBPIXEL_BITS - 1);
+                while self.y <= self.max_y {
+                    let (x, y) = (self.x, self.y);
+                    if x >= self.max_x {
+                        self.x = self.min_x;
+                        self.y += 1;
+                    } else {
+                        self.x += 1;
+                    }
+                    let center = Vec2::new(x as i128, y as i128).map(|e| (e << Self::SUBPIXEL_BITS) + half);
+                    let mut weights = [0_i128; 3];
+                    let mut is_inside = true;
+                    for (w, e) in weights.iter_mut().zip(self.edges.iter()) {
+                        let p = center - e.origin;
+                        *w = e.delta.x * p.y - e.delta.y * p.x;
+                        is_inside &= *w >= e.bias;
+                    }
+                    if is_inside {
+                        let area = self.double_area as f64;
+                        let weights = Vec3::<i128>::from(weights).map(|w| (w as f64 / area) as f32);
+                        return Some((Vec2::new(x, y), weights));
+                    }
+                }
+                None
+            }
+        }
+
         /// 3D ray, represented by a starting point and a normalized direction vector.
         #[derive(Debug, Default, Clone, Copy, Hash, Eq, PartialEq, /*Ord, PartialOrd*/)]
         #[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
@@ -740,12 +2839,32 @@ macro_rules! geom_complete_mod {
             ///
             /// This uses the [Möller–Trumbore intersection algorithm](https://en.wikipedia.org/wiki/M%C3%B6ller%E2%80%93Trumbore_intersection_algorithm).
             pub fn triangle_intersection(&self, tri: [Vec3<T>; 3]) -> Option<T> {
+                self.triangle_intersection_with_epsilon(tri, T::epsilon())
+            }
+            /// Same as `triangle_intersection()`, but the ray is considered parallel to the
+            /// triangle's plane when the (unnormalized) determinant of the system is within
+            /// `epsilon` of zero, instead of `T::epsilon()`.
+            ///
+            /// The determinant scales with the square of the triangle's size, so for very
+            /// small or very large triangles, use a scale-relative tolerance:
+            ///
+            /// ```
+            /// # use vek::{Ray, Vec3};
+            /// # use vek::ops::relative_epsilon_for;
+            /// let s = 1e-4_f32;
+            /// let tri = [Vec3::new(0., 0., 0.), Vec3::new(s, 0., 0.), Vec3::new(0., s, 0.)];
+            /// let ray = Ray::new(Vec3::new(s / 4., s / 4., 1.), -Vec3::unit_z());
+            /// assert_eq!(ray.triangle_intersection(tri), None);
+            /// let eps = relative_epsilon_for(Some(s * s), 4.);
+            /// assert_eq!(ray.triangle_intersection_with_epsilon(tri, eps), Some(1.));
+            /// ```
+            pub fn triangle_intersection_with_epsilon(&self, tri: [Vec3<T>; 3], epsilon: T) -> Option<T> {
                 let (v0, v1, v2) = (tri[0], tri[1], tri[2]);
                 let edge1 = v1 - v0;
                 let edge2 = v2 - v0;
                 let h = self.direction.cross(edge2);
                 let a = edge1.dot(h);
-                if a > -T::epsilon() && a < T::epsilon() {
+                if a > -epsilon && a < epsilon {
                     return None;
                 }
                 let f = a.recip();
@@ -761,14 +2880,279 @@ macro_rules! geom_complete_mod {
                 }
                 Some(f * edge2.dot(q))
             }
+            /// Gets the part of this ray which is inside the given bounding box (boundary included),
+            /// from where the ray enters it (or its origin, if it starts inside) to where it exits it,
+            /// or `None` if they don't intersect.
+            ///
+            /// See `LineSegment3::clipped_to_aabb()`.
+            ///
+            /// ```
+            /// # use vek::{Aabb, LineSegment3, Ray, Vec3};
+            /// let aabb = Aabb { min: Vec3::new(0_f32, 0., 0.), max: Vec3::new(4., 4., 4.) };
+            /// let ray = Ray::new(Vec3::new(2., 2., -3.), Vec3::unit_z());
+            /// assert_eq!(ray.clipped_to_aabb(aabb), Some(LineSegment3 { start: Vec3::new(2., 2., 0.), end: Vec3::new(2., 2., 4.) }));
+            /// let far = Aabb { min: Vec3::new(10., 0., 0.), max: Vec3::new(14., 4., 4.) };
+            /// assert_eq!(ray.clipped_to_aabb(far), None);
+            /// ```
+            pub fn clipped_to_aabb(self, aabb: Aabb<T>) -> Option<LineSegment3<T>> {
+                let (t0, t1) = liang_barsky(self.origin.as_slice(), self.direction.as_slice(), aabb.min.as_slice(), aabb.max.as_slice(), T::zero(), T::max_value())?;
+                let at = |t: T| Vec3::partial_min(Vec3::partial_max(self.origin + self.direction * t, aabb.min), aabb.max);
+                Some(LineSegment3 {
+                    start: if t0 == T::zero() { self.origin } else { at(t0) },
+                    end: at(t1),
+                })
+            }
+            /// Project the given point onto this ray (i.e gets the closest point on the ray).
+            pub fn projected_point(self, p: Vec3<T>) -> Vec3<T> {
+                let t = (p - self.origin).dot(self.direction).max(T::zero());
+                self.origin + self.direction * t
+            }
+            /// Get the smallest distance between this ray and a point.
+            pub fn distance_to_point(self, p: Vec3<T>) -> T {
+                self.projected_point(p).distance(p)
+            }
+            /// Returns the smallest distance between this ray and a curve, to within `tolerance`
+            /// (see `CubicBezier3::distance_to_shape()` for the exact guarantee).
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{CubicBezier3, Ray, Vec3};
+            /// # fn main() {
+            /// let curve = CubicBezier3 {
+            ///     start: Vec3::new(0_f64, 0., 0.),
+            ///     ctrl0: Vec3::new(0., 4., 0.),
+            ///     ctrl1: Vec3::new(4., 4., 0.),
+            ///     end: Vec3::new(4., 0., 0.),
+            /// };
+            /// let ray = Ray::new(Vec3::new(2., 5., -10.), Vec3::unit_z());
+            /// assert_relative_eq!(ray.distance_to_curve(curve, 1e-6), 2., epsilon = 1e-6);
+            /// // The curve is behind the ray.
+            /// let ray = Ray::new(Vec3::new(2., 3., 1.), Vec3::unit_z());
+            /// assert_relative_eq!(ray.distance_to_curve(curve, 1e-6), 1., epsilon = 1e-6);
+            /// # }
+            /// ```
+            pub fn distance_to_curve(self, curve: CubicBezier3<T>, tolerance: T) -> T {
+                curve.distance_to_shape(|p| self.distance_to_point(p), tolerance)
+            }
         }
-    }
-}
 
-#[cfg(all(nightly, feature="repr_simd"))]
-pub mod repr_simd {
-    //! Basic geometric primitives that use `#[repr(simd)]` vectors.
-    use super::*;
+        /// A read-only view over a heightmap, i.e a grid of heights stored in row-major order.
+        ///
+        /// Grid coordinates map to world coordinates as follows: the grid's X axis is the world's
+        /// X axis, the grid's Y axis is the world's Z axis, and heights are along the world's Y axis.
+        /// Sample `(x, y)` lies at world position `(x * cell.w, height, y * cell.h)` where `cell` is
+        /// the world size of a cell.
+        ///
+        /// All queries clamp positions to the grid's bounds, so they never panic for positions
+        /// on (or beyond) the last row or column.
+        #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+        pub struct Heightfield<'a, T: 'a> {
+            /// The heights, in row-major order (i.e the height at `(x, y)` is at index `y * size.w + x`).
+            pub heights: &'a [T],
+            /// The number of samples along each axis.
+            pub size: Extent2<usize>,
+        }
+
+        impl<'a, T: Real> Heightfield<'a, T> {
+            /// Creates a `Heightfield` view over the given heights.
+            ///
+            /// # Panics
+            /// Panics if `heights.len()` is not `size.w * size.h`, or if `size` is zero in either dimension.
+            pub fn new(heights: &'a [T], size: Extent2<usize>) -> Self {
+                assert_eq!(heights.len(), size.w * size.h);
+                assert!(size.w > 0 && size.h > 0);
+                Self { heights, size }
+            }
+            /// Gets the height at the given sample, clamping coordinates to the grid's bounds.
+            pub fn height_at(&self, x: usize, y: usize) -> T {
+                let x = x.min(self.size.w - 1);
+                let y = y.min(self.size.h - 1);
+                self.heights[y * self.size.w + x]
+            }
+            // Returns the index of the cell containing `p` along an axis of `n` samples, and the
+            // position of `p` within this cell (in [0, 1]).
+            fn cell(p: T, n: usize) -> (usize, T) {
+                if n < 2 {
+                    return (0, T::zero());
+                }
+                let last = T::from(n - 1).unwrap();
+                let p = p.max(T::zero()).min(last);
+                let i = p.floor().to_usize().unwrap().min(n - 2);
+                (i, p - T::from(i).unwrap())
+            }
+            /// Samples the height at the given position (in grid coordinates), bilinearly
+            /// interpolating between the four surrounding samples.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{Heightfield, Vec2, Extent2};
+            /// # fn main() {
+            /// let heights = [0_f32, 1., 2., 3.];
+            /// let hf = Heightfield::new(&heights, Extent2::new(2, 2));
+            /// assert_relative_eq!(hf.sample_height_bilinear(Vec2::new(0.5, 0.5)), 1.5);
+            /// assert_relative_eq!(hf.sample_height_bilinear(Vec2::new(1., 1.)), 3.);
+            /// assert_relative_eq!(hf.sample_height_bilinear(Vec2::new(7., -2.)), 1.);
+            /// # }
+            /// ```
+            pub fn sample_height_bilinear(&self, pos: Vec2<T>) -> T {
+                let (x, fx) = Self::cell(pos.x, self.size.w);
+                let (y, fy) = Self::cell(pos.y, self.size.h);
+                let h00 = self.height_at(x, y);
+                let h10 = self.height_at(x + 1, y);
+                let h01 = self.height_at(x, y + 1);
+                let h11 = self.height_at(x + 1, y + 1);
+                let top = h00 + (h10 - h00) * fx;
+                let bottom = h01 + (h11 - h01) * fx;
+                top + (bottom - top) * fy
+            }
+            /// Computes the world-space unit normal at the given position (in grid coordinates),
+            /// using central differences of `sample_height_bilinear()` one cell apart.
+            ///
+            /// At the grid's borders, the differences become one-sided.
+            pub fn sample_normal(&self, pos: Vec2<T>, cell_world_size: Extent2<T>) -> Vec3<T> {
+                let clamp = |p: T, n: usize| p.max(T::zero()).min(T::from(n - 1).unwrap());
+                let x0 = clamp(pos.x - T::one(), self.size.w);
+                let x1 = clamp(pos.x + T::one(), self.size.w);
+                let y0 = clamp(pos.y - T::one(), self.size.h);
+                let y1 = clamp(pos.y + T::one(), self.size.h);
+                let slope = |h0: T, h1: T, p0: T, p1: T, cell: T| {
+                    if p1 > p0 { (h1 - h0) / ((p1 - p0) * cell) } else { T::zero() }
+                };
+                let dx = slope(
+                    self.sample_height_bilinear(Vec2::new(x0, pos.y)),
+                    self.sample_height_bilinear(Vec2::new(x1, pos.y)),
+                    x0, x1, cell_world_size.w
+                );
+                let dz = slope(
+                    self.sample_height_bilinear(Vec2::new(pos.x, y0)),
+                    self.sample_height_bilinear(Vec2::new(pos.x, y1)),
+                    y0, y1, cell_world_size.h
+                );
+                Vec3::new(-dx, T::one(), -dz).normalized()
+            }
+            /// Finds the first intersection of a world-space ray with the heightfield's surface,
+            /// within `max_distance` along the ray.
+            ///
+            /// The surface is made of bilinear patches, one per cell. This steps through the cells
+            /// crossed by the ray (DDA) and solves for the exact intersection with each patch.
+            pub fn raycast_heightfield(&self, ray: Ray<T>, cell_world_size: Extent2<T>, max_distance: T) -> Option<Vec3<T>> {
+                if self.size.w < 2 || self.size.h < 2 {
+                    return None;
+                }
+                // Work in grid space, where cells have unit size. Heights are unchanged.
+                let o = Vec2::new(ray.origin.x / cell_world_size.w, ray.origin.z / cell_world_size.h);
+                let d = Vec2::new(ray.direction.x / cell_world_size.w, ray.direction.z / cell_world_size.h);
+                let max = Vec2::new(T::from(self.size.w - 1).unwrap(), T::from(self.size.h - 1).unwrap());
+
+                // Clip the ray against the grid's bounds.
+                let mut t_min = T::zero();
+                let mut t_max = max_distance;
+                for i in 0..2 {
+                    if d[i] == T::zero() {
+                        if o[i] < T::zero() || o[i] > max[i] {
+                            return None;
+                        }
+                    } else {
+                        let t0 = (T::zero() - o[i]) / d[i];
+                        let t1 = (max[i] - o[i]) / d[i];
+                        t_min = t_min.max(t0.min(t1));
+                        t_max = t_max.min(t0.max(t1));
+                    }
+                }
+                if t_min > t_max {
+                    return None;
+                }
+
+                let p = o + d * t_min;
+                let (mut cell_x, _) = Self::cell(p.x, self.size.w);
+                let (mut cell_y, _) = Self::cell(p.y, self.size.h);
+                let mut t_enter = t_min;
+                loop {
+                    let cell = Vec2::new(T::from(cell_x).unwrap(), T::from(cell_y).unwrap());
+                    // Parameter at which the ray leaves the current cell along each axis.
+                    let exit = |o: T, d: T, c: T| {
+                        if d > T::zero() {
+                            (c + T::one() - o) / d
+                        } else if d < T::zero() {
+                            (c - o) / d
+                        } else {
+                            t_max
+                        }
+                    };
+                    let t_exit_x = exit(o.x, d.x, cell.x);
+                    let t_exit_y = exit(o.y, d.y, cell.y);
+                    let t_exit = t_exit_x.min(t_exit_y).min(t_max);
+
+                    if let Some(t) = self.cell_intersection(ray, o - cell, d, cell_x, cell_y, t_enter, t_exit) {
+                        return Some(ray.origin + ray.direction * t);
+                    }
+                    if t_exit >= t_max {
+                        return None;
+                    }
+                    if t_exit_x <= t_exit_y {
+                        if d.x > T::zero() { cell_x += 1; } else { cell_x = cell_x.wrapping_sub(1); }
+                    } else if d.y > T::zero() {
+                        cell_y += 1;
+                    } else {
+                        cell_y = cell_y.wrapping_sub(1);
+                    }
+                    if cell_x > self.size.w - 2 || cell_y > self.size.h - 2 {
+                        return None;
+                    }
+                    t_enter = t_exit;
+                }
+            }
+            // Intersects the ray with the bilinear patch of a cell, where `o` and `d` are the ray's
+            // origin and direction in the cell's local (grid) coordinates.
+            #[allow(clippy::too_many_arguments)]
+            fn cell_intersection(&self, ray: Ray<T>, o: Vec2<T>, d: Vec2<T>, x: usize, y: usize, t_enter: T, t_exit: T) -> Option<T> {
+                let h00 = self.height_at(x, y);
+                let h10 = self.height_at(x + 1, y);
+                let h01 = self.height_at(x, y + 1);
+                let h11 = self.height_at(x + 1, y + 1);
+                // H(u, v) = a + b*u + c*v + e*u*v
+                let (a, b, c, e) = (h00, h10 - h00, h01 - h00, h00 - h10 - h01 + h11);
+                // f(t) = ray.y(t) - H(u(t), v(t)) = qa*t^2 + qb*t + qc
+                let qa = -e * d.x * d.y;
+                let qb = ray.direction.y - (b * d.x + c * d.y + e * (o.x * d.y + o.y * d.x));
+                let qc = ray.origin.y - (a + b * o.x + c * o.y + e * o.x * o.y);
+
+                let eps = T::epsilon() * (T::one() + t_exit.abs());
+                let is_in_range = |t: T| t >= t_enter - eps && t <= t_exit + eps;
+                let two = T::one() + T::one();
+                if qa.abs() <= T::epsilon() * (qb.abs() + qc.abs()) {
+                    if qb == T::zero() {
+                        return if qc == T::zero() { Some(t_enter) } else { None };
+                    }
+                    let t = -qc / qb;
+                    return if is_in_range(t) { Some(t.max(t_enter)) } else { None };
+                }
+                let discriminant = qb * qb - two * two * qa * qc;
+                if discriminant < T::zero() {
+                    return None;
+                }
+                let sqrt_d = discriminant.sqrt();
+                let t0 = (-qb - sqrt_d) / (two * qa);
+                let t1 = (-qb + sqrt_d) / (two * qa);
+                let (t0, t1) = (t0.min(t1), t0.max(t1));
+                if is_in_range(t0) {
+                    Some(t0.max(t_enter))
+                } else if is_in_range(t1) {
+                    Some(t1.max(t_enter))
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+
+#[cfg(all(nightly, feature="repr_simd"))]
+pub mod repr_simd {
+    //! Basic geometric primitives that use `#[repr(simd)]` vectors.
+    use super::*;
     geom_complete_mod!(repr_simd);
 }
 
@@ -815,4 +3199,1642 @@ mod tests {
         let segment = LineSegment3 { start: Vec3::new(-5_f32, 0., 5.), end: Vec3::new(5., 0., -5.) };
         assert_relative_eq!(2.0f32.sqrt(), segment.distance_to_point(Vec3::new(-1., 0., -1.)));
     }
+    #[test] fn expanded_by_displacement() {
+        let aabb = Aabb { min: Vec3::new(-1_i32, -2, -3), max: Vec3::new(1, 2, 3) };
+        assert_eq!(aabb.expanded_by_displacement(Vec3::zero()), aabb);
+        for &x in &[-5, 0, 5] {
+            for &y in &[-5, 0, 5] {
+                for &z in &[-5, 0, 5] {
+                    let d = Vec3::new(x, y, z);
+                    let translated = Aabb { min: aabb.min + d, max: aabb.max + d };
+                    assert_eq!(aabb.expanded_by_displacement(d), aabb.union(translated));
+                }
+            }
+        }
+        let aabr = Aabr { min: Vec2::new(0_f32, 0.), max: Vec2::new(1., 1.) };
+        let expected = Aabr { min: Vec2::new(-2., 0.), max: Vec2::new(1., 4.) };
+        assert_eq!(aabr.expanded_by_displacement(Vec2::new(-2., 3.)), expected);
+    }
+    #[test] fn expanded_by_radius() {
+        let aabr = Aabr { min: Vec2::new(0_f32, 1.), max: Vec2::new(2., 3.) };
+        let expected = Aabr { min: Vec2::new(-0.5, 0.5), max: Vec2::new(2.5, 3.5) };
+        assert_eq!(aabr.expanded_by_radius(0.5), expected);
+        let aabb = Aabb { min: Vec3::new(0_f32, 1., 2.), max: Vec3::new(2., 3., 4.) };
+        assert_eq!(aabb.expanded_by_radius(0.), aabb);
+    }
+
+    #[cfg(feature="rand")]
+    #[test] fn random_point_inside() {
+        let mut rng = crate::vtest::XorShiftRng(42);
+        let aabb = Aabb { min: Vec3::new(-1_f32, 0., 2.), max: Vec3::new(1., 4., 3.) };
+        let mut buckets = [0_u32; 4];
+        for _ in 0..8000 {
+            let p = aabb.random_point_inside(&mut rng);
+            assert!(aabb.contains_point(p));
+            buckets[((p.y) as usize).min(3)] += 1;
+        }
+        for b in buckets.iter() {
+            assert!((*b as i32 - 2000).abs() < 200, "bucket count: {}", b);
+        }
+        let aabr = Aabr { min: Vec2::new(0_i32, 5), max: Vec2::new(2, 5) };
+        for _ in 0..100 {
+            assert!(aabr.contains_point(aabr.random_point_inside(&mut rng)));
+        }
+    }
+    #[test] fn scale_relative_epsilons() {
+        use crate::ops::relative_epsilon_for;
+        for &scale in [1e-3_f32, 1., 1e6].iter() {
+            let eps = relative_epsilon_for(Some(scale), 16.);
+            // A segment which is short, but long relative to the scene.
+            let seg = LineSegment2 { start: Vec2::new(0., 0.), end: Vec2::new(scale, 0.) } ;
+            let p = Vec2::new(scale * 0.5, scale);
+            assert_relative_eq!(seg.distance_to_point_with_epsilon(p, eps), scale, max_relative = 1e-5);
+            // Triangles are hit regardless of their size.
+            let tri = [Vec3::new(0., 0., 0.), Vec3::new(scale, 0., 0.), Vec3::new(0., scale, 0.)];
+            let ray = Ray::new(Vec3::new(scale / 4., scale / 4., scale), -Vec3::unit_z());
+            let eps = relative_epsilon_for(Some(scale * scale), 16.);
+            assert_relative_eq!(ray.triangle_intersection_with_epsilon(tri, eps).unwrap(), scale, max_relative = 1e-5);
+        }
+        // The fixed epsilons fail at small scales.
+        let seg = LineSegment2 { start: Vec2::new(0_f32, 0.), end: Vec2::new(1e-4, 0.) } ;
+        // The segment is mistaken for a point, even though `end` is on it.
+        assert_relative_eq!(seg.distance_to_point(seg.end), 1e-4);
+        assert_relative_eq!(seg.distance_to_point_with_epsilon(seg.end, relative_epsilon_for(Some(1e-4), 16.)), 0.);
+        let tri = [Vec3::new(0_f32, 0., 0.), Vec3::new(1e-4, 0., 0.), Vec3::new(0., 1e-4, 0.)];
+        let ray = Ray::new(Vec3::new(2.5e-5, 2.5e-5, 1e-3), -Vec3::unit_z());
+        assert_eq!(ray.triangle_intersection(tri), None);
+    }
+    #[test] fn line2_and_plane() {
+        let line = Line2::from_points(Vec2::new(0_f64, 1.), Vec2::new(2., 3.));
+        assert_relative_eq!(line.normal, Vec2::new(-1., 1.).normalized());
+        assert_relative_eq!(line.signed_distance_to_point(Vec2::new(0., 1.)), 0.);
+        assert_relative_eq!(line.signed_distance_to_point(Vec2::new(0., 3.)), 2_f64.sqrt(), epsilon = 1e-12);
+        assert_relative_eq!(line.projected_point(Vec2::new(0., 3.)), Vec2::new(1., 2.), epsilon = 1e-12);
+        assert_relative_eq!(line.reflected_point(Vec2::new(0., 3.)), Vec2::new(2., 1.), epsilon = 1e-12);
+        assert_relative_eq!(line.reflected_direction(Vec2::unit_y()), Vec2::unit_x(), epsilon = 1e-12);
+
+        let plane = Plane::from_points(Vec3::new(0_f64, 0., 2.), Vec3::new(1., 0., 2.), Vec3::new(0., 1., 2.));
+        assert_relative_eq!(plane.normal, Vec3::unit_z());
+        assert_relative_eq!(plane.distance, 2.);
+        let p = Vec3::new(3., -4., -1.);
+        assert_relative_eq!(plane.signed_distance_to_point(p), -3.);
+        assert_relative_eq!(plane.reflected_point(p), Vec3::new(3., -4., 5.));
+        assert_relative_eq!(plane.reflected_point(plane.reflected_point(p)), p);
+        assert_relative_eq!(plane.reflected_direction(p), Vec3::new(3., -4., 1.));
+    }
+    #[test] fn plane_intersection_of_three() {
+        let (x, y, z) = (Plane::new(Vec3::unit_x(), 0_f64), Plane::new(Vec3::unit_y(), 0.), Plane::new(Vec3::unit_z(), 0.));
+        assert_eq!(Plane::intersection_of_three(x, y, z), Some(Vec3::zero()));
+        assert_eq!(Plane::intersection_of_three(z, x.flipped(), y), Some(Vec3::zero()));
+        // Oblique planes, through a known point.
+        let p = Vec3::new(3., -4., 5.);
+        let planes = [Vec3::new(1., 2., 3.), Vec3::new(-2., 1., 0.5), Vec3::new(0.3, -1., 2.)].map(|n| Plane::from_point_and_normal(p, n));
+        assert_relative_eq!(Plane::intersection_of_three(planes[0], planes[1], planes[2]).unwrap(), p, epsilon = 1e-12);
+        // Parallel planes, and planes sharing a line.
+        assert_eq!(Plane::intersection_of_three(x, y, Plane::new(Vec3::unit_x(), 1.)), None);
+        let diagonal = Plane::from_point_and_normal(Vec3::zero(), Vec3::new(1., 1., 0.));
+        assert_eq!(Plane::intersection_of_three(x, y, diagonal), None);
+        // Nearly parallel: the exact intersection would be about 1e10 away.
+        let tilted = Plane::from_point_and_normal(Vec3::unit_z(), Vec3::new(1e-10, 0., 1.));
+        assert_eq!(Plane::intersection_of_three(y, z, tilted), None);
+        assert!(Plane::intersection_of_three_with_epsilon(y, z, tilted, 0.).unwrap().x.abs() > 1e9);
+        // Single precision gives up earlier.
+        let tilted = Plane::from_point_and_normal(Vec3::unit_z(), Vec3::new(1e-5_f32, 0., 1.));
+        assert_eq!(Plane::intersection_of_three(Plane::new(Vec3::unit_y(), 0.), Plane::new(Vec3::unit_z(), 0.), tilted), None);
+    }
+    #[test] fn aabb_face_planes() {
+        let aabb = Aabb { min: Vec3::new(-1_f64, 2., -3.), max: Vec3::new(4., 5., 6.) };
+        let planes = aabb.face_planes();
+        let corners = [0, 1, 2, 3, 4, 5, 6, 7].map(|i: u8| Vec3::new(
+            if i & 1 == 0 { aabb.min.x } else { aabb.max.x },
+            if i & 2 == 0 { aabb.min.y } else { aabb.max.y },
+            if i & 4 == 0 { aabb.min.z } else { aabb.max.z },
+        ));
+        for (axis, pair) in planes.chunks(2).enumerate() {
+            let (min_face, max_face) = (pair[0], pair[1]);
+            assert_eq!(min_face.normal[axis], -1.);
+            assert_eq!(max_face.normal[axis], 1.);
+            for corner in corners.iter() {
+                // Every corner is on one face of each pair, and inside the other one.
+                let (a, b) = (min_face.signed_distance_to_point(*corner), max_face.signed_distance_to_point(*corner));
+                assert!(a == 0. && b < 0. || b == 0. && a < 0.);
+            }
+        }
+        assert!(planes.iter().all(|plane| plane.signed_distance_to_point(aabb.center()) < 0.));
+        assert_eq!(planes[3].flipped().signed_distance_to_point(Vec3::new(0., 7., 0.)), -2.);
+        // The corners can be recovered from the planes alone, like those of a frustum.
+        for corner in corners.iter() {
+            let pick = |axis: usize| planes[2 * axis + (corner[axis] == aabb.max[axis]) as usize];
+            assert_eq!(Plane::intersection_of_three(pick(0), pick(1), pick(2)), Some(*corner));
+        }
+    }
+    mod rasterization {
+        use super::*;
+
+        const N: usize = 24;
+
+        fn rasterize(triangles: &[Triangle2<f32>], clip: Option<Aabr<i32>>) -> [[u8; N]; N] {
+            let mut counts = [[0; N]; N];
+            for tri in triangles {
+                let pixels = match clip {
+                    Some(clip) => tri.covered_pixels_iter_clipped(clip),
+                    None => tri.covered_pixels_iter(),
+                };
+                for (p, _) in pixels {
+                    counts[p.y as usize][p.x as usize] += 1;
+                }
+            }
+            counts
+        }
+        // Is the point strictly inside the convex, counter-clockwise polygon ?
+        fn is_strictly_inside(polygon: &[Vec2<f32>], p: Vec2<f32>) -> bool {
+            (0..polygon.len()).all(|i| {
+                let (a, b) = (polygon[i], polygon[(i + 1) % polygon.len()]);
+                Triangle2::new(a, b, p).signed_double_area() > 0.
+            })
+        }
+        fn assert_covered_once(polygon: &[Vec2<f32>], counts: &[[u8; N]; N]) {
+            for (y, row) in counts.iter().enumerate() {
+                for (x, &count) in row.iter().enumerate() {
+                    assert!(count <= 1, "pixel ({}, {}) covered {} times", x, y, count);
+                    let center = Vec2::new(x as f32 + 0.5, y as f32 + 0.5);
+                    if is_strictly_inside(polygon, center) {
+                        assert_eq!(count, 1, "pixel ({}, {}) is not covered", x, y);
+                    }
+                }
+            }
+        }
+
+        #[test] fn split_quad_has_no_gaps_nor_overlaps() {
+            // Vertices lie exactly on pixel centers, so many centers lie exactly on edges.
+            let quads = [
+                [Vec2::new(0.5, 0.5), Vec2::new(10.5, 2.5), Vec2::new(12.5, 9.5), Vec2::new(1.5, 11.5)],
+                [Vec2::new(2., 2.), Vec2::new(20., 2.), Vec2::new(20., 20.), Vec2::new(2., 20.)],
+                [Vec2::new(3.25, 1.7), Vec2::new(22.9, 6.1), Vec2::new(17.3, 23.), Vec2::new(0.6, 13.37)],
+            ];
+            for q in quads.iter() {
+                // Split along both diagonals, and with both windings.
+                let splits = [
+                    [Triangle2::new(q[0], q[1], q[2]), Triangle2::new(q[0], q[2], q[3])],
+                    [Triangle2::new(q[1], q[2], q[3]), Triangle2::new(q[1], q[3], q[0])],
+                    [Triangle2::new(q[0], q[2], q[1]), Triangle2::new(q[2], q[0], q[3])],
+                ];
+                for split in splits.iter() {
+                    assert_covered_once(q, &rasterize(split, None));
+                }
+            }
+            // An axis-aligned square covers exactly its area.
+            let counts = rasterize(&[Triangle2::new(quads[1][0], quads[1][1], quads[1][2]), Triangle2::new(quads[1][0], quads[1][2], quads[1][3])], None);
+            assert_eq!(counts.iter().flatten().map(|&c| c as usize).sum::<usize>(), 18 * 18);
+        }
+        #[test] fn fan_has_no_gaps_nor_overlaps() {
+            // The center and outer vertices lie on pixel centers.
+            let center = Vec2::new(11.5, 11.5);
+            let outer = [
+                Vec2::new(20.5, 11.5), Vec2::new(17.5, 17.5), Vec2::new(11.5, 21.5), Vec2::new(3.5, 18.5),
+                Vec2::new(1.5, 11.5), Vec2::new(5.5, 3.5), Vec2::new(11.5, 0.5), Vec2::new(19.5, 4.5),
+            ];
+            let mut fan = [Triangle2::default(); 8];
+            for i in 0..8 {
+                fan[i] = Triangle2::new(center, outer[i], outer[(i + 1) % 8]);
+            }
+            let counts = rasterize(&fan, None);
+            assert_covered_once(&outer, &counts);
+            assert_eq!(counts[11][11], 1);
+        }
+        #[test] fn clipped_tiles_cover_each_pixel_once() {
+            let tri = Triangle2::new(Vec2::new(0.5, 23.), Vec2::new(3.5, 0.5), Vec2::new(23.5, 12.5));
+            let whole = rasterize(&[tri], None);
+            let mut tiled = [[0; N]; N];
+            for ty in 0..3 {
+                for tx in 0..3 {
+                    let clip = Aabr { min: Vec2::new(tx * 8, ty * 8), max: Vec2::new(tx * 8 + 8, ty * 8 + 8) };
+                    for (p, _) in tri.covered_pixels_iter_clipped(clip) {
+                        assert!(clip.contains_point(p) && p.x < clip.max.x && p.y < clip.max.y);
+                        tiled[p.y as usize][p.x as usize] += 1;
+                    }
+                }
+            }
+            assert_eq!(tiled, whole);
+            let empty = Aabr { min: Vec2::new(100, 100), max: Vec2::new(200, 200) };
+            assert_eq!(tri.covered_pixels_iter_clipped(empty).count(), 0);
+        }
+        #[test] fn barycentric_weights() {
+            let tri = Triangle2::new(Vec2::new(1.5_f32, 2.), Vec2::new(21., 4.25), Vec2::new(6., 19.5));
+            let mut count = 0;
+            for (p, w) in tri.covered_pixels_iter() {
+                let center = Vec2::new(p.x as f32 + 0.5, p.y as f32 + 0.5);
+                assert_relative_eq!(w.sum(), 1., epsilon = 1e-5);
+                assert!(w.x >= 0. && w.y >= 0. && w.z >= 0.);
+                assert_relative_eq!(tri.a * w.x + tri.b * w.y + tri.c * w.z, center, epsilon = 1e-4);
+                count += 1;
+            }
+            assert!(count > 0);
+        }
+        #[test] fn degenerate_triangles_cover_nothing() {
+            let tri = Triangle2::new(Vec2::new(0.5_f32, 0.5), Vec2::new(5.5, 5.5), Vec2::new(10.5, 10.5));
+            assert_eq!(tri.covered_pixels_iter().count(), 0);
+            let tri = Triangle2::new(Vec2::broadcast(3.5_f32), Vec2::broadcast(3.5), Vec2::broadcast(3.5));
+            assert_eq!(tri.covered_pixels_iter().count(), 0);
+        }
+    }
+    #[cfg(feature="std")]
+    mod conservative_tiles {
+        use super::*;
+        use crate::vec::Extent2;
+        use alloc::vec::Vec;
+
+        fn triangles() -> [Triangle2<f64>; 5] {
+            [
+                Triangle2::new(Vec2::new(1., 1.), Vec2::new(30., 2.), Vec2::new(2., 20.)),
+                // Clockwise.
+                Triangle2::new(Vec2::new(-7.5, 3.), Vec2::new(12., 40.), Vec2::new(33., -9.25)),
+                // Thin, and crossing tile corners.
+                Triangle2::new(Vec2::new(0., 0.), Vec2::new(64., 32.), Vec2::new(64., 32.5)),
+                // Exactly on tile boundaries.
+                Triangle2::new(Vec2::new(8., 8.), Vec2::new(24., 8.), Vec2::new(8., 24.)),
+                // Within a single tile.
+                Triangle2::new(Vec2::new(3., 3.), Vec2::new(4., 3.), Vec2::new(3., 4.)),
+            ]
+        }
+        // Points of the triangle, on a dense grid of barycentric coordinates.
+        fn samples(tri: Triangle2<f64>, n: usize) -> impl Iterator<Item=Vec2<f64>> {
+            (0..=n).flat_map(move |i| (0..=n - i).map(move |j| {
+                let (u, v) = (i as f64 / n as f64, j as f64 / n as f64);
+                tri.a + (tri.b - tri.a) * u + (tri.c - tri.a) * v
+            }))
+        }
+        fn contains(tri: Triangle2<f64>, p: Vec2<f64>) -> bool {
+            let sign = tri.signed_double_area().signum();
+            [(tri.a, tri.b), (tri.b, tri.c), (tri.c, tri.a)].iter()
+                .all(|&(from, to)| Triangle2::new(from, to, p).signed_double_area() * sign >= 0.)
+        }
+
+        #[test] fn superset_of_sampled_tiles() {
+            let size = Extent2::new(8., 4.);
+            for &tri in triangles().iter() {
+                let tiles: Vec<_> = tri.covered_tiles_conservative(size).collect();
+                for p in samples(tri, 200) {
+                    let tile = (p / Vec2::new(8., 4.)).map(|e| e.floor() as i32);
+                    assert!(tiles.contains(&tile), "{:?} {:?}", tri, tile);
+                }
+                // Row by row, without duplicates.
+                for pair in tiles.windows(2) {
+                    assert!((pair[0].y, pair[0].x) < (pair[1].y, pair[1].x));
+                }
+                // It also contains the pixels of exact rasterization, with unit tiles.
+                let pixels: Vec<_> = tri.covered_tiles_conservative(Extent2::new(1., 1.)).collect();
+                for (pixel, _) in Triangle2::new(tri.a.as_::<f32>(), tri.b.as_::<f32>(), tri.c.as_::<f32>()).covered_pixels_iter() {
+                    assert!(pixels.contains(&pixel));
+                }
+            }
+        }
+        #[test] fn classification() {
+            for &tri in triangles().iter() {
+                let samples: Vec<_> = samples(tri, 30).collect();
+                for y in -12..45 {
+                    for x in -10..66 {
+                        let aabr = Aabr { min: Vec2::new(x as f64, y as f64), max: Vec2::new(x as f64 + 3., y as f64 + 2.) };
+                        let corners = [aabr.min, Vec2::new(aabr.max.x, aabr.min.y), aabr.max, Vec2::new(aabr.min.x, aabr.max.y)];
+                        let coverage = tri.classify_aabr(aabr);
+                        assert_eq!(coverage == Coverage::FullyInside, corners.iter().all(|&p| contains(tri, p)), "{:?} {:?}", tri, aabr);
+                        if coverage == Coverage::Outside {
+                            assert!(corners.iter().all(|&p| !contains(tri, p)));
+                            assert!(samples.iter().all(|&p| !aabr.contains_point(p)));
+                        }
+                    }
+                }
+            }
+            // Touching is partial.
+            let tri = triangles()[3];
+            assert_eq!(tri.classify_aabr(Aabr { min: Vec2::new(0., 0.), max: Vec2::new(8., 8.) }), Coverage::Partial);
+            assert_eq!(tri.classify_aabr(Aabr { min: Vec2::new(0., 0.), max: Vec2::new(7.9, 8.) }), Coverage::Outside);
+            assert_eq!(tri.classify_aabr(Aabr { min: Vec2::new(8., 8.), max: Vec2::new(12., 12.) }), Coverage::FullyInside);
+        }
+        #[test] fn degenerate_triangles() {
+            let segment = Triangle2::new(Vec2::new(1., 1.), Vec2::new(9., 5.), Vec2::new(17., 9.));
+            let tiles: Vec<_> = segment.covered_tiles_conservative(Extent2::new(4., 4.)).collect();
+            assert_eq!(tiles, [Vec2::new(0, 0), Vec2::new(1, 0), Vec2::new(1, 1), Vec2::new(2, 1), Vec2::new(3, 1), Vec2::new(3, 2), Vec2::new(4, 2)]);
+            let point = Triangle2::new(Vec2::broadcast(4.), Vec2::broadcast(4.), Vec2::broadcast(4.));
+            assert_eq!(point.covered_tiles_conservative(Extent2::new(4., 4.)).collect::<Vec<_>>(), [Vec2::new(1, 1)]);
+            let aabr = Aabr { min: Vec2::new(3., 3.), max: Vec2::new(5., 5.) };
+            assert_eq!(point.classify_aabr(aabr), Coverage::Partial);
+        }
+        #[test]
+        #[should_panic]
+        fn empty_tiles() {
+            triangles()[0].covered_tiles_conservative(Extent2::new(0., 4.)).count();
+        }
+    }
+    mod mtv {
+        use super::*;
+
+        fn aabb(min: (i32, i32, i32), max: (i32, i32, i32)) -> Aabb<i32> {
+            Aabb { min: min.into(), max: max.into() }
+        }
+
+        #[test] fn each_axis_can_be_minimal() {
+            let a = aabb((0, 0, 0), (10, 10, 10));
+            assert_eq!(a.minimum_translation_vector(aabb((8, 2, 3), (20, 9, 9))), Some(Vec3::new(-2, 0, 0)));
+            assert_eq!(a.minimum_translation_vector(aabb((1, -5, 1), (9, 3, 9))), Some(Vec3::new(0, 3, 0)));
+            assert_eq!(a.minimum_translation_vector(aabb((2, 2, 9), (8, 8, 30))), Some(Vec3::new(0, 0, -1)));
+            for &b in &[aabb((8, 2, 3), (20, 9, 9)), aabb((1, -5, 1), (9, 3, 9)), aabb((2, 2, 9), (8, 8, 30))] {
+                let mtv = a.minimum_translation_vector(b).unwrap();
+                let moved = Aabb { min: a.min + mtv, max: a.max + mtv };
+                assert!(!moved.collides_with_aabb(b));
+                assert_eq!(b.minimum_translation_vector(a), Some(-mtv));
+            }
+        }
+        #[test] fn ties_are_deterministic() {
+            let a = aabb((0, 0, 0), (10, 10, 10));
+            // Same penetration (2) along every axis: X wins.
+            assert_eq!(a.minimum_translation_vector(aabb((8, 8, 8), (20, 20, 20))), Some(Vec3::new(-2, 0, 0)));
+            // Y and Z tie: Y wins.
+            assert_eq!(a.minimum_translation_vector(aabb((-5, 8, 8), (20, 20, 20))), Some(Vec3::new(0, -2, 0)));
+            // Centered along X, and shallower along X than along the other axes: positive X wins.
+            assert_eq!(a.minimum_translation_vector(aabb((3, -1, -1), (7, 11, 11))), Some(Vec3::new(7, 0, 0)));
+            let r = Aabr { min: Vec2::new(0., 0.), max: Vec2::new(2., 2.) };
+            assert_eq!(r.minimum_translation_vector(r), Some(Vec2::new(2., 0.)));
+        }
+        #[test] fn touching_boxes_dont_collide() {
+            let a = aabb((0, 0, 0), (10, 10, 10));
+            for &b in &[aabb((10, 0, 0), (12, 10, 10)), aabb((0, -3, 0), (10, 0, 10)), aabb((10, 10, 10), (11, 11, 11))] {
+                assert!(!a.collides_with_aabb(b));
+                assert_eq!(a.minimum_translation_vector(b), None);
+                assert_eq!(a.collides_and_mtv(b), (false, Vec3::zero()));
+            }
+            assert_eq!(a.minimum_translation_vector(aabb((20, 20, 20), (30, 30, 30))), None);
+        }
+        #[test] fn containment_pushes_out_the_nearest_face() {
+            let outer = aabb((0, 0, 0), (10, 10, 10));
+            let inner = aabb((1, 4, 3), (3, 8, 6));
+            // inner's nearest face is at x = 0, 1 away; moving out requires 3 units.
+            assert_eq!(inner.minimum_translation_vector(outer), Some(Vec3::new(-3, 0, 0)));
+            assert_eq!(outer.minimum_translation_vector(inner), Some(Vec3::new(3, 0, 0)));
+            assert_eq!(inner.collides_and_mtv(outer), (true, Vec3::new(-3, 0, 0)));
+
+            let outer = Aabr { min: Vec2::new(0_f32, 0.), max: Vec2::new(10., 10.) };
+            let inner = Aabr { min: Vec2::new(4., 7.), max: Vec2::new(6., 9.) };
+            assert_eq!(inner.minimum_translation_vector(outer), Some(Vec2::new(0., 3.)));
+        }
+    }
+    #[cfg(feature="std")]
+    mod support {
+        use super::*;
+        use alloc::vec::Vec;
+
+        // Points spread evenly over the unit sphere.
+        fn fibonacci_sphere(n: usize) -> Vec<Vec3<f64>> {
+            let golden_angle = core::f64::consts::PI * (3. - 5_f64.sqrt());
+            (0..n).map(|i| {
+                let z = 1. - 2. * (i as f64 + 0.5) / n as f64;
+                let r = (1. - z * z).sqrt();
+                let a = golden_angle * i as f64;
+                Vec3::new(r * a.cos(), r * a.sin(), z)
+            }).collect()
+        }
+        fn directions() -> Vec<Vec3<f64>> {
+            let mut directions: Vec<_> = fibonacci_sphere(100).into_iter().map(|d| d * 3.).collect();
+            directions.extend_from_slice(&[Vec3::unit_x(), Vec3::unit_y(), Vec3::unit_z(), -Vec3::unit_x(), -Vec3::unit_y(), -Vec3::unit_z()]);
+            directions
+        }
+        fn assert_maximizes<S>(shape: &S, samples: &[Vec3<f64>]) where S: SupportMap<Vector=Vec3<f64>> + ?Sized {
+            for d in directions() {
+                let support = shape.support_point(d).dot(d);
+                for &p in samples {
+                    assert!(p.dot(d) <= support + 1e-9, "{} is farther than the support point along {}", p, d);
+                }
+            }
+        }
+
+        #[test] fn sphere() {
+            let sphere = Sphere::new(Vec3::new(1., -2., 3.), 2.5);
+            let samples: Vec<_> = fibonacci_sphere(2000).into_iter().map(|p| sphere.center + p * sphere.radius).collect();
+            assert_maximizes(&sphere, &samples);
+            for d in directions() {
+                assert_relative_eq!(sphere.support_point(d).distance(sphere.center), 2.5, epsilon = 1e-12);
+            }
+            assert_eq!(sphere.support_point(Vec3::zero()), sphere.center);
+            let disk = Disk::new(Vec2::new(1., 1.), 2.);
+            assert_eq!(disk.support_point(Vec2::new(0., -5.)), Vec2::new(1., -1.));
+        }
+        #[test] fn aabb() {
+            let aabb = Aabb { min: Vec3::new(-1., 0., 2.), max: Vec3::new(3., 0.5, 7.) };
+            let grid: Vec<_> = (0..11 * 11 * 11).map(|i| {
+                let t = Vec3::new(i % 11, i / 11 % 11, i / 121).map(|e| e as f64 / 10.);
+                aabb.min + (aabb.max - aabb.min) * t
+            }).collect();
+            assert_maximizes(&aabb, &grid);
+            assert_eq!(aabb.support_point(Vec3::zero()), aabb.max);
+            assert_eq!(aabb.support_point(Vec3::new(-1., 0., 1.)), Vec3::new(-1., 0.5, 7.));
+            let aabr = Aabr { min: Vec2::new(0, 0), max: Vec2::new(4, 2) };
+            assert_eq!(aabr.support_point(Vec2::new(1, -1)), Vec2::new(4, 0));
+        }
+        #[test] fn capsule() {
+            let capsule = Capsule::new(Vec3::new(0., 0., 0.), Vec3::new(4., 2., -1.), 0.75);
+            let sphere = fibonacci_sphere(300);
+            let samples: Vec<_> = (0..=20).flat_map(|i| {
+                let center = capsule.segment.start + (capsule.segment.end - capsule.segment.start) * (i as f64 / 20.);
+                sphere.iter().map(move |&p| center + p * capsule.radius)
+            }).collect();
+            assert_maximizes(&capsule, &samples);
+            for d in directions() {
+                let p = capsule.support_point(d);
+                assert!(capsule.contains_point(p * (1. - 1e-12)) || capsule.contains_point(p));
+                assert_relative_eq!(capsule.segment.distance_to_point(p), 0.75, epsilon = 1e-12);
+            }
+            // Perpendicular to the axis, both caps are equally far: `start` wins.
+            let perpendicular = Vec3::new(0., 0.5, 1.);
+            assert_relative_eq!(capsule.support_point(perpendicular), perpendicular.normalized() * 0.75);
+            assert_eq!(capsule.support_point(Vec3::zero()), Vec3::zero());
+        }
+        #[test] fn point_cloud() {
+            let points = [
+                Vec3::new(1., 2., 3.), Vec3::new(-4., 0.5, 1.), Vec3::new(3., -3., 0.),
+                Vec3::new(0., 5., -2.), Vec3::new(2., 2., 2.), Vec3::new(-1., -1., -6.),
+            ];
+            assert_maximizes(&points[..], &points);
+            for d in directions() {
+                assert!(points.contains(&points[..].support_point(d)));
+            }
+            // Ties and the zero direction pick the first point.
+            assert_eq!(points[..].support_point(Vec3::zero()), points[0]);
+            assert_eq!(points[..].support_point(Vec3::new(1., 0., 0.)), points[2]);
+            assert_eq!([points[4], points[0]][..].support_point(Vec3::new(0., 1., 0.)), points[4]);
+            let segment = LineSegment3 { start: points[0], end: points[4] };
+            assert_eq!(segment.support_point(Vec3::unit_y()), points[0]);
+            assert_eq!(segment.support_point(-Vec3::unit_z()), points[4]);
+        }
+        #[test] fn minkowski_difference() {
+            let aabb = Aabb { min: Vec3::new(-1., -1., -1.), max: Vec3::new(1., 2., 1.) };
+            let points = [Vec3::new(3., 0., 0.), Vec3::new(4., 1., 0.), Vec3::new(3., 0., 2.)];
+            let difference = MinkowskiDifference { a: aabb, b: &points[..] };
+            let corners = (0..8).map(|i| Vec3::new(
+                if i & 1 == 0 { aabb.min.x } else { aabb.max.x },
+                if i & 2 == 0 { aabb.min.y } else { aabb.max.y },
+                if i & 4 == 0 { aabb.min.z } else { aabb.max.z },
+            ));
+            let samples: Vec<_> = corners.flat_map(|a| points.iter().map(move |&b| a - b)).collect();
+            assert_maximizes(&difference, &samples);
+            // Zero direction: `a`'s and `b`'s own conventions apply.
+            assert_eq!(difference.support_point(Vec3::zero()), aabb.max - points[0]);
+            // The boxes are 2 units apart along X.
+            assert_eq!(difference.support_point(Vec3::unit_x()).x, -2.);
+        }
+    }
+    mod ellipsoid {
+        use super::*;
+        use crate::mat::Mat3;
+        use crate::quaternion::Quaternion;
+
+        // Standard normal samples, using the Box-Muller transform over a xorshift generator.
+        fn gaussian(n: usize) -> impl Iterator<Item=Vec3<f64>> {
+            let mut state = 0x9e3779b97f4a7c15_u64;
+            let mut uniform = move || {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                ((state >> 11) as f64 + 0.5) / (1_u64 << 53) as f64
+            };
+            let mut normal = move || {
+                let (u, v) = (uniform(), uniform());
+                (-2. * u.ln()).sqrt() * (2. * ::std::f64::consts::PI * v).cos()
+            };
+            (0..n).map(move |_| Vec3::new(normal(), normal(), normal()))
+        }
+
+        // A distribution with known axes and standard deviations.
+        fn distribution() -> (Vec3<f64>, Quaternion<f64>, Vec3<f64>, Mat3<f64>) {
+            let mean = Vec3::new(10., -3., 2.);
+            let rotation = Quaternion::rotation_3d(0.8, Vec3::new(1., 2., -1.));
+            let std_dev = Vec3::new(0.5, 3., 1.5);
+            let r = Mat3::from(rotation);
+            let covariance = r * Mat3::scaling_3d(std_dev * std_dev) * r.transposed();
+            (mean, rotation, std_dev, covariance)
+        }
+
+        #[test] fn gaussian_samples_fall_inside_at_the_expected_rate() {
+            let (mean, rotation, std_dev, covariance) = distribution();
+            let e = Ellipsoid::from_covariance(mean, covariance, 3.);
+            let n = 20_000;
+            let inside = gaussian(n).filter(|z| e.contains_point(mean + rotation * (*z * std_dev))).count();
+            // The chi-squared distribution with 3 degrees of freedom gives P(X <= 9) = 0.97071.
+            let rate = inside as f64 / n as f64;
+            assert!((rate - 0.97071).abs() < 0.005, "rate: {}", rate);
+            // 1 standard deviation: P(X <= 1) = 0.19875.
+            let e = Ellipsoid::from_covariance(mean, covariance, 1.);
+            let rate = gaussian(n).filter(|z| e.contains_point(mean + rotation * (*z * std_dev))).count() as f64 / n as f64;
+            assert!((rate - 0.19875).abs() < 0.01, "rate: {}", rate);
+        }
+        #[test] fn containment_matches_quadratic_form() {
+            let (mean, rotation, std_dev, covariance) = distribution();
+            let e = Ellipsoid::from_covariance(mean, covariance, 2.);
+            assert_relative_eq!(Vec3::from(e.radius), Vec3::new(6., 3., 1.), epsilon = 1e-9);
+            for z in gaussian(5000) {
+                let p = mean + z * 4.;
+                // (p - mean)ᵀ Σ⁻¹ (p - mean), from the known axes.
+                let q = ((rotation.conjugate() * (p - mean)) / std_dev).magnitude_squared();
+                if (q - 4.).abs() > 1e-9 {
+                    assert_eq!(e.contains_point(p), q <= 4., "{}", p);
+                }
+            }
+        }
+        #[test] fn surface_and_bounds() {
+            let (mean, _, _, covariance) = distribution();
+            let e = Ellipsoid::from_covariance(mean, covariance, 1.5);
+            let aabb = e.bounding_aabb();
+            let mut max = Vec3::broadcast(f64::MIN);
+            for d in gaussian(20_000) {
+                let p = e.surface_point(d);
+                assert_relative_eq!((p - mean).normalized(), d.normalized(), epsilon = 1e-9);
+                assert!(e.contains_point(mean + (p - mean) * (1. - 1e-9)));
+                assert!(!e.contains_point(mean + (p - mean) * (1. + 1e-9)));
+                assert!(aabb.contains_point(p));
+                max = Vec3::partial_max(max, p);
+            }
+            // The box is tight.
+            assert_relative_eq!(max, aabb.max, epsilon = 0.05);
+            assert_eq!(e.surface_point(Vec3::zero()), mean);
+        }
+        #[test] fn degenerate() {
+            // All samples on a line along X.
+            let covariance = Mat3::new(4_f64, 0., 0., 0., 0., 0., 0., 0., 0.);
+            let e = Ellipsoid::from_covariance(Vec3::zero(), covariance, 1.);
+            assert_relative_eq!(Vec3::from(e.radius), Vec3::new(2., 0., 0.));
+            assert!(e.contains_point(Vec3::new(1.5, 0., 0.)));
+            assert!(!e.contains_point(Vec3::new(1.5, 0.1, 0.)));
+            assert_eq!(e.surface_point(Vec3::unit_y()), Vec3::zero());
+            assert_relative_eq!(e.surface_point(-Vec3::unit_x()), Vec3::new(-2., 0., 0.));
+            assert_eq!(Ellipsoid::<f32, f32>::default().orientation, Quaternion::identity());
+        }
+    }
+    mod overflow {
+        use super::*;
+        use crate::vec::{Extent2, Extent3};
+
+        #[test] fn extent_area_boundaries() {
+            assert_eq!(Extent2::new(65535_u32, 65537).checked_area(), Some(u32::MAX));
+            assert_eq!(Extent2::new(65536_u32, 65536).checked_area(), None);
+            assert_eq!(Extent2::new(65536_u32, 65536).area_u64(), 1 << 32);
+            assert_eq!(Extent2::new(u16::MAX, u16::MAX).area_u64(), 0xfffe_0001);
+            assert_eq!(Extent2::new(0_u32, u32::MAX).checked_area(), Some(0));
+
+            assert_eq!(Extent3::new(1024_u32, 2048, 2047).checked_volume(), Some(1024 * 2048 * 2047));
+            assert_eq!(Extent3::new(1024_u32, 2048, 2048).checked_volume(), None);
+            assert_eq!(Extent3::new(1024_u32, 2048, 2048).volume_u128(), 1 << 32);
+            assert_eq!(Extent3::new(u32::MAX, u32::MAX, u32::MAX).volume_u128(), (u32::MAX as u128).pow(3));
+        }
+        #[test] fn rect_area_boundaries() {
+            let r = Rect::new(-5_i32, 7, 65536_u32, 65535);
+            assert_eq!(r.checked_area(), Some(65536 * 65535));
+            assert_eq!(Rect { h: 65536, ..r }.checked_area(), None);
+            assert_eq!(Rect { h: 65536, ..r }.area_u64(), 1 << 32);
+
+            let r = Rect3::new(0_u8, 0, 0, 1024_u32, 2048, 2048);
+            assert_eq!(r.checked_volume(), None);
+            assert_eq!(r.volume_u128(), 1 << 32);
+            assert_eq!(Rect3 { d: 2047, ..r }.checked_volume(), Some(1024 * 2048 * 2047));
+        }
+        #[test] fn aabr_area_boundaries() {
+            let aabr = Aabr { min: Vec2::new(0_u32, 0), max: Vec2::new(65536, 65535) };
+            assert_eq!(aabr.checked_area(), Some(65536 * 65535));
+            assert_eq!(aabr.area_u64(), 65536 * 65535);
+            let aabr = Aabr { min: Vec2::new(0_u32, 0), max: Vec2::new(65536, 65536) };
+            assert_eq!(aabr.checked_area(), None);
+            assert_eq!(aabr.area_u64(), 1 << 32);
+
+            // Invalid unsigned shapes can't be sized, and count as empty.
+            let invalid = Aabr { min: Vec2::new(3_u32, 0), max: Vec2::new(1, 10) };
+            assert_eq!(invalid.checked_size(), None);
+            assert_eq!(invalid.checked_area(), None);
+            assert_eq!(invalid.area_u64(), 0);
+
+            // The width of this one doesn't even fit in an i32.
+            let wide = Aabr { min: Vec2::new(i32::MIN, 0), max: Vec2::new(i32::MAX, 1) };
+            assert_eq!(wide.checked_size(), None);
+            assert_eq!(wide.area_u64(), u32::MAX as u64);
+            let aabr = Aabr { min: Vec2::new(-1_i32, -1), max: Vec2::new(i32::MAX - 1, 0) };
+            assert_eq!(aabr.checked_size(), Some(Extent2::new(i32::MAX, 1)));
+            assert_eq!(aabr.checked_area(), Some(i32::MAX));
+        }
+        #[test] fn aabb_volume_boundaries() {
+            let aabb = Aabb { min: Vec3::new(0_u32, 0, 0), max: Vec3::new(1024, 2048, 2047) };
+            assert_eq!(aabb.checked_volume(), Some(1024 * 2048 * 2047));
+            assert_eq!(aabb.volume_u128(), 1024 * 2048 * 2047);
+            let aabb = Aabb { max: Vec3::new(1024, 2048, 2048), ..aabb };
+            assert_eq!(aabb.checked_volume(), None);
+            assert_eq!(aabb.volume_u128(), 1 << 32);
+
+            let aabb = Aabb { min: Vec3::broadcast(i32::MIN), max: Vec3::broadcast(i32::MAX) };
+            assert_eq!(aabb.checked_volume(), None);
+            assert_eq!(aabb.volume_u128(), (u32::MAX as u128).pow(3));
+            let invalid = Aabb { min: Vec3::new(0_i32, 0, 0), max: Vec3::new(-5, 3, 3) };
+            assert_eq!(invalid.volume_u128(), 0);
+            assert_eq!(invalid.made_valid().volume_u128(), 45);
+        }
+    }
+    mod signed_distance {
+        use super::*;
+        use crate::ops::gradient;
+
+        fn cube() -> Aabb<f64> {
+            Aabb { min: Vec3::new(-1., -2., -3.), max: Vec3::new(3., 2., 1.) }
+        }
+
+        #[test] fn aabb_boundary() {
+            let b = cube();
+            let c = b.center();
+            // Face centers, edge midpoints and corners are all on the boundary.
+            for &x in &[-1., 0., 1.] {
+                for &y in &[-1., 0., 1.] {
+                    for &z in &[-1., 0., 1.] {
+                        let dir = Vec3::new(x, y, z);
+                        if dir == Vec3::zero() {
+                            continue;
+                        }
+                        let on_boundary = c + Vec3::new(2., 2., 2.) * dir;
+                        assert_relative_eq!(b.signed_distance(on_boundary), 0.);
+                        // Moving outwards by 1 along each involved axis.
+                        let outside = on_boundary + dir;
+                        assert_relative_eq!(b.signed_distance(outside), dir.magnitude());
+                        assert_relative_eq!(b.gradient_at(outside), dir.normalized());
+                    }
+                }
+            }
+        }
+        #[test] fn aabb_interior() {
+            let b = cube();
+            assert_relative_eq!(b.signed_distance(b.center()), -2.);
+            // The closest face wins, even if the box is not a cube.
+            let stretched = Aabb { min: Vec3::new(0., 0., 0.), max: Vec3::new(10., 2., 4.) };
+            assert_relative_eq!(stretched.signed_distance(Vec3::new(5., 1.5, 2.)), -0.5);
+            assert_relative_eq!(stretched.gradient_at(Vec3::new(5., 1.5, 2.)), Vec3::unit_y());
+            assert_relative_eq!(stretched.signed_distance(Vec3::new(0.25, 1., 2.)), -0.25);
+            assert_relative_eq!(stretched.gradient_at(Vec3::new(0.25, 1., 2.)), -Vec3::unit_x());
+            // Ties go to the first axis.
+            assert_relative_eq!(b.gradient_at(b.center()), Vec3::unit_x());
+        }
+        #[test] fn aabb_gradient_matches_numerical_gradient() {
+            let b = cube();
+            let points = [
+                Vec3::new(5., 0.3, -0.2), Vec3::new(4., 3., 2.5), Vec3::new(-3., -4., -5.),
+                Vec3::new(0.2, 0.1, 0.7), Vec3::new(2.5, -1.8, 0.), Vec3::new(1., 1.5, -2.9),
+            ];
+            for p in points.iter().cloned() {
+                let numerical = gradient(|q| b.signed_distance(q), p, 1e-6);
+                assert_relative_eq!(b.gradient_at(p), numerical, epsilon = 1e-6);
+                // Exact distance: moving along the gradient changes the distance by the same amount.
+                assert_relative_eq!(b.signed_distance(p + b.gradient_at(p) * 0.01), b.signed_distance(p) + 0.01, epsilon = 1e-9);
+            }
+        }
+        #[test] fn aabr() {
+            let r = Aabr { min: Vec2::new(0_f32, 0.), max: Vec2::new(4., 2.) };
+            assert_relative_eq!(r.signed_distance(Vec2::new(2., 2.)), 0.);
+            assert_relative_eq!(r.signed_distance(Vec2::new(7., -4.)), 5.);
+            assert_relative_eq!(r.signed_distance(Vec2::new(2., 1.)), -1.);
+            assert_relative_eq!(r.signed_distance(Vec2::new(3.5, 1.)), -0.5);
+            assert_relative_eq!(r.gradient_at(Vec2::new(3.5, 1.)), Vec2::unit_x());
+            assert_relative_eq!(r.gradient_at(Vec2::new(-1., 1.)), -Vec2::unit_x());
+        }
+        #[test] fn disk_and_sphere() {
+            let sphere = Sphere::new(Vec3::new(1_f64, 2., 3.), 2.);
+            assert_relative_eq!(sphere.signed_distance(sphere.center), -2.);
+            assert_relative_eq!(sphere.signed_distance(Vec3::new(1., 2., 5.)), 0.);
+            assert_relative_eq!(sphere.signed_distance(Vec3::new(4., 6., 3.)), 3.);
+            assert_relative_eq!(sphere.gradient_at(Vec3::new(4., 6., 3.)), Vec3::new(0.6, 0.8, 0.));
+            assert_eq!(sphere.gradient_at(sphere.center), Vec3::zero());
+            let disk = Disk::new(Vec2::new(0_f32, 0.), 1.);
+            assert_relative_eq!(disk.signed_distance(Vec2::new(0., -0.25)), -0.75);
+            assert_relative_eq!(disk.gradient_at(Vec2::new(0., -0.25)), -Vec2::unit_y());
+        }
+    }
+    mod subtracted {
+        use super::*;
+
+        fn area(r: Aabr<i32>) -> i32 { r.size().product() }
+        fn parts<T: Copy + PartialOrd>(a: Aabr<T>, b: Aabr<T>) -> [Option<Aabr<T>>; 4] {
+            let mut it = a.subtracted(b);
+            let parts = [it.next(), it.next(), it.next(), it.next()];
+            assert!(it.next().is_none());
+            parts
+        }
+
+        // Checks every cell of `a`'s neighbourhood against the half-open convention.
+        fn check(a: Aabr<i32>, b: Aabr<i32>) {
+            let parts = parts(a, b);
+            let inside = |r: Aabr<i32>, x, y| r.min.x <= x && x < r.max.x && r.min.y <= y && y < r.max.y;
+            let mut parts_area = 0;
+            for p in parts.iter().flatten() {
+                assert!(p.min.x < p.max.x && p.min.y < p.max.y, "{:?} - {:?} gave empty {:?}", a, b, p);
+                assert!(a.contains_aabr(*p));
+                parts_area += area(*p);
+            }
+            let inter_area = if a.collides_with_aabr(b) { area(a.intersection(b)) } else { 0 };
+            assert_eq!(parts_area + inter_area, area(a), "{:?} - {:?}", a, b);
+            for y in a.min.y - 1 .. a.max.y + 1 {
+                for x in a.min.x - 1 .. a.max.x + 1 {
+                    let n = parts.iter().flatten().filter(|p| inside(**p, x, y)).count();
+                    let expected = inside(a, x, y) && !inside(b, x, y);
+                    assert_eq!(n, expected as usize, "{:?} - {:?} at ({}, {})", a, b, x, y);
+                }
+            }
+        }
+
+        #[test] fn all_configurations() {
+            // Exhaustively covers no overlap, edge contact, corner overlap, edge strips,
+            // full containment both ways, and exact equality.
+            let a = Aabr { min: Vec2::new(0, 0), max: Vec2::new(4, 3) };
+            for x0 in -1..6 { for x1 in x0+1..6 {
+                for y0 in -1..5 { for y1 in y0+1..5 {
+                    check(a, Aabr { min: Vec2::new(x0, y0), max: Vec2::new(x1, y1) });
+                }}
+            }}
+        }
+        #[test] fn special_cases() {
+            let a = Aabr { min: Vec2::new(0, 0), max: Vec2::new(4, 4) };
+            assert_eq!(a.subtracted(a).count(), 0);
+            let far = Aabr { min: Vec2::new(10, 10), max: Vec2::new(12, 12) };
+            assert_eq!(parts(a, far), [Some(a), None, None, None]);
+            // Touching edges don't overlap.
+            let touching = Aabr { min: Vec2::new(4, 0), max: Vec2::new(6, 4) };
+            assert_eq!(parts(a, touching), [Some(a), None, None, None]);
+            // Subtracting from an empty rectangle gives nothing.
+            let empty = Aabr { min: Vec2::new(1, 1), max: Vec2::new(1, 3) };
+            assert_eq!(empty.subtracted(far).count(), 0);
+            // A hole in the middle gives 4 parts.
+            let hole = Aabr { min: Vec2::new(1, 1), max: Vec2::new(3, 3) };
+            assert_eq!(a.subtracted(hole).count(), 4);
+        }
+        #[test] fn floats_and_rects() {
+            let a = Aabr { min: Vec2::new(-1_f64, -1.), max: Vec2::new(1., 2.) };
+            let b = Aabr { min: Vec2::new(0.5, -3.), max: Vec2::new(4., 0.25) };
+            let area = |r: Aabr<f64>| r.size().product();
+            let total: f64 = a.subtracted(b).map(area).sum();
+            assert_relative_eq!(total + area(a.intersection(b)), area(a));
+            let parts = parts(a, b);
+            for (i, p) in parts.iter().flatten().enumerate() {
+                for q in parts.iter().flatten().skip(i + 1) {
+                    assert!(!p.collides_with_aabr(*q));
+                }
+            }
+            assert!(a.into_rect().subtracted(b.into_rect()).eq(parts.iter().flatten().map(|p| p.into_rect())));
+        }
+    }
+    mod projection {
+        use super::*;
+        use crate::mat::Mat4;
+        use crate::vec::{Vec4, Extent2};
+
+        fn to_pixels(view_proj: Mat4<f64>, p: Vec3<f64>, viewport: Extent2<u32>) -> Vec2<f64> {
+            let clip = view_proj * Vec4::from_point(p);
+            (Vec2::new(clip.x, clip.y) / clip.w + 1.) / 2. * Vec2::new(viewport.w as f64, viewport.h as f64)
+        }
+
+        #[test]
+        fn sphere_projected_radius_matches_sampling() {
+            let viewport = Extent2::new(800, 600);
+            let fov = 60_f64.to_radians();
+            let eye = Vec3::new(1., 2., 3.);
+            let sphere = Sphere::new(Vec3::new(-2., 1., -7.), 2_f64);
+            let view = Mat4::look_at_rh(eye, sphere.center, Vec3::unit_y());
+            let view_proj = Mat4::perspective_rh_no(fov, 800. / 600., 0.1, 100.) * view;
+            let center = to_pixels(view_proj, sphere.center, viewport);
+            // Fibonacci sphere sampling of the surface.
+            let n = 20_000;
+            let golden_angle = core::f64::consts::PI * (3. - 5_f64.sqrt());
+            let max = (0..n).map(|i| {
+                let z = 1. - 2. * (i as f64 + 0.5) / n as f64;
+                let r = (1. - z * z).sqrt();
+                let a = golden_angle * i as f64;
+                let p = sphere.center + Vec3::new(r * a.cos(), r * a.sin(), z) * sphere.radius;
+                (to_pixels(view_proj, p, viewport) - center).magnitude()
+            }).fold(0., f64::max);
+            let expected = sphere.projected_radius_pixels(eye, fov, viewport);
+            assert_relative_eq!(max, expected, max_relative = 1e-3);
+            assert_eq!(sphere.projected_radius_pixels(sphere.center, fov, viewport), f64::MAX);
+        }
+
+        fn sampled_aabr(aabb: Aabb<f64>, view_proj: Mat4<f64>, viewport: Extent2<u32>, near_plane_z: f64) -> Aabr<f64> {
+            let steps = 64;
+            let lerp = |a: f64, b: f64, i: usize| a + (b - a) * i as f64 / steps as f64;
+            let mut aabr: Option<Aabr<f64>> = None;
+            let mut add = |p: Vec3<f64>| {
+                let clip = view_proj * Vec4::from_point(p);
+                if clip.z + clip.w < 0. {
+                    return;
+                }
+                let p = to_pixels(view_proj, p, viewport);
+                aabr = Some(match aabr {
+                    None => Aabr { min: p, max: p },
+                    Some(b) => Aabr { min: Vec2::partial_min(b.min, p), max: Vec2::partial_max(b.max, p) },
+                });
+            };
+            for i in 0..=steps {
+                for j in 0..=steps {
+                    let (x, y) = (lerp(aabb.min.x, aabb.max.x, i), lerp(aabb.min.y, aabb.max.y, j));
+                    // Points of the box's volume...
+                    for k in 0..=steps {
+                        add(Vec3::new(x, y, lerp(aabb.min.z, aabb.max.z, k)));
+                    }
+                    // ...and of its intersection with the near plane.
+                    if aabb.min.z <= near_plane_z && near_plane_z <= aabb.max.z {
+                        add(Vec3::new(x, y, near_plane_z));
+                    }
+                }
+            }
+            aabr.unwrap()
+        }
+
+        #[test]
+        fn aabb_projected_aabr_matches_sampling() {
+            let viewport = Extent2::new(640, 480);
+            let near = 0.1;
+            let view = Mat4::look_at_rh(Vec3::zero(), -Vec3::unit_z(), Vec3::unit_y());
+            let view_proj = Mat4::perspective_rh_no(70_f64.to_radians(), 640. / 480., near, 100.);
+            let view_proj = view_proj * view;
+            let boxes = [
+                // In front of the camera.
+                Aabb { min: Vec3::new(-1., -2., -9.), max: Vec3::new(3., 1., -4.) },
+                // Straddling the near plane, and partly behind the camera.
+                Aabb { min: Vec3::new(-0.5, 0.2, -3.), max: Vec3::new(2., 1., 1.) },
+            ];
+            for &aabb in &boxes {
+                let expected = sampled_aabr(aabb, view_proj, viewport, -near);
+                let actual = aabb.projected_aabr_no(view_proj, viewport).unwrap();
+                assert_relative_eq!(actual.min, expected.min, max_relative = 1e-6);
+                assert_relative_eq!(actual.max, expected.max, max_relative = 1e-6);
+            }
+            let behind = Aabb { min: Vec3::new(-1., -1., 0.), max: Vec3::new(1., 1., 2.) };
+            assert_eq!(behind.projected_aabr_no(view_proj, viewport), None);
+        }
+
+        #[test]
+        fn aabb_projected_aabr_zo() {
+            let viewport = Extent2::new(100, 100);
+            let view = Mat4::look_at_rh(Vec3::zero(), -Vec3::unit_z(), Vec3::unit_y());
+            let no = Mat4::perspective_rh_no(90_f64.to_radians(), 1., 1., 100.) * view;
+            let zo = Mat4::perspective_rh_zo(90_f64.to_radians(), 1., 1., 100.) * view;
+            let aabb = Aabb { min: Vec3::new(-1., -1., -4.), max: Vec3::new(1., 0.5, 2.) };
+            // Both conventions clip against the same near plane, so the results match.
+            let a = aabb.projected_aabr_no(no, viewport).unwrap();
+            let b = aabb.projected_aabr_zo(zo, viewport).unwrap();
+            assert_relative_eq!(a.min, b.min, epsilon = 1e-9);
+            assert_relative_eq!(a.max, b.max, epsilon = 1e-9);
+            assert_relative_eq!(a.max, Vec2::new(100., 75.), epsilon = 1e-9);
+        }
+    }
+
+    mod rounded_rect {
+        use super::*;
+        use crate::vec::Vec4;
+
+        fn rr() -> RoundedRect<f64> {
+            RoundedRect::new(Rect::new(1., 2., 10., 6.), Vec4::new(0., 1., 2., 3.))
+        }
+
+        #[test]
+        fn clamped_radii() {
+            let r = RoundedRect::new(Rect::new(0., 0., 4., 10.), Vec4::new(-1., 1., 3., 2.));
+            assert_eq!(r.clamped_radii(), Vec4::new(0., 1., 2., 2.));
+            // Oversized radii turn the rectangle into a stadium.
+            let stadium = RoundedRect::with_uniform_radius(Rect::new(0., 0., 4., 10.), 100.);
+            assert_relative_eq!(stadium.signed_distance(Vec2::new(2., 5.)), -2.);
+            assert_relative_eq!(stadium.signed_distance(Vec2::new(2., 12.)), 2.);
+            assert_relative_eq!(stadium.signed_distance(Vec2::new(6., 11.)), 3.);
+        }
+
+        #[test]
+        fn signed_distance_sign() {
+            let r = rr();
+            // Inside, away from the corners.
+            assert_relative_eq!(r.signed_distance(Vec2::new(6., 5.)), -3.);
+            assert!(r.contains_point(Vec2::new(6., 5.)));
+            // Outside, along each edge.
+            assert_relative_eq!(r.signed_distance(Vec2::new(6., 0.)), 2.);
+            assert_relative_eq!(r.signed_distance(Vec2::new(13., 5.)), 2.);
+            assert_relative_eq!(r.signed_distance(Vec2::new(6., 9.)), 1.);
+            assert_relative_eq!(r.signed_distance(Vec2::new(0., 5.)), 1.);
+            // On the boundary.
+            assert_relative_eq!(r.signed_distance(Vec2::new(6., 2.)), 0.);
+            assert!(r.contains_point(Vec2::new(6., 2.)));
+            // The bottom-left corner is sharp.
+            assert_relative_eq!(r.signed_distance(Vec2::new(1., 2.)), 0.);
+            assert_relative_eq!(r.signed_distance(Vec2::new(0., 1.)), 2_f64.sqrt());
+            // The top-left corner has a radius of 3, centered on (4, 5).
+            let corner = Vec2::new(1., 8.);
+            assert!(!r.contains_point(corner));
+            assert_relative_eq!(r.signed_distance(corner), 18_f64.sqrt() - 3.);
+            let on_arc = Vec2::new(4., 5.) + Vec2::new(-1., 1.).normalized() * 3.;
+            assert_relative_eq!(r.signed_distance(on_arc), 0., epsilon = 1e-12);
+            // The distance inside a corner is to the arc.
+            let p = Vec2::new(4., 5.) + Vec2::new(-1., 1.).normalized() * 2.;
+            assert_relative_eq!(r.signed_distance(p), -1., epsilon = 1e-12);
+        }
+
+        // Distance to the outline, by sampling its edges and arcs.
+        fn sampled_distance(r: RoundedRect<f64>, p: Vec2<f64>) -> f64 {
+            let radii = r.clamped_radii();
+            let aabr = r.rect.into_aabr();
+            let (min, max) = (aabr.min, aabr.max);
+            let corners = [
+                (radii.x, Vec2::new(-1., -1.), min, Vec2::new(max.x - radii.y, min.y)),
+                (radii.y, Vec2::new(1., -1.), Vec2::new(max.x, min.y), Vec2::new(max.x, max.y - radii.z)),
+                (radii.z, Vec2::new(1., 1.), max, Vec2::new(min.x + radii.w, max.y)),
+                (radii.w, Vec2::new(-1., 1.), Vec2::new(min.x, max.y), Vec2::new(min.x, min.y + radii.x)),
+            ];
+            let n = 2000;
+            let mut distance = f64::INFINITY;
+            for &(radius, dir, corner, next_edge_end) in corners.iter() {
+                let center = corner - dir * radius;
+                // The edge which starts at the end of this corner's arc, going counter-clockwise.
+                let edge_start = center + if dir.x == dir.y { Vec2::new(0., radius * dir.y) } else { Vec2::new(radius * dir.x, 0.) };
+                for i in 0..=n {
+                    let t = i as f64 / n as f64;
+                    let angle = t * core::f64::consts::FRAC_PI_2;
+                    let on_arc = center + Vec2::new(dir.x * angle.cos(), dir.y * angle.sin()) * radius;
+                    let on_edge = edge_start + (next_edge_end - edge_start) * t;
+                    distance = distance.min(p.distance(on_arc)).min(p.distance(on_edge));
+                }
+            }
+            distance
+        }
+
+        // With unequal radii, next to the midlines, where the closest part of the outline can
+        // belong to another corner than the one of the point's quadrant.
+        #[test]
+        fn signed_distance_unequal_radii() {
+            let rects = [
+                rr(),
+                RoundedRect::new(Rect::new(0., 0., 4., 4.), Vec4::new(0., 2., 0.5, 1.5)),
+                RoundedRect::new(Rect::new(-2., -1., 3., 8.), Vec4::new(1.5, 0., 1.5, 0.25)),
+            ];
+            for r in rects.iter() {
+                let aabr = r.rect.into_aabr();
+                let center = aabr.center();
+                for i in 0..=40 {
+                    for &offset in [-0.05, -0.01, 0., 0.01, 0.05].iter() {
+                        let x = aabr.min.x - 1. + (aabr.max.x - aabr.min.x + 2.) * i as f64 / 40.;
+                        let y = aabr.min.y - 1. + (aabr.max.y - aabr.min.y + 2.) * i as f64 / 40.;
+                        for &p in [Vec2::new(x, center.y + offset), Vec2::new(center.x + offset, y)].iter() {
+                            let d = r.signed_distance(p);
+                            assert_relative_eq!(d.abs(), sampled_distance(*r, p), epsilon = 3e-3);
+                            // Close to the midlines, the outline is (almost) where the rectangle's is.
+                            if (p.x - aabr.min.x).abs().min((p.x - aabr.max.x).abs()) > 0.01 && (p.y - aabr.min.y).abs().min((p.y - aabr.max.y).abs()) > 0.01 {
+                                assert_eq!(d < 0., aabr.contains_point(p), "{:?} {:?}", r, p);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn bezier_outline_follows_sdf() {
+            for r in [rr(), RoundedRect::with_uniform_radius(Rect::new(-3., -3., 6., 2.), 5.)].iter() {
+                let outline = r.to_cubic_beziers();
+                for i in 0..8 {
+                    assert_eq!(outline[i].end, outline[(i + 1) % 8].start);
+                    for j in 0..=32 {
+                        let p = outline[i].evaluate(j as f64 / 32.);
+                        // The quarter-circle approximation deviates from the circle by
+                        // less than 0.03% of the radius.
+                        assert!(r.signed_distance(p).abs() < 1e-3, "{:?}", p);
+                    }
+                }
+            }
+        }
+    }
+
+    mod heightfield {
+        use super::*;
+        use crate::vec::Extent2;
+
+        // A 5x4 grid of "random" heights.
+        const HEIGHTS: [f64; 20] = [
+            0.5, 1.2, 0.3, 2.0, 1.1,
+            0.7, 0.1, 1.8, 0.9, 0.4,
+            1.5, 2.2, 0.6, 0.2, 1.3,
+            0.8, 1.9, 1.0, 0.3, 0.6,
+        ];
+        fn heightfield() -> Heightfield<'static, f64> {
+            Heightfield::new(&HEIGHTS, Extent2::new(5, 4))
+        }
+
+        #[test] fn bilinear_matches_samples() {
+            let hf = heightfield();
+            for y in 0..4 {
+                for x in 0..5 {
+                    let h = hf.sample_height_bilinear(Vec2::new(x as f64, y as f64));
+                    assert_relative_eq!(h, HEIGHTS[y * 5 + x]);
+                }
+            }
+        }
+        #[test] fn bilinear_on_gridlines_and_borders() {
+            let hf = heightfield();
+            // On a vertical gridline, the height is linear along the line.
+            assert_relative_eq!(hf.sample_height_bilinear(Vec2::new(2., 0.25)), 0.3 * 0.75 + 1.8 * 0.25);
+            // Last row and column, and beyond, are clamped.
+            assert_relative_eq!(hf.sample_height_bilinear(Vec2::new(4., 3.)), 0.6);
+            assert_relative_eq!(hf.sample_height_bilinear(Vec2::new(4.5, 2.5)), 1.3 * 0.5 + 0.6 * 0.5);
+            assert_relative_eq!(hf.sample_height_bilinear(Vec2::new(-1., -1.)), 0.5);
+        }
+        #[test] fn normal_of_plane() {
+            // h = 0.5 * x + 0.25 * y, on cells of size (2, 4).
+            let mut heights = [0_f64; 16];
+            for (i, h) in heights.iter_mut().enumerate() {
+                *h = 0.5 * (i % 4) as f64 + 0.25 * (i / 4) as f64;
+            }
+            let hf = Heightfield::new(&heights, Extent2::new(4, 4));
+            let expected = Vec3::new(-0.25, 1., -0.0625).normalized();
+            for &p in &[Vec2::new(1.5, 1.5), Vec2::new(0., 0.), Vec2::new(3., 3.), Vec2::new(3., 1.2)] {
+                assert_relative_eq!(hf.sample_normal(p, Extent2::new(2., 4.)), expected, epsilon = 1e-12);
+            }
+        }
+        #[test] fn raycast_heightfield_matches_brute_force() {
+            let hf = heightfield();
+            let cell = Extent2::new(1.5, 0.5);
+            let height_at = |p: Vec3<f64>| hf.sample_height_bilinear(Vec2::new(p.x / cell.w, p.z / cell.h));
+            let rays = [
+                Ray::new(Vec3::new(-1., 3., -0.5), Vec3::new(1., -0.5, 0.4).normalized()),
+                Ray::new(Vec3::new(7., 2.5, 1.6), Vec3::new(-1., -0.3, -0.2).normalized()),
+                Ray::new(Vec3::new(3., 5., 0.5), Vec3::new(0., -1., 0.)),
+                Ray::new(Vec3::new(0.75, 2.5, 1.), Vec3::new(1., -0.2, 0.)),
+                Ray::new(Vec3::new(0., 3., 0.), Vec3::new(1., 0.1, 1.).normalized()),
+            ];
+            let mut hit_count = 0;
+            for ray in rays.iter() {
+                let hit = hf.raycast_heightfield(*ray, cell, 100.);
+                // Brute force: march along the ray until it goes below the surface.
+                let step = 0.0001;
+                let mut expected = None;
+                let mut t = 0.;
+                while t < 20. {
+                    let p = ray.origin + ray.direction * t;
+                    let inside = p.x >= 0. && p.x <= 6. && p.z >= 0. && p.z <= 1.5;
+                    if inside && p.y <= height_at(p) {
+                        expected = Some(p);
+                        break;
+                    }
+                    t += step;
+                }
+                match (hit, expected) {
+                    (Some(hit), Some(expected)) => {
+                        assert_relative_eq!(hit, expected, epsilon = 0.001);
+                        assert_relative_eq!(hit.y, height_at(hit), epsilon = 1e-9);
+                        hit_count += 1;
+                    },
+                    (None, None) => (),
+                    _ => panic!("{:?}: expected {:?}, got {:?}", ray, expected, hit),
+                }
+            }
+            assert_eq!(hit_count, 3);
+        }
+    }
+
+    #[cfg(feature="std")]
+    mod clipping {
+        use super::*;
+        use alloc::vec::Vec;
+        use crate::geom::{LineSegment2, LineSegment3, Aabr, Aabb, Ray};
+
+        fn random() -> impl FnMut() -> f64 {
+            let mut state = 0x2545f4914f6cdd1d_u64;
+            move || {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                (state >> 11) as f64 / (1_u64 << 53) as f64
+            }
+        }
+        fn sample(segment: LineSegment2<f64>, i: usize, n: usize) -> Vec2<f64> {
+            segment.start + (segment.end - segment.start) * (i as f64 / n as f64)
+        }
+
+        #[test] fn matches_brute_force() {
+            let mut random = random();
+            let aabr = Aabr { min: Vec2::new(-1., -2.), max: Vec2::new(3., 1.) };
+            let n = 1000;
+            for _ in 0..500 {
+                let mut point = || Vec2::new(random() * 10. - 5., random() * 10. - 5.);
+                let segment = LineSegment2 { start: point(), end: point() };
+                let inside: Vec<_> = (0..=n).map(|i| sample(segment, i, n)).filter(|&p| aabr.contains_point(p)).collect();
+                match segment.clipped_to_aabr(aabr) {
+                    None => assert!(inside.is_empty()),
+                    Some(clipped) => {
+                        // The clipped segment is inside, and contains all sampled points which are.
+                        assert!(aabr.contains_point(clipped.start) && aabr.contains_point(clipped.end));
+                        for i in 0..=n {
+                            assert!(aabr.distance_to_point(sample(clipped, i, n)) < 1e-9);
+                        }
+                        for &p in &inside {
+                            assert!(clipped.distance_to_point(p) < 1e-9);
+                        }
+                        // Same direction.
+                        assert!((clipped.end - clipped.start).dot(segment.end - segment.start) >= 0.);
+                    },
+                }
+            }
+        }
+        #[test] fn special_cases() {
+            let aabr = Aabr { min: Vec2::new(0_f64, 0.), max: Vec2::new(4., 4.) };
+            // Entirely inside.
+            let segment = LineSegment2 { start: Vec2::new(1., 1.), end: Vec2::new(3., 2.) };
+            assert_eq!(segment.clipped_to_aabr(aabr), Some(segment));
+            // Entirely outside, including parallel to an edge.
+            assert_eq!(LineSegment2 { start: Vec2::new(5., 1.), end: Vec2::new(8., 2.) }.clipped_to_aabr(aabr), None);
+            assert_eq!(LineSegment2 { start: Vec2::new(-1., 5.), end: Vec2::new(9., 5.) }.clipped_to_aabr(aabr), None);
+            assert_eq!(LineSegment2 { start: Vec2::new(-3., 2.), end: Vec2::new(2., 7.) }.clipped_to_aabr(aabr), None);
+            // Touching only a corner.
+            let segment = LineSegment2 { start: Vec2::new(2., 6.), end: Vec2::new(6., 2.) };
+            assert_eq!(segment.clipped_to_aabr(aabr), Some(LineSegment2 { start: Vec2::new(4., 4.), end: Vec2::new(4., 4.) }));
+            let segment = LineSegment2 { start: Vec2::new(-2., 2.), end: Vec2::new(2., -2.) };
+            assert_eq!(segment.clipped_to_aabr(aabr), Some(LineSegment2 { start: Vec2::zero(), end: Vec2::zero() }));
+            // Exactly along an edge.
+            let segment = LineSegment2 { start: Vec2::new(-1., 4.), end: Vec2::new(6., 4.) };
+            assert_eq!(segment.clipped_to_aabr(aabr), Some(LineSegment2 { start: Vec2::new(0., 4.), end: Vec2::new(4., 4.) }));
+            // Crossing, in both directions.
+            let segment = LineSegment2 { start: Vec2::new(-2., -2.), end: Vec2::new(6., 6.) };
+            assert_eq!(segment.clipped_to_aabr(aabr), Some(LineSegment2 { start: Vec2::zero(), end: Vec2::broadcast(4.) }));
+            let segment = LineSegment2 { start: segment.end, end: segment.start };
+            assert_eq!(segment.clipped_to_aabr(aabr), Some(LineSegment2 { start: Vec2::broadcast(4.), end: Vec2::zero() }));
+            // Single points.
+            let point = LineSegment2 { start: Vec2::new(1., 1.), end: Vec2::new(1., 1.) };
+            assert_eq!(point.clipped_to_aabr(aabr), Some(point));
+            assert_eq!(LineSegment2 { start: Vec2::new(5., 1.), end: Vec2::new(5., 1.) }.clipped_to_aabr(aabr), None);
+        }
+        #[test] fn integer() {
+            let aabr = Aabr { min: Vec2::new(0_i32, 0), max: Vec2::new(4, 4) };
+            let segment = LineSegment2 { start: Vec2::new(1, 1), end: Vec2::new(3, 2) };
+            assert_eq!(segment.clipped_to_aabr_integer(aabr), Some(segment));
+            assert_eq!(LineSegment2 { start: Vec2::new(5, 1), end: Vec2::new(8, 2) }.clipped_to_aabr_integer(aabr), None);
+            let segment = LineSegment2 { start: Vec2::new(2, 6), end: Vec2::new(6, 2) };
+            assert_eq!(segment.clipped_to_aabr_integer(aabr), Some(LineSegment2 { start: Vec2::new(4, 4), end: Vec2::new(4, 4) }));
+            let segment = LineSegment2 { start: Vec2::new(-1, 4), end: Vec2::new(6, 4) };
+            assert_eq!(segment.clipped_to_aabr_integer(aabr), Some(LineSegment2 { start: Vec2::new(0, 4), end: Vec2::new(4, 4) }));
+            // Enters exactly through a corner.
+            let segment = LineSegment2 { start: Vec2::new(-4, 5), end: Vec2::new(4, 3) };
+            assert_eq!(segment.clipped_to_aabr_integer(aabr), Some(LineSegment2 { start: Vec2::new(0, 4), end: Vec2::new(4, 3) }));
+            // Passes half a unit above a corner.
+            let segment = LineSegment2 { start: Vec2::new(-3, 3), end: Vec2::new(1, 5) };
+            assert_eq!(segment.clipped_to_aabr_integer(aabr), None);
+            // Intersections are rounded.
+            let segment = LineSegment2 { start: Vec2::new(-1, 1), end: Vec2::new(2, 3) };
+            assert_eq!(segment.clipped_to_aabr_integer(aabr), Some(LineSegment2 { start: Vec2::new(0, 2), end: Vec2::new(2, 3) }));
+            // Unsigned elements, and extreme values.
+            let aabr = Aabr { min: Vec2::new(10_u8, 10), max: Vec2::new(20, 20) };
+            let segment = LineSegment2 { start: Vec2::new(0_u8, 15), end: Vec2::new(255, 15) };
+            assert_eq!(segment.clipped_to_aabr_integer(aabr), Some(LineSegment2 { start: Vec2::new(10, 15), end: Vec2::new(20, 15) }));
+            let aabr = Aabr { min: Vec2::broadcast(i32::MIN), max: Vec2::broadcast(i32::MAX) };
+            let segment = LineSegment2 { start: Vec2::new(i32::MIN, i32::MAX), end: Vec2::new(i32::MAX, i32::MIN) };
+            assert_eq!(segment.clipped_to_aabr_integer(aabr), Some(segment));
+        }
+        #[test] fn integer_matches_float() {
+            let mut random = random();
+            let aabr = Aabr { min: Vec2::new(-10_i64, -20), max: Vec2::new(30, 10) };
+            let float_aabr = Aabr { min: aabr.min.as_(), max: aabr.max.as_() };
+            for _ in 0..2000 {
+                let mut point = || Vec2::new((random() * 100. - 50.) as i64, (random() * 100. - 50.) as i64);
+                let segment = LineSegment2 { start: point(), end: point() };
+                let float = LineSegment2::<f64> { start: segment.start.as_(), end: segment.end.as_() }.clipped_to_aabr(float_aabr);
+                match (segment.clipped_to_aabr_integer(aabr), float) {
+                    (Some(int), Some(float)) => {
+                        assert!(aabr.contains_point(int.start) && aabr.contains_point(int.end));
+                        assert!((int.start.as_::<f64>() - float.start).map(f64::abs).reduce_partial_max() <= 0.5 + 1e-9);
+                        assert!((int.end.as_::<f64>() - float.end).map(f64::abs).reduce_partial_max() <= 0.5 + 1e-9);
+                    },
+                    (None, None) => (),
+                    // Only possible when touching the rectangle, where floating-point rounding may differ.
+                    (Some(int), None) => assert!(int.start == int.end),
+                    (None, Some(float)) => assert!(float.start.distance(float.end) < 1e-9),
+                }
+            }
+        }
+        #[test] fn three_d() {
+            let aabb = Aabb { min: Vec3::new(0_f64, 0., 0.), max: Vec3::new(4., 4., 4.) };
+            let segment = LineSegment3 { start: Vec3::new(-2., 2., 1.), end: Vec3::new(6., 2., 3.) };
+            assert_eq!(segment.clipped_to_aabb(aabb), Some(LineSegment3 { start: Vec3::new(0., 2., 1.5), end: Vec3::new(4., 2., 2.5) }));
+            assert_eq!(LineSegment3 { start: Vec3::new(-2., 5., 1.), end: Vec3::new(6., 5., 3.) }.clipped_to_aabb(aabb), None);
+            // Rays starting inside exit once, and those pointing away miss.
+            let ray = Ray::new(Vec3::new(1., 1., 1.), Vec3::new(1., 1., 0.).normalized());
+            let clipped = ray.clipped_to_aabb(aabb).unwrap();
+            assert_eq!(clipped.start, ray.origin);
+            assert_relative_eq!(clipped.end, Vec3::new(4., 4., 1.), epsilon = 1e-12);
+            assert_eq!(Ray::new(Vec3::new(5., 1., 1.), Vec3::unit_x()).clipped_to_aabb(aabb), None);
+            let ray = Ray::new(Vec3::new(-1., 4., 5.), Vec3::new(1., 0., -1.).normalized());
+            let clipped = ray.clipped_to_aabb(aabb).unwrap();
+            assert_relative_eq!(clipped.start, Vec3::new(0., 4., 4.), epsilon = 1e-12);
+            assert_relative_eq!(clipped.end, Vec3::new(4., 4., 0.), epsilon = 1e-12);
+        }
+    }
+    #[cfg(feature="std")]
+    mod grid_order {
+        use super::*;
+        use alloc::vec::Vec;
+        use crate::geom::{Aabr, Aabb, hilbert_index, hilbert_position};
+
+        fn random() -> impl FnMut() -> u64 {
+            let mut state = 0x2545f4914f6cdd1d_u64;
+            move || {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state
+            }
+        }
+        fn manhattan(a: Vec2<u32>, b: Vec2<u32>) -> u32 {
+            (a.x as i64 - b.x as i64).unsigned_abs() as u32 + (a.y as i64 - b.y as i64).unsigned_abs() as u32
+        }
+
+        #[test] fn hilbert_exhaustive() {
+            for order in 0..=6 {
+                let n = 1_u32 << order;
+                let mut seen = alloc::vec![false; (n * n) as usize];
+                let mut previous = None;
+                for index in 0..(n as u64 * n as u64) {
+                    let p = hilbert_position(index, order);
+                    assert!(p.x < n && p.y < n);
+                    assert_eq!(hilbert_index(p, order), index);
+                    assert!(!seen[(p.y * n + p.x) as usize]);
+                    seen[(p.y * n + p.x) as usize] = true;
+                    if let Some(previous) = previous {
+                        assert_eq!(manhattan(previous, p), 1, "order {}, index {}", order, index);
+                    }
+                    previous = Some(p);
+                }
+                assert_eq!(hilbert_position(0, order), Vec2::zero());
+                assert_eq!(hilbert_position(n as u64 * n as u64 - 1, order), Vec2::new(n - 1, 0));
+            }
+        }
+        #[test] fn hilbert_large_orders() {
+            let mut random = random();
+            for _ in 0..1000 {
+                let p = Vec2::new(random() as u32, random() as u32);
+                assert_eq!(hilbert_position(hilbert_index(p, 32), 32), p);
+                let p = p.map(|e| e >> 12);
+                assert_eq!(hilbert_position(hilbert_index(p, 20), 20), p);
+            }
+            assert_eq!(hilbert_index(Vec2::new(u32::MAX, 0), 32), u64::MAX);
+            assert_eq!(hilbert_position(u64::MAX, 32), Vec2::new(u32::MAX, 0));
+        }
+        #[test] fn iter_positions_hilbert_matches_curve() {
+            let mut random = random();
+            let order = 5;
+            let curve: Vec<_> = (0..1 << (2 * order)).map(|i| hilbert_position(i, order)).collect();
+            for _ in 0..200 {
+                let mut r = || (random() % 33) as u32;
+                let (x0, x1, y0, y1) = (r(), r(), r(), r());
+                let aabr = Aabr { min: Vec2::new(x0.min(x1), y0.min(y1)), max: Vec2::new(x0.max(x1), y0.max(y1)) };
+                let expected: Vec<_> = curve.iter().copied().filter(|&p| aabr.iter_positions().any(|q| q == p)).collect();
+                let cells: Vec<_> = aabr.iter_positions_hilbert(order).collect();
+                assert_eq!(cells, expected, "{:?}", aabr);
+                assert_eq!(cells.len() as u64, aabr.area_u64());
+            }
+        }
+        #[test] fn iter_positions_hilbert_skips_blocks() {
+            // Only a few cells out of 2^64, which must not be visited one by one.
+            let aabr = Aabr { min: Vec2::new(u32::MAX - 2, 1 << 20), max: Vec2::new(u32::MAX, (1 << 20) + 3) };
+            let cells: Vec<_> = aabr.iter_positions_hilbert(32).collect();
+            assert_eq!(cells.len(), 6);
+            assert!(cells.windows(2).all(|w| hilbert_index(w[0], 32) < hilbert_index(w[1], 32)));
+            let mut sorted = cells.clone();
+            sorted.sort_unstable_by_key(|p| (p.y, p.x));
+            assert_eq!(sorted, aabr.iter_positions().collect::<Vec<_>>());
+            let full = Aabr { min: Vec2::zero(), max: Vec2::broadcast(u32::MAX) };
+            assert_eq!(full.iter_positions_hilbert(32).next(), Some(Vec2::zero()));
+        }
+        #[test] #[should_panic] fn iter_positions_hilbert_outside_of_grid() {
+            let aabr = Aabr { min: Vec2::new(0_u32, 0), max: Vec2::new(5, 4) };
+            let _ = aabr.iter_positions_hilbert(2);
+        }
+        #[test] fn iter_positions() {
+            let empty = Aabr { min: Vec2::new(3_i64, 0), max: Vec2::new(3, 10) };
+            assert_eq!(empty.iter_positions().count(), 0);
+            let inverted = Aabr { min: Vec2::new(0_i8, 10), max: Vec2::new(3, 0) };
+            assert_eq!(inverted.iter_positions().count(), 0);
+            let edge = Aabr { min: Vec2::new(u8::MAX - 2, u8::MAX - 1), max: Vec2::broadcast(u8::MAX) };
+            assert_eq!(edge.iter_positions().collect::<Vec<_>>(), [Vec2::new(253, 254), Vec2::new(254, 254)]);
+            let aabb = Aabb { min: Vec3::new(-1_i32, 2, 5), max: Vec3::new(2, 4, 8) };
+            let cells: Vec<_> = aabb.iter_positions().collect();
+            assert_eq!(cells.len() as u128, aabb.volume_u128());
+            assert!(cells.windows(2).all(|w| (w[0].z, w[0].y, w[0].x) < (w[1].z, w[1].y, w[1].x)));
+            let empty = Aabb { min: Vec3::new(0_u32, 0, 0), max: Vec3::new(4, 0, 4) };
+            assert_eq!(empty.iter_positions().count(), 0);
+        }
+        #[test] fn hilbert_locality() {
+            // Number of distinct 8x8 chunks touched by windows of 64 consecutive cells of a 64x64 grid.
+            fn chunks_per_window(cells: &[Vec2<u32>]) -> f64 {
+                let windows = cells.chunks(64).map(|window| {
+                    let mut chunks: Vec<_> = window.iter().map(|p| (p.x / 8, p.y / 8)).collect();
+                    chunks.sort_unstable();
+                    chunks.dedup();
+                    chunks.len()
+                });
+                windows.sum::<usize>() as f64 / (cells.len() / 64) as f64
+            }
+            let aabr = Aabr { min: Vec2::new(0_u32, 0), max: Vec2::new(64, 64) };
+            let hilbert: Vec<_> = aabr.iter_positions_hilbert(6).collect();
+            let row_major: Vec<_> = aabr.iter_positions().collect();
+            assert_eq!(chunks_per_window(&hilbert), 1.);
+            assert_eq!(chunks_per_window(&row_major), 8.);
+            // Consecutive cells are always adjacent.
+            let mean_step = |cells: &[Vec2<u32>]| cells.windows(2).map(|w| manhattan(w[0], w[1]) as f64).sum::<f64>() / (cells.len() - 1) as f64;
+            assert_eq!(mean_step(&hilbert), 1.);
+            assert!(mean_step(&row_major) > 1.9);
+        }
+    }
+    mod defaults {
+        use super::*;
+
+        #[test] fn aabb_default_is_union_identity() {
+            let b = Aabb { min: Vec3::new(-1_f32, 2., -3.), max: Vec3::new(4., 5., 6.) };
+            assert_eq!(Aabb::<f32>::default(), Aabb::empty());
+            assert_eq!(Aabb::default().union(b), b);
+            assert_eq!(b.union(Aabb::default()), b);
+            assert_eq!(Aabb::default().intersection(b), Aabb::default());
+            assert!(!Aabb::<f32>::default().is_valid());
+            assert!(!Aabb::default().contains_point(Vec3::<f32>::zero()));
+            let points = [Vec3::new(1_f64, 0., 0.), Vec3::new(-2., 3., 0.5), Vec3::new(0., -1., 2.)];
+            let bounds = points.iter().fold(Aabb::default(), |b, &p| b.expanded_to_contain_point(p));
+            assert_eq!(bounds, Aabb { min: Vec3::new(-2., -1., 0.), max: Vec3::new(1., 3., 2.) });
+        }
+        #[test] fn aabr_default_is_union_identity() {
+            let b = Aabr { min: Vec2::new(-7_i32, 1), max: Vec2::new(3, 9) };
+            assert_eq!(Aabr::default(), Aabr { min: Vec2::broadcast(i32::MAX), max: Vec2::broadcast(i32::MIN) });
+            assert_eq!(Aabr::default().union(b), b);
+            let mut u = Aabr::<u8>::default();
+            u.expand_to_contain_point(Vec2::new(4, 200));
+            assert_eq!(u, Aabr::new_empty(Vec2::new(4, 200)));
+            assert_ne!(Aabr::<f32>::default(), Aabr::new_empty(Vec2::zero()));
+        }
+        #[test] fn other_defaults() {
+            // Shapes without an "empty" value default to zero-sized ones at the origin.
+            assert_eq!(Rect::<f32, f32>::default(), Rect::new(0., 0., 0., 0.));
+            assert_eq!(Sphere::<f32, f32>::default(), Sphere { center: Vec3::zero(), radius: 0. });
+            let e = Ellipsoid::<f32, f32>::default();
+            assert_eq!(e.orientation, crate::quaternion::Quaternion::identity());
+        }
+    }
+    mod rotation_bounds {
+        use super::*;
+        use crate::quaternion::Quaternion;
+
+        fn random() -> impl FnMut() -> f64 {
+            let mut state = 0x9e3779b97f4a7c15_u64;
+            move || {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                (state >> 11) as f64 / (1_u64 << 53) as f64
+            }
+        }
+        fn contains(aabb: Aabb<f64>, p: Vec3<f64>) -> bool {
+            let epsilon = 1e-9;
+            (0..3).all(|i| p[i] >= aabb.min[i] - epsilon && p[i] <= aabb.max[i] + epsilon)
+        }
+
+        #[test]
+        fn contains_every_orientation() {
+            let mut random = random();
+            let mut random_vec = |scale: f64| Vec3::new(random() - 0.5, random() - 0.5, random() - 0.5) * scale;
+            for _ in 0..100 {
+                let (axis0, axis1) = (random_vec(2.), random_vec(2.));
+                let from = Quaternion::rotation_3d(axis0.magnitude() * 6., axis0);
+                let to = Quaternion::rotation_3d(axis1.magnitude() * 6., axis1);
+                let center = random_vec(4.);
+                let min = random_vec(4.);
+                let aabb = Aabb { min, max: min + random_vec(2.).map(f64::abs) };
+                let swept = aabb.swept_rotation_bounds(center, from, to);
+                // The largest extent reached by the samples, to check tightness.
+                let mut reached = Aabb { min: Vec3::broadcast(f64::MAX), max: Vec3::broadcast(f64::MIN) };
+                for i in 0..=500 {
+                    let q = Quaternion::slerp_unclamped(from, to, i as f64 / 500.);
+                    for corner in 0..8 {
+                        let p = Vec3::new(
+                            if corner & 1 == 0 { aabb.min.x } else { aabb.max.x },
+                            if corner & 2 == 0 { aabb.min.y } else { aabb.max.y },
+                            if corner & 4 == 0 { aabb.min.z } else { aabb.max.z },
+                        );
+                        let rotated = center + q * (p - center);
+                        assert!(contains(swept, rotated), "{:?} not in {:?}", rotated, swept);
+                        reached.expand_to_contain_point(rotated);
+                    }
+                }
+                assert_relative_eq!(reached.min, swept.min, epsilon = 1e-3);
+                assert_relative_eq!(reached.max, swept.max, epsilon = 1e-3);
+            }
+        }
+        #[test]
+        fn shortest_arc() {
+            let p = Vec3::new(2., 0., 1.);
+            let center = Vec3::new(1., 0., 0.);
+            // Three quarters of a turn one way is a quarter of a turn the other way.
+            let to = Quaternion::rotation_z(3. * core::f64::consts::FRAC_PI_2);
+            let aabb = Aabb::point_rotation_bounds(p, center, Quaternion::identity(), to);
+            assert_relative_eq!(aabb.min, Vec3::new(1., -1., 1.));
+            assert_relative_eq!(aabb.max, Vec3::new(2., 0., 1.));
+            // The sign of the quaternions doesn't matter.
+            let negated = Aabb::point_rotation_bounds(p, center, -Quaternion::identity(), to);
+            assert_relative_eq!(negated.min, aabb.min);
+            assert_relative_eq!(negated.max, aabb.max);
+        }
+        #[test]
+        fn no_rotation() {
+            let aabb = Aabb { min: Vec3::new(-1., 2., 0.5), max: Vec3::new(3., 4., 1.) };
+            let q = Quaternion::rotation_3d(0.7, Vec3::new(1., -1., 2.));
+            assert_eq!(aabb.swept_rotation_bounds(Vec3::new(5., 5., 5.), Quaternion::identity(), Quaternion::identity()), aabb);
+            // The bounds of the rotated box.
+            let rotated = aabb.swept_rotation_bounds(Vec3::zero(), q, q);
+            let mut expected = Aabb::new_empty(q * aabb.min);
+            for &x in &[aabb.min.x, aabb.max.x] {
+                for &y in &[aabb.min.y, aabb.max.y] {
+                    for &z in &[aabb.min.z, aabb.max.z] {
+                        expected.expand_to_contain_point(q * Vec3::new(x, y, z));
+                    }
+                }
+            }
+            assert_relative_eq!(rotated.min, expected.min, epsilon = 1e-12);
+            assert_relative_eq!(rotated.max, expected.max, epsilon = 1e-12);
+            assert_eq!(Aabb::point_rotation_bounds(Vec3::new(1., 2., 3.), Vec3::new(1., 2., 3.), Quaternion::identity(), q), Aabb::new_empty(Vec3::new(1., 2., 3.)));
+        }
+    }
+    mod nine_slice {
+        use super::*;
+        use crate::vec::Vec4;
+
+        // Checks that each cell of `outer` is in exactly one piece.
+        fn assert_tiles(outer: Rect<i32, i32>, pieces: [Rect<i32, i32>; 9]) {
+            for piece in pieces.iter() {
+                assert!(piece.w >= 0 && piece.h >= 0, "{:?}", pieces);
+            }
+            let area: i32 = pieces.iter().map(|r| r.w * r.h).sum();
+            assert_eq!(area, outer.w * outer.h);
+            for y in outer.y..outer.y + outer.h {
+                for x in outer.x..outer.x + outer.w {
+                    let count = pieces.iter().filter(|r| x >= r.x && x < r.x + r.w && y >= r.y && y < r.y + r.h).count();
+                    assert_eq!(count, 1, "({}, {}) in {:?}", x, y, pieces);
+                }
+            }
+        }
+
+        #[test]
+        fn tiles_integers() {
+            let margins = [Vec4::new(3, 5, 2, 4), Vec4::new(0, 0, 0, 0), Vec4::new(7, 1, 9, 9), Vec4::new(-2, 3, 1, -1)];
+            let rects = [Rect::new(-5, 2, 20, 13), Rect::new(0, 0, 8, 6), Rect::new(1, 1, 3, 2), Rect::new(4, 4, 0, 5), Rect::new(0, 0, 1, 1)];
+            for &outer in rects.iter() {
+                for &m in margins.iter() {
+                    assert_tiles(outer, outer.nine_slice(m));
+                    assert_tiles(outer, outer.nine_slice_uv(m));
+                }
+            }
+        }
+        #[test]
+        fn tiles_floats() {
+            let outer = Rect::new(-1.5, 2., 10., 4.);
+            for &m in [Vec4::new(1., 2., 0.5, 1.), Vec4::new(8., 4., 3., 3.), Vec4::new(0., 0., 10., 0.)].iter() {
+                let pieces = outer.nine_slice(m);
+                let area: f64 = pieces.iter().map(|r| r.w * r.h).sum();
+                assert_relative_eq!(area, outer.w * outer.h);
+                for (i, a) in pieces.iter().enumerate() {
+                    assert!(a.w >= 0. && a.h >= 0.);
+                    for b in pieces[i + 1..].iter() {
+                        let overlap = a.into_aabr().intersection(b.into_aabr());
+                        assert!(overlap.max.x <= overlap.min.x || overlap.max.y <= overlap.min.y, "{:?} {:?}", a, b);
+                    }
+                }
+                assert_eq!(pieces[0].position(), outer.position());
+                assert_eq!(pieces[8].x + pieces[8].w, outer.x + outer.w);
+                assert_eq!(pieces[8].y + pieces[8].h, outer.y + outer.h);
+            }
+        }
+        #[test]
+        fn degenerate() {
+            // Proportionally shrunk borders, and an empty center.
+            let pieces = Rect::new(0., 0., 6., 2.).nine_slice(Vec4::new(4., 8., 3., 1.));
+            assert_eq!((pieces[0].w, pieces[1].w, pieces[2].w), (2., 0., 4.));
+            assert_eq!((pieces[0].h, pieces[3].h, pieces[6].h), (0.5, 0., 1.5));
+            assert_eq!(pieces[4].w * pieces[4].h, 0.);
+            // An empty rectangle gives empty pieces.
+            let pieces = Rect::new(3, 3, 0, 0).nine_slice(Vec4::new(1, 1, 1, 1));
+            assert!(pieces.iter().all(|r| r.w == 0 && r.h == 0));
+        }
+        #[test]
+        fn uv_matches_destination() {
+            // The texture is Y-down, the destination is Y-up: corners pair up by index.
+            let margins = Vec4::new(2, 3, 4, 5);
+            let texture = Rect::new(10, 20, 16, 16);
+            let source = texture.nine_slice_uv(margins);
+            let destination = Rect::new(0, 0, 50, 40).nine_slice(margins);
+            for &i in &[0, 2, 6, 8] {
+                assert_eq!(source[i].extent(), destination[i].extent());
+            }
+            // The top-left of the image is at the lowest Y of the texture, and the highest Y of the destination.
+            assert_eq!(source[6], Rect::new(10, 20, 2, 4));
+            assert_eq!(destination[6], Rect::new(0, 36, 2, 4));
+            assert_eq!(source[0], Rect::new(10, 31, 2, 5));
+        }
+    }
+    mod inertia {
+        use super::*;
+        use crate::mat::Mat3;
+
+        // Deterministic pseudo-random values in [0, 1), using a xorshift generator.
+        fn random() -> impl FnMut() -> f64 {
+            let mut state = 0x9e3779b97f4a7c15_u64;
+            move || {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                (state >> 11) as f64 / (1_u64 << 53) as f64
+            }
+        }
+        // Estimates the inertia tensor about `origin` of a body of uniform density, by sampling
+        // points of `bounds` uniformly and keeping those for which `inside` returns true.
+        fn monte_carlo(bounds: Aabb<f64>, mass: f64, origin: Vec3<f64>, inside: impl Fn(Vec3<f64>) -> bool) -> Mat3<f64> {
+            let mut random = random();
+            let (mut tensor, mut count) = (Mat3::zero(), 0);
+            for _ in 0..400_000 {
+                let p = bounds.min + (bounds.max - bounds.min) * Vec3::new(random(), random(), random());
+                if inside(p) {
+                    tensor += parallel_axis_shift(Mat3::zero(), 1., p - origin);
+                    count += 1;
+                }
+            }
+            tensor * (mass / count as f64)
+        }
+        // Compares within 1% of the largest element.
+        fn assert_close(estimate: Mat3<f64>, exact: Mat3<f64>) {
+            let scale = (0..3).fold(0_f64, |max, i| max.max(exact[(i, i)]));
+            for i in 0..3 {
+                for j in 0..3 {
+                    assert!((estimate[(i, j)] - exact[(i, j)]).abs() <= scale * 0.01, "{:?} vs {:?}", estimate, exact);
+                }
+            }
+        }
+        fn bounds(center: Vec3<f64>, half: f64) -> Aabb<f64> {
+            Aabb { min: center - half, max: center + half }
+        }
+
+        #[test] fn sphere() {
+            let sphere = Sphere::new(Vec3::new(1., -2., 0.5), 1.5);
+            let estimate = monte_carlo(bounds(sphere.center, 1.5), 3., sphere.center, |p| p.distance(sphere.center) <= 1.5);
+            assert_close(estimate, sphere.inertia_tensor(3.));
+            // The shell is sampled by projecting points of the ball on it.
+            let mut shell = Mat3::zero();
+            let mut random = random();
+            let mut count = 0;
+            while count < 100_000 {
+                let d = Vec3::new(random(), random(), random()) * 2. - 1.;
+                let length = d.magnitude();
+                if length <= 1. && length > 0. {
+                    shell += parallel_axis_shift(Mat3::zero(), 3. / 100_000., d / length * 1.5);
+                    count += 1;
+                }
+            }
+            assert_close(shell, sphere.hollow_inertia_tensor(3.));
+        }
+        #[test] fn aabb() {
+            let aabb = Aabb { min: Vec3::new(-1., 0., 2.), max: Vec3::new(3., 1., 4.) };
+            let estimate = monte_carlo(aabb, 2., aabb.center(), |_| true);
+            assert_close(estimate, aabb.inertia_tensor(2.));
+        }
+        #[test] fn capsule() {
+            // Tilted, so that all elements of the tensor are non-zero.
+            let capsule = Capsule::new(Vec3::new(-1., -0.5, 0.), Vec3::new(1., 1., 0.75), 0.6);
+            let center = (capsule.segment.start + capsule.segment.end) / 2.;
+            let estimate = monte_carlo(bounds(center, 2.), 5., center, |p| capsule.contains_point(p));
+            let exact = capsule.inertia_tensor(5.);
+            assert!(exact[(0, 1)].abs() > 0.1 && exact[(1, 2)].abs() > 0.1);
+            assert_close(estimate, exact);
+            // Degenerate, as a sphere.
+            let ball = Capsule::new(center, center, 0.6);
+            assert_relative_eq!(ball.inertia_tensor(5.), Sphere::new(center, 0.6).inertia_tensor(5.), epsilon = 1e-12);
+        }
+        #[test] fn cylinder() {
+            let cylinder = Cylinder::new(Vec3::new(0.5, -1., 0.), Vec3::new(-0.5, 1., 1.5), 0.8);
+            let center = (cylinder.segment.start + cylinder.segment.end) / 2.;
+            let estimate = monte_carlo(bounds(center, 2.), 4., center, |p| cylinder.contains_point(p));
+            assert_close(estimate, cylinder.inertia_tensor(4.));
+            assert!(!cylinder.contains_point(cylinder.segment.start * 1.1));
+            assert!(cylinder.contains_point(cylinder.segment.start * 0.99 + center * 0.01));
+        }
+        #[test] fn parallel_axis_theorem() {
+            let aabb = Aabb { min: Vec3::new(-1., 0., 2.), max: Vec3::new(3., 1., 4.) };
+            let pivot = Vec3::new(0.5, -2., 1.);
+            let estimate = monte_carlo(aabb, 2., pivot, |_| true);
+            assert_close(estimate, parallel_axis_shift(aabb.inertia_tensor(2.), 2., aabb.center() - pivot));
+            assert_close(estimate, parallel_axis_shift(aabb.inertia_tensor(2.), 2., pivot - aabb.center()));
+        }
+        #[test] fn combine() {
+            // A dumbbell: a cylinder with a ball at each end.
+            let bar = Cylinder::new(Vec3::new(0., 0., -1.), Vec3::new(0., 0., 1.), 0.1);
+            let (a, b) = (Sphere::new(Vec3::new(0., 0., -1.2), 0.3), Sphere::new(Vec3::new(0., 0., 1.2), 0.3));
+            let parts = [(1., Vec3::zero(), bar.inertia_tensor(1.)), (2., a.center, a.inertia_tensor(2.)), (2., b.center, b.inertia_tensor(2.))];
+            let (mass, center, tensor) = combine_inertia(&parts);
+            assert_eq!(mass, 5.);
+            assert_relative_eq!(center, Vec3::zero());
+            let expected = parallel_axis_shift(a.inertia_tensor(4.), 4., Vec3::new(0., 0., 1.2)) + bar.inertia_tensor(1.);
+            assert_relative_eq!(tensor, expected, epsilon = 1e-12);
+            assert_eq!(combine_inertia::<f32>(&[]), (0., Vec3::zero(), Mat3::zero()));
+        }
+    }
 }
@@ -0,0 +1,499 @@
+//! Geometric predicates on 2D points: orientation and in-circle tests.
+//!
+//! `orient2d()` and `incircle()` evaluate the determinants naively, which is fast but may give
+//! the wrong sign for floating-point inputs that are nearly degenerate (e.g nearly collinear
+//! points), since the determinant is then smaller than its rounding error. Algorithms such as
+//! Delaunay triangulation or convex hulls may then fail in surprising ways.
+//!
+//! With the `robust-predicates` feature, `orient2d_exact()` and `incircle_exact()` give the
+//! correct sign for all `f64` inputs, following Shewchuk's _"Adaptive Precision Floating-Point
+//! Arithmetic and Fast Robust Geometric Predicates"_: the determinant is evaluated in plain
+//! floating-point first, and only when it's too close to zero to be trusted (which is rare), it
+//! is evaluated again exactly, as a sum of non-overlapping floating-point numbers.
+//!
+//! The exact versions assume that no overflow nor underflow happens, which holds for coordinates
+//! whose magnitudes are within about `1e-70` and `1e70` (or zero).
+
+use std::ops::*;
+#[cfg(feature="robust-predicates")]
+use num_traits::real::Real;
+use crate::vec::repr_c::Vec2;
+
+/// Gets twice the signed area of the triangle `a`, `b`, `c`: positive if they are in
+/// counter-clockwise order (with Y pointing upwards), negative if they are clockwise, and zero
+/// if they are collinear.
+///
+/// This is the naive determinant, whose sign may be wrong for nearly collinear floating-point
+/// points. See `orient2d_exact()`.
+///
+/// ```
+/// # use vek::{Vec2, geom::predicates::orient2d};
+/// assert!(orient2d(Vec2::new(0, 0), Vec2::new(1, 0), Vec2::new(0, 1)) > 0);
+/// assert!(orient2d(Vec2::new(0, 0), Vec2::new(0, 1), Vec2::new(1, 0)) < 0);
+/// assert_eq!(orient2d(Vec2::new(0, 0), Vec2::new(1, 1), Vec2::new(2, 2)), 0);
+/// ```
+pub fn orient2d<T>(a: Vec2<T>, b: Vec2<T>, c: Vec2<T>) -> T
+    where T: Copy + Add<Output=T> + Sub<Output=T> + Mul<Output=T>
+{
+    (a.x - c.x) * (b.y - c.y) - (a.y - c.y) * (b.x - c.x)
+}
+
+/// Tells where `d` is relative to the circle which goes through `a`, `b` and `c`, which must be in
+/// counter-clockwise order: the result is positive if `d` is inside, negative if it is outside,
+/// and zero if it is on the circle. The sign is reversed if `a`, `b` and `c` are clockwise.
+///
+/// This is the naive determinant, whose sign may be wrong for nearly cocircular floating-point
+/// points. See `incircle_exact()`.
+///
+/// ```
+/// # use vek::{Vec2, geom::predicates::incircle};
+/// let (a, b, c) = (Vec2::new(1, 0), Vec2::new(0, 1), Vec2::new(-1, 0));
+/// assert!(incircle(a, b, c, Vec2::new(0, 0)) > 0);
+/// assert!(incircle(a, b, c, Vec2::new(2, 0)) < 0);
+/// assert_eq!(incircle(a, b, c, Vec2::new(0, -1)), 0);
+/// ```
+pub fn incircle<T>(a: Vec2<T>, b: Vec2<T>, c: Vec2<T>, d: Vec2<T>) -> T
+    where T: Copy + Add<Output=T> + Sub<Output=T> + Mul<Output=T>
+{
+    let (ad, bd, cd) = (a - d, b - d, c - d);
+    let (alift, blift, clift) = (ad.x * ad.x + ad.y * ad.y, bd.x * bd.x + bd.y * bd.y, cd.x * cd.x + cd.y * cd.y);
+    alift * (bd.x * cd.y - cd.x * bd.y) + blift * (cd.x * ad.y - ad.x * cd.y) + clift * (ad.x * bd.y - bd.x * ad.y)
+}
+
+/// Same as `orient2d()`, but the sign of the result is always correct.
+///
+/// The result is exact when the naive determinant can be trusted; otherwise only its sign is
+/// guaranteed, and its magnitude is an approximation.
+///
+/// ```
+/// # use vek::{Vec2, geom::predicates::orient2d_exact};
+/// let (b, c) = (Vec2::new(12., 12.), Vec2::new(24., 24.));
+/// assert_eq!(orient2d_exact(Vec2::new(0.5, 0.5), b, c), 0.);
+/// // Just above the line y = x, i.e to its left.
+/// assert!(orient2d_exact(Vec2::new(0.5, 0.5000000000000001), b, c) > 0.);
+/// assert!(orient2d_exact(Vec2::new(0.5000000000000001, 0.5), b, c) < 0.);
+/// ```
+#[cfg(feature="robust-predicates")]
+pub fn orient2d_exact(a: Vec2<f64>, b: Vec2<f64>, c: Vec2<f64>) -> f64 {
+    let detleft = (a.x - c.x) * (b.y - c.y);
+    let detright = (a.y - c.y) * (b.x - c.x);
+    let det = detleft - detright;
+    // If both products have opposite signs (or either is zero), the subtraction can't cancel.
+    let detsum = if detleft > 0. {
+        if detright <= 0. {
+            return det;
+        }
+        detleft + detright
+    } else if detleft < 0. {
+        if detright >= 0. {
+            return det;
+        }
+        -detleft - detright
+    } else {
+        return det;
+    };
+    let bound = exact::CCW_ERROR_BOUND * detsum;
+    if det >= bound || -det >= bound {
+        return det;
+    }
+    exact::orient2d(a, b, c)
+}
+
+/// Same as `incircle()`, but the sign of the result is always correct.
+///
+/// The result is exact when the naive determinant can be trusted; otherwise only its sign is
+/// guaranteed, and its magnitude is an approximation.
+///
+/// ```
+/// # use vek::{Vec2, geom::predicates::incircle_exact};
+/// let (a, b, c) = (Vec2::new(1., 0.), Vec2::new(0., 1.), Vec2::new(-1., 0.));
+/// assert!(incircle_exact(a, b, c, Vec2::new(0., -1. + 1e-16)) > 0.);
+/// assert!(incircle_exact(a, b, c, Vec2::new(0., -1. - 2e-16)) < 0.);
+/// assert_eq!(incircle_exact(a, b, c, Vec2::new(0., -1.)), 0.);
+/// ```
+#[cfg(feature="robust-predicates")]
+pub fn incircle_exact(a: Vec2<f64>, b: Vec2<f64>, c: Vec2<f64>, d: Vec2<f64>) -> f64 {
+    let (ad, bd, cd) = (a - d, b - d, c - d);
+    let (bdxcdy, cdxbdy) = (bd.x * cd.y, cd.x * bd.y);
+    let (cdxady, adxcdy) = (cd.x * ad.y, ad.x * cd.y);
+    let (adxbdy, bdxady) = (ad.x * bd.y, bd.x * ad.y);
+    let (alift, blift, clift) = (ad.x * ad.x + ad.y * ad.y, bd.x * bd.x + bd.y * bd.y, cd.x * cd.x + cd.y * cd.y);
+    let det = alift * (bdxcdy - cdxbdy) + blift * (cdxady - adxcdy) + clift * (adxbdy - bdxady);
+    let permanent = (Real::abs(bdxcdy) + Real::abs(cdxbdy)) * alift
+        + (Real::abs(cdxady) + Real::abs(adxcdy)) * blift
+        + (Real::abs(adxbdy) + Real::abs(bdxady)) * clift;
+    let bound = exact::ICC_ERROR_BOUND * permanent;
+    if det > bound || -det > bound {
+        return det;
+    }
+    exact::incircle(a, b, c, d)
+}
+
+/// Exact arithmetic on floating-point expansions, i.e sums of non-overlapping `f64`s.
+#[cfg(feature="robust-predicates")]
+mod exact {
+    use super::Vec2;
+
+    // Half the machine epsilon, i.e the relative rounding error of a single operation.
+    const EPSILON: f64 = f64::EPSILON / 2.;
+    // 2^27 + 1, used to split a f64 into two halves of 26 bits.
+    const SPLITTER: f64 = 134_217_729.;
+    pub const CCW_ERROR_BOUND: f64 = (3. + 16. * EPSILON) * EPSILON;
+    pub const ICC_ERROR_BOUND: f64 = (10. + 96. * EPSILON) * EPSILON;
+
+    /// A sum of non-overlapping `f64`s, in increasing order of magnitude, without zeroes
+    /// (except for a single zero when the sum is zero).
+    #[derive(Clone, Copy)]
+    pub struct Expansion<const N: usize> {
+        terms: [f64; N],
+        len: usize,
+    }
+
+    // x + y == a + b exactly, with x the rounded sum.
+    fn two_sum(a: f64, b: f64) -> (f64, f64) {
+        let x = a + b;
+        let b_virtual = x - a;
+        let a_virtual = x - b_virtual;
+        (x, (a - a_virtual) + (b - b_virtual))
+    }
+    fn split(a: f64) -> (f64, f64) {
+        let c = SPLITTER * a;
+        let hi = c - (c - a);
+        (hi, a - hi)
+    }
+    // x + y == a * b exactly, with x the rounded product.
+    fn two_product(a: f64, b: f64) -> (f64, f64) {
+        let x = a * b;
+        let ((ahi, alo), (bhi, blo)) = (split(a), split(b));
+        let err = x - ahi * bhi - alo * bhi - ahi * blo;
+        (x, alo * blo - err)
+    }
+
+    impl<const N: usize> Expansion<N> {
+        fn new() -> Self {
+            Self { terms: [0.; N], len: 0 }
+        }
+        fn push(&mut self, x: f64) {
+            self.terms[self.len] = x;
+            self.len += 1;
+        }
+        fn finish(mut self, q: f64) -> Self {
+            if q != 0. || self.len == 0 {
+                self.push(q);
+            }
+            self
+        }
+        fn terms(&self) -> &[f64] {
+            &self.terms[..self.len]
+        }
+        /// `a * b - c * d`.
+        pub fn two_products_diff(a: f64, b: f64, c: f64, d: f64) -> Self {
+            let (x, y) = two_product(a, b);
+            let (z, w) = two_product(c, d);
+            Expansion::<2> { terms: [y, x], len: 2 }.sum::<2, N>(&Expansion::<2> { terms: [-w, -z], len: 2 })
+        }
+        /// `self + b`.
+        fn grow<const R: usize>(&self, b: f64) -> Expansion<R> {
+            let mut h = Expansion::new();
+            let mut q = b;
+            for &e in self.terms() {
+                let (sum, err) = two_sum(q, e);
+                q = sum;
+                if err != 0. {
+                    h.push(err);
+                }
+            }
+            h.finish(q)
+        }
+        /// `self + other`; `R` must be at least `N + M`.
+        pub fn sum<const M: usize, const R: usize>(&self, other: &Expansion<M>) -> Expansion<R> {
+            other.terms().iter().fold(self.grow(0.), |h: Expansion<R>, &f| h.grow(f))
+        }
+        /// `self - other`; `R` must be at least `N + M`.
+        pub fn diff<const M: usize, const R: usize>(&self, other: &Expansion<M>) -> Expansion<R> {
+            other.terms().iter().fold(self.grow(0.), |h: Expansion<R>, &f| h.grow(-f))
+        }
+        /// `self * b`; `R` must be at least `2 * N`.
+        pub fn scale<const R: usize>(&self, b: f64) -> Expansion<R> {
+            let mut h = Expansion::new();
+            let mut q = 0.;
+            for &e in self.terms() {
+                let (product, product_err) = two_product(e, b);
+                let (sum, sum_err) = two_sum(q, product_err);
+                if sum_err != 0. {
+                    h.push(sum_err);
+                }
+                let (new_q, err) = two_sum(product, sum);
+                if err != 0. {
+                    h.push(err);
+                }
+                q = new_q;
+            }
+            h.finish(q)
+        }
+        /// Gets the largest term, which has the sign of the whole sum.
+        pub fn most_significant(&self) -> f64 {
+            self.terms().last().copied().unwrap_or(0.)
+        }
+    }
+
+    pub fn orient2d(a: Vec2<f64>, b: Vec2<f64>, c: Vec2<f64>) -> f64 {
+        // a.x * (b.y - c.y) + b.x * (c.y - a.y) + c.x * (a.y - b.y), from exact products.
+        let ab = Expansion::<4>::two_products_diff(a.x, b.y, a.y, b.x);
+        let bc = Expansion::<4>::two_products_diff(b.x, c.y, b.y, c.x);
+        let ca = Expansion::<4>::two_products_diff(c.x, a.y, c.y, a.x);
+        ab.sum::<4, 8>(&bc).sum::<4, 12>(&ca).most_significant()
+    }
+
+    pub fn incircle(a: Vec2<f64>, b: Vec2<f64>, c: Vec2<f64>, d: Vec2<f64>) -> f64 {
+        // The 4x4 determinant of rows (x, y, x² + y², 1), expanded along the lifted column.
+        let ab = Expansion::<4>::two_products_diff(a.x, b.y, b.x, a.y);
+        let bc = Expansion::<4>::two_products_diff(b.x, c.y, c.x, b.y);
+        let cd = Expansion::<4>::two_products_diff(c.x, d.y, d.x, c.y);
+        let da = Expansion::<4>::two_products_diff(d.x, a.y, a.x, d.y);
+        let ac = Expansion::<4>::two_products_diff(a.x, c.y, c.x, a.y);
+        let bd = Expansion::<4>::two_products_diff(b.x, d.y, d.x, b.y);
+
+        // The 3x3 minors, i.e the orientations of the triangles formed by the other three points.
+        let cda = cd.sum::<4, 8>(&da).sum::<4, 12>(&ac);
+        let dab = da.sum::<4, 8>(&ab).sum::<4, 12>(&bd);
+        let abc = ab.sum::<4, 8>(&bc).diff::<4, 12>(&ac);
+        let bcd = bc.sum::<4, 8>(&cd).diff::<4, 12>(&bd);
+
+        // (x² + y²) * minor.
+        let lifted = |minor: &Expansion<12>, p: Vec2<f64>| -> Expansion<96> {
+            let x = minor.scale::<24>(p.x).scale::<48>(p.x);
+            let y = minor.scale::<24>(p.y).scale::<48>(p.y);
+            x.sum(&y)
+        };
+        let adet = lifted(&bcd, a);
+        let bdet = lifted(&cda, b);
+        let cdet = lifted(&dab, c);
+        let ddet = lifted(&abc, d);
+        let abdet = adet.diff::<96, 192>(&bdet);
+        let cddet = cdet.diff::<96, 192>(&ddet);
+        abdet.sum::<192, 384>(&cddet).most_significant()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn naive() {
+        let (a, b, c) = (Vec2::new(0_f64, 0.), Vec2::new(4., 0.), Vec2::new(0., 3.));
+        assert_eq!(orient2d(a, b, c), 12.);
+        assert_eq!(orient2d(a, c, b), -12.);
+        assert_eq!(orient2d(b, c, a), 12.);
+        // The circumcircle is centered on (2, 1.5), with a radius of 2.5.
+        assert!(incircle(a, b, c, Vec2::new(2., 1.5)) > 0.);
+        assert!(incircle(a, b, c, Vec2::new(4., 3.)) == 0.);
+        assert!(incircle(a, b, c, Vec2::new(4.6, 1.5)) < 0.);
+        assert!(incircle(a, c, b, Vec2::new(2., 1.5)) < 0.);
+    }
+
+    #[cfg(all(feature="robust-predicates", feature="std"))]
+    mod exact {
+        use super::*;
+        use alloc::vec::Vec;
+        use std::cmp::Ordering;
+
+        // A slow but simple arbitrary-precision reference: signed integers, as little-endian
+        // base 2^32 digits, scaled by a common power of two so that all inputs are integers.
+        #[derive(Clone, Debug)]
+        struct BigInt {
+            negative: bool,
+            digits: Vec<u32>,
+        }
+
+        impl BigInt {
+            fn from_u128(x: u128, negative: bool) -> Self {
+                let digits = (0..4).map(|i| (x >> (32 * i)) as u32).collect();
+                Self { negative, digits }.trimmed()
+            }
+            fn trimmed(mut self) -> Self {
+                while self.digits.last() == Some(&0) {
+                    self.digits.pop();
+                }
+                if self.digits.is_empty() {
+                    self.negative = false;
+                }
+                self
+            }
+            fn cmp_magnitude(a: &[u32], b: &[u32]) -> Ordering {
+                a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()))
+            }
+            fn add_magnitudes(a: &[u32], b: &[u32]) -> Vec<u32> {
+                let mut out = Vec::new();
+                let mut carry = 0_u64;
+                for i in 0..a.len().max(b.len()) {
+                    let sum = *a.get(i).unwrap_or(&0) as u64 + *b.get(i).unwrap_or(&0) as u64 + carry;
+                    out.push(sum as u32);
+                    carry = sum >> 32;
+                }
+                out.push(carry as u32);
+                out
+            }
+            // a - b, with |a| >= |b|.
+            fn sub_magnitudes(a: &[u32], b: &[u32]) -> Vec<u32> {
+                let mut out = Vec::new();
+                let mut borrow = 0_i64;
+                for (i, &x) in a.iter().enumerate() {
+                    let mut diff = x as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+                    borrow = (diff < 0) as i64;
+                    if diff < 0 {
+                        diff += 1 << 32;
+                    }
+                    out.push(diff as u32);
+                }
+                out
+            }
+            fn add(&self, other: &Self) -> Self {
+                if self.negative == other.negative {
+                    return Self { negative: self.negative, digits: Self::add_magnitudes(&self.digits, &other.digits) }.trimmed();
+                }
+                match Self::cmp_magnitude(&self.digits, &other.digits) {
+                    Ordering::Less => Self { negative: other.negative, digits: Self::sub_magnitudes(&other.digits, &self.digits) },
+                    _ => Self { negative: self.negative, digits: Self::sub_magnitudes(&self.digits, &other.digits) },
+                }.trimmed()
+            }
+            fn neg(&self) -> Self {
+                Self { negative: !self.negative, digits: self.digits.clone() }.trimmed()
+            }
+            fn sub(&self, other: &Self) -> Self {
+                self.add(&other.neg())
+            }
+            fn mul(&self, other: &Self) -> Self {
+                let mut out = alloc::vec![0_u32; self.digits.len() + other.digits.len() + 1];
+                for (i, &x) in self.digits.iter().enumerate() {
+                    let mut carry = 0_u64;
+                    for (j, &y) in other.digits.iter().enumerate() {
+                        let t = out[i + j] as u64 + x as u64 * y as u64 + carry;
+                        out[i + j] = t as u32;
+                        carry = t >> 32;
+                    }
+                    let mut k = i + other.digits.len();
+                    while carry != 0 {
+                        let t = out[k] as u64 + carry;
+                        out[k] = t as u32;
+                        carry = t >> 32;
+                        k += 1;
+                    }
+                }
+                Self { negative: self.negative != other.negative, digits: out }.trimmed()
+            }
+            fn sign(&self) -> f64 {
+                if self.digits.is_empty() { 0. } else if self.negative { -1. } else { 1. }
+            }
+        }
+
+        // The inputs of the tests are multiples of 2^-1074 (i.e all finite f64s) but we only
+        // use ones whose exponents are within a narrow range, so they fit in a u128 once scaled.
+        const SCALE_EXPONENT: i32 = 80;
+        fn big(x: f64) -> BigInt {
+            let scaled = x.abs() * 2_f64.powi(SCALE_EXPONENT);
+            assert!(scaled.fract() == 0. && scaled < 2_f64.powi(127), "{} can't be represented", x);
+            BigInt::from_u128(scaled as u128, x < 0.)
+        }
+        fn reference_orient2d(a: Vec2<f64>, b: Vec2<f64>, c: Vec2<f64>) -> f64 {
+            let (acx, bcy) = (big(a.x).sub(&big(c.x)), big(b.y).sub(&big(c.y)));
+            let (acy, bcx) = (big(a.y).sub(&big(c.y)), big(b.x).sub(&big(c.x)));
+            acx.mul(&bcy).sub(&acy.mul(&bcx)).sign()
+        }
+        fn reference_incircle(a: Vec2<f64>, b: Vec2<f64>, c: Vec2<f64>, d: Vec2<f64>) -> f64 {
+            let diff = |p: Vec2<f64>| (big(p.x).sub(&big(d.x)), big(p.y).sub(&big(d.y)));
+            let ((adx, ady), (bdx, bdy), (cdx, cdy)) = (diff(a), diff(b), diff(c));
+            let lift = |x: &BigInt, y: &BigInt| x.mul(x).add(&y.mul(y));
+            let alift = lift(&adx, &ady).mul(&bdx.mul(&cdy).sub(&cdx.mul(&bdy)));
+            let blift = lift(&bdx, &bdy).mul(&cdx.mul(&ady).sub(&adx.mul(&cdy)));
+            let clift = lift(&cdx, &cdy).mul(&adx.mul(&bdy).sub(&bdx.mul(&ady)));
+            alift.add(&blift).add(&clift).sign()
+        }
+
+        // Deterministic pseudo-random values, using a xorshift generator.
+        fn random() -> impl FnMut() -> u64 {
+            let mut state = 0x9e3779b97f4a7c15_u64;
+            move || {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state
+            }
+        }
+        fn sign(x: f64) -> f64 {
+            if x > 0. { 1. } else if x < 0. { -1. } else { 0. }
+        }
+        fn ulps(x: f64, n: i64) -> f64 {
+            f64::from_bits((x.to_bits() as i64 + n) as u64)
+        }
+
+        // Shewchuk's classic example: points near the line y = x, nudged by a few ulps, for which
+        // the naive predicate is wrong more often than not.
+        #[test]
+        fn orient2d_nearly_collinear() {
+            let (b, c) = (Vec2::new(12., 12.), Vec2::new(24., 24.));
+            let (mut wrong, mut total) = (0, 0);
+            for i in 0..256 {
+                for j in 0..256 {
+                    let a = Vec2::new(ulps(0.5, i), ulps(0.5, j));
+                    let expected = reference_orient2d(a, b, c);
+                    assert_eq!(sign(orient2d_exact(a, b, c)), expected, "{:?}", a);
+                    assert_eq!(sign(orient2d_exact(b, c, a)), expected, "{:?}", a);
+                    assert_eq!(sign(orient2d_exact(c, b, a)), -expected, "{:?}", a);
+                    if sign(orient2d(a, b, c)) != expected {
+                        wrong += 1;
+                    }
+                    total += 1;
+                }
+            }
+            // Otherwise the test would be pointless.
+            assert!(wrong > total / 10, "{} out of {}", wrong, total);
+        }
+        #[test]
+        fn orient2d_random() {
+            let mut random = random();
+            for _ in 0..2000 {
+                // Points on a line through random points, rounded, then nudged.
+                let p = Vec2::new((random() % 1000) as f64 / 8., (random() % 1000) as f64 / 8.);
+                let q = Vec2::new((random() % 1000) as f64 / 8., (random() % 1000) as f64 / 8.);
+                let s = (random() % 1024) as f64 / 1024.;
+                let r = p + (q - p) * s;
+                let r = Vec2::new(ulps(r.x, (random() % 5) as i64 - 2), ulps(r.y, (random() % 5) as i64 - 2));
+                let expected = reference_orient2d(p, q, r);
+                assert_eq!(sign(orient2d_exact(p, q, r)), expected, "{:?} {:?} {:?}", p, q, r);
+            }
+        }
+        #[test]
+        fn incircle_nearly_cocircular() {
+            let mut random = random();
+            let (mut wrong, mut total) = (0, 0);
+            for _ in 0..2000 {
+                // Points on a circle, rounded, then nudged.
+                let center = Vec2::new((random() % 64) as f64 / 4., (random() % 64) as f64 / 4.);
+                let on_circle = |angle: f64| center + Vec2::new(angle.cos(), angle.sin()) * 10.;
+                let mut angle = || (random() % 6283) as f64 / 1000.;
+                let (a, b, c, d) = (on_circle(angle()), on_circle(angle()), on_circle(angle()), on_circle(angle()));
+                let d = Vec2::new(ulps(d.x, (random() % 7) as i64 - 3), ulps(d.y, (random() % 7) as i64 - 3));
+                let expected = reference_incircle(a, b, c, d);
+                assert_eq!(sign(incircle_exact(a, b, c, d)), expected, "{:?} {:?} {:?} {:?}", a, b, c, d);
+                assert_eq!(sign(incircle_exact(b, a, c, d)), -expected, "{:?} {:?} {:?} {:?}", a, b, c, d);
+                if sign(incircle(a, b, c, d)) != expected {
+                    wrong += 1;
+                }
+                total += 1;
+            }
+            assert!(wrong > 0, "{} out of {}", wrong, total);
+        }
+        #[test]
+        fn exactly_degenerate() {
+            let (a, b, c) = (Vec2::new(0.1, 0.1), Vec2::new(0.2, 0.2), Vec2::new(0.30000000000000004, 0.30000000000000004));
+            assert_eq!(sign(orient2d_exact(a, b, c)), reference_orient2d(a, b, c));
+            // A square's corners are cocircular, even when off the grid.
+            let (a, b, c, d) = (Vec2::new(0.1, 0.1), Vec2::new(0.7, 0.1), Vec2::new(0.7, 0.7), Vec2::new(0.1, 0.7));
+            assert_eq!(incircle_exact(a, b, c, d), 0.);
+            assert_eq!(orient2d_exact(a, a, a), 0.);
+            assert_eq!(incircle_exact(a, a, b, c), 0.);
+        }
+    }
+}
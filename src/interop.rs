@@ -0,0 +1,654 @@
+//! Conversions to and from other math crates' types (`glam`, `nalgebra`, `cgmath`).
+//!
+//! Each crate has its own Cargo feature (`glam-interop`, `nalgebra-interop` and `cgmath-interop`),
+//! which enables `From`/`Into` implementations between its vectors, quaternions and
+//! 3x3/4x4 matrices and `vek`'s.
+//!
+//! All three crates store matrices in column-major order. Conversions from and to
+//! `vek`'s `row_major` matrices transpose the storage, but always preserve the mathematical
+//! meaning, i.e the element at row `i` and column `j` stays at row `i` and column `j`.
+//!
+//! Each submodule also provides functions for reinterpreting slices without copying,
+//! for the `repr_c` types whose layout is guaranteed to match.
+//! When a conversion is only safe in one direction (e.g because the other crate's type
+//! has a stricter alignment), only that direction is provided.
+//! `repr_simd` types and `row_major` matrices are never reinterpreted; convert them element-wise
+//! instead.
+
+#[allow(unused_imports)]
+use std::{mem, slice};
+
+/// Reinterprets a slice of `S` as a slice of `D`.
+///
+/// Callers must guarantee that `S` and `D` have the same size and compatible layouts,
+/// and that `D`'s alignment is not greater than `S`'s.
+#[allow(dead_code)]
+unsafe fn cast_slice<S, D>(s: &[S]) -> &[D] {
+    debug_assert_eq!(mem::size_of::<S>(), mem::size_of::<D>());
+    debug_assert!(mem::align_of::<D>() <= mem::align_of::<S>());
+    slice::from_raw_parts(s.as_ptr() as *const D, s.len())
+}
+
+#[cfg(feature = "glam-interop")]
+pub mod glam {
+    //! Conversions to and from `glam` types.
+    //!
+    //! `glam` is not generic over the scalar type, so conversions are provided for `f32`
+    //! (`Vec2`, `Vec3`, `Vec3A`, `Vec4`, `Quat`, `Mat3`, `Mat4`) and `f64`
+    //! (`DVec2`, `DVec3`, `DVec4`, `DQuat`, `DMat3`, `DMat4`).
+    //!
+    //! ```
+    //! # extern crate vek;
+    //! # extern crate glam;
+    //! use vek::{Vec3, Mat4};
+    //!
+    //! let m: glam::Mat4 = Mat4::<f32>::translation_3d(Vec3::new(1., 2., 3.)).into();
+    //! assert_eq!(m, glam::Mat4::from_translation(glam::Vec3::new(1., 2., 3.)));
+    //! ```
+
+    use super::cast_slice;
+    use crate::vec::repr_c::{Vec2, Vec3, Vec4};
+    use crate::quaternion::repr_c::Quaternion;
+    use crate::mat::repr_c::column_major::Mat4;
+
+    macro_rules! glam_impl_repr {
+        ($repr:ident $T:ident $GVec2:ident $GVec3:ident $GVec4:ident $GQuat:ident $GMat3:ident $GMat4:ident) => {
+            glam_impl_repr!{@vec $repr $T $GVec2 Vec2}
+            glam_impl_repr!{@vec $repr $T $GVec3 Vec3}
+            glam_impl_repr!{@vec $repr $T $GVec4 Vec4}
+            glam_impl_repr!{@mat $repr $T $GMat3 Mat3}
+            glam_impl_repr!{@mat $repr $T $GMat4 Mat4}
+
+            impl From<::glam::$GQuat> for crate::quaternion::$repr::Quaternion<$T> {
+                fn from(q: ::glam::$GQuat) -> Self {
+                    let [x, y, z, w] = q.to_array();
+                    Self::from_xyzw(x, y, z, w)
+                }
+            }
+            impl From<crate::quaternion::$repr::Quaternion<$T>> for ::glam::$GQuat {
+                fn from(q: crate::quaternion::$repr::Quaternion<$T>) -> Self {
+                    Self::from_xyzw(q.x, q.y, q.z, q.w)
+                }
+            }
+        };
+        (@vec $repr:ident $T:ident $GVec:ident $Vec:ident) => {
+            impl From<::glam::$GVec> for crate::vec::$repr::$Vec<$T> {
+                fn from(v: ::glam::$GVec) -> Self {
+                    v.to_array().into()
+                }
+            }
+            impl From<crate::vec::$repr::$Vec<$T>> for ::glam::$GVec {
+                fn from(v: crate::vec::$repr::$Vec<$T>) -> Self {
+                    Self::from_array(v.into_array())
+                }
+            }
+        };
+        (@mat $repr:ident $T:ident $GMat:ident $Mat:ident) => {
+            impl From<::glam::$GMat> for crate::mat::$repr::column_major::$Mat<$T> {
+                fn from(m: ::glam::$GMat) -> Self {
+                    Self::from_col_arrays(m.to_cols_array_2d())
+                }
+            }
+            impl From<crate::mat::$repr::column_major::$Mat<$T>> for ::glam::$GMat {
+                fn from(m: crate::mat::$repr::column_major::$Mat<$T>) -> Self {
+                    Self::from_cols_array_2d(&m.into_col_arrays())
+                }
+            }
+            impl From<::glam::$GMat> for crate::mat::$repr::row_major::$Mat<$T> {
+                fn from(m: ::glam::$GMat) -> Self {
+                    Self::from_col_arrays(m.to_cols_array_2d())
+                }
+            }
+            impl From<crate::mat::$repr::row_major::$Mat<$T>> for ::glam::$GMat {
+                fn from(m: crate::mat::$repr::row_major::$Mat<$T>) -> Self {
+                    Self::from_cols_array_2d(&m.into_col_arrays())
+                }
+            }
+        };
+        (@vec3a $repr:ident) => {
+            impl From<::glam::Vec3A> for crate::vec::$repr::Vec3<f32> {
+                fn from(v: ::glam::Vec3A) -> Self {
+                    v.to_array().into()
+                }
+            }
+            impl From<crate::vec::$repr::Vec3<f32>> for ::glam::Vec3A {
+                fn from(v: crate::vec::$repr::Vec3<f32>) -> Self {
+                    Self::from_array(v.into_array())
+                }
+            }
+        };
+    }
+
+    glam_impl_repr!{repr_c f32 Vec2 Vec3 Vec4 Quat Mat3 Mat4}
+    glam_impl_repr!{repr_c f64 DVec2 DVec3 DVec4 DQuat DMat3 DMat4}
+    glam_impl_repr!{@vec3a repr_c}
+    #[cfg(all(nightly, feature="repr_simd"))]
+    glam_impl_repr!{repr_simd f32 Vec2 Vec3 Vec4 Quat Mat3 Mat4}
+    #[cfg(all(nightly, feature="repr_simd"))]
+    glam_impl_repr!{repr_simd f64 DVec2 DVec3 DVec4 DQuat DMat3 DMat4}
+    #[cfg(all(nightly, feature="repr_simd"))]
+    glam_impl_repr!{@vec3a repr_simd}
+
+    /// Reinterprets a slice of `vek` `Vec2`s as a slice of `glam` `Vec2`s, without copying.
+    pub fn vec2s_as_glam(s: &[Vec2<f32>]) -> &[::glam::Vec2] {
+        unsafe { cast_slice(s) }
+    }
+    /// Reinterprets a slice of `glam` `Vec2`s as a slice of `vek` `Vec2`s, without copying.
+    pub fn vec2s_from_glam(s: &[::glam::Vec2]) -> &[Vec2<f32>] {
+        unsafe { cast_slice(s) }
+    }
+    /// Reinterprets a slice of `vek` `Vec3`s as a slice of `glam` `Vec3`s, without copying.
+    ///
+    /// There is no such function for `Vec3A`, which is 16 bytes large.
+    pub fn vec3s_as_glam(s: &[Vec3<f32>]) -> &[::glam::Vec3] {
+        unsafe { cast_slice(s) }
+    }
+    /// Reinterprets a slice of `glam` `Vec3`s as a slice of `vek` `Vec3`s, without copying.
+    pub fn vec3s_from_glam(s: &[::glam::Vec3]) -> &[Vec3<f32>] {
+        unsafe { cast_slice(s) }
+    }
+    /// Reinterprets a slice of `glam` `Vec4`s as a slice of `vek` `Vec4`s, without copying.
+    ///
+    /// The opposite direction is not provided: `glam`'s `Vec4` may require 16-byte alignment,
+    /// which a slice of `vek` `Vec4`s does not guarantee.
+    pub fn vec4s_from_glam(s: &[::glam::Vec4]) -> &[Vec4<f32>] {
+        unsafe { cast_slice(s) }
+    }
+    /// Reinterprets a slice of `glam` `Quat`s as a slice of `vek` `Quaternion`s, without copying.
+    ///
+    /// The opposite direction is not provided, for the same reason as `vec4s_from_glam()`.
+    pub fn quats_from_glam(s: &[::glam::Quat]) -> &[Quaternion<f32>] {
+        unsafe { cast_slice(s) }
+    }
+    /// Reinterprets a slice of `glam` `Mat4`s as a slice of `vek` column-major `Mat4`s, without copying.
+    ///
+    /// The opposite direction is not provided, for the same reason as `vec4s_from_glam()`.
+    pub fn mat4s_from_glam(s: &[::glam::Mat4]) -> &[Mat4<f32>] {
+        unsafe { cast_slice(s) }
+    }
+
+    // Guard the reinterpreting functions against layout changes in either crate.
+    assert_eq_size!(Vec2<f32>, ::glam::Vec2);
+    assert_eq_size!(Vec3<f32>, ::glam::Vec3);
+    assert_eq_size!(Vec4<f32>, ::glam::Vec4);
+    assert_eq_size!(Quaternion<f32>, ::glam::Quat);
+    assert_eq_size!(Mat4<f32>, ::glam::Mat4);
+    const_assert!(std::mem::align_of::<Vec2<f32>>() == std::mem::align_of::<::glam::Vec2>());
+    const_assert!(std::mem::align_of::<Vec3<f32>>() == std::mem::align_of::<::glam::Vec3>());
+    const_assert!(std::mem::align_of::<Vec4<f32>>() <= std::mem::align_of::<::glam::Vec4>());
+    const_assert!(std::mem::align_of::<Quaternion<f32>>() <= std::mem::align_of::<::glam::Quat>());
+    const_assert!(std::mem::align_of::<Mat4<f32>>() <= std::mem::align_of::<::glam::Mat4>());
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::mat::repr_c::{row_major, column_major};
+        use crate::mat::repr_c::Mat3;
+
+        #[test]
+        fn vec_round_trip() {
+            let v = Vec2::new(1_f32, 2.);
+            assert_eq!(Vec2::from(::glam::Vec2::from(v)), v);
+            let v = Vec3::new(1_f32, 2., 3.);
+            assert_eq!(Vec3::from(::glam::Vec3::from(v)), v);
+            assert_eq!(Vec3::from(::glam::Vec3A::from(v)), v);
+            let v = Vec4::new(1_f64, 2., 3., 4.);
+            assert_eq!(Vec4::from(::glam::DVec4::from(v)), v);
+            assert_eq!(::glam::DVec4::from(v), ::glam::DVec4::new(1., 2., 3., 4.));
+        }
+
+        #[test]
+        fn quaternion_round_trip() {
+            let q = Quaternion::<f32>::rotation_y(0.5);
+            assert_eq!(Quaternion::from(::glam::Quat::from(q)), q);
+            assert_eq!(::glam::Quat::from(q).to_array(), q.into_vec4().into_array());
+            let g = ::glam::Quat::from_rotation_y(0.5);
+            assert_relative_eq!(Quaternion::from(g), q);
+        }
+
+        #[test]
+        fn mat_round_trip() {
+            let m = row_major::Mat4::<f32>::new(
+                0., 1., 2., 3.,
+                4., 5., 6., 7.,
+                8., 9., 10., 11.,
+                12., 13., 14., 15.,
+            );
+            assert_eq!(row_major::Mat4::from(::glam::Mat4::from(m)), m);
+            let m = column_major::Mat4::from(m);
+            assert_eq!(column_major::Mat4::from(::glam::Mat4::from(m)), m);
+            let m = Mat3::<f64>::new(0., 1., 2., 3., 4., 5., 6., 7., 8.);
+            assert_eq!(Mat3::from(::glam::DMat3::from(m)), m);
+        }
+
+        #[test]
+        fn mat_layout() {
+            let t = Vec3::new(1_f32, 2., 3.);
+            let g = ::glam::Mat4::from_translation(t.into());
+            assert_eq!(column_major::Mat4::from(g), column_major::Mat4::translation_3d(t));
+            assert_eq!(row_major::Mat4::from(g), row_major::Mat4::translation_3d(t));
+            let m = row_major::Mat4::<f32>::new(
+                0., 1., 2., 3.,
+                4., 5., 6., 7.,
+                8., 9., 10., 11.,
+                12., 13., 14., 15.,
+            );
+            let g = ::glam::Mat4::from(m);
+            assert_eq!(g.row(1), ::glam::Vec4::new(4., 5., 6., 7.));
+            assert_eq!(g.col(1), ::glam::Vec4::new(1., 5., 9., 13.));
+        }
+
+        #[test]
+        fn slices() {
+            let v = [Vec3::new(1_f32, 2., 3.), Vec3::new(4., 5., 6.)];
+            let g = vec3s_as_glam(&v);
+            assert_eq!(g, &[::glam::Vec3::new(1., 2., 3.), ::glam::Vec3::new(4., 5., 6.)]);
+            assert_eq!(vec3s_from_glam(g), &v);
+            let v = [Vec2::new(1_f32, 2.)];
+            assert_eq!(vec2s_from_glam(vec2s_as_glam(&v)), &v);
+            let g = [::glam::Vec4::new(1., 2., 3., 4.)];
+            assert_eq!(vec4s_from_glam(&g), &[Vec4::new(1., 2., 3., 4.)]);
+            let g = [::glam::Quat::from_xyzw(1., 2., 3., 4.)];
+            assert_eq!(quats_from_glam(&g), &[Quaternion::from_xyzw(1., 2., 3., 4.)]);
+            let g = [::glam::Mat4::from_translation(::glam::Vec3::new(1., 2., 3.))];
+            assert_eq!(mat4s_from_glam(&g), &[Mat4::translation_3d(Vec3::new(1., 2., 3.))]);
+        }
+    }
+}
+
+#[cfg(feature = "nalgebra-interop")]
+pub mod nalgebra {
+    //! Conversions to and from `nalgebra` types.
+    //!
+    //! Conversions are provided for `Vector2/3/4`, `Point2/3`, `Quaternion`, `Matrix3` and `Matrix4`.
+    //! `UnitQuaternion` converts into `vek`'s `Quaternion`, but not the other way around
+    //! since that would bypass the unit invariant; use `UnitQuaternion::new_normalize()` instead.
+    //!
+    //! ```
+    //! # extern crate vek;
+    //! # extern crate nalgebra;
+    //! use vek::{Vec3, Mat4};
+    //!
+    //! let m: nalgebra::Matrix4<f32> = Mat4::<f32>::translation_3d(Vec3::new(1., 2., 3.)).into();
+    //! assert_eq!(m, nalgebra::Matrix4::new_translation(&nalgebra::Vector3::new(1., 2., 3.)));
+    //! ```
+
+    use ::nalgebra::{ArrayStorage, Scalar};
+    use super::cast_slice;
+    use crate::vec::repr_c::{Vec2, Vec3, Vec4};
+    use crate::mat::repr_c::column_major::Mat4;
+
+    macro_rules! nalgebra_impl_repr {
+        ($repr:ident) => {
+            nalgebra_impl_repr!{@vec $repr Vector2 Vec2 (x y)}
+            nalgebra_impl_repr!{@vec $repr Vector3 Vec3 (x y z)}
+            nalgebra_impl_repr!{@vec $repr Vector4 Vec4 (x y z w)}
+            nalgebra_impl_repr!{@point $repr Point2 Vector2 Vec2}
+            nalgebra_impl_repr!{@point $repr Point3 Vector3 Vec3}
+            nalgebra_impl_repr!{@mat $repr Matrix3 Mat3 column_major}
+            nalgebra_impl_repr!{@mat $repr Matrix4 Mat4 column_major}
+            nalgebra_impl_repr!{@mat $repr Matrix3 Mat3 row_major}
+            nalgebra_impl_repr!{@mat $repr Matrix4 Mat4 row_major}
+
+            impl<T: Scalar> From<::nalgebra::Quaternion<T>> for crate::quaternion::$repr::Quaternion<T> {
+                fn from(q: ::nalgebra::Quaternion<T>) -> Self {
+                    // nalgebra stores the coordinates as (i, j, k, w).
+                    let [[x, y, z, w]] = q.coords.data.0;
+                    Self::from_xyzw(x, y, z, w)
+                }
+            }
+            impl<T: Scalar> From<crate::quaternion::$repr::Quaternion<T>> for ::nalgebra::Quaternion<T> {
+                fn from(q: crate::quaternion::$repr::Quaternion<T>) -> Self {
+                    Self::from_vector(::nalgebra::Vector4::new(q.x, q.y, q.z, q.w))
+                }
+            }
+            impl<T: Scalar> From<::nalgebra::UnitQuaternion<T>> for crate::quaternion::$repr::Quaternion<T> {
+                fn from(q: ::nalgebra::UnitQuaternion<T>) -> Self {
+                    q.into_inner().into()
+                }
+            }
+        };
+        (@vec $repr:ident $NVec:ident $Vec:ident ($($get:ident)+)) => {
+            impl<T: Scalar> From<::nalgebra::$NVec<T>> for crate::vec::$repr::$Vec<T> {
+                fn from(v: ::nalgebra::$NVec<T>) -> Self {
+                    let [[$($get),+]] = v.data.0;
+                    Self::new($($get),+)
+                }
+            }
+            impl<T: Scalar> From<crate::vec::$repr::$Vec<T>> for ::nalgebra::$NVec<T> {
+                fn from(v: crate::vec::$repr::$Vec<T>) -> Self {
+                    Self::from_array_storage(ArrayStorage([v.into_array()]))
+                }
+            }
+        };
+        (@point $repr:ident $NPoint:ident $NVec:ident $Vec:ident) => {
+            impl<T: Scalar> From<::nalgebra::$NPoint<T>> for crate::vec::$repr::$Vec<T> {
+                fn from(p: ::nalgebra::$NPoint<T>) -> Self {
+                    p.coords.into()
+                }
+            }
+            impl<T: Scalar> From<crate::vec::$repr::$Vec<T>> for ::nalgebra::$NPoint<T> {
+                fn from(v: crate::vec::$repr::$Vec<T>) -> Self {
+                    Self::from(::nalgebra::$NVec::from(v))
+                }
+            }
+        };
+        (@mat $repr:ident $NMat:ident $Mat:ident $layout:ident) => {
+            impl<T: Scalar> From<::nalgebra::$NMat<T>> for crate::mat::$repr::$layout::$Mat<T> {
+                fn from(m: ::nalgebra::$NMat<T>) -> Self {
+                    Self::from_col_arrays(m.data.0)
+                }
+            }
+            impl<T: Scalar> From<crate::mat::$repr::$layout::$Mat<T>> for ::nalgebra::$NMat<T> {
+                fn from(m: crate::mat::$repr::$layout::$Mat<T>) -> Self {
+                    Self::from_array_storage(ArrayStorage(m.into_col_arrays()))
+                }
+            }
+        };
+    }
+
+    nalgebra_impl_repr!{repr_c}
+    #[cfg(all(nightly, feature="repr_simd"))]
+    nalgebra_impl_repr!{repr_simd}
+
+    /// Reinterprets a slice of `vek` `Vec2`s as a slice of `nalgebra` `Vector2`s, without copying.
+    pub fn vec2s_as_nalgebra<T>(s: &[Vec2<T>]) -> &[::nalgebra::Vector2<T>] {
+        unsafe { cast_slice(s) }
+    }
+    /// Reinterprets a slice of `nalgebra` `Vector2`s as a slice of `vek` `Vec2`s, without copying.
+    pub fn vec2s_from_nalgebra<T>(s: &[::nalgebra::Vector2<T>]) -> &[Vec2<T>] {
+        unsafe { cast_slice(s) }
+    }
+    /// Reinterprets a slice of `vek` `Vec3`s as a slice of `nalgebra` `Vector3`s, without copying.
+    pub fn vec3s_as_nalgebra<T>(s: &[Vec3<T>]) -> &[::nalgebra::Vector3<T>] {
+        unsafe { cast_slice(s) }
+    }
+    /// Reinterprets a slice of `nalgebra` `Vector3`s as a slice of `vek` `Vec3`s, without copying.
+    pub fn vec3s_from_nalgebra<T>(s: &[::nalgebra::Vector3<T>]) -> &[Vec3<T>] {
+        unsafe { cast_slice(s) }
+    }
+    /// Reinterprets a slice of `vek` `Vec4`s as a slice of `nalgebra` `Vector4`s, without copying.
+    pub fn vec4s_as_nalgebra<T>(s: &[Vec4<T>]) -> &[::nalgebra::Vector4<T>] {
+        unsafe { cast_slice(s) }
+    }
+    /// Reinterprets a slice of `nalgebra` `Vector4`s as a slice of `vek` `Vec4`s, without copying.
+    pub fn vec4s_from_nalgebra<T>(s: &[::nalgebra::Vector4<T>]) -> &[Vec4<T>] {
+        unsafe { cast_slice(s) }
+    }
+    /// Reinterprets a slice of `vek` column-major `Mat4`s as a slice of `nalgebra` `Matrix4`s, without copying.
+    pub fn mat4s_as_nalgebra<T>(s: &[Mat4<T>]) -> &[::nalgebra::Matrix4<T>] {
+        unsafe { cast_slice(s) }
+    }
+    /// Reinterprets a slice of `nalgebra` `Matrix4`s as a slice of `vek` column-major `Mat4`s, without copying.
+    pub fn mat4s_from_nalgebra<T>(s: &[::nalgebra::Matrix4<T>]) -> &[Mat4<T>] {
+        unsafe { cast_slice(s) }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::quaternion::repr_c::Quaternion;
+        use crate::mat::repr_c::{row_major, column_major};
+
+        #[test]
+        fn vec_round_trip() {
+            let v = Vec2::new(1_i32, 2);
+            assert_eq!(Vec2::from(::nalgebra::Vector2::from(v)), v);
+            let v = Vec3::new(1_f32, 2., 3.);
+            assert_eq!(Vec3::from(::nalgebra::Vector3::from(v)), v);
+            assert_eq!(Vec3::from(::nalgebra::Point3::from(v)), v);
+            let v = Vec4::new(1_f64, 2., 3., 4.);
+            assert_eq!(::nalgebra::Vector4::from(v), ::nalgebra::Vector4::new(1., 2., 3., 4.));
+            assert_eq!(Vec4::from(::nalgebra::Vector4::from(v)), v);
+        }
+
+        #[test]
+        fn quaternion_round_trip() {
+            let q = Quaternion::<f32>::rotation_y(0.5);
+            assert_eq!(Quaternion::from(::nalgebra::Quaternion::from(q)), q);
+            let n = ::nalgebra::UnitQuaternion::from_axis_angle(&::nalgebra::Vector3::y_axis(), 0.5);
+            assert_relative_eq!(Quaternion::from(n), q);
+            assert_relative_eq!(n.w, q.w);
+        }
+
+        #[test]
+        fn mat_round_trip() {
+            let m = row_major::Mat4::<u32>::new(
+                0, 1, 2, 3,
+                4, 5, 6, 7,
+                8, 9, 10, 11,
+                12, 13, 14, 15,
+            );
+            assert_eq!(row_major::Mat4::from(::nalgebra::Matrix4::from(m)), m);
+            let m = column_major::Mat4::from(m);
+            assert_eq!(column_major::Mat4::from(::nalgebra::Matrix4::from(m)), m);
+            let m = row_major::Mat3::<f64>::new(0., 1., 2., 3., 4., 5., 6., 7., 8.);
+            assert_eq!(row_major::Mat3::from(::nalgebra::Matrix3::from(m)), m);
+        }
+
+        #[test]
+        fn mat_layout() {
+            let m = row_major::Mat3::<u32>::new(
+                0, 1, 2,
+                3, 4, 5,
+                6, 7, 8,
+            );
+            let n = ::nalgebra::Matrix3::new(
+                0, 1, 2,
+                3, 4, 5,
+                6, 7, 8,
+            );
+            assert_eq!(::nalgebra::Matrix3::from(m), n);
+            assert_eq!(::nalgebra::Matrix3::from(column_major::Mat3::from(m)), n);
+            let t = Vec3::new(1_f32, 2., 3.);
+            let n = ::nalgebra::Matrix4::new_translation(&t.into());
+            assert_eq!(column_major::Mat4::from(n), column_major::Mat4::translation_3d(t));
+            assert_eq!(row_major::Mat4::from(n), row_major::Mat4::translation_3d(t));
+        }
+
+        #[test]
+        fn slices() {
+            let v = [Vec3::new(1_f32, 2., 3.), Vec3::new(4., 5., 6.)];
+            let n = vec3s_as_nalgebra(&v);
+            assert_eq!(n, &[::nalgebra::Vector3::new(1., 2., 3.), ::nalgebra::Vector3::new(4., 5., 6.)]);
+            assert_eq!(vec3s_from_nalgebra(n), &v);
+            let v = [Vec2::new(1_u8, 2)];
+            assert_eq!(vec2s_from_nalgebra(vec2s_as_nalgebra(&v)), &v);
+            let v = [Vec4::new(1_f64, 2., 3., 4.)];
+            assert_eq!(vec4s_from_nalgebra(vec4s_as_nalgebra(&v)), &v);
+            let m = [Mat4::<f32>::translation_3d(Vec3::new(1., 2., 3.))];
+            assert_eq!(mat4s_as_nalgebra(&m), &[::nalgebra::Matrix4::new_translation(&::nalgebra::Vector3::new(1., 2., 3.))]);
+            assert_eq!(mat4s_from_nalgebra(mat4s_as_nalgebra(&m)), &m);
+        }
+    }
+}
+
+#[cfg(feature = "cgmath-interop")]
+pub mod cgmath {
+    //! Conversions to and from `cgmath` types.
+    //!
+    //! Conversions are provided for `Vector2/3/4`, `Point2/3`, `Quaternion`, `Matrix3` and `Matrix4`.
+    //! Note that `cgmath` requires the standard library, so this feature implies `std`.
+    //!
+    //! ```
+    //! # extern crate vek;
+    //! # extern crate cgmath;
+    //! use vek::{Vec3, Mat4};
+    //!
+    //! let m: cgmath::Matrix4<f32> = Mat4::<f32>::translation_3d(Vec3::new(1., 2., 3.)).into();
+    //! assert_eq!(m, cgmath::Matrix4::from_translation(cgmath::Vector3::new(1., 2., 3.)));
+    //! ```
+
+    use super::cast_slice;
+    use crate::vec::repr_c::{Vec2, Vec3, Vec4};
+    use crate::mat::repr_c::column_major::Mat4;
+
+    macro_rules! cgmath_impl_repr {
+        ($repr:ident) => {
+            cgmath_impl_repr!{@vec $repr Vector2 Vec2 (x y)}
+            cgmath_impl_repr!{@vec $repr Vector3 Vec3 (x y z)}
+            cgmath_impl_repr!{@vec $repr Vector4 Vec4 (x y z w)}
+            cgmath_impl_repr!{@vec $repr Point2 Vec2 (x y)}
+            cgmath_impl_repr!{@vec $repr Point3 Vec3 (x y z)}
+            cgmath_impl_repr!{@mat $repr Matrix3 Mat3 Vector3 Vec3 (x y z) column_major}
+            cgmath_impl_repr!{@mat $repr Matrix4 Mat4 Vector4 Vec4 (x y z w) column_major}
+            cgmath_impl_repr!{@mat $repr Matrix3 Mat3 Vector3 Vec3 (x y z) row_major}
+            cgmath_impl_repr!{@mat $repr Matrix4 Mat4 Vector4 Vec4 (x y z w) row_major}
+
+            impl<T> From<::cgmath::Quaternion<T>> for crate::quaternion::$repr::Quaternion<T> {
+                fn from(q: ::cgmath::Quaternion<T>) -> Self {
+                    let ::cgmath::Quaternion { v, s } = q;
+                    Self::from_xyzw(v.x, v.y, v.z, s)
+                }
+            }
+            impl<T> From<crate::quaternion::$repr::Quaternion<T>> for ::cgmath::Quaternion<T> {
+                fn from(q: crate::quaternion::$repr::Quaternion<T>) -> Self {
+                    let crate::quaternion::$repr::Quaternion { x, y, z, w } = q;
+                    Self { v: ::cgmath::Vector3 { x, y, z }, s: w }
+                }
+            }
+        };
+        (@vec $repr:ident $CVec:ident $Vec:ident ($($get:ident)+)) => {
+            impl<T> From<::cgmath::$CVec<T>> for crate::vec::$repr::$Vec<T> {
+                fn from(v: ::cgmath::$CVec<T>) -> Self {
+                    Self { $($get: v.$get),+ }
+                }
+            }
+            impl<T> From<crate::vec::$repr::$Vec<T>> for ::cgmath::$CVec<T> {
+                fn from(v: crate::vec::$repr::$Vec<T>) -> Self {
+                    Self { $($get: v.$get),+ }
+                }
+            }
+        };
+        (@mat $repr:ident $CMat:ident $Mat:ident $CVec:ident $Vec:ident ($($get:ident)+) $layout:ident) => {
+            // cgmath matrices are made of column vectors named x, y, z (and w).
+            impl<T> From<::cgmath::$CMat<T>> for crate::mat::$repr::$layout::$Mat<T> {
+                fn from(m: ::cgmath::$CMat<T>) -> Self {
+                    Self::from_col_arrays([$(m.$get.into()),+])
+                }
+            }
+            impl<T> From<crate::mat::$repr::$layout::$Mat<T>> for ::cgmath::$CMat<T> {
+                fn from(m: crate::mat::$repr::$layout::$Mat<T>) -> Self {
+                    let [$($get),+] = m.into_col_arrays();
+                    Self { $($get: crate::vec::$repr::$Vec::from($get).into()),+ }
+                }
+            }
+        };
+    }
+
+    cgmath_impl_repr!{repr_c}
+    #[cfg(all(nightly, feature="repr_simd"))]
+    cgmath_impl_repr!{repr_simd}
+
+    /// Reinterprets a slice of `vek` `Vec2`s as a slice of `cgmath` `Vector2`s, without copying.
+    pub fn vec2s_as_cgmath<T>(s: &[Vec2<T>]) -> &[::cgmath::Vector2<T>] {
+        unsafe { cast_slice(s) }
+    }
+    /// Reinterprets a slice of `cgmath` `Vector2`s as a slice of `vek` `Vec2`s, without copying.
+    pub fn vec2s_from_cgmath<T>(s: &[::cgmath::Vector2<T>]) -> &[Vec2<T>] {
+        unsafe { cast_slice(s) }
+    }
+    /// Reinterprets a slice of `vek` `Vec3`s as a slice of `cgmath` `Vector3`s, without copying.
+    pub fn vec3s_as_cgmath<T>(s: &[Vec3<T>]) -> &[::cgmath::Vector3<T>] {
+        unsafe { cast_slice(s) }
+    }
+    /// Reinterprets a slice of `cgmath` `Vector3`s as a slice of `vek` `Vec3`s, without copying.
+    pub fn vec3s_from_cgmath<T>(s: &[::cgmath::Vector3<T>]) -> &[Vec3<T>] {
+        unsafe { cast_slice(s) }
+    }
+    /// Reinterprets a slice of `vek` `Vec4`s as a slice of `cgmath` `Vector4`s, without copying.
+    pub fn vec4s_as_cgmath<T>(s: &[Vec4<T>]) -> &[::cgmath::Vector4<T>] {
+        unsafe { cast_slice(s) }
+    }
+    /// Reinterprets a slice of `cgmath` `Vector4`s as a slice of `vek` `Vec4`s, without copying.
+    pub fn vec4s_from_cgmath<T>(s: &[::cgmath::Vector4<T>]) -> &[Vec4<T>] {
+        unsafe { cast_slice(s) }
+    }
+    /// Reinterprets a slice of `vek` column-major `Mat4`s as a slice of `cgmath` `Matrix4`s, without copying.
+    pub fn mat4s_as_cgmath<T>(s: &[Mat4<T>]) -> &[::cgmath::Matrix4<T>] {
+        unsafe { cast_slice(s) }
+    }
+    /// Reinterprets a slice of `cgmath` `Matrix4`s as a slice of `vek` column-major `Mat4`s, without copying.
+    pub fn mat4s_from_cgmath<T>(s: &[::cgmath::Matrix4<T>]) -> &[Mat4<T>] {
+        unsafe { cast_slice(s) }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::quaternion::repr_c::Quaternion;
+        use crate::mat::repr_c::{row_major, column_major};
+
+        #[test]
+        fn vec_round_trip() {
+            let v = Vec2::new(1_i32, 2);
+            assert_eq!(Vec2::from(::cgmath::Vector2::from(v)), v);
+            let v = Vec3::new(1_f32, 2., 3.);
+            assert_eq!(Vec3::from(::cgmath::Vector3::from(v)), v);
+            assert_eq!(Vec3::from(::cgmath::Point3::from(v)), v);
+            let v = Vec4::new(1_f64, 2., 3., 4.);
+            assert_eq!(::cgmath::Vector4::from(v), ::cgmath::Vector4::new(1., 2., 3., 4.));
+            assert_eq!(Vec4::from(::cgmath::Vector4::from(v)), v);
+        }
+
+        #[test]
+        fn quaternion_round_trip() {
+            use ::cgmath::Rotation3;
+            let q = Quaternion::<f32>::rotation_y(0.5);
+            assert_eq!(Quaternion::from(::cgmath::Quaternion::from(q)), q);
+            let c = ::cgmath::Quaternion::from_angle_y(::cgmath::Rad(0.5));
+            assert_relative_eq!(Quaternion::from(c), q);
+        }
+
+        #[test]
+        fn mat_round_trip() {
+            let m = row_major::Mat4::<u32>::new(
+                0, 1, 2, 3,
+                4, 5, 6, 7,
+                8, 9, 10, 11,
+                12, 13, 14, 15,
+            );
+            assert_eq!(row_major::Mat4::from(::cgmath::Matrix4::from(m)), m);
+            let m = column_major::Mat4::from(m);
+            assert_eq!(column_major::Mat4::from(::cgmath::Matrix4::from(m)), m);
+            let m = row_major::Mat3::<f64>::new(0., 1., 2., 3., 4., 5., 6., 7., 8.);
+            assert_eq!(row_major::Mat3::from(::cgmath::Matrix3::from(m)), m);
+        }
+
+        #[test]
+        fn mat_layout() {
+            let m = row_major::Mat3::<u32>::new(
+                0, 1, 2,
+                3, 4, 5,
+                6, 7, 8,
+            );
+            // cgmath's constructors take elements in column-major order.
+            let c = ::cgmath::Matrix3::new(
+                0, 3, 6,
+                1, 4, 7,
+                2, 5, 8,
+            );
+            assert_eq!(::cgmath::Matrix3::from(m), c);
+            assert_eq!(::cgmath::Matrix3::from(column_major::Mat3::from(m)), c);
+            let t = Vec3::new(1_f32, 2., 3.);
+            let c = ::cgmath::Matrix4::from_translation(t.into());
+            assert_eq!(column_major::Mat4::from(c), column_major::Mat4::translation_3d(t));
+            assert_eq!(row_major::Mat4::from(c), row_major::Mat4::translation_3d(t));
+        }
+
+        #[test]
+        fn slices() {
+            let v = [Vec3::new(1_f32, 2., 3.), Vec3::new(4., 5., 6.)];
+            let c = vec3s_as_cgmath(&v);
+            assert_eq!(c, &[::cgmath::Vector3::new(1., 2., 3.), ::cgmath::Vector3::new(4., 5., 6.)]);
+            assert_eq!(vec3s_from_cgmath(c), &v);
+            let v = [Vec2::new(1_u8, 2)];
+            assert_eq!(vec2s_from_cgmath(vec2s_as_cgmath(&v)), &v);
+            let v = [Vec4::new(1_f64, 2., 3., 4.)];
+            assert_eq!(vec4s_from_cgmath(vec4s_as_cgmath(&v)), &v);
+            let m = [Mat4::<f32>::translation_3d(Vec3::new(1., 2., 3.))];
+            assert_eq!(mat4s_as_cgmath(&m), &[::cgmath::Matrix4::from_translation(::cgmath::Vector3::new(1., 2., 3.))]);
+            assert_eq!(mat4s_from_cgmath(mat4s_as_cgmath(&m)), &m);
+        }
+    }
+}
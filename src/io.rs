@@ -0,0 +1,401 @@
+//! Writing and reading lists of points as simple text formats, e.g to inspect them in
+//! MeshLab or CloudCompare while debugging.
+//!
+//! All functions are generic over `Vec2`, `Vec3` and `Vec4` (see `VectorSpace`).
+//!
+//! Since this crate is `#![no_std]`, writers take a `core::fmt::Write` (e.g a `String`), which
+//! can then be saved with `std::fs::write()`; readers take the whole text as a `&str`.
+//! Reading is only available with the `std` feature.
+//!
+//! Writers take a `precision`, which is the number of digits after the decimal point.
+//! `None` writes each number as Rust's `Display` does, which for floats is the shortest
+//! representation that reads back to the exact same value.
+
+use std::fmt::{self, Display};
+#[cfg(feature="std")]
+use std::str::FromStr;
+#[cfg(feature="std")]
+use alloc::{string::{String, ToString}, vec::Vec};
+use crate::ops::VectorSpace;
+
+/// Names of the elements, as used by CSV headers.
+const ELEMENT_NAMES: [&str; 4] = ["x", "y", "z", "w"];
+
+fn write_value<W: fmt::Write, T: Display>(w: &mut W, value: T, precision: Option<usize>) -> fmt::Result {
+    match precision {
+        Some(precision) => write!(w, "{:.*}", precision, value),
+        None => write!(w, "{}", value),
+    }
+}
+
+fn write_lines<W, V>(w: &mut W, points: &[V], prefix: &str, separator: &str, precision: Option<usize>) -> fmt::Result
+    where W: fmt::Write, V: VectorSpace, V::Scalar: Display
+{
+    for p in points {
+        w.write_str(prefix)?;
+        for (i, value) in p.iter().enumerate() {
+            if i > 0 {
+                w.write_str(separator)?;
+            }
+            write_value(w, *value, precision)?;
+        }
+        w.write_char('\n')?;
+    }
+    Ok(())
+}
+
+/// Writes one point per line, as values separated by a space (e.g `1.5 -2 3`).
+///
+/// This is the XYZ point cloud format, which is also the body of an ASCII PLY file
+/// whose only vertex properties are the point's coordinates.
+///
+/// ```
+/// # use vek::{Vec3, io::write_xyz};
+/// let mut text = String::new();
+/// write_xyz(&mut text, &[Vec3::new(1.5_f32, -2., 3.), Vec3::new(0.1, 0., 1e-3)], None).unwrap();
+/// assert_eq!(text, "1.5 -2 3\n0.1 0 0.001\n");
+/// text.clear();
+/// write_xyz(&mut text, &[Vec3::new(1.5_f32, -2., 3.)], Some(2)).unwrap();
+/// assert_eq!(text, "1.50 -2.00 3.00\n");
+/// ```
+pub fn write_xyz<W, V>(w: &mut W, points: &[V], precision: Option<usize>) -> fmt::Result
+    where W: fmt::Write, V: VectorSpace, V::Scalar: Display
+{
+    write_lines(w, points, "", " ", precision)
+}
+
+/// Writes a header line naming the elements (e.g `x,y,z`), then one point per line,
+/// as comma-separated values.
+///
+/// ```
+/// # use vek::{Vec2, io::write_csv};
+/// let mut text = String::new();
+/// write_csv(&mut text, &[Vec2::new(1, 2), Vec2::new(-3, 4)], None).unwrap();
+/// assert_eq!(text, "x,y\n1,2\n-3,4\n");
+/// ```
+pub fn write_csv<W, V>(w: &mut W, points: &[V], precision: Option<usize>) -> fmt::Result
+    where W: fmt::Write, V: VectorSpace, V::Scalar: Display
+{
+    for (i, name) in ELEMENT_NAMES[..V::DIM].iter().enumerate() {
+        if i > 0 {
+            w.write_char(',')?;
+        }
+        w.write_str(name)?;
+    }
+    w.write_char('\n')?;
+    write_lines(w, points, "", ",", precision)
+}
+
+/// Writes one OBJ vertex line per point (e.g `v 1.5 -2 3`).
+///
+/// The output is a valid OBJ file with no faces, which most tools display as a point cloud.
+///
+/// ```
+/// # use vek::{Vec3, io::write_obj_vertices};
+/// let mut text = String::new();
+/// write_obj_vertices(&mut text, &[Vec3::new(1.5_f64, -2., 3.)], None).unwrap();
+/// assert_eq!(text, "v 1.5 -2 3\n");
+/// ```
+pub fn write_obj_vertices<W, V>(w: &mut W, points: &[V], precision: Option<usize>) -> fmt::Result
+    where W: fmt::Write, V: VectorSpace, V::Scalar: Display
+{
+    write_lines(w, points, "v ", " ", precision)
+}
+
+/// Error returned when reading points fails.
+#[cfg(feature="std")]
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct ParsePointsError {
+    /// The number of the offending line, starting at 1.
+    pub line: usize,
+    /// What went wrong on that line.
+    pub kind: ParsePointsErrorKind,
+}
+
+/// What went wrong when reading a line of points.
+#[cfg(feature="std")]
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub enum ParsePointsErrorKind {
+    /// A value isn't a valid number.
+    InvalidNumber {
+        /// The position of the value in its line, starting at 1 (the OBJ `v` keyword is not
+        /// counted).
+        column: usize,
+        /// The value, as written.
+        value: String,
+    },
+    /// The line doesn't have as many values as the points have elements.
+    WrongValueCount {
+        /// The number of elements of the points.
+        expected: usize,
+        /// The number of values in the line.
+        found: usize,
+    },
+}
+
+#[cfg(feature="std")]
+impl Display for ParsePointsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            ParsePointsErrorKind::InvalidNumber { column, ref value } => write!(f, "line {}, column {}: invalid number {:?}", self.line, column, value),
+            ParsePointsErrorKind::WrongValueCount { expected, found } => write!(f, "line {}: expected {} values, found {}", self.line, expected, found),
+        }
+    }
+}
+
+/// Parses a point from its values. If `allow_extra_values` is `true`, values after the first
+/// `V::DIM` ones are ignored (without being parsed).
+///
+/// `buffer` is only there so that its allocation can be reused across lines.
+#[cfg(feature="std")]
+fn parse_point<'a, V, I>(values: I, line: usize, allow_extra_values: bool, buffer: &mut Vec<V::Scalar>) -> Result<V, ParsePointsError>
+    where V: VectorSpace, V::Scalar: FromStr, I: Iterator<Item=&'a str>
+{
+    buffer.clear();
+    let mut found = 0;
+    for value in values {
+        found += 1;
+        if found > V::DIM {
+            if allow_extra_values {
+                break;
+            }
+            continue;
+        }
+        match value.parse() {
+            Ok(value) => buffer.push(value),
+            Err(_) => return Err(ParsePointsError {
+                line,
+                kind: ParsePointsErrorKind::InvalidNumber { column: found, value: value.to_string() },
+            }),
+        }
+    }
+    if found < V::DIM || (found > V::DIM && !allow_extra_values) {
+        return Err(ParsePointsError { line, kind: ParsePointsErrorKind::WrongValueCount { expected: V::DIM, found } });
+    }
+    Ok(V::from_fn(|i| buffer[i]))
+}
+
+/// Iterates over lines which are neither empty nor comments (starting with `#`), along with
+/// their number.
+#[cfg(feature="std")]
+fn data_lines(text: &str) -> impl Iterator<Item=(usize, &str)> {
+    text.lines().enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+}
+
+/// Reads points written one per line, as values separated by whitespace.
+///
+/// Empty lines and lines starting with `#` are skipped. Every other line must contain exactly
+/// as many values as the points have elements.
+///
+/// ```
+/// # use vek::{Vec3, io::{read_xyz, ParsePointsErrorKind}};
+/// let points: Vec<Vec3<f32>> = read_xyz("# Two points\n1.5 -2 3\n\n0.1\t0 1e-3\n").unwrap();
+/// assert_eq!(points, [Vec3::new(1.5, -2., 3.), Vec3::new(0.1, 0., 1e-3)]);
+///
+/// let error = read_xyz::<Vec3<f32>>("1 2 3\n4 five 6\n").unwrap_err();
+/// assert_eq!(error.line, 2);
+/// assert_eq!(error.to_string(), "line 2, column 2: invalid number \"five\"");
+/// let error = read_xyz::<Vec3<f32>>("1 2 3\n4 5\n").unwrap_err();
+/// assert_eq!(error.kind, ParsePointsErrorKind::WrongValueCount { expected: 3, found: 2 });
+/// ```
+#[cfg(feature="std")]
+pub fn read_xyz<V>(text: &str) -> Result<Vec<V>, ParsePointsError>
+    where V: VectorSpace, V::Scalar: FromStr
+{
+    let mut buffer = Vec::with_capacity(V::DIM);
+    data_lines(text)
+        .map(|(line, values)| parse_point(values.split_whitespace(), line, false, &mut buffer))
+        .collect()
+}
+
+/// Reads the vertices of the body of an ASCII PLY file (i.e without its header), one per
+/// line, as values separated by whitespace.
+///
+/// This is like `read_xyz()`, except that values after the point's elements (e.g normals or
+/// colors) are ignored.
+///
+/// ```
+/// # use vek::{Vec3, io::read_ply_vertices};
+/// let points: Vec<Vec3<f64>> = read_ply_vertices("1 2 3 0 0 1 255 0 0\n4 5 6 0 1 0 0 255 0\n").unwrap();
+/// assert_eq!(points, [Vec3::new(1., 2., 3.), Vec3::new(4., 5., 6.)]);
+/// ```
+#[cfg(feature="std")]
+pub fn read_ply_vertices<V>(text: &str) -> Result<Vec<V>, ParsePointsError>
+    where V: VectorSpace, V::Scalar: FromStr
+{
+    let mut buffer = Vec::with_capacity(V::DIM);
+    data_lines(text)
+        .map(|(line, values)| parse_point(values.split_whitespace(), line, true, &mut buffer))
+        .collect()
+}
+
+/// Reads points written one per line, as comma-separated values, optionally preceded by a
+/// header line such as the one written by `write_csv()`.
+///
+/// Empty lines and lines starting with `#` are skipped. The first other line is considered to
+/// be a header (and skipped) if it starts with a letter. Values may be surrounded by whitespace.
+///
+/// ```
+/// # use vek::{Vec2, io::read_csv};
+/// let points: Vec<Vec2<i32>> = read_csv("x,y\n1,2\n-3, 4\n").unwrap();
+/// assert_eq!(points, [Vec2::new(1, 2), Vec2::new(-3, 4)]);
+/// assert_eq!(read_csv::<Vec2<i32>>("1,2\n").unwrap(), [Vec2::new(1, 2)]);
+/// assert_eq!(read_csv::<Vec2<i32>>("x,y\n1,2,3\n").unwrap_err().to_string(), "line 2: expected 2 values, found 3");
+/// ```
+#[cfg(feature="std")]
+pub fn read_csv<V>(text: &str) -> Result<Vec<V>, ParsePointsError>
+    where V: VectorSpace, V::Scalar: FromStr
+{
+    let mut buffer = Vec::with_capacity(V::DIM);
+    let mut lines = data_lines(text).peekable();
+    if let Some((_, header)) = lines.peek() {
+        if header.starts_with(|c: char| c.is_alphabetic()) {
+            lines.next();
+        }
+    }
+    lines
+        .map(|(line, values)| parse_point(values.split(',').map(str::trim), line, false, &mut buffer))
+        .collect()
+}
+
+/// Reads the vertex positions of an OBJ file, i.e its lines which start with `v`.
+///
+/// All other lines (faces, normals, comments, etc) are ignored. Values after the point's
+/// elements (e.g the optional `w` coordinate, or vertex colors) are ignored as well.
+///
+/// ```
+/// # use vek::{Vec3, io::read_obj_vertices};
+/// let obj = "# A triangle\nv 0 0 0\nv 1 0 0 1\nvn 0 0 1\nv 0 1 0\nf 1 2 3\n";
+/// let points: Vec<Vec3<f32>> = read_obj_vertices(obj).unwrap();
+/// assert_eq!(points, [Vec3::zero(), Vec3::unit_x(), Vec3::unit_y()]);
+/// assert_eq!(read_obj_vertices::<Vec3<f32>>("v 1 2\n").unwrap_err().to_string(), "line 1: expected 3 values, found 2");
+/// ```
+#[cfg(feature="std")]
+pub fn read_obj_vertices<V>(text: &str) -> Result<Vec<V>, ParsePointsError>
+    where V: VectorSpace, V::Scalar: FromStr
+{
+    let mut buffer = Vec::with_capacity(V::DIM);
+    data_lines(text)
+        .filter_map(|(line, values)| {
+            let mut values = values.split_whitespace();
+            match values.next() {
+                Some("v") => Some(parse_point(values, line, true, &mut buffer)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+#[cfg(all(test, feature="std"))]
+mod tests {
+    use super::*;
+    use crate::vec::{Vec2, Vec3, Vec4};
+
+    // Deterministic values of all magnitudes, including ones with long decimal expansions.
+    fn awkward_points() -> Vec<Vec3<f64>> {
+        let mut state = 0x2545f4914f6cdd1d_u64;
+        (0..200).map(|_| Vec3::<f64>::from_fn(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let mantissa = (state >> 11) as f64 / (1_u64 << 53) as f64 - 0.5;
+            let exponent = (state % 40) as i32 - 20;
+            mantissa * 10_f64.powi(exponent)
+        })).collect()
+    }
+
+    mod round_trip {
+        use super::*;
+
+        #[test]
+        fn exact_without_precision() {
+            let points = awkward_points();
+            let mut text = String::new();
+            write_xyz(&mut text, &points, None).unwrap();
+            assert_eq!(read_xyz::<Vec3<f64>>(&text).unwrap(), points);
+            assert_eq!(read_ply_vertices::<Vec3<f64>>(&text).unwrap(), points);
+            text.clear();
+            write_csv(&mut text, &points, None).unwrap();
+            assert_eq!(read_csv::<Vec3<f64>>(&text).unwrap(), points);
+            text.clear();
+            write_obj_vertices(&mut text, &points, None).unwrap();
+            assert_eq!(read_obj_vertices::<Vec3<f64>>(&text).unwrap(), points);
+
+            let points: Vec<Vec3<f32>> = points.iter().map(|p| p.map(|e| e as f32)).collect();
+            text.clear();
+            write_xyz(&mut text, &points, None).unwrap();
+            assert_eq!(read_xyz::<Vec3<f32>>(&text).unwrap(), points);
+        }
+        #[test]
+        fn within_precision() {
+            let points: Vec<_> = awkward_points().iter().map(|p| p.map(|e| e.clamp(-1e6, 1e6))).collect();
+            for &precision in &[0, 3, 9] {
+                let mut text = String::new();
+                write_csv(&mut text, &points, Some(precision)).unwrap();
+                let read = read_csv::<Vec3<f64>>(&text).unwrap();
+                assert_eq!(read.len(), points.len());
+                // Rounding to the nearest, plus some slack for the parsing of large values.
+                let tolerance = 0.5 * 10_f64.powi(-(precision as i32)) + 1e-9;
+                for (a, b) in read.iter().zip(points.iter()) {
+                    assert!((*a - *b).map(f64::abs).reduce_partial_max() <= tolerance, "{} was read back as {}", b, a);
+                }
+            }
+        }
+        #[test]
+        fn other_dimensions_and_integers() {
+            let points = [Vec2::new(1_i64, -2), Vec2::new(i64::MAX, i64::MIN)];
+            let mut text = String::new();
+            write_xyz(&mut text, &points, Some(3)).unwrap();
+            assert_eq!(text, "1 -2\n9223372036854775807 -9223372036854775808\n");
+            assert_eq!(read_xyz::<Vec2<i64>>(&text).unwrap(), points);
+
+            let points = [Vec4::new(1_u8, 2, 3, 4)];
+            text.clear();
+            write_csv(&mut text, &points, None).unwrap();
+            assert_eq!(text, "x,y,z,w\n1,2,3,4\n");
+            assert_eq!(read_csv::<Vec4<u8>>(&text).unwrap(), points);
+        }
+        #[test]
+        fn empty() {
+            let mut text = String::new();
+            write_xyz::<_, Vec3<f32>>(&mut text, &[], None).unwrap();
+            assert_eq!(text, "");
+            write_csv::<_, Vec3<f32>>(&mut text, &[], None).unwrap();
+            assert_eq!(text, "x,y,z\n");
+            assert_eq!(read_csv::<Vec3<f32>>(&text).unwrap(), []);
+            assert_eq!(read_xyz::<Vec3<f32>>("").unwrap(), []);
+            assert_eq!(read_obj_vertices::<Vec3<f32>>("# nothing\n").unwrap(), []);
+        }
+    }
+
+    mod errors {
+        use super::*;
+
+        #[test]
+        fn line_numbers_count_skipped_lines() {
+            let text = "# comment\n\n1 2 3\r\n4 5 6\n   \n7 8 nine\n";
+            assert_eq!(read_xyz::<Vec3<f32>>(text), Err(ParsePointsError {
+                line: 6,
+                kind: ParsePointsErrorKind::InvalidNumber { column: 3, value: "nine".to_string() },
+            }));
+            let text = "v 1 2 3\nf 1 1 1\nvt 0 0\nv 1 2 oops\n";
+            assert_eq!(read_obj_vertices::<Vec3<f32>>(text).unwrap_err().to_string(), "line 4, column 3: invalid number \"oops\"");
+            // A bad header-like line after the header is reported.
+            assert_eq!(read_csv::<Vec2<f32>>("x,y\ny,x\n").unwrap_err().to_string(), "line 2, column 1: invalid number \"y\"");
+        }
+        #[test]
+        fn value_counts() {
+            assert_eq!(read_xyz::<Vec3<f32>>("1 2 3 4\n").unwrap_err().kind, ParsePointsErrorKind::WrongValueCount { expected: 3, found: 4 });
+            assert_eq!(read_csv::<Vec3<f32>>("1,2,\n").unwrap_err().to_string(), "line 1, column 3: invalid number \"\"");
+            assert_eq!(read_ply_vertices::<Vec3<f32>>("1 2\n").unwrap_err().kind, ParsePointsErrorKind::WrongValueCount { expected: 3, found: 2 });
+            // Extra values are not parsed when they're ignored.
+            assert_eq!(read_ply_vertices::<Vec3<f32>>("1 2 3 red\n").unwrap(), [Vec3::new(1., 2., 3.)]);
+        }
+        #[test]
+        fn out_of_range_integers() {
+            assert_eq!(read_xyz::<Vec2<u8>>("255 256\n").unwrap_err().to_string(), "line 1, column 2: invalid number \"256\"");
+        }
+    }
+}
@@ -0,0 +1,566 @@
+//! Closed-form motion helpers for gameplay code: projectiles under gravity, damped springs, and
+//! hanging cables.
+//!
+//! Gravity is given as a non-negative magnitude, and always pulls along -Y.
+//! Horizontal distances are measured in the XZ plane.
+
+use num_traits::{real::Real, One};
+use crate::ops::{VectorSpace, sin, cos};
+use crate::vec::repr_c::{Vec2, Vec3};
+#[cfg(feature="std")]
+use alloc::vec::Vec;
+
+/// Gets the position, at time `t`, of a projectile launched from `start` with `velocity`.
+///
+/// ```
+/// # use vek::{Vec3, kinematics::trajectory_position};
+/// let start = Vec3::new(0_f32, 1., 0.);
+/// let velocity = Vec3::new(2., 10., 0.);
+/// assert_eq!(trajectory_position(start, velocity, 10., 1.), Vec3::new(2., 6., 0.));
+/// assert_eq!(trajectory_position(start, velocity, 10., 2.), Vec3::new(4., 1., 0.));
+/// ```
+pub fn trajectory_position<T: Real>(start: Vec3<T>, velocity: Vec3<T>, gravity: T, t: T) -> Vec3<T> {
+    let two = T::one() + T::one();
+    start + velocity * t - Vec3::unit_y() * (gravity * t * t / two)
+}
+
+/// Gets the farthest horizontal distance that a projectile launched at `speed` can reach when
+/// landing `height_delta` above its launch point (or below, if negative).
+///
+/// Returns `None` if that height can't be reached at all. `gravity` must be positive.
+///
+/// ```
+/// # use vek::kinematics::ballistic_range;
+/// // On flat ground, the best angle is 45°, which reaches v²/g.
+/// assert_eq!(ballistic_range(10_f32, 10., 0.), Some(10.));
+/// // Straight up, just high enough.
+/// assert_eq!(ballistic_range(10_f32, 10., 5.), Some(0.));
+/// assert_eq!(ballistic_range(10_f32, 10., 6.), None);
+/// ```
+pub fn ballistic_range<T: Real>(speed: T, gravity: T, height_delta: T) -> Option<T> {
+    let two = T::one() + T::one();
+    let speed_sq = speed * speed;
+    let remaining = speed_sq - two * gravity * height_delta;
+    if remaining < T::zero() {
+        return None;
+    }
+    Some(speed / gravity * remaining.sqrt())
+}
+
+/// Gets the launch velocities, of magnitude `speed`, with which a projectile launched from
+/// `start` passes through `target`.
+///
+/// Returns the low (flattest) and high (steepest) solutions, in this order. They are equal
+/// when the target is exactly at the edge of the range. Returns `None` when the target
+/// is out of range (see `ballistic_range()`).
+///
+/// When the target is directly above `start`, both solutions go straight up. When it is
+/// directly below (or at `start`), the low one goes straight down, and the high one straight
+/// up, as the projectile eventually falls back through it.
+/// With zero gravity, both solutions aim straight at the target.
+///
+/// ```
+/// # extern crate vek;
+/// # #[macro_use] extern crate approx;
+/// # use vek::{Vec3, kinematics::{ballistic_arc, trajectory_position}};
+/// # fn main() {
+/// let start = Vec3::new(0_f64, 0., 0.);
+/// let target = Vec3::new(10., 0., 0.);
+/// let (low, high) = ballistic_arc(start, target, 15., 9.81).unwrap();
+/// assert!(low.y < high.y);
+/// assert_relative_eq!(low.magnitude(), 15.);
+/// // Both reach the target after covering the horizontal distance.
+/// for v in [low, high] {
+///     let t = 10. / v.x;
+///     assert_relative_eq!(trajectory_position(start, v, 9.81, t), target, epsilon = 1e-9);
+/// }
+/// // Too far for this speed.
+/// assert_eq!(ballistic_arc(start, Vec3::new(30., 0., 0.), 15., 9.81), None);
+/// # }
+/// ```
+pub fn ballistic_arc<T: Real>(start: Vec3<T>, target: Vec3<T>, speed: T, gravity: T) -> Option<(Vec3<T>, Vec3<T>)> {
+    let two = T::one() + T::one();
+    let delta = target - start;
+    let height = delta.y;
+    let horizontal = Vec2::new(delta.x, delta.z);
+    let distance = horizontal.magnitude();
+    let up = Vec3::unit_y() * speed;
+
+    if distance == T::zero() {
+        return if height > T::zero() {
+            // Must rise high enough before falling back.
+            if speed > T::zero() && speed * speed >= two * gravity * height { Some((up, up)) } else { None }
+        } else if gravity == T::zero() && height < T::zero() {
+            // Going up never comes back.
+            Some((-up, -up))
+        } else {
+            Some((-up, up))
+        };
+    }
+    if gravity == T::zero() {
+        return if speed > T::zero() { let v = delta.normalized() * speed; Some((v, v)) } else { None };
+    }
+
+    // Solutions of the trajectory equation for the tangent of the launch angle:
+    // tan = (v² ± sqrt(v⁴ - g(gd² + 2hv²))) / gd.
+    // The low one is computed as the product of both roots divided by the high one, to avoid
+    // cancellation.
+    let speed_sq = speed * speed;
+    let discriminant = speed_sq * speed_sq - gravity * (gravity * distance * distance + two * height * speed_sq);
+    if discriminant < T::zero() {
+        return None;
+    }
+    let root = discriminant.sqrt();
+    let direction = horizontal / distance;
+    let velocity = |horizontal: T, vertical: T| {
+        let v = Vec2::new(horizontal, vertical).normalized() * speed;
+        Vec3::new(direction.x * v.x, v.y, direction.y * v.x)
+    };
+    let high = velocity(gravity * distance, speed_sq + root);
+    let low = velocity(distance * (speed_sq + root), two * height * speed_sq + gravity * distance * distance);
+    Some((low, high))
+}
+
+/// Advances a damped spring which pulls `position` towards `target`, by `dt`, and returns the
+/// new position and velocity.
+///
+/// This solves `x'' = -ω²(x - target) - 2ζωx'` exactly, so it is stable for any `dt`, and
+/// taking two steps is the same as taking one twice as long.
+/// `angular_frequency` (ω) is the speed of the undamped oscillation, in radians per unit of time.
+/// `damping_ratio` (ζ) is 0 for no damping (endless oscillation), below 1 for oscillations which
+/// die out, 1 for reaching the target as fast as possible without overshooting it (critical
+/// damping), and above 1 for an even slower approach.
+///
+/// This works for `Vec2`, `Vec3` and `Vec4`.
+///
+/// ```
+/// # extern crate vek;
+/// # #[macro_use] extern crate approx;
+/// # use vek::{Vec2, kinematics::damped_spring};
+/// # fn main() {
+/// let target = Vec2::new(10_f64, 0.);
+/// let (mut position, mut velocity) = (Vec2::zero(), Vec2::zero());
+/// for _ in 0..100 {
+///     let (p, v) = damped_spring(position, velocity, target, 10., 1., 0.05);
+///     // Critically damped: never overshoots.
+///     assert!(p.x <= target.x);
+///     position = p;
+///     velocity = v;
+/// }
+/// assert_relative_eq!(position, target, epsilon = 1e-9);
+/// # }
+/// ```
+pub fn damped_spring<V>(position: V, velocity: V, target: V, angular_frequency: V::Scalar, damping_ratio: V::Scalar, dt: V::Scalar) -> (V, V)
+    where V: VectorSpace, V::Scalar: Real
+{
+    let (omega, zeta, t) = (angular_frequency, damping_ratio, dt);
+    let one = V::Scalar::one();
+    let y0 = position - target;
+    let v0 = velocity;
+    let (y, v) = if zeta < one {
+        let omega_d = omega * (one - zeta * zeta).sqrt();
+        let decay = (-zeta * omega * t).exp();
+        let (s, c) = (sin(omega_d * t), cos(omega_d * t));
+        let y = (y0 * c + (v0 + y0 * (zeta * omega)) * (s / omega_d)) * decay;
+        let v = (v0 * c - (y0 * omega + v0 * zeta) * (omega * s / omega_d)) * decay;
+        (y, v)
+    } else if zeta == one {
+        let decay = (-omega * t).exp();
+        let k = v0 + y0 * omega;
+        ((y0 + k * t) * decay, (v0 - k * (omega * t)) * decay)
+    } else {
+        let root = (zeta * zeta - one).sqrt();
+        let (r1, r2) = (-omega * (zeta - root), -omega * (zeta + root));
+        let c1 = (v0 - y0 * r2) / (r1 - r2);
+        let c2 = y0 - c1;
+        let (e1, e2) = ((r1 * t).exp(), (r2 * t).exp());
+        (c1 * e1 + c2 * e2, c1 * (r1 * e1) + c2 * (r2 * e2))
+    };
+    (target + y, v)
+}
+
+/// Samples the curve of a cable of the given `length` hanging between `a` and `b` (a catenary),
+/// or returns `None` if `length` is shorter than the distance between them.
+///
+/// The `samples` points are evenly spaced along the cable, from exactly `a` to exactly `b`,
+/// so the polyline they form is a bit shorter than `length`.
+/// The catenary's parameter is found by Newton's method, which stays accurate even when the
+/// cable is nearly taut. A taut cable is a straight line, and a cable between points on the same
+/// vertical line folds onto itself below them.
+///
+/// # Panics
+/// Panics if `samples` is less than 2.
+///
+/// ```
+/// # extern crate vek;
+/// # #[macro_use] extern crate approx;
+/// # use vek::{Vec2, kinematics::catenary_2d};
+/// # fn main() {
+/// let points = catenary_2d(Vec2::new(0_f64, 0.), Vec2::new(4., 0.), 6., 65).unwrap();
+/// assert_eq!(points.len(), 65);
+/// assert_eq!(points[64], Vec2::new(4., 0.));
+/// // Symmetric, and sagging in the middle.
+/// assert_relative_eq!(points[32].x, 2., epsilon = 1e-9);
+/// assert!(points[32].y < -1.);
+/// assert_eq!(catenary_2d(Vec2::new(0_f64, 0.), Vec2::new(4., 0.), 3., 65), None);
+/// # }
+/// ```
+#[cfg(feature="std")]
+pub fn catenary_2d<T: Real>(a: Vec2<T>, b: Vec2<T>, length: T, samples: usize) -> Option<Vec<Vec2<T>>> {
+    assert!(samples >= 2, "a catenary needs at least 2 samples");
+    let distance = a.distance(b);
+    if length < distance {
+        return None;
+    }
+    let (zero, one) = (T::zero(), T::one());
+    let two = one + one;
+    let last = T::from(samples - 1).unwrap();
+    let (h, v) = ((b.x - a.x).abs(), b.y - a.y);
+    let direction = if b.x < a.x { -one } else { one };
+    // Precise even when the cable is nearly taut.
+    let slack = (length - distance) * (length + distance);
+
+    let mut points = Vec::with_capacity(samples);
+    if slack <= zero {
+        points.extend((0..samples).map(|i| a + (b - a) * (T::from(i).unwrap() / last)));
+    } else if h <= length * T::epsilon() {
+        // Vertical: down from `a` to the lowest point, then up to `b`.
+        let bottom = (length - v) / two;
+        points.extend((0..samples).map(|i| {
+            let l = length * T::from(i).unwrap() / last;
+            let y = if l <= bottom { -l } else { l - bottom - bottom };
+            Vec2::new(a.x, a.y + y)
+        }));
+    } else {
+        // With q the length of the cable once it is tilted to remove the height difference, the
+        // catenary's parameter is h / 2z, where sinh(z) / z = q / h.
+        let q = (slack + h * h).sqrt();
+        let z = solve_sinhc(slack / ((q + h) * h));
+        let scale = h / (two * z);
+        // The cable is y = scale * cosh(u), with u = x / scale + u0, where sinh(u) grows linearly
+        // with the arc length.
+        let u0 = (v / q).asinh() - z;
+        let sinh0 = u0.sinh();
+        let cosh0 = (one + sinh0 * sinh0).sqrt();
+        points.extend((0..samples).map(|i| {
+            let l = length * T::from(i).unwrap() / last;
+            let delta = l / scale;
+            let sinh = sinh0 + delta;
+            let cosh = (one + sinh * sinh).sqrt();
+            // x is scale * (u - u0), i.e scale * asinh(sinh(u - u0)), and y is scale * (cosh - cosh0).
+            // Both are written so that nothing cancels out, since the scale is huge when the
+            // cable is nearly taut.
+            let sinh_du = if sinh * sinh0 > zero {
+                delta * (sinh + sinh0) / (sinh * cosh0 + sinh0 * cosh)
+            } else {
+                delta * (one + cosh * cosh0 - sinh * sinh0) / (cosh + cosh0)
+            };
+            Vec2::new(a.x + scale * sinh_du.asinh() * direction, a.y + l * (sinh + sinh0) / (cosh + cosh0))
+        }));
+    }
+    points[0] = a;
+    points[samples - 1] = b;
+    Some(points)
+}
+
+/// Samples the curve of a cable of the given `length` hanging between `a` and `b`, in the
+/// vertical plane which contains them both, or returns `None` if `length` is shorter than the
+/// distance between them.
+///
+/// See `catenary_2d()`, which this relies on.
+///
+/// # Panics
+/// Panics if `samples` is less than 2.
+///
+/// ```
+/// # use vek::{Vec3, kinematics::catenary_3d};
+/// let (a, b) = (Vec3::new(0_f32, 5., 0.), Vec3::new(3., 4., 4.));
+/// let points = catenary_3d(a, b, 8., 33).unwrap();
+/// assert_eq!((points[0], points[32]), (a, b));
+/// assert!(points.iter().all(|p| p.y <= 5.));
+/// ```
+#[cfg(feature="std")]
+pub fn catenary_3d<T: Real>(a: Vec3<T>, b: Vec3<T>, length: T, samples: usize) -> Option<Vec<Vec3<T>>> {
+    let horizontal = Vec2::new(b.x - a.x, b.z - a.z);
+    let h = horizontal.magnitude();
+    let direction = if h > T::zero() { horizontal / h } else { Vec2::unit_x() };
+    let mut points: Vec<Vec3<T>> = catenary_2d(Vec2::new(T::zero(), a.y), Vec2::new(h, b.y), length, samples)?
+        .into_iter()
+        .map(|p| Vec3::new(a.x + direction.x * p.x, p.y, a.z + direction.y * p.x))
+        .collect();
+    points[0] = a;
+    points[samples - 1] = b;
+    Some(points)
+}
+
+// Solves sinh(z) / z = 1 + k for z, given k > 0.
+#[cfg(feature="std")]
+fn solve_sinhc<T: Real>(k: T) -> T {
+    let one = T::one();
+    let (two, six) = (one + one, T::from(6).unwrap());
+    let half = one / two;
+    // sinh(z) / z - 1, and its derivative, without cancellation for small z.
+    let f = |z: T| {
+        if z < half {
+            let z2 = z * z;
+            let (mut term, mut value, mut derivative) = (z2 / six, T::zero(), T::zero());
+            for n in 1..10 {
+                let n = T::from(n).unwrap();
+                value = value + term;
+                derivative = derivative + term * two * n / z;
+                term = term * z2 / ((two * n + two) * (two * n + T::from(3).unwrap()));
+            }
+            (value, derivative)
+        } else {
+            (z.sinh() / z - one, (z * z.cosh() - z.sinh()) / (z * z))
+        }
+    };
+    // Both are upper bounds of the solution; from there, Newton's method converges monotonically
+    // since f is increasing and convex.
+    let mut z = (six * k).sqrt().min(two * (T::from(4).unwrap() * (one + k)).ln());
+    for _ in 0..100 {
+        let (value, derivative) = f(z);
+        let step = (value - k) / derivative;
+        // Also stops on NaN.
+        if step.partial_cmp(&(z * T::epsilon())) != Some(std::cmp::Ordering::Greater) {
+            break;
+        }
+        z = z - step;
+    }
+    z
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod ballistics {
+        use super::*;
+
+        #[test]
+        fn arcs_pass_through_targets() {
+            let gravity = 9.81;
+            let starts = [Vec3::new(0., 0., 0.), Vec3::new(-3., 2., 5.)];
+            let mut reached = 0;
+            let mut missed = 0;
+            for &start in &starts {
+                for &speed in &[0., 5., 12., 30.] {
+                    for x in -4..=4 {
+                        for y in -3..=3 {
+                            for z in -2..=2 {
+                                let target = start + Vec3::new(x as f64 * 7., y as f64 * 5., z as f64 * 9.);
+                                let delta = target - start;
+                                let distance = Vec2::new(delta.x, delta.z).magnitude();
+                                let range = ballistic_range(speed, gravity, delta.y);
+                                match ballistic_arc(start, target, speed, gravity) {
+                                    None => {
+                                        missed += 1;
+                                        assert!(range.is_none_or(|range| distance > range), "{} should reach {}", speed, target);
+                                    },
+                                    Some((low, high)) => {
+                                        reached += 1;
+                                        assert!(distance <= range.unwrap() + 1e-9);
+                                        assert!(low.y <= high.y);
+                                        for &v in &[low, high] {
+                                            assert_relative_eq!(v.magnitude(), speed, epsilon = 1e-9);
+                                            if distance == 0. {
+                                                continue;
+                                            }
+                                            let t = distance / Vec2::new(v.x, v.z).magnitude();
+                                            let p = trajectory_position(start, v, gravity, t);
+                                            assert_relative_eq!(p, target, epsilon = 1e-8);
+                                        }
+                                    },
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            assert!(reached > 200 && missed > 200, "{} reached, {} missed", reached, missed);
+        }
+        #[test]
+        fn edge_of_range() {
+            let range = ballistic_range(20_f64, 9.81, 3.).unwrap();
+            let start = Vec3::new(1., 1., 1.);
+            let target = |distance: f64| start + Vec3::new(0.6, 0., 0.8) * distance + Vec3::unit_y() * 3.;
+            let (low, high) = ballistic_arc(start, target(range), 20., 9.81).unwrap();
+            assert_relative_eq!(low, high, epsilon = 1e-6);
+            assert!(ballistic_arc(start, target(range * (1. - 1e-9)), 20., 9.81).is_some());
+            assert_eq!(ballistic_arc(start, target(range * (1. + 1e-9)), 20., 9.81), None);
+        }
+        #[test]
+        fn vertical_targets() {
+            let start = Vec3::new(2_f32, 0., 2.);
+            let above = start + Vec3::unit_y() * 5.;
+            let below = start - Vec3::unit_y() * 5.;
+            let up = Vec3::unit_y() * 10.;
+            assert_eq!(ballistic_arc(start, above, 10., 10.), Some((up, up)));
+            assert_eq!(ballistic_arc(start, above, 9.9, 10.), None);
+            assert_eq!(ballistic_arc(start, below, 10., 10.), Some((-up, up)));
+            assert_eq!(ballistic_arc(start, start, 10., 10.), Some((-up, up)));
+            assert_eq!(ballistic_arc(start, below, 0., 10.), Some((Vec3::zero(), Vec3::zero())));
+            assert_eq!(ballistic_arc(start, above, 0., 10.), None);
+        }
+        #[test]
+        fn zero_gravity() {
+            let start = Vec3::new(0_f32, 0., 0.);
+            let v = Vec3::new(3., 4., 0.);
+            assert_eq!(ballistic_arc(start, Vec3::new(30., 40., 0.), 5., 0.), Some((v, v)));
+            assert_eq!(ballistic_arc(start, Vec3::new(0., -1., 0.), 5., 0.), Some((Vec3::new(0., -5., 0.), Vec3::new(0., -5., 0.))));
+            assert_eq!(ballistic_arc(start, Vec3::new(30., 40., 0.), 0., 0.), None);
+        }
+    }
+
+    mod springs {
+        use super::*;
+
+        // Integrates the spring numerically with RK4, in small steps.
+        fn integrate(position: Vec3<f64>, velocity: Vec3<f64>, target: Vec3<f64>, omega: f64, zeta: f64, dt: f64) -> (Vec3<f64>, Vec3<f64>) {
+            let acceleration = |p: Vec3<f64>, v: Vec3<f64>| -(p - target) * (omega * omega) - v * (2. * zeta * omega);
+            let steps = 10_000;
+            let h = dt / steps as f64;
+            let (mut p, mut v) = (position, velocity);
+            for _ in 0..steps {
+                let (k1p, k1v) = (v, acceleration(p, v));
+                let (k2p, k2v) = (v + k1v * (h / 2.), acceleration(p + k1p * (h / 2.), v + k1v * (h / 2.)));
+                let (k3p, k3v) = (v + k2v * (h / 2.), acceleration(p + k2p * (h / 2.), v + k2v * (h / 2.)));
+                let (k4p, k4v) = (v + k3v * h, acceleration(p + k3p * h, v + k3v * h));
+                p += (k1p + k2p * 2. + k3p * 2. + k4p) * (h / 6.);
+                v += (k1v + k2v * 2. + k3v * 2. + k4v) * (h / 6.);
+            }
+            (p, v)
+        }
+
+        #[test]
+        fn matches_numerical_integration() {
+            let position = Vec3::new(1., -2., 0.5);
+            let velocity = Vec3::new(0., 3., -1.);
+            let target = Vec3::new(4., 0., 0.);
+            for &zeta in &[0., 0.2, 0.999, 1., 1.001, 3.] {
+                for &dt in &[0.01, 0.5, 2.] {
+                    let (p, v) = damped_spring(position, velocity, target, 3., zeta, dt);
+                    let (expected_p, expected_v) = integrate(position, velocity, target, 3., zeta, dt);
+                    assert_relative_eq!(p, expected_p, epsilon = 1e-7);
+                    assert_relative_eq!(v, expected_v, epsilon = 1e-7);
+                }
+            }
+        }
+        #[test]
+        fn steps_compose() {
+            let target = Vec2::new(0_f64, 0.);
+            for &zeta in &[0.1, 1., 2.] {
+                let (p, v) = damped_spring(Vec2::new(1., 1.), Vec2::new(-2., 0.), target, 5., zeta, 0.3);
+                let (p, v) = damped_spring(p, v, target, 5., zeta, 0.3);
+                let (p2, v2) = damped_spring(Vec2::new(1., 1.), Vec2::new(-2., 0.), target, 5., zeta, 0.6);
+                assert_relative_eq!(p, p2, epsilon = 1e-12);
+                assert_relative_eq!(v, v2, epsilon = 1e-12);
+            }
+        }
+        #[test]
+        fn undamped_keeps_energy() {
+            let target = Vec3::zero();
+            let (p, v) = damped_spring(Vec3::unit_x(), Vec3::zero(), target, 2., 0., 1000.);
+            let energy = p.magnitude_squared() * 4. + v.magnitude_squared();
+            assert_relative_eq!(energy, 4., epsilon = 1e-9);
+            assert_eq!(damped_spring(target, Vec3::zero(), target, 2., 0.5, 1.), (target, Vec3::zero()));
+        }
+    }
+
+    #[cfg(feature="std")]
+    mod catenary {
+        use super::*;
+
+        fn polyline_length<V: Copy + core::ops::Sub<Output=V>>(points: &[V], magnitude: impl Fn(V) -> f64) -> f64 {
+            points.windows(2).map(|w| magnitude(w[1] - w[0])).sum()
+        }
+        // Checks the endpoints, the length, and that the cable hangs (it is convex).
+        fn check(a: Vec2<f64>, b: Vec2<f64>, length: f64) {
+            let points = catenary_2d(a, b, length, 2001).unwrap();
+            assert_eq!((points[0], points[2000]), (a, b));
+            // Chords are shorter than the arcs they span, most of all where the cable bends sharply.
+            assert_relative_eq!(polyline_length(&points, |d| d.magnitude()), length, max_relative = 1e-4);
+            // Going from left to right, the cable turns counter-clockwise.
+            let winding = (b.x - a.x).signum();
+            for w in points.windows(3) {
+                let (d0, d1) = (w[1] - w[0], w[2] - w[1]);
+                assert!((d0.x * d1.y - d0.y * d1.x) * winding >= -1e-9 * length * length, "{:?} {:?} {}", a, b, length);
+            }
+            // Samples are evenly spaced along the cable, so no chord is longer than the spacing.
+            let spacing = length / 2000.;
+            assert!(points.windows(2).all(|w| w[0].distance(w[1]) <= spacing * (1. + 1e-9)));
+        }
+
+        #[test]
+        fn lengths_and_endpoints() {
+            for &(a, b) in &[
+                (Vec2::new(0., 0.), Vec2::new(4., 0.)),
+                (Vec2::new(-2., 3.), Vec2::new(5., -1.)),
+                (Vec2::new(10., 1.), Vec2::new(7., 4.)),
+                (Vec2::new(0., 0.), Vec2::new(1e-3, 100.)),
+            ] {
+                let distance = a.distance(b);
+                for &factor in &[1. + 1e-12, 1. + 1e-8, 1.001, 1.1, 2., 10., 100.] {
+                    check(a, b, distance * factor);
+                }
+                assert_eq!(catenary_2d(a, b, distance * (1. - 1e-12), 10), None);
+            }
+        }
+        #[test]
+        fn nearly_taut() {
+            let (a, b) = (Vec2::new(0., 0.), Vec2::new(10., 0.));
+            let mut previous_sag = 0.;
+            for &slack in &[1e-14, 1e-12, 1e-9, 1e-6, 1e-3] {
+                let length = 10. * (1. + slack);
+                let points = catenary_2d(a, b, length, 3).unwrap();
+                // For a shallow cable, the sag is sqrt(3 * span * extra length / 8).
+                let sag = -points[1].y;
+                assert_relative_eq!(sag, (3. * 10. * (length - 10.) / 8.).sqrt(), max_relative = 1e-3);
+                assert!(sag > previous_sag);
+                assert_relative_eq!(points[1].x, 5., epsilon = 1e-9);
+                previous_sag = sag;
+            }
+            // Exactly taut: a straight line.
+            let points = catenary_2d(a, b, 10., 11).unwrap();
+            for (i, p) in points.iter().enumerate() {
+                assert_eq!(*p, Vec2::new(i as f64, 0.));
+            }
+        }
+        #[test]
+        fn vertical() {
+            let (a, b) = (Vec2::new(1., 0.), Vec2::new(1., -1.));
+            let points = catenary_2d(a, b, 3., 7).unwrap();
+            let ys: Vec<f64> = points.iter().map(|p| p.y).collect();
+            assert_eq!(ys, [0., -0.5, -1., -1.5, -2., -1.5, -1.]);
+            assert!(points.iter().all(|p| p.x == 1.));
+            // Exactly as long as the distance.
+            let points = catenary_2d(b, a, 1., 3).unwrap();
+            assert_eq!(points, [b, Vec2::new(1., -0.5), a]);
+            // Both ends at the same point.
+            let points = catenary_2d(a, a, 2., 5).unwrap();
+            assert_eq!(points.iter().map(|p| p.y).collect::<Vec<_>>(), [0., -0.5, -1., -0.5, 0.]);
+        }
+        #[test]
+        fn in_vertical_plane() {
+            let (a, b) = (Vec3::new(1., 2., 3.), Vec3::new(-3., 0., 6.));
+            let points = catenary_3d(a, b, 9., 1001).unwrap();
+            assert_eq!((points[0], points[1000]), (a, b));
+            assert_relative_eq!(polyline_length(&points, |d| d.magnitude()), 9., max_relative = 1e-6);
+            // The plane contains the Y axis and the horizontal direction from a to b.
+            let normal = Vec3::new(-4., 0., 3.).cross(Vec3::unit_y());
+            for p in &points {
+                assert_relative_eq!((*p - a).dot(normal), 0., epsilon = 1e-9);
+                assert!(p.y <= 2.);
+            }
+            // Vertical.
+            let points = catenary_3d(a, a - Vec3::unit_y(), 3., 7).unwrap();
+            assert_eq!(points[4], a - Vec3::unit_y() * 2.);
+            assert_eq!(catenary_3d(a, b, 5., 10), None);
+        }
+        #[test]
+        #[should_panic]
+        fn single_sample() {
+            catenary_2d(Vec2::new(0_f32, 0.), Vec2::new(1., 0.), 2., 1);
+        }
+    }
+}
@@ -0,0 +1,808 @@
+//! Compile-time layout information for the `#[repr(C)]` types of this crate.
+//!
+//! FFI and GPU code (e.g vertex buffers and uniform blocks) relies on the exact layout of
+//! `vek`'s types. This module exposes their sizes and alignments as constants, as well as the
+//! byte offsets of their elements, so that such code can refer to them instead of hardcoding
+//! values. The crate's own tests assert all of these at compile time, so that a layout change
+//! is caught there rather than in a user's renderer.
+//!
+//! The documented layout is that of a C struct whose fields are all of the element type:
+//! elements are contiguous, in declaration order, without padding; the size is the number of
+//! elements times the size of the element type, and the alignment is that of the element type.
+//! Matrices (either row-major or column-major, which share the same layout) are arrays of their
+//! rows or columns.
+//!
+//! Only `#[repr(C)]` types are covered; the layout of `#[repr(simd)]` types depends on the target.
+//!
+//! Building on these guarantees, `interleave2()`, `interleave3()` and their `deinterleave`
+//! counterparts convert between separate attribute streams (e.g positions, normals and texture
+//! coordinates) and packed, interleaved vertex buffers, whose layout is given by `InterleavedLayout`.
+//!
+//! ```
+//! use vek::layout::*;
+//! use vek::Vec3;
+//!
+//! assert_eq!(VEC3_F32_SIZE, 12);
+//! assert_eq!(VEC3_F32_ALIGN, 4);
+//! const Z: usize = offset_of_z::<Vec3<f32>>();
+//! assert_eq!(Z, 8);
+//! ```
+
+use std::mem::{size_of, align_of};
+use std::slice;
+#[cfg(feature="std")]
+use alloc::vec::Vec;
+use crate::vec::repr_c::*;
+use crate::mat::repr_c::{Mat2, Mat3, Mat4};
+use crate::quaternion::repr_c::Quaternion;
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// A `#[repr(C)]` type whose elements are laid out contiguously, in declaration order,
+/// without padding.
+///
+/// This trait is implemented by all `#[repr(C)]` vector types and `Quaternion`, and can't be
+/// implemented outside of this crate. See `offset_of_element()`.
+pub trait VectorLayout: private::Sealed {
+    /// The type of each element.
+    type Element;
+    /// The number of elements.
+    const ELEMENT_COUNT: usize;
+}
+
+macro_rules! impl_vector_layout {
+    ($Vec:ident $n:expr) => {
+        impl<T> private::Sealed for $Vec<T> {}
+        impl<T> VectorLayout for $Vec<T> {
+            type Element = T;
+            const ELEMENT_COUNT: usize = $n;
+        }
+    };
+}
+
+impl_vector_layout!{Vec2 2}
+impl_vector_layout!{Vec3 3}
+impl_vector_layout!{Vec4 4}
+#[cfg(feature="vec8")] impl_vector_layout!{Vec8 8}
+#[cfg(feature="vec16")] impl_vector_layout!{Vec16 16}
+#[cfg(feature="vec32")] impl_vector_layout!{Vec32 32}
+#[cfg(feature="vec64")] impl_vector_layout!{Vec64 64}
+impl_vector_layout!{Extent2 2}
+impl_vector_layout!{Extent3 3}
+#[cfg(feature="rgb")] impl_vector_layout!{Rgb 3}
+#[cfg(feature="rgba")] impl_vector_layout!{Rgba 4}
+#[cfg(feature="uv")] impl_vector_layout!{Uv 2}
+#[cfg(feature="uvw")] impl_vector_layout!{Uvw 3}
+impl_vector_layout!{Quaternion 4}
+
+/// Gets the offset, in bytes, of the element at `index` (in declaration order) in `V`.
+///
+/// # Panics
+/// Panics (at compile time, in a constant context) if `index` is not less than `V::ELEMENT_COUNT`.
+///
+/// ```
+/// use vek::{Rgba, layout::offset_of_element};
+/// assert_eq!(offset_of_element::<Rgba<u16>>(3), 6);
+/// ```
+pub const fn offset_of_element<V: VectorLayout>(index: usize) -> usize {
+    assert!(index < V::ELEMENT_COUNT, "Element index out of bounds");
+    index * size_of::<V::Element>()
+}
+/// Gets the offset, in bytes, of the first element in `V` (`x` for vectors and quaternions).
+pub const fn offset_of_x<V: VectorLayout>() -> usize {
+    offset_of_element::<V>(0)
+}
+/// Gets the offset, in bytes, of the second element in `V` (`y` for vectors and quaternions).
+pub const fn offset_of_y<V: VectorLayout>() -> usize {
+    offset_of_element::<V>(1)
+}
+/// Gets the offset, in bytes, of the third element in `V` (`z` for vectors and quaternions).
+pub const fn offset_of_z<V: VectorLayout>() -> usize {
+    offset_of_element::<V>(2)
+}
+/// Gets the offset, in bytes, of the fourth element in `V` (`w` for vectors and quaternions).
+pub const fn offset_of_w<V: VectorLayout>() -> usize {
+    offset_of_element::<V>(3)
+}
+
+/// A primitive element type, for which any bit pattern is a valid value.
+///
+/// This trait is implemented by all primitive integer and floating-point types, and can't be
+/// implemented outside of this crate. Vectors of such elements can be read from arbitrary bytes,
+/// which is what `deinterleave2()` and `deinterleave3()` rely on.
+pub trait PlainElement: private::Sealed + Copy {}
+
+macro_rules! impl_plain_element {
+    ($($T:ty)+) => {
+        $(
+            impl private::Sealed for $T {}
+            impl PlainElement for $T {}
+        )+
+    };
+}
+
+impl_plain_element!{i8 u8 i16 u16 i32 u32 i64 u64 isize usize f32 f64}
+
+/// The layout of a packed, interleaved vertex buffer, as computed by `InterleavedLayout::new()`.
+///
+/// Attributes are packed without padding, so vertices are `stride` bytes apart and each of them
+/// starts at a multiple of `stride`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InterleavedLayout<const N: usize> {
+    /// The size, in bytes, of a single vertex.
+    pub stride: usize,
+    /// The offset, in bytes, of each attribute within a vertex.
+    ///
+    /// These are indexed like the attribute streams (i.e the arguments of `interleave3()`),
+    /// not by their position within a vertex.
+    pub offsets: [usize; N],
+}
+
+impl<const N: usize> InterleavedLayout<N> {
+    /// Computes the layout of attributes of the given sizes, packed in the given order.
+    ///
+    /// `order` lists attribute indices (into `sizes`) in the order in which they are stored
+    /// within a vertex, e.g `[2, 0, 1]` stores attribute 2 first.
+    ///
+    /// # Panics
+    /// Panics (at compile time, in a constant context) if `order` is not a permutation of `0..N`.
+    ///
+    /// ```
+    /// use vek::layout::InterleavedLayout;
+    /// const LAYOUT: InterleavedLayout<3> = InterleavedLayout::new([12, 12, 8], [2, 0, 1]);
+    /// assert_eq!(LAYOUT.stride, 32);
+    /// assert_eq!(LAYOUT.offsets, [8, 20, 0]);
+    /// ```
+    pub const fn new(sizes: [usize; N], order: [usize; N]) -> Self {
+        let mut offsets = [0; N];
+        let mut seen = [false; N];
+        let mut stride = 0;
+        let mut i = 0;
+        while i < N {
+            let attribute = order[i];
+            assert!(attribute < N && !seen[attribute], "The attribute order must be a permutation of the attribute indices");
+            seen[attribute] = true;
+            offsets[attribute] = stride;
+            stride += sizes[attribute];
+            i += 1;
+        }
+        Self { stride, offsets }
+    }
+}
+
+/// Computes the layout of vertices made of an `A` and a `B` attribute, packed in the given order.
+///
+/// See `InterleavedLayout::new()`.
+pub const fn interleaved_layout2<A: VectorLayout, B: VectorLayout>(order: [usize; 2]) -> InterleavedLayout<2> {
+    InterleavedLayout::new([size_of::<A>(), size_of::<B>()], order)
+}
+/// Computes the layout of vertices made of an `A`, a `B` and a `C` attribute, packed in the given order.
+///
+/// See `InterleavedLayout::new()`.
+///
+/// ```
+/// use vek::{Vec2, Vec3, layout::*};
+/// let layout = interleaved_layout3::<Vec3<f32>, Vec3<f32>, Vec2<f32>>([0, 1, 2]);
+/// assert_eq!(layout.stride, 2 * VEC3_F32_SIZE + VEC2_F32_SIZE);
+/// assert_eq!(layout.offsets, [0, VEC3_F32_SIZE, 2 * VEC3_F32_SIZE]);
+/// ```
+pub const fn interleaved_layout3<A: VectorLayout, B: VectorLayout, C: VectorLayout>(order: [usize; 3]) -> InterleavedLayout<3> {
+    InterleavedLayout::new([size_of::<A>(), size_of::<B>(), size_of::<C>()], order)
+}
+
+// Copies `value`'s bytes to the start of `bytes`.
+fn write_attribute<V>(value: &V, bytes: &mut [u8]) where V: VectorLayout + Copy, V::Element: PlainElement {
+    // SAFETY: `V`'s elements are plain and contiguous, without padding, so all of its bytes are initialized.
+    let src = unsafe { slice::from_raw_parts(value as *const V as *const u8, size_of::<V>()) };
+    bytes[..size_of::<V>()].copy_from_slice(src);
+}
+// Reads a `V` from the start of `bytes`.
+#[cfg(feature="std")]
+fn read_attribute<V>(bytes: &[u8]) -> V where V: VectorLayout + Copy, V::Element: PlainElement {
+    let bytes = &bytes[..size_of::<V>()];
+    // SAFETY: `bytes` is large enough, and any bit pattern is a valid `V` since its elements are plain.
+    unsafe { std::ptr::read_unaligned(bytes.as_ptr() as *const V) }
+}
+
+/// Writes the `a` and `b` attribute streams as packed, interleaved vertices into `bytes`,
+/// returning the number of bytes written.
+///
+/// Elements are written in native endianness, as expected by GPU APIs.
+/// See `interleaved_layout2()` for `order`.
+///
+/// # Panics
+/// Panics if `a` and `b` have different lengths, if `bytes` is too small, or if `order` is invalid.
+pub fn interleave2_into<A, B>(a: &[A], b: &[B], order: [usize; 2], bytes: &mut [u8]) -> usize
+    where A: VectorLayout + Copy, A::Element: PlainElement,
+          B: VectorLayout + Copy, B::Element: PlainElement,
+{
+    assert_eq!(a.len(), b.len(), "All attribute streams must have the same length");
+    let layout = interleaved_layout2::<A, B>(order);
+    let len = a.len() * layout.stride;
+    for (i, vertex) in bytes[..len].chunks_exact_mut(layout.stride).enumerate() {
+        write_attribute(&a[i], &mut vertex[layout.offsets[0]..]);
+        write_attribute(&b[i], &mut vertex[layout.offsets[1]..]);
+    }
+    len
+}
+/// Writes the `a`, `b` and `c` attribute streams as packed, interleaved vertices into `bytes`,
+/// returning the number of bytes written.
+///
+/// Elements are written in native endianness, as expected by GPU APIs.
+/// See `interleaved_layout3()` for `order`.
+///
+/// # Panics
+/// Panics if the streams have different lengths, if `bytes` is too small, or if `order` is invalid.
+pub fn interleave3_into<A, B, C>(a: &[A], b: &[B], c: &[C], order: [usize; 3], bytes: &mut [u8]) -> usize
+    where A: VectorLayout + Copy, A::Element: PlainElement,
+          B: VectorLayout + Copy, B::Element: PlainElement,
+          C: VectorLayout + Copy, C::Element: PlainElement,
+{
+    assert!(a.len() == b.len() && a.len() == c.len(), "All attribute streams must have the same length");
+    let layout = interleaved_layout3::<A, B, C>(order);
+    let len = a.len() * layout.stride;
+    for (i, vertex) in bytes[..len].chunks_exact_mut(layout.stride).enumerate() {
+        write_attribute(&a[i], &mut vertex[layout.offsets[0]..]);
+        write_attribute(&b[i], &mut vertex[layout.offsets[1]..]);
+        write_attribute(&c[i], &mut vertex[layout.offsets[2]..]);
+    }
+    len
+}
+
+/// Packs the `a` and `b` attribute streams into a new interleaved vertex buffer.
+///
+/// See `interleave2_into()`.
+#[cfg(feature="std")]
+pub fn interleave2<A, B>(a: &[A], b: &[B], order: [usize; 2]) -> Vec<u8>
+    where A: VectorLayout + Copy, A::Element: PlainElement,
+          B: VectorLayout + Copy, B::Element: PlainElement,
+{
+    let mut bytes = alloc::vec![0; a.len() * interleaved_layout2::<A, B>(order).stride];
+    interleave2_into(a, b, order, &mut bytes);
+    bytes
+}
+/// Packs the `a`, `b` and `c` attribute streams into a new interleaved vertex buffer.
+///
+/// See `interleave3_into()`.
+///
+/// ```
+/// use vek::{Vec2, Vec3, layout::*};
+///
+/// let positions = [Vec3::new(0_f32, 0., 0.), Vec3::new(1., 0., 0.)];
+/// let normals = [Vec3::<f32>::unit_z(); 2];
+/// let uvs = [Vec2::new(0_f32, 0.), Vec2::new(1., 0.)];
+///
+/// // Stores texture coordinates first.
+/// let order = [2, 0, 1];
+/// let bytes = interleave3(&positions, &normals, &uvs, order);
+/// let layout = interleaved_layout3::<Vec3<f32>, Vec3<f32>, Vec2<f32>>(order);
+/// assert_eq!(bytes.len(), 2 * layout.stride);
+/// let x = layout.stride + layout.offsets[0];
+/// assert_eq!(bytes[x..x+4], 1_f32.to_ne_bytes());
+///
+/// let (p, n, t) = deinterleave3::<Vec3<f32>, Vec3<f32>, Vec2<f32>>(&bytes, order);
+/// assert_eq!((&p[..], &n[..], &t[..]), (&positions[..], &normals[..], &uvs[..]));
+/// ```
+#[cfg(feature="std")]
+pub fn interleave3<A, B, C>(a: &[A], b: &[B], c: &[C], order: [usize; 3]) -> Vec<u8>
+    where A: VectorLayout + Copy, A::Element: PlainElement,
+          B: VectorLayout + Copy, B::Element: PlainElement,
+          C: VectorLayout + Copy, C::Element: PlainElement,
+{
+    let mut bytes = alloc::vec![0; a.len() * interleaved_layout3::<A, B, C>(order).stride];
+    interleave3_into(a, b, c, order, &mut bytes);
+    bytes
+}
+
+/// Reads a packed, interleaved vertex buffer (e.g written by `interleave2()`) back into
+/// separate attribute streams.
+///
+/// `order` must be the one the buffer was written with.
+///
+/// # Panics
+/// Panics if the length of `bytes` is not a multiple of the vertex size, or if `order` is invalid.
+#[cfg(feature="std")]
+pub fn deinterleave2<A, B>(bytes: &[u8], order: [usize; 2]) -> (Vec<A>, Vec<B>)
+    where A: VectorLayout + Copy, A::Element: PlainElement,
+          B: VectorLayout + Copy, B::Element: PlainElement,
+{
+    let layout = interleaved_layout2::<A, B>(order);
+    assert_eq!(bytes.len() % layout.stride, 0, "The buffer's length must be a multiple of the vertex size");
+    let count = bytes.len() / layout.stride;
+    let (mut a, mut b) = (Vec::with_capacity(count), Vec::with_capacity(count));
+    for vertex in bytes.chunks_exact(layout.stride) {
+        a.push(read_attribute(&vertex[layout.offsets[0]..]));
+        b.push(read_attribute(&vertex[layout.offsets[1]..]));
+    }
+    (a, b)
+}
+/// Reads a packed, interleaved vertex buffer (e.g written by `interleave3()`) back into
+/// separate attribute streams.
+///
+/// `order` must be the one the buffer was written with.
+///
+/// # Panics
+/// Panics if the length of `bytes` is not a multiple of the vertex size, or if `order` is invalid.
+#[cfg(feature="std")]
+pub fn deinterleave3<A, B, C>(bytes: &[u8], order: [usize; 3]) -> (Vec<A>, Vec<B>, Vec<C>)
+    where A: VectorLayout + Copy, A::Element: PlainElement,
+          B: VectorLayout + Copy, B::Element: PlainElement,
+          C: VectorLayout + Copy, C::Element: PlainElement,
+{
+    let layout = interleaved_layout3::<A, B, C>(order);
+    assert_eq!(bytes.len() % layout.stride, 0, "The buffer's length must be a multiple of the vertex size");
+    let count = bytes.len() / layout.stride;
+    let (mut a, mut b, mut c) = (Vec::with_capacity(count), Vec::with_capacity(count), Vec::with_capacity(count));
+    for vertex in bytes.chunks_exact(layout.stride) {
+        a.push(read_attribute(&vertex[layout.offsets[0]..]));
+        b.push(read_attribute(&vertex[layout.offsets[1]..]));
+        c.push(read_attribute(&vertex[layout.offsets[2]..]));
+    }
+    (a, b, c)
+}
+
+macro_rules! layout_consts {
+    ($($(#[$attrs:meta])* $SIZE:ident $ALIGN:ident = $T:ty;)+) => {
+        $(
+            $(#[$attrs])*
+            #[doc = concat!("Size of `", stringify!($T), "`, in bytes.")]
+            pub const $SIZE: usize = size_of::<$T>();
+            $(#[$attrs])*
+            #[doc = concat!("Alignment of `", stringify!($T), "`, in bytes.")]
+            pub const $ALIGN: usize = align_of::<$T>();
+        )+
+    };
+}
+
+layout_consts!{
+    // Vec2
+    VEC2_I8_SIZE VEC2_I8_ALIGN = Vec2<i8>;
+    VEC2_U8_SIZE VEC2_U8_ALIGN = Vec2<u8>;
+    VEC2_I16_SIZE VEC2_I16_ALIGN = Vec2<i16>;
+    VEC2_U16_SIZE VEC2_U16_ALIGN = Vec2<u16>;
+    VEC2_I32_SIZE VEC2_I32_ALIGN = Vec2<i32>;
+    VEC2_U32_SIZE VEC2_U32_ALIGN = Vec2<u32>;
+    VEC2_I64_SIZE VEC2_I64_ALIGN = Vec2<i64>;
+    VEC2_U64_SIZE VEC2_U64_ALIGN = Vec2<u64>;
+    VEC2_F32_SIZE VEC2_F32_ALIGN = Vec2<f32>;
+    VEC2_F64_SIZE VEC2_F64_ALIGN = Vec2<f64>;
+    // Vec3
+    VEC3_I8_SIZE VEC3_I8_ALIGN = Vec3<i8>;
+    VEC3_U8_SIZE VEC3_U8_ALIGN = Vec3<u8>;
+    VEC3_I16_SIZE VEC3_I16_ALIGN = Vec3<i16>;
+    VEC3_U16_SIZE VEC3_U16_ALIGN = Vec3<u16>;
+    VEC3_I32_SIZE VEC3_I32_ALIGN = Vec3<i32>;
+    VEC3_U32_SIZE VEC3_U32_ALIGN = Vec3<u32>;
+    VEC3_I64_SIZE VEC3_I64_ALIGN = Vec3<i64>;
+    VEC3_U64_SIZE VEC3_U64_ALIGN = Vec3<u64>;
+    VEC3_F32_SIZE VEC3_F32_ALIGN = Vec3<f32>;
+    VEC3_F64_SIZE VEC3_F64_ALIGN = Vec3<f64>;
+    // Vec4
+    VEC4_I8_SIZE VEC4_I8_ALIGN = Vec4<i8>;
+    VEC4_U8_SIZE VEC4_U8_ALIGN = Vec4<u8>;
+    VEC4_I16_SIZE VEC4_I16_ALIGN = Vec4<i16>;
+    VEC4_U16_SIZE VEC4_U16_ALIGN = Vec4<u16>;
+    VEC4_I32_SIZE VEC4_I32_ALIGN = Vec4<i32>;
+    VEC4_U32_SIZE VEC4_U32_ALIGN = Vec4<u32>;
+    VEC4_I64_SIZE VEC4_I64_ALIGN = Vec4<i64>;
+    VEC4_U64_SIZE VEC4_U64_ALIGN = Vec4<u64>;
+    VEC4_F32_SIZE VEC4_F32_ALIGN = Vec4<f32>;
+    VEC4_F64_SIZE VEC4_F64_ALIGN = Vec4<f64>;
+    // Vec8
+    #[cfg(feature="vec8")] VEC8_I8_SIZE VEC8_I8_ALIGN = Vec8<i8>;
+    #[cfg(feature="vec8")] VEC8_U8_SIZE VEC8_U8_ALIGN = Vec8<u8>;
+    #[cfg(feature="vec8")] VEC8_I16_SIZE VEC8_I16_ALIGN = Vec8<i16>;
+    #[cfg(feature="vec8")] VEC8_U16_SIZE VEC8_U16_ALIGN = Vec8<u16>;
+    #[cfg(feature="vec8")] VEC8_I32_SIZE VEC8_I32_ALIGN = Vec8<i32>;
+    #[cfg(feature="vec8")] VEC8_U32_SIZE VEC8_U32_ALIGN = Vec8<u32>;
+    #[cfg(feature="vec8")] VEC8_I64_SIZE VEC8_I64_ALIGN = Vec8<i64>;
+    #[cfg(feature="vec8")] VEC8_U64_SIZE VEC8_U64_ALIGN = Vec8<u64>;
+    #[cfg(feature="vec8")] VEC8_F32_SIZE VEC8_F32_ALIGN = Vec8<f32>;
+    #[cfg(feature="vec8")] VEC8_F64_SIZE VEC8_F64_ALIGN = Vec8<f64>;
+    // Vec16
+    #[cfg(feature="vec16")] VEC16_I8_SIZE VEC16_I8_ALIGN = Vec16<i8>;
+    #[cfg(feature="vec16")] VEC16_U8_SIZE VEC16_U8_ALIGN = Vec16<u8>;
+    #[cfg(feature="vec16")] VEC16_I16_SIZE VEC16_I16_ALIGN = Vec16<i16>;
+    #[cfg(feature="vec16")] VEC16_U16_SIZE VEC16_U16_ALIGN = Vec16<u16>;
+    #[cfg(feature="vec16")] VEC16_I32_SIZE VEC16_I32_ALIGN = Vec16<i32>;
+    #[cfg(feature="vec16")] VEC16_U32_SIZE VEC16_U32_ALIGN = Vec16<u32>;
+    #[cfg(feature="vec16")] VEC16_I64_SIZE VEC16_I64_ALIGN = Vec16<i64>;
+    #[cfg(feature="vec16")] VEC16_U64_SIZE VEC16_U64_ALIGN = Vec16<u64>;
+    #[cfg(feature="vec16")] VEC16_F32_SIZE VEC16_F32_ALIGN = Vec16<f32>;
+    #[cfg(feature="vec16")] VEC16_F64_SIZE VEC16_F64_ALIGN = Vec16<f64>;
+    // Vec32
+    #[cfg(feature="vec32")] VEC32_I8_SIZE VEC32_I8_ALIGN = Vec32<i8>;
+    #[cfg(feature="vec32")] VEC32_U8_SIZE VEC32_U8_ALIGN = Vec32<u8>;
+    #[cfg(feature="vec32")] VEC32_I16_SIZE VEC32_I16_ALIGN = Vec32<i16>;
+    #[cfg(feature="vec32")] VEC32_U16_SIZE VEC32_U16_ALIGN = Vec32<u16>;
+    #[cfg(feature="vec32")] VEC32_I32_SIZE VEC32_I32_ALIGN = Vec32<i32>;
+    #[cfg(feature="vec32")] VEC32_U32_SIZE VEC32_U32_ALIGN = Vec32<u32>;
+    #[cfg(feature="vec32")] VEC32_I64_SIZE VEC32_I64_ALIGN = Vec32<i64>;
+    #[cfg(feature="vec32")] VEC32_U64_SIZE VEC32_U64_ALIGN = Vec32<u64>;
+    #[cfg(feature="vec32")] VEC32_F32_SIZE VEC32_F32_ALIGN = Vec32<f32>;
+    #[cfg(feature="vec32")] VEC32_F64_SIZE VEC32_F64_ALIGN = Vec32<f64>;
+    // Vec64
+    #[cfg(feature="vec64")] VEC64_I8_SIZE VEC64_I8_ALIGN = Vec64<i8>;
+    #[cfg(feature="vec64")] VEC64_U8_SIZE VEC64_U8_ALIGN = Vec64<u8>;
+    #[cfg(feature="vec64")] VEC64_I16_SIZE VEC64_I16_ALIGN = Vec64<i16>;
+    #[cfg(feature="vec64")] VEC64_U16_SIZE VEC64_U16_ALIGN = Vec64<u16>;
+    #[cfg(feature="vec64")] VEC64_I32_SIZE VEC64_I32_ALIGN = Vec64<i32>;
+    #[cfg(feature="vec64")] VEC64_U32_SIZE VEC64_U32_ALIGN = Vec64<u32>;
+    #[cfg(feature="vec64")] VEC64_I64_SIZE VEC64_I64_ALIGN = Vec64<i64>;
+    #[cfg(feature="vec64")] VEC64_U64_SIZE VEC64_U64_ALIGN = Vec64<u64>;
+    #[cfg(feature="vec64")] VEC64_F32_SIZE VEC64_F32_ALIGN = Vec64<f32>;
+    #[cfg(feature="vec64")] VEC64_F64_SIZE VEC64_F64_ALIGN = Vec64<f64>;
+    // Extent2
+    EXTENT2_I8_SIZE EXTENT2_I8_ALIGN = Extent2<i8>;
+    EXTENT2_U8_SIZE EXTENT2_U8_ALIGN = Extent2<u8>;
+    EXTENT2_I16_SIZE EXTENT2_I16_ALIGN = Extent2<i16>;
+    EXTENT2_U16_SIZE EXTENT2_U16_ALIGN = Extent2<u16>;
+    EXTENT2_I32_SIZE EXTENT2_I32_ALIGN = Extent2<i32>;
+    EXTENT2_U32_SIZE EXTENT2_U32_ALIGN = Extent2<u32>;
+    EXTENT2_I64_SIZE EXTENT2_I64_ALIGN = Extent2<i64>;
+    EXTENT2_U64_SIZE EXTENT2_U64_ALIGN = Extent2<u64>;
+    EXTENT2_F32_SIZE EXTENT2_F32_ALIGN = Extent2<f32>;
+    EXTENT2_F64_SIZE EXTENT2_F64_ALIGN = Extent2<f64>;
+    // Extent3
+    EXTENT3_I8_SIZE EXTENT3_I8_ALIGN = Extent3<i8>;
+    EXTENT3_U8_SIZE EXTENT3_U8_ALIGN = Extent3<u8>;
+    EXTENT3_I16_SIZE EXTENT3_I16_ALIGN = Extent3<i16>;
+    EXTENT3_U16_SIZE EXTENT3_U16_ALIGN = Extent3<u16>;
+    EXTENT3_I32_SIZE EXTENT3_I32_ALIGN = Extent3<i32>;
+    EXTENT3_U32_SIZE EXTENT3_U32_ALIGN = Extent3<u32>;
+    EXTENT3_I64_SIZE EXTENT3_I64_ALIGN = Extent3<i64>;
+    EXTENT3_U64_SIZE EXTENT3_U64_ALIGN = Extent3<u64>;
+    EXTENT3_F32_SIZE EXTENT3_F32_ALIGN = Extent3<f32>;
+    EXTENT3_F64_SIZE EXTENT3_F64_ALIGN = Extent3<f64>;
+    // Rgb
+    #[cfg(feature="rgb")] RGB_I8_SIZE RGB_I8_ALIGN = Rgb<i8>;
+    #[cfg(feature="rgb")] RGB_U8_SIZE RGB_U8_ALIGN = Rgb<u8>;
+    #[cfg(feature="rgb")] RGB_I16_SIZE RGB_I16_ALIGN = Rgb<i16>;
+    #[cfg(feature="rgb")] RGB_U16_SIZE RGB_U16_ALIGN = Rgb<u16>;
+    #[cfg(feature="rgb")] RGB_I32_SIZE RGB_I32_ALIGN = Rgb<i32>;
+    #[cfg(feature="rgb")] RGB_U32_SIZE RGB_U32_ALIGN = Rgb<u32>;
+    #[cfg(feature="rgb")] RGB_I64_SIZE RGB_I64_ALIGN = Rgb<i64>;
+    #[cfg(feature="rgb")] RGB_U64_SIZE RGB_U64_ALIGN = Rgb<u64>;
+    #[cfg(feature="rgb")] RGB_F32_SIZE RGB_F32_ALIGN = Rgb<f32>;
+    #[cfg(feature="rgb")] RGB_F64_SIZE RGB_F64_ALIGN = Rgb<f64>;
+    // Rgba
+    #[cfg(feature="rgba")] RGBA_I8_SIZE RGBA_I8_ALIGN = Rgba<i8>;
+    #[cfg(feature="rgba")] RGBA_U8_SIZE RGBA_U8_ALIGN = Rgba<u8>;
+    #[cfg(feature="rgba")] RGBA_I16_SIZE RGBA_I16_ALIGN = Rgba<i16>;
+    #[cfg(feature="rgba")] RGBA_U16_SIZE RGBA_U16_ALIGN = Rgba<u16>;
+    #[cfg(feature="rgba")] RGBA_I32_SIZE RGBA_I32_ALIGN = Rgba<i32>;
+    #[cfg(feature="rgba")] RGBA_U32_SIZE RGBA_U32_ALIGN = Rgba<u32>;
+    #[cfg(feature="rgba")] RGBA_I64_SIZE RGBA_I64_ALIGN = Rgba<i64>;
+    #[cfg(feature="rgba")] RGBA_U64_SIZE RGBA_U64_ALIGN = Rgba<u64>;
+    #[cfg(feature="rgba")] RGBA_F32_SIZE RGBA_F32_ALIGN = Rgba<f32>;
+    #[cfg(feature="rgba")] RGBA_F64_SIZE RGBA_F64_ALIGN = Rgba<f64>;
+    // Uv
+    #[cfg(feature="uv")] UV_I8_SIZE UV_I8_ALIGN = Uv<i8>;
+    #[cfg(feature="uv")] UV_U8_SIZE UV_U8_ALIGN = Uv<u8>;
+    #[cfg(feature="uv")] UV_I16_SIZE UV_I16_ALIGN = Uv<i16>;
+    #[cfg(feature="uv")] UV_U16_SIZE UV_U16_ALIGN = Uv<u16>;
+    #[cfg(feature="uv")] UV_I32_SIZE UV_I32_ALIGN = Uv<i32>;
+    #[cfg(feature="uv")] UV_U32_SIZE UV_U32_ALIGN = Uv<u32>;
+    #[cfg(feature="uv")] UV_I64_SIZE UV_I64_ALIGN = Uv<i64>;
+    #[cfg(feature="uv")] UV_U64_SIZE UV_U64_ALIGN = Uv<u64>;
+    #[cfg(feature="uv")] UV_F32_SIZE UV_F32_ALIGN = Uv<f32>;
+    #[cfg(feature="uv")] UV_F64_SIZE UV_F64_ALIGN = Uv<f64>;
+    // Uvw
+    #[cfg(feature="uvw")] UVW_I8_SIZE UVW_I8_ALIGN = Uvw<i8>;
+    #[cfg(feature="uvw")] UVW_U8_SIZE UVW_U8_ALIGN = Uvw<u8>;
+    #[cfg(feature="uvw")] UVW_I16_SIZE UVW_I16_ALIGN = Uvw<i16>;
+    #[cfg(feature="uvw")] UVW_U16_SIZE UVW_U16_ALIGN = Uvw<u16>;
+    #[cfg(feature="uvw")] UVW_I32_SIZE UVW_I32_ALIGN = Uvw<i32>;
+    #[cfg(feature="uvw")] UVW_U32_SIZE UVW_U32_ALIGN = Uvw<u32>;
+    #[cfg(feature="uvw")] UVW_I64_SIZE UVW_I64_ALIGN = Uvw<i64>;
+    #[cfg(feature="uvw")] UVW_U64_SIZE UVW_U64_ALIGN = Uvw<u64>;
+    #[cfg(feature="uvw")] UVW_F32_SIZE UVW_F32_ALIGN = Uvw<f32>;
+    #[cfg(feature="uvw")] UVW_F64_SIZE UVW_F64_ALIGN = Uvw<f64>;
+    // Mat2
+    MAT2_F32_SIZE MAT2_F32_ALIGN = Mat2<f32>;
+    MAT2_F64_SIZE MAT2_F64_ALIGN = Mat2<f64>;
+    MAT2_I32_SIZE MAT2_I32_ALIGN = Mat2<i32>;
+    MAT2_U32_SIZE MAT2_U32_ALIGN = Mat2<u32>;
+    // Mat3
+    MAT3_F32_SIZE MAT3_F32_ALIGN = Mat3<f32>;
+    MAT3_F64_SIZE MAT3_F64_ALIGN = Mat3<f64>;
+    MAT3_I32_SIZE MAT3_I32_ALIGN = Mat3<i32>;
+    MAT3_U32_SIZE MAT3_U32_ALIGN = Mat3<u32>;
+    // Mat4
+    MAT4_F32_SIZE MAT4_F32_ALIGN = Mat4<f32>;
+    MAT4_F64_SIZE MAT4_F64_ALIGN = Mat4<f64>;
+    MAT4_I32_SIZE MAT4_I32_ALIGN = Mat4<i32>;
+    MAT4_U32_SIZE MAT4_U32_ALIGN = Mat4<u32>;
+    // Quaternion
+    QUATERNION_F32_SIZE QUATERNION_F32_ALIGN = Quaternion<f32>;
+    QUATERNION_F64_SIZE QUATERNION_F64_ALIGN = Quaternion<f64>;
+    QUATERNION_I32_SIZE QUATERNION_I32_ALIGN = Quaternion<i32>;
+    QUATERNION_U32_SIZE QUATERNION_U32_ALIGN = Quaternion<u32>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! assert_layout {
+        ($SIZE:ident == $size:expr, $ALIGN:ident == $T:ty) => {
+            const_assert_eq!($SIZE, $size);
+            const_assert_eq!($ALIGN, align_of::<$T>());
+        };
+    }
+
+    // Vec2
+    assert_layout!(VEC2_I8_SIZE == 2, VEC2_I8_ALIGN == i8);
+    assert_layout!(VEC2_U8_SIZE == 2, VEC2_U8_ALIGN == u8);
+    assert_layout!(VEC2_I16_SIZE == 4, VEC2_I16_ALIGN == i16);
+    assert_layout!(VEC2_U16_SIZE == 4, VEC2_U16_ALIGN == u16);
+    assert_layout!(VEC2_I32_SIZE == 8, VEC2_I32_ALIGN == i32);
+    assert_layout!(VEC2_U32_SIZE == 8, VEC2_U32_ALIGN == u32);
+    assert_layout!(VEC2_I64_SIZE == 16, VEC2_I64_ALIGN == i64);
+    assert_layout!(VEC2_U64_SIZE == 16, VEC2_U64_ALIGN == u64);
+    assert_layout!(VEC2_F32_SIZE == 8, VEC2_F32_ALIGN == f32);
+    assert_layout!(VEC2_F64_SIZE == 16, VEC2_F64_ALIGN == f64);
+    // Vec3
+    assert_layout!(VEC3_I8_SIZE == 3, VEC3_I8_ALIGN == i8);
+    assert_layout!(VEC3_U8_SIZE == 3, VEC3_U8_ALIGN == u8);
+    assert_layout!(VEC3_I16_SIZE == 6, VEC3_I16_ALIGN == i16);
+    assert_layout!(VEC3_U16_SIZE == 6, VEC3_U16_ALIGN == u16);
+    assert_layout!(VEC3_I32_SIZE == 12, VEC3_I32_ALIGN == i32);
+    assert_layout!(VEC3_U32_SIZE == 12, VEC3_U32_ALIGN == u32);
+    assert_layout!(VEC3_I64_SIZE == 24, VEC3_I64_ALIGN == i64);
+    assert_layout!(VEC3_U64_SIZE == 24, VEC3_U64_ALIGN == u64);
+    assert_layout!(VEC3_F32_SIZE == 12, VEC3_F32_ALIGN == f32);
+    assert_layout!(VEC3_F64_SIZE == 24, VEC3_F64_ALIGN == f64);
+    // Vec4
+    assert_layout!(VEC4_I8_SIZE == 4, VEC4_I8_ALIGN == i8);
+    assert_layout!(VEC4_U8_SIZE == 4, VEC4_U8_ALIGN == u8);
+    assert_layout!(VEC4_I16_SIZE == 8, VEC4_I16_ALIGN == i16);
+    assert_layout!(VEC4_U16_SIZE == 8, VEC4_U16_ALIGN == u16);
+    assert_layout!(VEC4_I32_SIZE == 16, VEC4_I32_ALIGN == i32);
+    assert_layout!(VEC4_U32_SIZE == 16, VEC4_U32_ALIGN == u32);
+    assert_layout!(VEC4_I64_SIZE == 32, VEC4_I64_ALIGN == i64);
+    assert_layout!(VEC4_U64_SIZE == 32, VEC4_U64_ALIGN == u64);
+    assert_layout!(VEC4_F32_SIZE == 16, VEC4_F32_ALIGN == f32);
+    assert_layout!(VEC4_F64_SIZE == 32, VEC4_F64_ALIGN == f64);
+    // Vec8
+    #[cfg(feature="vec8")] assert_layout!(VEC8_I8_SIZE == 8, VEC8_I8_ALIGN == i8);
+    #[cfg(feature="vec8")] assert_layout!(VEC8_U8_SIZE == 8, VEC8_U8_ALIGN == u8);
+    #[cfg(feature="vec8")] assert_layout!(VEC8_I16_SIZE == 16, VEC8_I16_ALIGN == i16);
+    #[cfg(feature="vec8")] assert_layout!(VEC8_U16_SIZE == 16, VEC8_U16_ALIGN == u16);
+    #[cfg(feature="vec8")] assert_layout!(VEC8_I32_SIZE == 32, VEC8_I32_ALIGN == i32);
+    #[cfg(feature="vec8")] assert_layout!(VEC8_U32_SIZE == 32, VEC8_U32_ALIGN == u32);
+    #[cfg(feature="vec8")] assert_layout!(VEC8_I64_SIZE == 64, VEC8_I64_ALIGN == i64);
+    #[cfg(feature="vec8")] assert_layout!(VEC8_U64_SIZE == 64, VEC8_U64_ALIGN == u64);
+    #[cfg(feature="vec8")] assert_layout!(VEC8_F32_SIZE == 32, VEC8_F32_ALIGN == f32);
+    #[cfg(feature="vec8")] assert_layout!(VEC8_F64_SIZE == 64, VEC8_F64_ALIGN == f64);
+    // Vec16
+    #[cfg(feature="vec16")] assert_layout!(VEC16_I8_SIZE == 16, VEC16_I8_ALIGN == i8);
+    #[cfg(feature="vec16")] assert_layout!(VEC16_U8_SIZE == 16, VEC16_U8_ALIGN == u8);
+    #[cfg(feature="vec16")] assert_layout!(VEC16_I16_SIZE == 32, VEC16_I16_ALIGN == i16);
+    #[cfg(feature="vec16")] assert_layout!(VEC16_U16_SIZE == 32, VEC16_U16_ALIGN == u16);
+    #[cfg(feature="vec16")] assert_layout!(VEC16_I32_SIZE == 64, VEC16_I32_ALIGN == i32);
+    #[cfg(feature="vec16")] assert_layout!(VEC16_U32_SIZE == 64, VEC16_U32_ALIGN == u32);
+    #[cfg(feature="vec16")] assert_layout!(VEC16_I64_SIZE == 128, VEC16_I64_ALIGN == i64);
+    #[cfg(feature="vec16")] assert_layout!(VEC16_U64_SIZE == 128, VEC16_U64_ALIGN == u64);
+    #[cfg(feature="vec16")] assert_layout!(VEC16_F32_SIZE == 64, VEC16_F32_ALIGN == f32);
+    #[cfg(feature="vec16")] assert_layout!(VEC16_F64_SIZE == 128, VEC16_F64_ALIGN == f64);
+    // Vec32
+    #[cfg(feature="vec32")] assert_layout!(VEC32_I8_SIZE == 32, VEC32_I8_ALIGN == i8);
+    #[cfg(feature="vec32")] assert_layout!(VEC32_U8_SIZE == 32, VEC32_U8_ALIGN == u8);
+    #[cfg(feature="vec32")] assert_layout!(VEC32_I16_SIZE == 64, VEC32_I16_ALIGN == i16);
+    #[cfg(feature="vec32")] assert_layout!(VEC32_U16_SIZE == 64, VEC32_U16_ALIGN == u16);
+    #[cfg(feature="vec32")] assert_layout!(VEC32_I32_SIZE == 128, VEC32_I32_ALIGN == i32);
+    #[cfg(feature="vec32")] assert_layout!(VEC32_U32_SIZE == 128, VEC32_U32_ALIGN == u32);
+    #[cfg(feature="vec32")] assert_layout!(VEC32_I64_SIZE == 256, VEC32_I64_ALIGN == i64);
+    #[cfg(feature="vec32")] assert_layout!(VEC32_U64_SIZE == 256, VEC32_U64_ALIGN == u64);
+    #[cfg(feature="vec32")] assert_layout!(VEC32_F32_SIZE == 128, VEC32_F32_ALIGN == f32);
+    #[cfg(feature="vec32")] assert_layout!(VEC32_F64_SIZE == 256, VEC32_F64_ALIGN == f64);
+    // Vec64
+    #[cfg(feature="vec64")] assert_layout!(VEC64_I8_SIZE == 64, VEC64_I8_ALIGN == i8);
+    #[cfg(feature="vec64")] assert_layout!(VEC64_U8_SIZE == 64, VEC64_U8_ALIGN == u8);
+    #[cfg(feature="vec64")] assert_layout!(VEC64_I16_SIZE == 128, VEC64_I16_ALIGN == i16);
+    #[cfg(feature="vec64")] assert_layout!(VEC64_U16_SIZE == 128, VEC64_U16_ALIGN == u16);
+    #[cfg(feature="vec64")] assert_layout!(VEC64_I32_SIZE == 256, VEC64_I32_ALIGN == i32);
+    #[cfg(feature="vec64")] assert_layout!(VEC64_U32_SIZE == 256, VEC64_U32_ALIGN == u32);
+    #[cfg(feature="vec64")] assert_layout!(VEC64_I64_SIZE == 512, VEC64_I64_ALIGN == i64);
+    #[cfg(feature="vec64")] assert_layout!(VEC64_U64_SIZE == 512, VEC64_U64_ALIGN == u64);
+    #[cfg(feature="vec64")] assert_layout!(VEC64_F32_SIZE == 256, VEC64_F32_ALIGN == f32);
+    #[cfg(feature="vec64")] assert_layout!(VEC64_F64_SIZE == 512, VEC64_F64_ALIGN == f64);
+    // Extent2
+    assert_layout!(EXTENT2_I8_SIZE == 2, EXTENT2_I8_ALIGN == i8);
+    assert_layout!(EXTENT2_U8_SIZE == 2, EXTENT2_U8_ALIGN == u8);
+    assert_layout!(EXTENT2_I16_SIZE == 4, EXTENT2_I16_ALIGN == i16);
+    assert_layout!(EXTENT2_U16_SIZE == 4, EXTENT2_U16_ALIGN == u16);
+    assert_layout!(EXTENT2_I32_SIZE == 8, EXTENT2_I32_ALIGN == i32);
+    assert_layout!(EXTENT2_U32_SIZE == 8, EXTENT2_U32_ALIGN == u32);
+    assert_layout!(EXTENT2_I64_SIZE == 16, EXTENT2_I64_ALIGN == i64);
+    assert_layout!(EXTENT2_U64_SIZE == 16, EXTENT2_U64_ALIGN == u64);
+    assert_layout!(EXTENT2_F32_SIZE == 8, EXTENT2_F32_ALIGN == f32);
+    assert_layout!(EXTENT2_F64_SIZE == 16, EXTENT2_F64_ALIGN == f64);
+    // Extent3
+    assert_layout!(EXTENT3_I8_SIZE == 3, EXTENT3_I8_ALIGN == i8);
+    assert_layout!(EXTENT3_U8_SIZE == 3, EXTENT3_U8_ALIGN == u8);
+    assert_layout!(EXTENT3_I16_SIZE == 6, EXTENT3_I16_ALIGN == i16);
+    assert_layout!(EXTENT3_U16_SIZE == 6, EXTENT3_U16_ALIGN == u16);
+    assert_layout!(EXTENT3_I32_SIZE == 12, EXTENT3_I32_ALIGN == i32);
+    assert_layout!(EXTENT3_U32_SIZE == 12, EXTENT3_U32_ALIGN == u32);
+    assert_layout!(EXTENT3_I64_SIZE == 24, EXTENT3_I64_ALIGN == i64);
+    assert_layout!(EXTENT3_U64_SIZE == 24, EXTENT3_U64_ALIGN == u64);
+    assert_layout!(EXTENT3_F32_SIZE == 12, EXTENT3_F32_ALIGN == f32);
+    assert_layout!(EXTENT3_F64_SIZE == 24, EXTENT3_F64_ALIGN == f64);
+    // Rgb
+    #[cfg(feature="rgb")] assert_layout!(RGB_I8_SIZE == 3, RGB_I8_ALIGN == i8);
+    #[cfg(feature="rgb")] assert_layout!(RGB_U8_SIZE == 3, RGB_U8_ALIGN == u8);
+    #[cfg(feature="rgb")] assert_layout!(RGB_I16_SIZE == 6, RGB_I16_ALIGN == i16);
+    #[cfg(feature="rgb")] assert_layout!(RGB_U16_SIZE == 6, RGB_U16_ALIGN == u16);
+    #[cfg(feature="rgb")] assert_layout!(RGB_I32_SIZE == 12, RGB_I32_ALIGN == i32);
+    #[cfg(feature="rgb")] assert_layout!(RGB_U32_SIZE == 12, RGB_U32_ALIGN == u32);
+    #[cfg(feature="rgb")] assert_layout!(RGB_I64_SIZE == 24, RGB_I64_ALIGN == i64);
+    #[cfg(feature="rgb")] assert_layout!(RGB_U64_SIZE == 24, RGB_U64_ALIGN == u64);
+    #[cfg(feature="rgb")] assert_layout!(RGB_F32_SIZE == 12, RGB_F32_ALIGN == f32);
+    #[cfg(feature="rgb")] assert_layout!(RGB_F64_SIZE == 24, RGB_F64_ALIGN == f64);
+    // Rgba
+    #[cfg(feature="rgba")] assert_layout!(RGBA_I8_SIZE == 4, RGBA_I8_ALIGN == i8);
+    #[cfg(feature="rgba")] assert_layout!(RGBA_U8_SIZE == 4, RGBA_U8_ALIGN == u8);
+    #[cfg(feature="rgba")] assert_layout!(RGBA_I16_SIZE == 8, RGBA_I16_ALIGN == i16);
+    #[cfg(feature="rgba")] assert_layout!(RGBA_U16_SIZE == 8, RGBA_U16_ALIGN == u16);
+    #[cfg(feature="rgba")] assert_layout!(RGBA_I32_SIZE == 16, RGBA_I32_ALIGN == i32);
+    #[cfg(feature="rgba")] assert_layout!(RGBA_U32_SIZE == 16, RGBA_U32_ALIGN == u32);
+    #[cfg(feature="rgba")] assert_layout!(RGBA_I64_SIZE == 32, RGBA_I64_ALIGN == i64);
+    #[cfg(feature="rgba")] assert_layout!(RGBA_U64_SIZE == 32, RGBA_U64_ALIGN == u64);
+    #[cfg(feature="rgba")] assert_layout!(RGBA_F32_SIZE == 16, RGBA_F32_ALIGN == f32);
+    #[cfg(feature="rgba")] assert_layout!(RGBA_F64_SIZE == 32, RGBA_F64_ALIGN == f64);
+    // Uv
+    #[cfg(feature="uv")] assert_layout!(UV_I8_SIZE == 2, UV_I8_ALIGN == i8);
+    #[cfg(feature="uv")] assert_layout!(UV_U8_SIZE == 2, UV_U8_ALIGN == u8);
+    #[cfg(feature="uv")] assert_layout!(UV_I16_SIZE == 4, UV_I16_ALIGN == i16);
+    #[cfg(feature="uv")] assert_layout!(UV_U16_SIZE == 4, UV_U16_ALIGN == u16);
+    #[cfg(feature="uv")] assert_layout!(UV_I32_SIZE == 8, UV_I32_ALIGN == i32);
+    #[cfg(feature="uv")] assert_layout!(UV_U32_SIZE == 8, UV_U32_ALIGN == u32);
+    #[cfg(feature="uv")] assert_layout!(UV_I64_SIZE == 16, UV_I64_ALIGN == i64);
+    #[cfg(feature="uv")] assert_layout!(UV_U64_SIZE == 16, UV_U64_ALIGN == u64);
+    #[cfg(feature="uv")] assert_layout!(UV_F32_SIZE == 8, UV_F32_ALIGN == f32);
+    #[cfg(feature="uv")] assert_layout!(UV_F64_SIZE == 16, UV_F64_ALIGN == f64);
+    // Uvw
+    #[cfg(feature="uvw")] assert_layout!(UVW_I8_SIZE == 3, UVW_I8_ALIGN == i8);
+    #[cfg(feature="uvw")] assert_layout!(UVW_U8_SIZE == 3, UVW_U8_ALIGN == u8);
+    #[cfg(feature="uvw")] assert_layout!(UVW_I16_SIZE == 6, UVW_I16_ALIGN == i16);
+    #[cfg(feature="uvw")] assert_layout!(UVW_U16_SIZE == 6, UVW_U16_ALIGN == u16);
+    #[cfg(feature="uvw")] assert_layout!(UVW_I32_SIZE == 12, UVW_I32_ALIGN == i32);
+    #[cfg(feature="uvw")] assert_layout!(UVW_U32_SIZE == 12, UVW_U32_ALIGN == u32);
+    #[cfg(feature="uvw")] assert_layout!(UVW_I64_SIZE == 24, UVW_I64_ALIGN == i64);
+    #[cfg(feature="uvw")] assert_layout!(UVW_U64_SIZE == 24, UVW_U64_ALIGN == u64);
+    #[cfg(feature="uvw")] assert_layout!(UVW_F32_SIZE == 12, UVW_F32_ALIGN == f32);
+    #[cfg(feature="uvw")] assert_layout!(UVW_F64_SIZE == 24, UVW_F64_ALIGN == f64);
+    // Mat2
+    assert_layout!(MAT2_F32_SIZE == 16, MAT2_F32_ALIGN == f32);
+    assert_layout!(MAT2_F64_SIZE == 32, MAT2_F64_ALIGN == f64);
+    assert_layout!(MAT2_I32_SIZE == 16, MAT2_I32_ALIGN == i32);
+    assert_layout!(MAT2_U32_SIZE == 16, MAT2_U32_ALIGN == u32);
+    // Mat3
+    assert_layout!(MAT3_F32_SIZE == 36, MAT3_F32_ALIGN == f32);
+    assert_layout!(MAT3_F64_SIZE == 72, MAT3_F64_ALIGN == f64);
+    assert_layout!(MAT3_I32_SIZE == 36, MAT3_I32_ALIGN == i32);
+    assert_layout!(MAT3_U32_SIZE == 36, MAT3_U32_ALIGN == u32);
+    // Mat4
+    assert_layout!(MAT4_F32_SIZE == 64, MAT4_F32_ALIGN == f32);
+    assert_layout!(MAT4_F64_SIZE == 128, MAT4_F64_ALIGN == f64);
+    assert_layout!(MAT4_I32_SIZE == 64, MAT4_I32_ALIGN == i32);
+    assert_layout!(MAT4_U32_SIZE == 64, MAT4_U32_ALIGN == u32);
+    // Quaternion
+    assert_layout!(QUATERNION_F32_SIZE == 16, QUATERNION_F32_ALIGN == f32);
+    assert_layout!(QUATERNION_F64_SIZE == 32, QUATERNION_F64_ALIGN == f64);
+    assert_layout!(QUATERNION_I32_SIZE == 16, QUATERNION_I32_ALIGN == i32);
+    assert_layout!(QUATERNION_U32_SIZE == 16, QUATERNION_U32_ALIGN == u32);
+
+    // Offsets are checked against the addresses of actual fields.
+    #[test]
+    fn offsets() {
+        fn of<V: VectorLayout>(_: &V, i: usize) -> usize { offset_of_element::<V>(i) }
+        let v = Vec4::new(1_u8, 2, 3, 4);
+        assert_eq!([of(&v, 0), of(&v, 1), of(&v, 2), of(&v, 3)], [0, 1, 2, 3]);
+        let v = Vec3::new(1_f64, 2., 3.);
+        let base = &v as *const _ as usize;
+        assert_eq!(&v.y as *const _ as usize - base, offset_of_y::<Vec3<f64>>());
+        assert_eq!(&v.z as *const _ as usize - base, offset_of_z::<Vec3<f64>>());
+        let q = Quaternion::<f32>::identity();
+        let base = &q as *const _ as usize;
+        assert_eq!(&q.w as *const _ as usize - base, offset_of_w::<Quaternion<f32>>());
+        let e = Extent3::new(1_u16, 2, 3);
+        let base = &e as *const _ as usize;
+        assert_eq!(&e.d as *const _ as usize - base, offset_of_z::<Extent3<u16>>());
+        let c = Rgba::new(1_f32, 2., 3., 4.);
+        let base = &c as *const _ as usize;
+        assert_eq!(&c.a as *const _ as usize - base, offset_of_w::<Rgba<f32>>());
+        let m = Mat4::<f32>::identity();
+        let base = &m as *const _ as usize;
+        assert_eq!(&m.cols.w.y as *const _ as usize - base, 3 * VEC4_F32_SIZE + offset_of_y::<Vec4<f32>>());
+    }
+    #[test]
+    #[should_panic]
+    fn offset_out_of_bounds() {
+        let i = 2;
+        let _ = offset_of_element::<Vec2<f32>>(i);
+    }
+
+    mod interleave {
+        use super::*;
+
+        // Deterministic pseudo-random values in [-1, 1), using a xorshift generator.
+        fn random() -> impl FnMut() -> f32 {
+            let mut state = 0x9e3779b97f4a7c15_u64;
+            move || {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                (state >> 40) as f32 / (1_u64 << 23) as f32 - 1.
+            }
+        }
+
+        #[test]
+        fn offsets_match_manual_layout() {
+            type Layout = (Vec3<f32>, Vec3<f32>, Vec2<f32>);
+            let layout = interleaved_layout3::<Vec3<f32>, Vec3<f32>, Vec2<f32>>([0, 1, 2]);
+            assert_eq!(layout.stride, size_of::<Layout>());
+            assert_eq!(layout.offsets, [0, 12, 24]);
+            let layout = interleaved_layout3::<Vec3<f32>, Vec3<f32>, Vec2<f32>>([2, 0, 1]);
+            assert_eq!(layout.stride, 32);
+            assert_eq!(layout.offsets, [VEC2_F32_SIZE, VEC2_F32_SIZE + VEC3_F32_SIZE, 0]);
+            let layout = interleaved_layout2::<Vec4<u8>, Vec3<f64>>([1, 0]);
+            assert_eq!(layout.stride, VEC4_U8_SIZE + VEC3_F64_SIZE);
+            assert_eq!(layout.offsets, [VEC3_F64_SIZE, 0]);
+            const LAYOUT: InterleavedLayout<4> = InterleavedLayout::new([1, 2, 4, 8], [3, 1, 0, 2]);
+            assert_eq!(LAYOUT, InterleavedLayout { stride: 15, offsets: [10, 8, 11, 0] });
+        }
+        #[test]
+        fn bytes_are_at_offsets() {
+            let positions = [Vec3::new(1_f32, 2., 3.), Vec3::new(4., 5., 6.)];
+            let colors = [Rgba::new(10_u8, 20, 30, 40), Rgba::new(50, 60, 70, 80)];
+            let mut bytes = [0; 32];
+            assert_eq!(interleave2_into(&positions, &colors, [1, 0], &mut bytes), 32);
+            assert_eq!(bytes[..4], [10, 20, 30, 40]);
+            assert_eq!(bytes[4..8], 1_f32.to_ne_bytes());
+            assert_eq!(bytes[12..16], 3_f32.to_ne_bytes());
+            assert_eq!(bytes[16..20], [50, 60, 70, 80]);
+            assert_eq!(bytes[24..28], 5_f32.to_ne_bytes());
+        }
+        #[cfg(feature="std")]
+        #[test]
+        fn round_trip_random_meshes() {
+            let mut random = random();
+            for &count in &[0, 1, 7, 100] {
+                let positions: Vec<_> = (0..count).map(|_| Vec3::new(random(), random(), random()) * 100.).collect();
+                let normals: Vec<_> = (0..count).map(|_| Vec3::new(random(), random(), random()).normalized()).collect();
+                let uvs: Vec<_> = (0..count).map(|_| Vec2::new(random(), random())).collect();
+                for &order in &[[0, 1, 2], [2, 0, 1], [1, 2, 0], [2, 1, 0]] {
+                    let bytes = interleave3(&positions, &normals, &uvs, order);
+                    assert_eq!(bytes.len(), count * 32);
+                    let (p, n, t) = deinterleave3::<Vec3<f32>, Vec3<f32>, Vec2<f32>>(&bytes, order);
+                    assert_eq!((p, n, t), (positions.clone(), normals.clone(), uvs.clone()));
+                }
+                let ids: Vec<_> = (0..count as u16).map(|i| Vec2::new(i, !i)).collect();
+                let bytes = interleave2(&ids, &uvs, [1, 0]);
+                assert_eq!(deinterleave2::<Vec2<u16>, Vec2<f32>>(&bytes, [1, 0]), (ids, uvs));
+            }
+        }
+        #[test]
+        #[should_panic]
+        fn invalid_order() {
+            let _ = interleaved_layout3::<Vec3<f32>, Vec3<f32>, Vec2<f32>>([0, 1, 1]);
+        }
+        #[test]
+        #[should_panic]
+        fn mismatched_lengths() {
+            let mut bytes = [0; 64];
+            interleave2_into(&[Vec2::<f32>::zero(); 2], &[Vec2::<f32>::zero(); 3], [0, 1], &mut bytes);
+        }
+        #[cfg(feature="std")]
+        #[test]
+        #[should_panic]
+        fn truncated_buffer() {
+            let _ = deinterleave2::<Vec2<f32>, Vec2<f32>>(&[0; 20], [0, 1]);
+        }
+    }
+}
@@ -12,10 +12,27 @@
 //! - `repr_simd` enables Nightly Rust's `repr_simd` and `simd_ffi` features, and unlock
 //!   SIMD versions of all appropriate types (though `repr_simd` modules).
 //!   On Stable, this feature has no effect.
-//! - `serde` makes vectors and matrices derive `Serialize` and `Deserialize`.
+//! - `serde` makes vectors and matrices derive `Serialize` and `Deserialize`, and enables the
+//!   `serde_helpers` module, which (de)serializes quaternions as angles in degrees.
+//! - `serde-euler-degrees` makes `Transform` serialize its orientation as Euler angles in degrees
+//!   (see `serde_helpers::quat_as_euler_degrees`) instead of quaternion components.
 //! - `image` makes color vectors implement the `Pixel` trait from the `image` crate.
 //! - `mint` enables conversion to the `mint` crate's types.
 //!   `mint` is an interoperability layer for math libraries.
+//! - `rand` enables methods that take a random number generator, such as `Vec3::jittered()`.
+//! - `glam-interop`, `nalgebra-interop` and `cgmath-interop` enable conversions to and from
+//!   these crates' types, as well as zero-copy slice conversions (see the `interop` module).
+//! - `std` (enabled by default) enables types that need heap allocation, such as `MatrixStack`.
+//! - `deterministic-trig` makes all trigonometry performed by this crate (e.g in rotation and
+//!   projection matrices) use software implementations, which yield bit-identical results
+//!   across platforms. These are exposed as `vek::ops::{sin, cos, tan, acos, atan2}`.
+//!   `sin()` and `cos()` are within `2 * T::epsilon()` of the exact result for `|x| < 2^32`,
+//!   and lose accuracy past that range.
+//! - `robust-predicates` enables `geom::predicates::{orient2d_exact, incircle_exact}`, which
+//!   give the exact sign of the orientation and in-circle determinants, for robust geometric
+//!   algorithms such as Delaunay triangulation.
+//! - `derive` enables `#[derive(Lerp)]`, which implements `Lerp` field-wise for structs
+//!   (see the `vek-derive` crate).
 //!
 //! # `#![no_std]`
 //! This crate is `#![no_std]`.
@@ -44,6 +61,9 @@
 
 extern crate core as std;
 
+#[cfg(feature = "std")]
+extern crate alloc;
+
 #[cfg(test)]
 mod vtest;
 
@@ -53,6 +73,14 @@ extern crate serde;
 
 #[cfg(feature = "mint")]
 extern crate mint;
+#[cfg(feature = "rand")]
+extern crate rand;
+#[cfg(feature = "glam-interop")]
+extern crate glam;
+#[cfg(feature = "nalgebra-interop")]
+extern crate nalgebra;
+#[cfg(feature = "cgmath-interop")]
+extern crate cgmath;
 
 extern crate num_integer;
 extern crate num_traits;
@@ -70,12 +98,16 @@ mod simd_llvm;
 
 pub mod ops;
 pub use crate::ops::*;
+#[cfg(feature="derive")]
+pub use vek_derive::Lerp;
 pub mod vec;
 pub use crate::vec::*;
 pub mod mat;
 pub use crate::mat::*;
 pub mod quaternion;
 pub use crate::quaternion::*;
+pub mod rotation2;
+pub use crate::rotation2::*;
 pub mod transition;
 pub use crate::transition::*;
 pub mod transform;
@@ -84,3 +116,26 @@ pub mod bezier;
 pub use crate::bezier::*;
 pub mod geom;
 pub use crate::geom::*;
+pub mod sampling;
+pub mod mesh;
+pub use crate::mesh::*;
+pub mod polygon;
+pub use crate::polygon::*;
+pub mod io;
+pub use crate::io::*;
+pub mod kinematics;
+pub use crate::kinematics::*;
+pub mod stats;
+pub use crate::stats::*;
+pub mod layout;
+#[cfg(feature = "std")]
+pub mod bvh;
+#[cfg(feature = "std")]
+pub use crate::bvh::*;
+#[cfg(feature = "std")]
+pub mod summed_area;
+#[cfg(feature = "std")]
+pub use crate::summed_area::*;
+pub mod interop;
+#[cfg(feature = "serde")]
+pub mod serde_helpers;
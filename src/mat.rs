@@ -5,12 +5,14 @@ use std::ptr;
 use std::slice;
 use std::ops::Add;
 use std::fmt::{self, Display, Formatter, Debug};
+#[cfg(feature="std")]
+use alloc::vec::Vec;
 use std::ops::*;
 use num_traits::{Zero, One, real::Real, FloatConst, NumCast, AsPrimitive};
 use approx::{AbsDiffEq, RelativeEq, UlpsEq};
-use crate::ops::MulAdd;
+use crate::ops::{MulAdd, IntoAngleRadians, sin, cos, tan};
 use crate::vec;
-use crate::geom::{Rect, FrustumPlanes}; // NOTE: Rect is therefore always repr_c here
+use crate::geom::{Rect, FrustumPlanes, Plane}; // NOTE: Rect and Plane are therefore always repr_c here
 use crate::quaternion;
 use crate::transform;
 
@@ -25,6 +27,70 @@ unsafe fn transmute_unchecked<S, D>(s: S) -> D {
     d
 }
 
+/// Byte serialization with explicit endianness, for each primitive element type.
+macro_rules! mat_impl_bytes {
+    ($Mat:ident ($nrows:tt x $ncols:tt) $($T:ident $size:tt)+) => {
+        $(
+            impl $Mat<$T> {
+                mat_impl_bytes!{@endian $T $size ($nrows x $ncols) "little" to_le_bytes from_le_bytes write_le_bytes_into read_le_bytes_from}
+                mat_impl_bytes!{@endian $T $size ($nrows x $ncols) "big" to_be_bytes from_be_bytes write_be_bytes_into read_be_bytes_from}
+            }
+        )+
+    };
+    (@endian $T:ident $size:tt ($nrows:tt x $ncols:tt) $endian:tt $to_bytes:ident $from_bytes:ident $write_into:ident $read_from:ident) => {
+        #[doc = concat!("Encodes this matrix's elements as ", $endian, "-endian bytes.")]
+        ///
+        /// Elements are always written in column-major order (i.e the order of `into_col_array()`),
+        /// regardless of this matrix's layout, so that row-major and column-major matrices
+        /// have the same encoding.
+        pub fn $to_bytes(self) -> [u8; $nrows * $ncols * $size] {
+            let mut bytes = [0; $nrows * $ncols * $size];
+            for (chunk, x) in bytes.chunks_exact_mut($size).zip(self.into_col_array().iter()) {
+                chunk.copy_from_slice(&x.$to_bytes());
+            }
+            bytes
+        }
+        #[doc = concat!("Decodes a matrix from its elements encoded as ", $endian, "-endian bytes, in column-major order.")]
+        pub fn $from_bytes(bytes: [u8; $nrows * $ncols * $size]) -> Self {
+            let mut array = [0 as $T; $nrows * $ncols];
+            for (x, chunk) in array.iter_mut().zip(bytes.chunks_exact($size)) {
+                let mut b = [0; $size];
+                b.copy_from_slice(chunk);
+                *x = <$T>::$from_bytes(b);
+            }
+            Self::from_col_array(array)
+        }
+        #[doc = concat!("Encodes each matrix of `slice` as ", $endian, "-endian bytes (see `", stringify!($to_bytes), "()`), ")]
+        /// one after the other, into `out`. Returns the number of bytes written.
+        ///
+        /// # Panics
+        /// Panics if `out` is too small.
+        pub fn $write_into(slice: &[Self], out: &mut [u8]) -> usize {
+            let len = slice.len() * $nrows * $ncols * $size;
+            assert!(out.len() >= len, "output buffer is too small ({} bytes needed, got {})", len, out.len());
+            for (chunk, m) in out.chunks_exact_mut($nrows * $ncols * $size).zip(slice) {
+                chunk.copy_from_slice(&m.$to_bytes());
+            }
+            len
+        }
+        #[doc = concat!("Decodes matrices encoded as ", $endian, "-endian bytes (see `", stringify!($from_bytes), "()`) ")]
+        /// from `bytes` until `out` is filled. Returns the number of bytes read.
+        ///
+        /// # Panics
+        /// Panics if `bytes` is too small.
+        pub fn $read_from(bytes: &[u8], out: &mut [Self]) -> usize {
+            let len = out.len() * $nrows * $ncols * $size;
+            assert!(bytes.len() >= len, "input buffer is too small ({} bytes needed, got {})", len, bytes.len());
+            for (m, chunk) in out.iter_mut().zip(bytes.chunks_exact($nrows * $ncols * $size)) {
+                let mut b = [0; $nrows * $ncols * $size];
+                b.copy_from_slice(chunk);
+                *m = Self::$from_bytes(b);
+            }
+            len
+        }
+    };
+}
+
 macro_rules! mat_impl_mat {
     (rows $Mat:ident $MintRowMat:ident $MintColMat:ident $CVec:ident $Vec:ident ($nrows:tt x $ncols:tt) ($($get:tt)+)) => {
 
@@ -1068,6 +1134,8 @@ macro_rules! mat_impl_mat {
     };
     (common $lines:ident $Mat:ident $CVec:ident $Vec:ident ($nrows:tt x $ncols:tt) ($($get:tt)+)) => {
 
+        mat_impl_bytes!{$Mat ($nrows x $ncols) i32 4 u32 4 f32 4 f64 8}
+
         /// The default value for a square matrix is the identity.
         ///
         /// ```
@@ -1964,6 +2032,26 @@ macro_rules! mat_impl_mat4 {
             {
                 V::from(self * Vec4::from_direction(rhs))
             }
+            /// Transforms a fixed-size chunk of points, as `mul_point()` would do for each of them.
+            ///
+            /// Since `N` is known at compile time, the compiler can fully unroll this
+            /// (chunks can be obtained with `Vec3::slice_chunks()`).
+            ///
+            /// ```
+            /// # use vek::{Mat4, Vec3};
+            /// let m = Mat4::<f32>::translation_3d(Vec3::new(1., 2., 3.)).scaled_3d(Vec3::broadcast(2.));
+            /// let points = [Vec3::zero(), Vec3::unit_x(), Vec3::unit_y(), Vec3::unit_z()];
+            /// let out = m.transform_chunk(&points);
+            /// for (p, o) in points.iter().zip(out.iter()) {
+            ///     assert_eq!(*o, m.mul_point(*p));
+            /// }
+            /// ```
+            pub fn transform_chunk<const N: usize>(&self, chunk: &[Vec3<T>; N]) -> [Vec3<T>; N]
+                where T: Real + MulAdd<T,T,Output=T>
+            {
+                let m = *self;
+                ::std::array::from_fn(|i| m.mul_point(chunk[i]))
+            }
 
             //
             // TRANSFORMS
@@ -2063,9 +2151,10 @@ macro_rules! mat_impl_mat4 {
                 Self::rotation_x(angle_radians) * self
             }
             /// Creates a matrix that rotates around the X axis.
-            pub fn rotation_x(angle_radians: T) -> Self where T: Real {
-                let c = angle_radians.cos();
-                let s = angle_radians.sin();
+            pub fn rotation_x<A: IntoAngleRadians<T>>(angle_radians: A) -> Self where T: Real {
+                let angle_radians = angle_radians.into_angle_radians();
+                let c = cos(angle_radians);
+                let s = sin(angle_radians);
                 Self::new(
                     T::one(), T::zero(), T::zero(), T::zero(),
                     T::zero(), c, -s, T::zero(),
@@ -2086,9 +2175,10 @@ macro_rules! mat_impl_mat4 {
                 Self::rotation_y(angle_radians) * self
             }
             /// Creates a matrix that rotates around the Y axis.
-            pub fn rotation_y(angle_radians: T) -> Self where T: Real {
-                let c = angle_radians.cos();
-                let s = angle_radians.sin();
+            pub fn rotation_y<A: IntoAngleRadians<T>>(angle_radians: A) -> Self where T: Real {
+                let angle_radians = angle_radians.into_angle_radians();
+                let c = cos(angle_radians);
+                let s = sin(angle_radians);
                 Self::new(
                     c, T::zero(), s, T::zero(),
                     T::zero(), T::one(), T::zero(), T::zero(),
@@ -2109,9 +2199,10 @@ macro_rules! mat_impl_mat4 {
                 Self::rotation_z(angle_radians) * self
             }
             /// Creates a matrix that rotates around the Z axis.
-            pub fn rotation_z(angle_radians: T) -> Self where T: Real {
-                let c = angle_radians.cos();
-                let s = angle_radians.sin();
+            pub fn rotation_z<A: IntoAngleRadians<T>>(angle_radians: A) -> Self where T: Real {
+                let angle_radians = angle_radians.into_angle_radians();
+                let c = cos(angle_radians);
+                let s = sin(angle_radians);
                 Self::new(
                     c, -s, T::zero(), T::zero(),
                     s,  c, T::zero(), T::zero(),
@@ -2179,10 +2270,11 @@ macro_rules! mat_impl_mat4 {
             /// }
             /// # }
             /// ```
-            pub fn rotation_3d<V: Into<Vec3<T>>>(angle_radians: T, axis: V) -> Self where T: Real + Add<T, Output=T> {
+            pub fn rotation_3d<A: IntoAngleRadians<T>, V: Into<Vec3<T>>>(angle_radians: A, axis: V) -> Self where T: Real + Add<T, Output=T> {
+                let angle_radians = angle_radians.into_angle_radians();
                 let Vec3 { x, y, z } = axis.into().normalized();
-                let s = angle_radians.sin();
-                let c = angle_radians.cos();
+                let s = sin(angle_radians);
+                let c = cos(angle_radians);
                 let oc = T::one() - c;
                 Self::new(
                     oc*x*x + c  , oc*x*y - z*s, oc*z*x + y*s, T::zero(),
@@ -2508,6 +2600,69 @@ macro_rules! mat_impl_mat4 {
                 )
             }
 
+            /// Creates a matrix which squashes geometry onto `plane`, along the rays coming from a light,
+            /// i.e which gives the positions of the shadows that geometry casts on the plane.
+            ///
+            /// `light` is a point light at `(x, y, z, 1)`, or a directional light shining along
+            /// `-(x, y, z)` when `w` is zero, in which case the projection is parallel. The plane's
+            /// normal doesn't need to be normalized. Projected points come out with a `w` other than
+            /// one, which the perspective divide takes care of.
+            ///
+            /// This is the classic `(P·L)I - LPᵀ`, for column vectors (`m * v`), like every matrix
+            /// of this crate. Transpose it for row vectors (`v * m`).
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{Mat4, Vec3, Vec4, Plane};
+            /// # fn main() {
+            /// let ground = Plane::new(Vec3::unit_y(), 0_f32);
+            /// let shadow = Mat4::planar_shadow(ground, Vec4::new(0., 10., 0., 1.));
+            /// let p = shadow * Vec4::new(1., 5., 2., 1.);
+            /// assert_relative_eq!(Vec3::from(p) / p.w, Vec3::new(2., 0., 4.));
+            /// # }
+            /// ```
+            pub fn planar_shadow(plane: Plane<T>, light: Vec4<T>) -> Self where T: Real {
+                let p = [plane.normal.x, plane.normal.y, plane.normal.z, -plane.distance];
+                let l = [light.x, light.y, light.z, light.w];
+                let dot = p[0] * l[0] + p[1] * l[1] + p[2] * l[2] + p[3] * l[3];
+                let m = |i: usize, j: usize| if i == j { dot - l[i] * p[j] } else { -l[i] * p[j] };
+                Self::new(
+                    m(0, 0), m(0, 1), m(0, 2), m(0, 3),
+                    m(1, 0), m(1, 1), m(1, 2), m(1, 3),
+                    m(2, 0), m(2, 1), m(2, 2), m(2, 3),
+                    m(3, 0), m(3, 1), m(3, 2), m(3, 3),
+                )
+            }
+            /// Creates a matrix which mirrors geometry across `plane`, e.g to render reflections
+            /// in a mirror or on the surface of water.
+            ///
+            /// The plane's normal must be normalized. This transforms column vectors (`m * v`), like
+            /// every matrix of this crate; transpose it for row vectors (`v * m`). The result is its
+            /// own inverse, and flips the handedness of space, so triangle winding is reversed.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{Mat4, Vec3, Vec4, Plane};
+            /// # fn main() {
+            /// let mirror = Mat4::planar_reflection(Plane::new(Vec3::unit_x(), 2_f32));
+            /// assert_relative_eq!(mirror * Vec4::new(3., 1., 1., 1.), Vec4::new(1., 1., 1., 1.));
+            /// // Directions are reflected too, but not translated.
+            /// assert_relative_eq!(mirror * Vec4::new(1., 1., 0., 0.), Vec4::new(-1., 1., 0., 0.));
+            /// # }
+            /// ```
+            pub fn planar_reflection(plane: Plane<T>) -> Self where T: Real {
+                let Plane { normal: n, distance: d } = plane;
+                let two = T::one() + T::one();
+                Self::new(
+                    T::one() - two * n.x * n.x, -two * n.x * n.y, -two * n.x * n.z, two * d * n.x,
+                    -two * n.y * n.x, T::one() - two * n.y * n.y, -two * n.y * n.z, two * d * n.y,
+                    -two * n.z * n.x, -two * n.z * n.y, T::one() - two * n.z * n.z, two * d * n.z,
+                    T::zero(), T::zero(), T::zero(), T::one()
+                )
+            }
+
 
             //
             // PROJECTIONS
@@ -2677,7 +2832,7 @@ macro_rules! mat_impl_mat4 {
                 debug_assert!(far  > T::zero(), "The far plane distance cannot be below zero, found: {:?}", far);
                 debug_assert!(far  > near, "The far plane cannot be closer than the near plane, found: far: {:?}, near: {:?}", far, near);
                 let two = T::one() + T::one();
-                let tan_half_fovy = (fov_y_radians / two).tan();
+                let tan_half_fovy = tan(fov_y_radians / two);
                 let m00 = T::one() / (aspect_ratio * tan_half_fovy);
                 let m11 = T::one() / tan_half_fovy;
                 let m22 = far / (near - far);
@@ -2712,7 +2867,7 @@ macro_rules! mat_impl_mat4 {
                 debug_assert!(far  > T::zero(), "The far plane distance cannot be below zero, found: {:?}", far);
                 debug_assert!(far  > near, "The far plane cannot be closer than the near plane, found: far: {:?}, near: {:?}", far, near);
                 let two = T::one() + T::one();
-                let tan_half_fovy = (fov_y_radians / two).tan();
+                let tan_half_fovy = tan(fov_y_radians / two);
                 let m00 = T::one() / (aspect_ratio * tan_half_fovy);
                 let m11 = T::one() / tan_half_fovy;
                 let m22 = -(far + near) / (far - near);
@@ -2753,7 +2908,7 @@ macro_rules! mat_impl_mat4 {
 
                 let two = T::one() + T::one();
                 let rad = fov_y_radians;
-                let h = (rad/two).cos() / (rad/two).sin();
+                let h = cos(rad/two) / sin(rad/two);
                 let w = h * height / width;
 
                 let m00 = w;
@@ -2800,7 +2955,7 @@ macro_rules! mat_impl_mat4 {
 
                 let two = T::one() + T::one();
                 let rad = fov_y_radians;
-                let h = (rad/two).cos() / (rad/two).sin();
+                let h = cos(rad/two) / sin(rad/two);
                 let w = h * height / width;
 
                 let m00 = w;
@@ -2843,7 +2998,7 @@ macro_rules! mat_impl_mat4 {
                 debug_assert!(near > T::zero(), "The near plane distance cannot be below zero, found: {:?}", near);
 
                 let two = T::one() + T::one();
-                let range = (fov_y_radians / two).tan() * near;
+                let range = tan(fov_y_radians / two) * near;
                 let left = -range * aspect_ratio;
                 let right = range * aspect_ratio;
                 let bottom = -range;
@@ -3004,6 +3159,80 @@ macro_rules! mat_impl_mat4 {
 
                 obj.into()
             }
+            /// Gets one ray per pixel of a tile of the viewport, through the pixel's center, in
+            /// row-major order, for a depth clip space ranging from -1 to 1
+            /// (`GL_DEPTH_NEGATIVE_ONE_TO_ONE`, hence the `_no` suffix).
+            ///
+            /// `inverse_view_proj` is the inverse of the view-projection matrix. The tile holds pixel
+            /// positions, with `(0, 0)` at the bottom-left corner of the viewport, and `max` excluded
+            /// (e.g `Aabr { min: Vec2::zero(), max: Vec2::new(w, h) }` is the whole viewport); it is
+            /// clipped to the viewport. Each ray starts on the near plane, and its direction is normalized.
+            ///
+            /// This is the same as unprojecting two points for each pixel (see `viewport_to_world_no()`),
+            /// but only unprojects two points per row: unprojected homogeneous points are linear
+            /// along a row, so the others are interpolated from these, before the division by `w`.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{Aabr, Extent2, Mat4, Vec2, Vec3};
+            /// # fn main() {
+            /// let view = Mat4::look_at_rh(Vec3::new(0., 0., 5.), Vec3::zero(), Vec3::unit_y());
+            /// let proj = Mat4::perspective_rh_no(90_f64.to_radians(), 1., 0.1, 100.);
+            /// let viewport = Extent2::new(64, 64);
+            /// let tile = Aabr { min: Vec2::new(31, 31), max: Vec2::new(33, 33) };
+            /// let rays: Vec<_> = Mat4::viewport_rays_no((proj * view).inverted(), viewport, tile).collect();
+            /// assert_eq!(rays.len(), 4);
+            /// assert_eq!(rays[1].0, Vec2::new(32, 31));
+            /// // The pixels around the center of the viewport look almost straight ahead.
+            /// for (_, ray) in rays {
+            ///     assert_relative_eq!(ray.origin.z, 4.9, epsilon = 1e-9);
+            ///     assert_relative_eq!(ray.direction, -Vec3::unit_z(), epsilon = 0.05);
+            /// }
+            /// # }
+            /// ```
+            pub fn viewport_rays_no(inverse_view_proj: Self, viewport: Extent2<u32>, tile: Aabr<u32>) -> impl Iterator<Item=(Vec2<u32>, Ray<T>)>
+                where T: Real + MulAdd<T,T,Output=T>
+            {
+                Self::viewport_rays_between(inverse_view_proj, viewport, tile, -T::one(), T::zero())
+            }
+            /// Gets one ray per pixel of a tile of the viewport, through the pixel's center, in
+            /// row-major order, for a depth clip space ranging from 0 to 1 (`GL_DEPTH_ZERO_TO_ONE`,
+            /// hence the `_zo` suffix).
+            ///
+            /// See `viewport_rays_no()`.
+            pub fn viewport_rays_zo(inverse_view_proj: Self, viewport: Extent2<u32>, tile: Aabr<u32>) -> impl Iterator<Item=(Vec2<u32>, Ray<T>)>
+                where T: Real + MulAdd<T,T,Output=T>
+            {
+                let half = T::one() / (T::one() + T::one());
+                Self::viewport_rays_between(inverse_view_proj, viewport, tile, T::zero(), half)
+            }
+            /// Rays from the unprojected points at the clip-space depths `near` to those at `far`,
+            /// which are both finite even for an infinite far plane.
+            fn viewport_rays_between(inverse_view_proj: Self, viewport: Extent2<u32>, tile: Aabr<u32>, near: T, far: T) -> impl Iterator<Item=(Vec2<u32>, Ray<T>)>
+                where T: Real + MulAdd<T,T,Output=T>
+            {
+                let m = inverse_view_proj;
+                let max = Vec2::new(tile.max.x.min(viewport.w), tile.max.y.min(viewport.h));
+                let min = Vec2::new(tile.min.x.min(max.x), tile.min.y.min(max.y));
+                let two = T::one() + T::one();
+                let (w, h) = (T::from(viewport.w).unwrap(), T::from(viewport.h).unwrap());
+                let ndc = move |pixel: u32, size: T| (T::from(pixel).unwrap() * two + T::one()) / size - T::one();
+                // Moving one pixel to the right moves both unprojected points by this much.
+                let step = m * Vec4::new(two / w, T::zero(), T::zero(), T::zero());
+                (min.y..max.y).flat_map(move |y| {
+                    let (x0, y0) = (ndc(min.x, w), ndc(y, h));
+                    let near_start = m * Vec4::new(x0, y0, near, T::one());
+                    let far_start = m * Vec4::new(x0, y0, far, T::one());
+                    (min.x..max.x).map(move |x| {
+                        let i = T::from(x - min.x).unwrap();
+                        let (near, far) = (near_start + step * i, far_start + step * i);
+                        let origin = Vec3::from(near / near.w);
+                        let direction = (Vec3::from(far / far.w) - origin).normalized();
+                        (Vec2::new(x, y), Ray::new(origin, direction))
+                    })
+                })
+            }
         }
         use super::mat3::Mat3;
         impl<T> From<Mat3<T>> for Mat4<T> where T: Zero + One {
@@ -3510,9 +3739,10 @@ macro_rules! mat_impl_mat3 {
                 Self::rotation_x(angle_radians) * self
             }
             /// Creates a matrix that rotates around the X axis.
-            pub fn rotation_x(angle_radians: T) -> Self where T: Real {
-                let c = angle_radians.cos();
-                let s = angle_radians.sin();
+            pub fn rotation_x<A: IntoAngleRadians<T>>(angle_radians: A) -> Self where T: Real {
+                let angle_radians = angle_radians.into_angle_radians();
+                let c = cos(angle_radians);
+                let s = sin(angle_radians);
                 Self::new(
                     T::one(), T::zero(), T::zero(),
                     T::zero(), c, -s,
@@ -3532,9 +3762,10 @@ macro_rules! mat_impl_mat3 {
                 Self::rotation_y(angle_radians) * self
             }
             /// Creates a matrix that rotates around the Y axis.
-            pub fn rotation_y(angle_radians: T) -> Self where T: Real {
-                let c = angle_radians.cos();
-                let s = angle_radians.sin();
+            pub fn rotation_y<A: IntoAngleRadians<T>>(angle_radians: A) -> Self where T: Real {
+                let angle_radians = angle_radians.into_angle_radians();
+                let c = cos(angle_radians);
+                let s = sin(angle_radians);
                 Self::new(
                     c, T::zero(), s,
                     T::zero(), T::one(), T::zero(),
@@ -3554,9 +3785,10 @@ macro_rules! mat_impl_mat3 {
                 Self::rotation_z(angle_radians) * self
             }
             /// Creates a matrix that rotates around the Z axis.
-            pub fn rotation_z(angle_radians: T) -> Self where T: Real {
-                let c = angle_radians.cos();
-                let s = angle_radians.sin();
+            pub fn rotation_z<A: IntoAngleRadians<T>>(angle_radians: A) -> Self where T: Real {
+                let angle_radians = angle_radians.into_angle_radians();
+                let c = cos(angle_radians);
+                let s = sin(angle_radians);
                 Self::new(
                     c, -s, T::zero(),
                     s,  c, T::zero(),
@@ -3625,10 +3857,11 @@ macro_rules! mat_impl_mat3 {
             /// }
             /// # }
             /// ```
-            pub fn rotation_3d<V: Into<Vec3<T>>>(angle_radians: T, axis: V) -> Self where T: Real + Add<T, Output=T> {
+            pub fn rotation_3d<A: IntoAngleRadians<T>, V: Into<Vec3<T>>>(angle_radians: A, axis: V) -> Self where T: Real + Add<T, Output=T> {
+                let angle_radians = angle_radians.into_angle_radians();
                 let Vec3 { x, y, z } = axis.into().normalized();
-                let s = angle_radians.sin();
-                let c = angle_radians.cos();
+                let s = sin(angle_radians);
+                let c = cos(angle_radians);
                 let oc = T::one() - c;
                 Self::new(
                     oc*x*x + c  , oc*x*y - z*s, oc*z*x + y*s,
@@ -3660,6 +3893,158 @@ macro_rules! mat_impl_mat3 {
             }
         }
 
+        impl<T: Real> Mat3<T> {
+            /// Computes the eigenvalues and eigenvectors of this matrix, **assuming it is symmetric**
+            /// (e.g a covariance or inertia matrix), using the Jacobi eigenvalue algorithm.
+            ///
+            /// Returns the eigenvalues in decreasing order, and a rotation matrix (orthonormal,
+            /// with a determinant of 1) whose columns are the matching unit eigenvectors, such that
+            /// `self == vectors * Mat3::scaling_3d(values) * vectors.transposed()`.
+            /// The elements below the diagonal are ignored.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{Mat3, Vec3};
+            /// # fn main() {
+            /// let m = Mat3::new(
+            ///     2_f64, 1., 0.,
+            ///     1., 2., 0.,
+            ///     0., 0., 5.,
+            /// );
+            /// let (values, vectors) = m.symmetric_eigen();
+            /// assert_relative_eq!(values, Vec3::new(5., 3., 1.), epsilon = 1e-12);
+            /// assert_relative_eq!(m * vectors.cols[1], vectors.cols[1] * 3., epsilon = 1e-12);
+            /// assert_relative_eq!(vectors * Mat3::scaling_3d(values) * vectors.transposed(), m, epsilon = 1e-12);
+            /// # }
+            /// ```
+            pub fn symmetric_eigen(self) -> (Vec3<T>, Self) {
+                let m = self.into_row_arrays();
+                let mut a = [[m[0][0], m[0][1], m[0][2]], [m[0][1], m[1][1], m[1][2]], [m[0][2], m[1][2], m[2][2]]];
+                let mut v = Self::identity().into_row_arrays();
+                let two = T::one() + T::one();
+                let scale = a.iter().flat_map(|row| row.iter()).fold(T::zero(), |acc, &x| acc + x * x);
+                // Convergence is quadratic, so this many sweeps are never reached in practice.
+                for _ in 0..32 {
+                    let off = a[0][1] * a[0][1] + a[0][2] * a[0][2] + a[1][2] * a[1][2];
+                    if off <= scale * T::epsilon() * T::epsilon() {
+                        break;
+                    }
+                    for &(p, q) in &[(0, 1), (0, 2), (1, 2)] {
+                        if a[p][q] == T::zero() {
+                            continue;
+                        }
+                        // Rotate in the (p, q) plane so that a[p][q] becomes zero.
+                        let theta = (a[q][q] - a[p][p]) / (two * a[p][q]);
+                        let t = theta.signum() / (theta.abs() + (theta * theta + T::one()).sqrt());
+                        let c = T::one() / (t * t + T::one()).sqrt();
+                        let s = t * c;
+                        a[p][p] = a[p][p] - t * a[p][q];
+                        a[q][q] = a[q][q] + t * a[p][q];
+                        a[p][q] = T::zero();
+                        a[q][p] = T::zero();
+                        let r = 3 - p - q;
+                        let (arp, arq) = (a[r][p], a[r][q]);
+                        a[r][p] = c * arp - s * arq;
+                        a[p][r] = a[r][p];
+                        a[r][q] = s * arp + c * arq;
+                        a[q][r] = a[r][q];
+                        for row in v.iter_mut() {
+                            let (vp, vq) = (row[p], row[q]);
+                            row[p] = c * vp - s * vq;
+                            row[q] = s * vp + c * vq;
+                        }
+                    }
+                }
+                // Sort by decreasing eigenvalue.
+                let mut order = [0, 1, 2];
+                order.sort_unstable_by(|&i, &j| a[j][j].partial_cmp(&a[i][i]).unwrap_or(::std::cmp::Ordering::Equal));
+                let values = Vec3::new(a[order[0]][order[0]], a[order[1]][order[1]], a[order[2]][order[2]]);
+                let mut vectors = [
+                    [v[0][order[0]], v[0][order[1]], v[0][order[2]]],
+                    [v[1][order[0]], v[1][order[1]], v[1][order[2]]],
+                    [v[2][order[0]], v[2][order[1]], v[2][order[2]]],
+                ];
+                // Make it a rotation, by flipping the last eigenvector if needed.
+                if Self::from_row_arrays(vectors).determinant() < T::zero() {
+                    for row in vectors.iter_mut() {
+                        row[2] = -row[2];
+                    }
+                }
+                (values, Self::from_row_arrays(vectors))
+            }
+            /// Computes the polar decomposition of this matrix, i.e a rotation `R` and a symmetric
+            /// matrix `S` such that `self == R * S`, as in shape matching and deformation analysis.
+            ///
+            /// `R` is always a proper rotation (orthonormal, with a determinant of 1), which is the
+            /// rotation closest to this matrix. If this matrix contains a reflection (i.e its determinant
+            /// is negative), the flip is absorbed into `S`, along its axis of least stretch, so `S` then
+            /// has a negative eigenvalue. Otherwise `S` is positive semi-definite.
+            ///
+            /// This is robust to singular and near-singular matrices (e.g the deformation gradient of a
+            /// flattened element), for which `R` is partly arbitrary but still a rotation.
+            /// It is computed from the eigenvectors of `self.transposed() * self`, see `symmetric_eigen()`.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{Mat3, Vec3};
+            /// # fn main() {
+            /// let rotation = Mat3::rotation_3d(0.5_f64, Vec3::new(1., 2., 3.));
+            /// let stretch = Mat3::new(
+            ///     2., 0.5, 0.,
+            ///     0.5, 1., 0.,
+            ///     0., 0., 3.,
+            /// );
+            /// let (r, s) = (rotation * stretch).polar_decomposition();
+            /// assert_relative_eq!(r, rotation, epsilon = 1e-12);
+            /// assert_relative_eq!(s, stretch, epsilon = 1e-12);
+            ///
+            /// // A mirror image: the flip ends up in the stretch.
+            /// let (r, s) = (rotation * Mat3::scaling_3d(Vec3::new(1., 1., -1.))).polar_decomposition();
+            /// assert_relative_eq!(r.determinant(), 1., epsilon = 1e-12);
+            /// assert_relative_eq!(s.determinant(), -1., epsilon = 1e-12);
+            /// # }
+            /// ```
+            pub fn polar_decomposition(self) -> (Self, Self) where T: MulAdd<T,T,Output=T> {
+                let (_, v) = (self.transposed() * self).symmetric_eigen();
+                let [v0, v1, _] = v.into_col_arrays();
+                let (v0, v1) = (Vec3::from(v0), Vec3::from(v1));
+                // The images of the eigenvectors are orthogonal, and their lengths are the singular
+                // values; they are the columns of U in the SVD M = U * Σ * Vᵀ, and R = U * Vᵀ.
+                let a = self * v0;
+                let u0 = if a.magnitude_squared() > T::zero() { a.normalized() } else { v0 };
+                let b = self * v1;
+                let b = b - u0 * u0.dot(b);
+                let u1 = if b.magnitude() > a.magnitude() * T::epsilon() {
+                    b.normalized()
+                } else {
+                    // Squashed onto a line (or a point): any direction orthogonal to `u0` will do,
+                    // but prefer `v1`, so that `R` is the identity for the zero matrix.
+                    let w = v1 - u0 * u0.dot(v1);
+                    let half = T::one() / (T::one() + T::one());
+                    if w.magnitude() > half {
+                        w.normalized()
+                    } else {
+                        let axis = if u0.x.abs() <= u0.y.abs() && u0.x.abs() <= u0.z.abs() {
+                            Vec3::unit_x()
+                        } else if u0.y.abs() <= u0.z.abs() {
+                            Vec3::unit_y()
+                        } else {
+                            Vec3::unit_z()
+                        };
+                        u0.cross(axis).normalized()
+                    }
+                };
+                // Completing U with a cross product makes it (and thus R) a proper rotation.
+                let u = Self::from_col_arrays([u0.into_array(), u1.into_array(), u0.cross(u1).into_array()]);
+                let r = u * v.transposed();
+                let s = r.transposed() * self;
+                let two = T::one() + T::one();
+                (r, (s + s.transposed()).map(|x| x / two))
+            }
+        }
+
         use super::mat4::Mat4;
         impl<T> From<Mat4<T>> for Mat3<T> {
             fn from(m: Mat4<T>) -> Self {
@@ -3959,9 +4344,10 @@ macro_rules! mat_impl_mat2 {
                 Self::rotation_z(angle_radians) * self
             }
             /// Creates a matrix that rotates around the Z axis (counter-clockwise rotation in 2D).
-            pub fn rotation_z(angle_radians: T) -> Self where T: Real {
-                let c = angle_radians.cos();
-                let s = angle_radians.sin();
+            pub fn rotation_z<A: IntoAngleRadians<T>>(angle_radians: A) -> Self where T: Real {
+                let angle_radians = angle_radians.into_angle_radians();
+                let c = cos(angle_radians);
+                let s = sin(angle_radians);
                 Self::new(
                     c, -s,
                     s,  c
@@ -4026,6 +4412,54 @@ macro_rules! mat_impl_mat2 {
                 )
             }
         }
+        impl<T: Real> Mat2<T> {
+            /// Computes the polar decomposition of this matrix, i.e a rotation `R` and a symmetric
+            /// matrix `S` such that `self == R * S`, in closed form.
+            ///
+            /// `R` is always a proper rotation (orthonormal, with a determinant of 1), which is the
+            /// rotation closest to this matrix. If this matrix contains a reflection (i.e its determinant
+            /// is negative), the flip is absorbed into `S`, which then has a negative eigenvalue.
+            /// Otherwise `S` is positive semi-definite. The zero matrix gives the identity for `R`.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{Mat2, Vec2};
+            /// # fn main() {
+            /// let rotation = Mat2::rotation_z(1.2_f64);
+            /// let stretch = Mat2::new(
+            ///     2., 0.5,
+            ///     0.5, 1.,
+            /// );
+            /// let (r, s) = (rotation * stretch).polar_decomposition();
+            /// assert_relative_eq!(r, rotation, epsilon = 1e-12);
+            /// assert_relative_eq!(s, stretch, epsilon = 1e-12);
+            ///
+            /// let m = Mat2::<f64>::scaling_2d(Vec2::new(-2., 3.));
+            /// let (r, s) = m.polar_decomposition();
+            /// assert_relative_eq!(r * s, m, epsilon = 1e-12);
+            /// assert_relative_eq!(r.determinant(), 1., epsilon = 1e-12);
+            /// # }
+            /// ```
+            pub fn polar_decomposition(self) -> (Self, Self) where T: MulAdd<T,T,Output=T> {
+                let [[a, b], [c, d]] = self.into_row_arrays();
+                // Rotating by the angle of this vector makes Rᵀ * M symmetric, with the largest trace.
+                let (x, y) = (a + d, c - b);
+                let norm = x.hypot(y);
+                let r = if norm > T::zero() {
+                    let (cos, sin) = (x / norm, y / norm);
+                    Self::new(
+                        cos, -sin,
+                        sin, cos
+                    )
+                } else {
+                    Self::identity()
+                };
+                let s = r.transposed() * self;
+                let two = T::one() + T::one();
+                (r, (s + s.transposed()).map(|x| x / two))
+            }
+        }
         use super::mat3::Mat3;
         impl<T> From<Mat3<T>> for Mat2<T> {
             fn from(m: Mat3<T>) -> Self {
@@ -4057,6 +4491,146 @@ macro_rules! mat_impl_mat2 {
 
 
 
+#[cfg(feature="std")]
+macro_rules! mat_impl_matrix_stack {
+    () => {
+        /// A stack of 4x4 matrices, for hierarchical transforms (e.g. immediate-mode rendering
+        /// or scene graph traversal).
+        ///
+        /// The stack is never empty: its bottom-most element (the root) can be modified,
+        /// but never popped.
+        ///
+        /// Operations are explicit about the order of multiplication: `mul_local()` (and the
+        /// `translate()`, `rotate_*()` and `scale()` conveniences) post-multiply the current matrix,
+        /// i.e apply the transform in the current local space, like OpenGL's legacy matrix stack does.
+        /// `mul_global()` pre-multiplies it instead.
+        ///
+        /// ```
+        /// # extern crate vek;
+        /// # #[macro_use] extern crate approx;
+        /// # use vek::{MatrixStack, Vec3};
+        /// # fn main() {
+        /// let mut stack = MatrixStack::<f32>::new();
+        /// stack.translate(Vec3::new(1., 0., 0.));
+        /// stack.scoped(|stack| {
+        ///     stack.scale(Vec3::broadcast(2.));
+        ///     assert_relative_eq!(stack.current().mul_point(Vec3::unit_y()), Vec3::new(1., 2., 0.));
+        /// });
+        /// assert_relative_eq!(stack.current().mul_point(Vec3::unit_y()), Vec3::new(1., 1., 0.));
+        /// assert_eq!(stack.depth(), 1);
+        /// # }
+        /// ```
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct MatrixStack<T> {
+            stack: Vec<Mat4<T>>,
+            // Number of matrices which can't be popped: 1 for the root, more within `scoped()`.
+            floor: usize,
+        }
+
+        impl<T: Zero + One> Default for MatrixStack<T> {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl<T> MatrixStack<T> {
+            /// Creates a stack whose root is the identity matrix.
+            pub fn new() -> Self where T: Zero + One {
+                Self::with_root(Mat4::identity())
+            }
+            /// Creates a stack with the given root matrix.
+            pub fn with_root(root: Mat4<T>) -> Self {
+                let mut stack = Vec::with_capacity(16);
+                stack.push(root);
+                Self { stack, floor: 1 }
+            }
+            /// Gets the number of matrices in the stack, which is always at least 1.
+            pub fn depth(&self) -> usize {
+                self.stack.len()
+            }
+            /// Gets the matrix at the top of the stack.
+            pub fn current(&self) -> &Mat4<T> {
+                self.stack.last().unwrap()
+            }
+            /// Gets a mutable reference to the matrix at the top of the stack.
+            pub fn current_mut(&mut self) -> &mut Mat4<T> {
+                self.stack.last_mut().unwrap()
+            }
+            /// Replaces the matrix at the top of the stack.
+            pub fn set(&mut self, m: Mat4<T>) {
+                *self.current_mut() = m;
+            }
+            /// Pushes a copy of the current matrix onto the stack.
+            pub fn push(&mut self) where T: Copy {
+                let m = *self.current();
+                self.stack.push(m);
+            }
+            /// Pops the matrix at the top of the stack and returns it.
+            ///
+            /// The root is never popped: this returns `None` and leaves the stack unchanged
+            /// if the stack only contains its root. Likewise, within `scoped()`, the matrix it
+            /// pushed is never popped.
+            pub fn pop(&mut self) -> Option<Mat4<T>> {
+                if self.stack.len() <= self.floor {
+                    return None;
+                }
+                self.stack.pop()
+            }
+            /// Pushes a copy of the current matrix, calls `f`, then restores the stack as it
+            /// was before this call, even if `f` left it unbalanced.
+            ///
+            /// Within `f`, the pushed copy acts as the root: `pop()` returns `None` rather than
+            /// popping it, so `f` can't pop or modify the matrices below it.
+            pub fn scoped<F, R>(&mut self, f: F) -> R where F: FnOnce(&mut Self) -> R, T: Copy {
+                let (depth, floor) = (self.depth(), self.floor);
+                self.push();
+                self.floor = depth + 1;
+                let r = f(self);
+                self.stack.truncate(depth);
+                self.floor = floor;
+                r
+            }
+            /// Post-multiplies the current matrix by `m` (i.e `current = current * m`), such that
+            /// `m` is applied in the current local space.
+            pub fn mul_local(&mut self, m: Mat4<T>) where T: MulAdd<T,T,Output=T> + Mul<Output=T> + Copy {
+                let current = self.current_mut();
+                *current = *current * m;
+            }
+            /// Pre-multiplies the current matrix by `m` (i.e `current = m * current`), such that
+            /// `m` is applied after the current transform, in global space.
+            pub fn mul_global(&mut self, m: Mat4<T>) where T: MulAdd<T,T,Output=T> + Mul<Output=T> + Copy {
+                let current = self.current_mut();
+                *current = m * *current;
+            }
+            /// Translates the current local space (see `mul_local()`).
+            pub fn translate<V: Into<Vec3<T>>>(&mut self, v: V) where T: Real + MulAdd<T,T,Output=T> {
+                self.mul_local(Mat4::translation_3d(v));
+            }
+            /// Rotates the current local space around a 3D axis (see `mul_local()`).
+            /// The axis is not required to be normalized.
+            pub fn rotate_3d<V: Into<Vec3<T>>>(&mut self, angle_radians: T, axis: V) where T: Real + MulAdd<T,T,Output=T> + Add<T, Output=T> {
+                self.mul_local(Mat4::rotation_3d(angle_radians, axis));
+            }
+            /// Rotates the current local space around the X axis (see `mul_local()`).
+            pub fn rotate_x(&mut self, angle_radians: T) where T: Real + MulAdd<T,T,Output=T> {
+                self.mul_local(Mat4::rotation_x(angle_radians));
+            }
+            /// Rotates the current local space around the Y axis (see `mul_local()`).
+            pub fn rotate_y(&mut self, angle_radians: T) where T: Real + MulAdd<T,T,Output=T> {
+                self.mul_local(Mat4::rotation_y(angle_radians));
+            }
+            /// Rotates the current local space around the Z axis (see `mul_local()`).
+            pub fn rotate_z(&mut self, angle_radians: T) where T: Real + MulAdd<T,T,Output=T> {
+                self.mul_local(Mat4::rotation_z(angle_radians));
+            }
+            /// Scales the current local space (see `mul_local()`).
+            pub fn scale<V: Into<Vec3<T>>>(&mut self, v: V) where T: Real + MulAdd<T,T,Output=T> {
+                self.mul_local(Mat4::scaling_3d(v));
+            }
+        }
+    };
+}
+
 macro_rules! mat_impl_all_mats {
     ($lines:ident) => {
         /// 4x4 matrix.
@@ -4072,8 +4646,12 @@ macro_rules! mat_impl_all_mats {
             }
             mat_impl_mat!{$lines Mat4 RowMatrix4 ColumnMatrix4 CVec4 Vec4 (4 x 4) (x y z w)}
             mat_impl_mat4!{$lines}
+            #[cfg(feature="std")]
+            mat_impl_matrix_stack!{}
         }
         pub use self::mat4::Mat4;
+        #[cfg(feature="std")]
+        pub use self::mat4::MatrixStack;
 
         /// 3x3 matrix.
         pub mod mat3 {
@@ -4110,6 +4688,134 @@ macro_rules! mat_impl_all_mats {
     }
 }
 
+macro_rules! mat_impl_mat3x4 {
+    () => {
+        /// 3x4 affine matrix, stored as three `Vec4` rows.
+        ///
+        /// This is a 4x4 matrix whose last row, assumed to be `(0, 0, 0, 1)`, is dropped.
+        /// It's typically used for skinning matrices and other affine transforms that are
+        /// uploaded to the GPU, where it saves a fourth of the bandwidth.
+        ///
+        /// Regardless of the `column_major` or `row_major` layout, the storage is always
+        /// three rows, i.e the elements are stored in the order
+        /// `m00, m01, m02, m03, m10, ..., m23`. This matches e.g GLSL's `mat3x4` with `row_major` qualifier
+        /// and HLSL's `float3x4`.
+        ///
+        /// ```
+        /// # extern crate vek;
+        /// # #[macro_use] extern crate approx;
+        /// # use vek::{Mat3x4, Mat4, Vec3};
+        /// # fn main() {
+        /// let m = Mat4::<f32>::translation_3d(Vec3::new(1., 2., 3.)) * Mat4::rotation_z(0.5);
+        /// let p = Vec3::new(4., 5., 6.);
+        /// assert_relative_eq!(Mat3x4::from_affine_mat4(m).mul_point(p), m.mul_point(p));
+        /// assert_eq!(Mat4::from(Mat3x4::from_affine_mat4(m)), m);
+        /// # }
+        /// ```
+        #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+        #[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+        #[repr(C)]
+        pub struct Mat3x4<T> {
+            #[allow(missing_docs)]
+            pub rows: CVec3<Vec4<T>>,
+        }
+
+        /// The default value for a 3x4 matrix is the identity.
+        impl<T: Zero + One> Default for Mat3x4<T> {
+            fn default() -> Self {
+                Self::identity()
+            }
+        }
+
+        impl<T> Mat3x4<T> {
+            /// Creates a new 3x4 matrix from elements.
+            ///
+            /// The parameters are named `mij` where `i` is the row index and
+            /// `j` the column index.
+            #[allow(clippy::too_many_arguments)]
+            pub fn new(
+                m00: T, m01: T, m02: T, m03: T,
+                m10: T, m11: T, m12: T, m13: T,
+                m20: T, m21: T, m22: T, m23: T
+            ) -> Self {
+                Self {
+                    rows: CVec3::new(
+                        Vec4::new(m00, m01, m02, m03),
+                        Vec4::new(m10, m11, m12, m13),
+                        Vec4::new(m20, m21, m22, m23)
+                    )
+                }
+            }
+            /// The identity matrix.
+            pub fn identity() -> Self where T: Zero + One {
+                Self::new(
+                    T::one(), T::zero(), T::zero(), T::zero(),
+                    T::zero(), T::one(), T::zero(), T::zero(),
+                    T::zero(), T::zero(), T::one(), T::zero()
+                )
+            }
+            /// Converts this matrix into an array of elements, in storage (row-major) order.
+            pub fn into_row_array(self) -> [T; 12] {
+                let CVec3 { x, y, z } = self.rows;
+                let (x, y, z) = (x.into_array(), y.into_array(), z.into_array());
+                let [m00, m01, m02, m03] = x;
+                let [m10, m11, m12, m13] = y;
+                let [m20, m21, m22, m23] = z;
+                [m00, m01, m02, m03, m10, m11, m12, m13, m20, m21, m22, m23]
+            }
+            /// Creates a matrix from an array of elements, in storage (row-major) order.
+            pub fn from_row_array(array: [T; 12]) -> Self {
+                let [m00, m01, m02, m03, m10, m11, m12, m13, m20, m21, m22, m23] = array;
+                Self::new(m00, m01, m02, m03, m10, m11, m12, m13, m20, m21, m22, m23)
+            }
+            /// Creates a matrix from the first three rows of a 4x4 matrix (of either layout),
+            /// discarding its last row.
+            ///
+            /// This only preserves the transform if `m` is affine, i.e if its last row is
+            /// `(0, 0, 0, 1)`, which is not checked: e.g the last row of a projection matrix
+            /// is discarded as well.
+            pub fn from_affine_mat4<M: Into<row_major::Mat4<T>>>(m: M) -> Self {
+                let [x, y, z, _] = m.into().into_row_arrays();
+                Self { rows: CVec3::new(x.into(), y.into(), z.into()) }
+            }
+            /// Transforms a 3D point (i.e as if its W element was 1).
+            pub fn mul_point(self, p: Vec3<T>) -> Vec3<T> where T: Real + MulAdd<T,T,Output=T> {
+                let p = Vec4::from_point(p);
+                Vec3::new(self.rows.x.dot(p), self.rows.y.dot(p), self.rows.z.dot(p))
+            }
+            /// Transforms a 3D direction (i.e as if its W element was 0).
+            pub fn mul_direction(self, d: Vec3<T>) -> Vec3<T> where T: Real + MulAdd<T,T,Output=T> {
+                let d = Vec4::from_direction(d);
+                Vec3::new(self.rows.x.dot(d), self.rows.y.dot(d), self.rows.z.dot(d))
+            }
+        }
+
+        /// Sets the last row of the 4x4 matrix to `(0, 0, 0, 1)`.
+        impl<T: Zero + One> From<Mat3x4<T>> for column_major::Mat4<T> {
+            fn from(m: Mat3x4<T>) -> Self {
+                let CVec3 { x, y, z } = m.rows;
+                Self::from_row_arrays([x.into_array(), y.into_array(), z.into_array(), [T::zero(), T::zero(), T::zero(), T::one()]])
+            }
+        }
+        /// Sets the last row of the 4x4 matrix to `(0, 0, 0, 1)`.
+        impl<T: Zero + One> From<Mat3x4<T>> for row_major::Mat4<T> {
+            fn from(m: Mat3x4<T>) -> Self {
+                let CVec3 { x, y, z } = m.rows;
+                Self::from_row_arrays([x.into_array(), y.into_array(), z.into_array(), [T::zero(), T::zero(), T::zero(), T::one()]])
+            }
+        }
+        /// A `Mat3x4` can be obtained from a `Transform`, by rotating, then scaling, then
+        /// translating.
+        impl<T> From<Transform<T,T,T>> for Mat3x4<T>
+            where T: Real + MulAdd<T,T,Output=T>
+        {
+            fn from(xform: Transform<T,T,T>) -> Self {
+                Self::from_affine_mat4(column_major::Mat4::from(xform))
+            }
+        }
+    };
+}
+
 macro_rules! mat_declare_modules {
     () => {
         pub mod column_major {
@@ -4136,6 +4842,9 @@ macro_rules! mat_declare_modules {
             use super::*;
             mat_impl_all_mats!{rows}
         }
+
+        mat_impl_mat3x4!{}
+
         /// Column-major layout is the default.
         ///
         /// Rationale:
@@ -4160,6 +4869,8 @@ pub mod repr_c {
 
     use super::quaternion::repr_c::Quaternion;
     use super::transform::repr_c::Transform;
+    use super::vec::repr_c::Extent2;
+    use crate::geom::repr_c::{Aabr, Ray};
 
     mat_declare_modules!{}
 }
@@ -4182,6 +4893,8 @@ pub mod repr_simd {
 
     use super::quaternion::repr_simd::Quaternion;
     use super::transform::repr_simd::Transform;
+    use super::vec::repr_simd::Extent2;
+    use crate::geom::repr_simd::{Aabr, Ray};
 
     mat_declare_modules!{}
 }
@@ -4287,6 +5000,17 @@ mod tests {
         assert_eq!(a * v, b * v);
     }
 
+    #[test]
+    fn default_is_identity() {
+        use super::{Mat2, Mat3, Mat3x4};
+        assert_eq!(Mat2::<i32>::default(), Mat2::identity());
+        assert_eq!(Mat3::<f32>::default(), Mat3::identity());
+        assert_eq!(Mat4::<f64>::default(), Mat4::identity());
+        assert_eq!(Mat3x4::<f32>::default(), Mat3x4::identity());
+        let v = Vec4::new(1_f32, -2., 3., 1.);
+        assert_eq!(Mat4::<f32>::default() * v, v);
+    }
+
     #[test]
     fn test_model_look_at_rh() {
         let eye = Vec4::new(1_f32, 0., 1., 1.);
@@ -4334,4 +5058,577 @@ mod tests {
         assert_relative_eq!(view * target, Vec4::new(0_f32, 0., 2_f32.sqrt(), 1.));
     }
 
+    mod symmetric_eigen {
+        use crate::mat::Mat3;
+        use crate::vec::Vec3;
+
+        fn check(m: Mat3<f64>) {
+            let (values, vectors) = m.symmetric_eigen();
+            assert!(values.x >= values.y && values.y >= values.z, "{}", values);
+            assert_relative_eq!(vectors.determinant(), 1., epsilon = 1e-12);
+            assert_relative_eq!(vectors.transposed() * vectors, Mat3::identity(), epsilon = 1e-12);
+            let scale = m.into_row_array().iter().fold(1_f64, |acc, x| acc.max(x.abs()));
+            assert_relative_eq!(vectors * Mat3::scaling_3d(values) * vectors.transposed(), m, epsilon = 1e-12 * scale);
+            for i in 0..3 {
+                assert_relative_eq!(m * vectors.cols[i], vectors.cols[i] * values[i], epsilon = 1e-12 * scale);
+            }
+        }
+
+        #[test] fn random_matrices() {
+            let mut state = 12345_u32;
+            let mut next = move || {
+                state = state.wrapping_mul(1664525).wrapping_add(1013904223);
+                (state >> 8) as f64 / (1 << 24) as f64 * 20. - 10.
+            };
+            for _ in 0..500 {
+                let (a, b, c, d, e, f) = (next(), next(), next(), next(), next(), next());
+                check(Mat3::new(a, b, c, b, d, e, c, e, f));
+            }
+        }
+        #[test] fn repeated_and_zero_eigenvalues() {
+            check(Mat3::identity());
+            check(Mat3::zero());
+            check(Mat3::new(2., 0., 0., 0., 1., 0., 0., 0., 2.));
+            check(Mat3::new(1., 1., 1., 1., 1., 1., 1., 1., 1.));
+            let (values, _) = Mat3::new(1., 1., 1., 1., 1., 1., 1., 1., 1.).symmetric_eigen();
+            assert_relative_eq!(values, Vec3::new(3., 0., 0.), epsilon = 1e-12);
+            // Already diagonal, in increasing order.
+            let (values, vectors) = Mat3::new(-1., 0., 0., 0., 2., 0., 0., 0., 3.).symmetric_eigen();
+            assert_eq!(values, Vec3::new(3., 2., -1.));
+            assert_eq!(vectors.cols[0].map(f64::abs), Vec3::unit_z());
+        }
+        #[test] fn lower_triangle_is_ignored() {
+            let upper = Mat3::new(1., 2., 3., 0., 4., 5., 0., 0., 6.);
+            let symmetric = Mat3::new(1., 2., 3., 2., 4., 5., 3., 5., 6.);
+            assert_eq!(upper.symmetric_eigen(), symmetric.symmetric_eigen());
+        }
+    }
+
+    #[cfg(feature="std")]
+    mod planar {
+        use crate::mat::{row_major, column_major};
+        use crate::vec::{Vec3, Vec4};
+        use crate::geom::Plane;
+
+        fn planes() -> [Plane<f64>; 3] {
+            [
+                Plane::new(Vec3::unit_y(), 0.),
+                Plane::from_point_and_normal(Vec3::new(1., 2., 3.), Vec3::new(1., -2., 0.5)),
+                Plane::from_point_and_normal(Vec3::new(-4., 0., 1.), Vec3::new(-1., -1., -1.)),
+            ]
+        }
+        fn points() -> [Vec3<f64>; 4] {
+            [Vec3::new(0., 5., 0.), Vec3::new(1., -2., 3.), Vec3::new(-7., 4., 2.), Vec3::new(0.5, 0.25, -9.)]
+        }
+
+        #[test] fn reflection() {
+            for &plane in planes().iter() {
+                let m = column_major::Mat4::planar_reflection(plane);
+                assert_relative_eq!(m * m, column_major::Mat4::identity(), epsilon = 1e-12);
+                assert_relative_eq!(m.determinant(), -1., epsilon = 1e-12);
+                for &p in points().iter() {
+                    assert_relative_eq!(Vec3::from(m * Vec4::from_point(p)), plane.reflected_point(p), epsilon = 1e-12);
+                    // Points on the plane stay where they are.
+                    let q = plane.projected_point(p);
+                    assert_relative_eq!(Vec3::from(m * Vec4::from_point(q)), q, epsilon = 1e-12);
+                }
+                let rows = row_major::Mat4::planar_reflection(plane);
+                assert_eq!(rows.into_row_array(), m.into_row_array());
+            }
+        }
+        #[test] fn shadow_from_point_light() {
+            let light = Vec3::new(2., 20., -1.);
+            for &plane in planes().iter() {
+                let m = column_major::Mat4::planar_shadow(plane, Vec4::from_point(light));
+                for &p in points().iter() {
+                    let s = m * Vec4::from_point(p);
+                    let s = Vec3::from(s) / s.w;
+                    assert_relative_eq!(plane.signed_distance_to_point(s), 0., epsilon = 1e-9);
+                    // The shadow is on the line from the light through the point.
+                    assert_relative_eq!((s - light).cross(p - light).magnitude(), 0., epsilon = 1e-9);
+                }
+                // The normal's length and orientation don't matter.
+                let flipped = Plane::new(plane.normal * -3., plane.distance * -3.);
+                let p = m * Vec4::from_point(points()[1]);
+                let q = column_major::Mat4::planar_shadow(flipped, Vec4::from_point(light)) * Vec4::from_point(points()[1]);
+                assert_relative_eq!(Vec3::from(p) / p.w, Vec3::from(q) / q.w, epsilon = 1e-9);
+                let rows = row_major::Mat4::planar_shadow(plane, Vec4::from_point(light));
+                assert_eq!(rows.into_row_array(), m.into_row_array());
+            }
+        }
+        #[test] fn shadow_from_directional_light() {
+            let direction = Vec3::new(1., 3., 0.5);
+            for &plane in planes().iter() {
+                let m = column_major::Mat4::planar_shadow(plane, Vec4::from_direction(direction));
+                // The projection is parallel: every point moves along the light's direction.
+                for &p in points().iter() {
+                    let s = m * Vec4::from_point(p);
+                    let s = Vec3::from(s) / s.w;
+                    assert_relative_eq!(plane.signed_distance_to_point(s), 0., epsilon = 1e-9);
+                    assert_relative_eq!((s - p).cross(direction).magnitude(), 0., epsilon = 1e-9);
+                }
+                // It is affine, so parallel lines stay parallel.
+                let w = m * Vec4::new(0., 0., 0., 1.);
+                assert_relative_eq!(m.cols.x.w, 0.);
+                assert_relative_eq!(m.cols.y.w, 0.);
+                assert_relative_eq!(m.cols.z.w, 0.);
+                assert_relative_eq!(w.w, plane.normal.dot(direction), epsilon = 1e-12);
+            }
+        }
+    }
+    mod viewport_rays {
+        use alloc::vec::Vec;
+        use crate::mat::Mat4;
+        use crate::vec::{Vec2, Vec3, Extent2};
+        use crate::geom::{Aabr, Rect, FrustumPlanes, Ray};
+
+        // Unprojects the near point and a point further away, for each pixel independently.
+        fn reference(view: Mat4<f64>, proj: Mat4<f64>, viewport: Extent2<u32>, pixel: Vec2<u32>, no: bool) -> Ray<f64> {
+            let rect = Rect { x: 0., y: 0., w: viewport.w as f64, h: viewport.h as f64 };
+            let unproject = |z| {
+                let p = Vec3::new(pixel.x as f64 + 0.5, pixel.y as f64 + 0.5, z);
+                if no { Mat4::viewport_to_world_no(p, view, proj, rect) } else { Mat4::viewport_to_world_zo(p, view, proj, rect) }
+            };
+            let origin = unproject(0.);
+            Ray::new(origin, (unproject(0.5) - origin).normalized())
+        }
+        fn check(view: Mat4<f64>, proj: Mat4<f64>, no: bool) {
+            let viewport = Extent2::new(160, 90);
+            let tile = Aabr { min: Vec2::new(0, 0), max: Vec2::new(160, 90) };
+            let inverse = (proj * view).inverted();
+            let rays: Vec<_> = if no {
+                Mat4::viewport_rays_no(inverse, viewport, tile).collect()
+            } else {
+                Mat4::viewport_rays_zo(inverse, viewport, tile).collect()
+            };
+            assert_eq!(rays.len(), 160 * 90);
+            for (i, &(pixel, ray)) in rays.iter().enumerate() {
+                assert_eq!(pixel, Vec2::new(i as u32 % 160, i as u32 / 160));
+                if i % 37 == 0 {
+                    let expected = reference(view, proj, viewport, pixel, no);
+                    assert_relative_eq!(ray.origin, expected.origin, epsilon = 1e-9);
+                    assert_relative_eq!(ray.direction, expected.direction, epsilon = 1e-9);
+                }
+            }
+        }
+
+        #[test] fn perspective() {
+            let view = Mat4::look_at_rh(Vec3::new(1., 2., 5.), Vec3::new(0., 0.5, 0.), Vec3::unit_y());
+            check(view, Mat4::perspective_rh_no(1.2, 16. / 9., 0.1, 100.), true);
+            check(view, Mat4::perspective_rh_zo(1.2, 16. / 9., 0.1, 100.), false);
+            // All rays come from the eye.
+            let inverse = (Mat4::perspective_rh_no(1.2, 16. / 9., 0.1, 100.) * view).inverted();
+            let tile = Aabr { min: Vec2::new(10, 20), max: Vec2::new(30, 25) };
+            for (_, ray) in Mat4::viewport_rays_no(inverse, Extent2::new(160, 90), tile) {
+                let eye = ray.origin - ray.direction * ray.origin.distance(Vec3::new(1., 2., 5.));
+                assert_relative_eq!(eye, Vec3::new(1., 2., 5.), epsilon = 1e-9);
+            }
+        }
+        #[test] fn orthographic() {
+            let view = Mat4::look_at_rh(Vec3::new(0., 0., 5.), Vec3::zero(), Vec3::unit_y());
+            let planes = FrustumPlanes { left: -8., right: 8., bottom: -4.5, top: 4.5, near: 1., far: 10. };
+            check(view, Mat4::orthographic_rh_no(planes), true);
+            check(view, Mat4::orthographic_rh_zo(planes), false);
+        }
+        #[test] fn tile_is_clipped() {
+            let tile = Aabr { min: Vec2::new(150, 80), max: Vec2::new(200, 200) };
+            let pixels: Vec<_> = Mat4::viewport_rays_no(Mat4::<f32>::identity(), Extent2::new(160, 90), tile).map(|(p, _)| p).collect();
+            assert_eq!(pixels.len(), 100);
+            assert_eq!(pixels[0], Vec2::new(150, 80));
+            assert_eq!(pixels[99], Vec2::new(159, 89));
+            let outside = Aabr { min: Vec2::new(170, 0), max: Vec2::new(200, 90) };
+            assert_eq!(Mat4::viewport_rays_zo(Mat4::<f32>::identity(), Extent2::new(160, 90), outside).count(), 0);
+        }
+    }
+
+    mod polar_decomposition {
+        use crate::mat::{Mat2, Mat3};
+        use crate::vec::{Vec2, Vec3};
+
+        fn random() -> impl FnMut() -> f64 {
+            let mut state = 54321_u32;
+            move || {
+                state = state.wrapping_mul(1664525).wrapping_add(1013904223);
+                (state >> 8) as f64 / (1 << 24) as f64 * 2. - 1.
+            }
+        }
+        fn check3(m: Mat3<f64>) -> (Mat3<f64>, Mat3<f64>) {
+            let (r, s) = m.polar_decomposition();
+            let scale = m.into_row_array().iter().fold(1_f64, |acc, x| acc.max(x.abs()));
+            assert_relative_eq!(r.transposed() * r, Mat3::identity(), epsilon = 1e-12);
+            assert_relative_eq!(r.determinant(), 1., epsilon = 1e-12);
+            assert_eq!(s, s.transposed());
+            assert_relative_eq!(r * s, m, epsilon = 1e-12 * scale);
+            // The flip of a reflection goes into the stretch.
+            let (values, _) = s.symmetric_eigen();
+            assert!(values.y >= -1e-12 * scale);
+            assert_eq!(values.z < -1e-12 * scale, m.determinant() < -1e-9 * scale * scale * scale);
+            (r, s)
+        }
+        fn check2(m: Mat2<f64>) -> (Mat2<f64>, Mat2<f64>) {
+            let (r, s) = m.polar_decomposition();
+            let scale = m.into_row_array().iter().fold(1_f64, |acc, x| acc.max(x.abs()));
+            assert_relative_eq!(r.transposed() * r, Mat2::identity(), epsilon = 1e-12);
+            assert_relative_eq!(r.determinant(), 1., epsilon = 1e-12);
+            assert_eq!(s, s.transposed());
+            assert_relative_eq!(r * s, m, epsilon = 1e-12 * scale);
+            // The trace is maximal, so the stretch has at most one negative eigenvalue.
+            assert!(s[(0, 0)] + s[(1, 1)] >= 0.);
+            (r, s)
+        }
+
+        #[test] fn pure_rotation() {
+            let rotation = Mat3::rotation_3d(2.5, Vec3::new(-1., 0.5, 2.));
+            let (r, s) = check3(rotation);
+            assert_relative_eq!(r, rotation, epsilon = 1e-12);
+            assert_relative_eq!(s, Mat3::identity(), epsilon = 1e-12);
+            let (r, s) = check2(Mat2::rotation_z(-2.));
+            assert_relative_eq!(r, Mat2::rotation_z(-2.), epsilon = 1e-12);
+            assert_relative_eq!(s, Mat2::identity(), epsilon = 1e-12);
+        }
+        #[test] fn pure_scale() {
+            let scale = Mat3::scaling_3d(Vec3::new(2., 0.5, 3.));
+            assert_relative_eq!(check3(scale).0, Mat3::identity(), epsilon = 1e-12);
+            assert_relative_eq!(check3(scale).1, scale, epsilon = 1e-12);
+            let scale = Mat2::scaling_2d(Vec2::new(0.25, 4.));
+            assert_eq!(check2(scale), (Mat2::identity(), scale));
+            // Uniform scaling is the same in every direction, which isn't a special case.
+            assert_relative_eq!(check3(Mat3::identity() * 3.).1, Mat3::identity() * 3., epsilon = 1e-12);
+        }
+        #[test] fn perturbed_deformation_gradient() {
+            let mut random = random();
+            for _ in 0..500 {
+                let rotation = Mat3::rotation_3d(random() * 3., Vec3::new(random(), random(), random()));
+                let perturbation = Mat3::new(
+                    random(), random(), random(),
+                    random(), random(), random(),
+                    random(), random(), random(),
+                ) * 0.3;
+                let (r, s) = check3(rotation * (perturbation + Mat3::identity()));
+                // A small deformation doesn't change the rotation much.
+                assert_relative_eq!(r, rotation, epsilon = 0.9);
+                assert!(s.determinant() > 0.);
+                let rotation = Mat2::rotation_z(random() * 3.);
+                check2(rotation * (Mat2::new(random(), random(), random(), random()) * 0.3 + Mat2::identity()));
+                // Arbitrary matrices, with reflections half of the time.
+                check3(Mat3::new(
+                    random(), random(), random(),
+                    random(), random(), random(),
+                    random(), random(), random(),
+                ) * 10.);
+                check2(Mat2::new(random(), random(), random(), random()) * 10.);
+            }
+        }
+        #[test] fn reflection() {
+            let m = Mat3::rotation_x(0.3) * Mat3::scaling_3d(Vec3::new(3., -1., 2.));
+            let (r, s) = check3(m);
+            // The least stretched axis is y, which takes the flip.
+            assert_relative_eq!(r, Mat3::rotation_x(0.3), epsilon = 1e-12);
+            assert_relative_eq!(s, Mat3::scaling_3d(Vec3::new(3., -1., 2.)), epsilon = 1e-12);
+            let (_, s) = check2(Mat2::new(0., 1., 1., 0.));
+            assert_relative_eq!(s.determinant(), -1., epsilon = 1e-12);
+        }
+        #[test] fn singular() {
+            assert_eq!(check3(Mat3::zero()).0, Mat3::identity());
+            assert_eq!(check2(Mat2::zero()).0, Mat2::identity());
+            // Flattened onto a plane, a line, or almost.
+            check3(Mat3::rotation_z(1.) * Mat3::scaling_3d(Vec3::new(2., 1., 0.)));
+            check3(Mat3::rotation_z(1.) * Mat3::scaling_3d(Vec3::new(2., 1., 1e-9)));
+            check3(Mat3::rotation_z(1.) * Mat3::scaling_3d(Vec3::new(2., 0., 0.)));
+            check3(Mat3::new(1., 2., 3., 2., 4., 6., -1., -2., -3.));
+            check3(Mat3::new(0., 0., 0., 0., 0., 0., 0., 0., 1e-30));
+            check2(Mat2::new(1., 2., 2., 4.));
+            check2(Mat2::new(1., 2., -2., -4.));
+        }
+    }
+
+    mod mat3x4 {
+        use crate::mat::repr_c::{Mat3x4, column_major::Mat4 as Cols4, row_major::Mat4 as Rows4};
+        use crate::vec::Vec3;
+        use crate::quaternion::Quaternion;
+        use crate::transform::Transform;
+        use std::mem;
+
+        fn affine() -> Cols4<f32> {
+            Cols4::<f32>::translation_3d(Vec3::new(1., -2., 3.)) * Cols4::rotation_3d(0.7, Vec3::new(1., 1., 0.)) * Cols4::scaling_3d(Vec3::new(2., 3., 4.))
+        }
+
+        #[test] fn size_and_align() {
+            assert_eq!(mem::size_of::<Mat3x4<f32>>(), 48);
+            assert_eq!(mem::align_of::<Mat3x4<f32>>(), mem::align_of::<f32>());
+            assert_eq!(mem::size_of::<Mat3x4<f64>>(), 96);
+        }
+        #[test] fn upload_order() {
+            let m = affine();
+            let a = Mat3x4::from_affine_mat4(m);
+            let packed = unsafe { ::std::slice::from_raw_parts(&a as *const _ as *const f32, 12) };
+            assert_eq!(packed, &m.into_row_array()[..12]);
+            assert_eq!(a.into_row_array(), Mat3x4::from_affine_mat4(Rows4::from(m)).into_row_array());
+            assert_eq!(Mat3x4::from_row_array(a.into_row_array()), a);
+        }
+        #[test] fn round_trip() {
+            let m = affine();
+            assert_eq!(Cols4::from(Mat3x4::from_affine_mat4(m)), m);
+            assert_eq!(Rows4::from(Mat3x4::from_affine_mat4(m)), Rows4::from(m));
+            assert_eq!(Cols4::from(Mat3x4::<f32>::identity()), Cols4::identity());
+        }
+        #[test] fn mul() {
+            let m = affine();
+            let a = Mat3x4::from_affine_mat4(m);
+            let v = Vec3::new(0.5, -1.5, 2.);
+            assert_relative_eq!(a.mul_point(v), m.mul_point(v), epsilon = 0.00001);
+            assert_relative_eq!(a.mul_direction(v), m.mul_direction(v), epsilon = 0.00001);
+        }
+        #[test] fn from_transform() {
+            let xform = Transform {
+                position: Vec3::new(1_f32, 2., 3.),
+                orientation: Quaternion::rotation_y(1.),
+                scale: Vec3::new(1., 2., 1.),
+            };
+            assert_eq!(Mat3x4::from(xform), Mat3x4::from_affine_mat4(Cols4::from(xform)));
+        }
+        #[test] fn from_projection_discards_last_row() {
+            let p = Cols4::<f32>::perspective_rh_no(1., 1., 0.1, 100.);
+            let mut affine = p;
+            affine.cols.x.w = 0.;
+            affine.cols.y.w = 0.;
+            affine.cols.z.w = 0.;
+            affine.cols.w.w = 1.;
+            assert_eq!(Cols4::from(Mat3x4::from_affine_mat4(p)), affine);
+            assert_eq!(Mat3x4::from_affine_mat4(Rows4::from(p)), Mat3x4::from_affine_mat4(p));
+        }
+    }
+
+    #[cfg(feature="std")]
+    macro_rules! test_matrix_stack {
+        ($($lines:ident)+) => {
+            $(mod $lines {
+                use crate::mat::repr_c::$lines::{Mat4, MatrixStack};
+                use crate::vec::Vec3;
+
+                #[test] fn mul_order() {
+                    let t = Mat4::<f32>::translation_3d(Vec3::new(1., 2., 3.));
+                    let r = Mat4::<f32>::rotation_z(0.5);
+                    let mut stack = MatrixStack::with_root(t);
+                    stack.mul_local(r);
+                    assert_relative_eq!(*stack.current(), t * r);
+                    stack.set(t);
+                    stack.mul_global(r);
+                    assert_relative_eq!(*stack.current(), r * t);
+                }
+                #[test] fn conveniences_are_local() {
+                    let mut stack = MatrixStack::<f32>::new();
+                    stack.translate(Vec3::new(1., 2., 3.));
+                    stack.rotate_y(0.3);
+                    stack.scale(Vec3::new(2., 2., 2.));
+                    let expected = Mat4::<f32>::translation_3d(Vec3::new(1., 2., 3.)) * Mat4::rotation_y(0.3) * Mat4::scaling_3d(Vec3::new(2., 2., 2.));
+                    assert_relative_eq!(*stack.current(), expected);
+                }
+                #[test] fn push_pop() {
+                    let mut stack = MatrixStack::<f32>::new();
+                    assert_eq!(stack.pop(), None);
+                    assert_eq!(stack.depth(), 1);
+                    stack.push();
+                    stack.translate(Vec3::unit_x());
+                    assert_eq!(stack.depth(), 2);
+                    assert_eq!(stack.pop(), Some(Mat4::translation_3d(Vec3::unit_x())));
+                    assert_eq!(*stack.current(), Mat4::identity());
+                    assert_eq!(stack.pop(), None);
+                    assert_eq!(*stack.current(), Mat4::identity());
+                }
+                #[test] fn scoped_restores_depth() {
+                    let mut stack = MatrixStack::<f32>::new();
+                    let depth = stack.scoped(|stack| {
+                        stack.push();
+                        stack.push();
+                        stack.rotate_x(1.);
+                        stack.depth()
+                    });
+                    assert_eq!(depth, 4);
+                    assert_eq!(stack.depth(), 1);
+                    assert_eq!(*stack.current(), Mat4::identity());
+                }
+                #[test] fn scoped_over_pop() {
+                    let t = Mat4::<f32>::translation_3d(Vec3::unit_x());
+                    let mut stack = MatrixStack::<f32>::new();
+                    stack.push();
+                    stack.set(t);
+                    stack.scoped(|stack| {
+                        stack.scoped(|stack| {
+                            assert!(stack.pop().is_none());
+                            assert!(stack.pop().is_none());
+                            stack.rotate_z(1.);
+                        });
+                        stack.push();
+                        assert!(stack.pop().is_some());
+                        assert!(stack.pop().is_none());
+                        assert_eq!(stack.depth(), 3);
+                        stack.scale(Vec3::broadcast(2.));
+                    });
+                    assert_eq!(stack.depth(), 2);
+                    assert_eq!(*stack.current(), t);
+                    assert_eq!(stack.pop(), Some(t));
+                    assert_eq!(stack.pop(), None);
+                    assert_eq!(*stack.current(), Mat4::identity());
+                }
+            })+
+        };
+    }
+    #[cfg(feature="std")]
+    mod matrix_stack {
+        test_matrix_stack!{column_major row_major}
+    }
+
+    mod bytes {
+        use crate::mat::repr_c::{column_major, row_major};
+
+        fn known() -> column_major::Mat4<f32> {
+            let mut a = [0_f32; 16];
+            for (i, x) in a.iter_mut().enumerate() {
+                *x = i as f32;
+            }
+            column_major::Mat4::from_col_array(a)
+        }
+
+        #[test]
+        fn golden_le() {
+            // Columns are (0, 1, 2, 3), (4, 5, 6, 7), etc; Elements are little-endian f32.
+            let golden: [u8; 16] = [
+                0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00, 0x80, 0x3f,
+                0x00, 0x00, 0x00, 0x40,
+                0x00, 0x00, 0x40, 0x40,
+            ];
+            let m = known();
+            let bytes = m.to_le_bytes();
+            assert_eq!(&bytes[..16], &golden);
+            assert_eq!(&bytes[60..], &15_f32.to_le_bytes());
+            // The encoding doesn't depend on the layout.
+            assert_eq!(row_major::Mat4::from(m).to_le_bytes()[..], bytes[..]);
+            assert_eq!(row_major::Mat4::<f32>::from_le_bytes(bytes), row_major::Mat4::from(m));
+            assert_eq!(column_major::Mat4::<f32>::from_le_bytes(bytes), m);
+        }
+        #[test]
+        fn round_trip_be() {
+            let m = known().map(|x| x as f64 * -0.5);
+            let be = m.to_be_bytes();
+            let le = m.to_le_bytes();
+            for (l, b) in le.chunks(8).zip(be.chunks(8)) {
+                assert!(l.iter().eq(b.iter().rev()));
+            }
+            assert_eq!(column_major::Mat4::<f64>::from_be_bytes(be), m);
+            let m3 = column_major::Mat3::new(1_i32, 2, 3, 4, 5, 6, 7, 8, -9);
+            let mut buf = [0_u8; 72];
+            assert_eq!(column_major::Mat3::<i32>::write_be_bytes_into(&[m3, m3], &mut buf), 72);
+            let mut out = [column_major::Mat3::zero(); 2];
+            assert_eq!(row_major::Mat3::<i32>::read_be_bytes_from(&buf, &mut [row_major::Mat3::zero(); 2]), 72);
+            column_major::Mat3::<i32>::read_be_bytes_from(&buf, &mut out);
+            assert_eq!(out, [m3, m3]);
+        }
+    }
+    mod transform_chunk {
+        use crate::mat::repr_c::{column_major, row_major};
+        use crate::vec::repr_c::Vec3;
+
+        fn points() -> [Vec3<f32>; 19] {
+            let mut p = [Vec3::zero(); 19];
+            for (i, p) in p.iter_mut().enumerate() {
+                let i = i as f32;
+                *p = Vec3::new(i, i * 0.5 - 3., 7. - i * i * 0.1);
+            }
+            p
+        }
+
+        #[test]
+        fn matches_mul_point() {
+            let c = column_major::Mat4::<f32>::rotation_3d(0.7, Vec3::new(1., 2., 3.)).translated_3d(Vec3::new(4., 5., 6.));
+            let r = row_major::Mat4::from(c);
+            let points = points();
+            let (chunks, rest) = Vec3::slice_chunks::<4>(&points);
+            assert_eq!(chunks.len(), 4);
+            assert_eq!(rest.len(), 3);
+            for (chunk, expected) in chunks.iter().zip(points.chunks(4)) {
+                for ((pc, pr), p) in c.transform_chunk(chunk).iter().zip(r.transform_chunk(chunk).iter()).zip(expected) {
+                    assert_eq!(*pc, c.mul_point(*p));
+                    assert_eq!(*pr, r.mul_point(*p));
+                }
+            }
+            assert_eq!(rest, &points[16..]);
+        }
+    }
+
+    #[cfg(nightly)]
+    mod bench {
+        extern crate test;
+        use self::test::{Bencher, black_box};
+        use crate::mat::Mat4;
+        use crate::vec::{Vec2, Vec3, Vec4, Extent2};
+        use crate::geom::{Aabr, Ray};
+
+        fn setup() -> (Mat4<f32>, [Vec3<f32>; 1024]) {
+            let m = Mat4::rotation_3d(0.7, Vec3::new(1., 2., 3.)).translated_3d(Vec3::new(4., 5., 6.));
+            let mut points = [Vec3::zero(); 1024];
+            for (i, p) in points.iter_mut().enumerate() {
+                *p = Vec3::broadcast(i as f32);
+            }
+            (m, points)
+        }
+
+        #[bench]
+        fn transform_per_point(b: &mut Bencher) {
+            let (m, mut points) = setup();
+            b.iter(|| {
+                let m = black_box(m);
+                for p in points.iter_mut() {
+                    *p = m.mul_point(*p);
+                }
+                black_box(&points);
+            });
+        }
+        #[bench]
+        fn transform_chunks_of_8(b: &mut Bencher) {
+            let (m, mut points) = setup();
+            b.iter(|| {
+                let m = black_box(m);
+                let (chunks, _) = Vec3::slice_chunks_mut::<8>(&mut points);
+                for chunk in chunks.iter_mut() {
+                    *chunk = m.transform_chunk(chunk);
+                }
+                black_box(&points);
+            });
+        }
+        fn setup_camera() -> (Mat4<f32>, Extent2<u32>, Aabr<u32>) {
+            let view = Mat4::look_at_rh(Vec3::new(1., 2., 5.), Vec3::zero(), Vec3::unit_y());
+            let proj = Mat4::perspective_rh_no(1.2, 16. / 9., 0.1, 100.);
+            let viewport = Extent2::new(1920, 1080);
+            ((proj * view).inverted(), viewport, Aabr { min: Vec2::zero(), max: Vec2::new(1920, 1080) })
+        }
+
+        #[bench]
+        fn viewport_rays_1080p(b: &mut Bencher) {
+            let (inverse, viewport, tile) = setup_camera();
+            b.iter(|| {
+                for ray in Mat4::viewport_rays_no(black_box(inverse), viewport, tile) {
+                    black_box(ray);
+                }
+            });
+        }
+        #[bench]
+        fn viewport_rays_1080p_unprojecting_each_pixel(b: &mut Bencher) {
+            let (inverse, viewport, tile) = setup_camera();
+            b.iter(|| {
+                let inverse = black_box(inverse);
+                let (w, h) = (viewport.w as f32, viewport.h as f32);
+                for y in tile.min.y..tile.max.y {
+                    for x in tile.min.x..tile.max.x {
+                        let ndc = Vec2::new((x as f32 + 0.5) * 2. / w - 1., (y as f32 + 0.5) * 2. / h - 1.);
+                        let near = inverse * Vec4::new(ndc.x, ndc.y, -1., 1.);
+                        let far = inverse * Vec4::new(ndc.x, ndc.y, 0., 1.);
+                        let origin = Vec3::<f32>::from(near / near.w);
+                        black_box((Vec2::new(x, y), Ray::new(origin, (Vec3::<f32>::from(far / far.w) - origin).normalized())));
+                    }
+                }
+            });
+        }
+    }
 }
@@ -0,0 +1,631 @@
+//! Utilities for indexed triangle meshes, given as a buffer of positions and a buffer of indices.
+//!
+//! Every 3 consecutive indices form a triangle, and triangles are expected to be
+//! wound counter-clockwise when seen from the side their normal points to.
+//! Trailing indices which do not form a full triangle are ignored.
+//!
+//! Functions which fill a `Vec` are only available with the `std` feature.
+
+use num_traits::real::Real;
+#[cfg(feature="std")]
+use alloc::vec::Vec;
+use crate::vec::repr_c::Vec3;
+use crate::geom::repr_c::Aabb;
+use crate::ops::atan2;
+#[cfg(feature="std")]
+use crate::ops::cos;
+#[cfg(feature="std")]
+use crate::sampling::spatial_hash;
+
+/// Computes the smallest `Aabb` that contains all of the given positions, or `None` if there are none.
+///
+/// ```
+/// # use vek::{Vec3, Aabb, mesh::compute_aabb};
+/// let positions = [Vec3::new(1, 2, 3), Vec3::new(-1, 5, 0), Vec3::new(0, 0, 4)];
+/// let aabb = compute_aabb(&positions).unwrap();
+/// assert_eq!(aabb, Aabb { min: Vec3::new(-1, 0, 0), max: Vec3::new(1, 5, 4) });
+/// assert_eq!(compute_aabb::<f32>(&[]), None);
+/// ```
+pub fn compute_aabb<T: Copy + PartialOrd>(positions: &[Vec3<T>]) -> Option<Aabb<T>> {
+    let (first, rest) = positions.split_first()?;
+    let mut aabb = Aabb::new_empty(*first);
+    for p in rest {
+        aabb.expand_to_contain_point(*p);
+    }
+    Some(aabb)
+}
+
+/// A borrowed indexed triangle mesh, which gives access to each corner of each triangle (or "face").
+///
+/// This is the view of a mesh that per-corner algorithms need, such as tangent space generators
+/// (e.g bindings to mikktspace, whose callbacks query positions by face and corner). The
+/// whole-buffer functions of this module are built on the same accessors.
+///
+/// ```
+/// # extern crate vek;
+/// # #[macro_use] extern crate approx;
+/// # use vek::{Vec3, mesh::IndexedMesh};
+/// # fn main() {
+/// let positions = [Vec3::new(0_f32, 0., 0.), Vec3::new(1., 0., 0.), Vec3::new(1., 1., 0.), Vec3::new(0., 1., 0.)];
+/// let indices = [0, 1, 2, 0, 2, 3];
+/// let mesh = IndexedMesh::new(&positions, &indices);
+/// assert_eq!(mesh.face_count(), 2);
+/// assert_eq!(mesh.vertex_index(1, 2), 3);
+/// assert_eq!(mesh.position(1, 2), Vec3::new(0., 1., 0.));
+/// assert_relative_eq!(mesh.corner_angle(0, 1), std::f32::consts::FRAC_PI_2);
+/// assert_relative_eq!(mesh.face_normal(1), Vec3::unit_z());
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IndexedMesh<'a, T> {
+    /// The positions of the vertices.
+    pub positions: &'a [Vec3<T>],
+    /// Indices into `positions`, 3 per triangle. Trailing indices which do not form a full
+    /// triangle are ignored.
+    pub indices: &'a [u32],
+}
+
+impl<'a, T> IndexedMesh<'a, T> {
+    /// Creates a view of the mesh given by `positions` and `indices`.
+    pub fn new(positions: &'a [Vec3<T>], indices: &'a [u32]) -> Self {
+        Self { positions, indices }
+    }
+    /// Gets the number of triangles.
+    pub fn face_count(&self) -> usize {
+        self.indices.len() / 3
+    }
+    /// Gets the index, into `positions`, of the vertex at `corner` (0, 1 or 2) of triangle `face`.
+    ///
+    /// # Panics
+    /// Panics if `face` or `corner` is out of bounds.
+    pub fn vertex_index(&self, face: usize, corner: usize) -> usize {
+        assert!(corner < 3, "triangles only have 3 corners");
+        assert!(face < self.face_count(), "face index out of bounds");
+        self.indices[face * 3 + corner] as usize
+    }
+    /// Gets the position of the vertex at `corner` (0, 1 or 2) of triangle `face`.
+    ///
+    /// # Panics
+    /// Panics if `face` or `corner` is out of bounds, or if the vertex's index is out of the
+    /// bounds of `positions`.
+    pub fn position(&self, face: usize, corner: usize) -> Vec3<T> where T: Copy {
+        self.positions[self.vertex_index(face, corner)]
+    }
+    /// Gets the positions of the corners of triangle `face`, starting with `corner` and
+    /// keeping their winding.
+    fn corners_from(&self, face: usize, corner: usize) -> (Vec3<T>, Vec3<T>, Vec3<T>) where T: Copy {
+        (self.position(face, corner), self.position(face, (corner + 1) % 3), self.position(face, (corner + 2) % 3))
+    }
+    /// Gets the normal of triangle `face`, or zero if it has no area.
+    pub fn face_normal(&self, face: usize) -> Vec3<T> where T: Real {
+        normalized_or_zero(self.weighted_face_normal(face))
+    }
+    /// Gets the non-normalized normal of triangle `face`, whose magnitude is twice its area.
+    fn weighted_face_normal(&self, face: usize) -> Vec3<T> where T: Real {
+        let (a, b, c) = self.corners_from(face, 0);
+        (b - a).cross(c - a)
+    }
+    /// Gets the interior angle, in radians, of triangle `face` at `corner` (0, 1 or 2).
+    ///
+    /// This is computed with `atan2()` rather than `acos()`, so it stays accurate for angles
+    /// close to zero or to a half turn.
+    pub fn corner_angle(&self, face: usize, corner: usize) -> T where T: Real {
+        let (a, b, c) = self.corners_from(face, corner);
+        let (ab, ac) = (b - a, c - a);
+        atan2(ab.cross(ac).magnitude(), ab.dot(ac))
+    }
+}
+
+/// Adds the normal of the triangle `corner_a`, `corner_b`, `corner_c` to `out`, weighted by
+/// the triangle's interior angle at `corner_a`.
+///
+/// Summing this over the triangles around a vertex (with `corner_a` being that vertex, and the
+/// triangles' winding preserved), then normalizing, gives the vertex's angle-weighted normal.
+/// Unlike weighting by area, this doesn't depend on how the faces around the vertex are
+/// triangulated, which is what makes shading of low-poly hard-surface models look right.
+/// It is also what most modelling tools and tangent space generators expect.
+///
+/// The angle is computed with `atan2()`, without `acos()`, so that it stays accurate for very
+/// thin triangles. Triangles with no area add nothing.
+///
+/// ```
+/// # extern crate vek;
+/// # #[macro_use] extern crate approx;
+/// # use vek::{Vec3, mesh::accumulate_angle_weighted_normal};
+/// # fn main() {
+/// let (o, x, y, z) = (Vec3::zero(), Vec3::unit_x(), Vec3::unit_y(), Vec3::unit_z());
+/// let mut normal = Vec3::zero();
+/// // A cube's corner, with one of its three faces split in two triangles.
+/// accumulate_angle_weighted_normal(&mut normal, o, y, x);
+/// accumulate_angle_weighted_normal(&mut normal, o, z, y + z);
+/// accumulate_angle_weighted_normal(&mut normal, o, y + z, y);
+/// accumulate_angle_weighted_normal(&mut normal, o, x, z);
+/// assert_relative_eq!(normal.normalized(), -Vec3::<f64>::one().normalized());
+/// # }
+/// ```
+pub fn accumulate_angle_weighted_normal<T: Real>(out: &mut Vec3<T>, corner_a: Vec3<T>, corner_b: Vec3<T>, corner_c: Vec3<T>) {
+    let (ab, ac) = (corner_b - corner_a, corner_c - corner_a);
+    let cross = ab.cross(ac);
+    let sin = cross.magnitude();
+    if sin > T::zero() {
+        // `sin` is |ab| |ac| times the sine of the angle.
+        *out = *out + cross * (atan2(sin, ab.dot(ac)) / sin);
+    }
+}
+
+/// Normalizes `v`, or returns zero if `v` is zero.
+fn normalized_or_zero<T: Real>(v: Vec3<T>) -> Vec3<T> {
+    let m = v.magnitude();
+    if m > T::zero() { v / m } else { Vec3::zero() }
+}
+
+/// Computes one normal per vertex, by summing the normals of the triangles that use each vertex,
+/// weighted by their area, then normalizing the result.
+///
+/// `out` is cleared, then filled with exactly `positions.len()` normals.
+/// Zero-area triangles contribute nothing; vertices which are not used by any triangle (or only by
+/// zero-area ones) get a zero normal. The output never contains NaNs as long as the positions are
+/// finite.
+///
+/// # Panics
+/// Panics if an index is out of the bounds of `positions`.
+///
+/// ```
+/// # extern crate vek;
+/// # #[macro_use] extern crate approx;
+/// # use vek::{Vec3, mesh::compute_vertex_normals};
+/// # fn main() {
+/// // A quad in the XY plane, and an unused vertex.
+/// let positions = [Vec3::new(0_f32, 0., 0.), Vec3::new(1., 0., 0.), Vec3::new(1., 1., 0.), Vec3::new(0., 1., 0.), Vec3::zero()];
+/// let indices = [0, 1, 2, 0, 2, 3];
+/// let mut normals = Vec::new();
+/// compute_vertex_normals(&positions, &indices, &mut normals);
+/// assert_eq!(normals.len(), 5);
+/// for n in &normals[..4] {
+///     assert_relative_eq!(*n, Vec3::unit_z());
+/// }
+/// assert_eq!(normals[4], Vec3::zero());
+/// # }
+/// ```
+#[cfg(feature="std")]
+pub fn compute_vertex_normals<T: Real>(positions: &[Vec3<T>], indices: &[u32], out: &mut Vec<Vec3<T>>) {
+    let mesh = IndexedMesh::new(positions, indices);
+    out.clear();
+    out.resize(positions.len(), Vec3::zero());
+    for face in 0..mesh.face_count() {
+        let n = mesh.weighted_face_normal(face);
+        for corner in 0..3 {
+            let i = mesh.vertex_index(face, corner);
+            out[i] = out[i] + n;
+        }
+    }
+    for n in out.iter_mut() {
+        *n = normalized_or_zero(*n);
+    }
+}
+
+/// Computes one normal per vertex, by summing the normals of the triangles that use each vertex,
+/// weighted by their interior angle at that vertex, then normalizing the result.
+///
+/// See `accumulate_angle_weighted_normal()` for why this is usually preferable to
+/// `compute_vertex_normals()`, which weights triangles by their area.
+/// `out` is cleared, then filled with exactly `positions.len()` normals. Zero-area triangles
+/// contribute nothing; vertices which are not used by any triangle (or only by zero-area ones)
+/// get a zero normal.
+///
+/// # Panics
+/// Panics if an index is out of the bounds of `positions`.
+///
+/// ```
+/// # extern crate vek;
+/// # #[macro_use] extern crate approx;
+/// # use vek::{Vec3, mesh::{compute_vertex_normals, compute_angle_weighted_vertex_normals}};
+/// # fn main() {
+/// // Two faces of a box, folded at a right angle along the X axis. The top one is split in two
+/// // triangles at vertex 0, the front one is a single (half) triangle.
+/// let positions = [Vec3::new(0_f64, 0., 0.), Vec3::new(1., 0., 0.), Vec3::new(1., 1., 0.), Vec3::new(0., 1., 0.), Vec3::new(0., 0., -1.)];
+/// let indices = [0, 1, 2, 0, 2, 3, 0, 4, 1];
+/// let mut normals = Vec::new();
+/// compute_angle_weighted_vertex_normals(&positions, &indices, &mut normals);
+/// // Each face spans a right angle at vertex 0, so they are weighted equally.
+/// assert_relative_eq!(normals[0], Vec3::new(0., -1., 1.).normalized());
+/// compute_vertex_normals(&positions, &indices, &mut normals);
+/// assert!(normals[0].z > normals[0].y.abs());
+/// # }
+/// ```
+#[cfg(feature="std")]
+pub fn compute_angle_weighted_vertex_normals<T: Real>(positions: &[Vec3<T>], indices: &[u32], out: &mut Vec<Vec3<T>>) {
+    let mesh = IndexedMesh::new(positions, indices);
+    out.clear();
+    out.resize(positions.len(), Vec3::zero());
+    for face in 0..mesh.face_count() {
+        for corner in 0..3 {
+            let (a, b, c) = mesh.corners_from(face, corner);
+            accumulate_angle_weighted_normal(&mut out[mesh.vertex_index(face, corner)], a, b, c);
+        }
+    }
+    for n in out.iter_mut() {
+        *n = normalized_or_zero(*n);
+    }
+}
+
+/// Computes one normal per triangle corner, smoothing only across triangles whose normals
+/// are at most `max_angle_radians` apart.
+///
+/// This keeps hard edges sharp (e.g the edges of a cube, with a threshold below 90°) while still
+/// smoothing curved surfaces. Since a vertex on a hard edge needs several normals, the output
+/// is not indexed: `out` is cleared, then filled with exactly one normal per index, in the
+/// same order as `indices`.
+///
+/// The normal of a corner is the area-weighted sum of the normals of all triangles that share
+/// its vertex and are within the angle threshold of its own triangle, normalized.
+/// A zero-area triangle has no normal of its own, so its corners get the area-weighted sum of
+/// all triangles sharing their vertex (i.e the same normal as `compute_vertex_normals()`).
+///
+/// # Panics
+/// Panics if an index is out of the bounds of `positions`.
+///
+/// ```
+/// # extern crate vek;
+/// # #[macro_use] extern crate approx;
+/// # use vek::{Vec3, mesh::compute_smooth_normals_with_angle_threshold};
+/// # fn main() {
+/// // Two triangles folded at a right angle along the X axis.
+/// let positions = [Vec3::new(0_f32, 0., 0.), Vec3::new(1., 0., 0.), Vec3::new(0., 1., 0.), Vec3::new(0., 0., 1.)];
+/// let indices = [0, 1, 2, 1, 0, 3];
+/// let mut normals = Vec::new();
+/// compute_smooth_normals_with_angle_threshold(&positions, &indices, 1., &mut normals);
+/// // The fold is sharper than 1 radian, so it stays a hard edge.
+/// assert_relative_eq!(normals[0], Vec3::unit_z());
+/// assert_relative_eq!(normals[4], Vec3::unit_y());
+/// compute_smooth_normals_with_angle_threshold(&positions, &indices, 2., &mut normals);
+/// assert_relative_eq!(normals[0], Vec3::new(0., 1., 1.).normalized());
+/// assert_relative_eq!(normals[4], Vec3::new(0., 1., 1.).normalized());
+/// # }
+/// ```
+#[cfg(feature="std")]
+pub fn compute_smooth_normals_with_angle_threshold<T: Real>(positions: &[Vec3<T>], indices: &[u32], max_angle_radians: T, out: &mut Vec<Vec3<T>>) {
+    let mesh = IndexedMesh::new(positions, indices);
+    let triangle_count = mesh.face_count();
+    let weighted: Vec<_> = (0..triangle_count).map(|t| mesh.weighted_face_normal(t)).collect();
+    let unit: Vec<_> = weighted.iter().map(|n| normalized_or_zero(*n)).collect();
+
+    // For each vertex, the list of triangles that use it, in compressed form:
+    // triangles_of_vertex[starts[v]..starts[v + 1]].
+    let mut starts = alloc::vec![0_usize; positions.len() + 1];
+    for &i in &indices[..triangle_count * 3] {
+        starts[i as usize + 1] += 1;
+    }
+    for v in 0..positions.len() {
+        starts[v + 1] += starts[v];
+    }
+    let mut fill = starts.clone();
+    let mut triangles_of_vertex = alloc::vec![0_usize; triangle_count * 3];
+    for (t, triangle) in indices.chunks_exact(3).enumerate() {
+        for &i in triangle {
+            triangles_of_vertex[fill[i as usize]] = t;
+            fill[i as usize] += 1;
+        }
+    }
+
+    let cos_max_angle = cos(max_angle_radians);
+    out.clear();
+    out.reserve(triangle_count * 3);
+    for (t, triangle) in indices.chunks_exact(3).enumerate() {
+        let own = unit[t];
+        let is_degenerate = own == Vec3::zero();
+        for &i in triangle {
+            let i = i as usize;
+            let mut sum = Vec3::zero();
+            for &other in &triangles_of_vertex[starts[i]..starts[i + 1]] {
+                if is_degenerate || other == t || own.dot(unit[other]) >= cos_max_angle {
+                    sum = sum + weighted[other];
+                }
+            }
+            out.push(normalized_or_zero(sum));
+        }
+    }
+}
+
+/// Merges positions which are within `tolerance` of each other (e.g the duplicated corners of
+/// faces which were exported separately), returning the distinct positions, and for each given
+/// position the index of the one it was merged into.
+///
+/// Positions are kept in the order of their first occurrence, and each position is merged into
+/// the closest position already kept which is within `tolerance` of it (inclusive), if any.
+/// Welding is not transitive: a chain of positions, each within `tolerance` of the next, may
+/// yield more than one position. The indices of a mesh are updated with
+/// `indices.iter().map(|&i| remap[i as usize])`.
+///
+/// Positions are found with a spatial hash grid whose cells are `tolerance` wide, which is
+/// why the 27 cells around each position are searched: close positions on either side of a
+/// boundary between cells are merged all the same. The result is stable across runs and
+/// platforms, see `sampling::spatial_hash()`.
+///
+/// # Panics
+/// Panics if `tolerance` is not positive, or if there are more than `u32::MAX` positions.
+///
+/// ```
+/// # use vek::{Vec3, mesh::weld_vertices};
+/// let positions = [
+///     Vec3::new(0_f32, 0., 0.), Vec3::new(1., 0., 0.), Vec3::new(0., 1., 0.),
+///     Vec3::new(1., 0., 0.0001), Vec3::new(0., 1., 0.), Vec3::new(1., 1., 0.),
+/// ];
+/// let (welded, remap) = weld_vertices(&positions, 0.001);
+/// assert_eq!(welded, [Vec3::new(0., 0., 0.), Vec3::new(1., 0., 0.), Vec3::new(0., 1., 0.), Vec3::new(1., 1., 0.)]);
+/// assert_eq!(remap, [0, 1, 2, 1, 2, 3]);
+/// ```
+#[cfg(feature="std")]
+pub fn weld_vertices<T: Real>(positions: &[Vec3<T>], tolerance: T) -> (Vec<Vec3<T>>, Vec<u32>) {
+    assert!(tolerance > T::zero(), "the welding tolerance must be positive");
+    assert!(positions.len() <= u32::MAX as usize, "too many positions to weld");
+    const NONE: u32 = u32::MAX;
+    let cell_of = |p: Vec3<T>| p.map(|x| (x / tolerance).floor().to_i64().unwrap_or(0));
+    // Singly linked lists of kept positions, one per bucket, through `next`.
+    let buckets = (positions.len() * 2).next_power_of_two();
+    let bucket_of = |cell: Vec3<i64>| spatial_hash(&[cell.x as u64, cell.y as u64, cell.z as u64], 0) as usize & (buckets - 1);
+    let mut heads = alloc::vec![NONE; buckets];
+    let mut next = Vec::new();
+    let mut welded = Vec::new();
+    let mut remap = Vec::with_capacity(positions.len());
+    let tolerance_squared = tolerance * tolerance;
+    for &p in positions {
+        let cell = cell_of(p);
+        let mut closest = None;
+        for z in -1..=1 {
+            for y in -1..=1 {
+                for x in -1..=1 {
+                    let neighbour = cell.map2(Vec3::new(x, y, z), i64::wrapping_add);
+                    let mut i = heads[bucket_of(neighbour)];
+                    while i != NONE {
+                        let q: Vec3<T> = welded[i as usize];
+                        let d = q.distance_squared(p);
+                        // Other cells may share the bucket, but then they are too far anyway.
+                        let is_closer = match closest {
+                            Some((_, closest_d)) => d < closest_d,
+                            None => true,
+                        };
+                        if d <= tolerance_squared && is_closer {
+                            closest = Some((i, d));
+                        }
+                        i = next[i as usize];
+                    }
+                }
+            }
+        }
+        match closest {
+            Some((i, _)) => remap.push(i),
+            None => {
+                let i = welded.len() as u32;
+                let bucket = bucket_of(cell);
+                next.push(heads[bucket]);
+                heads[bucket] = i;
+                welded.push(p);
+                remap.push(i);
+            },
+        }
+    }
+    (welded, remap)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A unit cube whose faces are split along the diagonal which joins its "even" corners
+    // (those whose coordinates sum to an even number), so that each corner is touched by
+    // the same area on each of its 3 faces.
+    fn cube() -> ([Vec3<f64>; 8], [u32; 36]) {
+        let positions = [
+            Vec3::new(0., 0., 0.), Vec3::new(1., 0., 0.), Vec3::new(1., 1., 0.), Vec3::new(0., 1., 0.),
+            Vec3::new(0., 0., 1.), Vec3::new(1., 0., 1.), Vec3::new(1., 1., 1.), Vec3::new(0., 1., 1.),
+        ];
+        // Even corners are 0, 2, 5, 7.
+        let indices = [
+            0, 2, 1,  0, 3, 2, // -Z
+            5, 7, 4,  5, 6, 7, // +Z
+            0, 5, 4,  0, 1, 5, // -Y
+            2, 7, 6,  2, 3, 7, // +Y
+            0, 7, 3,  0, 4, 7, // -X
+            5, 2, 6,  5, 1, 2, // +X
+        ];
+        (positions, indices)
+    }
+
+    #[test]
+    fn cube_vertex_normals_point_diagonally() {
+        let (positions, indices) = cube();
+        let mut normals = Vec::new();
+        compute_vertex_normals(&positions, &indices, &mut normals);
+        let center = Vec3::broadcast(0.5);
+        for (p, n) in positions.iter().zip(normals.iter()) {
+            assert_relative_eq!(*n, (*p - center).normalized(), epsilon = 1e-12);
+        }
+    }
+    #[test]
+    fn cube_keeps_hard_edges() {
+        let (positions, indices) = cube();
+        let mut normals = Vec::new();
+        compute_smooth_normals_with_angle_threshold(&positions, &indices, 0.5, &mut normals);
+        assert_eq!(normals.len(), indices.len());
+        for (corner, n) in normals.iter().enumerate() {
+            let t = corner / 3;
+            let face = IndexedMesh::new(&positions, &indices).face_normal(t);
+            assert_relative_eq!(*n, face, epsilon = 1e-12);
+        }
+        // With a threshold above 90°, all faces of a corner are smoothed together.
+        compute_smooth_normals_with_angle_threshold(&positions, &indices, 2., &mut normals);
+        let mut vertex_normals = Vec::new();
+        compute_vertex_normals(&positions, &indices, &mut vertex_normals);
+        for (corner, n) in normals.iter().enumerate() {
+            assert_relative_eq!(*n, vertex_normals[indices[corner] as usize], epsilon = 1e-12);
+        }
+    }
+    #[test]
+    fn angle_weighting_ignores_triangulation() {
+        // Each quad is split from its first corner, so corner 1 is touched by two triangles on
+        // +X but by a single one on -Y and -Z.
+        let (positions, _) = cube();
+        let quads = [[0, 3, 2, 1], [4, 5, 6, 7], [0, 1, 5, 4], [3, 7, 6, 2], [0, 4, 7, 3], [1, 2, 6, 5]];
+        let indices: Vec<u32> = quads.iter().flat_map(|q| [q[0], q[1], q[2], q[0], q[2], q[3]].to_vec()).collect();
+        let center = Vec3::broadcast(0.5);
+        let mut normals = Vec::new();
+        compute_angle_weighted_vertex_normals(&positions, &indices, &mut normals);
+        for (p, n) in positions.iter().zip(normals.iter()) {
+            assert_relative_eq!(*n, (*p - center).normalized(), epsilon = 1e-12);
+        }
+        compute_vertex_normals(&positions, &indices, &mut normals);
+        assert_relative_eq!(normals[0], (positions[0] - center).normalized(), epsilon = 1e-12);
+        assert!(normals[1].x > 0.7);
+    }
+    #[test]
+    fn angle_weighted_cylinder_cap() {
+        // An open cylinder, whose top cap is a fan from its first rim vertex.
+        const N: u32 = 8;
+        let angle = |i: u32| 2. * std::f64::consts::PI * i as f64 / N as f64;
+        let positions: Vec<_> = (0..2 * N).map(|i| {
+            let a = angle(i % N);
+            Vec3::new(a.cos(), a.sin(), (i / N) as f64)
+        }).collect();
+        let mut indices = Vec::new();
+        for i in 1..N - 1 {
+            indices.extend_from_slice(&[N, N + i, N + i + 1]);
+        }
+        for i in 0..N {
+            let j = (i + 1) % N;
+            indices.extend_from_slice(&[i, j, N + j, i, N + j, N + i]);
+        }
+        let mesh = IndexedMesh::new(&positions, &indices);
+        assert_eq!(mesh.face_count(), (N - 2 + 2 * N) as usize);
+        assert_relative_eq!(mesh.face_normal(0), Vec3::unit_z(), epsilon = 1e-12);
+
+        // The cap's interior angle is (N-2)π/N at every rim vertex, and the two side quads
+        // contribute a right angle each.
+        let pi = std::f64::consts::PI;
+        let cap = (N - 2) as f64 * pi / N as f64;
+        let side = pi * (pi / N as f64).cos();
+        let mut normals = Vec::new();
+        compute_angle_weighted_vertex_normals(&positions, &indices, &mut normals);
+        for i in 0..N {
+            let a = angle(i);
+            let expected = Vec3::new(side * a.cos(), side * a.sin(), cap).normalized();
+            assert_relative_eq!(normals[(N + i) as usize], expected, epsilon = 1e-12);
+        }
+        // Area weighting makes the fan's apex much more "vertical" than its neighbours.
+        compute_vertex_normals(&positions, &indices, &mut normals);
+        assert!(normals[N as usize].z > normals[N as usize + 1].z + 0.1);
+    }
+    #[test]
+    fn corner_accessors() {
+        let (positions, indices) = cube();
+        let mesh = IndexedMesh::new(&positions, &indices[..]);
+        assert_eq!(mesh.face_count(), 12);
+        assert_eq!(IndexedMesh::new(&positions, &indices[..7]).face_count(), 2);
+        for face in 0..mesh.face_count() {
+            let sum = (0..3).map(|c| mesh.corner_angle(face, c)).fold(0., |a, b| a + b);
+            assert_relative_eq!(sum, std::f64::consts::PI, epsilon = 1e-12);
+            let (a, b, c) = (mesh.position(face, 0), mesh.position(face, 1), mesh.position(face, 2));
+            assert_relative_eq!(mesh.face_normal(face), (b - a).cross(c - a).normalized(), epsilon = 1e-12);
+        }
+        // Very thin triangles keep an accurate angle.
+        let thin = [Vec3::new(0., 0., 0.), Vec3::new(1., 0., 0.), Vec3::new(1., 1e-9, 0.)];
+        let thin_mesh = IndexedMesh::new(&thin, &[0, 1, 2]);
+        assert_relative_eq!(thin_mesh.corner_angle(0, 0), 1e-9, max_relative = 1e-12);
+        let mut n = Vec3::zero();
+        accumulate_angle_weighted_normal(&mut n, thin[0], thin[1], thin[1]);
+        assert_eq!(n, Vec3::zero());
+    }
+    #[test]
+    #[should_panic]
+    fn corner_out_of_bounds() {
+        let (positions, indices) = cube();
+        IndexedMesh::new(&positions, &indices).vertex_index(0, 3);
+    }
+    #[test]
+    fn degenerate_triangles_and_unused_vertices() {
+        let positions = [
+            Vec3::new(0_f32, 0., 0.), Vec3::new(1., 0., 0.), Vec3::new(0., 1., 0.),
+            Vec3::new(2., 0., 0.), // collinear with 0 and 1
+            Vec3::new(5., 5., 5.), // unused
+            Vec3::new(7., 7., 7.), // only used by a zero-area triangle
+        ];
+        let indices = [0, 1, 2, 0, 1, 3, 5, 5, 5, 1, 2];
+        let mut normals = Vec::new();
+        compute_vertex_normals(&positions, &indices, &mut normals);
+        assert_eq!(normals.len(), positions.len());
+        assert!(normals.iter().all(|n| !n.x.is_nan() && !n.y.is_nan() && !n.z.is_nan()));
+        assert_relative_eq!(normals[0], Vec3::unit_z());
+        assert_eq!(normals[3], Vec3::zero());
+        assert_eq!(normals[4], Vec3::zero());
+        assert_eq!(normals[5], Vec3::zero());
+
+        compute_smooth_normals_with_angle_threshold(&positions, &indices, 0.1, &mut normals);
+        assert_eq!(normals.len(), 9);
+        assert!(normals.iter().all(|n| !n.x.is_nan() && !n.y.is_nan() && !n.z.is_nan()));
+        // The corners of the zero-area triangle (0, 1, 3) take the smooth normal of their vertex.
+        assert_relative_eq!(normals[3], Vec3::unit_z());
+        assert_relative_eq!(normals[4], Vec3::unit_z());
+        assert_eq!(normals[5], Vec3::zero());
+    }
+    #[test]
+    fn weld_cube_faces() {
+        let (corners, indices) = cube();
+        // Each face has its own copy of its 4 corners, slightly off, as from a mesh exported per face.
+        let mut positions = Vec::new();
+        let mut face_indices = Vec::new();
+        for (face, triangles) in indices.chunks_exact(6).enumerate() {
+            let mut local = Vec::new();
+            for &i in triangles {
+                if !local.contains(&i) {
+                    local.push(i);
+                    let jitter = Vec3::new(1., -2., 3.) * (face as f64 - 2.5) * 1e-7;
+                    positions.push(corners[i as usize] + jitter);
+                }
+                face_indices.push((positions.len() - local.len() + local.iter().position(|&j| j == i).unwrap()) as u32);
+            }
+        }
+        assert_eq!(positions.len(), 24);
+        let (welded, remap) = weld_vertices(&positions, 1e-5);
+        assert_eq!(welded.len(), 8);
+        assert_eq!(remap.len(), 24);
+        for (p, &i) in positions.iter().zip(remap.iter()) {
+            assert!(p.distance(welded[i as usize]) <= 1e-5);
+        }
+        // The welded mesh has the same triangles as the original one.
+        for (t, original) in face_indices.chunks_exact(3).zip(indices.chunks_exact(3)) {
+            for (&i, &j) in t.iter().zip(original.iter()) {
+                assert_relative_eq!(welded[remap[i as usize] as usize], corners[j as usize], epsilon = 1e-6);
+            }
+        }
+        // Nothing to weld.
+        let (welded, remap) = weld_vertices(&corners, 0.5);
+        assert_eq!((welded, remap), (corners.to_vec(), (0..8).collect::<Vec<_>>()));
+        assert_eq!(weld_vertices::<f32>(&[], 1.), (Vec::new(), Vec::new()));
+    }
+    #[test]
+    fn weld_tolerance() {
+        let tolerance = 0.1_f32;
+        let weld = |a: Vec3<f32>, b: Vec3<f32>| weld_vertices(&[a, b], tolerance).1 == [0, 0];
+        for &origin in &[Vec3::zero(), Vec3::new(0.05, 0.05, 0.05), Vec3::new(-3.01, 7.1, 0.099), Vec3::new(0.0999, 0.1999, -0.0001)] {
+            for &direction in &[Vec3::unit_x(), -Vec3::unit_y(), Vec3::new(1., 1., 1.).normalized(), Vec3::new(-1., 0., 1.).normalized()] {
+                assert!(weld(origin, origin + direction * tolerance * 0.99));
+                assert!(!weld(origin, origin + direction * tolerance * 1.01));
+            }
+        }
+        // Across a boundary between cells, in a diagonal direction.
+        assert!(weld(Vec3::new(0.0999, 0.0999, 0.0999), Vec3::new(0.1001, 0.1001, 0.1001)));
+        // Merged into the closest kept position.
+        let (welded, remap) = weld_vertices(&[Vec3::new(0_f32, 0., 0.), Vec3::new(0.15, 0., 0.), Vec3::new(0.1, 0., 0.)], tolerance);
+        assert_eq!(welded.len(), 2);
+        assert_eq!(remap, [0, 1, 1]);
+        // Not transitive.
+        let chain: Vec<_> = (0..10).map(|i| Vec3::new(i as f32 * 0.09, 0., 0.)).collect();
+        assert_eq!(weld_vertices(&chain, tolerance).1, [0, 0, 1, 1, 2, 2, 3, 3, 4, 4]);
+    }
+    #[test]
+    fn aabb() {
+        let (positions, _) = cube();
+        assert_eq!(compute_aabb(&positions), Some(Aabb { min: Vec3::zero(), max: Vec3::one() }));
+        assert_eq!(compute_aabb(&positions[6..7]), Some(Aabb::new_empty(Vec3::one())));
+    }
+}
@@ -18,6 +18,417 @@ pub fn partial_max<T: PartialOrd + Sized>(a: T, b: T) -> T {
     if a >= b { a } else { b }
 }
 
+/// Returns a tolerance suited to comparing quantities of the same magnitude as `values`,
+/// computed as `max(|v| for v in values) * T::epsilon() * factor`.
+///
+/// Fixed epsilons (such as `T::epsilon()` or `RelativeEq`'s defaults) are too large for
+/// geometry that is millimeters small, and too small for geometry that is kilometers across.
+/// This helper is meant to be fed to the `*_with_epsilon()` variants of predicates, such as
+/// `Vec3::is_approx_zero_with_epsilon()`. `factor` accounts for the number of operations that
+/// led to the compared values; something like `4` to `16` is typical.
+///
+/// ```
+/// # use vek::ops::relative_epsilon_for;
+/// let eps = relative_epsilon_for([1e6_f32, -3e6, 2.], 4.);
+/// assert_eq!(eps, 3e6 * f32::EPSILON * 4.);
+/// assert_eq!(relative_epsilon_for(Vec::<f32>::new(), 4.), 0.);
+/// ```
+pub fn relative_epsilon_for<T: Real, I: IntoIterator<Item=T>>(values: I, factor: T) -> T {
+    let max_abs = values.into_iter().fold(T::zero(), |m, v| m.max(v.abs()));
+    max_abs * T::epsilon() * factor
+}
+
+/// Numerically computes the gradient of the scalar field `f` at `p`, using central differences
+/// with step `eps`.
+///
+/// ```
+/// # extern crate vek;
+/// # #[macro_use] extern crate approx;
+/// # use vek::{Vec3, ops::gradient};
+/// # fn main() {
+/// let f = |p: Vec3<f64>| p.x * p.x + 3. * p.y * p.z;
+/// let g = gradient(f, Vec3::new(1., 2., -1.), 1e-4);
+/// assert_relative_eq!(g, Vec3::new(2., -3., 6.), epsilon = 1e-6);
+/// # }
+/// ```
+pub fn gradient<T: Real, F: Fn(crate::vec::Vec3<T>) -> T>(f: F, p: crate::vec::Vec3<T>, eps: T) -> crate::vec::Vec3<T> {
+    let two_eps = eps + eps;
+    let d = |axis: crate::vec::Vec3<T>| (f(p + axis * eps) - f(p - axis * eps)) / two_eps;
+    crate::vec::Vec3::new(
+        d(crate::vec::Vec3::unit_x()),
+        d(crate::vec::Vec3::unit_y()),
+        d(crate::vec::Vec3::unit_z()),
+    )
+}
+
+/// Numerically computes the Jacobian matrix of the vector field `f` at `p`, using central
+/// differences with step `eps`.
+///
+/// Element `(i, j)` of the result is the partial derivative of `f(p)[i]` with respect to `p[j]`,
+/// so that column `j` is the rate of change of `f` along axis `j`.
+/// This follows `Mat3`'s multiply order: `f(p + d)` is approximately `f(p) + jacobian * d`.
+///
+/// ```
+/// # extern crate vek;
+/// # #[macro_use] extern crate approx;
+/// # use vek::{Vec3, Mat3, ops::jacobian};
+/// # fn main() {
+/// let a = Mat3::new(
+///     1., 2., 3.,
+///     4., 5., 6.,
+///     7., 8., 10.,
+/// );
+/// let j = jacobian(|p| a * p + Vec3::one(), Vec3::new(0.5_f64, -2., 1.), 1e-3);
+/// assert_relative_eq!(j, a, epsilon = 1e-9);
+/// # }
+/// ```
+pub fn jacobian<T: Real, F: Fn(crate::vec::Vec3<T>) -> crate::vec::Vec3<T>>(f: F, p: crate::vec::Vec3<T>, eps: T) -> crate::mat::Mat3<T> {
+    let two_eps = eps + eps;
+    let d = |axis: crate::vec::Vec3<T>| (f(p + axis * eps) - f(p - axis * eps)) / two_eps;
+    crate::mat::Mat3::from_col_arrays([
+        d(crate::vec::Vec3::unit_x()).into_array(),
+        d(crate::vec::Vec3::unit_y()).into_array(),
+        d(crate::vec::Vec3::unit_z()).into_array(),
+    ])
+}
+
+/// Numerically computes the divergence of the vector field `f` at `p` (i.e the trace of its
+/// `jacobian()`), using central differences with step `eps`.
+///
+/// ```
+/// # extern crate vek;
+/// # #[macro_use] extern crate approx;
+/// # use vek::{Vec3, ops::divergence};
+/// # fn main() {
+/// // A field flowing outwards uniformly.
+/// let div = divergence(|p: Vec3<f64>| p * 2., Vec3::new(3., 1., -4.), 1e-3);
+/// assert_relative_eq!(div, 6., epsilon = 1e-9);
+/// # }
+/// ```
+pub fn divergence<T: Real, F: Fn(crate::vec::Vec3<T>) -> crate::vec::Vec3<T>>(f: F, p: crate::vec::Vec3<T>, eps: T) -> T {
+    let j = jacobian(f, p, eps);
+    j.cols.x.x + j.cols.y.y + j.cols.z.z
+}
+
+/// Numerically computes the curl of the vector field `f` at `p`, from its `jacobian()`,
+/// using central differences with step `eps`.
+///
+/// ```
+/// # extern crate vek;
+/// # #[macro_use] extern crate approx;
+/// # use vek::{Vec3, ops::curl};
+/// # fn main() {
+/// // The velocity field of a rigid rotation with angular velocity `w` has a curl of `2w`.
+/// let w = Vec3::new(0.5_f64, -1., 2.);
+/// let c = curl(|p| w.cross(p), Vec3::new(1., 2., 3.), 1e-3);
+/// assert_relative_eq!(c, w * 2., epsilon = 1e-9);
+/// # }
+/// ```
+pub fn curl<T: Real, F: Fn(crate::vec::Vec3<T>) -> crate::vec::Vec3<T>>(f: F, p: crate::vec::Vec3<T>, eps: T) -> crate::vec::Vec3<T> {
+    let c = jacobian(f, p, eps).cols;
+    // c.j.i is the derivative of component i along axis j.
+    crate::vec::Vec3::new(
+        c.y.z - c.z.y,
+        c.z.x - c.x.z,
+        c.x.y - c.y.x,
+    )
+}
+
+/// Polynomial smooth minimum of `a` and `b`, blending over a band of width `k`.
+///
+/// When `a` and `b` differ by at least `k`, this is exactly `min(a, b)`. Otherwise it is
+/// below it, by at most `k / 4` (reached where `a == b`). It is symmetric, continuous in
+/// its derivative, and converges to `min(a, b)` as `k` goes to zero. A `k` that is zero or
+/// negative gives the plain minimum.
+///
+/// Applied to signed distances, this is the smooth union of both shapes, and the result
+/// is still a bound suitable for sphere tracing (it is 1-Lipschitz, like its inputs).
+///
+/// ```
+/// # extern crate vek;
+/// # #[macro_use] extern crate approx;
+/// # use vek::ops::smooth_min;
+/// # fn main() {
+/// assert_relative_eq!(smooth_min(1_f32, 3., 1.), 1.);
+/// assert_relative_eq!(smooth_min(2_f32, 2., 1.), 1.75);
+/// assert_relative_eq!(smooth_min(2_f32, 2., 0.), 2.);
+/// # }
+/// ```
+pub fn smooth_min<T: Real>(a: T, b: T, k: T) -> T {
+    if k <= T::zero() {
+        return a.min(b);
+    }
+    let h = (k - (a - b).abs()).max(T::zero()) / k;
+    a.min(b) - h * h * k / T::from(4).unwrap()
+}
+/// Polynomial smooth maximum of `a` and `b`, blending over a band of width `k`.
+///
+/// This is the mirror image of `smooth_min()`: it is never below `max(a, b)`, and
+/// above it by at most `k / 4`. Applied to signed distances, this is the smooth
+/// intersection of both shapes.
+///
+/// ```
+/// # extern crate vek;
+/// # #[macro_use] extern crate approx;
+/// # use vek::ops::smooth_max;
+/// # fn main() {
+/// assert_relative_eq!(smooth_max(1_f32, 3., 1.), 3.);
+/// assert_relative_eq!(smooth_max(2_f32, 2., 1.), 2.25);
+/// # }
+/// ```
+pub fn smooth_max<T: Real>(a: T, b: T, k: T) -> T {
+    -smooth_min(-a, -b, k)
+}
+
+// NOTE: The `sdf_*` functions below operate on signed distances, which are negative inside
+// a shape, zero on its boundary and positive outside. Given exact distances as inputs,
+// they return bounds on the distance to the combined shape (never greater than the true
+// distance outside of it), which is what sphere tracing needs.
+
+/// Signed distance to the union of two shapes, given the signed distances `a` and `b` to each.
+///
+/// Use `smooth_min()` for a union with a rounded seam.
+///
+/// ```
+/// # extern crate vek;
+/// # use vek::{Disk, Vec2, ops::sdf_union};
+/// # fn main() {
+/// let (a, b) = (Disk::new(Vec2::new(-1_f32, 0.), 1.), Disk::new(Vec2::new(1_f32, 0.), 1.));
+/// let d = |p| sdf_union(a.signed_distance(p), b.signed_distance(p));
+/// assert_eq!(d(Vec2::new(-1., 0.)), -1.);
+/// assert_eq!(d(Vec2::new(4., 0.)), 2.);
+/// # }
+/// ```
+pub fn sdf_union<T: Real>(a: T, b: T) -> T {
+    a.min(b)
+}
+/// Signed distance to the intersection of two shapes, given the signed distances `a` and `b` to each.
+///
+/// Use `smooth_max()` for an intersection with a rounded seam.
+///
+/// ```
+/// # extern crate vek;
+/// # use vek::{Disk, Vec2, ops::sdf_intersection};
+/// # fn main() {
+/// let (a, b) = (Disk::new(Vec2::new(-1_f32, 0.), 2.), Disk::new(Vec2::new(1_f32, 0.), 2.));
+/// let d = |p| sdf_intersection(a.signed_distance(p), b.signed_distance(p));
+/// assert_eq!(d(Vec2::new(0., 0.)), -1.);
+/// assert_eq!(d(Vec2::new(-2., 0.)), 1.);
+/// # }
+/// ```
+pub fn sdf_intersection<T: Real>(a: T, b: T) -> T {
+    a.max(b)
+}
+/// Signed distance to the shape `a` with the shape `b` carved out of it, given the signed
+/// distances `a` and `b` to each.
+///
+/// Use `smooth_max(a, -b, k)` for a subtraction with a rounded seam.
+///
+/// ```
+/// # extern crate vek;
+/// # use vek::{Disk, Vec2, ops::sdf_subtraction};
+/// # fn main() {
+/// let (a, b) = (Disk::new(Vec2::new(0_f32, 0.), 2.), Disk::new(Vec2::new(2_f32, 0.), 2.));
+/// let d = |p| sdf_subtraction(a.signed_distance(p), b.signed_distance(p));
+/// assert_eq!(d(Vec2::new(-1., 0.)), -1.);
+/// assert_eq!(d(Vec2::new(1., 0.)), 1.);
+/// # }
+/// ```
+pub fn sdf_subtraction<T: Real>(a: T, b: T) -> T {
+    a.max(-b)
+}
+/// Signed distance to a shape grown by `radius`, given the signed distance `d` to it.
+///
+/// This rounds the corners of the shape. Unlike the other `sdf_*` functions, it maps
+/// exact distances to exact distances (for a non-negative `radius`).
+///
+/// ```
+/// # extern crate vek;
+/// # use vek::{Aabr, Vec2, ops::sdf_round};
+/// # fn main() {
+/// let aabr = Aabr { min: Vec2::new(0_f32, 0.), max: Vec2::new(2., 2.) };
+/// assert_eq!(sdf_round(aabr.signed_distance(Vec2::new(5., 6.)), 1.), 4.);
+/// # }
+/// ```
+pub fn sdf_round<T: Real>(d: T, radius: T) -> T {
+    d - radius
+}
+/// Signed distance to a shell of the given `thickness` around the boundary of a shape,
+/// given the signed distance `d` to it.
+///
+/// The shell extends `thickness` on both sides of the boundary, so it is `2 * thickness`
+/// thick. Onions can be nested, by applying this repeatedly.
+///
+/// ```
+/// # extern crate vek;
+/// # use vek::{Disk, Vec2, ops::sdf_onion};
+/// # fn main() {
+/// let disk = Disk::new(Vec2::new(0_f32, 0.), 2.);
+/// let d = |p| sdf_onion(disk.signed_distance(p), 0.5);
+/// assert_eq!(d(Vec2::new(2., 0.)), -0.5);
+/// assert_eq!(d(Vec2::new(0., 0.)), 1.5);
+/// # }
+/// ```
+pub fn sdf_onion<T: Real>(d: T, thickness: T) -> T {
+    d.abs() - thickness
+}
+
+/// How texture coordinates outside of the texture are brought back into it, with the same
+/// semantics as OpenGL's and Vulkan's.
+///
+/// See `wrap_uv()` and `wrap_texel()`.
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+pub enum WrapMode {
+    /// The texture is tiled (`GL_REPEAT`).
+    Repeat,
+    /// The texture is tiled, every other tile being mirrored, so that edges meet their own
+    /// reflection (`GL_MIRRORED_REPEAT`).
+    MirroredRepeat,
+    /// Coordinates are clamped, so the edges of the texture extend forever (`GL_CLAMP_TO_EDGE`).
+    ClampToEdge,
+}
+
+/// Wraps normalized texture coordinates into `[0, 1]`, according to `mode`.
+///
+/// `Repeat` keeps the fractional part (`uv - floor(uv)`) so its results are in `[0, 1)`;
+/// `MirroredRepeat` goes back and forth, reaching `1` at odd integers; `ClampToEdge` clamps
+/// to `[0, 1]`. At texel centers, these agree with `wrap_texel()`; clamping to the centers
+/// of the edge texels is left to texel addressing, since the texture's size isn't known here.
+///
+/// ```
+/// # use vek::{Vec2, ops::{wrap_uv, WrapMode}};
+/// let uv = Vec2::new(1.25_f32, -0.25);
+/// assert_eq!(wrap_uv(uv, WrapMode::Repeat), Vec2::new(0.25, 0.75));
+/// assert_eq!(wrap_uv(uv, WrapMode::MirroredRepeat), Vec2::new(0.75, 0.25));
+/// assert_eq!(wrap_uv(uv, WrapMode::ClampToEdge), Vec2::new(1., 0.));
+/// ```
+pub fn wrap_uv<T: Real>(uv: crate::vec::Vec2<T>, mode: WrapMode) -> crate::vec::Vec2<T> {
+    let (zero, one) = (T::zero(), T::one());
+    let two = one + one;
+    uv.map(|x| match mode {
+        WrapMode::Repeat => x - x.floor(),
+        WrapMode::MirroredRepeat => {
+            let x = x - (x / two).floor() * two;
+            one - (x - one).abs()
+        },
+        WrapMode::ClampToEdge => x.max(zero).min(one),
+    })
+}
+
+/// Wraps integer texel coordinates into a texture of the given `size`, according to `mode`.
+///
+/// This follows the OpenGL specification (and Vulkan's) exactly, including for `MirroredRepeat`,
+/// which repeats the edge texels across the boundary, as in `..., 1, 0, 0, 1, ...`.
+///
+/// # Panics
+/// Panics if either dimension of `size` is zero.
+///
+/// ```
+/// # use vek::{Vec2, Extent2, ops::{wrap_texel, WrapMode}};
+/// let size = Extent2::new(4, 4);
+/// assert_eq!(wrap_texel(Vec2::new(5, -1), size, WrapMode::Repeat), Vec2::new(1, 3));
+/// assert_eq!(wrap_texel(Vec2::new(5, -1), size, WrapMode::MirroredRepeat), Vec2::new(2, 0));
+/// assert_eq!(wrap_texel(Vec2::new(5, -1), size, WrapMode::ClampToEdge), Vec2::new(3, 0));
+/// ```
+pub fn wrap_texel(coord: crate::vec::Vec2<i32>, size: crate::vec::Extent2<u32>, mode: WrapMode) -> crate::vec::Vec2<u32> {
+    assert!(size.w > 0 && size.h > 0, "textures must not be empty");
+    let wrap = |i: i32, size: u32| {
+        // Widened, so that the largest sizes and coordinates don't overflow.
+        let (i, size) = (i64::from(i), i64::from(size));
+        let wrapped = match mode {
+            WrapMode::Repeat => i.rem_euclid(size),
+            WrapMode::MirroredRepeat => {
+                // (size - 1) - mirror((i mod 2*size) - size), where mirror(a) is a if a >= 0,
+                // and -(1 + a) otherwise.
+                let a = i.rem_euclid(size * 2) - size;
+                let mirrored = if a >= 0 { a } else { -(1 + a) };
+                size - 1 - mirrored
+            },
+            WrapMode::ClampToEdge => i.max(0).min(size - 1),
+        };
+        wrapped as u32
+    };
+    crate::vec::Vec2::new(wrap(coord.x, size.w), wrap(coord.y, size.h))
+}
+
+// Field of view helpers. The horizontal and vertical fields of view are not proportional:
+// it's the tangents of their halves that are related by the aspect ratio.
+
+/// Converts a horizontal field of view to the vertical one, for a viewport whose
+/// `aspect_ratio` is `width / height`, which is what `Mat4::perspective_rh_zo()` and
+/// its siblings expect.
+///
+/// ```
+/// # #[macro_use] extern crate approx;
+/// # use vek::ops::fov_y_from_fov_x;
+/// # use std::f32::consts::FRAC_PI_2;
+/// # fn main() {
+/// // A 90° horizontal field of view on a 16:9 screen.
+/// assert_relative_eq!(fov_y_from_fov_x(FRAC_PI_2, 16. / 9.).to_degrees(), 58.715508);
+/// # }
+/// ```
+pub fn fov_y_from_fov_x<T: Real>(fov_x_radians: T, aspect_ratio: T) -> T {
+    let two = T::one() + T::one();
+    atan2(tan(fov_x_radians / two), aspect_ratio) * two
+}
+/// Converts a vertical field of view to the horizontal one, for a viewport whose
+/// `aspect_ratio` is `width / height`.
+///
+/// This is the inverse of `fov_y_from_fov_x()`.
+///
+/// ```
+/// # #[macro_use] extern crate approx;
+/// # use vek::ops::{fov_x_from_fov_y, fov_y_from_fov_x};
+/// # fn main() {
+/// let fov_y = 60_f64.to_radians();
+/// assert_relative_eq!(fov_x_from_fov_y(fov_y, 1.), fov_y);
+/// assert_relative_eq!(fov_y_from_fov_x(fov_x_from_fov_y(fov_y, 2.35), 2.35), fov_y);
+/// # }
+/// ```
+pub fn fov_x_from_fov_y<T: Real>(fov_y_radians: T, aspect_ratio: T) -> T {
+    let two = T::one() + T::one();
+    atan2(tan(fov_y_radians / two) * aspect_ratio, T::one()) * two
+}
+/// Gets the field of view, in radians, of a pinhole camera given its focal length and the
+/// size of its sensor along the same axis, both in the same unit (usually millimeters).
+///
+/// Pass the sensor's height to get the vertical field of view, or its width to get the
+/// horizontal one.
+///
+/// ```
+/// # #[macro_use] extern crate approx;
+/// # use vek::ops::fov_from_focal_length;
+/// # fn main() {
+/// // A 50mm lens on a 36x24mm ("full frame") sensor.
+/// assert_relative_eq!(fov_from_focal_length(50_f32, 24.).to_degrees(), 26.991467);
+/// assert_relative_eq!(fov_from_focal_length(50_f32, 36.).to_degrees(), 39.597752);
+/// # }
+/// ```
+pub fn fov_from_focal_length<T: Real>(focal_length: T, sensor_size: T) -> T {
+    let two = T::one() + T::one();
+    atan2(sensor_size, focal_length * two) * two
+}
+/// Gets the field of view resulting from magnifying a view of `base_fov_radians` by `zoom`.
+///
+/// A zoom factor of 2 makes everything look twice as large, which halves the tangent of the
+/// half-angle (not the angle itself); factors below 1 zoom out.
+///
+/// ```
+/// # #[macro_use] extern crate approx;
+/// # use vek::ops::{zoom_factor_to_fov, fov_from_focal_length};
+/// # fn main() {
+/// // Zooming is the same as scaling the focal length.
+/// let base = fov_from_focal_length(35_f64, 24.);
+/// assert_relative_eq!(zoom_factor_to_fov(base, 1.), base);
+/// assert_relative_eq!(zoom_factor_to_fov(base, 3.), fov_from_focal_length(105., 24.));
+/// # }
+/// ```
+pub fn zoom_factor_to_fov<T: Real>(base_fov_radians: T, zoom: T) -> T {
+    let two = T::one() + T::one();
+    atan2(tan(base_fov_radians / two), zoom) * two
+}
+
 /// A value that can tell whether or not it is between two bounds (inclusive).
 pub trait IsBetween<Bound=Self>: Sized {
     /// `bool` for scalars, or vector of `bool`s for vectors.
@@ -707,6 +1118,403 @@ wrap_impl_sint!{
     Wrapping<isize>
 }
 
+macro_rules! angle_impl_newtype {
+    ($Angle:ident ($unit:literal) $half_turn:expr) => {
+        impl<T: Real + FloatConst> $Angle<T> {
+            /// Returns this angle wrapped to the half-open range (-half turn, half turn],
+            #[doc = $unit]
+            pub fn wrap(self) -> Self {
+                let half_turn: T = $half_turn;
+                let full_turn = half_turn + half_turn;
+                let r = half_turn - self.0;
+                $Angle(half_turn - (r - (r / full_turn).floor() * full_turn))
+            }
+            /// Interpolates between two angles along the shortest arc.
+            ///
+            /// The result is not wrapped; call `wrap()` on it if needed.
+            pub fn lerp_shortest(from: Self, to: Self, factor: T) -> Self {
+                let delta = (to - from).wrap();
+                from + delta * factor
+            }
+        }
+        impl<T: Add<Output=T>> Add for $Angle<T> {
+            type Output = Self;
+            fn add(self, rhs: Self) -> Self { $Angle(self.0 + rhs.0) }
+        }
+        impl<T: Sub<Output=T>> Sub for $Angle<T> {
+            type Output = Self;
+            fn sub(self, rhs: Self) -> Self { $Angle(self.0 - rhs.0) }
+        }
+        impl<T: Neg<Output=T>> Neg for $Angle<T> {
+            type Output = Self;
+            fn neg(self) -> Self { $Angle(-self.0) }
+        }
+        impl<T: Mul<Output=T>> Mul<T> for $Angle<T> {
+            type Output = Self;
+            fn mul(self, rhs: T) -> Self { $Angle(self.0 * rhs) }
+        }
+        impl<T: Div<Output=T>> Div<T> for $Angle<T> {
+            type Output = Self;
+            fn div(self, rhs: T) -> Self { $Angle(self.0 / rhs) }
+        }
+        impl<T: Add<Output=T> + Copy> AddAssign for $Angle<T> {
+            fn add_assign(&mut self, rhs: Self) { *self = *self + rhs; }
+        }
+        impl<T: Sub<Output=T> + Copy> SubAssign for $Angle<T> {
+            fn sub_assign(&mut self, rhs: Self) { *self = *self - rhs; }
+        }
+        impl<T: Mul<Output=T> + Copy> MulAssign<T> for $Angle<T> {
+            fn mul_assign(&mut self, rhs: T) { *self = *self * rhs; }
+        }
+        impl<T: Div<Output=T> + Copy> DivAssign<T> for $Angle<T> {
+            fn div_assign(&mut self, rhs: T) { *self = *self / rhs; }
+        }
+    };
+}
+
+/// An angle in radians.
+///
+/// This newtype helps avoiding confusion between radians and degrees. All functions of this crate
+/// that take an angle in radians also accept `Radians` and `Degrees` (see `IntoAngleRadians`).
+///
+/// ```
+/// # extern crate vek;
+/// # #[macro_use] extern crate approx;
+/// # use vek::{Radians, Degrees, Mat4};
+/// use std::f32::consts::PI;
+/// # fn main() {
+/// assert_relative_eq!(Radians::from(Degrees(90_f32)).0, PI / 2.);
+/// assert_relative_eq!(Radians(3. * PI / 2.).wrap().0, -PI / 2.);
+/// assert_eq!(Mat4::rotation_z(Radians(PI / 2.)), Mat4::rotation_z(PI / 2.));
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, Copy, Hash, Eq, PartialEq, PartialOrd)]
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+pub struct Radians<T>(pub T);
+
+/// An angle in degrees.
+///
+/// This newtype helps avoiding confusion between radians and degrees. All functions of this crate
+/// that take an angle in radians also accept `Radians` and `Degrees` (see `IntoAngleRadians`).
+///
+/// ```
+/// # extern crate vek;
+/// # #[macro_use] extern crate approx;
+/// # use vek::Degrees;
+/// # fn main() {
+/// assert_relative_eq!(Degrees(270_f32).wrap().0, -90.);
+/// assert_relative_eq!(Degrees::lerp_shortest(Degrees(170_f32), Degrees(-170.), 0.5).wrap().0, 180.);
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, Copy, Hash, Eq, PartialEq, PartialOrd)]
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+pub struct Degrees<T>(pub T);
+
+angle_impl_newtype!{Radians ("i.e (-PI, PI].") T::PI()}
+angle_impl_newtype!{Degrees ("i.e (-180, 180].") T::from(180).unwrap()}
+
+impl<T: Real> From<Degrees<T>> for Radians<T> {
+    fn from(d: Degrees<T>) -> Self {
+        Radians(d.0.to_radians())
+    }
+}
+impl<T: Real> From<Radians<T>> for Degrees<T> {
+    fn from(r: Radians<T>) -> Self {
+        Degrees(r.0.to_degrees())
+    }
+}
+
+/// A value that can be converted to an angle in radians.
+///
+/// This is implemented for bare scalars (assumed to already be in radians), `Radians` and `Degrees`.
+/// Functions that take an angle, such as `Mat4::rotation_z()`, accept any of these.
+pub trait IntoAngleRadians<T> {
+    /// Converts this value to an angle in radians.
+    fn into_angle_radians(self) -> T;
+}
+impl<T: Real> IntoAngleRadians<T> for T {
+    fn into_angle_radians(self) -> T {
+        self
+    }
+}
+impl<T: Real> IntoAngleRadians<T> for Radians<T> {
+    fn into_angle_radians(self) -> T {
+        self.0
+    }
+}
+impl<T: Real> IntoAngleRadians<T> for Degrees<T> {
+    fn into_angle_radians(self) -> T {
+        self.0.to_radians()
+    }
+}
+
+// Trigonometry used throughout this crate.
+//
+// By default these forward to the standard library (or `libm`), whose results may differ in the
+// last bits between platforms. With the `deterministic-trig` feature, they are computed in
+// software using only basic arithmetic (which IEEE 754 guarantees to be correctly rounded),
+// so that results are bit-identical everywhere.
+
+/// Returns the sine of `x` (in radians).
+///
+/// This is the function this crate uses internally; with the `deterministic-trig` feature, it
+/// yields bit-identical results on every platform.
+pub fn sin<T: Real>(x: T) -> T {
+    #[cfg(feature="deterministic-trig")] { soft_trig::sin(x) }
+    #[cfg(not(feature="deterministic-trig"))] { x.sin() }
+}
+/// Returns the cosine of `x` (in radians).
+///
+/// This is the function this crate uses internally; with the `deterministic-trig` feature, it
+/// yields bit-identical results on every platform.
+pub fn cos<T: Real>(x: T) -> T {
+    #[cfg(feature="deterministic-trig")] { soft_trig::cos(x) }
+    #[cfg(not(feature="deterministic-trig"))] { x.cos() }
+}
+/// Returns the tangent of `x` (in radians).
+///
+/// This is the function this crate uses internally; with the `deterministic-trig` feature, it
+/// yields bit-identical results on every platform.
+pub fn tan<T: Real>(x: T) -> T {
+    #[cfg(feature="deterministic-trig")] { soft_trig::tan(x) }
+    #[cfg(not(feature="deterministic-trig"))] { x.tan() }
+}
+/// Returns the arc cosine of `x`, in radians, in the range `[0, PI]`.
+///
+/// This is the function this crate uses internally; with the `deterministic-trig` feature, it
+/// yields bit-identical results on every platform.
+pub fn acos<T: Real>(x: T) -> T {
+    #[cfg(feature="deterministic-trig")] { soft_trig::acos(x) }
+    #[cfg(not(feature="deterministic-trig"))] { x.acos() }
+}
+/// Returns the four-quadrant arc tangent of `y/x`, in radians, in the range `[-PI, PI]`.
+///
+/// This is the function this crate uses internally; with the `deterministic-trig` feature, it
+/// yields bit-identical results on every platform.
+pub fn atan2<T: Real>(y: T, x: T) -> T {
+    #[cfg(feature="deterministic-trig")] { soft_trig::atan2(y, x) }
+    #[cfg(not(feature="deterministic-trig"))] { y.atan2(x) }
+}
+/// Returns the square root of `x`.
+///
+/// IEEE 754 requires the square root to be correctly rounded, so this is deterministic
+/// regardless of the `deterministic-trig` feature; it is provided for completeness.
+pub fn sqrt<T: Real>(x: T) -> T {
+    x.sqrt()
+}
+
+/// Software implementations of the trigonometric functions, built from basic arithmetic only.
+///
+/// The polynomials are the ones from FDLIBM. Arguments of `sin()`, `cos()` and `tan()` are reduced
+/// to `[-PI/4, PI/4]` in `f64` (whatever `T` is, so this assumes `T` is no more precise than `f64`),
+/// with a 4-part Cody-Waite reduction: for `|x| < 2^32`, `sin()` and `cos()` are within
+/// `2 * T::epsilon()` of the exact result, for both `f32` and `f64`. Past that range, reduction
+/// loses accuracy since it is not performed in extended precision, and results are meaningless
+/// for `|x|` beyond about `2^40`.
+#[cfg_attr(not(feature="deterministic-trig"), allow(dead_code))]
+mod soft_trig {
+    use super::*;
+
+    fn c<T: Real>(x: f64) -> T {
+        T::from(x).unwrap()
+    }
+
+    // Reduces `x` to `r` in `[-PI/4, PI/4]`, returning the quadrant of `x` in `0..4`.
+    //
+    // This is done in `f64`, because in `f32` the products of `k` with the parts of PI/2 below
+    // would stop being exact past `|x| ~ 2^8`, and `sin(1e4_f32)` would be off by about `6e-5`.
+    fn reduce<T: Real>(x: T) -> (T, u8) {
+        let (r, q) = reduce_f64(x.to_f64().unwrap());
+        (c(r), q)
+    }
+    fn reduce_f64(x: f64) -> (f64, u8) {
+        let k = Real::round(x * core::f64::consts::FRAC_2_PI);
+        // NOTE: PI/2 split in four parts (Cody-Waite). The first three have only 16 significant
+        // bits, so that their products with `k` are exact as long as `|k| < 2^37`.
+        let r = x - k * 1.570770263671875;
+        let r = r - k * 2.6063062250614166e-05;
+        let r = r - k * 6.077094383272197e-11;
+        let r = r - k * 6.123233995736766e-17;
+        let q = k - Real::floor(k / 4.) * 4.;
+        (r, q as u8)
+    }
+    fn kernel_sin<T: Real>(r: T) -> T {
+        let z = r * r;
+        let p = c::<T>(1.58969099521155e-10);
+        let p = p * z + c(-2.5050760253406863e-08);
+        let p = p * z + c(2.7557313707070068e-06);
+        let p = p * z + c(-0.0001984126982985795);
+        let p = p * z + c(0.00833333333332249);
+        let p = p * z + c(-0.16666666666666632);
+        r + r * z * p
+    }
+    fn kernel_cos<T: Real>(r: T) -> T {
+        let z = r * r;
+        let p = c::<T>(-1.1359647557788195e-11);
+        let p = p * z + c(2.087572321298175e-09);
+        let p = p * z + c(-2.7557314351390663e-07);
+        let p = p * z + c(2.480158728947673e-05);
+        let p = p * z + c(-0.001388888888887411);
+        let p = p * z + c(0.0416666666666666);
+        T::one() - z * c(0.5) + z * z * p
+    }
+    // Arc tangent for `|x| <= 7/16`.
+    fn kernel_atan<T: Real>(x: T) -> T {
+        let z = x * x;
+        let w = z * z;
+        let s1 = c::<T>(0.016285820115365782);
+        let s1 = s1 * w + c(0.049768779946159324);
+        let s1 = s1 * w + c(0.06661073137387531);
+        let s1 = s1 * w + c(0.09090887133436507);
+        let s1 = s1 * w + c(0.14285714272503466);
+        let s1 = s1 * w + c(0.3333333333333293);
+        let s2 = c::<T>(-0.036531572744216916);
+        let s2 = s2 * w + c(-0.058335701337905735);
+        let s2 = s2 * w + c(-0.0769187620504483);
+        let s2 = s2 * w + c(-0.11111110405462356);
+        let s2 = s2 * w + c(-0.19999999999876483);
+        x - x * (z * s1 + w * s2)
+    }
+
+    pub fn sin<T: Real>(x: T) -> T {
+        let zero = x * T::zero(); // NaN for NaN and infinities
+        if zero != T::zero() {
+            return zero;
+        }
+        let (r, q) = reduce(x);
+        match q {
+            0 => kernel_sin(r),
+            1 => kernel_cos(r),
+            2 => -kernel_sin(r),
+            _ => -kernel_cos(r),
+        }
+    }
+    pub fn cos<T: Real>(x: T) -> T {
+        let zero = x * T::zero();
+        if zero != T::zero() {
+            return zero;
+        }
+        let (r, q) = reduce(x);
+        match q {
+            0 => kernel_cos(r),
+            1 => -kernel_sin(r),
+            2 => -kernel_cos(r),
+            _ => kernel_sin(r),
+        }
+    }
+    pub fn tan<T: Real>(x: T) -> T {
+        sin(x) / cos(x)
+    }
+    pub fn atan<T: Real>(x: T) -> T {
+        let a = x.abs();
+        let r = if a > T::one() {
+            c::<T>(core::f64::consts::FRAC_PI_2) - atan(a.recip())
+        } else if a > c(0.41421356237309503) {
+            c::<T>(core::f64::consts::FRAC_PI_4) + kernel_atan((a - T::one()) / (a + T::one()))
+        } else {
+            kernel_atan(a)
+        };
+        if x.is_sign_negative() { -r } else { r }
+    }
+    pub fn atan2<T: Real>(y: T, x: T) -> T {
+        let pi = c::<T>(core::f64::consts::PI);
+        if x > T::zero() {
+            atan(y / x)
+        } else if x < T::zero() {
+            if y.is_sign_negative() { atan(y / x) - pi } else { atan(y / x) + pi }
+        } else if x == T::zero() {
+            let half_pi = c::<T>(core::f64::consts::FRAC_PI_2);
+            if y > T::zero() {
+                half_pi
+            } else if y < T::zero() {
+                -half_pi
+            } else if x.is_sign_negative() {
+                if y.is_sign_negative() { -pi } else { pi }
+            } else {
+                y
+            }
+        } else {
+            x + y
+        }
+    }
+    pub fn acos<T: Real>(x: T) -> T {
+        atan2(((T::one() - x) * (T::one() + x)).sqrt(), x)
+    }
+}
+
+/// A fixed-size spatial vector, which allows writing algorithms once for any number of dimensions.
+///
+/// This is implemented by `Vec2`, `Vec3` and `Vec4`. Elements are accessed by index, through
+/// the `Deref<Target=[Scalar]>` implementation they already have.
+///
+/// ```
+/// use vek::{Vec2, Vec3, ops::VectorSpace};
+///
+/// // The point of a set which is farthest along a direction (e.g a GJK support function).
+/// fn support<V: VectorSpace<Scalar=f32>>(points: &[V], dir: V) -> V {
+///     let mut best = points[0];
+///     for &p in points {
+///         if p.dot(dir) > best.dot(dir) {
+///             best = p;
+///         }
+///     }
+///     best
+/// }
+/// let square = [Vec2::new(0., 0.), Vec2::new(1., 0.), Vec2::new(1., 1.), Vec2::new(0., 1.)];
+/// assert_eq!(support(&square, Vec2::new(-1., 2.)), Vec2::new(0., 1.));
+/// let tetrahedron = [Vec3::zero(), Vec3::unit_x(), Vec3::unit_y(), Vec3::unit_z()];
+/// assert_eq!(support(&tetrahedron, Vec3::new(1., -1., 2.)), Vec3::unit_z());
+/// assert_eq!(<Vec3<f32> as VectorSpace>::DIM, 3);
+/// ```
+pub trait VectorSpace: Copy + PartialEq
+    + Deref<Target=[<Self as VectorSpace>::Scalar]> + DerefMut
+    + Add<Output=Self> + Sub<Output=Self>
+    + Mul<<Self as VectorSpace>::Scalar, Output=Self> + Div<<Self as VectorSpace>::Scalar, Output=Self>
+{
+    /// The type of this vector's elements.
+    type Scalar: Copy;
+    /// The number of elements (i.e dimensions) of this vector.
+    const DIM: usize;
+    /// Creates a vector by calling `f` with the index of each element, in order.
+    fn from_fn<F: FnMut(usize) -> Self::Scalar>(f: F) -> Self;
+    /// Creates a vector with all elements set to `val`.
+    fn broadcast(val: Self::Scalar) -> Self {
+        Self::from_fn(|_| val)
+    }
+    /// Returns a vector with each element of this one converted with `f`.
+    fn map_elements<F: FnMut(Self::Scalar) -> Self::Scalar>(self, mut f: F) -> Self {
+        Self::from_fn(|i| f(self[i]))
+    }
+    /// The dot product of two vectors.
+    fn dot(self, rhs: Self) -> Self::Scalar where Self::Scalar: Zero + Mul<Output=Self::Scalar> {
+        (0..Self::DIM).fold(Self::Scalar::zero(), |acc, i| acc + self[i] * rhs[i])
+    }
+    /// The squared magnitude of this vector.
+    fn magnitude_squared(self) -> Self::Scalar where Self::Scalar: Zero + Mul<Output=Self::Scalar> {
+        self.dot(self)
+    }
+    /// The magnitude of this vector.
+    fn magnitude(self) -> Self::Scalar where Self::Scalar: Real {
+        self.magnitude_squared().sqrt()
+    }
+    /// The squared distance between two points.
+    fn distance_squared(a: Self, b: Self) -> Self::Scalar where Self::Scalar: Zero + Mul<Output=Self::Scalar> {
+        (a - b).magnitude_squared()
+    }
+    /// The distance between two points.
+    fn distance(a: Self, b: Self) -> Self::Scalar where Self::Scalar: Real {
+        (a - b).magnitude()
+    }
+    /// Returns the element-wise minimum of two vectors, using partial ordering.
+    fn partial_min(a: Self, b: Self) -> Self where Self::Scalar: PartialOrd {
+        Self::from_fn(|i| partial_min(a[i], b[i]))
+    }
+    /// Returns the element-wise maximum of two vectors, using partial ordering.
+    fn partial_max(a: Self, b: Self) -> Self where Self::Scalar: PartialOrd {
+        Self::from_fn(|i| partial_max(a[i], b[i]))
+    }
+}
+
 /// Trait for types that are suitable for representing a color component value.
 pub trait ColorComponent : Zero {
     /// The minimum value such that the color is at its maximum.
@@ -883,6 +1691,473 @@ mod tests {
         };
     }
 
+    mod angles {
+        use super::*;
+        use crate::{Mat4, Quaternion, Vec2, Vec3};
+        use core::f64::consts::{PI, FRAC_PI_2};
+
+        #[test] fn conversions() {
+            assert_relative_eq!(Radians::from(Degrees(180_f64)).0, PI);
+            assert_relative_eq!(Degrees::from(Radians(FRAC_PI_2)).0, 90.);
+            assert_relative_eq!(Degrees(45_f64).into_angle_radians(), PI / 4.);
+            assert_eq!(Radians(1_f64).into_angle_radians(), 1.);
+            assert_eq!(1_f64.into_angle_radians(), 1.);
+        }
+        #[test] fn wrap() {
+            assert_relative_eq!(Radians(PI).wrap().0, PI);
+            assert_relative_eq!(Radians(-PI).wrap().0, PI);
+            assert_relative_eq!(Radians(5. * PI / 2.).wrap().0, FRAC_PI_2);
+            assert_relative_eq!(Radians(-5. * PI / 2.).wrap().0, -FRAC_PI_2);
+            assert_eq!(Degrees(180_f64).wrap().0, 180.);
+            assert_eq!(Degrees(-180_f64).wrap().0, 180.);
+            assert_eq!(Degrees(540_f64).wrap().0, 180.);
+            assert_eq!(Degrees(-190_f64).wrap().0, 170.);
+            assert_eq!(Degrees(0_f64).wrap().0, 0.);
+        }
+        #[test] fn lerp_shortest() {
+            assert_relative_eq!(Degrees::lerp_shortest(Degrees(350_f64), Degrees(10.), 0.5).wrap().0, 0.);
+            assert_relative_eq!(Degrees::lerp_shortest(Degrees(10_f64), Degrees(-30.), 0.25).0, 0.);
+            assert_relative_eq!(Radians::lerp_shortest(Radians(3_f64), Radians(-3.), 0.5).wrap().0, PI);
+        }
+        #[test] fn arithmetic() {
+            let mut a = Degrees(30_f64) + Degrees(60.) - Degrees(10.);
+            a *= 2.;
+            a /= 4.;
+            a += Degrees(5.);
+            a -= Degrees(5.);
+            assert_eq!(-a, Degrees(-40.));
+        }
+        #[test] fn constructors_accept_any_angle() {
+            assert_relative_eq!(Mat4::rotation_z(Degrees(90_f64)), Mat4::rotation_z(Radians(FRAC_PI_2)));
+            assert_relative_eq!(Mat4::rotation_z(Degrees(90_f64)), Mat4::rotation_z(FRAC_PI_2));
+            assert_relative_eq!(Mat4::rotation_3d(Degrees(90_f64), Vec3::unit_y()), Mat4::rotation_y(FRAC_PI_2));
+            assert_relative_eq!(Quaternion::rotation_3d(Degrees(90_f64), Vec3::unit_x()), Quaternion::rotation_x(Radians(FRAC_PI_2)));
+            assert_relative_eq!(Vec2::from_angle(Degrees(90_f64)), Vec2::from_angle(FRAC_PI_2));
+        }
+    }
+
+    mod soft_trig {
+        use super::super::soft_trig;
+
+        #[test]
+        fn close_to_std() {
+            for i in -2000..2000 {
+                let x = i as f64 / 97.;
+                assert_relative_eq!(soft_trig::sin(x), x.sin(), epsilon = 1e-15);
+                assert_relative_eq!(soft_trig::cos(x), x.cos(), epsilon = 1e-15);
+                assert_relative_eq!(soft_trig::atan2(x, 1.5), x.atan2(1.5), epsilon = 1e-15);
+                assert_relative_eq!(soft_trig::atan2(-1.5, x), (-1.5_f64).atan2(x), epsilon = 1e-15);
+                assert_relative_eq!(soft_trig::sin(x as f32), (x as f32).sin(), epsilon = 1e-6);
+                assert_relative_eq!(soft_trig::cos(x as f32), (x as f32).cos(), epsilon = 1e-6);
+            }
+            for i in -100..=100 {
+                let x = i as f64 / 100.;
+                assert_relative_eq!(soft_trig::acos(x), x.acos(), epsilon = 1e-15);
+            }
+        }
+        // Pins the accuracy documented on `soft_trig`: absolute errors of `sin()` and `cos()` are
+        // within `2 * T::epsilon()` for `|x| < 2^32`, including for `f32`.
+        #[test]
+        fn error_bound() {
+            let mut state = 0x9e3779b97f4a7c15_u64;
+            for &range in &[1_f64, 256., 65536., 4294967296.] {
+                for _ in 0..20000 {
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    let x = ((state >> 11) as f64 / (1_u64 << 53) as f64 * 2. - 1.) * range;
+                    assert!((soft_trig::sin(x) - x.sin()).abs() <= 2. * f64::EPSILON, "sin({})", x);
+                    assert!((soft_trig::cos(x) - x.cos()).abs() <= 2. * f64::EPSILON, "cos({})", x);
+                    let x = x as f32;
+                    let (sin, cos) = ((x as f64).sin(), (x as f64).cos());
+                    assert!((soft_trig::sin(x) as f64 - sin).abs() <= 2. * f32::EPSILON as f64, "sin({}_f32)", x);
+                    assert!((soft_trig::cos(x) as f64 - cos).abs() <= 2. * f32::EPSILON as f64, "cos({}_f32)", x);
+                }
+            }
+            // Used to be off by about 6e-5, when `f32` arguments were reduced in `f32`.
+            assert!((soft_trig::sin(1e4_f32) as f64 - 1e4_f64.sin()).abs() <= f32::EPSILON as f64);
+        }
+        #[test]
+        fn special_values() {
+            use core::f64::consts::PI;
+            assert!(soft_trig::sin(f64::NAN).is_nan());
+            assert!(soft_trig::cos(f64::INFINITY).is_nan());
+            assert_eq!(soft_trig::atan2(0_f64, 0.), 0.);
+            assert_eq!(soft_trig::atan2(0_f64, -0.), PI);
+            assert_eq!(soft_trig::atan2(-0_f64, -0.), -PI);
+            assert_eq!(soft_trig::atan2(1_f64, 0.), PI / 2.);
+            assert_eq!(soft_trig::atan2(1_f64, f64::INFINITY), 0.);
+        }
+    }
+
+    // Snapshots of exact bit patterns, which must not change across platforms or releases.
+    #[cfg(feature="deterministic-trig")]
+    mod deterministic_trig {
+        use crate::{Mat4, Quaternion, Vec3};
+
+        #[test]
+        fn rotations() {
+            let q = Quaternion::rotation_3d(0.7_f32, Vec3::new(1_f32, 2., 3.).normalized());
+            assert_eq!(q.into_vec4().map(f32::to_bits).into_array(), SNAPSHOT_Q);
+            let m = Mat4::rotation_z(2.5_f32) * Mat4::rotation_x(-1.2_f32);
+            assert_eq!(m.into_col_array().map(f32::to_bits), SNAPSHOT_M);
+        }
+        #[test]
+        fn projections() {
+            let m = Mat4::perspective_rh_zo(1.1_f32, 16. / 9., 0.1, 100.);
+            assert_eq!(m.into_col_array().map(f32::to_bits), SNAPSHOT_P);
+        }
+        #[test]
+        fn scalars() {
+            assert_eq!(crate::ops::sin(1_f64).to_bits(), SNAPSHOT_SIN);
+            assert_eq!(crate::ops::atan2(1_f64, -3.).to_bits(), SNAPSHOT_ATAN2);
+        }
+        const SNAPSHOT_Q: [u32; 4] = [1035710332, 1044098940, 1049412508, 1064336059];
+        const SNAPSHOT_M: [u32; 16] = [3209500608, 1058616697, 0, 0, 3193835729, 3197411912, 3211696669, 0, 3205417970, 3208587148, 1052346100, 0, 0, 0, 0, 1065353216];
+        const SNAPSHOT_P: [u32; 16] = [1063968437, 0, 0, 0, 0, 1070646775, 0, 0, 0, 0, 3212845261, 3212836864, 0, 0, 3184329032, 0];
+        const SNAPSHOT_SIN: u64 = 4605754516372524270;
+        const SNAPSHOT_ATAN2: u64 = 4613532138713602364;
+    }
+
+    mod vector_space {
+        use crate::{Vec2, Vec3, Vec4};
+        use super::super::VectorSpace;
+
+        // An implicit k-d tree, stored in a slice: the median of each subtree sits in its middle,
+        // with lower elements (along the split axis) before it and greater ones after it.
+        fn build_kd_tree<V: VectorSpace<Scalar=f64>>(points: &mut [V], depth: usize) {
+            if points.len() <= 1 {
+                return;
+            }
+            let axis = depth % V::DIM;
+            let mid = points.len() / 2;
+            points.select_nth_unstable_by(mid, |a, b| a[axis].partial_cmp(&b[axis]).unwrap());
+            let (lo, hi) = points.split_at_mut(mid);
+            build_kd_tree(lo, depth + 1);
+            build_kd_tree(&mut hi[1..], depth + 1);
+        }
+        fn nearest<V: VectorSpace<Scalar=f64>>(points: &[V], depth: usize, target: V, best: &mut Option<V>) {
+            if points.is_empty() {
+                return;
+            }
+            let axis = depth % V::DIM;
+            let mid = points.len() / 2;
+            let p = points[mid];
+            let is_closer = match *best {
+                None => true,
+                Some(b) => V::distance_squared(p, target) < V::distance_squared(b, target),
+            };
+            if is_closer {
+                *best = Some(p);
+            }
+            let (lo, hi) = (&points[..mid], &points[mid + 1..]);
+            let delta = target[axis] - p[axis];
+            let (near, far) = if delta < 0. { (lo, hi) } else { (hi, lo) };
+            nearest(near, depth + 1, target, best);
+            if delta * delta < V::distance_squared(best.unwrap(), target) {
+                nearest(far, depth + 1, target, best);
+            }
+        }
+        fn brute_force_nearest<V: VectorSpace<Scalar=f64>>(points: &[V], target: V) -> V {
+            let mut best = points[0];
+            for &p in points {
+                if V::distance_squared(p, target) < V::distance_squared(best, target) {
+                    best = p;
+                }
+            }
+            best
+        }
+        // Deterministic pseudo-random coordinates in [-10, 10).
+        fn coords(seed: usize) -> impl FnMut(usize) -> f64 {
+            let mut state = (seed as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1;
+            move |_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                (state % 20_000) as f64 / 1000. - 10.
+            }
+        }
+        fn check_kd_tree<V: VectorSpace<Scalar=f64> + core::fmt::Debug>() {
+            let mut points = [V::broadcast(0.); 200];
+            for (i, p) in points.iter_mut().enumerate() {
+                *p = V::from_fn(coords(i));
+            }
+            let original = points;
+            build_kd_tree(&mut points, 0);
+            for i in 0..100 {
+                let target = V::from_fn(coords(1000 + i));
+                let mut best = None;
+                nearest(&points, 0, target, &mut best);
+                let expected = brute_force_nearest(&original, target);
+                assert_eq!(V::distance_squared(best.unwrap(), target), V::distance_squared(expected, target));
+            }
+        }
+
+        #[test] fn kd_tree_nearest_neighbour() {
+            check_kd_tree::<Vec2<f64>>();
+            check_kd_tree::<Vec3<f64>>();
+            check_kd_tree::<Vec4<f64>>();
+        }
+        #[test] fn matches_inherent_methods() {
+            let (a, b) = (Vec3::new(1_f64, -2., 3.), Vec3::new(-4., 0.5, 2.));
+            assert_eq!(VectorSpace::dot(a, b), a.dot(b));
+            assert_eq!(VectorSpace::magnitude(a), a.magnitude());
+            assert_eq!(<Vec3<f64> as VectorSpace>::distance(a, b), a.distance(b));
+            assert_eq!(<Vec3<f64> as VectorSpace>::partial_min(a, b), Vec3::partial_min(a, b));
+            assert_eq!(<Vec3<f64> as VectorSpace>::partial_max(a, b), Vec3::partial_max(a, b));
+            assert_eq!(VectorSpace::map_elements(a, |e| e * 2.), a * 2.);
+            assert_eq!(<Vec2<i32> as VectorSpace>::from_fn(|i| i as i32 * 10), Vec2::new(0, 10));
+            assert_eq!(<Vec4<u8> as VectorSpace>::broadcast(7), Vec4::broadcast(7));
+            assert_eq!(<Vec4<u8> as VectorSpace>::DIM, 4);
+        }
+    }
+    mod derivatives {
+        use crate::{Mat3, Vec3};
+        use super::super::{gradient, jacobian, divergence, curl};
+
+        #[test]
+        fn linear_field_has_exact_jacobian() {
+            let a = Mat3::new(
+                0.5_f64, -2., 3.,
+                1., 0.25, -1.,
+                4., 0., 2.,
+            );
+            let b = Vec3::new(1., -1., 0.5);
+            for p in [Vec3::zero(), Vec3::new(3., -7., 11.), Vec3::new(-0.1, 0.2, 100.)].iter().cloned() {
+                let j = jacobian(|v| a * v + b, p, 0.5);
+                assert_relative_eq!(j, a, epsilon = 1e-12);
+                // The convention matches Mat3's multiply order.
+                let d = Vec3::new(1e-3, -2e-3, 5e-4);
+                assert_relative_eq!(j * d, (a * (p + d) + b) - (a * p + b), epsilon = 1e-12);
+                assert_relative_eq!(divergence(|v| a * v + b, p, 0.5), 0.5 + 0.25 + 2., epsilon = 1e-12);
+            }
+        }
+        #[test]
+        fn gradient_of_quadratic() {
+            let f = |p: Vec3<f64>| p.x * p.y + p.z * p.z - 2. * p.x;
+            let p = Vec3::new(2., -3., 0.5);
+            // Central differences are exact for quadratics, up to rounding.
+            assert_relative_eq!(gradient(f, p, 0.1), Vec3::new(p.y - 2., p.x, 2. * p.z), epsilon = 1e-12);
+        }
+        #[test]
+        fn curl_of_rotation_is_twice_angular_velocity() {
+            let w = Vec3::new(-0.3_f64, 1.5, 0.7);
+            let c = Vec3::new(1., 2., -3.);
+            for p in [Vec3::zero(), Vec3::new(4., -1., 2.)].iter().cloned() {
+                assert_relative_eq!(curl(|v| w.cross(v - c), p, 1e-2), w * 2., epsilon = 1e-12);
+                assert_relative_eq!(divergence(|v| w.cross(v - c), p, 1e-2), 0., epsilon = 1e-12);
+                // A gradient field is curl-free.
+                let g = |v: Vec3<f64>| gradient(|q| q.x * q.x * q.y + q.z.sin(), v, 1e-4);
+                assert_relative_eq!(curl(g, p, 1e-3), Vec3::zero(), epsilon = 1e-5);
+            }
+        }
+    }
+    mod texture_wrap {
+        use crate::{Vec2, Extent2};
+        use super::super::{wrap_uv, wrap_texel, WrapMode};
+
+        // Texel coordinates from -9 to 9 in a texture of size 4, as given by OpenGL's rules.
+        const REPEAT: [u32; 19]          = [3, 0, 1, 2, 3, 0, 1, 2, 3, 0, 1, 2, 3, 0, 1, 2, 3, 0, 1];
+        const MIRRORED_REPEAT: [u32; 19] = [0, 0, 1, 2, 3, 3, 2, 1, 0, 0, 1, 2, 3, 3, 2, 1, 0, 0, 1];
+        const CLAMP_TO_EDGE: [u32; 19]   = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 2, 3, 3, 3, 3, 3, 3, 3];
+
+        #[test] fn texel_table() {
+            let size = Extent2::new(4, 7);
+            for (i, ((&repeat, &mirrored), &clamped)) in REPEAT.iter().zip(MIRRORED_REPEAT.iter()).zip(CLAMP_TO_EDGE.iter()).enumerate() {
+                let x = i as i32 - 9;
+                assert_eq!(wrap_texel(Vec2::new(x, 0), size, WrapMode::Repeat).x, repeat, "{}", x);
+                assert_eq!(wrap_texel(Vec2::new(x, 0), size, WrapMode::MirroredRepeat).x, mirrored, "{}", x);
+                assert_eq!(wrap_texel(Vec2::new(x, 0), size, WrapMode::ClampToEdge).x, clamped, "{}", x);
+            }
+            // Each axis uses its own size.
+            assert_eq!(wrap_texel(Vec2::new(-1, -1), size, WrapMode::Repeat), Vec2::new(3, 6));
+            assert_eq!(wrap_texel(Vec2::new(-1, 7), size, WrapMode::MirroredRepeat), Vec2::new(0, 6));
+            assert_eq!(wrap_texel(Vec2::new(4, 7), size, WrapMode::ClampToEdge), Vec2::new(3, 6));
+        }
+        #[test] fn texel_extremes() {
+            let one = Extent2::new(1, 1);
+            for &mode in &[WrapMode::Repeat, WrapMode::MirroredRepeat, WrapMode::ClampToEdge] {
+                for &x in &[i32::MIN, -1, 0, 1, i32::MAX] {
+                    assert_eq!(wrap_texel(Vec2::new(x, x), one, mode), Vec2::zero());
+                }
+            }
+            let huge = Extent2::new(u32::MAX, u32::MAX);
+            assert_eq!(wrap_texel(Vec2::new(-1, i32::MIN), huge, WrapMode::Repeat), Vec2::new(u32::MAX - 1, u32::MAX - (1 << 31)));
+            assert_eq!(wrap_texel(Vec2::new(-1, i32::MAX), huge, WrapMode::MirroredRepeat), Vec2::new(0, i32::MAX as u32));
+            assert_eq!(wrap_texel(Vec2::new(i32::MIN, i32::MAX), huge, WrapMode::ClampToEdge), Vec2::new(0, i32::MAX as u32));
+        }
+        #[test]
+        #[should_panic]
+        fn empty_texture() {
+            wrap_texel(Vec2::new(0, 0), Extent2::new(4, 0), WrapMode::ClampToEdge);
+        }
+        #[test] fn uv_table() {
+            let cases = [
+                // u, Repeat, MirroredRepeat, ClampToEdge
+                (-2.25, 0.75, 0.25, 0.),
+                (-1., 0., 1., 0.),
+                (-0.75, 0.25, 0.75, 0.),
+                (-0.25, 0.75, 0.25, 0.),
+                (0., 0., 0., 0.),
+                (0.25, 0.25, 0.25, 0.25),
+                (1., 0., 1., 1.),
+                (1.25, 0.25, 0.75, 1.),
+                (1.75, 0.75, 0.25, 1.),
+                (2., 0., 0., 1.),
+                (3.5, 0.5, 0.5, 1.),
+            ];
+            for &(u, repeat, mirrored, clamped) in cases.iter() {
+                let uv = Vec2::new(u, 0.5_f64);
+                assert_eq!(wrap_uv(uv, WrapMode::Repeat), Vec2::new(repeat, 0.5), "{}", u);
+                assert_eq!(wrap_uv(uv, WrapMode::MirroredRepeat), Vec2::new(mirrored, 0.5), "{}", u);
+                assert_eq!(wrap_uv(uv, WrapMode::ClampToEdge), Vec2::new(clamped, 0.5), "{}", u);
+            }
+        }
+        #[test] fn uv_agrees_with_texels() {
+            // At texel centers, addressing the wrapped coordinates gives the wrapped texel.
+            let size = Extent2::new(4, 5);
+            for &mode in &[WrapMode::Repeat, WrapMode::MirroredRepeat, WrapMode::ClampToEdge] {
+                for x in -20..20 {
+                    let texel = Vec2::new(x, -x);
+                    let uv = (texel.numcast::<f64>().unwrap() + 0.5) / Vec2::new(4., 5.);
+                    let wrapped = wrap_uv(uv, mode) * Vec2::new(4., 5.);
+                    let addressed = wrap_texel(wrapped.map(|x| x.floor() as i32), size, WrapMode::ClampToEdge);
+                    assert_eq!(addressed, wrap_texel(texel, size, mode), "{:?} {:?}", mode, texel);
+                }
+            }
+        }
+    }
+    mod fov {
+        use crate::{Mat4, Vec4, Extent2};
+        use super::super::{fov_y_from_fov_x, fov_x_from_fov_y, fov_from_focal_length, zoom_factor_to_fov};
+
+        // Horizontal fields of view and aspect ratios, from portrait to ultra-wide.
+        const FOVS_X: [f64; 5] = [0.1, 0.9, std::f64::consts::FRAC_PI_2, 2.5, 3.1];
+        const ASPECTS: [f64; 5] = [0.5, 1., 4. / 3., 16. / 9., 32. / 9.];
+
+        #[test] fn round_trip() {
+            for &fov_x in FOVS_X.iter() {
+                for &aspect in ASPECTS.iter() {
+                    let fov_y = fov_y_from_fov_x(fov_x, aspect);
+                    assert!(fov_y > 0. && fov_y < std::f64::consts::PI);
+                    assert_eq!(fov_y < fov_x, aspect > 1., "{} {}", fov_x, aspect);
+                    assert_relative_eq!(fov_x_from_fov_y(fov_y, aspect), fov_x, epsilon = 1e-12);
+                }
+            }
+        }
+        #[test] fn projection_has_same_horizontal_extent() {
+            for &fov_x in FOVS_X.iter() {
+                for &aspect in ASPECTS.iter() {
+                    let from_fov_y = Mat4::perspective_rh_zo(fov_y_from_fov_x(fov_x, aspect), aspect, 0.1, 100.);
+                    // The same frustum, on its side: its vertical extent is given by `fov_x`.
+                    let sideways = Mat4::perspective_rh_zo(fov_x, 1. / aspect, 0.1, 100.);
+                    assert_relative_eq!(from_fov_y[(0, 0)], sideways[(1, 1)], max_relative = 1e-12);
+                    assert_relative_eq!(from_fov_y[(1, 1)], sideways[(0, 0)], max_relative = 1e-12);
+                    // A point on the edge of the horizontal field of view lands on the edge of the screen.
+                    let depth = 7.;
+                    let edge = from_fov_y * Vec4::new(depth * (fov_x / 2.).tan(), 0., -depth, 1.);
+                    assert_relative_eq!(edge.x / edge.w, 1., epsilon = 1e-9);
+                    let size = Extent2::new(aspect * 600., 600.);
+                    let from_size = Mat4::perspective_fov_rh_zo(fov_y_from_fov_x(fov_x, size.aspect_ratio()), size.w, size.h, 0.1, 100.);
+                    assert_relative_eq!(from_size, from_fov_y, max_relative = 1e-9);
+                }
+            }
+        }
+        #[test] fn focal_length_and_zoom() {
+            // Focal length equal to half the sensor size: 90°.
+            assert_relative_eq!(fov_from_focal_length(18., 36.), std::f64::consts::FRAC_PI_2);
+            for &fov in FOVS_X.iter() {
+                assert_relative_eq!(zoom_factor_to_fov(fov, 1.), fov, epsilon = 1e-12);
+                // Zooming twice by 2 is zooming by 4, and zooming back undoes it.
+                let zoomed = zoom_factor_to_fov(zoom_factor_to_fov(fov, 2.), 2.);
+                assert_relative_eq!(zoomed, zoom_factor_to_fov(fov, 4.), epsilon = 1e-12);
+                assert_relative_eq!(zoom_factor_to_fov(zoomed, 0.25), fov, epsilon = 1e-12);
+                // Zoom is linear in the tangent of the half angle, like the focal length.
+                assert_relative_eq!((zoom_factor_to_fov(fov, 3.) / 2.).tan() * 3., (fov / 2.).tan(), max_relative = 1e-12);
+            }
+        }
+    }
+    mod sdf {
+        use crate::{Aabr, Disk, Vec2};
+        use super::super::{smooth_min, smooth_max, sdf_union, sdf_intersection, sdf_subtraction, sdf_round, sdf_onion};
+
+        // Deterministic pseudo-random values in [-1, 1), using a xorshift generator.
+        fn random() -> impl FnMut() -> f64 {
+            let mut state = 0x2545f4914f6cdd1d_u64;
+            move || {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                (state >> 11) as f64 / (1_u64 << 52) as f64 - 1.
+            }
+        }
+
+        #[test]
+        fn smooth_min_max() {
+            let mut random = random();
+            for _ in 0..1000 {
+                let (a, b, k) = (random() * 4., random() * 4., random().abs());
+                assert_eq!(smooth_min(a, b, k), smooth_min(b, a, k));
+                assert_eq!(smooth_max(a, b, k), smooth_max(b, a, k));
+                assert!(smooth_min(a, b, k) <= a.min(b));
+                assert!(smooth_min(a, b, k) >= a.min(b) - k / 4.);
+                assert!(smooth_max(a, b, k) >= a.max(b));
+                assert!(smooth_max(a, b, k) <= a.max(b) + k / 4.);
+                if (a - b).abs() >= k {
+                    assert_eq!(smooth_min(a, b, k), a.min(b));
+                }
+                // Converges to the plain minimum as `k` goes to zero.
+                for &k in &[1e-2, 1e-4, 1e-8, 0.] {
+                    assert_relative_eq!(smooth_min(a, b, k), a.min(b), epsilon = k / 4. + 1e-15);
+                }
+                assert_eq!(smooth_min(a, b, 0.), a.min(b));
+                assert_eq!(smooth_min(a, b, -1.), a.min(b));
+            }
+        }
+        #[test]
+        #[cfg(feature="std")]
+        fn combinations_are_lipschitz_bounds() {
+            use alloc::vec::Vec;
+            let disk = Disk::new(Vec2::new(0.5_f64, -0.25), 1.);
+            let aabr = Aabr { min: Vec2::new(-1.5, -1.), max: Vec2::new(0.75, 0.5) };
+            let (a, b) = (|p| disk.signed_distance(p), move |p| aabr.signed_distance(p));
+            let sdfs: [&dyn Fn(Vec2<f64>) -> f64; 9] = [
+                &|p| sdf_union(a(p), b(p)),
+                &|p| sdf_intersection(a(p), b(p)),
+                &|p| sdf_subtraction(a(p), b(p)),
+                &|p| sdf_subtraction(b(p), a(p)),
+                &|p| smooth_min(a(p), b(p), 0.5),
+                &|p| smooth_max(a(p), b(p), 0.5),
+                &|p| smooth_max(a(p), -b(p), 0.5),
+                &|p| sdf_round(sdf_intersection(a(p), b(p)), 0.25),
+                &|p| sdf_onion(sdf_onion(sdf_union(a(p), b(p)), 0.3), 0.1),
+            ];
+            let mut random = random();
+            let mut random_point = || Vec2::new(random(), random()) * 3.;
+            for sdf in sdfs.iter() {
+                // Lipschitz: the value never changes faster than the distance between points.
+                for _ in 0..2000 {
+                    let (p, q) = (random_point(), random_point() * 0.1);
+                    let q = p + q;
+                    assert!((sdf(p) - sdf(q)).abs() <= p.distance(q) * (1. + 1e-12));
+                }
+                // Bound: outside, no point of the shape is closer than the returned distance.
+                let inside: Vec<_> = (0..200 * 200)
+                    .map(|i| Vec2::new((i % 200) as f64, (i / 200) as f64) * (6. / 199.) - 3.)
+                    .filter(|&p| sdf(p) <= 0.)
+                    .collect();
+                assert!(!inside.is_empty());
+                for _ in 0..100 {
+                    let p = random_point();
+                    let d = sdf(p);
+                    if d > 0. {
+                        assert!(inside.iter().all(|&s| p.distance(s) >= d));
+                    }
+                }
+            }
+        }
+    }
+
     for_each_float_type!{f32 f64}
     for_each_signed_type!{i8 i16 i32 i64 isize}
     for_each_unsigned_type!{u8 u16 u32 u64 usize}
@@ -0,0 +1,253 @@
+//! Utilities for simple closed 2D polygons, given as a slice of vertices.
+//!
+//! The last vertex is implicitly connected to the first one; it should not be repeated.
+//! Both windings are accepted, unless stated otherwise.
+//!
+//! Functions which return a `Vec` are only available with the `std` feature.
+
+use num_traits::real::Real;
+#[cfg(feature="std")]
+use alloc::vec::Vec;
+#[cfg(feature="std")]
+use crate::ops::atan2;
+#[cfg(feature="std")]
+use crate::bezier::LineJoin;
+use crate::vec::repr_c::Vec2;
+
+/// Gets the signed area of a polygon; positive if its vertices are counter-clockwise
+/// (with Y pointing upwards).
+///
+/// ```
+/// # use vek::{Vec2, polygon::signed_area};
+/// let square = [Vec2::new(0_f32, 0.), Vec2::new(2., 0.), Vec2::new(2., 2.), Vec2::new(0., 2.)];
+/// assert_eq!(signed_area(&square), 4.);
+/// let mut reversed = square;
+/// reversed.reverse();
+/// assert_eq!(signed_area(&reversed), -4.);
+/// ```
+pub fn signed_area<T: Real>(points: &[Vec2<T>]) -> T {
+    let mut double_area = T::zero();
+    for (i, &a) in points.iter().enumerate() {
+        let b = points[(i + 1) % points.len()];
+        double_area = double_area + (a.x * b.y - a.y * b.x);
+    }
+    double_area / (T::one() + T::one())
+}
+
+/// Offsets a closed polygon by `distance`, outwards if it is positive, inwards if it is negative.
+///
+/// Each edge is moved along its normal by `distance`. Where moving the edges opens a gap between
+/// them (at convex corners when outsetting, at concave corners when insetting), the gap is filled
+/// according to `join`. Elsewhere, the corner becomes the intersection of the adjacent offset edges.
+/// Round joins are approximated by one vertex every 11.25° at most.
+///
+/// The result has the same winding as `points`. Consecutive duplicate vertices are ignored, and
+/// an empty `Vec` is returned for polygons with fewer than 3 distinct vertices or zero area.
+///
+/// **Limitation**: self-intersections are not resolved. When insetting by more than the local
+/// feature size (e.g the width of a spike), the affected part of the result is flipped over
+/// (and usually crosses the rest of the polygon) instead of vanishing; the same happens to
+/// concave notches narrower than twice `distance` when outsetting.
+///
+/// ```
+/// # extern crate vek;
+/// # #[macro_use] extern crate approx;
+/// # use vek::{Vec2, LineJoin, polygon::offset_polygon};
+/// # fn main() {
+/// let square = [Vec2::new(0_f32, 0.), Vec2::new(2., 0.), Vec2::new(2., 2.), Vec2::new(0., 2.)];
+/// let outset = offset_polygon(&square, 1., LineJoin::Miter(4.));
+/// let expected = [Vec2::new(-1., -1.), Vec2::new(3., -1.), Vec2::new(3., 3.), Vec2::new(-1., 3.)];
+/// for (p, e) in outset.iter().zip(expected.iter()) {
+///     assert_relative_eq!(p, e, epsilon = 1e-6);
+/// }
+/// // Bevel joins cut each outer corner.
+/// assert_eq!(offset_polygon(&square, 1., LineJoin::Bevel).len(), 8);
+/// # }
+/// ```
+#[cfg(feature="std")]
+pub fn offset_polygon<T: Real>(points: &[Vec2<T>], distance: T, join: LineJoin<T>) -> Vec<Vec2<T>> {
+    let mut points = points.to_vec();
+    points.dedup();
+    while points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+    let area = signed_area(&points);
+    if points.len() < 3 || area == T::zero() {
+        return Vec::new();
+    }
+    let one = T::one();
+    // Outward normals are on the right of edges for counter-clockwise polygons.
+    let side = if area > T::zero() { one } else { -one };
+    let outward_normal = |a: Vec2<T>, b: Vec2<T>| {
+        let d = (b - a).normalized();
+        Vec2::new(d.y, -d.x) * side
+    };
+    let round_step = T::from(core::f64::consts::PI / 16.).unwrap();
+
+    let mut out = Vec::with_capacity(points.len());
+    for (i, &p) in points.iter().enumerate() {
+        let prev = points[(i + points.len() - 1) % points.len()];
+        let next = points[(i + 1) % points.len()];
+        let (n_in, n_out) = (outward_normal(prev, p), outward_normal(p, next));
+        // Positive where the offset edges move apart from each other.
+        let cross = n_in.x * n_out.y - n_in.y * n_out.x;
+        let opens_gap = cross * side * distance > T::zero();
+        let bisector = n_in + n_out;
+        // `cos_half` is the cosine of half the angle between the normals.
+        let cos_half = if bisector.magnitude_squared() > T::epsilon() {
+            bisector.normalized().dot(n_in)
+        } else {
+            T::zero()
+        };
+        let bevel = |out: &mut Vec<_>| {
+            out.push(p + n_in * distance);
+            out.push(p + n_out * distance);
+        };
+        if cross == T::zero() && n_in.dot(n_out) > T::zero() {
+            // Collinear edges.
+            out.push(p + n_in * distance);
+        } else if !opens_gap {
+            // The offset edges cross each other; Their intersection is at infinity for hairpin turns.
+            if cos_half > T::epsilon() {
+                out.push(p + bisector.normalized() * (distance / cos_half));
+            } else {
+                bevel(&mut out);
+            }
+        } else {
+            match join {
+                LineJoin::Bevel => bevel(&mut out),
+                LineJoin::Miter(limit) => {
+                    // The miter's length divided by `distance` is 1 / cos_half.
+                    if cos_half > T::zero() && one <= limit * cos_half {
+                        out.push(p + bisector.normalized() * (distance / cos_half));
+                    } else {
+                        bevel(&mut out);
+                    }
+                },
+                LineJoin::Round => {
+                    let sweep = atan2(cross, n_in.dot(n_out));
+                    let steps = (sweep.abs() / round_step).ceil().max(one);
+                    let mut k = T::zero();
+                    while k <= steps {
+                        out.push(p + n_in.rotated_z(sweep * k / steps) * distance);
+                        k = k + one;
+                    }
+                },
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_polygon_eq(actual: &[Vec2<f64>], expected: &[Vec2<f64>]) {
+        assert_eq!(actual.len(), expected.len(), "{:?} != {:?}", actual, expected);
+        for (a, e) in actual.iter().zip(expected.iter()) {
+            assert_relative_eq!(*a, *e, epsilon = 1e-9);
+        }
+    }
+    // Scales a polygon about a center.
+    fn scaled(points: &[Vec2<f64>], center: Vec2<f64>, factor: f64) -> Vec<Vec2<f64>> {
+        points.iter().map(|&p| center + (p - center) * factor).collect()
+    }
+
+    #[test]
+    fn square_with_all_joins() {
+        let square = [Vec2::new(0., 0.), Vec2::new(2., 0.), Vec2::new(2., 2.), Vec2::new(0., 2.)];
+        let miter = offset_polygon(&square, 1., LineJoin::Miter(1.5));
+        assert_polygon_eq(&miter, &[Vec2::new(-1., -1.), Vec2::new(3., -1.), Vec2::new(3., 3.), Vec2::new(-1., 3.)]);
+        // A square corner's miter ratio is sqrt(2).
+        let limited = offset_polygon(&square, 1., LineJoin::Miter(1.4));
+        assert_eq!(limited, offset_polygon(&square, 1., LineJoin::Bevel));
+        assert_polygon_eq(&limited[..2], &[Vec2::new(-1., 0.), Vec2::new(0., -1.)]);
+
+        let round = offset_polygon(&square, 1., LineJoin::Round);
+        assert_eq!(round.len(), 4 * 9);
+        assert_polygon_eq(&round[..2], &[Vec2::new(-1., 0.), Vec2::new(-1., 0.).rotated_z(core::f64::consts::PI / 16.)]);
+        for p in &round {
+            let nearest = Vec2::new(p.x.clamp(0., 2.), p.y.clamp(0., 2.));
+            assert_relative_eq!(p.distance(nearest), 1., epsilon = 1e-9);
+        }
+
+        // Insetting moves the edges, and the join style doesn't matter for convex polygons.
+        let inset = [Vec2::new(0.25, 0.25), Vec2::new(1.75, 0.25), Vec2::new(1.75, 1.75), Vec2::new(0.25, 1.75)];
+        for &join in &[LineJoin::Miter(1.), LineJoin::Round, LineJoin::Bevel] {
+            assert_polygon_eq(&offset_polygon(&square, -0.25, join), &inset);
+        }
+        // Clockwise polygons are still offset outwards, and keep their winding.
+        let mut clockwise = square;
+        clockwise.reverse();
+        let mut expected = miter.clone();
+        expected.reverse();
+        assert_polygon_eq(&offset_polygon(&clockwise, 1., LineJoin::Miter(1.5)), &expected);
+    }
+    #[test]
+    fn plus_shape_concave_corners() {
+        let plus = [
+            Vec2::new(1., -3.), Vec2::new(1., -1.), Vec2::new(3., -1.), Vec2::new(3., 1.),
+            Vec2::new(1., 1.), Vec2::new(1., 3.), Vec2::new(-1., 3.), Vec2::new(-1., 1.),
+            Vec2::new(-3., 1.), Vec2::new(-3., -1.), Vec2::new(-1., -1.), Vec2::new(-1., -3.),
+        ];
+        // Outwards, concave corners are intersections, convex ones get the join.
+        let outset = offset_polygon(&plus, 0.5, LineJoin::Bevel);
+        assert_polygon_eq(&outset[..6], &[
+            Vec2::new(1., -3.5), Vec2::new(1.5, -3.),
+            Vec2::new(1.5, -1.5),
+            Vec2::new(3., -1.5), Vec2::new(3.5, -1.),
+            Vec2::new(3.5, 1.),
+        ]);
+        assert_eq!(outset.len(), 8 * 2 + 4);
+        // Inwards, it's the other way around.
+        let inset = offset_polygon(&plus, -0.5, LineJoin::Miter(2.));
+        assert_polygon_eq(&inset, &[
+            Vec2::new(0.5, -2.5), Vec2::new(0.5, -0.5), Vec2::new(2.5, -0.5), Vec2::new(2.5, 0.5),
+            Vec2::new(0.5, 0.5), Vec2::new(0.5, 2.5), Vec2::new(-0.5, 2.5), Vec2::new(-0.5, 0.5),
+            Vec2::new(-2.5, 0.5), Vec2::new(-2.5, -0.5), Vec2::new(-0.5, -0.5), Vec2::new(-0.5, -2.5),
+        ]);
+    }
+    #[test]
+    fn star_scales_about_its_center() {
+        // All edges of a regular star are at the same distance from its center, so mitering
+        // every corner is the same as scaling it.
+        let mut star = [Vec2::zero(); 10];
+        for (i, p) in star.iter_mut().enumerate() {
+            let radius = if i % 2 == 0 { 5. } else { 2. };
+            *p = Vec2::unit_y().rotated_z(i as f64 * core::f64::consts::PI / 5.) * radius;
+        }
+        let edge = star[1] - star[0];
+        let apothem = (star[0].x * edge.y - star[0].y * edge.x).abs() / edge.magnitude();
+        for &d in &[1., -0.5] {
+            let offset = offset_polygon(&star, d, LineJoin::Miter(100.));
+            assert_polygon_eq(&offset, &scaled(&star, Vec2::zero(), (apothem + d) / apothem));
+        }
+        // Bevels only affect the tips when outsetting, and the inner corners when insetting.
+        assert_eq!(offset_polygon(&star, 1., LineJoin::Bevel).len(), 15);
+        assert_eq!(offset_polygon(&star, -0.5, LineJoin::Bevel).len(), 15);
+    }
+    #[test]
+    fn inset_inverts_spike() {
+        // A right isosceles triangle's inset is the triangle scaled about its incenter.
+        let triangle = [Vec2::new(0., 0.), Vec2::new(4., 0.), Vec2::new(0., 4.)];
+        let inradius = 4. - 2. * 2_f64.sqrt();
+        let incenter = Vec2::broadcast(inradius);
+        let inset = offset_polygon(&triangle, -0.5, LineJoin::Round);
+        assert_polygon_eq(&inset, &scaled(&triangle, incenter, (inradius - 0.5) / inradius));
+        // Insetting by more than the inradius flips the triangle about its incenter, so that
+        // the spike at (0, 4) now points downwards.
+        let inverted = offset_polygon(&triangle, -2., LineJoin::Round);
+        assert_polygon_eq(&inverted, &scaled(&triangle, incenter, (inradius - 2.) / inradius));
+        assert!(inverted[2].y < incenter.y);
+    }
+    #[test]
+    fn degenerate_polygons() {
+        let line = [Vec2::new(0., 0.), Vec2::new(1., 1.), Vec2::new(2., 2.)];
+        assert!(offset_polygon(&line, 1., LineJoin::Bevel).is_empty());
+        assert!(offset_polygon(&line[..2], 1., LineJoin::Bevel).is_empty());
+        // Duplicate vertices, including a closing one, are ignored.
+        let square = [Vec2::new(0., 0.), Vec2::new(2., 0.), Vec2::new(2., 0.), Vec2::new(2., 2.), Vec2::new(0., 2.), Vec2::new(0., 0.)];
+        assert_polygon_eq(&offset_polygon(&square, 1., LineJoin::Miter(2.)), &[Vec2::new(-1., -1.), Vec2::new(3., -1.), Vec2::new(3., 3.), Vec2::new(-1., 3.)]);
+    }
+}
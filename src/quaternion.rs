@@ -68,10 +68,11 @@ macro_rules! impl_mul_by_vec {
         impl<T: Real + Add<T, Output=T>> Mul<$Vec3<T>> for Quaternion<T> {
             type Output = $Vec3<T>;
             fn mul(self, rhs: $Vec3<T>) -> Self::Output {
-                let $Vec3 { x, y, z } = rhs;
-                let v = Self { x, y, z, w: T::zero() };
-                let qi = self.conjugate(); // We want the inverse, and assume self is normalized.
-                (self * v * qi).into()
+                // Same as `self * v * self.conjugate()`, but without the quaternion products.
+                // This must stay in sync with `rotate_points()`, so that both give the same results.
+                let u = $Vec3 { x: self.x, y: self.y, z: self.z };
+                let t = (u + u).cross(rhs);
+                rhs + t * self.w + u.cross(t)
             }
         }
 
@@ -108,6 +109,32 @@ macro_rules! quaternion_vec3_vec4 {
     };
 }
 
+/// Byte serialization with explicit endianness, through `Vec4`.
+macro_rules! quaternion_impl_bytes {
+    ($($T:ident $size:tt)+) => {
+        $(
+            impl Quaternion<$T> {
+                /// Encodes this quaternion's `x`, `y`, `z` and `w` elements, in this order, as little-endian bytes.
+                pub fn to_le_bytes(self) -> [u8; 4 * $size] {
+                    Vec4::<$T>::from(self).to_le_bytes()
+                }
+                /// Encodes this quaternion's `x`, `y`, `z` and `w` elements, in this order, as big-endian bytes.
+                pub fn to_be_bytes(self) -> [u8; 4 * $size] {
+                    Vec4::<$T>::from(self).to_be_bytes()
+                }
+                /// Decodes a quaternion from its `x`, `y`, `z` and `w` elements, in this order, encoded as little-endian bytes.
+                pub fn from_le_bytes(bytes: [u8; 4 * $size]) -> Self {
+                    Vec4::<$T>::from_le_bytes(bytes).into()
+                }
+                /// Decodes a quaternion from its `x`, `y`, `z` and `w` elements, in this order, encoded as big-endian bytes.
+                pub fn from_be_bytes(bytes: [u8; 4 * $size]) -> Self {
+                    Vec4::<$T>::from_be_bytes(bytes).into()
+                }
+            }
+        )+
+    };
+}
+
 macro_rules! quaternion_complete_mod {
     ($mod:ident #[$attrs:meta]) => {
 
@@ -140,6 +167,8 @@ macro_rules! quaternion_complete_mod {
             }
         }
 
+        quaternion_impl_bytes!{f32 4 f64 8}
+
         impl<T> Quaternion<T> {
             /// Creates a new quaternion with `x`, `y`, `z` and `w` elements in order.
             ///
@@ -310,25 +339,26 @@ macro_rules! quaternion_complete_mod {
 
             /// Creates a quaternion from an angle and axis.
             /// The axis is not required to be normalized.
-            pub fn rotation_3d<V: Into<Vec3<T>>>(angle_radians: T, axis: V) -> Self
+            pub fn rotation_3d<A: IntoAngleRadians<T>, V: Into<Vec3<T>>>(angle_radians: A, axis: V) -> Self
                 where T: Real + Add<T, Output=T>
             {
+                let angle_radians = angle_radians.into_angle_radians();
                 let axis = axis.into().normalized();
                 let two = T::one() + T::one();
-                let Vec3 { x, y, z } = axis * (angle_radians/two).sin();
-                let w = (angle_radians/two).cos();
+                let Vec3 { x, y, z } = axis * sin(angle_radians/two);
+                let w = cos(angle_radians/two);
                 Self { x, y, z, w }
             }
             /// Creates a quaternion from an angle for a rotation around the X axis.
-            pub fn rotation_x(angle_radians: T) -> Self where T: Real + Add<T, Output=T> {
+            pub fn rotation_x<A: IntoAngleRadians<T>>(angle_radians: A) -> Self where T: Real + Add<T, Output=T> {
                 Self::rotation_3d(angle_radians, Vec3::unit_x())
             }
             /// Creates a quaternion from an angle for a rotation around the Y axis.
-            pub fn rotation_y(angle_radians: T) -> Self where T: Real + Add<T, Output=T> {
+            pub fn rotation_y<A: IntoAngleRadians<T>>(angle_radians: A) -> Self where T: Real + Add<T, Output=T> {
                 Self::rotation_3d(angle_radians, Vec3::unit_y())
             }
             /// Creates a quaternion from an angle for a rotation around the Y axis.
-            pub fn rotation_z(angle_radians: T) -> Self where T: Real + Add<T, Output=T> {
+            pub fn rotation_z<A: IntoAngleRadians<T>>(angle_radians: A) -> Self where T: Real + Add<T, Output=T> {
                 Self::rotation_3d(angle_radians, Vec3::unit_z())
             }
             /// Returns this quaternion rotated around the given axis with given angle.
@@ -365,6 +395,55 @@ macro_rules! quaternion_complete_mod {
             pub fn rotate_z(&mut self, angle_radians: T) where T: Real + Add<T, Output=T> {
                 *self = self.rotated_z(angle_radians);
             }
+            /// Rotates all of `points` by this quaternion, **assuming it is normalized**.
+            ///
+            /// This gives exactly the same results as premultiplying each point by this
+            /// quaternion, but the setup is done once for the whole slice.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{Quaternion, Vec3};
+            /// # fn main() {
+            /// let q = Quaternion::rotation_z(std::f32::consts::FRAC_PI_2);
+            /// let mut points = [Vec3::unit_x(), Vec3::unit_y(), Vec3::new(1., 2., 3.)];
+            /// q.rotate_points(&mut points);
+            /// assert_relative_eq!(points[0], Vec3::unit_y());
+            /// assert_relative_eq!(points[1], -Vec3::unit_x());
+            /// assert_eq!(points[2], q * Vec3::new(1., 2., 3.));
+            /// # }
+            /// ```
+            pub fn rotate_points(&self, points: &mut [Vec3<T>]) where T: Real + Add<T, Output=T> {
+                let (u, u2, w) = self.rotation_setup();
+                for p in points.iter_mut() {
+                    *p = Self::rotate_point(u, u2, w, *p);
+                }
+            }
+            /// Writes into `dst` each point of `src` rotated by this quaternion, **assuming it is
+            /// normalized**.
+            ///
+            /// See `rotate_points()`.
+            ///
+            /// # Panics
+            /// Panics if `src` and `dst` don't have the same length.
+            pub fn rotate_points_into(&self, src: &[Vec3<T>], dst: &mut [Vec3<T>]) where T: Real + Add<T, Output=T> {
+                assert_eq!(src.len(), dst.len(), "source and destination slices must have the same length");
+                let (u, u2, w) = self.rotation_setup();
+                for (d, s) in dst.iter_mut().zip(src.iter()) {
+                    *d = Self::rotate_point(u, u2, w, *s);
+                }
+            }
+            // The vector part, twice the vector part, and the scalar part.
+            fn rotation_setup(&self) -> (Vec3<T>, Vec3<T>, T) where T: Real {
+                let u = Vec3::new(self.x, self.y, self.z);
+                (u, u + u, self.w)
+            }
+            // v + 2w(u × v) + 2u × (u × v), i.e q*v*q⁻¹ without the quaternion products.
+            // This must stay in sync with `Mul<Vec3<T>>`, so that both give the same results.
+            fn rotate_point(u: Vec3<T>, u2: Vec3<T>, w: T, v: Vec3<T>) -> Vec3<T> where T: Real {
+                let t = u2.cross(v);
+                v + t * w + u.cross(t)
+            }
 
             /// Convert this quaternion to angle-axis representation,
             /// **assuming the quaternion is normalized.**
@@ -393,7 +472,7 @@ macro_rules! quaternion_complete_mod {
                 // http://www.euclideanspace.com/maths/geometry/rotations/conversions/quaternionToAngle/
                 // Also, Q57 of matrix-quaternion FAQ
                 let Self { x, y, z, w } = self;
-                let angle = w.acos();
+                let angle = acos(w);
                 let angle = angle + angle;
                 let s = (T::one() - w*w).sqrt();
                 let axis = if s < T::epsilon() {
@@ -404,6 +483,152 @@ macro_rules! quaternion_complete_mod {
                 (angle, axis)
             }
 
+            /// Gets the rotation which takes `previous` to this orientation, i.e `self * previous.conjugate()`,
+            /// **assuming both quaternions are normalized.**
+            ///
+            /// Since `q` and `-q` represent the same rotation, the result is negated if needed so that
+            /// it is the shortest rotation (i.e its `w` is positive), which makes it independent of the
+            /// signs of the inputs.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::Quaternion;
+            /// # fn main() {
+            /// let previous = Quaternion::rotation_x(0.5_f32);
+            /// let current = Quaternion::rotation_y(0.25) * previous;
+            /// assert_relative_eq!(current.delta_from(previous), Quaternion::rotation_y(0.25));
+            /// assert_relative_eq!((-current).delta_from(previous), Quaternion::rotation_y(0.25));
+            /// assert_relative_eq!(current.delta_from(previous) * previous, current);
+            /// # }
+            /// ```
+            pub fn delta_from(self, previous: Self) -> Self where T: Real + Add<T, Output=T> {
+                let delta = self * previous.conjugate();
+                if delta.w < T::zero() { -delta } else { delta }
+            }
+            /// Gets the angular velocity which takes `previous` to this orientation in `dt`,
+            /// **assuming both quaternions are normalized.**
+            ///
+            /// The result is a world-space axis whose magnitude is the speed, in radians per unit of
+            /// time. It is the shortest such rotation (see `delta_from()`), so rotations by more
+            /// than half a turn during `dt` can't be recovered.
+            /// `applied_angular_velocity()` does the opposite.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{Quaternion, Vec3};
+            /// # fn main() {
+            /// let previous = Quaternion::rotation_x(0.5_f32);
+            /// let current = Quaternion::rotation_z(0.25) * previous;
+            /// let w = current.angular_velocity_from(previous, 0.5);
+            /// assert_relative_eq!(w, Vec3::new(0., 0., 0.5));
+            /// assert_relative_eq!(previous.applied_angular_velocity(w, 0.5), current);
+            /// # }
+            /// ```
+            pub fn angular_velocity_from(self, previous: Self, dt: T) -> Vec3<T> where T: Real + Add<T, Output=T> {
+                let Self { x, y, z, w } = self.delta_from(previous);
+                let v = Vec3 { x, y, z };
+                let s = v.magnitude();
+                let two = T::one() + T::one();
+                // The rotation angle is `2 * atan2(s, w)`; the scaled axis is `v / s` times that,
+                // which tends to `v * 2 / w` for small angles.
+                let scale = if s > T::epsilon() { two * atan2(s, w) / s } else { two / w };
+                v * (scale / dt)
+            }
+            /// Rotates this orientation by the angular velocity `angular_velocity` during `dt`,
+            /// **assuming this quaternion is normalized**.
+            ///
+            /// `angular_velocity` is a world-space axis whose magnitude is the speed, in radians
+            /// per unit of time. This is the opposite of `angular_velocity_from()`.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::{Quaternion, Vec3};
+            /// # fn main() {
+            /// let q = Quaternion::rotation_x(0.5_f32);
+            /// assert_relative_eq!(q.applied_angular_velocity(Vec3::new(0., 2., 0.), 0.25), Quaternion::rotation_y(0.5) * q);
+            /// assert_eq!(q.applied_angular_velocity(Vec3::zero(), 0.25), q);
+            /// # }
+            /// ```
+            pub fn applied_angular_velocity(self, angular_velocity: Vec3<T>, dt: T) -> Self where T: Real + Add<T, Output=T> {
+                let speed = angular_velocity.magnitude();
+                if speed == T::zero() {
+                    return self;
+                }
+                Self::rotation_3d(speed * dt, angular_velocity) * self
+            }
+
+            /// Computes the weighted average of the given rotations, **assuming they are normalized**.
+            ///
+            /// This is the eigenvector associated with the largest eigenvalue of the weighted sum
+            /// of the outer products `q * q^T` (Markley et al., _"Averaging Quaternions"_),
+            /// found by power iteration. As such, the result does not depend on whether some
+            /// inputs are given as `q` or `-q`.
+            ///
+            /// The sign of the result is that of the weighted sum of the inputs, once each of them
+            /// has been flipped (if needed) into the same hemisphere as the first input: the result
+            /// has a non-negative dot product with that sum. The result is therefore not guaranteed
+            /// to lie in the same hemisphere as the first input itself, and its sign is arbitrary if
+            /// that sum is zero.
+            ///
+            /// Returns `None` if there are no inputs, or if the weights sum to zero or less.
+            /// The result is arbitrary when the average is ambiguous, e.g when averaging two
+            /// rotations that are 180 degrees apart.
+            ///
+            /// ```
+            /// # extern crate vek;
+            /// # #[macro_use] extern crate approx;
+            /// # use vek::Quaternion;
+            /// # fn main() {
+            /// let a = Quaternion::rotation_z(0.2_f32);
+            /// let b = Quaternion::rotation_z(0.6_f32);
+            /// let avg = Quaternion::weighted_average(vec![(a, 1.), (-b, 1.)]).unwrap();
+            /// assert_relative_eq!(avg, Quaternion::rotation_z(0.4), epsilon = 0.0001);
+            /// # }
+            /// ```
+            pub fn weighted_average<I>(rotations: I) -> Option<Self>
+                where T: Real + Add<T, Output=T>, I: IntoIterator<Item=(Self, T)>
+            {
+                let mut m = [Vec4::zero(); 4];
+                let mut sum = Vec4::zero();
+                let mut total_weight = T::zero();
+                let mut first = None;
+                for (q, weight) in rotations {
+                    let q = q.into_vec4();
+                    for j in 0..4 {
+                        m[j] = m[j] + q * (weight * q[j]);
+                    }
+                    let first = *first.get_or_insert(q);
+                    // NOTE: The sign-corrected sum serves as the initial guess.
+                    sum = if q.dot(first) < T::zero() { sum - q * weight } else { sum + q * weight };
+                    total_weight = total_weight + weight;
+                }
+                let first = first?;
+                if total_weight <= T::zero() {
+                    return None;
+                }
+                let mut v = if sum.magnitude_squared() > T::epsilon() { sum.normalized() } else { first };
+                for _ in 0..64 {
+                    let mv = m[0] * v[0] + m[1] * v[1] + m[2] * v[2] + m[3] * v[3];
+                    let magnitude = mv.magnitude();
+                    if magnitude <= T::zero() {
+                        break;
+                    }
+                    let next = mv / magnitude;
+                    let converged = (next - v).magnitude_squared() <= T::epsilon() * T::epsilon();
+                    v = next;
+                    if converged {
+                        break;
+                    }
+                }
+                if v.dot(sum) < T::zero() {
+                    v = -v;
+                }
+                Some(Self::from_vec4(v))
+            }
+
             /// Converts this quaternion to a `Vec4` by destructuring.
             pub fn into_vec4(self) -> Vec4<T> {
                 self.into()
@@ -543,8 +768,8 @@ macro_rules! quaternion_complete_mod {
                 if cos_theta > T::one() - T::epsilon() {
                     return Self::lerp_unclamped(from, to, factor);
                 }
-                let angle = cos_theta.acos();
-                (from * ((T::one() - factor) * angle).sin() + to * (factor * angle).sin()) / angle.sin()
+                let angle = acos(cos_theta);
+                (from * sin((T::one() - factor) * angle) + to * sin(factor * angle)) / sin(angle)
             }
             /// Perform spherical linear interpolation, constraining `factor` to
             /// be between 0 and 1.
@@ -764,6 +989,168 @@ mod tests {
     use super::Quaternion;
     use crate::vec::Vec3;
 
+    #[test] fn default_is_identity() {
+        let v = Vec3::new(1_f32, -2., 3.);
+        assert_eq!(Quaternion::<f32>::default(), Quaternion::identity());
+        assert_eq!(Quaternion::<f32>::default() * v, v);
+        assert_eq!(Quaternion::<f32>::default() * Quaternion::rotation_x(0.5), Quaternion::rotation_x(0.5));
+        assert_eq!(Quaternion::<f32>::default().magnitude(), 1.);
+    }
+
+    mod weighted_average {
+        use super::*;
+
+        #[test] fn empty() {
+            assert_eq!(Quaternion::<f32>::weighted_average(None), None);
+            assert_eq!(Quaternion::weighted_average(Some((Quaternion::<f32>::identity(), 0.))), None);
+        }
+        #[test] fn cluster() {
+            let center = Quaternion::rotation_3d(1.3_f64, Vec3::new(1., -2., 0.5));
+            let offsets = [
+                Vec3::new(0.05, 0., 0.), Vec3::new(-0.05, 0., 0.),
+                Vec3::new(0., 0.05, 0.), Vec3::new(0., -0.05, 0.),
+                Vec3::new(0., 0., 0.05), Vec3::new(0., 0., -0.05),
+            ];
+            let cluster = offsets.iter().map(|&o| Quaternion::rotation_3d(o.magnitude(), o) * center);
+            let avg = Quaternion::weighted_average(cluster.clone().map(|q| (q, 1.))).unwrap();
+            assert_relative_eq!(avg, center, epsilon = 0.001);
+            assert_relative_eq!(avg.magnitude(), 1., epsilon = 0.000001);
+
+            // Flipping the sign of some inputs must not change the result.
+            let flipped = cluster.enumerate().map(|(i, q)| (if i % 2 == 0 { -q } else { q }, 1.));
+            let flipped_avg = Quaternion::weighted_average(flipped).unwrap();
+            assert_relative_eq!(flipped_avg.dot(avg).abs(), 1., epsilon = 0.000001);
+        }
+        #[test] fn weights() {
+            let a = Quaternion::rotation_x(0.3_f64);
+            let b = Quaternion::rotation_x(0.9_f64);
+            let avg = Quaternion::weighted_average([(a, 2.), (b, 0.)].iter().cloned()).unwrap();
+            assert_relative_eq!(avg, a, epsilon = 0.000001);
+        }
+        #[test] fn sign() {
+            let q = |angle: f64, axis: Vec3<f64>| Quaternion::rotation_3d(angle, axis);
+            let inputs = [
+                [(q(0., Vec3::unit_x()), 1.), (q(2.9, Vec3::unit_x()), 1.), (q(3.4, Vec3::unit_y()), 1.)],
+                [(q(0.5, Vec3::unit_z()), 0.01), (q(3., Vec3::unit_x()), 1.), (-q(2.5, Vec3::new(1., 1., 0.)), 2.)],
+                [(q(1., Vec3::unit_y()), -1.), (q(3.4, Vec3::unit_x()), 2.), (q(-2., Vec3::unit_z()), 0.5)],
+            ];
+            for inputs in inputs.iter() {
+                let first = inputs[0].0;
+                let sum = inputs.iter().fold(Quaternion::zero(), |sum, &(q, w)| {
+                    sum + (if q.dot(first) < 0. { -q } else { q }) * w
+                });
+                let avg = Quaternion::weighted_average(inputs.iter().cloned()).unwrap();
+                assert!(avg.dot(sum) >= 0.);
+                // Flipping inputs other than the first doesn't change the result at all.
+                let flipped = inputs.iter().enumerate().map(|(i, &(q, w))| (if i == 0 { q } else { -q }, w));
+                assert_relative_eq!(Quaternion::weighted_average(flipped).unwrap(), avg, epsilon = 1e-9);
+            }
+        }
+    }
+
+    mod angular_velocity {
+        use super::*;
+
+        fn orientations() -> impl Iterator<Item=Quaternion<f64>> {
+            (0..40).map(|i| {
+                let i = i as f64;
+                Quaternion::rotation_3d(i * 0.7, Vec3::new((i * 1.3).sin(), (i * 0.9).cos(), 0.5))
+            })
+        }
+
+        #[test] fn round_trip() {
+            let dt = 1. / 60.;
+            for previous in orientations() {
+                for delta in orientations() {
+                    let current = delta * previous;
+                    // Also across the q/-q boundary, from either side.
+                    for &(previous, current) in &[(previous, current), (-previous, current), (previous, -current)] {
+                        let w = current.angular_velocity_from(previous, dt);
+                        assert!(w.magnitude() * dt <= ::std::f64::consts::PI + 1e-9);
+                        let integrated = previous.applied_angular_velocity(w, dt);
+                        assert_relative_eq!(integrated.dot(current).abs(), 1., epsilon = 1e-9);
+                        let v = Vec3::new(0.3, -1., 2.);
+                        assert_relative_eq!(integrated * v, current * v, epsilon = 1e-9);
+                    }
+                }
+            }
+        }
+        #[test] fn shortest_rotation() {
+            let previous = Quaternion::rotation_y(0.2_f64);
+            // Three quarters of a turn one way is a quarter of a turn the other way.
+            let current = Quaternion::rotation_z(1.5 * ::std::f64::consts::PI) * previous;
+            assert_relative_eq!(current.delta_from(previous), Quaternion::rotation_z(-0.5 * ::std::f64::consts::PI), epsilon = 1e-12);
+            assert_relative_eq!(current.angular_velocity_from(previous, 2.), Vec3::new(0., 0., -0.25 * ::std::f64::consts::PI), epsilon = 1e-12);
+        }
+        #[test] fn small_and_zero_rotations() {
+            let previous = Quaternion::rotation_3d(1_f64, Vec3::new(1., 2., 3.));
+            assert_eq!(previous.angular_velocity_from(previous, 0.1), Vec3::zero());
+            for &angle in &[1e-6, 1e-9, 1e-12] {
+                let current = Quaternion::rotation_x(angle) * previous;
+                let w = current.angular_velocity_from(previous, 1.);
+                assert_relative_eq!(w, Vec3::new(angle, 0., 0.), epsilon = 1e-15, max_relative = 1e-6);
+            }
+        }
+    }
+
+    #[test] fn bytes() {
+        let q = Quaternion::rotation_3d(1.3_f32, Vec3::new(1., -2., 0.5));
+        let le = q.to_le_bytes();
+        assert_eq!(&le[12..], &q.w.to_le_bytes());
+        assert_eq!(Quaternion::<f32>::from_le_bytes(le), q);
+        let q = Quaternion::<f64>::identity();
+        assert_eq!(Quaternion::<f64>::from_be_bytes(q.to_be_bytes()), q);
+        assert_eq!(&q.to_be_bytes()[24..], &1_f64.to_be_bytes());
+    }
+
+    mod rotate_points {
+        use super::*;
+
+        // Deterministic pseudo-random values in [-1, 1), using a xorshift generator.
+        fn random() -> impl FnMut() -> f32 {
+            let mut state = 0x2545f4914f6cdd1d_u64;
+            move || {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                (state >> 40) as f32 / (1_u32 << 23) as f32 - 1.
+            }
+        }
+
+        #[test] fn matches_per_point() {
+            let mut random = random();
+            for _ in 0..64 {
+                let q = Quaternion::from_xyzw(random(), random(), random(), random()).normalized();
+                let mut points = [Vec3::<f32>::zero(); 64];
+                for p in points.iter_mut() {
+                    *p = Vec3::new(random(), random(), random()) * 100.;
+                }
+                let mut rotated = points;
+                q.rotate_points(&mut rotated);
+                let mut into = [Vec3::zero(); 64];
+                q.rotate_points_into(&points, &mut into);
+                for ((p, r), i) in points.iter().zip(rotated.iter()).zip(into.iter()) {
+                    assert_ulps_eq!(*r, q * *p, max_ulps = 1);
+                    assert_ulps_eq!(*i, q * *p, max_ulps = 1);
+                    // Also agrees with the full quaternion products.
+                    let full: Vec3<f32> = (q * Quaternion::from_xyzw(p.x, p.y, p.z, 0.) * q.conjugate()).into();
+                    assert_relative_eq!(*r, full, epsilon = 1e-3, max_relative = 1e-5);
+                }
+            }
+        }
+        #[test] fn empty() {
+            let q = Quaternion::rotation_x(1_f64);
+            q.rotate_points(&mut []);
+            q.rotate_points_into(&[], &mut []);
+        }
+        #[test]
+        #[should_panic]
+        fn mismatched_lengths() {
+            let q = Quaternion::rotation_x(1_f64);
+            q.rotate_points_into(&[Vec3::zero(); 2], &mut [Vec3::zero(); 3]);
+        }
+    }
+
     // Ensures that quaternions generated by our API are normalized.
     mod is_normalized {
         use super::*;
@@ -794,4 +1181,60 @@ mod tests {
             }
         }
     }
+
+    #[cfg(nightly)]
+    mod bench {
+        extern crate test;
+        use self::test::{Bencher, black_box};
+        use super::*;
+
+        fn setup() -> (Quaternion<f32>, [Vec3<f32>; 1024]) {
+            let q = Quaternion::rotation_3d(0.7, Vec3::new(1., 2., 3.));
+            let mut points = [Vec3::zero(); 1024];
+            for (i, p) in points.iter_mut().enumerate() {
+                *p = Vec3::new(i as f32, -(i as f32), 1.);
+            }
+            (q, points)
+        }
+
+        #[bench]
+        fn rotate_per_point(b: &mut Bencher) {
+            let (q, mut points) = setup();
+            b.iter(|| {
+                let q = black_box(q);
+                for p in points.iter_mut() {
+                    *p = q * *p;
+                }
+                black_box(&points);
+            });
+        }
+        #[bench]
+        fn rotate_per_point_full_products(b: &mut Bencher) {
+            let (q, mut points) = setup();
+            b.iter(|| {
+                let q = black_box(q);
+                for p in points.iter_mut() {
+                    *p = (q * Quaternion::from_xyzw(p.x, p.y, p.z, 0.) * q.conjugate()).into();
+                }
+                black_box(&points);
+            });
+        }
+        #[bench]
+        fn rotate_points(b: &mut Bencher) {
+            let (q, mut points) = setup();
+            b.iter(|| {
+                black_box(q).rotate_points(&mut points);
+                black_box(&points);
+            });
+        }
+        #[bench]
+        fn rotate_points_into(b: &mut Bencher) {
+            let (q, points) = setup();
+            let mut dst = [Vec3::zero(); 1024];
+            b.iter(|| {
+                black_box(q).rotate_points_into(&points, &mut dst);
+                black_box(&dst);
+            });
+        }
+    }
 }
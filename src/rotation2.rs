@@ -0,0 +1,309 @@
+//! A lightweight 2D rotation type, stored as the cosine and sine of its angle.
+
+use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+use num_traits::{real::Real, One, Zero};
+use crate::ops::*;
+use std::ops::*;
+
+/// A 2D rotation, stored as the cosine and sine of its angle (i.e a unit complex number).
+///
+/// It is lighter than a `Quaternion` and a `Mat2`, and rotating many points by the same
+/// `Rotation2` doesn't involve any trigonometric function call. Composing rotations
+/// (with `*`) is a complex multiplication, so angles add up with natural wrap-around.
+///
+/// **IMPORTANT**: This is only a valid rotation as long as `cos*cos + sin*sin` is 1.
+/// Operations provided here preserve this (up to rounding errors); use `normalized()`
+/// to correct accumulated drift after many compositions.
+///
+/// ```
+/// # extern crate vek;
+/// # #[macro_use] extern crate approx;
+/// # use vek::{Rotation2, Vec2};
+/// use std::f32::consts::PI;
+///
+/// # fn main() {
+/// let r = Rotation2::from_angle(PI / 2.);
+/// assert_relative_eq!(r * Vec2::unit_x(), Vec2::unit_y());
+/// assert_relative_eq!((r * r).angle().abs(), PI);
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+pub struct Rotation2<T> {
+    /// Cosine of the rotation's angle.
+    pub cos: T,
+    /// Sine of the rotation's angle.
+    pub sin: T,
+}
+
+/// The default value for a `Rotation2` is the identity.
+///
+/// ```
+/// # use vek::Rotation2;
+/// assert_eq!(Rotation2::<f32>::identity(), Rotation2::default());
+/// ```
+impl<T: Zero + One> Default for Rotation2<T> {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl<T> Rotation2<T> {
+    /// Creates a `Rotation2` from the cosine and sine of its angle.
+    /// **You are responsible for ensuring that `cos*cos + sin*sin` is 1.**
+    pub fn from_cos_sin(cos: T, sin: T) -> Self {
+        Self { cos, sin }
+    }
+    /// The identity rotation (i.e an angle of zero).
+    pub fn identity() -> Self where T: Zero + One {
+        Self { cos: T::one(), sin: T::zero() }
+    }
+    /// Creates a counter-clockwise rotation by the given angle.
+    pub fn from_angle<A: IntoAngleRadians<T>>(angle_radians: A) -> Self where T: Real {
+        let angle_radians = angle_radians.into_angle_radians();
+        Self { cos: cos(angle_radians), sin: sin(angle_radians) }
+    }
+    /// Creates the rotation which brings the direction of `from` onto the direction of `to`.
+    /// Neither vector needs to be normalized, but neither must be zero.
+    ///
+    /// ```
+    /// # extern crate vek;
+    /// # #[macro_use] extern crate approx;
+    /// # use vek::{Rotation2, Vec2};
+    /// # fn main() {
+    /// let r = Rotation2::rotation_from_to(Vec2::new(2_f32, 0.), Vec2::new(-3., 3.));
+    /// assert_relative_eq!(r.angle(), 3. * std::f32::consts::FRAC_PI_4);
+    /// # }
+    /// ```
+    pub fn rotation_from_to(from: crate::vec::Vec2<T>, to: crate::vec::Vec2<T>) -> Self where T: Real {
+        Self {
+            cos: from.x * to.x + from.y * to.y,
+            sin: from.x * to.y - from.y * to.x,
+        }.normalized()
+    }
+    /// Gets the angle of this rotation, in radians, in `[-PI, PI]`.
+    pub fn angle(self) -> T where T: Real {
+        atan2(self.sin, self.cos)
+    }
+    /// Gets the inverse of this rotation (i.e the rotation by the opposite angle).
+    pub fn inverse(self) -> Self where T: Neg<Output=T> {
+        Self { cos: self.cos, sin: -self.sin }
+    }
+    /// Gets this rotation, rescaled so that `cos*cos + sin*sin` is 1.
+    pub fn normalized(self) -> Self where T: Real {
+        let m = (self.cos * self.cos + self.sin * self.sin).sqrt();
+        Self { cos: self.cos / m, sin: self.sin / m }
+    }
+    /// Rescales this rotation so that `cos*cos + sin*sin` is 1.
+    pub fn normalize(&mut self) where T: Real {
+        *self = self.normalized();
+    }
+    /// Rotates a 2D vector counter-clockwise by this rotation's angle.
+    ///
+    /// This is the same as `self * v`.
+    pub fn rotate_vec2<V>(self, v: V) -> V where Self: Mul<V, Output=V> {
+        self * v
+    }
+}
+
+/// Composes two rotations; The resulting angle is the sum of both angles.
+impl<T: Real> Mul for Rotation2<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Self {
+            cos: self.cos * rhs.cos - self.sin * rhs.sin,
+            sin: self.sin * rhs.cos + self.cos * rhs.sin,
+        }
+    }
+}
+impl<T: Real> MulAssign for Rotation2<T> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+/// SLERP on `Rotation2` interpolates the angle along the shortest path.
+///
+/// ```
+/// # extern crate vek;
+/// # #[macro_use] extern crate approx;
+/// # use vek::{Rotation2, Slerp};
+/// use std::f32::consts::PI;
+///
+/// # fn main() {
+/// // Going from 170° to -170° passes through 180°, not 0°.
+/// let a = Rotation2::from_angle(PI * 170. / 180.);
+/// let b = Rotation2::from_angle(-PI * 170. / 180.);
+/// assert_relative_eq!(Rotation2::slerp(a, b, 0.5).angle().abs(), PI);
+/// # }
+/// ```
+impl<T: Real> Slerp<T> for Rotation2<T> {
+    type Output = Self;
+    fn slerp_unclamped(from: Self, to: Self, factor: T) -> Self {
+        let delta = (to * from.inverse()).angle();
+        from * Self::from_angle(delta * factor)
+    }
+}
+
+impl<T: AbsDiffEq> AbsDiffEq for Rotation2<T>
+where
+    T::Epsilon: Copy,
+{
+    type Epsilon = T::Epsilon;
+
+    fn default_epsilon() -> T::Epsilon {
+        T::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        T::abs_diff_eq(&self.cos, &other.cos, epsilon)
+            && T::abs_diff_eq(&self.sin, &other.sin, epsilon)
+    }
+}
+
+impl<T: UlpsEq> UlpsEq for Rotation2<T>
+where
+    T::Epsilon: Copy,
+{
+    fn default_max_ulps() -> u32 {
+        T::default_max_ulps()
+    }
+
+    fn ulps_eq(&self, other: &Self, epsilon: T::Epsilon, max_ulps: u32) -> bool {
+           T::ulps_eq(&self.cos, &other.cos, epsilon, max_ulps)
+        && T::ulps_eq(&self.sin, &other.sin, epsilon, max_ulps)
+    }
+}
+
+impl<T: RelativeEq> RelativeEq for Rotation2<T>
+where
+    T::Epsilon: Copy,
+{
+    fn default_max_relative() -> T::Epsilon {
+        T::default_max_relative()
+    }
+
+    fn relative_eq(
+        &self,
+        other: &Self,
+        epsilon: T::Epsilon,
+        max_relative: T::Epsilon,
+    ) -> bool {
+           T::relative_eq(&self.cos, &other.cos, epsilon, max_relative)
+        && T::relative_eq(&self.sin, &other.sin, epsilon, max_relative)
+    }
+}
+
+macro_rules! rotation2_impl_vec2 {
+    ($Vec2:path) => {
+        /// 2D vectors can be rotated by being premultiplied by a `Rotation2`.
+        impl<T: Real> Mul<$Vec2> for Rotation2<T> {
+            type Output = $Vec2;
+            fn mul(self, rhs: $Vec2) -> $Vec2 {
+                let (c, s) = (self.cos, self.sin);
+                let (x, y) = (rhs.x, rhs.y);
+                let mut out = rhs;
+                out.x = c*x - s*y;
+                out.y = s*x + c*y;
+                out
+            }
+        }
+    };
+}
+
+macro_rules! rotation2_impl_mat {
+    ($layout:ident) => {
+        /// A `Mat2` can be obtained from a `Rotation2`, and is equal to `Mat2::rotation_z()`.
+        impl<T: Real> From<Rotation2<T>> for $layout::Mat2<T> {
+            fn from(r: Rotation2<T>) -> Self {
+                let Rotation2 { cos: c, sin: s } = r;
+                Self::new(
+                    c, -s,
+                    s,  c
+                )
+            }
+        }
+        /// A `Mat3` can be obtained from a `Rotation2`, and is equal to `Mat3::rotation_z()`.
+        impl<T: Real> From<Rotation2<T>> for $layout::Mat3<T> {
+            fn from(r: Rotation2<T>) -> Self {
+                let Rotation2 { cos: c, sin: s } = r;
+                Self::new(
+                    c, -s, T::zero(),
+                    s,  c, T::zero(),
+                    T::zero(), T::zero(), T::one()
+                )
+            }
+        }
+    };
+}
+
+mod impls {
+    use super::*;
+    use crate::mat::repr_c::{row_major, column_major};
+    rotation2_impl_vec2!(crate::vec::repr_c::Vec2<T>);
+    rotation2_impl_mat!(row_major);
+    rotation2_impl_mat!(column_major);
+}
+#[cfg(all(nightly, feature="repr_simd"))]
+mod simd_impls {
+    use super::*;
+    use crate::mat::repr_simd::{row_major, column_major};
+    rotation2_impl_vec2!(crate::vec::repr_simd::Vec2<T>);
+    rotation2_impl_mat!(row_major);
+    rotation2_impl_mat!(column_major);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Rotation2;
+    use crate::vec::Vec2;
+    use crate::mat::{Mat2, Mat3};
+    use crate::ops::Slerp;
+    use std::f64::consts::PI;
+
+    // Angles spread over several turns, in both directions.
+    fn angles() -> impl Iterator<Item=f64> {
+        (0..97).map(|i| (i as f64 - 48.) * 0.2718)
+    }
+
+    #[test] fn rotate_vec2() {
+        let v = Vec2::new(3_f64, -1.5);
+        for a in angles() {
+            let r = Rotation2::from_angle(a);
+            assert_relative_eq!(r.rotate_vec2(v), v.rotated_z(a), epsilon = 1e-12);
+            assert_relative_eq!(Mat2::from(r), Mat2::rotation_z(a));
+            assert_relative_eq!(Mat3::from(r), Mat3::rotation_z(a));
+            assert_relative_eq!(Mat2::from(r) * v, r * v, epsilon = 1e-12);
+            assert_relative_eq!(r.inverse() * (r * v), v, epsilon = 1e-12);
+        }
+    }
+    #[test] fn composition_wraps_around() {
+        for a in angles() {
+            for b in angles() {
+                let r = Rotation2::from_angle(a) * Rotation2::from_angle(b);
+                let expected = (a + b + PI).rem_euclid(2. * PI) - PI;
+                let d = (r.angle() - expected).abs();
+                assert!(d < 1e-9 || (d - 2. * PI).abs() < 1e-9, "{} + {} gave {}", a, b, r.angle());
+                assert_relative_eq!(r, Rotation2::from_angle(a + b), epsilon = 1e-12);
+            }
+        }
+    }
+    #[test] fn slerp_shortest_path() {
+        for a in angles() {
+            for d in [-3_f64, -1., 0., 0.5, 2.9].iter().cloned() {
+                let (from, to) = (Rotation2::from_angle(a), Rotation2::from_angle(a + d));
+                assert_relative_eq!(Rotation2::slerp(from, to, 0.), from, epsilon = 1e-12);
+                assert_relative_eq!(Rotation2::slerp(from, to, 1.), to, epsilon = 1e-12);
+                assert_relative_eq!(Rotation2::slerp(from, to, 0.25), Rotation2::from_angle(a + d * 0.25), epsilon = 1e-12);
+            }
+        }
+    }
+    #[test] fn rotation_from_to() {
+        let from = Vec2::new(1_f64, 2.);
+        for a in angles() {
+            let to = from.rotated_z(a) * 3.;
+            let r = Rotation2::rotation_from_to(from, to);
+            assert_relative_eq!(r, Rotation2::from_angle(a), epsilon = 1e-12);
+        }
+    }
+}
@@ -0,0 +1,410 @@
+//! Low-discrepancy sequences and sample warping functions, for Monte Carlo integration.
+//!
+//! The sequences (`halton()`, `radical_inverse_vdc()`, `hammersley_2d()`) produce
+//! deterministic, well-distributed points in the unit square.
+//! The `sample_*` functions then warp a point `u` of the unit square to some other domain,
+//! and each of them has a matching `*_pdf` function that returns the probability density
+//! (with respect to area or solid angle) of the samples it produces.
+//!
+//! Hemispheres are always oriented around the +Z axis.
+//!
+//! This module does not provide a random number generator; functions that need
+//! randomness take a closure that returns uniformly distributed values in `[0, 1)`.
+
+use num_traits::{Float, FloatConst, NumCast};
+use crate::ops::{cos, sin, sqrt};
+use crate::vec::repr_c::{Vec2, Vec3};
+#[cfg(feature="std")]
+use crate::vec::repr_c::Extent2;
+#[cfg(feature="std")]
+use alloc::vec::Vec;
+
+/// Returns `1 - epsilon`, a value just below one, used to keep sequences in `[0, 1)`.
+///
+/// This is not the largest value below one (that would be `1 - epsilon / 2`), but it is
+/// enough for clamping and doesn't rely on the exact representation of `T`.
+fn one_minus_epsilon<T: Float>() -> T {
+    T::one() - T::epsilon()
+}
+
+/// Deterministically hashes a sequence of 64-bit words together with a seed.
+///
+/// The result only depends on the inputs, so it is stable across calls, runs and platforms.
+/// This is meant for procedural placement (e.g `Vec3::jittered_hashed()`), not for security purposes.
+///
+/// ```
+/// # use vek::sampling::spatial_hash;
+/// assert_eq!(spatial_hash(&[1, 2, 3], 42), spatial_hash(&[1, 2, 3], 42));
+/// assert_ne!(spatial_hash(&[1, 2, 3], 42), spatial_hash(&[1, 2, 3], 43));
+/// assert_ne!(spatial_hash(&[1, 2, 3], 42), spatial_hash(&[3, 2, 1], 42));
+/// ```
+pub fn spatial_hash(words: &[u64], seed: u64) -> u64 {
+    // SplitMix64's finalizer, applied after folding in each word.
+    fn mix(mut z: u64) -> u64 {
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+    let mut h = mix(seed.wrapping_add(0x9e37_79b9_7f4a_7c15));
+    for &w in words {
+        h = mix(h.wrapping_add(0x9e37_79b9_7f4a_7c15) ^ w);
+    }
+    h
+}
+
+/// Maps a hash to a value in `[0, 1)`, keeping the 53 high bits.
+pub(crate) fn hash_to_unit(h: u64) -> f64 {
+    (h >> 11) as f64 * (1. / (1_u64 << 53) as f64)
+}
+
+/// Returns the `index`-th element of the Halton sequence (radical inverse) in the given `base`.
+///
+/// The result is in `[0, 1)`. Using distinct prime bases per dimension yields
+/// a multi-dimensional low-discrepancy sequence.
+///
+/// # Panics
+/// Panics if `base` is less than 2.
+///
+/// ```
+/// # extern crate vek;
+/// # #[macro_use] extern crate approx;
+/// # use vek::sampling::halton;
+/// # fn main() {
+/// assert_relative_eq!(halton::<f32>(1, 2), 0.5);
+/// assert_relative_eq!(halton::<f32>(2, 2), 0.25);
+/// assert_relative_eq!(halton::<f32>(3, 2), 0.75);
+/// assert_relative_eq!(halton::<f32>(1, 3), 1./3.);
+/// assert_relative_eq!(halton::<f32>(5, 3), 7./9.);
+/// # }
+/// ```
+pub fn halton<T: Float>(mut index: u32, base: u32) -> T {
+    assert!(base >= 2, "The base of a Halton sequence must be at least 2");
+    let inv_base = T::one() / <T as NumCast>::from(base).unwrap();
+    let mut f = T::one();
+    let mut r = T::zero();
+    while index > 0 {
+        f = f * inv_base;
+        r = r + f * <T as NumCast>::from(index % base).unwrap();
+        index /= base;
+    }
+    r.min(one_minus_epsilon())
+}
+
+/// Returns the Van der Corput radical inverse of `bits` in base 2, in `[0, 1)`.
+///
+/// This mirrors the bits of `bits` around the binary point, and is equivalent to
+/// (but much faster than) `halton(bits, 2)`.
+///
+/// ```
+/// # extern crate vek;
+/// # #[macro_use] extern crate approx;
+/// # use vek::sampling::radical_inverse_vdc;
+/// # fn main() {
+/// assert_relative_eq!(radical_inverse_vdc::<f32>(0), 0.);
+/// assert_relative_eq!(radical_inverse_vdc::<f32>(1), 0.5);
+/// assert_relative_eq!(radical_inverse_vdc::<f32>(6), 0.375);
+/// assert!(radical_inverse_vdc::<f32>(!0) < 1.);
+/// # }
+/// ```
+pub fn radical_inverse_vdc<T: Float>(bits: u32) -> T {
+    let scale = T::one() / <T as NumCast>::from(1_u64 << 32).unwrap();
+    let r = <T as NumCast>::from(bits.reverse_bits()).unwrap() * scale;
+    r.min(one_minus_epsilon())
+}
+
+/// Returns the `i`-th point of an `n`-point Hammersley set in the unit square.
+///
+/// The X coordinate is `i / n` and the Y coordinate is the radical inverse of `i`.
+/// Unlike the Halton sequence, the total number of points must be known up-front.
+///
+/// # Panics
+/// Panics if `n` is zero.
+///
+/// ```
+/// # extern crate vek;
+/// # #[macro_use] extern crate approx;
+/// # use vek::{Vec2, sampling::hammersley_2d};
+/// # fn main() {
+/// assert_relative_eq!(hammersley_2d(0, 4), Vec2::new(0., 0.));
+/// assert_relative_eq!(hammersley_2d(1, 4), Vec2::new(0.25, 0.5));
+/// assert_relative_eq!(hammersley_2d(3, 4), Vec2::new(0.75, 0.75));
+/// # }
+/// ```
+pub fn hammersley_2d(i: u32, n: u32) -> Vec2<f32> {
+    assert_ne!(n, 0, "A Hammersley set must have at least one point");
+    Vec2::new(i as f32 / n as f32, radical_inverse_vdc(i))
+}
+
+/// Generates one jittered sample in each cell of a `cells.w * cells.h` grid over the unit square.
+///
+/// `rng` must return uniformly distributed values in `[0, 1)`; it is called twice per sample.
+/// Samples are returned in row-major order (one row of cells along X after another).
+///
+/// ```
+/// # extern crate vek;
+/// # use vek::{Extent2, sampling::stratified_2d};
+/// let mut state = 1_u32;
+/// let mut rng = || {
+///     state = state.wrapping_mul(1664525).wrapping_add(1013904223);
+///     (state >> 8) as f32 / (1 << 24) as f32
+/// };
+/// let samples = stratified_2d(Extent2::new(4, 2), &mut rng);
+/// assert_eq!(samples.len(), 8);
+/// for (i, s) in samples.iter().enumerate() {
+///     let (x, y) = ((i % 4) as f32, (i / 4) as f32);
+///     assert!(s.x >= x / 4. && s.x < (x + 1.) / 4.);
+///     assert!(s.y >= y / 2. && s.y < (y + 1.) / 2.);
+/// }
+/// ```
+#[cfg(feature="std")]
+pub fn stratified_2d<T: Float, R: FnMut() -> T>(cells: Extent2<usize>, mut rng: R) -> Vec<Vec2<T>> {
+    let w = <T as NumCast>::from(cells.w).unwrap();
+    let h = <T as NumCast>::from(cells.h).unwrap();
+    let mut samples = Vec::with_capacity(cells.w * cells.h);
+    for y in 0..cells.h {
+        for x in 0..cells.w {
+            let jx = rng();
+            let jy = rng();
+            let sx = (<T as NumCast>::from(x).unwrap() + jx) / w;
+            let sy = (<T as NumCast>::from(y).unwrap() + jy) / h;
+            samples.push(Vec2::new(sx, sy));
+        }
+    }
+    samples
+}
+
+/// Maps a point of the unit square to a point of the unit disk, with uniform density.
+///
+/// This uses Shirley's concentric mapping, which preserves adjacency and
+/// has low distortion compared to the naive polar mapping.
+///
+/// ```
+/// # extern crate vek;
+/// # #[macro_use] extern crate approx;
+/// # use vek::{Vec2, sampling::sample_uniform_disk};
+/// # fn main() {
+/// assert_relative_eq!(sample_uniform_disk(Vec2::new(0.5_f32, 0.5)), Vec2::zero());
+/// assert_relative_eq!(sample_uniform_disk(Vec2::new(1_f32, 0.5)), Vec2::unit_x());
+/// # }
+/// ```
+pub fn sample_uniform_disk<T: Float + FloatConst>(u: Vec2<T>) -> Vec2<T> {
+    let two = T::one() + T::one();
+    let o = u * two - T::one();
+    if o.x == T::zero() && o.y == T::zero() {
+        return Vec2::zero();
+    }
+    let (r, theta) = if o.x.abs() > o.y.abs() {
+        (o.x, T::FRAC_PI_4() * (o.y / o.x))
+    } else {
+        (o.y, T::FRAC_PI_2() - T::FRAC_PI_4() * (o.x / o.y))
+    };
+    Vec2::new(cos(theta), sin(theta)) * r
+}
+
+/// The probability density (with respect to area) of `sample_uniform_disk()`, i.e `1 / PI`.
+pub fn uniform_disk_pdf<T: Float + FloatConst>() -> T {
+    T::FRAC_1_PI()
+}
+
+/// Maps a point of the unit square to a direction on the unit sphere, with uniform density.
+///
+/// ```
+/// # extern crate vek;
+/// # #[macro_use] extern crate approx;
+/// # use vek::{Vec2, Vec3, sampling::sample_uniform_sphere};
+/// # fn main() {
+/// assert_relative_eq!(sample_uniform_sphere(Vec2::new(0_f32, 0.)), Vec3::unit_z());
+/// assert_relative_eq!(sample_uniform_sphere(Vec2::new(1_f32, 0.)), -Vec3::unit_z());
+/// assert_relative_eq!(sample_uniform_sphere(Vec2::new(0.3_f32, 0.8)).magnitude(), 1.);
+/// # }
+/// ```
+pub fn sample_uniform_sphere<T: Float + FloatConst>(u: Vec2<T>) -> Vec3<T> {
+    let two = T::one() + T::one();
+    let z = T::one() - two * u.x;
+    let r = sqrt((T::one() - z * z).max(T::zero()));
+    let phi = two * T::PI() * u.y;
+    Vec3::new(r * cos(phi), r * sin(phi), z)
+}
+
+/// The probability density (with respect to solid angle) of `sample_uniform_sphere()`, i.e `1 / (4 * PI)`.
+pub fn uniform_sphere_pdf<T: Float + FloatConst>() -> T {
+    let four = T::one() + T::one() + T::one() + T::one();
+    T::FRAC_1_PI() / four
+}
+
+/// Maps a point of the unit square to a direction on the +Z unit hemisphere, with uniform density.
+///
+/// ```
+/// # extern crate vek;
+/// # #[macro_use] extern crate approx;
+/// # use vek::{Vec2, Vec3, sampling::sample_uniform_hemisphere};
+/// # fn main() {
+/// assert_relative_eq!(sample_uniform_hemisphere(Vec2::new(0_f32, 0.)), Vec3::unit_z());
+/// let d = sample_uniform_hemisphere(Vec2::new(0.9_f32, 0.4));
+/// assert!(d.z >= 0.);
+/// assert_relative_eq!(d.magnitude(), 1.);
+/// # }
+/// ```
+pub fn sample_uniform_hemisphere<T: Float + FloatConst>(u: Vec2<T>) -> Vec3<T> {
+    let two = T::one() + T::one();
+    let z = T::one() - u.x;
+    let r = sqrt((T::one() - z * z).max(T::zero()));
+    let phi = two * T::PI() * u.y;
+    Vec3::new(r * cos(phi), r * sin(phi), z)
+}
+
+/// The probability density (with respect to solid angle) of `sample_uniform_hemisphere()`, i.e `1 / (2 * PI)`.
+pub fn uniform_hemisphere_pdf<T: Float + FloatConst>() -> T {
+    let two = T::one() + T::one();
+    T::FRAC_1_PI() / two
+}
+
+/// Maps a point of the unit square to a direction on the +Z unit hemisphere,
+/// with a density proportional to the cosine of the angle with +Z.
+///
+/// This is Malley's method: uniformly sample the unit disk, then project upwards.
+/// It is the usual importance sampling strategy for Lambertian reflectance.
+///
+/// ```
+/// # extern crate vek;
+/// # #[macro_use] extern crate approx;
+/// # use vek::{Vec2, Vec3, sampling::sample_cosine_hemisphere};
+/// # fn main() {
+/// assert_relative_eq!(sample_cosine_hemisphere(Vec2::new(0.5_f32, 0.5)), Vec3::unit_z());
+/// let d = sample_cosine_hemisphere(Vec2::new(0.1_f32, 0.7));
+/// assert!(d.z >= 0.);
+/// assert_relative_eq!(d.magnitude(), 1.);
+/// # }
+/// ```
+pub fn sample_cosine_hemisphere<T: Float + FloatConst>(u: Vec2<T>) -> Vec3<T> {
+    let d = sample_uniform_disk(u);
+    let z = sqrt((T::one() - d.x * d.x - d.y * d.y).max(T::zero()));
+    Vec3::new(d.x, d.y, z)
+}
+
+/// The probability density (with respect to solid angle) of `sample_cosine_hemisphere()`,
+/// given the cosine of the angle between the direction and +Z (i.e its Z coordinate).
+pub fn cosine_hemisphere_pdf<T: Float + FloatConst>(cos_theta: T) -> T {
+    cos_theta.max(T::zero()) * T::FRAC_1_PI()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const N: u32 = 4096;
+
+    fn points() -> impl Iterator<Item=Vec2<f64>> {
+        (0..N).map(|i| {
+            let p = hammersley_2d(i, N);
+            Vec2::new(p.x as f64, p.y as f64)
+        })
+    }
+
+    #[test]
+    fn sequences_stay_in_unit_interval() {
+        for i in (0..u32::MAX).step_by(65521) {
+            for base in [2, 3, 5, 7] {
+                let h = halton::<f32>(i, base);
+                assert!((0. ..1.).contains(&h));
+            }
+            let r = radical_inverse_vdc::<f32>(i);
+            assert!((0. ..1.).contains(&r));
+        }
+    }
+
+    #[test]
+    fn radical_inverse_vdc_matches_halton_base_2() {
+        for i in 0..1024 {
+            assert_relative_eq!(radical_inverse_vdc::<f64>(i), halton::<f64>(i, 2));
+        }
+    }
+
+    #[test]
+    fn sequence_means() {
+        let n = 2000;
+        let mean = (0..n).map(|i| halton::<f64>(i, 3)).sum::<f64>() / n as f64;
+        assert_relative_eq!(mean, 0.5, epsilon = 1e-2);
+        let mean = points().fold(Vec2::zero(), |acc, p| acc + p) / N as f64;
+        assert_relative_eq!(mean, Vec2::broadcast(0.5), epsilon = 1e-3);
+    }
+
+    #[cfg(feature="std")]
+    #[test]
+    fn stratified_2d_covers_every_cell() {
+        let mut state = 7_u32;
+        let rng = || {
+            state = state.wrapping_mul(1664525).wrapping_add(1013904223);
+            (state >> 8) as f64 / (1 << 24) as f64
+        };
+        let cells = Extent2::new(16, 8);
+        let samples = stratified_2d(cells, rng);
+        assert_eq!(samples.len(), 128);
+        for (i, s) in samples.iter().enumerate() {
+            let cell = Vec2::new((i % cells.w) as f64, (i / cells.w) as f64);
+            let local = *s * Vec2::new(16., 8.) - cell;
+            assert!(local.x >= 0. && local.x < 1.);
+            assert!(local.y >= 0. && local.y < 1.);
+        }
+        let mean = samples.iter().fold(Vec2::zero(), |acc, &p| acc + p) / samples.len() as f64;
+        assert_relative_eq!(mean, Vec2::broadcast(0.5), epsilon = 2e-2);
+    }
+
+    #[test]
+    fn uniform_disk_moments() {
+        // E[r^2] over the unit disk is 1/2, and the mean position is the center.
+        let (sum, sum_r2) = points().map(sample_uniform_disk).fold((Vec2::zero(), 0.), |(s, r2), p| {
+            assert!(p.magnitude_squared() <= 1. + 1e-12);
+            (s + p, r2 + p.magnitude_squared())
+        });
+        assert_relative_eq!(sum / N as f64, Vec2::zero(), epsilon = 1e-2);
+        assert_relative_eq!(sum_r2 / N as f64, 0.5, epsilon = 1e-2);
+        assert_relative_eq!(uniform_disk_pdf::<f64>() * core::f64::consts::PI, 1.);
+    }
+
+    #[test]
+    fn uniform_sphere_moments() {
+        let (sum, sum_z2) = points().map(sample_uniform_sphere).fold((Vec3::zero(), 0.), |(s, z2), d| {
+            assert_relative_eq!(d.magnitude(), 1., epsilon = 1e-9);
+            (s + d, z2 + d.z * d.z)
+        });
+        assert_relative_eq!(sum / N as f64, Vec3::zero(), epsilon = 1e-2);
+        assert_relative_eq!(sum_z2 / N as f64, 1. / 3., epsilon = 1e-2);
+        // The surface area of the unit sphere is 4 * PI.
+        assert_relative_eq!(1. / uniform_sphere_pdf::<f64>(), 4. * core::f64::consts::PI);
+    }
+
+    #[test]
+    fn uniform_hemisphere_integrates_constant() {
+        // Integrating f = 1 over the hemisphere yields its solid angle, 2 * PI.
+        let pdf = uniform_hemisphere_pdf::<f64>();
+        let estimate = points().map(sample_uniform_hemisphere).map(|d| {
+            assert!(d.z >= 0.);
+            1. / pdf
+        }).sum::<f64>() / N as f64;
+        assert_relative_eq!(estimate, 2. * core::f64::consts::PI, epsilon = 1e-9);
+        // Integrating cos(theta) yields PI.
+        let estimate = points().map(sample_uniform_hemisphere).map(|d| d.z / pdf).sum::<f64>() / N as f64;
+        assert_relative_eq!(estimate, core::f64::consts::PI, epsilon = 1e-2);
+    }
+
+    #[test]
+    fn cosine_hemisphere_integrals() {
+        use core::f64::consts::PI;
+        // The integral of cos^3(theta) over the hemisphere is PI / 2.
+        // Samples on the rim have a zero PDF and contribute nothing.
+        let estimate = points().map(sample_cosine_hemisphere).map(|d| {
+            assert!(d.z >= 0.);
+            assert_relative_eq!(d.magnitude(), 1., epsilon = 1e-9);
+            let pdf = cosine_hemisphere_pdf(d.z);
+            if pdf > 0. { d.z * d.z * d.z / pdf } else { 0. }
+        }).sum::<f64>() / N as f64;
+        assert_relative_eq!(estimate, PI / 2., epsilon = 1e-2);
+        // The integral of cos^2(theta) over the hemisphere is 2 * PI / 3.
+        let estimate = points().map(sample_cosine_hemisphere).map(|d| d.z * PI).sum::<f64>() / N as f64;
+        assert_relative_eq!(estimate, 2. * PI / 3., epsilon = 1e-2);
+        // The mean direction of a cosine lobe is (0, 0, 2/3).
+        let mean = points().map(sample_cosine_hemisphere).fold(Vec3::zero(), |acc, d| acc + d) / N as f64;
+        assert_relative_eq!(mean, Vec3::new(0., 0., 2. / 3.), epsilon = 1e-2);
+    }
+}
@@ -0,0 +1,390 @@
+//! Human-readable serialization forms, for use with `#[serde(with = "...")]`.
+//!
+//! Raw quaternion components are impractical to write by hand, e.g in configuration files.
+//! The modules in here (de)serialize a `Quaternion` as angles in degrees instead:
+//!
+//! - `quat_as_euler_degrees`: `{ x, y, z }`, the angles of rotations around the X, Y and Z axes;
+//! - `quat_as_axis_angle_degrees`: `{ axis: [x, y, z], angle }`.
+//!
+//! They work with the quaternions of any representation (i.e `repr_c` and `repr_simd`).
+//!
+//! Serialization canonicalizes the rotation, so deserializing the result gives back the same
+//! rotation, but not necessarily the same components (e.g `-q` instead of `q`).
+//!
+//! With the `serde-euler-degrees` feature, the `orientation` of `Transform` is serialized with
+//! `quat_as_euler_degrees`.
+//!
+//! ```
+//! # extern crate vek;
+//! # #[macro_use] extern crate serde;
+//! use vek::Quaternion;
+//!
+//! # #[allow(dead_code)]
+//! #[derive(Serialize, Deserialize)]
+//! struct Camera {
+//!     #[serde(with = "vek::serde_helpers::quat_as_euler_degrees")]
+//!     orientation: Quaternion<f32>,
+//!     #[serde(with = "vek::serde_helpers::quat_as_axis_angle_degrees")]
+//!     lens_tilt: Quaternion<f32>,
+//! }
+//! # fn main() {}
+//! ```
+
+use num_traits::{real::Real, One, Zero};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use crate::ops::{atan2, cos, sin};
+use crate::vec::repr_c::Vec3;
+use crate::quaternion::repr_c::Quaternion;
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// A `Quaternion` of any representation (i.e `repr_c` or `repr_simd`), as accepted by the
+/// modules in here.
+///
+/// This trait can't be implemented outside of this crate.
+pub trait AnyQuaternion: private::Sealed + Copy {
+    /// The type of each element.
+    type Element;
+    #[doc(hidden)]
+    fn into_repr_c(self) -> Quaternion<Self::Element>;
+    #[doc(hidden)]
+    fn from_repr_c(q: Quaternion<Self::Element>) -> Self;
+}
+
+impl<T: Copy> private::Sealed for Quaternion<T> {}
+impl<T: Copy> AnyQuaternion for Quaternion<T> {
+    type Element = T;
+    fn into_repr_c(self) -> Quaternion<T> {
+        self
+    }
+    fn from_repr_c(q: Quaternion<T>) -> Self {
+        q
+    }
+}
+
+#[cfg(all(nightly, feature="repr_simd"))]
+impl<T: Copy> private::Sealed for crate::quaternion::repr_simd::Quaternion<T> {}
+#[cfg(all(nightly, feature="repr_simd"))]
+impl<T: Copy> AnyQuaternion for crate::quaternion::repr_simd::Quaternion<T> {
+    type Element = T;
+    fn into_repr_c(self) -> Quaternion<T> {
+        crate::vec::repr_c::Vec4::from(self).into()
+    }
+    fn from_repr_c(q: Quaternion<T>) -> Self {
+        crate::vec::repr_c::Vec4::from(q).into()
+    }
+}
+
+/// (De)serializes a `Quaternion` as Euler angles in degrees, `{ x, y, z }`.
+///
+/// The rotation is the one around the X axis by `x`, followed by the one around the Y axis by
+/// `y`, followed by the one around the Z axis by `z` (all axes being fixed), that is,
+/// `Quaternion::rotation_z(z) * Quaternion::rotation_y(y) * Quaternion::rotation_x(x)` with the
+/// angles converted to radians.
+///
+/// Serialization normalizes the quaternion, then gives `x` and `z` in `[-180, 180]` and `y` in
+/// `[-90, 90]`. At gimbal lock (i.e `y` is ±90), the rotation is only described by `z` and `x`
+/// is zero.
+///
+/// ```
+/// # extern crate vek;
+/// # #[macro_use] extern crate serde;
+/// # use vek::Quaternion;
+/// # #[allow(dead_code)]
+/// #[derive(Serialize, Deserialize)]
+/// struct Node {
+///     // In a file, e.g `orientation: (x: 0, y: 0, z: 90)`.
+///     #[serde(with = "vek::serde_helpers::quat_as_euler_degrees")]
+///     orientation: Quaternion<f64>,
+/// }
+/// # fn main() {}
+/// ```
+pub mod quat_as_euler_degrees {
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "EulerDegrees")]
+    struct EulerDegrees<T> {
+        x: T,
+        y: T,
+        z: T,
+    }
+
+    /// Serializes `q` as Euler angles in degrees.
+    pub fn serialize<Q, S>(q: &Q, serializer: S) -> Result<S::Ok, S::Error>
+        where Q: AnyQuaternion, Q::Element: Real + Serialize, S: Serializer
+    {
+        let (x, y, z) = into_euler_radians(q.into_repr_c());
+        EulerDegrees { x: x.to_degrees(), y: y.to_degrees(), z: z.to_degrees() }.serialize(serializer)
+    }
+
+    /// Deserializes a `Quaternion` from Euler angles in degrees.
+    pub fn deserialize<'de, Q, D>(deserializer: D) -> Result<Q, D::Error>
+        where Q: AnyQuaternion, Q::Element: Real + Deserialize<'de>, D: Deserializer<'de>
+    {
+        let EulerDegrees { x, y, z } = EulerDegrees::<Q::Element>::deserialize(deserializer)?;
+        let q = Quaternion::rotation_z(z.to_radians()) * Quaternion::rotation_y(y.to_radians()) * Quaternion::rotation_x(x.to_radians());
+        Ok(Q::from_repr_c(q))
+    }
+
+    fn into_euler_radians<T: Real>(q: Quaternion<T>) -> (T, T, T) {
+        let Quaternion { x, y, z, w } = q.normalized();
+        let (one, two) = (T::one(), T::one() + T::one());
+        // Elements of the rotation matrix, which is `Rz * Ry * Rx`.
+        let m00 = one - two * (y * y + z * z);
+        let m01 = two * (x * y - w * z);
+        let m10 = two * (x * y + w * z);
+        let m11 = one - two * (x * x + z * z);
+        let m20 = two * (x * z - w * y);
+        let m21 = two * (y * z + w * x);
+        let m22 = one - two * (x * x + y * y);
+
+        let cos_y = (m21 * m21 + m22 * m22).sqrt();
+        let ry = atan2(-m20, cos_y);
+        // At gimbal lock, X and Z rotate around the same axis, so X is arbitrarily zero.
+        let rx = if cos_y <= T::epsilon() { T::zero() } else { atan2(m21, m22) };
+        let rz = if cos_y >= two.recip() {
+            atan2(m10, m00)
+        } else {
+            // Near gimbal lock, Z is computed from X and Y instead, so that errors in X are
+            // compensated for: m01 = cos(z) * a - sin(z) * b, m11 = sin(z) * a + cos(z) * b.
+            let a = sin(ry) * sin(rx);
+            let b = cos(rx);
+            atan2(a * m11 - b * m01, b * m11 + a * m01)
+        };
+        (rx, ry, rz)
+    }
+}
+
+/// (De)serializes a `Quaternion` as an axis and an angle in degrees, `{ axis: [x, y, z], angle }`.
+///
+/// The rotation is counter-clockwise by `angle` around `axis` (when looking from `axis` towards
+/// the origin), that is, `Quaternion::rotation_3d(angle, axis)` with `angle` converted to radians.
+///
+/// Serialization normalizes the quaternion, then gives a unit `axis` and `angle` in `[0, 180]`.
+/// For the identity, the axis is `[1, 0, 0]`. When deserializing, the axis doesn't need to be
+/// normalized, but it may only be zero if the angle is zero too.
+///
+/// ```
+/// # extern crate vek;
+/// # #[macro_use] extern crate serde;
+/// # use vek::Quaternion;
+/// # #[allow(dead_code)]
+/// #[derive(Serialize, Deserialize)]
+/// struct Joint {
+///     // In a file, e.g `rest: (axis: (0, 0, 1), angle: 90)`.
+///     #[serde(with = "vek::serde_helpers::quat_as_axis_angle_degrees")]
+///     rest: Quaternion<f32>,
+/// }
+/// # fn main() {}
+/// ```
+pub mod quat_as_axis_angle_degrees {
+    use super::*;
+    use serde::de::Error;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "AxisAngleDegrees")]
+    struct AxisAngleDegrees<T> {
+        axis: [T; 3],
+        angle: T,
+    }
+
+    /// Serializes `q` as an axis and an angle in degrees.
+    pub fn serialize<Q, S>(q: &Q, serializer: S) -> Result<S::Ok, S::Error>
+        where Q: AnyQuaternion, Q::Element: Real + Serialize, S: Serializer
+    {
+        let q = q.into_repr_c().normalized();
+        // `q` and `-q` are the same rotation; picking a positive `w` gives the shortest angle.
+        let q = if q.w < Zero::zero() { -q } else { q };
+        let v = Vec3::new(q.x, q.y, q.z);
+        let sin = v.magnitude();
+        let angle = atan2(sin, q.w) * (Q::Element::one() + Q::Element::one());
+        let axis = if sin > Zero::zero() { v / sin } else { Vec3::unit_x() };
+        AxisAngleDegrees { axis: axis.into_array(), angle: angle.to_degrees() }.serialize(serializer)
+    }
+
+    /// Deserializes a `Quaternion` from an axis and an angle in degrees.
+    pub fn deserialize<'de, Q, D>(deserializer: D) -> Result<Q, D::Error>
+        where Q: AnyQuaternion, Q::Element: Real + Deserialize<'de>, D: Deserializer<'de>
+    {
+        let AxisAngleDegrees { axis, angle } = AxisAngleDegrees::<Q::Element>::deserialize(deserializer)?;
+        let axis = Vec3::from(axis);
+        let q = if axis == Vec3::zero() {
+            if angle != Zero::zero() {
+                return Err(D::Error::custom("the rotation axis must not be zero"));
+            }
+            Quaternion::identity()
+        } else {
+            Quaternion::rotation_3d(angle.to_radians(), axis)
+        };
+        Ok(Q::from_repr_c(q))
+    }
+}
+
+#[cfg(all(test, feature="std"))]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+    use serde::de::DeserializeOwned;
+    use serde_json::{json, Value};
+
+    fn to_json<V: Serialize>(value: &V) -> Value {
+        serde_json::to_value(value).unwrap()
+    }
+    fn from_json<V: DeserializeOwned>(json: Value) -> Result<V, serde_json::Error> {
+        serde_json::from_value(json)
+    }
+    fn round_trip<V: Serialize + DeserializeOwned>(value: &V) -> V {
+        serde_json::from_str(&serde_json::to_string(value).unwrap()).unwrap()
+    }
+    // The angles of `quat_as_euler_degrees`, as `[x, y, z]`.
+    fn euler_angles(json: &Value) -> [f64; 3] {
+        [json["x"].as_f64().unwrap(), json["y"].as_f64().unwrap(), json["z"].as_f64().unwrap()]
+    }
+    // The axis and angle of `quat_as_axis_angle_degrees`, as `[x, y, z, angle]`.
+    fn axis_angle(json: &Value) -> [f64; 4] {
+        let axis = &json["axis"];
+        [axis[0].as_f64().unwrap(), axis[1].as_f64().unwrap(), axis[2].as_f64().unwrap(), json["angle"].as_f64().unwrap()]
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[serde(bound(serialize = "T: Real + Serialize", deserialize = "T: Real + Deserialize<'de>"))]
+    struct Euler<T>(
+        #[serde(with = "quat_as_euler_degrees")] Quaternion<T>
+    );
+    #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[serde(bound(serialize = "T: Real + Serialize", deserialize = "T: Real + Deserialize<'de>"))]
+    struct AxisAngle<T>(
+        #[serde(with = "quat_as_axis_angle_degrees")] Quaternion<T>
+    );
+
+    // Deterministic pseudo-random values in [-1, 1), using a xorshift generator.
+    fn random() -> impl FnMut() -> f64 {
+        let mut state = 0x2545f4914f6cdd1d_u64;
+        move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state >> 11) as f64 / (1_u64 << 52) as f64 - 1.
+        }
+    }
+    // Various rotations, including the edge cases of each form.
+    fn rotations() -> Vec<Quaternion<f64>> {
+        use std::f64::consts::{FRAC_PI_2, PI};
+        let mut random = random();
+        let mut rotations: Vec<_> = (0..200).map(|_| Quaternion::from_xyzw(random(), random(), random(), random()).normalized()).collect();
+        for &angle in &[0., 1e-9, FRAC_PI_2, PI - 1e-9, PI, -PI, 2. * PI] {
+            for &axis in &[Vec3::unit_x(), Vec3::unit_y(), Vec3::unit_z(), Vec3::new(1., -2., 3.)] {
+                rotations.push(Quaternion::rotation_3d(angle, axis));
+            }
+        }
+        // Gimbal lock, and near it.
+        for &pitch in &[FRAC_PI_2, -FRAC_PI_2, FRAC_PI_2 - 1e-6, -FRAC_PI_2 + 1e-9] {
+            for &(x, z) in &[(0., 0.), (0.3, -1.2), (-2.5, 2.9), (PI, PI)] {
+                rotations.push(Quaternion::rotation_z(z) * Quaternion::rotation_y(pitch) * Quaternion::rotation_x(x));
+            }
+        }
+        rotations
+    }
+    // `q` and `-q` are the same rotation.
+    fn assert_same_rotation(a: Quaternion<f64>, b: Quaternion<f64>, epsilon: f64) {
+        let b = if a.dot(b) < 0. { -b } else { b };
+        assert_relative_eq!(a, b, epsilon = epsilon);
+        for &v in &[Vec3::unit_x(), Vec3::unit_y(), Vec3::unit_z()] {
+            assert_relative_eq!(a * v, b * v, epsilon = epsilon);
+        }
+    }
+
+    #[test]
+    fn euler_degrees() {
+        use std::f64::consts::FRAC_PI_2;
+        assert_eq!(to_json(&Euler(Quaternion::<f64>::identity())), json!({ "x": 0., "y": 0., "z": 0. }));
+        for (q, [x, y, z]) in [
+            (Quaternion::rotation_x(FRAC_PI_2), [90., 0., 0.]),
+            (Quaternion::rotation_y(FRAC_PI_2), [0., 90., 0.]),
+            (Quaternion::rotation_z(-FRAC_PI_2), [0., 0., -90.]),
+            (Quaternion::rotation_z(FRAC_PI_2) * Quaternion::rotation_x(FRAC_PI_2), [90., 0., 90.]),
+        ] {
+            let angles = euler_angles(&to_json(&Euler(q)));
+            assert_relative_eq!(&angles[..], &[x, y, z][..], epsilon = 1e-12);
+        }
+        let expected = Quaternion::rotation_z(60_f64.to_radians()) * Quaternion::rotation_y(45_f64.to_radians()) * Quaternion::rotation_x(30_f64.to_radians());
+        let q = serde_json::from_str::<Euler<f64>>(r#"{ "x": 30, "y": 45.0, "z": 60 }"#).unwrap().0;
+        assert_relative_eq!(q, expected);
+        assert!(from_json::<Euler<f64>>(json!({ "x": 30, "y": 45 })).is_err());
+    }
+    #[test]
+    fn euler_degrees_round_trip() {
+        for q in rotations() {
+            let [x, y, z] = euler_angles(&to_json(&Euler(q)));
+            assert!(x.abs() <= 180. && y.abs() <= 90. && z.abs() <= 180., "{:?}", [x, y, z]);
+            assert_same_rotation(q, round_trip(&Euler(q)).0, 1e-9);
+        }
+        // Canonical forms are stable, except near gimbal lock where X and Z are ill-conditioned.
+        for q in rotations() {
+            let json = to_json(&Euler(q));
+            let angles = euler_angles(&json);
+            if angles[1].abs() > 89. {
+                continue;
+            }
+            let again = euler_angles(&to_json(&from_json::<Euler<f64>>(json).unwrap()));
+            assert_relative_eq!(&angles[..], &again[..], epsilon = 1e-6);
+        }
+        for q in rotations() {
+            let q = Quaternion::<f32>::from_xyzw(q.x as f32, q.y as f32, q.z as f32, q.w as f32);
+            let back = round_trip(&Euler(q)).0;
+            let back = Quaternion::from_xyzw(back.x as f64, back.y as f64, back.z as f64, back.w as f64);
+            assert_same_rotation(Quaternion::from_xyzw(q.x as f64, q.y as f64, q.z as f64, q.w as f64), back, 1e-5);
+        }
+    }
+    #[test]
+    fn axis_angle_degrees() {
+        assert_eq!(to_json(&AxisAngle(Quaternion::<f64>::identity())), json!({ "axis": [1., 0., 0.], "angle": 0. }));
+        let floats = axis_angle(&to_json(&AxisAngle(Quaternion::rotation_3d(-std::f64::consts::FRAC_PI_2, Vec3::new(0., 0., 2.)))));
+        assert_relative_eq!(&floats[..], &[0., 0., -1., 90.][..], epsilon = 1e-12);
+        let q = serde_json::from_str::<AxisAngle<f64>>(r#"{ "axis": [0, 3, 0], "angle": 120 }"#).unwrap().0;
+        assert_relative_eq!(q, Quaternion::rotation_y(120_f64.to_radians()));
+        assert_eq!(from_json::<AxisAngle<f64>>(json!({ "axis": [0, 0, 0], "angle": 0 })).unwrap().0, Quaternion::identity());
+        assert!(from_json::<AxisAngle<f64>>(json!({ "axis": [0, 0, 0], "angle": 10 })).is_err());
+        assert!(from_json::<AxisAngle<f64>>(json!({ "axis": [0, 0], "angle": 10 })).is_err());
+    }
+    #[test]
+    fn axis_angle_degrees_round_trip() {
+        for q in rotations() {
+            let [x, y, z, angle] = axis_angle(&to_json(&AxisAngle(q)));
+            assert!((0. ..=180.).contains(&angle), "{:?}", [x, y, z, angle]);
+            assert_relative_eq!(Vec3::new(x, y, z).magnitude(), 1., epsilon = 1e-12);
+            assert_same_rotation(q, round_trip(&AxisAngle(q)).0, 1e-9);
+        }
+        // Non-normalized quaternions are the same rotation as their normalized version.
+        let q = Quaternion::rotation_3d(2., Vec3::new(1., 1., 0.));
+        assert_same_rotation(q, round_trip(&AxisAngle(q * 3.)).0, 1e-12);
+    }
+    #[cfg(feature="serde-euler-degrees")]
+    #[test]
+    fn transform() {
+        use crate::transform::repr_c::Transform;
+        let t = Transform {
+            position: Vec3::new(1_f64, 2., 3.),
+            orientation: Quaternion::rotation_z(std::f64::consts::FRAC_PI_2),
+            scale: Vec3::new(4_f64, 5., 6.),
+        };
+        let json = to_json(&t);
+        assert_eq!(json["position"], to_json(&t.position));
+        assert_eq!(json["scale"], to_json(&t.scale));
+        assert_relative_eq!(&euler_angles(&json["orientation"])[..], &[0., 0., 90.][..], epsilon = 1e-12);
+        let back: Transform<f64, f64, f64> = from_json(json).unwrap();
+        assert_eq!((back.position, back.scale), (t.position, t.scale));
+        assert_same_rotation(back.orientation, t.orientation, 1e-12);
+    }
+    #[cfg(feature="serde-euler-degrees")]
+    #[test]
+    fn transform_track_is_serializable() {
+        // `Transform`'s serde impls require `T: Real` with this feature, so containers must forward that bound.
+        fn assert_serde<V: Serialize + DeserializeOwned>() {}
+        assert_serde::<crate::transform::repr_c::TransformTrack<f32>>();
+        assert_serde::<crate::transform::repr_c::TransformTrack<f64>>();
+    }
+}
@@ -0,0 +1,268 @@
+//! Streaming statistics over points: mean, variance and covariance.
+//!
+//! `VecStats2` and `VecStats3` accumulate points one at a time with Welford's online algorithm,
+//! so the points never need to be held in memory, and the result stays accurate where the naive
+//! "sum of squares minus square of sum" formula loses all precision (e.g for points far from the
+//! origin with a small spread). Accumulators can be merged, which allows for parallel reduction.
+//!
+//! ```
+//! # extern crate vek;
+//! # #[macro_use] extern crate approx;
+//! # use vek::{Vec2, stats::VecStats2};
+//! # fn main() {
+//! let points = [Vec2::new(1_f64, 2.), Vec2::new(3., 2.), Vec2::new(5., 8.)];
+//! let stats: VecStats2<f64> = points.iter().copied().collect();
+//! assert_eq!(stats.count(), 3);
+//! assert_relative_eq!(stats.mean().unwrap(), Vec2::new(3., 4.));
+//! assert_relative_eq!(stats.variance().unwrap(), Vec2::new(8. / 3., 8.));
+//!
+//! // Splitting the data, e.g between threads, gives the same result.
+//! let mut left: VecStats2<f64> = points[..1].iter().copied().collect();
+//! left.merge(points[1..].iter().copied().collect());
+//! assert_relative_eq!(left.covariance().unwrap(), stats.covariance().unwrap());
+//! # }
+//! ```
+
+use num_traits::real::Real;
+use std::iter::FromIterator;
+use crate::vec::repr_c::{Vec2, Vec3};
+use crate::mat::repr_c::{Mat2, Mat3};
+
+macro_rules! stats_impl_vec_stats {
+    ($(#[$attrs:meta])* $VecStats:ident $Vec:ident $Mat:ident $dim:expr) => {
+        $(#[$attrs])*
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub struct $VecStats<T> {
+            count: u64,
+            mean: $Vec<T>,
+            // Sum of the outer products of the deviations from the mean.
+            m2: $Mat<T>,
+        }
+
+        impl<T: Real> Default for $VecStats<T> {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl<T: Real> $VecStats<T> {
+            /// Creates an accumulator with no points.
+            pub fn new() -> Self {
+                Self { count: 0, mean: $Vec::zero(), m2: $Mat::zero() }
+            }
+            /// Gets the number of accumulated points.
+            pub fn count(&self) -> u64 {
+                self.count
+            }
+            /// Accumulates a point.
+            pub fn push(&mut self, v: $Vec<T>) {
+                self.count += 1;
+                let n = T::from(self.count).unwrap();
+                let delta = v - self.mean;
+                self.mean = self.mean + delta / n;
+                // (v - old mean) * (v - new mean)ᵀ, written so that it stays exactly symmetric.
+                self.add_outer_product(delta, (n - T::one()) / n);
+            }
+            /// Accumulates all points of `other` into this one, as if they had been pushed.
+            ///
+            /// This uses the pairwise update of Chan et al., so that large accumulators can be
+            /// merged without loss of precision.
+            pub fn merge(&mut self, other: Self) {
+                if other.count == 0 {
+                    return;
+                }
+                if self.count == 0 {
+                    *self = other;
+                    return;
+                }
+                let (na, nb) = (T::from(self.count).unwrap(), T::from(other.count).unwrap());
+                let n = na + nb;
+                let delta = other.mean - self.mean;
+                self.count += other.count;
+                self.mean = self.mean + delta * (nb / n);
+                self.m2 += other.m2;
+                self.add_outer_product(delta, na * nb / n);
+            }
+            /// Returns the result of accumulating all points of `other` into this one.
+            ///
+            /// See `merge()`.
+            pub fn merged(mut self, other: Self) -> Self {
+                self.merge(other);
+                self
+            }
+            /// Gets the mean of the accumulated points, or `None` if there are none.
+            pub fn mean(&self) -> Option<$Vec<T>> {
+                if self.count == 0 { None } else { Some(self.mean) }
+            }
+            /// Gets the population variance (i.e divided by the number of points) along each axis,
+            /// or `None` if there are no points.
+            pub fn variance(&self) -> Option<$Vec<T>> {
+                self.covariance().map(Self::diagonal)
+            }
+            /// Gets the sample variance (i.e divided by the number of points minus one) along each
+            /// axis, or `None` if there are less than two points.
+            pub fn sample_variance(&self) -> Option<$Vec<T>> {
+                self.sample_covariance().map(Self::diagonal)
+            }
+            /// Gets the population covariance matrix (i.e divided by the number of points), or `None`
+            /// if there are no points.
+            pub fn covariance(&self) -> Option<$Mat<T>> {
+                self.m2_divided_by(self.count)
+            }
+            /// Gets the sample covariance matrix (i.e divided by the number of points minus one), or
+            /// `None` if there are less than two points.
+            pub fn sample_covariance(&self) -> Option<$Mat<T>> {
+                self.m2_divided_by(self.count.saturating_sub(1))
+            }
+
+            fn add_outer_product(&mut self, v: $Vec<T>, factor: T) {
+                for i in 0..$dim {
+                    for j in 0..$dim {
+                        self.m2[(i, j)] = self.m2[(i, j)] + v[i] * v[j] * factor;
+                    }
+                }
+            }
+            fn m2_divided_by(&self, divisor: u64) -> Option<$Mat<T>> {
+                if divisor == 0 {
+                    return None;
+                }
+                let divisor = T::from(divisor).unwrap();
+                Some(self.m2.map(|x| x / divisor))
+            }
+            fn diagonal(m: $Mat<T>) -> $Vec<T> {
+                let mut v = $Vec::zero();
+                for i in 0..$dim {
+                    v[i] = m[(i, i)];
+                }
+                v
+            }
+        }
+
+        impl<T: Real> Extend<$Vec<T>> for $VecStats<T> {
+            fn extend<I: IntoIterator<Item=$Vec<T>>>(&mut self, iter: I) {
+                for v in iter {
+                    self.push(v);
+                }
+            }
+        }
+
+        impl<T: Real> FromIterator<$Vec<T>> for $VecStats<T> {
+            fn from_iter<I: IntoIterator<Item=$Vec<T>>>(iter: I) -> Self {
+                let mut stats = Self::new();
+                stats.extend(iter);
+                stats
+            }
+        }
+    };
+}
+
+stats_impl_vec_stats!{
+    /// Accumulates the mean and covariance of a stream of 2D points, with Welford's online algorithm.
+    ///
+    /// See the [module-level documentation](index.html).
+    VecStats2 Vec2 Mat2 2
+}
+stats_impl_vec_stats!{
+    /// Accumulates the mean and covariance of a stream of 3D points, with Welford's online algorithm.
+    ///
+    /// The covariance matrix can be turned into an ellipsoid with `Ellipsoid::from_covariance()`.
+    /// See the [module-level documentation](index.html).
+    VecStats3 Vec3 Mat3 3
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Deterministic pseudo-random values in [0, 1), using a xorshift generator.
+    fn random() -> impl FnMut() -> f64 + Clone {
+        let mut state = 0x9e3779b97f4a7c15_u64;
+        move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state >> 11) as f64 / (1_u64 << 53) as f64
+        }
+    }
+    fn points(n: usize, offset: Vec3<f64>, scale: f64) -> impl Iterator<Item=Vec3<f64>> + Clone {
+        let mut random = random();
+        (0..n).map(move |_| offset + Vec3::new(random() - 0.5, 2. * random() - 1., random() + random()) * scale)
+    }
+    // Two-pass computation of the mean and population covariance.
+    fn reference<I: Iterator<Item=Vec3<f64>> + Clone>(points: I) -> (Vec3<f64>, Mat3<f64>) {
+        let n = points.clone().count() as f64;
+        let mean = points.clone().fold(Vec3::zero(), |sum, p| sum + p) / n;
+        let mut covariance = Mat3::zero();
+        for p in points {
+            let d = p - mean;
+            for i in 0..3 {
+                for j in 0..3 {
+                    covariance[(i, j)] += d[i] * d[j] / n;
+                }
+            }
+        }
+        (mean, covariance)
+    }
+
+    #[test]
+    fn matches_two_pass() {
+        let points = points(1000, Vec3::new(1., -2., 3.), 2.);
+        let (mean, covariance) = reference(points.clone());
+        let stats: VecStats3<f64> = points.collect();
+        assert_eq!(stats.count(), 1000);
+        assert_relative_eq!(stats.mean().unwrap(), mean, epsilon = 1e-12);
+        assert_relative_eq!(stats.covariance().unwrap(), covariance, epsilon = 1e-12);
+        assert_relative_eq!(stats.variance().unwrap(), Vec3::new(covariance[(0, 0)], covariance[(1, 1)], covariance[(2, 2)]), epsilon = 1e-12);
+        assert_relative_eq!(stats.sample_covariance().unwrap(), covariance * (1000. / 999.), epsilon = 1e-12);
+        // The covariance is exactly symmetric.
+        let m2 = stats.covariance().unwrap();
+        assert_eq!(m2, m2.transposed());
+    }
+    #[test]
+    fn badly_conditioned() {
+        // Far from the origin, with a tiny spread: the naive formula is useless in f32.
+        // The reference uses the same, f32-rounded, points.
+        let points = points(10_000, Vec3::new(1e4, -3e4, 5e3), 0.1).map(|p| p.as_::<f32>().as_::<f64>());
+        let (mean, covariance) = reference(points.clone());
+        let stats: VecStats3<f32> = points.clone().map(|p| p.as_()).collect();
+        assert_relative_eq!(stats.mean().unwrap().as_::<f64>(), mean, epsilon = 0.01);
+        let variance = stats.variance().unwrap().as_::<f64>();
+        for i in 0..3 {
+            assert_relative_eq!(variance[i], covariance[(i, i)], max_relative = 0.03);
+        }
+        // Naive sum of squares, in f32.
+        let n = 10_000_f32;
+        let (sum, sum_sq) = points.map(|p| p.x as f32).fold((0_f32, 0_f32), |(s, sq), x| (s + x, sq + x * x));
+        let naive = sum_sq / n - (sum / n) * (sum / n);
+        assert!((naive as f64 - covariance[(0, 0)]).abs() > covariance[(0, 0)]);
+    }
+    #[test]
+    fn merge() {
+        let all: VecStats3<f64> = points(1000, Vec3::new(5., 0., -5.), 3.).collect();
+        let mut merged = VecStats3::new();
+        let mut points = points(1000, Vec3::new(5., 0., -5.), 3.);
+        for &size in &[0, 1, 10, 300, 689] {
+            let chunk: VecStats3<f64> = points.by_ref().take(size).collect();
+            merged.merge(chunk);
+        }
+        assert_eq!(merged.count(), all.count());
+        assert_relative_eq!(merged.mean().unwrap(), all.mean().unwrap(), epsilon = 1e-12);
+        assert_relative_eq!(merged.covariance().unwrap(), all.covariance().unwrap(), epsilon = 1e-12);
+        assert_eq!(VecStats3::new().merged(all), all);
+        assert_eq!(all.merged(VecStats3::new()), all);
+    }
+    #[test]
+    fn degenerate() {
+        let mut stats = VecStats2::<f32>::new();
+        assert_eq!(stats, VecStats2::default());
+        assert_eq!((stats.mean(), stats.variance(), stats.covariance()), (None, None, None));
+        stats.push(Vec2::new(1., 2.));
+        assert_eq!(stats.mean(), Some(Vec2::new(1., 2.)));
+        assert_eq!(stats.variance(), Some(Vec2::zero()));
+        assert_eq!(stats.sample_variance(), None);
+        stats.extend([Vec2::new(3., 2.), Vec2::new(2., 2.)].iter().copied());
+        assert_eq!(stats.count(), 3);
+        assert_eq!(stats.mean(), Some(Vec2::new(2., 2.)));
+        assert_eq!(stats.sample_variance(), Some(Vec2::new(1., 0.)));
+    }
+}
@@ -0,0 +1,215 @@
+//! Summed-area tables (also known as integral images), for constant-time sums and averages
+//! over rectangular regions of a grid, e.g for box blurs.
+//!
+//! Grids are given as a row-major slice along with their size as an `Extent2<usize>`,
+//! like `Heightfield`. Regions are `Aabr<usize>` of cells, with half-open bounds: cells
+//! `(x, y)` such that `min <= (x, y) < max`, as in `Aabr::iter_positions()`.
+//!
+//! This module is only available with the `std` feature.
+
+use num_traits::{Zero, NumCast};
+use std::ops::{Add, Sub, Div};
+use alloc::vec::Vec;
+use crate::vec::repr_c::{Vec2, Extent2};
+use crate::geom::repr_c::Aabr;
+
+/// A summed-area table, which answers sum and average queries over rectangular regions of a
+/// grid in constant time, after a single pass over it.
+///
+/// Sums are accumulated in the type `A`, which values of the grid are converted to with
+/// `Into`, and which must be able to hold the sum of the whole grid. This allows widening
+/// small integer types, e.g building a `SummedAreaTable<u32>` from `u8` values, or summing
+/// `f32` values in `f64`. Vectors and colors work too, e.g `Vec3<f32>` or `Rgba<f32>`.
+///
+/// Queries never underflow with unsigned `A`, because they only ever subtract a sum from a
+/// larger one.
+///
+/// ```
+/// # use vek::{SummedAreaTable, Extent2, Aabr, Vec2};
+/// let values: [u8; 6] = [
+///     200, 200, 200,
+///     200, 200, 100,
+/// ];
+/// let table = SummedAreaTable::<u32>::new(&values, Extent2::new(3, 2));
+/// assert_eq!(table.region_sum(Aabr { min: Vec2::zero(), max: Vec2::new(3, 2) }), 1100);
+/// assert_eq!(table.region_sum(Aabr { min: Vec2::new(1, 1), max: Vec2::new(3, 2) }), 300);
+/// assert_eq!(table.region_average::<u32>(Aabr { min: Vec2::new(1, 1), max: Vec2::new(3, 2) }), Some(150));
+/// // Empty regions sum to zero, and have no average.
+/// assert_eq!(table.region_sum(Aabr { min: Vec2::new(2, 0), max: Vec2::new(2, 2) }), 0);
+/// assert_eq!(table.region_average::<u32>(Aabr { min: Vec2::new(2, 0), max: Vec2::new(2, 2) }), None);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SummedAreaTable<A> {
+    // Row-major, with an extra leading row and column of zeros, so that
+    // `sums[y * (size.w + 1) + x]` is the sum of cells `(0, 0)` to `(x, y)` excluded.
+    sums: Vec<A>,
+    size: Extent2<usize>,
+}
+
+impl<A> SummedAreaTable<A> where A: Copy + Zero + Add<A, Output=A> + Sub<A, Output=A> {
+    /// Builds the summed-area table of the grid `values`, given in row-major order
+    /// (i.e the value at `(x, y)` is at index `y * size.w + x`).
+    ///
+    /// # Panics
+    /// Panics if `values.len()` is not `size.w * size.h`.
+    pub fn new<S: Copy + Into<A>>(values: &[S], size: Extent2<usize>) -> Self {
+        assert_eq!(values.len(), size.w * size.h, "the number of values doesn't match the size of the grid");
+        let stride = size.w + 1;
+        let mut sums = Vec::with_capacity(stride * (size.h + 1));
+        sums.resize(stride, A::zero());
+        for y in 0..size.h {
+            let mut row = A::zero();
+            sums.push(A::zero());
+            for x in 0..size.w {
+                row = row + values[y * size.w + x].into();
+                let above = sums[y * stride + x + 1];
+                sums.push(above + row);
+            }
+        }
+        Self { sums, size }
+    }
+    /// Gets the size of the grid this table was built from.
+    pub fn size(&self) -> Extent2<usize> {
+        self.size
+    }
+    /// Gets the sum of the cells `(x, y)` such that `(x, y) < end`, i.e the sum of the
+    /// region from `(0, 0)` to `end`.
+    ///
+    /// `end` is clamped to the size of the grid.
+    pub fn prefix_sum(&self, end: Vec2<usize>) -> A {
+        let x = end.x.min(self.size.w);
+        let y = end.y.min(self.size.h);
+        self.sums[y * (self.size.w + 1) + x]
+    }
+    /// Gets the sum of the cells in `region`, in constant time.
+    ///
+    /// `region` is clamped to the bounds of the grid; the sum of an empty (or invalid) region is zero.
+    pub fn region_sum(&self, region: Aabr<usize>) -> A {
+        let (min, max) = self.clamped(region);
+        if min.x >= max.x || min.y >= max.y {
+            return A::zero();
+        }
+        // Both terms are sums over the columns `min.x..max.x`, and the second one covers fewer rows.
+        let bottom = self.prefix_sum(max) - self.prefix_sum(Vec2::new(min.x, max.y));
+        let top = self.prefix_sum(Vec2::new(max.x, min.y)) - self.prefix_sum(min);
+        bottom - top
+    }
+    /// Gets the average of the cells in `region`, in constant time, or `None` if it is empty.
+    ///
+    /// `region` is clamped to the bounds of the grid. The sum of the region is divided by the number
+    /// of its cells converted to `D`, which is usually `A` itself for scalars, and the type of the
+    /// elements for vectors (e.g `f32` for `Vec3<f32>`).
+    pub fn region_average<D: NumCast>(&self, region: Aabr<usize>) -> Option<A> where A: Div<D, Output=A> {
+        let (min, max) = self.clamped(region);
+        if min.x >= max.x || min.y >= max.y {
+            return None;
+        }
+        let count = D::from((max.x - min.x) * (max.y - min.y)).unwrap();
+        Some(self.region_sum(region) / count)
+    }
+    fn clamped(&self, region: Aabr<usize>) -> (Vec2<usize>, Vec2<usize>) {
+        let bounds = Vec2::new(self.size.w, self.size.h);
+        (Vec2::partial_min(region.min, bounds), Vec2::partial_min(region.max, bounds))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vec::repr_c::{Vec3, Rgba};
+
+    // Deterministic pseudo-random values, using a xorshift generator.
+    fn random() -> impl FnMut() -> u64 {
+        let mut state = 0x9e3779b97f4a7c15_u64;
+        move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        }
+    }
+    fn brute_force<S: Copy + Into<A>, A: Zero + Add<A, Output=A>>(values: &[S], size: Extent2<usize>, region: Aabr<usize>) -> A {
+        let mut sum = A::zero();
+        for y in region.min.y..region.max.y.min(size.h) {
+            for x in region.min.x..region.max.x.min(size.w) {
+                sum = sum + values[y * size.w + x].into();
+            }
+        }
+        sum
+    }
+    // Random regions, some of them empty or beyond the grid, along with every single cell and the full grid.
+    fn regions(size: Extent2<usize>) -> Vec<Aabr<usize>> {
+        let mut random = random();
+        let mut regions: Vec<_> = (0..200).map(|_| {
+            let mut coord = |n: usize| (random() % (n as u64 + 3)) as usize;
+            let (x0, x1, y0, y1) = (coord(size.w), coord(size.w), coord(size.h), coord(size.h));
+            Aabr { min: Vec2::new(x0.min(x1), y0.min(y1)), max: Vec2::new(x0.max(x1), y0.max(y1)) }
+        }).collect();
+        for y in 0..size.h {
+            for x in 0..size.w {
+                regions.push(Aabr { min: Vec2::new(x, y), max: Vec2::new(x + 1, y + 1) });
+            }
+        }
+        regions.push(Aabr { min: Vec2::zero(), max: Vec2::new(size.w, size.h) });
+        regions
+    }
+
+    #[test] fn widened_integers() {
+        let size = Extent2::new(13, 7);
+        let mut random = random();
+        let values: Vec<u8> = (0..size.w * size.h).map(|_| random() as u8).collect();
+        let table = SummedAreaTable::<u32>::new(&values, size);
+        for region in regions(size) {
+            assert_eq!(table.region_sum(region), brute_force::<u8, u32>(&values, size, region), "{:?}", region);
+        }
+        // All values at their maximum would overflow a `u8` sum right away.
+        let values = [255_u8; 64 * 64];
+        let table = SummedAreaTable::<u32>::new(&values, Extent2::new(64, 64));
+        assert_eq!(table.region_sum(Aabr { min: Vec2::zero(), max: Vec2::new(64, 64) }), 255 * 64 * 64);
+        assert_eq!(table.region_average::<u32>(Aabr { min: Vec2::new(3, 5), max: Vec2::new(9, 6) }), Some(255));
+    }
+    #[test] fn vectors_and_colors() {
+        let size = Extent2::new(5, 9);
+        let mut random = random();
+        let mut value = || (random() % 1000) as f32 / 8.;
+        let vectors: Vec<Vec3<f32>> = (0..size.w * size.h).map(|_| Vec3::new(value(), value(), value())).collect();
+        let colors: Vec<Rgba<f32>> = vectors.iter().map(|v| Rgba::new(v.x, v.y, v.z, 1.)).collect();
+        let vector_table = SummedAreaTable::<Vec3<f32>>::new(&vectors, size);
+        let color_table = SummedAreaTable::<Rgba<f32>>::new(&colors, size);
+        for region in regions(size) {
+            // Multiples of 1/8 are summed exactly.
+            assert_eq!(vector_table.region_sum(region), brute_force::<_, Vec3<f32>>(&vectors, size, region));
+            assert_eq!(color_table.region_sum(region), brute_force::<_, Rgba<f32>>(&colors, size, region));
+            match color_table.region_average::<f32>(region) {
+                None => assert_eq!(color_table.region_sum(region), Rgba::zero()),
+                Some(average) => assert_relative_eq!(average.a, 1.),
+            }
+        }
+    }
+    #[test] fn edges() {
+        let values = [1., 2., 3., 4., 5., 6.];
+        let size = Extent2::new(3, 2);
+        let table = SummedAreaTable::<f64>::new(&values, size);
+        assert_eq!(table.size(), size);
+        assert_eq!(table.prefix_sum(Vec2::zero()), 0.);
+        assert_eq!(table.prefix_sum(Vec2::new(3, 1)), 6.);
+        assert_eq!(table.prefix_sum(Vec2::new(100, 100)), 21.);
+        // Last row and column, then regions beyond the grid are clamped.
+        assert_eq!(table.region_sum(Aabr { min: Vec2::new(2, 0), max: Vec2::new(3, 2) }), 9.);
+        assert_eq!(table.region_sum(Aabr { min: Vec2::new(0, 1), max: Vec2::new(3, 2) }), 15.);
+        assert_eq!(table.region_sum(Aabr { min: Vec2::new(1, 1), max: Vec2::new(10, 10) }), 11.);
+        assert_eq!(table.region_average::<f64>(Aabr { min: Vec2::new(1, 1), max: Vec2::new(10, 10) }), Some(5.5));
+        assert_eq!(table.region_sum(Aabr { min: Vec2::new(3, 0), max: Vec2::new(10, 10) }), 0.);
+        // Invalid regions are empty.
+        assert_eq!(table.region_sum(Aabr { min: Vec2::new(2, 2), max: Vec2::new(1, 1) }), 0.);
+        assert_eq!(table.region_average::<f64>(Aabr { min: Vec2::new(2, 2), max: Vec2::new(1, 1) }), None);
+        // Empty grids.
+        let table = SummedAreaTable::<f64>::new(&[] as &[f64], Extent2::new(0, 4));
+        assert_eq!(table.region_sum(Aabr { min: Vec2::zero(), max: Vec2::new(1, 1) }), 0.);
+    }
+    #[test]
+    #[should_panic]
+    fn size_mismatch() {
+        SummedAreaTable::<u32>::new(&[1_u8, 2, 3], Extent2::new(2, 2));
+    }
+}
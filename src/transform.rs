@@ -8,7 +8,11 @@ macro_rules! transform_complete_mod {
         // look_at
         // rotate_around
 
-        use std::ops::Add;
+        use std::ops::{Add, Mul};
+        #[cfg(feature="std")]
+        use std::cmp::Ordering;
+        #[cfg(feature="std")]
+        use alloc::vec::Vec;
         use $crate::num_traits::{Zero, One, real::Real};
         use $crate::ops::*;
         use crate::vec::$mod::*;
@@ -42,6 +46,10 @@ macro_rules! transform_complete_mod {
             pub position: Vec3<P>,
             /// Local orientation; It is not named `rotation` because `rotation` denotes an
             /// operation, but not a current state.
+            #[cfg_attr(feature="serde-euler-degrees", serde(
+                with="crate::serde_helpers::quat_as_euler_degrees",
+                bound(serialize="O: Real + serde::Serialize", deserialize="O: Real + serde::Deserialize<'de>"),
+            ))]
             pub orientation: Quaternion<O>,
             /// Local scale.
             pub scale: Vec3<S>,
@@ -60,6 +68,18 @@ macro_rules! transform_complete_mod {
         /// ```
         impl<P: Zero, O: Zero + One, S: One> Default for Transform<P,O,S> {
             fn default() -> Self {
+                Self::identity()
+            }
+        }
+
+        impl<P,O,S> Transform<P,O,S> {
+            /// Gets the identity transform: no translation, no rotation and a scale of one.
+            ///
+            /// ```
+            /// # use vek::{Transform, Mat4};
+            /// assert_eq!(Mat4::from(Transform::<f32,f32,f32>::identity()), Mat4::identity());
+            /// ```
+            pub fn identity() -> Self where P: Zero, O: Zero + One, S: One {
                 Self {
                     position: Vec3::zero(),
                     orientation: Quaternion::identity(),
@@ -117,16 +137,568 @@ macro_rules! transform_complete_mod {
                 }
             }
         }
+
+        /// A sequence of time-stamped `Transform` keys, which can be sampled at any time, e.g for
+        /// camera flythroughs.
+        ///
+        /// In between keys, positions follow a centripetal Catmull-Rom spline, orientations are
+        /// interpolated with SQUAD, and scales linearly. The spline's tangents are rescaled from its
+        /// knot spacing to the time between keys, so that the velocity is continuous across keys
+        /// even if they are unevenly spaced in time; keys that are evenly spaced along a line
+        /// (or around an axis) give a constant velocity.
+        ///
+        /// Sampling exactly at a key's time yields that key, and so does sampling before the first
+        /// key or after the last one with `Extrapolation::Clamp`. With `Extrapolation::Loop`, the last
+        /// key is expected to be the same as the first, and the keys on either side of that seam
+        /// are used for the tangents, so that the loop is smooth.
+        ///
+        /// ```
+        /// # extern crate vek;
+        /// # #[macro_use] extern crate approx;
+        /// # use vek::{Transform, TransformTrack, Extrapolation, Quaternion, Vec3};
+        /// # fn main() {
+        /// let key = |x: f32, angle: f32| Transform {
+        ///     position: Vec3::new(x, 0., 0.),
+        ///     orientation: Quaternion::rotation_y(angle),
+        ///     scale: Vec3::one(),
+        /// };
+        /// let mut track = TransformTrack::new(Extrapolation::Clamp);
+        /// track.push(0., key(0., 0.));
+        /// track.push(1., key(2., 0.5));
+        /// track.push(2., key(4., 1.));
+        /// assert_eq!(track.sample(1.), key(2., 0.5));
+        /// assert_eq!(track.sample(-3.), key(0., 0.));
+        /// // Uniform motion stays uniform.
+        /// assert_relative_eq!(track.sample(1.5).position, Vec3::new(3., 0., 0.));
+        /// assert_relative_eq!(track.sample(1.5).orientation, Quaternion::rotation_y(0.75));
+        /// # }
+        /// ```
+        #[cfg(feature="std")]
+        #[derive(Debug, Clone, PartialEq)]
+        #[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+        #[cfg_attr(feature="serde-euler-degrees", serde(
+            bound(serialize="T: Real + serde::Serialize", deserialize="T: Real + serde::Deserialize<'de>"),
+        ))]
+        pub struct TransformTrack<T> {
+            keys: Vec<(T, Transform<T,T,T>)>,
+            /// How times outside of the keys' range are handled.
+            pub extrapolation: Extrapolation,
+        }
+
+        #[cfg(feature="std")]
+        impl<T: Real + Lerp<T,Output=T>> TransformTrack<T> {
+            /// Creates a track without keys.
+            pub fn new(extrapolation: Extrapolation) -> Self {
+                Self { keys: Vec::new(), extrapolation }
+            }
+            /// Adds a key at `time`.
+            ///
+            /// # Panics
+            /// Panics if `time` isn't after the time of the last key.
+            pub fn push(&mut self, time: T, transform: Transform<T,T,T>) {
+                if let Some(&(last, _)) = self.keys.last() {
+                    assert!(time > last, "keys must be pushed in increasing order of time");
+                }
+                self.keys.push((time, transform));
+            }
+            /// Gets the keys, in increasing order of time.
+            pub fn keys(&self) -> &[(T, Transform<T,T,T>)] {
+                &self.keys
+            }
+            /// Gets the time between the first and last keys, which is zero if there are less than two.
+            pub fn duration(&self) -> T {
+                match (self.keys.first(), self.keys.last()) {
+                    (Some(first), Some(last)) => last.0 - first.0,
+                    _ => T::zero(),
+                }
+            }
+            /// Samples the track at `time`.
+            ///
+            /// # Panics
+            /// Panics if there are no keys.
+            pub fn sample(&self, time: T) -> Transform<T,T,T> {
+                assert!(!self.keys.is_empty(), "cannot sample a track without keys");
+                let n = self.keys.len();
+                let (first, last) = (self.keys[0], self.keys[n - 1]);
+                let time = self.wrapped_time(time);
+                // NaN times end up here too.
+                if time.partial_cmp(&first.0) != Some(Ordering::Greater) {
+                    return first.1;
+                }
+                if time >= last.0 {
+                    return last.1;
+                }
+                let i = match self.keys.binary_search_by(|key| key.0.partial_cmp(&time).unwrap_or(Ordering::Less)) {
+                    Ok(i) => return self.keys[i].1,
+                    Err(i) => i - 1,
+                };
+                let ((t1, a), (t2, b)) = (self.keys[i], self.keys[i + 1]);
+                let dt = t2 - t1;
+                let u = (time - t1) / dt;
+
+                // Cubic Hermite interpolation of the positions.
+                let v1 = self.velocity(i) * dt;
+                let v2 = self.velocity(i + 1) * dt;
+                let (u2, u3) = (u * u, u * u * u);
+                let (two, three) = (T::one() + T::one(), T::one() + T::one() + T::one());
+                let position = a.position * (two * u3 - three * u2 + T::one())
+                    + v1 * (u3 - two * u2 + u)
+                    + b.position * (three * u2 - two * u3)
+                    + v2 * (u3 - u2);
+
+                // SQUAD, with every quaternion on the same side as `a`'s orientation.
+                let q1 = a.orientation;
+                let q2 = aligned(b.orientation, q1);
+                let s1 = self.squad_control(i);
+                let s2 = aligned(self.squad_control(i + 1), q2);
+                let s1 = aligned(s1, q1);
+                let outer = Quaternion::slerp_unclamped(q1, q2, u);
+                let inner = Quaternion::slerp_unclamped(s1, s2, u);
+                let orientation = Quaternion::slerp_unclamped(outer, inner, two * u * (T::one() - u)).normalized();
+
+                let scale = a.scale + (b.scale - a.scale) * u;
+                Transform { position, orientation, scale }
+            }
+
+            // Brings `time` back within the keys' range, according to the extrapolation mode.
+            // Times within the range are left untouched.
+            fn wrapped_time(&self, time: T) -> T {
+                let first = self.keys[0].0;
+                let duration = self.duration();
+                if duration <= T::zero() || (time >= first && time <= first + duration) {
+                    return time;
+                }
+                let period = match self.extrapolation {
+                    Extrapolation::Clamp => return time,
+                    Extrapolation::Loop => duration,
+                    Extrapolation::PingPong => duration + duration,
+                };
+                let offset = time - first;
+                let offset = (offset - (offset / period).floor() * period).max(T::zero()).min(period);
+                if offset > duration {
+                    first + period - offset
+                } else {
+                    first + offset
+                }
+            }
+            // Gets the key before the one at index `i`, or after it, accounting for the seam in
+            // loop mode, with its time shifted accordingly.
+            fn neighbour(&self, i: usize, after: bool) -> Option<(T, Transform<T,T,T>)> {
+                let n = self.keys.len();
+                let looped = self.extrapolation == Extrapolation::Loop && n >= 3;
+                match (after, i) {
+                    (false, 0) if looped => Some((self.keys[n - 2].0 - self.duration(), self.keys[n - 2].1)),
+                    (false, 0) => None,
+                    (false, i) => Some(self.keys[i - 1]),
+                    (true, i) if i == n - 1 && looped => Some((self.keys[1].0 + self.duration(), self.keys[1].1)),
+                    (true, i) if i == n - 1 => None,
+                    (true, i) => Some(self.keys[i + 1]),
+                }
+            }
+            // Gets the velocity at key `i`: the tangent of centripetal Catmull-Rom, rescaled from its
+            // knot spacing to the time between the key's neighbours.
+            fn velocity(&self, i: usize) -> Vec3<T> {
+                let (t1, p1) = (self.keys[i].0, self.keys[i].1.position);
+                match (self.neighbour(i, false), self.neighbour(i, true)) {
+                    (Some((t0, k0)), Some((t2, k2))) => {
+                        let (p0, p2) = (k0.position, k2.position);
+                        let d01 = p0.distance(p1).sqrt();
+                        let d12 = p1.distance(p2).sqrt();
+                        let knots = d01 + d12;
+                        if knots <= T::zero() {
+                            return Vec3::zero();
+                        }
+                        let incoming = if d01 > T::zero() { (p1 - p0) / d01 } else { Vec3::zero() };
+                        let outgoing = if d12 > T::zero() { (p2 - p1) / d12 } else { Vec3::zero() };
+                        (incoming + outgoing - (p2 - p0) / knots) * (knots / (t2 - t0))
+                    },
+                    // At the ends, as if the curve went on straight.
+                    (Some((t0, k0)), None) => (p1 - k0.position) / (t1 - t0),
+                    (None, Some((t2, k2))) => (k2.position - p1) / (t2 - t1),
+                    (None, None) => Vec3::zero(),
+                }
+            }
+            // Gets SQUAD's inner control quaternion at key `i`.
+            fn squad_control(&self, i: usize) -> Quaternion<T> {
+                let q = self.keys[i].1.orientation;
+                match (self.neighbour(i, false), self.neighbour(i, true)) {
+                    (Some((_, k0)), Some((_, k2))) => {
+                        let inverse = q.conjugate();
+                        let log0 = log(inverse * aligned(k0.orientation, q));
+                        let log2 = log(inverse * aligned(k2.orientation, q));
+                        let four = T::one() + T::one() + T::one() + T::one();
+                        q * exp(-(log0 + log2) / four)
+                    },
+                    // At the ends, as if the rotation went on at the same rate.
+                    _ => q,
+                }
+            }
+        }
+
+        // Gets `q` or `-q` (which is the same rotation), whichever is closest to `reference`.
+        #[cfg(feature="std")]
+        fn aligned<T: Real>(q: Quaternion<T>, reference: Quaternion<T>) -> Quaternion<T> {
+            if q.dot(reference) < T::zero() { -q } else { q }
+        }
+        // Logarithm of a unit quaternion, i.e half its rotation vector.
+        #[cfg(feature="std")]
+        fn log<T: Real>(q: Quaternion<T>) -> Vec3<T> {
+            let v = Vec3::new(q.x, q.y, q.z);
+            let sin = v.magnitude();
+            if sin <= T::epsilon() {
+                return v;
+            }
+            v * (atan2(sin, q.w) / sin)
+        }
+        // Exponential of a pure quaternion, which is the inverse of `log()`.
+        #[cfg(feature="std")]
+        fn exp<T: Real>(v: Vec3<T>) -> Quaternion<T> {
+            let angle = v.magnitude();
+            if angle <= T::epsilon() {
+                return Quaternion::from_xyzw(v.x, v.y, v.z, T::one()).normalized();
+            }
+            let v = v * (sin(angle) / angle);
+            Quaternion::from_xyzw(v.x, v.y, v.z, cos(angle))
+        }
+
+        /// Computes world-from-local matrices for a hierarchy described by a parent-index array.
+        ///
+        /// `parents[i]` is the index of node `i`'s parent, or `None` for a root. Each node's
+        /// world matrix is its parent's world matrix multiplied by the matrix of its local
+        /// `Transform`; roots simply get their local matrix.
+        ///
+        /// The input must be sorted so that every parent comes before its children
+        /// (`parents[i] < i`), which is how skeletons and flattened scene graphs are usually stored.
+        /// This also rules out cycles. The whole input is validated before anything
+        /// is written, so `out_world` is left untouched on error.
+        ///
+        /// The output may be any matrix type obtainable from a `Transform`, such as `Mat4` in either layout.
+        ///
+        /// ```
+        /// # extern crate vek;
+        /// # #[macro_use] extern crate approx;
+        /// # use vek::{Transform, Mat4, Vec3, propagate_transforms};
+        /// # fn main() {
+        /// let root = Transform { position: Vec3::unit_x(), ..Default::default() };
+        /// let child = Transform { position: Vec3::unit_y(), ..Default::default() };
+        /// let mut world = [Mat4::identity(); 2];
+        /// propagate_transforms(&[None, Some(0)], &[root, child], &mut world).unwrap();
+        /// assert_relative_eq!(world[1].mul_point(Vec3::zero()), Vec3::new(1_f32, 1., 0.));
+        /// # }
+        /// ```
+        pub fn propagate_transforms<T, M>(parents: &[Option<usize>], locals: &[Transform<T,T,T>], out_world: &mut [M]) -> Result<(), TransformHierarchyError>
+            where T: Copy, M: From<Transform<T,T,T>> + Mul<M, Output=M> + Copy
+        {
+            if parents.len() != locals.len() || parents.len() != out_world.len() {
+                return Err(TransformHierarchyError::LengthMismatch);
+            }
+            for (node, parent) in parents.iter().enumerate() {
+                if let Some(parent) = *parent {
+                    if parent >= node {
+                        return Err(TransformHierarchyError::ParentNotBeforeChild { node, parent });
+                    }
+                }
+            }
+            for (node, (parent, local)) in parents.iter().zip(locals.iter()).enumerate() {
+                let local = M::from(*local);
+                out_world[node] = match *parent {
+                    Some(parent) => out_world[parent] * local,
+                    None => local,
+                };
+            }
+            Ok(())
+        }
     }     
 }         
 
+use std::fmt::{self, Display, Formatter};
+
+/// Error returned by `propagate_transforms` when the hierarchy can't be processed.
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+pub enum TransformHierarchyError {
+    /// `parents`, `locals` and `out_world` don't all have the same length.
+    LengthMismatch,
+    /// A node refers to a parent which doesn't come before it (this includes itself,
+    /// out-of-bounds indices, and cycles).
+    ParentNotBeforeChild {
+        /// Index of the offending node.
+        node: usize,
+        /// Index of its parent.
+        parent: usize,
+    },
+}
+
+impl Display for TransformHierarchyError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            TransformHierarchyError::LengthMismatch => write!(f, "hierarchy slices have different lengths"),
+            TransformHierarchyError::ParentNotBeforeChild { node, parent } => write!(f, "node {} has parent {}, which does not come before it", node, parent),
+        }
+    }
+}
+
+/// How a `TransformTrack` is sampled at times before its first key or after its last one.
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+pub enum Extrapolation {
+    /// The first or last key is held.
+    Clamp,
+    /// The track repeats, going from the last key back to the first one.
+    Loop,
+    /// The track goes back and forth, every other repetition being played backwards.
+    PingPong,
+}
+
 #[cfg(all(nightly, feature="repr_simd"))]
 pub mod repr_simd {
     //! `Transform` struct that uses `#[repr(simd)]` vectors and quaternions.
+    use super::TransformHierarchyError;
+    #[cfg(feature="std")]
+    use super::Extrapolation;
     transform_complete_mod!(repr_simd);
 }
 pub mod repr_c {
     //! `Transform` struct that uses `#[repr(C)]` vectors and quaternions.
+    use super::TransformHierarchyError;
+    #[cfg(feature="std")]
+    use super::Extrapolation;
     transform_complete_mod!(repr_c);
 }
 pub use self::repr_c::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mat::Mat4;
+    use crate::vec::Vec3;
+    use crate::quaternion::Quaternion;
+
+    #[test]
+    fn default_is_identity() {
+        let t = Transform::<f32,f32,f32>::default();
+        assert_eq!(t, Transform::identity());
+        assert_eq!(Mat4::from(t), Mat4::identity());
+        for &child in rig().iter() {
+            assert_eq!(Mat4::from(t) * Mat4::from(child), Mat4::from(child));
+        }
+    }
+
+    fn rig() -> [Transform<f32,f32,f32>; 4] {
+        [
+            Transform { position: Vec3::new(1., 2., 3.), orientation: Quaternion::rotation_z(0.5), scale: Vec3::broadcast(2.) },
+            Transform { position: Vec3::new(0., 1., 0.), orientation: Quaternion::rotation_x(1.2), scale: Vec3::one() },
+            Transform { position: Vec3::new(-3., 0., 1.), orientation: Quaternion::rotation_y(-0.7), scale: Vec3::new(1., 0.5, 1.) },
+            Transform { position: Vec3::new(5., 5., 5.), orientation: Quaternion::identity(), scale: Vec3::one() },
+        ]
+    }
+
+    #[test]
+    fn three_levels() {
+        let locals = rig();
+        let parents = [None, Some(0), Some(1), None];
+        let mut world = [Mat4::identity(); 4];
+        propagate_transforms(&parents, &locals, &mut world).unwrap();
+        let m: [Mat4<f32>; 4] = [locals[0].into(), locals[1].into(), locals[2].into(), locals[3].into()];
+        assert_relative_eq!(world[0], m[0]);
+        assert_relative_eq!(world[1], m[0] * m[1], epsilon = 0.0001);
+        assert_relative_eq!(world[2], m[0] * m[1] * m[2], epsilon = 0.0001);
+        assert_relative_eq!(world[3], m[3]);
+    }
+    #[test]
+    fn row_major_output() {
+        use crate::mat::row_major::Mat4 as RowMat4;
+        let locals = rig();
+        let parents = [None, Some(0), Some(1), Some(0)];
+        let mut cols = [Mat4::identity(); 4];
+        let mut rows = [RowMat4::identity(); 4];
+        propagate_transforms(&parents, &locals, &mut cols).unwrap();
+        propagate_transforms(&parents, &locals, &mut rows).unwrap();
+        for (c, r) in cols.iter().zip(rows.iter()) {
+            for (a, b) in c.into_row_array().iter().zip(r.into_row_array().iter()) {
+                assert_relative_eq!(a, b, epsilon = 0.0001);
+            }
+        }
+    }
+    #[test]
+    fn rejects_unsorted_and_cycles() {
+        let locals = rig();
+        let mut world = [Mat4::<f32>::identity(); 4];
+        let before = world;
+        assert_eq!(
+            propagate_transforms(&[None, Some(2), Some(1), None], &locals, &mut world),
+            Err(TransformHierarchyError::ParentNotBeforeChild { node: 1, parent: 2 })
+        );
+        assert_eq!(
+            propagate_transforms(&[None, Some(1), None, None], &locals, &mut world),
+            Err(TransformHierarchyError::ParentNotBeforeChild { node: 1, parent: 1 })
+        );
+        assert_eq!(
+            propagate_transforms(&[None, None, None, Some(9)], &locals, &mut world),
+            Err(TransformHierarchyError::ParentNotBeforeChild { node: 3, parent: 9 })
+        );
+        assert_eq!(
+            propagate_transforms(&[None, None, None], &locals, &mut world),
+            Err(TransformHierarchyError::LengthMismatch)
+        );
+        assert_eq!(world, before);
+    }
+    #[cfg(feature="std")]
+    mod track {
+        use super::*;
+
+        // A flythrough with unevenly spaced keys, turning and scaling as it goes.
+        fn flythrough() -> TransformTrack<f64> {
+            let key = |x: f64, y: f64, z: f64, yaw: f64, pitch: f64, scale: f64| Transform {
+                position: Vec3::new(x, y, z),
+                orientation: Quaternion::rotation_y(yaw) * Quaternion::rotation_x(pitch),
+                scale: Vec3::broadcast(scale),
+            };
+            let mut track = TransformTrack::new(Extrapolation::Clamp);
+            track.push(0., key(0., 1., 0., 0., 0., 1.));
+            track.push(0.5, key(2., 1.5, -1., 0.4, 0.1, 1.));
+            track.push(2., key(3., 4., -6., 1.5, -0.3, 2.));
+            track.push(2.2, key(3.5, 4., -6.5, 1.7, -0.2, 2.));
+            track.push(4., key(10., 0., 0., 3., 0.5, 0.5));
+            track
+        }
+        // Numerical derivative of the position, from either side of `time`.
+        fn velocities(track: &TransformTrack<f64>, time: f64) -> (Vec3<f64>, Vec3<f64>) {
+            let h = 1e-6;
+            let at = |t| track.sample(t).position;
+            ((at(time) - at(time - h)) / h, (at(time + h) - at(time)) / h)
+        }
+
+        #[test] fn exact_at_keys_and_clamped() {
+            let track = flythrough();
+            for &(time, key) in track.keys() {
+                assert_eq!(track.sample(time), key);
+            }
+            let keys = track.keys();
+            assert_eq!(track.sample(-1.), keys[0].1);
+            assert_eq!(track.sample(100.), keys[4].1);
+            assert_eq!(track.sample(f64::NAN), keys[0].1);
+            assert_eq!(track.duration(), 4.);
+            // A single key is held forever.
+            let mut single = TransformTrack::new(Extrapolation::Loop);
+            single.push(3., keys[1].1);
+            assert_eq!(single.sample(-7.), keys[1].1);
+            assert_eq!(single.sample(3.5), keys[1].1);
+        }
+        #[test] fn smooth_between_keys() {
+            let track = flythrough();
+            for &(time, _) in &track.keys()[1..4] {
+                let (before, after) = velocities(&track, time);
+                assert_relative_eq!(before, after, epsilon = 1e-4, max_relative = 1e-4);
+            }
+            // Orientations and scales are continuous, and orientations stay normalized.
+            for i in 0..400 {
+                let t = i as f64 / 100.;
+                let (a, b) = (track.sample(t), track.sample(t + 1e-7));
+                assert_relative_eq!(a.orientation.magnitude(), 1., epsilon = 1e-12);
+                assert!(a.orientation.dot(b.orientation).abs() > 1. - 1e-9, "{}", t);
+                assert_relative_eq!(a.scale, b.scale, epsilon = 1e-5);
+            }
+        }
+        #[test] fn uniform_motion() {
+            // Evenly spaced keys along a line, and around an axis, are followed at a constant rate.
+            let mut track = TransformTrack::new(Extrapolation::Clamp);
+            for i in 0..5 {
+                let i = i as f64;
+                track.push(i * 0.5, Transform {
+                    position: Vec3::new(1., 2., 3.) * i,
+                    orientation: Quaternion::rotation_3d(i * 0.6, Vec3::new(1., 1., 0.)),
+                    scale: Vec3::one(),
+                });
+            }
+            for i in 0..=40 {
+                let t = i as f64 / 20.;
+                let sample = track.sample(t);
+                assert_relative_eq!(sample.position, Vec3::new(1., 2., 3.) * t * 2., epsilon = 1e-12);
+                assert_relative_eq!(sample.orientation, Quaternion::rotation_3d(t * 1.2, Vec3::new(1., 1., 0.)), epsilon = 1e-12);
+            }
+        }
+        #[test] fn loop_across_the_seam() {
+            // Around a square, coming back to the start.
+            let corners = [(0., 0.), (1., 0.), (1., 1.), (0., 1.), (0., 0.)];
+            let mut track = TransformTrack::new(Extrapolation::Loop);
+            for (i, &(x, z)) in corners.iter().enumerate() {
+                let angle = if i == 4 { 0. } else { i as f64 * -std::f64::consts::FRAC_PI_2 };
+                track.push(i as f64, Transform { position: Vec3::new(x, 0., z), orientation: Quaternion::rotation_y(angle), scale: Vec3::one() });
+            }
+            let (before, after) = velocities(&track, 4.);
+            assert_relative_eq!(before, after, epsilon = 1e-4);
+            assert_relative_eq!(before, (track.keys()[1].1.position - track.keys()[3].1.position) / 2., epsilon = 1e-4);
+            for i in 0..20 {
+                let t = i as f64 * 0.2;
+                let (a, b) = (track.sample(t), track.sample(t + 4.));
+                assert_relative_eq!(a.position, b.position, epsilon = 1e-9);
+                assert!(a.orientation.dot(b.orientation).abs() > 1. - 1e-12);
+                assert_relative_eq!(track.sample(t - 8.).position, a.position, epsilon = 1e-9);
+            }
+            // The orientation is continuous across the seam.
+            let (a, b) = (track.sample(4. - 1e-7), track.sample(4. + 1e-7));
+            assert!(a.orientation.dot(b.orientation).abs() > 1. - 1e-9);
+        }
+        #[test] fn ping_pong() {
+            let mut track = flythrough();
+            track.extrapolation = Extrapolation::PingPong;
+            for i in 0..20 {
+                let t = i as f64 * 0.2;
+                let forward = track.sample(t);
+                for &time in &[8. - t, -t, 8. + t] {
+                    let sample = track.sample(time);
+                    assert_relative_eq!(sample.position, forward.position, epsilon = 1e-12);
+                    assert_relative_eq!(sample.orientation, forward.orientation, epsilon = 1e-12);
+                    assert_relative_eq!(sample.scale, forward.scale, epsilon = 1e-12);
+                }
+            }
+        }
+        #[test]
+        #[should_panic]
+        fn unsorted_keys() {
+            let mut track = flythrough();
+            track.push(4., Transform::default());
+        }
+        #[test]
+        #[should_panic]
+        fn no_keys() {
+            TransformTrack::<f32>::new(Extrapolation::Clamp).sample(0.);
+        }
+    }
+
+    #[cfg(nightly)]
+    mod bench {
+        extern crate test;
+        use self::test::{Bencher, black_box};
+        use super::*;
+
+        // A 200-bone rig: a spine, with limbs made of short chains branching off it.
+        fn rig() -> ([Option<usize>; 200], [Transform<f32,f32,f32>; 200]) {
+            let mut parents = [None; 200];
+            let mut locals = [Transform::default(); 200];
+            for i in 0..200 {
+                parents[i] = match i {
+                    0 => None,
+                    i if i % 5 == 0 => Some(i / 5 * 2 - 2),
+                    i => Some(i - 1),
+                };
+                locals[i] = Transform {
+                    position: Vec3::new(0., 0.1, 0.),
+                    orientation: Quaternion::rotation_3d(i as f32 * 0.01, Vec3::new(1., 2., 3.)),
+                    scale: Vec3::one(),
+                };
+            }
+            (parents, locals)
+        }
+
+        #[bench]
+        fn propagate_200_bones(b: &mut Bencher) {
+            let (parents, locals) = rig();
+            let mut world = [Mat4::identity(); 200];
+            b.iter(|| {
+                propagate_transforms(black_box(&parents), black_box(&locals), &mut world).unwrap();
+                black_box(&world);
+            });
+        }
+    }
+}
@@ -13,7 +13,7 @@ use std::ptr;
 use std::cmp;
 use std::ops::*;
 use std::slice::{self, /*SliceIndex*/}; // NOTE: Will want to use SliceIndex once it's stabilized
-use num_traits::{Zero, One, NumCast, AsPrimitive, Signed, real::Real};
+use num_traits::{Zero, One, NumCast, AsPrimitive, Signed, CheckedMul, real::Real};
 use approx::{AbsDiffEq, RelativeEq, UlpsEq};
 use crate::ops::*;
 
@@ -243,6 +243,66 @@ macro_rules! vec_impl_unop {
     }
 }
 
+/// Byte serialization with explicit endianness, for each primitive element type.
+macro_rules! vec_impl_bytes {
+    ($Vec:ident ($dim:expr) $($T:ident $size:tt)+) => {
+        $(
+            impl $Vec<$T> {
+                vec_impl_bytes!{@endian $T $size ($dim) "little" to_le_bytes from_le_bytes write_le_bytes_into read_le_bytes_from}
+                vec_impl_bytes!{@endian $T $size ($dim) "big" to_be_bytes from_be_bytes write_be_bytes_into read_be_bytes_from}
+            }
+        )+
+    };
+    (@endian $T:ident $size:tt ($dim:expr) $endian:tt $to_bytes:ident $from_bytes:ident $write_into:ident $read_from:ident) => {
+        #[doc = concat!("Encodes this vector's elements, in order, as ", $endian, "-endian bytes.")]
+        pub fn $to_bytes(self) -> [u8; $dim * $size] {
+            let mut bytes = [0; $dim * $size];
+            for (chunk, x) in bytes.chunks_exact_mut($size).zip(self.as_slice()) {
+                chunk.copy_from_slice(&x.$to_bytes());
+            }
+            bytes
+        }
+        #[doc = concat!("Decodes a vector from its elements, in order, encoded as ", $endian, "-endian bytes.")]
+        pub fn $from_bytes(bytes: [u8; $dim * $size]) -> Self {
+            let mut array = [0 as $T; $dim];
+            for (x, chunk) in array.iter_mut().zip(bytes.chunks_exact($size)) {
+                let mut b = [0; $size];
+                b.copy_from_slice(chunk);
+                *x = <$T>::$from_bytes(b);
+            }
+            Self::from(array)
+        }
+        #[doc = concat!("Encodes each vector of `slice` as ", $endian, "-endian bytes (see `", stringify!($to_bytes), "()`), ")]
+        /// one after the other, into `out`. Returns the number of bytes written.
+        ///
+        /// # Panics
+        /// Panics if `out` is too small.
+        pub fn $write_into(slice: &[Self], out: &mut [u8]) -> usize {
+            let len = slice.len() * $dim * $size;
+            assert!(out.len() >= len, "output buffer is too small ({} bytes needed, got {})", len, out.len());
+            for (chunk, v) in out.chunks_exact_mut($dim * $size).zip(slice) {
+                chunk.copy_from_slice(&v.$to_bytes());
+            }
+            len
+        }
+        #[doc = concat!("Decodes vectors encoded as ", $endian, "-endian bytes (see `", stringify!($from_bytes), "()`) ")]
+        /// from `bytes` until `out` is filled. Returns the number of bytes read.
+        ///
+        /// # Panics
+        /// Panics if `bytes` is too small.
+        pub fn $read_from(bytes: &[u8], out: &mut [Self]) -> usize {
+            let len = out.len() * $dim * $size;
+            assert!(bytes.len() >= len, "input buffer is too small ({} bytes needed, got {})", len, bytes.len());
+            for (v, chunk) in out.iter_mut().zip(bytes.chunks_exact($dim * $size)) {
+                let mut b = [0; $dim * $size];
+                b.copy_from_slice(chunk);
+                *v = Self::$from_bytes(b);
+            }
+            len
+        }
+    };
+}
+
 /// Generates implementations specific to the given vector type.
 macro_rules! vec_impl_vec {
 
@@ -277,6 +337,70 @@ macro_rules! vec_impl_vec {
     (specific c $Vec:ident $vec:ident ($dim:expr) ($fmt:expr) ($($get:tt)+) ($($namedget:tt)+) ($($tupleget:tt)+) $Tuple:ty) => {
 
         use super::super::repr_c::$vec::$Vec as CVec;
+
+        // The flat slice conversions below rely on `#[repr(C)]` vectors being laid out exactly
+        // like arrays: their fields all have type T, so there can't be any padding between them,
+        // nor at the end (the struct's alignment is T's alignment, which divides T's size).
+        assert_eq_size!($Vec<u8>, [u8; $dim]);
+        assert_eq_size!($Vec<u16>, [u16; $dim]);
+        assert_eq_size!($Vec<f32>, [f32; $dim]);
+        assert_eq_size!($Vec<f64>, [f64; $dim]);
+        assert_eq_align!($Vec<u8>, u8);
+        assert_eq_align!($Vec<f64>, f64);
+
+        impl<T> $Vec<T> {
+            /// Reinterprets a slice of vectors as a flat slice of their elements, without copying.
+            ///
+            /// This is handy for passing vertex buffers to graphics APIs.
+            ///
+            /// ```
+            /// # use vek::vec::Vec3;
+            /// let v = [Vec3::new(0, 1, 2), Vec3::new(3, 4, 5)];
+            /// assert_eq!(Vec3::slice_as_flat(&v), &[0, 1, 2, 3, 4, 5]);
+            /// ```
+            pub fn slice_as_flat(slice: &[Self]) -> &[T] {
+                unsafe {
+                    slice::from_raw_parts(slice.as_ptr() as *const T, slice.len() * $dim)
+                }
+            }
+            /// Reinterprets a mutable slice of vectors as a flat mutable slice of their elements, without copying.
+            pub fn slice_as_flat_mut(slice: &mut [Self]) -> &mut [T] {
+                unsafe {
+                    slice::from_raw_parts_mut(slice.as_mut_ptr() as *mut T, slice.len() * $dim)
+                }
+            }
+            /// Reinterprets a flat slice of elements as a slice of vectors, without copying.
+            ///
+            /// Returns `None` if the length of `flat` is not a multiple of the vector's dimension,
+            /// or if `flat` is not suitably aligned (the latter can't happen with `#[repr(C)]`
+            /// vectors, which have the same alignment as `T`, but is checked nonetheless).
+            ///
+            /// ```
+            /// # use vek::vec::Vec3;
+            /// let flat = [0, 1, 2, 3, 4, 5];
+            /// assert_eq!(Vec3::flat_as_slice(&flat), Some(&[Vec3::new(0, 1, 2), Vec3::new(3, 4, 5)][..]));
+            /// assert_eq!(Vec3::flat_as_slice(&flat[1..]), None);
+            /// ```
+            pub fn flat_as_slice(flat: &[T]) -> Option<&[Self]> {
+                if flat.len() % $dim != 0 || flat.as_ptr() as usize % mem::align_of::<Self>() != 0 {
+                    return None;
+                }
+                Some(unsafe {
+                    slice::from_raw_parts(flat.as_ptr() as *const Self, flat.len() / $dim)
+                })
+            }
+            /// Reinterprets a flat mutable slice of elements as a mutable slice of vectors, without copying.
+            ///
+            /// Returns `None` in the same cases as `flat_as_slice()`.
+            pub fn flat_as_slice_mut(flat: &mut [T]) -> Option<&mut [Self]> {
+                if flat.len() % $dim != 0 || flat.as_ptr() as usize % mem::align_of::<Self>() != 0 {
+                    return None;
+                }
+                Some(unsafe {
+                    slice::from_raw_parts_mut(flat.as_mut_ptr() as *mut Self, flat.len() / $dim)
+                })
+            }
+        }
     };
     (specific simd $Vec:ident $vec:ident ($dim:expr) ($fmt:expr) ($($get:tt)+) ($($namedget:tt)+) ($($tupleget:tt)+) $Tuple:ty) => {
 
@@ -462,6 +586,52 @@ macro_rules! vec_impl_vec {
                 Self::from_iter(slice.into_iter().cloned())
             }
 
+            /// Splits a slice of vectors into fixed-size chunks of `N` vectors, and the remainder
+            /// (which has less than `N` elements), without copying.
+            ///
+            /// This mirrors `slice::as_chunks()` (which isn't available on all toolchains); because
+            /// `N` is known at compile time, loops over the elements of each chunk are easy to unroll
+            /// (see e.g `Mat4::transform_chunk()`).
+            ///
+            /// # Panics
+            /// Panics if `N` is zero.
+            ///
+            /// ```
+            /// # use vek::vec::Vec2;
+            /// let v = [Vec2::new(0, 1), Vec2::new(2, 3), Vec2::new(4, 5)];
+            /// let (chunks, rest) = Vec2::slice_chunks::<2>(&v);
+            /// assert_eq!(chunks, &[[Vec2::new(0, 1), Vec2::new(2, 3)]]);
+            /// assert_eq!(rest, &[Vec2::new(4, 5)]);
+            /// ```
+            pub fn slice_chunks<const N: usize>(slice: &[Self]) -> (&[[Self; N]], &[Self]) {
+                assert!(N != 0, "chunk size must be non-zero");
+                let (head, rest) = slice.split_at(slice.len() / N * N);
+                // SAFETY: `[Self; N]` has the size and alignment of `N` contiguous `Self`, so `head`'s
+                // elements are also `head.len() / N` arrays, and `head.len()` is a multiple of `N`
+                // so none of them is cut short. The result borrows from `slice`, like `head` does.
+                let chunks = unsafe {
+                    slice::from_raw_parts(head.as_ptr() as *const [Self; N], head.len() / N)
+                };
+                (chunks, rest)
+            }
+            /// Splits a mutable slice of vectors into fixed-size chunks of `N` vectors, and the remainder.
+            ///
+            /// See `slice_chunks()`.
+            ///
+            /// # Panics
+            /// Panics if `N` is zero.
+            pub fn slice_chunks_mut<const N: usize>(slice: &mut [Self]) -> (&mut [[Self; N]], &mut [Self]) {
+                assert!(N != 0, "chunk size must be non-zero");
+                let len = slice.len() / N * N;
+                let (head, rest) = slice.split_at_mut(len);
+                // SAFETY: See `slice_chunks()`. `head` and `rest` don't overlap, and `head` is
+                // consumed here, so the result is the only mutable borrow of these elements.
+                let chunks = unsafe {
+                    slice::from_raw_parts_mut(head.as_mut_ptr() as *mut [Self; N], head.len() / N)
+                };
+                (chunks, rest)
+            }
+
             /// Returns a memberwise-converted copy of this vector, using the given conversion
             /// closure.
             ///
@@ -665,6 +835,72 @@ macro_rules! vec_impl_vec {
                 reduce_binop!(&&, $(self.$get.is_positive()),+)
             }
 
+            /// Is each element of `self` strictly less than the respective element of `rhs` ?
+            ///
+            /// Lanes are compared with `PartialOrd`, so a NaN in either vector makes the
+            /// comparison false for that lane (and therefore makes all `is_each_*` predicates false).
+            ///
+            /// ```
+            /// # use vek::vec::Vec3;
+            /// let a = Vec3::new(0_f32, 1., 2.);
+            /// assert!(a.is_each_lt(&Vec3::new(1., 2., 3.)));
+            /// assert!(!a.is_each_lt(&Vec3::new(1., 1., 3.)));
+            /// assert!(a.is_each_le(&Vec3::new(1., 1., 3.)));
+            /// assert!(!a.is_each_lt(&Vec3::new(1., 2., f32::NAN)));
+            /// ```
+            #[inline]
+            pub fn is_each_lt(&self, rhs: &Self) -> bool where T: PartialOrd {
+                reduce_binop!(&&, $(self.$get < rhs.$get),+)
+            }
+            /// Is each element of `self` less than or equal to the respective element of `rhs` ?
+            #[inline]
+            pub fn is_each_le(&self, rhs: &Self) -> bool where T: PartialOrd {
+                reduce_binop!(&&, $(self.$get <= rhs.$get),+)
+            }
+            /// Is each element of `self` strictly greater than the respective element of `rhs` ?
+            #[inline]
+            pub fn is_each_gt(&self, rhs: &Self) -> bool where T: PartialOrd {
+                reduce_binop!(&&, $(self.$get > rhs.$get),+)
+            }
+            /// Is each element of `self` greater than or equal to the respective element of `rhs` ?
+            #[inline]
+            pub fn is_each_ge(&self, rhs: &Self) -> bool where T: PartialOrd {
+                reduce_binop!(&&, $(self.$get >= rhs.$get),+)
+            }
+            /// Is any element of `self` strictly less than the respective element of `rhs` ?
+            ///
+            /// Lanes are compared with `PartialOrd`, so lanes where either element is NaN
+            /// don't count, but the other lanes still can make the result true.
+            /// Note that `is_any_lt()` is therefore not the negation of `is_each_ge()` when NaNs are involved.
+            ///
+            /// ```
+            /// # use vek::vec::Vec3;
+            /// let a = Vec3::new(0_f32, 1., 2.);
+            /// assert!(a.is_any_lt(&Vec3::new(0., 0., 3.)));
+            /// assert!(!a.is_any_lt(&Vec3::new(0., 1., 2.)));
+            /// assert!(a.is_any_lt(&Vec3::new(f32::NAN, 0., 3.)));
+            /// assert!(!a.is_any_lt(&Vec3::new(f32::NAN, 0., 0.)));
+            /// ```
+            #[inline]
+            pub fn is_any_lt(&self, rhs: &Self) -> bool where T: PartialOrd {
+                reduce_binop!(||, $(self.$get < rhs.$get),+)
+            }
+            /// Is any element of `self` less than or equal to the respective element of `rhs` ?
+            #[inline]
+            pub fn is_any_le(&self, rhs: &Self) -> bool where T: PartialOrd {
+                reduce_binop!(||, $(self.$get <= rhs.$get),+)
+            }
+            /// Is any element of `self` strictly greater than the respective element of `rhs` ?
+            #[inline]
+            pub fn is_any_gt(&self, rhs: &Self) -> bool where T: PartialOrd {
+                reduce_binop!(||, $(self.$get > rhs.$get),+)
+            }
+            /// Is any element of `self` greater than or equal to the respective element of `rhs` ?
+            #[inline]
+            pub fn is_any_ge(&self, rhs: &Self) -> bool where T: PartialOrd {
+                reduce_binop!(||, $(self.$get >= rhs.$get),+)
+            }
+
             /// Compares elements of `a` and `b`, and returns the minimum values into a new
             /// vector, using total ordering.
             ///
@@ -985,6 +1221,85 @@ macro_rules! vec_impl_vec {
                 Self::new($(self.$get.round()),+)
             }
 
+            /// Returns a new vector which elements are the respective sines of this
+            /// vector's elements (in radians).
+            ///
+            /// With `#[repr(C)]` vectors, each element is exactly the result of `ops::sin()`.
+            /// `#[repr(simd)]` vectors use LLVM's vector intrinsic instead, which may be vectorized
+            /// depending on the target, unless the `deterministic-trig` feature is enabled.
+            ///
+            /// ```
+            /// # use vek::vec::Vec4;
+            /// let v = Vec4::new(0_f32, 1., -2., 1e6);
+            /// assert_eq!(v.sin(), v.map(vek::ops::sin));
+            /// ```
+            #[inline]
+            pub fn sin(self) -> Self where T: Real {
+                choose!{$c_or_simd {
+                    c => Self::new($(sin(self.$get)),+),
+                    simd_llvm => {
+                        #[cfg(feature="deterministic-trig")] { Self::new($(sin(self.$get)),+) }
+                        #[cfg(not(feature="deterministic-trig"))] { unsafe { simd_llvm::simd_fsin(self) } }
+                    },
+                }}
+            }
+            /// Returns a new vector which elements are the respective cosines of this
+            /// vector's elements (in radians).
+            ///
+            /// See `sin()` for notes on precision.
+            ///
+            /// ```
+            /// # use vek::vec::Vec4;
+            /// let v = Vec4::new(0_f32, 1., -2., 1e6);
+            /// assert_eq!(v.cos(), v.map(vek::ops::cos));
+            /// ```
+            #[inline]
+            pub fn cos(self) -> Self where T: Real {
+                choose!{$c_or_simd {
+                    c => Self::new($(cos(self.$get)),+),
+                    simd_llvm => {
+                        #[cfg(feature="deterministic-trig")] { Self::new($(cos(self.$get)),+) }
+                        #[cfg(not(feature="deterministic-trig"))] { unsafe { simd_llvm::simd_fcos(self) } }
+                    },
+                }}
+            }
+            /// Returns the `(sin(), cos())` pair of this vector.
+            ///
+            /// ```
+            /// # use vek::vec::Vec3;
+            /// let v = Vec3::new(0.5_f64, 3., -1e3);
+            /// assert_eq!(v.sin_cos(), (v.sin(), v.cos()));
+            /// ```
+            #[inline]
+            pub fn sin_cos(self) -> (Self, Self) where T: Real {
+                (Self::new($(sin(self.$get)),+), Self::new($(cos(self.$get)),+))
+            }
+            /// Returns a new vector which elements are the respective tangents of this
+            /// vector's elements (in radians).
+            ///
+            /// ```
+            /// # use vek::vec::Vec4;
+            /// let v = Vec4::new(0_f32, 1., -2., 1e6);
+            /// assert_eq!(v.tan(), v.map(vek::ops::tan));
+            /// ```
+            #[inline]
+            pub fn tan(self) -> Self where T: Real {
+                Self::new($(tan(self.$get)),+)
+            }
+            /// Returns a new vector which elements are the four-quadrant arctangents of
+            /// the respective elements of `self` (as `y`) and `x`, in radians.
+            ///
+            /// ```
+            /// # use vek::vec::Vec2;
+            /// let y = Vec2::new(1_f32, -1.);
+            /// let x = Vec2::new(-1_f32, -1.);
+            /// assert_eq!(y.atan2(x), Vec2::new(vek::ops::atan2(1_f32, -1.), vek::ops::atan2(-1_f32, -1.)));
+            /// ```
+            #[inline]
+            pub fn atan2(self, x: Self) -> Self where T: Real {
+                Self::new($(atan2(self.$get, x.$get)),+)
+            }
+
             /// Horizontally adds adjacent pairs of elements in `self` and `rhs` into a new vector.
             ///
             /// ```
@@ -1544,6 +1859,8 @@ macro_rules! vec_impl_vec {
 
         // CONVERSIONS
 
+        vec_impl_bytes!{$Vec ($dim) u8 1 u16 2 i16 2 u32 4 i32 4 f32 4 f64 8}
+
         #[cfg_attr(feature = "clippy", allow(type_complexity))]
         impl<T> From<$Tuple> for $Vec<T> {
             fn from(tuple: $Tuple) -> Self {
@@ -1701,9 +2018,49 @@ macro_rules! vec_impl_spatial {
                 self.magnitude_squared()
                     .relative_eq(&(x_squared), four_epsilon, four_max_rel)
             }
+            /// Get a copy of this direction vector such that its length equals 1.
+            /// If its magnitude is less than or equal to `epsilon`, None is returned.
+            ///
+            /// This is `try_normalized()` with an explicit tolerance, for use with geometry
+            /// whose scale is far from 1 (see `ops::relative_epsilon_for()`).
+            pub fn try_normalized_with_epsilon(self, epsilon: T) -> Option<Self>
+                where T: Add<T, Output=T> + Real
+            {
+                if self.is_approx_zero_with_epsilon(epsilon) {
+                    None
+                } else {
+                    Some(self.normalized())
+                }
+            }
+            /// Is this vector normalized, i.e is its magnitude within `epsilon` of 1 ?
+            #[inline]
+            pub fn is_normalized_with_epsilon(self, epsilon: T) -> bool where T: Add<T, Output=T> + Real {
+                self.is_magnitude_close_to_with_epsilon(T::one(), epsilon)
+            }
+            /// Is this vector approximately zero, i.e is its magnitude less than or equal to `epsilon` ?
+            ///
+            /// ```
+            /// # use vek::Vec2;
+            /// # use vek::ops::relative_epsilon_for;
+            /// // Rounding errors at a large scale...
+            /// let p = Vec2::new(1e6_f32, 2e6);
+            /// let q = p.rotated_z(std::f32::consts::PI * 2.);
+            /// assert!(!(q - p).is_approx_zero());
+            /// // ...are accounted for with a scale-relative tolerance.
+            /// let eps = relative_epsilon_for(p.into_array(), 16.);
+            /// assert!((q - p).is_approx_zero_with_epsilon(eps));
+            /// ```
+            #[inline]
+            pub fn is_approx_zero_with_epsilon(self, epsilon: T) -> bool where T: Add<T, Output=T> + Real {
+                self.magnitude() <= epsilon
+            }
+            /// Is the magnitude of the vector within `epsilon` of `x` ?
+            pub fn is_magnitude_close_to_with_epsilon(self, x: T, epsilon: T) -> bool where T: Add<T, Output=T> + Real {
+                (self.magnitude() - x).abs() <= epsilon
+            }
             /// Get the smallest angle, in radians, between two direction vectors.
             pub fn angle_between(self, v: Self) -> T where T: Add<T, Output=T> + Real + Clamp {
-                self.normalized().dot(v.normalized()).clamped_minus1_1().acos()
+                acos(self.normalized().dot(v.normalized()).clamped_minus1_1())
             }
             #[deprecated(note="Use `to_degrees()` on the value returned by `angle_between()` instead")]
             /// Get the smallest angle, in degrees, between two direction vectors.
@@ -1804,8 +2161,8 @@ macro_rules! vec_impl_spatial_2d {
             /// ```
             #[inline]
             pub fn rotated_z(self, angle_radians: T) -> Self where T: Real {
-                let c = angle_radians.cos();
-                let s = angle_radians.sin();
+                let c = cos(angle_radians);
+                let s = sin(angle_radians);
                 let Self { x, y } = self;
                 Self::new(c*x - s*y, s*x + c*y)
             }
@@ -1923,10 +2280,10 @@ macro_rules! vec_impl_spatial_3d {
                     let (mag_from, mag_to) = (from.magnitude(), to.magnitude());
                     let (from, to) = (from/mag_from, to/mag_to);
                     let cos_alpha = from.dot(to).clamped_minus1_1();
-                    let alpha = cos_alpha.acos();
-                    let sin_alpha = alpha.sin();
-                    let t1 = ((T::one() - factor) * alpha).sin() / sin_alpha;
-                    let t2 = (factor * alpha).sin() / sin_alpha;
+                    let alpha = acos(cos_alpha);
+                    let sin_alpha = sin(alpha);
+                    let t1 = sin((T::one() - factor) * alpha) / sin_alpha;
+                    let t2 = sin(factor * alpha) / sin_alpha;
                     (from * t1 + to * t2) * Lerp::lerp_unclamped(mag_from, mag_to, factor)
                 }
                 /// Performs spherical linear interpolation between this vector and another,
@@ -1975,6 +2332,67 @@ macro_rules! vec_impl_spatial_3d {
     }
 }
 
+/// Jittering (random offsets) for vectors used as spatial coordinates.
+macro_rules! vec_impl_jitter {
+    ($($Vec:ident)+) => {
+        $(
+            impl<T: Real> $Vec<T> {
+                /// Returns a copy of this vector where each component is offset by a random value
+                /// uniformly distributed in `[-amount, amount]`.
+                ///
+                /// Panics if `amount` is negative or NaN.
+                ///
+                /// ```
+                /// # extern crate vek;
+                /// # extern crate rand;
+                /// # use vek::Vec3;
+                /// # use rand::rngs::mock::StepRng;
+                /// # fn main() {
+                /// let mut rng = StepRng::new(0, 1 << 60);
+                /// let v = Vec3::<f32>::new(1., 2., 3.).jittered(0.5, &mut rng);
+                /// assert!(v.is_each_ge(&Vec3::new(0.5, 1.5, 2.5)));
+                /// assert!(v.is_each_le(&Vec3::new(1.5, 2.5, 3.5)));
+                /// # }
+                /// ```
+                #[cfg(feature="rand")]
+                pub fn jittered<R: $crate::rand::Rng + ?Sized>(self, amount: T, rng: &mut R) -> Self
+                    where T: $crate::rand::distributions::uniform::SampleUniform
+                {
+                    self.map(|x| x + rng.gen_range(-amount..=amount))
+                }
+                /// Returns a copy of this vector where each component is offset by a value in
+                /// `[-amount, amount]`, derived from a hash of this vector's components and `seed`.
+                ///
+                /// This doesn't need a random number generator, and always gives the same result
+                /// for the same input, across calls, runs and platforms (the hash is `sampling::spatial_hash()`).
+                /// Positive and negative zero are treated the same.
+                ///
+                /// ```
+                /// # use vek::Vec3;
+                /// let v = Vec3::<f32>::new(1., 2., 3.);
+                /// let j = v.jittered_hashed(0.5, 7);
+                /// assert_eq!(j, v.jittered_hashed(0.5, 7));
+                /// assert_ne!(j, v.jittered_hashed(0.5, 8));
+                /// assert!(j.is_each_ge(&(v - 0.5)) && j.is_each_le(&(v + 0.5)));
+                /// ```
+                pub fn jittered_hashed(self, amount: T, seed: u64) -> Self {
+                    // Converting to f64 is exact for f32 and f64, and adding zero turns -0 into +0.
+                    let bits = self.map(|x| (x.to_f64().unwrap() + 0.).to_bits());
+                    let base = $crate::sampling::spatial_hash(bits.as_slice(), seed);
+                    let one = T::one();
+                    let mut stream = 0_u64;
+                    self.map(|x| {
+                        let u = $crate::sampling::hash_to_unit($crate::sampling::spatial_hash(&[base, stream], 0));
+                        stream += 1;
+                        let u = T::from(u).unwrap();
+                        x + amount * (u + u - one)
+                    })
+                }
+            }
+        )+
+    }
+}
+
 macro_rules! vec_impl_spatial_4d {
     ($($Vec:ident)+) => {
         $(
@@ -2083,11 +2501,118 @@ macro_rules! vec_impl_spatial_4d {
                 pub fn is_direction(self) -> bool where T: RelativeEq + Zero {
                     self.w.relative_eq(&T::zero(), T::default_epsilon(), T::default_max_relative())
                 }
+                /// Returns true if this vector is a homogeneous point, i.e `w` is within `epsilon` of 1.
+                pub fn is_point_with_epsilon(self, epsilon: T) -> bool where T: Real {
+                    (self.w - T::one()).abs() <= epsilon
+                }
+                /// Returns true if this vector is a homogeneous direction, i.e `w` is within `epsilon` of 0.
+                pub fn is_direction_with_epsilon(self, epsilon: T) -> bool where T: Real {
+                    self.w.abs() <= epsilon
+                }
             }
         )+
     }
 }
 
+macro_rules! vec_impl_vector_space {
+    ($Vec:ident ($dim:expr) ($($get:tt)+) ($($idx:tt)+)) => {
+        impl<T> VectorSpace for $Vec<T>
+            where T: Copy + PartialEq + Add<Output=T> + Sub<Output=T> + Mul<Output=T> + Div<Output=T>
+        {
+            type Scalar = T;
+            const DIM: usize = $dim;
+            fn from_fn<F: FnMut(usize) -> T>(mut f: F) -> Self {
+                Self { $($get: f($idx)),+ }
+            }
+        }
+    };
+}
+
+macro_rules! vec_impl_mask {
+    ($Vec:ident ($($get:tt)+) ($($idx:tt)+)) => {
+        // Boolean vectors act as masks for branchless, lane-wise code, e.g with
+        // the results of `partial_cmplt()` and friends.
+        impl $Vec<bool> {
+            /// Are all elements `true` ? Same as `reduce_and()`.
+            ///
+            /// ```
+            /// # use vek::vec::Vec4;
+            /// let mask = Vec4::new(true, false, true, true);
+            /// assert!(!mask.all());
+            /// assert!(mask.any());
+            /// assert!(!mask.none());
+            /// assert_eq!(mask.count(), 3);
+            /// ```
+            pub fn all(self) -> bool {
+                reduce_binop!(&&, $(self.$get),+)
+            }
+            /// Is any element `true` ? Same as `reduce_or()`.
+            pub fn any(self) -> bool {
+                reduce_binop!(||, $(self.$get),+)
+            }
+            /// Are all elements `false` ?
+            pub fn none(self) -> bool {
+                !self.any()
+            }
+            /// Gets the number of `true` elements.
+            pub fn count(self) -> usize {
+                reduce_binop!(+, $(self.$get as usize),+)
+            }
+            /// Packs this mask into the low bits of a byte, where bit `i` is set if element `i` is `true`
+            /// (i.e `x` is the least significant bit), like x86's `movemask` instructions.
+            ///
+            /// ```
+            /// # use vek::vec::Vec4;
+            /// let mask = Vec4::new(true, false, true, true);
+            /// assert_eq!(mask.to_bits(), 0b1101);
+            /// assert_eq!(Vec4::from_bits(0b1101), mask);
+            /// ```
+            pub fn to_bits(self) -> u8 {
+                reduce_binop!(|, $((self.$get as u8) << $idx),+)
+            }
+            /// Unpacks a mask from the low bits of a byte; the inverse of `to_bits()`.
+            ///
+            /// Bits above the dimension of this vector are ignored.
+            pub fn from_bits(bits: u8) -> Self {
+                Self::new($(bits & (1 << $idx) != 0),+)
+            }
+            /// Picks each element from `if_true` where this mask is `true`, and from `if_false` elsewhere.
+            ///
+            /// ```
+            /// # use vek::vec::Vec4;
+            /// let a = Vec4::new(1, 2, 3, 4);
+            /// let b = Vec4::new(0, 5, 0, 5);
+            /// assert_eq!(a.partial_cmplt(&b).select(a, b), Vec4::new(0, 2, 0, 4));
+            /// ```
+            pub fn select<T>(self, if_true: $Vec<T>, if_false: $Vec<T>) -> $Vec<T> {
+                $Vec::new($(if self.$get { if_true.$get } else { if_false.$get }),+)
+            }
+            /// Converts this mask into the lane mask representation of SIMD hardware, where
+            /// `true` elements have all bits set and `false` elements have none.
+            ///
+            /// ```
+            /// # use vek::vec::Vec4;
+            /// let mask = Vec4::new(true, false, true, true);
+            /// assert_eq!(mask.to_lane_mask::<i32>(), Vec4::new(-1, 0, -1, -1));
+            /// assert_eq!(mask.to_lane_mask::<u8>(), Vec4::new(0xff, 0, 0xff, 0xff));
+            /// assert_eq!(Vec4::from_lane_mask(Vec4::new(-1, 0, -1, -1)), mask);
+            /// ```
+            pub fn to_lane_mask<M: Zero + Not<Output=M>>(self) -> $Vec<M> {
+                $Vec::new($(if self.$get { !M::zero() } else { M::zero() }),+)
+            }
+            /// Converts a lane mask, as produced by SIMD comparison instructions, into a boolean mask;
+            /// the inverse of `to_lane_mask()`.
+            ///
+            /// Elements are `true` wherever they are non-zero. Masks can move between the `repr_simd`
+            /// and `repr_c` forms with `into_repr_c()` and `into_repr_simd()`, either before or after
+            /// this conversion.
+            pub fn from_lane_mask<M: Zero>(mask: $Vec<M>) -> Self {
+                Self::new($(!mask.$get.is_zero()),+)
+            }
+        }
+    };
+}
+
 #[cfg(feature="image")]
 macro_rules! vec_impl_pixel_rgb {
     ($Vec:ident) => {
@@ -2754,7 +3279,10 @@ macro_rules! vec_impl_all_vecs {
             vec_impl_mint!(Vec2, Vector2, (x y));
             vec_impl_mint!(Vec2, Point2, (x y));
             vec_impl_spatial!(Vec2);
+            vec_impl_vector_space!(Vec2 (2) (x y) (0 1));
+            vec_impl_mask!(Vec2 (x y) (0 1));
             vec_impl_spatial_2d!(Vec2);
+            vec_impl_jitter!(Vec2);
 
             impl<T> Vec2<T> {
                 /// Returns a copy of this vector, with X and Y swapped.
@@ -2762,6 +3290,96 @@ macro_rules! vec_impl_all_vecs {
                     let Self { x, y } = self;
                     Self { x: y, y: x }
                 }
+                /// Creates a unit vector pointing in the direction given by an angle,
+                /// measured counterclockwise from the X axis.
+                ///
+                /// ```
+                /// # extern crate vek;
+                /// # #[macro_use] extern crate approx;
+                /// # use vek::{Vec2, Degrees};
+                /// # fn main() {
+                /// assert_relative_eq!(Vec2::<f32>::from_angle(Degrees(90.)), Vec2::unit_y());
+                /// # }
+                /// ```
+                pub fn from_angle<A: IntoAngleRadians<T>>(angle_radians: A) -> Self where T: Real {
+                    let angle_radians = angle_radians.into_angle_radians();
+                    Self::new(cos(angle_radians), sin(angle_radians))
+                }
+            }
+
+            /// Geographic coordinates on a sphere: these treat a `Vec2` as a `(latitude, longitude)` pair
+            /// in radians, i.e `x` is the latitude, positive to the north, and `y` is the longitude,
+            /// positive to the east.
+            ///
+            /// Bearings are in radians, measured clockwise from north.
+            impl<T: Real> Vec2<T> {
+                /// Converts these geographic coordinates to a point on the unit sphere.
+                ///
+                /// The X axis points at `(0, 0)`, the Y axis at `(0, 90°E)`, and the Z axis at the north pole.
+                ///
+                /// ```
+                /// # extern crate vek;
+                /// # #[macro_use] extern crate approx;
+                /// # use vek::Vec2;
+                /// # fn main() {
+                /// let paris = Vec2::new(48.8566_f64.to_radians(), 2.3522_f64.to_radians());
+                /// let v = paris.latlon_to_unit_vec3();
+                /// assert_relative_eq!(v.magnitude(), 1.);
+                /// assert_relative_eq!(Vec2::unit_vec3_to_latlon(v), paris, epsilon = 1e-12);
+                /// # }
+                /// ```
+                pub fn latlon_to_unit_vec3(self) -> Vec3<T> {
+                    let (lat, lon) = (self.x, self.y);
+                    Vec3::new(cos(lat) * cos(lon), cos(lat) * sin(lon), sin(lat))
+                }
+                /// Converts a point on the sphere to geographic coordinates; the inverse of `latlon_to_unit_vec3()`.
+                ///
+                /// The longitude is in `[-PI, PI]`, and is zero at the poles. `v` doesn't have to be normalized.
+                pub fn unit_vec3_to_latlon(v: Vec3<T>) -> Self {
+                    // Also avoids atan2(-0, -0) == -PI.
+                    let lon = if v.x.is_zero() && v.y.is_zero() { T::zero() } else { atan2(v.y, v.x) };
+                    Self::new(atan2(v.z, sqrt(v.x * v.x + v.y * v.y)), lon)
+                }
+                /// Gets the great-circle distance between two points on a sphere of the given radius,
+                /// with the haversine formula (which is accurate even for very close points).
+                ///
+                /// ```
+                /// # use vek::Vec2;
+                /// let london = Vec2::new(51.5074_f64.to_radians(), -0.1278_f64.to_radians());
+                /// let paris = Vec2::new(48.8566_f64.to_radians(), 2.3522_f64.to_radians());
+                /// let km = london.haversine_distance(paris, 6371.);
+                /// assert!((km - 343.5).abs() < 0.1);
+                /// ```
+                pub fn haversine_distance(self, other: Self, radius: T) -> T {
+                    let two = T::one() + T::one();
+                    let (half_dlat, half_dlon) = ((other.x - self.x) / two, (other.y - self.y) / two);
+                    let h = sin(half_dlat) * sin(half_dlat) + cos(self.x) * cos(other.x) * sin(half_dlon) * sin(half_dlon);
+                    let h = h.min(T::one());
+                    two * radius * atan2(sqrt(h), sqrt(T::one() - h))
+                }
+                /// Gets the initial bearing (i.e forward azimuth) of the great-circle path from `self` to `other`,
+                /// in `[-PI, PI]`, clockwise from north.
+                ///
+                /// The bearing generally changes along the path. Starting at a pole, it is relative to the
+                /// meridian of `self`'s longitude. It is zero when both points are the same.
+                pub fn initial_bearing(self, other: Self) -> T {
+                    let dlon = other.y - self.y;
+                    atan2(
+                        sin(dlon) * cos(other.x),
+                        cos(self.x) * sin(other.x) - sin(self.x) * cos(other.x) * cos(dlon),
+                    )
+                }
+                /// Gets the point reached by travelling `distance` from `self` along a great circle, with the
+                /// given initial bearing, on a sphere of the given radius.
+                ///
+                /// The longitude of the result is wrapped to `[-PI, PI]`, so the path may cross the antimeridian.
+                pub fn destination_point(self, bearing: T, distance: T, radius: T) -> Self {
+                    let angle = distance / radius;
+                    let sin_lat = (sin(self.x) * cos(angle) + cos(self.x) * sin(angle) * cos(bearing)).max(-T::one()).min(T::one());
+                    let lat = atan2(sin_lat, sqrt(T::one() - sin_lat * sin_lat));
+                    let lon = self.y + atan2(sin(bearing) * sin(angle) * cos(self.x), cos(angle) - sin(self.x) * sin_lat);
+                    Self::new(lat, atan2(sin(lon), cos(lon)))
+                }
             }
 
             impl<T> From<Vec3<T>> for Vec2<T> {
@@ -2795,7 +3413,10 @@ macro_rules! vec_impl_all_vecs {
             vec_impl_mint!(Vec3, Vector3, (x y z));
             vec_impl_mint!(Vec3, Point3, (x y z));
             vec_impl_spatial!(Vec3);
+            vec_impl_vector_space!(Vec3 (3) (x y z) (0 1 2));
+            vec_impl_mask!(Vec3 (x y z) (0 1 2));
             vec_impl_spatial_3d!(Vec3);
+            vec_impl_jitter!(Vec3);
 
             impl<T> Vec3<T> {
                 /// Returns a copy of this vector, with X and Z swapped.
@@ -2809,6 +3430,112 @@ macro_rules! vec_impl_all_vecs {
                 }
             }
 
+            impl<T: Real> Vec3<T> {
+                /// Encodes this unit vector as a point of the `[-1, 1]` square, using the octahedral
+                /// mapping (Cigolle et al., _"A Survey of Efficient Representations for Independent Unit Vectors"_).
+                ///
+                /// The negative Z hemisphere is folded over the square's corners.
+                /// `self` is assumed to be normalized.
+                ///
+                /// ```
+                /// # extern crate vek;
+                /// # #[macro_use] extern crate approx;
+                /// # use vek::Vec3;
+                /// # fn main() {
+                /// let v = Vec3::new(1_f32, -2., -3.).normalized();
+                /// assert_relative_eq!(Vec3::from_octahedral(v.to_octahedral()), v, epsilon = 0.00001);
+                /// # }
+                /// ```
+                pub fn to_octahedral(self) -> Vec2<T> {
+                    let sign_not_zero = |x: T| if x >= T::zero() { T::one() } else { -T::one() };
+                    let l1 = self.x.abs() + self.y.abs() + self.z.abs();
+                    let p = Vec2::new(self.x / l1, self.y / l1);
+                    if self.z >= T::zero() {
+                        p
+                    } else {
+                        Vec2::new(
+                            (T::one() - p.y.abs()) * sign_not_zero(p.x),
+                            (T::one() - p.x.abs()) * sign_not_zero(p.y),
+                        )
+                    }
+                }
+                /// Decodes a unit vector from a point of the `[-1, 1]` square, as produced by `to_octahedral()`.
+                ///
+                /// The result is normalized.
+                pub fn from_octahedral(p: Vec2<T>) -> Self {
+                    let sign_not_zero = |x: T| if x >= T::zero() { T::one() } else { -T::one() };
+                    let z = T::one() - p.x.abs() - p.y.abs();
+                    let v = if z >= T::zero() {
+                        Self::new(p.x, p.y, z)
+                    } else {
+                        Self::new(
+                            (T::one() - p.y.abs()) * sign_not_zero(p.x),
+                            (T::one() - p.x.abs()) * sign_not_zero(p.y),
+                            z
+                        )
+                    };
+                    v.normalized()
+                }
+            }
+
+            impl Vec3<f32> {
+                /// Encodes this unit vector as two 16-bit unsigned integers, using the octahedral
+                /// mapping (see `to_octahedral()`).
+                ///
+                /// The maximum angular error after decoding is about 0.005 degrees.
+                ///
+                /// ```
+                /// # extern crate vek;
+                /// # #[macro_use] extern crate approx;
+                /// # use vek::Vec3;
+                /// # fn main() {
+                /// let v = Vec3::new(1_f32, -2., -3.).normalized();
+                /// assert_relative_eq!(Vec3::from_octahedral_u16x2(v.to_octahedral_u16x2()), v, epsilon = 0.0001);
+                /// # }
+                /// ```
+                pub fn to_octahedral_u16x2(self) -> [u16; 2] {
+                    let p = self.to_octahedral();
+                    let quantize = |x: f32| ((x.clamped_minus1_1() * 0.5 + 0.5) * 65535.).round() as u16;
+                    [quantize(p.x), quantize(p.y)]
+                }
+                /// Decodes a unit vector from two 16-bit unsigned integers, as produced by `to_octahedral_u16x2()`.
+                pub fn from_octahedral_u16x2(p: [u16; 2]) -> Self {
+                    let dequantize = |x: u16| (x as f32 / 65535.) * 2. - 1.;
+                    Self::from_octahedral(Vec2::new(dequantize(p[0]), dequantize(p[1])))
+                }
+                /// Packs this vector into the `GL_INT_2_10_10_10_REV` signed normalized format,
+                /// e.g for compact vertex normals.
+                ///
+                /// Elements are clamped to `[-1, 1]`, then scaled by 511 and rounded to the nearest integer.
+                /// X lives in bits 0-9, Y in bits 10-19, and Z in bits 20-29; the 2-bit W element
+                /// (bits 30-31) is set to zero.
+                ///
+                /// ```
+                /// # use vek::Vec3;
+                /// assert_eq!(Vec3::new(1_f32, 0., -1.).to_snorm_2_10_10_10(), 0x201 << 20 | 0x1ff);
+                /// ```
+                pub fn to_snorm_2_10_10_10(self) -> u32 {
+                    let quantize = |x: f32| ((x.clamped_minus1_1() * 511.).round() as i32 as u32) & 0x3ff;
+                    quantize(self.x) | quantize(self.y) << 10 | quantize(self.z) << 20
+                }
+                /// Unpacks a vector from the `GL_INT_2_10_10_10_REV` signed normalized format,
+                /// ignoring the 2-bit W element.
+                ///
+                /// Following the OpenGL 4.2+ rules, each 10-bit integer `c` maps to `max(c / 511, -1)`,
+                /// so both -511 and -512 decode to -1.
+                ///
+                /// ```
+                /// # use vek::Vec3;
+                /// assert_eq!(Vec3::from_snorm_2_10_10_10(0x201 << 20 | 0x1ff), Vec3::new(1., 0., -1.));
+                /// assert_eq!(Vec3::from_snorm_2_10_10_10(0x200), Vec3::new(-1., 0., 0.));
+                /// ```
+                pub fn from_snorm_2_10_10_10(packed: u32) -> Self {
+                    // Shifting the 10 bits to the top then back sign-extends them.
+                    let dequantize = |shift: u32| ((((packed << (22 - shift)) as i32) >> 22) as f32 / 511.).max(-1.);
+                    Self::new(dequantize(0), dequantize(10), dequantize(20))
+                }
+            }
+
             impl<T: Zero> From<Vec2<T>> for Vec3<T> {
                 fn from(v: Vec2<T>) -> Self {
                     Self::new(v.x, v.y, T::zero())
@@ -2860,6 +3587,8 @@ macro_rules! vec_impl_all_vecs {
             vec_impl_vec!($c_or_simd struct Vec4   vec4    (4) ("({}, {}, {}, {})") (x y z w) (x y z w) (0 1 2 3) (T,T,T,T));
             vec_impl_mint!(Vec4, Vector4, (x y z w));
             vec_impl_spatial!(Vec4);
+            vec_impl_vector_space!(Vec4 (4) (x y z w) (0 1 2 3));
+            vec_impl_mask!(Vec4 (x y z w) (0 1 2 3));
             vec_impl_spatial_4d!(Vec4);
             vec_impl_shuffle_4d!(Vec4 (x y z w));
             vec_impl_mat2_via_vec4!(Vec4);
@@ -3018,6 +3747,32 @@ macro_rules! vec_impl_all_vecs {
                     Self::new(v.x, v.y, v.z)
                 }
             }
+
+            impl<T> Extent3<T> {
+                /// Gets the volume (`w * h * d`), or `None` if it overflows `T`.
+                ///
+                /// `product()` silently wraps (or panics in debug builds) instead.
+                ///
+                /// ```
+                /// # use vek::Extent3;
+                /// assert_eq!(Extent3::new(1024_u32, 1024, 4096).checked_volume(), None);
+                /// assert_eq!(Extent3::new(1024_u32, 1024, 4095).checked_volume(), Some(1024 * 1024 * 4095));
+                /// ```
+                pub fn checked_volume(self) -> Option<T> where T: CheckedMul {
+                    self.w.checked_mul(&self.h)?.checked_mul(&self.d)
+                }
+                /// Gets the volume (`w * h * d`) as an `u128`, which can't overflow since
+                /// elements are at most 32 bits wide.
+                ///
+                /// ```
+                /// # use vek::Extent3;
+                /// let e = Extent3::new(u32::MAX, u32::MAX, u32::MAX);
+                /// assert_eq!(e.volume_u128(), (u32::MAX as u128).pow(3));
+                /// ```
+                pub fn volume_u128(self) -> u128 where T: Into<u32> {
+                    self.w.into() as u128 * self.h.into() as u128 * self.d.into() as u128
+                }
+            }
         }
         pub use self::extent3::Extent3;
 
@@ -3045,6 +3800,44 @@ macro_rules! vec_impl_all_vecs {
                     Self::new(v.x, v.y)
                 }
             }
+
+            impl<T> Extent2<T> {
+                /// Gets the area (`w * h`), or `None` if it overflows `T`.
+                ///
+                /// `product()` silently wraps (or panics in debug builds) instead.
+                ///
+                /// ```
+                /// # use vek::Extent2;
+                /// assert_eq!(Extent2::new(65536_u32, 65536).checked_area(), None);
+                /// assert_eq!(Extent2::new(65536_u32, 65535).checked_area(), Some(65536 * 65535));
+                /// ```
+                pub fn checked_area(self) -> Option<T> where T: CheckedMul {
+                    self.w.checked_mul(&self.h)
+                }
+                /// Gets the area (`w * h`) as an `u64`, which can't overflow since
+                /// elements are at most 32 bits wide.
+                ///
+                /// ```
+                /// # use vek::Extent2;
+                /// assert_eq!(Extent2::new(65536_u32, 65536).area_u64(), 1 << 32);
+                /// assert_eq!(Extent2::new(u32::MAX, u32::MAX).area_u64(), (u32::MAX as u64).pow(2));
+                /// ```
+                pub fn area_u64(self) -> u64 where T: Into<u32> {
+                    self.w.into() as u64 * self.h.into() as u64
+                }
+                /// Gets the aspect ratio (`w / h`), as expected by perspective projections.
+                ///
+                /// Integer extents must be converted first, e.g with `as_()`, to avoid
+                /// truncation.
+                ///
+                /// ```
+                /// # use vek::Extent2;
+                /// assert_eq!(Extent2::new(1920_u32, 1080).as_::<f32>().aspect_ratio(), 16. / 9.);
+                /// ```
+                pub fn aspect_ratio(self) -> T where T: Real {
+                    self.w / self.h
+                }
+            }
         }
         pub use self::extent2::Extent2;
 
@@ -3072,6 +3865,23 @@ macro_rules! vec_impl_all_vecs {
                     self.into()
                 }
             }
+            #[cfg(feature="rgb")]
+            impl Rgba<f32> {
+                /// Applies an adjustment to the RGB part of each color of a slice (e.g a row of an
+                /// image), keeping alpha unchanged.
+                ///
+                /// ```
+                /// # use vek::Rgba;
+                /// let mut row = [Rgba::new(0.25_f32, 0.5, 0.75, 1.), Rgba::new(1., 0., 0., 0.5)];
+                /// Rgba::adjust_slice(&mut row, |c| c.levels(0., 0.5, 1., 0., 1.).hue_shifted(120.));
+                /// assert_eq!(row, [Rgba::new(1., 0.5, 1., 1.), Rgba::new(0., 1., 0., 0.5)]);
+                /// ```
+                pub fn adjust_slice<F: FnMut(Rgb<f32>) -> Rgb<f32>>(pixels: &mut [Self], mut adjust: F) {
+                    for pixel in pixels {
+                        *pixel = Self::from_translucent(adjust(pixel.rgb()), pixel.a);
+                    }
+                }
+            }
             impl<T> From<Vec4<T>> for Rgba<T> {
                 fn from(v: Vec4<T>) -> Self {
                     Self::new(v.x, v.y, v.z, v.w)
@@ -3103,6 +3913,209 @@ macro_rules! vec_impl_all_vecs {
             vec_impl_vec!($c_or_simd struct Rgb     rgb     (3) ("rgb({}, {}, {})") (r g b) (r g b) (0 1 2) (T,T,T));
             vec_impl_color_rgb!{Rgb}
 
+            impl Rgb<f32> {
+                /// Packs this color into the `GL_UNSIGNED_INT_5_9_9_9_REV` shared-exponent format
+                /// (a.k.a `RGB9E5`), commonly used for HDR textures.
+                ///
+                /// This follows the encoding described by the `EXT_texture_shared_exponent` specification:
+                /// elements are clamped to `[0, 65408]` (NaN becomes zero), the shared exponent is chosen
+                /// from the largest element, and mantissas are rounded to the nearest integer.
+                /// Red lives in bits 0-8, green in bits 9-17, blue in bits 18-26 and the exponent in bits 27-31.
+                ///
+                /// ```
+                /// # use vek::Rgb;
+                /// assert_eq!(Rgb::new(1_f32, 1., 1.).to_rgb9e5(), 0x84020100);
+                /// assert_eq!(Rgb::new(0_f32, 0., 0.).to_rgb9e5(), 0);
+                /// assert_eq!(Rgb::from_rgb9e5(0x84020100), Rgb::new(1., 1., 1.));
+                /// ```
+                pub fn to_rgb9e5(self) -> u32 {
+                    const MANTISSA_BITS: i32 = 9;
+                    const EXP_BIAS: i32 = 15;
+                    const MAX_EXP: i32 = 31;
+                    // (2^9 - 1) / 2^9 * 2^(31 - 15)
+                    const MAX_VALUE: f32 = 65408.;
+                    // Exact powers of two, built from their bit pattern.
+                    let exp2 = |e: i32| f32::from_bits(((e + 127) as u32) << 23);
+                    // `max` and `min` return the non-NaN operand, so NaN is mapped to zero.
+                    let clamp = |x: f32| x.max(0.).min(MAX_VALUE);
+                    let (r, g, b) = (clamp(self.r), clamp(self.g), clamp(self.b));
+                    let max = r.max(g).max(b);
+                    // floor(log2(max)), clamped below to -B - 1 (which also covers zero and subnormals).
+                    let floor_log2 = if max < exp2(-EXP_BIAS - 1) {
+                        -EXP_BIAS - 1
+                    } else {
+                        ((max.to_bits() >> 23) & 0xff) as i32 - 127
+                    };
+                    let mut exp = floor_log2 + 1 + EXP_BIAS;
+                    let max_mantissa = (max / exp2(exp - EXP_BIAS - MANTISSA_BITS) + 0.5).floor() as u32;
+                    if max_mantissa == 1 << MANTISSA_BITS {
+                        exp += 1;
+                    }
+                    debug_assert!((0..=MAX_EXP).contains(&exp));
+                    let scale = exp2(exp - EXP_BIAS - MANTISSA_BITS);
+                    let mantissa = |x: f32| (x / scale + 0.5).floor() as u32;
+                    mantissa(r) | mantissa(g) << 9 | mantissa(b) << 18 | (exp as u32) << 27
+                }
+                /// Unpacks a color from the `GL_UNSIGNED_INT_5_9_9_9_REV` shared-exponent format.
+                ///
+                /// See `to_rgb9e5()`.
+                pub fn from_rgb9e5(packed: u32) -> Self {
+                    let exp = (packed >> 27) as i32;
+                    let scale = f32::from_bits(((exp - 15 - 9 + 127) as u32) << 23);
+                    let mantissa = |shift: u32| ((packed >> shift) & 0x1ff) as f32 * scale;
+                    Self::new(mantissa(0), mantissa(9), mantissa(18))
+                }
+            }
+
+            /// Image adjustments, in the style of image editors.
+            ///
+            /// Elements are expected to be in `[0, 1]`.
+            impl Rgb<f32> {
+                /// Remaps each element with a "Levels" adjustment, the same way as common image
+                /// editors do.
+                ///
+                /// In order, each element is:
+                /// 1. remapped from `[in_black, in_white]` to `[0, 1]`, then clamped to `[0, 1]`;
+                /// 2. raised to the power of `1 / gamma` (so that a `gamma` above 1 brightens midtones);
+                /// 3. remapped from `[0, 1]` to `[out_black, out_white]`.
+                ///
+                /// Edge cases are the limits of the above: if `in_black == in_white`, elements below
+                /// them become `out_black`, and the others `out_white`.
+                /// If `gamma <= 0`, elements below `in_white` become `out_black`.
+                /// `in_white < in_black` inverts the input range.
+                ///
+                /// ```
+                /// # use vek::Rgb;
+                /// let c = Rgb::new(0.25_f32, 0.5, 0.75);
+                /// assert_eq!(c.levels(0., 1., 1., 0., 1.), c);
+                /// assert_eq!(c.levels(0.25, 0.75, 1., 0., 1.), Rgb::new(0., 0.5, 1.));
+                /// assert_eq!(c.levels(0., 1., 1., 1., 0.), Rgb::new(0.75, 0.5, 0.25));
+                /// assert_eq!(c.levels(0.5, 0.5, 1., 0., 1.), Rgb::new(0., 1., 1.));
+                /// ```
+                pub fn levels(self, in_black: f32, in_white: f32, gamma: f32, out_black: f32, out_white: f32) -> Self {
+                    self.map(|x| {
+                        let x = if in_black == in_white {
+                            if x < in_black { 0. } else { 1. }
+                        } else {
+                            ((x - in_black) / (in_white - in_black)).clamp(0., 1.)
+                        };
+                        let x = if gamma > 0. {
+                            x.powf(1. / gamma)
+                        } else if x < 1. {
+                            0.
+                        } else {
+                            1.
+                        };
+                        out_black + x * (out_white - out_black)
+                    })
+                }
+                /// Adjusts brightness, then contrast, the same way as GIMP's legacy
+                /// "Brightness-Contrast" tool. Both range from -1 to 1, and 0 leaves this color unchanged.
+                ///
+                /// A positive `brightness` moves elements towards 1 by this fraction of the
+                /// remaining distance; a negative one scales elements down towards 0.
+                /// Then, `contrast` scales the distance of elements to 0.5, by the tangent of
+                /// `(contrast + 1) * π/4`: -1 turns everything gray, and 1 thresholds at 0.5.
+                ///
+                /// Parameters are clamped to `[-1, 1]`, and the results to `[0, 1]`.
+                ///
+                /// ```
+                /// # use vek::Rgb;
+                /// let c = Rgb::new(0.25_f32, 0.5, 0.75);
+                /// assert_eq!(c.brightness_contrast(0., 0.), c);
+                /// assert_eq!(c.brightness_contrast(0.5, 0.), Rgb::new(0.625, 0.75, 0.875));
+                /// assert_eq!(c.brightness_contrast(-0.5, 0.), Rgb::new(0.125, 0.25, 0.375));
+                /// assert_eq!(c.brightness_contrast(0., -1.), Rgb::broadcast(0.5));
+                /// assert_eq!(c.brightness_contrast(0., 1.), Rgb::new(0., 1., 1.));
+                /// ```
+                pub fn brightness_contrast(self, brightness: f32, contrast: f32) -> Self {
+                    let brightness = brightness.clamp(-1., 1.);
+                    let contrast = contrast.clamp(-1., 1.);
+                    self.map(|x| {
+                        let x = if brightness < 0. {
+                            x * (1. + brightness)
+                        } else {
+                            x + (1. - x) * brightness
+                        };
+                        let x = if contrast >= 1. {
+                            // The slope is infinite.
+                            if x < 0.5 { 0. } else { 1. }
+                        } else {
+                            (x - 0.5) * tan((contrast + 1.) * core::f32::consts::FRAC_PI_4) + 0.5
+                        };
+                        x.clamp(0., 1.)
+                    })
+                }
+                /// Rotates the hue of this color by `degrees` (positive goes from red towards green),
+                /// keeping its HSV saturation and value.
+                ///
+                /// Since this also keeps the smallest element, the HSL saturation and lightness are
+                /// kept as well. Grays are left unchanged.
+                ///
+                /// ```
+                /// # use vek::Rgb;
+                /// assert_eq!(Rgb::new(1_f32, 0., 0.).hue_shifted(120.), Rgb::new(0., 1., 0.));
+                /// assert_eq!(Rgb::new(1_f32, 0.5, 0.).hue_shifted(30.), Rgb::new(1., 1., 0.));
+                /// assert_eq!(Rgb::new(1_f32, 0.5, 0.).hue_shifted(-420.), Rgb::new(1., 0., 0.5));
+                /// assert_eq!(Rgb::new(0.5_f32, 0.5, 0.5).hue_shifted(90.), Rgb::broadcast(0.5));
+                /// ```
+                pub fn hue_shifted(self, degrees: f32) -> Self {
+                    let max = self.r.max(self.g).max(self.b);
+                    let min = self.r.min(self.g).min(self.b);
+                    let chroma = max - min;
+                    if chroma <= 0. {
+                        return self;
+                    }
+                    // Hue, in sixths of a turn.
+                    let hue = if max == self.r {
+                        (self.g - self.b) / chroma
+                    } else if max == self.g {
+                        (self.b - self.r) / chroma + 2.
+                    } else {
+                        (self.r - self.g) / chroma + 4.
+                    };
+                    let hue = (hue + degrees / 60.) % 6.;
+                    let hue = if hue < 0. { hue + 6. } else { hue };
+                    let sector = (hue as u32).min(5);
+                    let x = min + chroma * (1. - (hue % 2. - 1.).abs());
+                    match sector {
+                        0 => Self::new(max, x, min),
+                        1 => Self::new(x, max, min),
+                        2 => Self::new(min, max, x),
+                        3 => Self::new(min, x, max),
+                        4 => Self::new(x, min, max),
+                        _ => Self::new(max, min, x),
+                    }
+                }
+                /// Gets the luminance of this color, as defined by Rec. 709 (i.e sRGB) for linear values.
+                ///
+                /// ```
+                /// # use vek::Rgb;
+                /// assert_eq!(Rgb::new(1_f32, 1., 1.).luminance(), 1.);
+                /// assert_eq!(Rgb::new(0_f32, 1., 0.).luminance(), 0.7152);
+                /// ```
+                pub fn luminance(self) -> f32 {
+                    0.2126 * self.r + 0.7152 * self.g + 0.0722 * self.b
+                }
+                /// Scales the saturation of this color, by linearly interpolating from the gray of
+                /// the same `luminance()` towards it, by `factor`.
+                ///
+                /// 0 gives the gray, 1 leaves this color unchanged, and values above 1 saturate it
+                /// further. Results are clamped to `[0, 1]`.
+                ///
+                /// ```
+                /// # use vek::Rgb;
+                /// let c = Rgb::new(1_f32, 0.5, 0.);
+                /// assert_eq!(c.saturated(1.), c);
+                /// assert_eq!(c.saturated(0.), Rgb::broadcast(c.luminance()));
+                /// assert_eq!(c.saturated(10.), Rgb::new(1., 0., 0.));
+                /// ```
+                pub fn saturated(self, factor: f32) -> Self {
+                    let gray = self.luminance();
+                    self.map(|x| (gray + (x - gray) * factor).clamp(0., 1.))
+                }
+            }
+
             impl<T> From<Vec3<T>> for Rgb<T> {
                 fn from(v: Vec3<T>) -> Self {
                     Self::new(v.x, v.y, v.z)
@@ -3268,6 +4281,27 @@ mod tests {
                 assert_eq!(v.as_slice(), &a);
             }
 
+            #[test] fn flat_slices() {
+                let mut v = [$Vec::<$T>::iota(), $Vec::<$T>::iota() + (1 as $T)];
+                let dim = v[0].elem_count();
+                let flat = $Vec::slice_as_flat(&v);
+                assert_eq!(flat.len(), 2 * dim);
+                assert_eq!(&flat[..dim], v[0].as_slice());
+                assert_eq!(&flat[dim..], v[1].as_slice());
+                assert_eq!($Vec::flat_as_slice(flat), Some(&v[..]));
+                assert_eq!($Vec::flat_as_slice(&flat[dim..]), Some(&v[1..]));
+                // Lengths that aren't a multiple of the dimension are rejected.
+                assert_eq!($Vec::flat_as_slice(&flat[1..]), None);
+                assert_eq!($Vec::flat_as_slice(&flat[..2 * dim - 1]), None);
+
+                $Vec::slice_as_flat_mut(&mut v)[dim] = 5 as $T;
+                assert_eq!(v[1][0], 5 as $T);
+                let mut flat = v[0].into_array();
+                $Vec::flat_as_slice_mut(&mut flat).unwrap()[0][1] = 7 as $T;
+                assert_eq!(flat[1], 7 as $T);
+                assert!($Vec::flat_as_slice_mut(&mut flat[1..]).is_none());
+            }
+
             #[test] fn commutative() {
                 let v = $Vec::from(5 as $T);
                 assert_eq!((2 as $T) * v, v * (2 as $T));
@@ -3275,6 +4309,588 @@ mod tests {
             }
         };
     }
+    mod packed_formats {
+        use crate::vec::repr_c::{Vec3, Rgb};
+
+        // Deterministic values in [0, 1).
+        fn lcg(n: usize) -> impl Iterator<Item=f32> {
+            let mut state = 12345_u32;
+            (0..n).map(move |_| {
+                state = state.wrapping_mul(1664525).wrapping_add(1013904223);
+                (state >> 8) as f32 / (1 << 24) as f32
+            })
+        }
+
+        #[test]
+        fn rgb9e5_special_values() {
+            assert_eq!(Rgb::new(65408_f32, 65408., 65408.).to_rgb9e5(), 0xffff_ffff);
+            assert_eq!(Rgb::new(1e9_f32, f32::INFINITY, 65408.).to_rgb9e5(), 0xffff_ffff);
+            assert_eq!(Rgb::from_rgb9e5(0xffff_ffff), Rgb::broadcast(65408.));
+            assert_eq!(Rgb::new(-1_f32, f32::NAN, -0.).to_rgb9e5(), 0);
+            // The smallest representable value is 2^-24 (mantissa 1, exponent 0).
+            let min = 1. / (1 << 24) as f32;
+            assert_eq!(Rgb::new(min, 0., 0.).to_rgb9e5(), 1);
+            assert_eq!(Rgb::from_rgb9e5(1), Rgb::new(min, 0., 0.));
+            assert_eq!(Rgb::new(min * 0.49, 0., 0.).to_rgb9e5(), 0);
+            // Rounding the largest mantissa up bumps the shared exponent.
+            let packed = Rgb::new(1.999_f32, 0.5, 0.).to_rgb9e5();
+            assert_eq!(packed >> 27, 17);
+            assert_eq!(Rgb::from_rgb9e5(packed), Rgb::new(2., 0.5, 0.));
+            // Each element keeps its own mantissa.
+            assert_eq!(Rgb::from_rgb9e5(Rgb::new(4_f32, 2., 0.25).to_rgb9e5()), Rgb::new(4., 2., 0.25));
+        }
+
+        #[test]
+        fn rgb9e5_round_trip() {
+            let mut values = lcg(3 * 3000);
+            for i in 0..3000 {
+                // Spread magnitudes over the whole representable range.
+                let scale = 2_f32.powi(i % 32 - 16);
+                let c = Rgb::new(values.next().unwrap(), values.next().unwrap(), values.next().unwrap()) * scale;
+                let max = c.r.max(c.g).max(c.b);
+                let decoded = Rgb::from_rgb9e5(c.to_rgb9e5());
+                for (a, b) in c.into_iter().zip(decoded) {
+                    // Half a step of a 9-bit mantissa relative to the largest element,
+                    // or half of the smallest step.
+                    let bound = (max / 512.).max(0.5 / (1 << 24) as f32);
+                    assert!((a - b).abs() <= bound * 1.0001, "{:?} -> {:?}", c, decoded);
+                }
+            }
+        }
+
+        #[test]
+        fn snorm_2_10_10_10() {
+            assert_eq!(Vec3::new(0_f32, 0., 0.).to_snorm_2_10_10_10(), 0);
+            assert_eq!(Vec3::new(2_f32, -2., 0.5).to_snorm_2_10_10_10(), Vec3::new(1_f32, -1., 0.5).to_snorm_2_10_10_10());
+            assert_eq!(Vec3::from_snorm_2_10_10_10(0x200 << 20 | 0x201), Vec3::new(-1., 0., -1.));
+            // The W bits are ignored.
+            assert_eq!(Vec3::from_snorm_2_10_10_10(0xc000_0000), Vec3::zero());
+            let mut values = lcg(3 * 3000).map(|x| x * 2. - 1.);
+            for _ in 0..3000 {
+                let v = Vec3::new(values.next().unwrap(), values.next().unwrap(), values.next().unwrap());
+                let packed = v.to_snorm_2_10_10_10();
+                assert_eq!(packed >> 30, 0);
+                let decoded = Vec3::from_snorm_2_10_10_10(packed);
+                for (a, b) in v.into_iter().zip(decoded) {
+                    assert!((a - b).abs() <= 0.5 / 511. + 1e-6);
+                }
+            }
+        }
+    }
+
+    mod color_adjustments {
+        use crate::vec::repr_c::{Rgb, Rgba};
+
+        // Expected outputs, in 8-bit units, for the 8-bit inputs 0, 64, 128, 200 and 255,
+        // computed with the formulas of the corresponding image editor tools.
+        fn assert_8bit<F: Fn(Rgb<f32>) -> Rgb<f32>>(adjust: F, expected: [f32; 5]) {
+            for (&input, &expected) in [0_f32, 64., 128., 200., 255.].iter().zip(expected.iter()) {
+                let output = adjust(Rgb::broadcast(input / 255.)) * 255.;
+                for x in output {
+                    assert!((x - expected).abs() < 0.01, "{} -> {}, expected {}", input, x, expected);
+                }
+            }
+        }
+
+        #[test]
+        fn levels() {
+            assert_8bit(|c| c.levels(20. / 255., 235. / 255., 1.5, 10. / 255., 245. / 255.), [10., 91.61, 158.499, 218.749, 245.]);
+            let c = Rgb::new(0.25_f32, 0.5, 1.);
+            // Gamma of zero (or less): only the white point stays white.
+            assert_eq!(c.levels(0., 1., 0., 0.1, 0.9), Rgb::new(0.1, 0.1, 0.9));
+            assert_eq!(c.levels(0., 1., -1., 0.1, 0.9), Rgb::new(0.1, 0.1, 0.9));
+            // Equal black and white points threshold, even with a gamma of zero.
+            assert_eq!(c.levels(0.5, 0.5, 0., 0.1, 0.9), Rgb::new(0.1, 0.9, 0.9));
+            // Out of range inputs are clamped first.
+            assert_eq!(Rgb::new(-1_f32, 2., 0.5).levels(0., 1., 2., 0., 1.), Rgb::new(0., 1., 0.5_f32.sqrt()));
+        }
+        #[test]
+        fn brightness_contrast() {
+            assert_8bit(|c| c.brightness_contrast(0.2, 0.3), [2.663, 86.214, 169.765, 255., 255.]);
+            assert_8bit(|c| c.brightness_contrast(-0.25, -0.5), [74.688, 94.57, 114.452, 136.82, 153.906]);
+            // Parameters are clamped.
+            let c = Rgb::new(0.25_f32, 0.5, 0.75);
+            assert_eq!(c.brightness_contrast(5., 0.), Rgb::broadcast(1.));
+            assert_eq!(c.brightness_contrast(-5., 0.), Rgb::broadcast(0.));
+            assert_eq!(c.brightness_contrast(0., 5.), c.brightness_contrast(0., 1.));
+            assert_eq!(c.brightness_contrast(0., -5.), Rgb::broadcast(0.5));
+        }
+        #[test]
+        fn hue_shifted() {
+            let orange = Rgb::new(1_f32, 0.5, 0.);
+            let steps = [
+                (0., orange), (60., Rgb::new(0.5, 1., 0.)), (120., Rgb::new(0., 1., 0.5)),
+                (180., Rgb::new(0., 0.5, 1.)), (240., Rgb::new(0.5, 0., 1.)), (300., Rgb::new(1., 0., 0.5)),
+                (360., orange), (-60., Rgb::new(1., 0., 0.5)), (720., orange),
+            ];
+            for &(degrees, expected) in steps.iter() {
+                assert_relative_eq!(orange.hue_shifted(degrees), expected, epsilon = 1e-6);
+            }
+            // Max and min are kept, so HSL lightness and saturation are too.
+            let c = Rgb::new(0.2_f32, 0.7, 0.4);
+            for i in 0..36 {
+                let shifted = c.hue_shifted(i as f32 * 10.);
+                assert_relative_eq!(shifted.reduce_partial_max(), 0.7);
+                assert_relative_eq!(shifted.reduce_partial_min(), 0.2);
+                assert_relative_eq!(shifted.hue_shifted(-(i as f32) * 10.), c, epsilon = 1e-6);
+            }
+            assert_eq!(Rgb::<f32>::black().hue_shifted(45.), Rgb::black());
+        }
+        #[test]
+        fn saturated() {
+            let c = Rgb::new(0.8_f32, 0.4, 0.2);
+            let gray = Rgb::broadcast(c.luminance());
+            assert_relative_eq!(c.saturated(0.), gray);
+            assert_relative_eq!(c.saturated(0.5), (c + gray) / 2.);
+            // Negative factors give the complementary color, around the same gray.
+            assert_relative_eq!(c.saturated(-1.), gray * 2. - c);
+            assert_eq!(Rgb::<f32>::white().saturated(3.), Rgb::white());
+        }
+        #[test]
+        fn adjust_slice_keeps_alpha() {
+            let mut row = [Rgba::new(0.2_f32, 0.4, 0.6, 0.), Rgba::new(1., 1., 1., 0.3), Rgba::new(0., 0., 0., 1.)];
+            let expected = row.map(|c| Rgba::from_translucent(c.rgb().saturated(0.5).brightness_contrast(0.1, 0.2), c.a));
+            Rgba::adjust_slice(&mut row, |c| c.saturated(0.5).brightness_contrast(0.1, 0.2));
+            assert_eq!(row, expected);
+            Rgba::adjust_slice(&mut [], |c| c.hue_shifted(10.));
+        }
+    }
+
+    mod each_any {
+        use crate::vec::repr_c::{Vec4, Extent2};
+
+        #[test]
+        fn mixed_lanes() {
+            let a = Vec4::new(0, 1, 2, 3);
+            let b = Vec4::new(1, 1, 1, 4);
+            assert!(!a.is_each_lt(&b) && !a.is_each_le(&b) && !a.is_each_gt(&b) && !a.is_each_ge(&b));
+            assert!(a.is_any_lt(&b) && a.is_any_le(&b) && a.is_any_gt(&b) && a.is_any_ge(&b));
+            assert!(a.is_each_le(&a) && a.is_each_ge(&a));
+            assert!(!a.is_any_lt(&a) && !a.is_any_gt(&a));
+            let e = Extent2::new(2_u32, 3);
+            assert!(e.is_each_lt(&Extent2::new(3, 4)));
+            assert!(!e.is_any_gt(&Extent2::new(3, 4)));
+        }
+
+        #[test]
+        fn nan_lanes() {
+            let a = Vec4::new(0_f32, 1., 2., f32::NAN);
+            let b = Vec4::new(1_f32, 2., 3., 4.);
+            // NaN lanes make every `each` form false...
+            assert!(!a.is_each_lt(&b) && !a.is_each_le(&b));
+            assert!(!b.is_each_gt(&a) && !b.is_each_ge(&a));
+            assert!(!a.is_each_le(&a) && !a.is_each_ge(&a));
+            // ...but don't prevent other lanes from satisfying the `any` forms.
+            assert!(a.is_any_lt(&b) && a.is_any_le(&b));
+            assert!(!a.is_any_gt(&b) && !a.is_any_ge(&b));
+            let nan = Vec4::broadcast(f32::NAN);
+            assert!(!nan.is_any_lt(&b) && !nan.is_any_le(&b) && !nan.is_any_gt(&b) && !nan.is_any_ge(&b));
+        }
+    }
+    mod bytes {
+        use crate::vec::repr_c::{Vec2, Vec3, Vec4, Rgba};
+
+        #[test]
+        fn round_trip_and_byte_order() {
+            let v = Vec3::new(1.5_f32, -2., 1e-7);
+            let le = v.to_le_bytes();
+            let be = v.to_be_bytes();
+            assert_eq!(le.len(), 12);
+            assert_eq!(&le[..4], &1.5_f32.to_le_bytes());
+            for (l, b) in le.chunks(4).zip(be.chunks(4)) {
+                assert!(l.iter().eq(b.iter().rev()));
+            }
+            assert_eq!(Vec3::<f32>::from_le_bytes(le), v);
+            assert_eq!(Vec3::<f32>::from_be_bytes(be), v);
+            let w = Vec2::new(-3_i16, 258);
+            assert_eq!(w.to_be_bytes(), [0xff, 0xfd, 0x01, 0x02]);
+            assert_eq!(Vec2::<i16>::from_le_bytes(w.to_le_bytes()), w);
+            let c = Rgba::new(1_u8, 2, 3, 4);
+            assert_eq!(c.to_le_bytes(), [1, 2, 3, 4]);
+            assert_eq!(c.to_be_bytes(), [1, 2, 3, 4]);
+        }
+        #[test]
+        fn slices() {
+            let src = [Vec4::new(1_f64, 2., 3., 4.), Vec4::new(-5., 6., -7., 8.5)];
+            let mut buf = [0_u8; 70];
+            assert_eq!(Vec4::<f64>::write_be_bytes_into(&src, &mut buf), 64);
+            assert_eq!(&buf[32..40], &(-5_f64).to_be_bytes());
+            let mut dst = [Vec4::zero(); 2];
+            assert_eq!(Vec4::<f64>::read_be_bytes_from(&buf, &mut dst), 64);
+            assert_eq!(dst, src);
+            assert_eq!(Vec4::<f64>::write_le_bytes_into(&src, &mut buf), 64);
+            assert_eq!(Vec4::<f64>::read_le_bytes_from(&buf, &mut dst[..1]), 32);
+            assert_eq!(dst[0], src[0]);
+        }
+        #[test]
+        #[should_panic]
+        fn write_too_small() {
+            let mut buf = [0_u8; 11];
+            Vec3::<f32>::write_le_bytes_into(&[Vec3::zero()], &mut buf);
+        }
+    }
+    mod relative_epsilon {
+        use crate::vec::repr_c::{Vec3, Vec4};
+        use crate::ops::relative_epsilon_for;
+
+        #[test]
+        fn millimeter_scale() {
+            // A perfectly usable direction, at a scale where fixed epsilons think it's zero.
+            let d = Vec3::new(1e-4_f32, -2e-4, 0.5e-4);
+            assert!(d.is_approx_zero());
+            assert_eq!(d.try_normalized(), None);
+            let eps = relative_epsilon_for(Some(1e-3), 4.);
+            assert!(!d.is_approx_zero_with_epsilon(eps));
+            let n = d.try_normalized_with_epsilon(eps).unwrap();
+            assert!(n.is_normalized_with_epsilon(relative_epsilon_for(Some(1.), 4.)));
+            assert_relative_eq!(n, d.normalized());
+        }
+        #[test]
+        fn kilometer_scale() {
+            // Two ways of computing the same point disagree by rounding errors that fixed
+            // epsilons consider significant.
+            let a = Vec3::new(1.1e6_f32, -2.3e6, 0.7e6);
+            let b = Vec3::from(a.xy().rotated_z(std::f32::consts::PI * 2.)) + Vec3::unit_z() * a.z;
+            let eps = relative_epsilon_for(a.into_array(), 16.);
+            assert!(!(b - a).is_approx_zero());
+            assert!((b - a).is_approx_zero_with_epsilon(eps));
+            let h = Vec4::from_point(a) * 1.0000001 / 1.0000001;
+            assert!(h.is_point_with_epsilon(relative_epsilon_for(Some(1.), 4.)));
+            assert!(Vec4::from_direction(a).is_direction_with_epsilon(0.));
+        }
+    }
+    mod trigonometry {
+        use crate::vec::repr_c::{Vec4, Extent3};
+        use crate::ops::{sin, cos, tan, atan2};
+
+        // Arguments from tiny to huge, of both signs, where argument reduction gets hard.
+        fn arguments() -> impl Iterator<Item=f64> {
+            (-300..300).map(|i| {
+                let m = 10_f64.powf(i as f64 / 10.) * 1.2345;
+                if i % 2 == 0 { m } else { -m }
+            })
+        }
+        fn same(a: f64, b: f64) -> bool {
+            a.to_bits() == b.to_bits() || (a.is_nan() && b.is_nan())
+        }
+
+        // `#[repr(C)]` vectors are documented to match the scalar functions exactly, i.e within 0 ulp.
+        // These are `crate::ops`' ones, so that this holds with `deterministic-trig` as well.
+        #[test]
+        fn matches_scalar() {
+            let args: [f64; 4] = [0., f64::INFINITY, f64::NAN, -0.];
+            let mut it = arguments().chain(args.iter().cloned());
+            while let (Some(x), Some(y), Some(z), Some(w)) = (it.next(), it.next(), it.next(), it.next()) {
+                let v = Vec4::new(x, y, z, w);
+                let (s, c) = v.sin_cos();
+                for i in 0..4 {
+                    assert!(same(v.sin()[i], sin(v[i])), "sin({})", v[i]);
+                    assert!(same(v.cos()[i], cos(v[i])), "cos({})", v[i]);
+                    assert!(same(v.tan()[i], tan(v[i])), "tan({})", v[i]);
+                    assert!(same(s[i], sin(v[i])) && same(c[i], cos(v[i])), "sin_cos({})", v[i]);
+                    assert!(same(v.atan2(v.wzyx())[i], atan2(v[i], v.wzyx()[i])), "atan2({})", v[i]);
+                }
+                let v = v.map(|x| x as f32);
+                assert_eq!(v.sin().map(f32::to_bits), v.map(|x| sin(x).to_bits()));
+            }
+        }
+        #[test]
+        fn extents() {
+            let e = Extent3::new(0.5_f32, 1e4, -3.);
+            assert_eq!(e.sin(), e.map(sin));
+            assert_eq!(e.cos(), e.map(cos));
+        }
+    }
+    mod slice_chunks {
+        use crate::vec::repr_c::Vec3;
+
+        #[test]
+        fn split_and_mutate() {
+            let mut v = [Vec3::broadcast(0_u32); 7];
+            for (i, v) in v.iter_mut().enumerate() {
+                *v = Vec3::broadcast(i as u32);
+            }
+            {
+                let (chunks, rest) = Vec3::slice_chunks::<3>(&v);
+                assert_eq!(chunks.len(), 2);
+                assert_eq!(chunks[1], [v[3], v[4], v[5]]);
+                assert_eq!(rest, &[v[6]]);
+                let (chunks, rest) = Vec3::slice_chunks::<8>(&v);
+                assert!(chunks.is_empty());
+                assert_eq!(rest.len(), 7);
+            }
+            let (chunks, rest) = Vec3::slice_chunks_mut::<7>(&mut v);
+            assert!(rest.is_empty());
+            chunks[0][6].x = 42;
+            assert_eq!(v[6], Vec3::new(42, 6, 6));
+        }
+        #[test]
+        #[should_panic]
+        fn zero_sized_chunks() {
+            let _ = Vec3::slice_chunks::<0>(&[Vec3::<u8>::zero()]);
+        }
+    }
+    mod jitter {
+        use crate::vec::repr_c::{Vec2, Vec3};
+
+        #[cfg(feature="rand")]
+        #[test]
+        fn uniform_within_bounds() {
+            let mut rng = crate::vtest::XorShiftRng(0x1234_5678_9abc_def0);
+            let center = Vec3::new(1_f64, -2., 3.);
+            let n = 20_000;
+            let mut buckets = [[0_u32; 10]; 3];
+            let mut sum = Vec3::zero();
+            for _ in 0..n {
+                let v = center.jittered(0.5, &mut rng);
+                assert!(v.is_each_ge(&(center - 0.5)) && v.is_each_le(&(center + 0.5)));
+                sum += v;
+                for (b, u) in buckets.iter_mut().zip((v - center + 0.5).into_array().iter()) {
+                    b[((u * 10.) as usize).min(9)] += 1;
+                }
+            }
+            assert_relative_eq!(sum / n as f64, center, epsilon = 0.01);
+            for b in buckets.iter().flat_map(|b| b.iter()) {
+                assert!((*b as i32 - n / 10).abs() < n / 50, "bucket count: {}", b);
+            }
+            assert_eq!(Vec2::new(4_f32, 5.).jittered(0., &mut rng), Vec2::new(4., 5.));
+        }
+        #[test]
+        fn hashed_is_reproducible() {
+            let v = Vec3::new(0.25_f32, -0., 12.);
+            let j = v.jittered_hashed(1., 99);
+            for _ in 0..4 {
+                assert_eq!(v.jittered_hashed(1., 99), j);
+            }
+            assert_eq!(Vec3::new(0.25_f32, 0., 12.).jittered_hashed(1., 99), j);
+            assert_ne!(v.jittered_hashed(1., 100), j);
+            assert!(j.is_each_ge(&(v - 1.)) && j.is_each_le(&(v + 1.)));
+            // Components are offset independently.
+            let d = j - v;
+            assert!(d.x != d.y && d.y != d.z);
+            let w = Vec2::new(3_f64, 4.);
+            assert_eq!(w.jittered_hashed(0.1, 1), w.jittered_hashed(0.1, 1));
+        }
+        #[test]
+        fn hashed_is_spread_out() {
+            // Offsets of nearby points in a grid should cover the whole range evenly.
+            let mut buckets = [0_u32; 8];
+            for y in 0..64 {
+                for x in 0..64 {
+                    let v = Vec2::new(x as f32, y as f32);
+                    let d = v.jittered_hashed(1., 5) - v;
+                    buckets[(((d.x + 1.) * 4.) as usize).min(7)] += 1;
+                }
+            }
+            for b in buckets.iter() {
+                assert!((*b as i32 - 512).abs() < 100, "bucket count: {}", b);
+            }
+        }
+    }
+    mod mask {
+        use crate::vec::repr_c::{Vec2, Vec3, Vec4};
+
+        #[test]
+        fn bit_order() {
+            assert_eq!(Vec4::new(true, false, false, false).to_bits(), 0b0001);
+            assert_eq!(Vec4::new(false, true, false, false).to_bits(), 0b0010);
+            assert_eq!(Vec4::new(false, false, true, false).to_bits(), 0b0100);
+            assert_eq!(Vec4::new(false, false, false, true).to_bits(), 0b1000);
+            assert_eq!(Vec3::new(false, true, true).to_bits(), 0b110);
+            assert_eq!(Vec2::new(true, false).to_bits(), 0b01);
+            for bits in 0..16_u8 {
+                let mask = Vec4::from_bits(bits);
+                assert_eq!(mask.to_bits(), bits);
+                assert_eq!(mask.count(), bits.count_ones() as usize);
+                assert_eq!((mask.all(), mask.any(), mask.none()), (bits == 15, bits != 0, bits == 0));
+                assert_eq!(Vec4::from_bits(bits | 0xf0), mask);
+                assert_eq!(Vec2::from_bits(bits).to_bits(), bits & 0b11);
+                assert_eq!(Vec3::from_bits(bits).to_bits(), bits & 0b111);
+            }
+        }
+        #[test]
+        fn bitwise() {
+            for a in 0..16_u8 {
+                for b in 0..16_u8 {
+                    let (ma, mb) = (Vec4::from_bits(a), Vec4::from_bits(b));
+                    assert_eq!((ma & mb).to_bits(), a & b);
+                    assert_eq!((ma | mb).to_bits(), a | b);
+                    assert_eq!((ma ^ mb).to_bits(), a ^ b);
+                }
+                assert_eq!((!Vec4::from_bits(a)).to_bits(), !a & 0xf);
+            }
+        }
+        #[test]
+        fn select() {
+            let mut state = 0x2545f4914f6cdd1d_u64;
+            let mut random = move || {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state
+            };
+            for _ in 0..1000 {
+                let mask = Vec4::from_bits(random() as u8);
+                let (a, b) = (Vec4::new(random(), random(), random(), random()), Vec4::new(random(), random(), random(), random()));
+                let selected = mask.select(a, b);
+                for i in 0..4 {
+                    assert_eq!(selected[i], if mask[i] { a[i] } else { b[i] });
+                }
+                let mask = Vec3::from_bits(random() as u8);
+                let selected = mask.select(a.xyz(), b.xyz());
+                for i in 0..3 {
+                    assert_eq!(selected[i], if mask[i] { a[i] } else { b[i] });
+                }
+            }
+        }
+        #[test]
+        fn lane_mask() {
+            for bits in 0..16_u8 {
+                let mask = Vec4::from_bits(bits);
+                let lanes = mask.to_lane_mask::<u32>();
+                for i in 0..4 {
+                    assert_eq!(lanes[i], if mask[i] { 0xffff_ffff } else { 0 });
+                }
+                assert_eq!(Vec4::from_lane_mask(lanes), mask);
+                assert_eq!(Vec4::from_lane_mask(mask.to_lane_mask::<i64>()), mask);
+                // Selecting with bitwise operations on lanes gives the same results.
+                let (a, b) = (Vec4::new(1_u32, 2, 3, 4), Vec4::new(5, 6, 7, 8));
+                assert_eq!((a & lanes) | (b & !lanes), mask.select(a, b));
+            }
+        }
+    }
+    mod geographic {
+        use crate::vec::repr_c::{Vec2, Vec3};
+
+        const EARTH_RADIUS_KM: f64 = 6371.;
+
+        fn dms(degrees: f64, minutes: f64, seconds: f64) -> f64 {
+            (degrees + minutes / 60. + seconds / 3600.).to_radians()
+        }
+        fn latlon(lat: f64, lon: f64) -> Vec2<f64> {
+            Vec2::new(lat.to_radians(), lon.to_radians())
+        }
+
+        #[test]
+        fn city_distances() {
+            let london = latlon(51.5074, -0.1278);
+            let paris = latlon(48.8566, 2.3522);
+            let tokyo = latlon(35.6762, 139.6503);
+            let san_francisco = latlon(37.7749, -122.4194);
+            let sydney = latlon(-33.8688, 151.2093);
+            let los_angeles = latlon(34.0522, -118.2437);
+            for &(a, b, km) in &[(london, paris, 343.5), (tokyo, san_francisco, 8275.), (sydney, los_angeles, 12074.)] {
+                let d = a.haversine_distance(b, EARTH_RADIUS_KM);
+                assert!((d - km).abs() < km * 0.001, "{} vs {}", d, km);
+                assert_relative_eq!(d, b.haversine_distance(a, EARTH_RADIUS_KM), max_relative = 1e-12);
+            }
+            assert_eq!(paris.haversine_distance(paris, EARTH_RADIUS_KM), 0.);
+            // Antipodes.
+            assert_relative_eq!(latlon(90., 0.).haversine_distance(latlon(-90., 0.), 1.), ::std::f64::consts::PI);
+            assert_relative_eq!(latlon(0., 170.).haversine_distance(latlon(0., -10.), 1.), ::std::f64::consts::PI);
+        }
+        // The examples at https://www.movable-type.co.uk/scripts/latlong.html
+        #[test]
+        fn lands_end_to_john_o_groats() {
+            let lands_end = Vec2::new(dms(50., 3., 59.), -dms(5., 42., 53.));
+            let john_o_groats = Vec2::new(dms(58., 38., 38.), -dms(3., 4., 12.));
+            assert!((lands_end.haversine_distance(john_o_groats, EARTH_RADIUS_KM) - 968.9).abs() < 0.1);
+            assert!((lands_end.initial_bearing(john_o_groats) - dms(9., 7., 11.)).abs() < dms(0., 0., 1.));
+        }
+        #[test]
+        fn destination_point() {
+            let start = Vec2::new(dms(53., 19., 14.), -dms(1., 43., 47.));
+            let end = start.destination_point(dms(96., 1., 18.), 124.8, EARTH_RADIUS_KM);
+            assert!((end - Vec2::new(dms(53., 11., 18.), dms(0., 8., 0.))).map(f64::abs).reduce_partial_max() < dms(0., 0., 1.));
+            // Travelling back along the reverse bearing.
+            let back = end.destination_point(end.initial_bearing(start), 124.8, EARTH_RADIUS_KM);
+            assert_relative_eq!(back, start, epsilon = 1e-9);
+        }
+        #[test]
+        fn antimeridian() {
+            let fiji = latlon(-17.7, 178.);
+            let samoa = latlon(-13.8, -172.1);
+            // Heading east, across the antimeridian, instead of around the world.
+            let bearing = fiji.initial_bearing(samoa);
+            assert!(bearing > 0. && bearing < ::std::f64::consts::FRAC_PI_2);
+            let distance = fiji.haversine_distance(samoa, EARTH_RADIUS_KM);
+            assert!(distance < 1200.);
+            let end = fiji.destination_point(bearing, distance, EARTH_RADIUS_KM);
+            assert_relative_eq!(end, samoa, epsilon = 1e-9);
+            // Longitudes stay wrapped.
+            assert_relative_eq!(latlon(0., 179.).destination_point(::std::f64::consts::FRAC_PI_2, 2_f64.to_radians(), 1.), latlon(0., -179.), epsilon = 1e-12);
+        }
+        #[test]
+        fn poles() {
+            let north = latlon(90., 0.);
+            assert_relative_eq!(north.latlon_to_unit_vec3(), Vec3::unit_z(), epsilon = 1e-15);
+            assert_relative_eq!(latlon(-90., 123.).latlon_to_unit_vec3(), -Vec3::unit_z(), epsilon = 1e-15);
+            assert_eq!(Vec2::unit_vec3_to_latlon(Vec3::unit_z()), latlon(90., 0.));
+            assert_eq!(Vec2::unit_vec3_to_latlon(-Vec3::<f64>::unit_z()), latlon(-90., 0.));
+            // From the north pole, every direction is south.
+            assert_relative_eq!(north.initial_bearing(latlon(10., 0.)), ::std::f64::consts::PI);
+            assert_relative_eq!(north.destination_point(::std::f64::consts::PI, 0.5, 1.).x, 90_f64.to_radians() - 0.5, epsilon = 1e-12);
+            assert_relative_eq!(latlon(80., 0.).initial_bearing(latlon(80., 180.)), 0., epsilon = 1e-12);
+        }
+        #[test]
+        fn round_trip() {
+            for i in 0..=36 {
+                for j in 0..=72 {
+                    let p = latlon(-90. + i as f64 * 5., -180. + j as f64 * 5.);
+                    let v = p.latlon_to_unit_vec3();
+                    assert_relative_eq!(v.magnitude(), 1., epsilon = 1e-15);
+                    let q = Vec2::unit_vec3_to_latlon(v);
+                    // The longitude is meaningless at the poles, and -180° is 180°.
+                    assert_relative_eq!(q.latlon_to_unit_vec3(), v, epsilon = 1e-15);
+                    assert_relative_eq!(q.x, p.x, epsilon = 1e-15);
+                    if i != 0 && i != 36 && j != 0 {
+                        assert_relative_eq!(q.y, p.y, epsilon = 1e-14);
+                    }
+                }
+            }
+            // Scaled vectors work too.
+            assert_relative_eq!(Vec2::unit_vec3_to_latlon(Vec3::new(0., 2., 2.)), latlon(45., 90.), epsilon = 1e-15);
+        }
+    }
+    mod octahedral {
+        use crate::vec::repr_c::Vec3;
+
+        // Points evenly distributed over the unit sphere.
+        fn fibonacci_sphere(n: usize) -> impl Iterator<Item=Vec3<f32>> {
+            let golden_angle = ::std::f64::consts::PI * (3. - 5_f64.sqrt());
+            (0..n).map(move |i| {
+                let z = 1. - 2. * (i as f64 + 0.5) / n as f64;
+                let r = (1. - z * z).sqrt();
+                let a = golden_angle * i as f64;
+                Vec3::new(r * a.cos(), r * a.sin(), z).map(|x| x as f32)
+            })
+        }
+
+        // Angle between two unit vectors, precise for small angles, unlike `acos()`.
+        fn angle_between(a: Vec3<f32>, b: Vec3<f32>) -> f32 {
+            2. * ((a - b).magnitude() / 2.).asin()
+        }
+
+        #[test]
+        fn round_trip() {
+            let mut max_error = 0_f32;
+            for v in fibonacci_sphere(20_000).chain([Vec3::unit_z(), -Vec3::unit_z(), Vec3::unit_x(), -Vec3::unit_y()].iter().cloned()) {
+                let p = v.to_octahedral();
+                assert!(p.x.abs() <= 1. && p.y.abs() <= 1.);
+                max_error = max_error.max(angle_between(v, Vec3::from_octahedral(p)));
+            }
+            assert!(max_error.to_degrees() < 0.001, "max error: {}°", max_error.to_degrees());
+        }
+        #[test]
+        fn round_trip_u16x2() {
+            let mut max_error = 0_f32;
+            for v in fibonacci_sphere(20_000) {
+                max_error = max_error.max(angle_between(v, Vec3::from_octahedral_u16x2(v.to_octahedral_u16x2())));
+            }
+            assert!(max_error.to_degrees() < 0.5, "max error: {}°", max_error.to_degrees());
+        }
+    }
+
     macro_rules! for_each_type {
         ($vec:ident $Vec:ident $($T:ident)+) => {
             mod $vec {
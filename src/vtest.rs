@@ -22,4 +22,31 @@ impl<T> Rc<T> {
     pub fn make_mut(s: &mut Self) -> &mut T {
         &mut s.val
     }
+}
+
+/// Small deterministic generator (xorshift64*) for tests that need a `rand::Rng`.
+#[cfg(feature = "rand")]
+pub struct XorShiftRng(pub u64);
+
+#[cfg(feature = "rand")]
+impl rand::RngCore for XorShiftRng {
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
 }
\ No newline at end of file
@@ -0,0 +1,257 @@
+//! Derive macros for the `vek` crate.
+//!
+//! These are re-exported by `vek` when its `derive` feature is enabled, which is the intended
+//! way to use them.
+
+#![warn(missing_docs)]
+
+extern crate proc_macro;
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, parse_quote, parse_quote_spanned, Data, DeriveInput, Fields, Member};
+
+/// How a field is interpolated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FieldMode {
+    Lerp,
+    Slerp,
+    Skip,
+}
+
+/// Gets the factor type given by `#[lerp(factor = ...)]` on the struct, if any.
+fn factor_type(attrs: &[syn::Attribute]) -> syn::Result<Option<syn::Type>> {
+    let mut factor = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("lerp")) {
+        attr.parse_nested_meta(|meta| {
+            if !meta.path.is_ident("factor") {
+                return Err(meta.error("unknown `lerp` attribute; expected `factor = ...`"));
+            }
+            factor = Some(meta.value()?.parse()?);
+            Ok(())
+        })?;
+    }
+    Ok(factor)
+}
+
+fn field_mode(field: &syn::Field) -> syn::Result<FieldMode> {
+    let mut mode = FieldMode::Lerp;
+    for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("lerp")) {
+        attr.parse_nested_meta(|meta| {
+            let new_mode = if meta.path.is_ident("skip") {
+                FieldMode::Skip
+            } else if meta.path.is_ident("slerp") {
+                FieldMode::Slerp
+            } else {
+                return Err(meta.error("unknown `lerp` attribute; expected `skip` or `slerp`"));
+            };
+            if mode != FieldMode::Lerp {
+                return Err(meta.error("a field can only have one of `#[lerp(skip)]` and `#[lerp(slerp)]`"));
+            }
+            mode = new_mode;
+            Ok(())
+        })?;
+    }
+    Ok(mode)
+}
+
+/// Implements `vek::ops::Lerp` for a struct, by interpolating each of its fields.
+///
+/// Like for the `Lerp` trait, the factor type is `f32` by default. Another one can be chosen with
+/// the `#[lerp(factor = ...)]` attribute on the struct, which may also name one of the struct's
+/// type parameters. It must be `Copy`.
+///
+/// Each field's type `T` must implement `Lerp<Factor, Output=T>`, so e.g `f32`, `Vec2<f32>` and
+/// `Rgba<f32>` fields can be mixed together. Fields which don't are reported where the struct
+/// is defined, except those whose types depend on the struct's type parameters, for which
+/// bounds are added to the implementation instead.
+///
+/// Fields accept the following attributes:
+/// - `#[lerp(slerp)]` interpolates the field with `vek::ops::Slerp` instead (e.g for
+///   quaternions), which must then be implemented as `Slerp<Factor, Output=T>`;
+/// - `#[lerp(skip)]` doesn't interpolate the field, which keeps the value of `from`. Its type
+///   doesn't need to implement anything.
+///
+/// `lerp_unclamped()` and `lerp_unclamped_precise()` are both implemented field-wise;
+/// `lerp()` and `lerp_precise()` clamp the factor once, then use them.
+///
+/// ```
+/// # extern crate vek;
+/// # #[macro_use] extern crate approx;
+/// use vek::{Lerp, Quaternion, Rgba, Vec2};
+///
+/// #[derive(Debug, Clone, PartialEq, Lerp)]
+/// struct Style {
+///     color: Rgba<f32>,
+///     offset: Vec2<f32>,
+///     corner_radius: f32,
+///     #[lerp(slerp)]
+///     orientation: Quaternion<f32>,
+///     #[lerp(skip)]
+///     name: &'static str,
+/// }
+///
+/// # fn main() {
+/// let from = Style {
+///     color: Rgba::black(), offset: Vec2::zero(), corner_radius: 2.,
+///     orientation: Quaternion::identity(), name: "from",
+/// };
+/// let to = Style {
+///     color: Rgba::white(), offset: Vec2::new(10., -4.), corner_radius: 6.,
+///     orientation: Quaternion::rotation_z(1.), name: "to",
+/// };
+/// let mid = Lerp::lerp(from, to, 0.5);
+/// assert_eq!(mid.color, Rgba::new(0.5, 0.5, 0.5, 1.));
+/// assert_eq!(mid.offset, Vec2::new(5., -2.));
+/// assert_eq!(mid.corner_radius, 4.);
+/// assert_relative_eq!(mid.orientation, Quaternion::rotation_z(0.5));
+/// assert_eq!(mid.name, "from");
+/// # }
+/// ```
+///
+/// Tuple structs and generic structs are supported as well:
+///
+/// ```
+/// use vek::{Lerp, Vec3};
+///
+/// #[derive(Debug, PartialEq, Lerp)]
+/// #[lerp(factor = f64)]
+/// struct Keyframe(Vec3<f64>, f64);
+///
+/// let k = Lerp::lerp_unclamped(Keyframe(Vec3::zero(), 1.), Keyframe(Vec3::one(), 3.), 2.);
+/// assert_eq!(k, Keyframe(Vec3::broadcast(2.), 5.));
+///
+/// #[derive(Debug, PartialEq, Lerp)]
+/// #[lerp(factor = T)]
+/// struct Segment<T> { start: Vec3<T>, end: Vec3<T> }
+///
+/// let a = Segment { start: Vec3::zero(), end: Vec3::one() };
+/// let b = Segment { start: Vec3::one(), end: Vec3::zero() };
+/// assert_eq!(Lerp::lerp(a, b, 0.25_f32), Segment { start: Vec3::broadcast(0.25), end: Vec3::broadcast(0.75) });
+/// ```
+///
+/// Fields which can't be interpolated are reported at their type:
+///
+/// ```compile_fail
+/// use vek::Lerp;
+///
+/// #[derive(Lerp)]
+/// struct Label {
+///     size: f32,
+///     text: String, // error: the trait bound `String: Lerp` is not satisfied
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use vek::{Lerp, Vec2};
+///
+/// #[derive(Lerp)]
+/// struct Particle {
+///     #[lerp(slerp)]
+///     position: Vec2<f32>, // error: the trait bound `Vec2<f32>: Slerp` is not satisfied
+/// }
+/// ```
+///
+/// Enums, unions and unknown attributes are rejected:
+///
+/// ```compile_fail
+/// use vek::Lerp;
+///
+/// #[derive(Lerp)]
+/// enum Shape { Point(f32), Circle(f32) } // error: `Lerp` can only be derived for structs
+/// ```
+///
+/// ```compile_fail
+/// use vek::Lerp;
+///
+/// #[derive(Lerp)]
+/// struct Style {
+///     #[lerp(smooth)] // error: unknown `lerp` attribute; expected `skip` or `slerp`
+///     radius: f32,
+/// }
+/// ```
+#[proc_macro_derive(Lerp, attributes(lerp))]
+pub fn derive_lerp(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand_lerp(input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn expand_lerp(input: DeriveInput) -> syn::Result<TokenStream> {
+    let fields = match input.data {
+        Data::Struct(data) => data.fields,
+        Data::Enum(data) => return Err(syn::Error::new(data.enum_token.span(), "`Lerp` can only be derived for structs")),
+        Data::Union(data) => return Err(syn::Error::new(data.union_token.span(), "`Lerp` can only be derived for structs")),
+    };
+    let fields: Vec<_> = match fields {
+        Fields::Named(fields) => fields.named.into_iter().collect(),
+        Fields::Unnamed(fields) => fields.unnamed.into_iter().collect(),
+        Fields::Unit => Vec::new(),
+    };
+
+    let name = &input.ident;
+    // Same default as the `Lerp` trait.
+    let factor = factor_type(&input.attrs)?.unwrap_or_else(|| parse_quote!(f32));
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    where_clause.predicates.push(parse_quote!(#factor: ::core::marker::Copy));
+
+    let mut members = Vec::new();
+    let mut from_bindings = Vec::new();
+    let mut to_bindings = Vec::new();
+    let mut values = Vec::new();
+    let mut precise_values = Vec::new();
+    for (i, field) in fields.iter().enumerate() {
+        let mode = field_mode(field)?;
+        let ty = &field.ty;
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(i.into()),
+        };
+        let (from, to) = (format_ident!("__from_{}", i), format_ident!("__to_{}", i));
+        // Bounds are spanned to the field's type, so that errors point to it.
+        let (value, precise_value) = match mode {
+            FieldMode::Lerp => {
+                where_clause.predicates.push(parse_quote_spanned!(ty.span()=> #ty: ::vek::ops::Lerp<#factor, Output=#ty>));
+                (
+                    quote_spanned!(ty.span()=> ::vek::ops::Lerp::lerp_unclamped(#from, #to, factor)),
+                    quote_spanned!(ty.span()=> ::vek::ops::Lerp::lerp_unclamped_precise(#from, #to, factor)),
+                )
+            },
+            FieldMode::Slerp => {
+                where_clause.predicates.push(parse_quote_spanned!(ty.span()=> #ty: ::vek::ops::Slerp<#factor, Output=#ty>));
+                let value = quote_spanned!(ty.span()=> ::vek::ops::Slerp::slerp_unclamped(#from, #to, factor));
+                (value.clone(), value)
+            },
+            FieldMode::Skip => (quote!(#from), quote!(#from)),
+        };
+        let to = if mode == FieldMode::Skip { quote!(_) } else { quote!(#to) };
+        members.push(member);
+        from_bindings.push(from);
+        to_bindings.push(to);
+        values.push(value);
+        precise_values.push(precise_value);
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::vek::ops::Lerp<#factor> for #name #ty_generics #where_clause {
+            type Output = Self;
+            #[allow(unused_variables)]
+            fn lerp_unclamped(from: Self, to: Self, factor: #factor) -> Self {
+                let Self { #(#members: #from_bindings,)* } = from;
+                let Self { #(#members: #to_bindings,)* } = to;
+                Self { #(#members: #values,)* }
+            }
+            #[allow(unused_variables)]
+            fn lerp_unclamped_precise(from: Self, to: Self, factor: #factor) -> Self {
+                let Self { #(#members: #from_bindings,)* } = from;
+                let Self { #(#members: #to_bindings,)* } = to;
+                Self { #(#members: #precise_values,)* }
+            }
+        }
+    })
+}
@@ -0,0 +1,86 @@
+use vek::{Lerp, Quaternion, Rgba, Vec2, Vec3};
+use vek::ops::Slerp;
+
+#[derive(Debug, Clone, PartialEq, Lerp)]
+struct Style {
+    color: Rgba<f32>,
+    offset: Vec2<f32>,
+    corner_radius: f32,
+    #[lerp(slerp)]
+    orientation: Quaternion<f32>,
+    #[lerp(skip)]
+    name: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Lerp)]
+#[lerp(factor = f64)]
+struct Keyframe(Vec3<f64>, #[lerp(skip)] u32, i32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Lerp)]
+#[lerp(factor = T)]
+struct Segment<T> where T: Copy {
+    start: Vec2<T>,
+    end: Vec2<T>,
+    width: T,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Lerp)]
+struct Nothing;
+
+fn style(x: f32, name: &str) -> Style {
+    Style {
+        color: Rgba::broadcast(x),
+        offset: Vec2::new(x, -x),
+        corner_radius: x * 2.,
+        orientation: Quaternion::rotation_y(x),
+        name: name.to_owned(),
+    }
+}
+
+#[test]
+fn named_fields() {
+    let (from, to) = (style(0., "from"), style(1., "to"));
+    for &t in &[0_f32, 0.25, 0.5, 1.] {
+        let s = Lerp::lerp(from.clone(), to.clone(), t);
+        assert_eq!(s.color, Lerp::lerp(from.color, to.color, t));
+        assert_eq!(s.offset, Lerp::lerp(from.offset, to.offset, t));
+        assert_eq!(s.corner_radius, Lerp::lerp(from.corner_radius, to.corner_radius, t));
+        assert_eq!(s.orientation, Slerp::slerp(from.orientation, to.orientation, t));
+        assert_eq!(s.name, "from");
+    }
+}
+
+#[test]
+fn factor_is_clamped_once() {
+    let (from, to) = (style(0., "from"), style(1., "to"));
+    assert_eq!(Lerp::lerp(from.clone(), to.clone(), 2.), Lerp::lerp_unclamped(from.clone(), to.clone(), 1.));
+    assert_eq!(Lerp::lerp_precise(from.clone(), to.clone(), -1.), Lerp::lerp_unclamped_precise(from, to, 0.));
+}
+
+#[test]
+fn tuple_fields_and_factor_type() {
+    let a = Keyframe(Vec3::zero(), 7, 10);
+    let b = Keyframe(Vec3::new(2., 4., 8.), 9, 20);
+    assert_eq!(Lerp::lerp_unclamped(a, b, 0.5_f64), Keyframe(Vec3::new(1., 2., 4.), 7, 15));
+    assert_eq!(Lerp::lerp_unclamped_precise(a, b, 1.5_f64), Keyframe(Vec3::new(3., 6., 12.), 7, 25));
+}
+
+#[test]
+fn generic_struct() {
+    let a = Segment { start: Vec2::zero(), end: Vec2::one(), width: 1. };
+    let b = Segment { start: Vec2::one(), end: Vec2::zero(), width: 3. };
+    let expected = Segment { start: Vec2::broadcast(0.25), end: Vec2::broadcast(0.75), width: 1.5 };
+    assert_eq!(Lerp::lerp(a, b, 0.25_f64), expected);
+    assert_eq!(Lerp::lerp(a.map_f32(), b.map_f32(), 0.25_f32), expected.map_f32());
+}
+
+impl Segment<f64> {
+    fn map_f32(self) -> Segment<f32> {
+        Segment { start: self.start.as_(), end: self.end.as_(), width: self.width as f32 }
+    }
+}
+
+#[test]
+fn unit_struct() {
+    assert_eq!(Lerp::lerp(Nothing, Nothing, 0.5), Nothing);
+}
@@ -0,0 +1,8 @@
+// Checks the errors reported for invalid uses of the derive macros, against the `.stderr` file
+// next to each case: invalid attributes and items, and fields whose types can't be interpolated.
+// Run with `TRYBUILD=overwrite` to update the `.stderr` files after an intended change.
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,9 @@
+use vek::Lerp;
+
+#[derive(Lerp)]
+enum Shape {
+    Point(f32),
+    Circle(f32),
+}
+
+fn main() {}
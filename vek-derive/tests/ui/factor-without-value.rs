@@ -0,0 +1,9 @@
+use vek::{Lerp, Vec2};
+
+#[derive(Lerp)]
+#[lerp(factor)]
+struct Particle {
+    position: Vec2<f64>,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use vek::Lerp;
+
+#[derive(Lerp)]
+struct Label {
+    size: f32,
+    text: String,
+}
+
+fn main() {}
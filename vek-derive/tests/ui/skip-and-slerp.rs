@@ -0,0 +1,9 @@
+use vek::{Lerp, Quaternion};
+
+#[derive(Lerp)]
+struct Joint {
+    #[lerp(skip, slerp)]
+    orientation: Quaternion<f32>,
+}
+
+fn main() {}
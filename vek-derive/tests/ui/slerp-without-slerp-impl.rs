@@ -0,0 +1,9 @@
+use vek::{Lerp, Vec2};
+
+#[derive(Lerp)]
+struct Particle {
+    #[lerp(slerp)]
+    position: Vec2<f32>,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use vek::Lerp;
+
+#[derive(Lerp)]
+struct Style {
+    #[lerp(smooth)]
+    radius: f32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use vek::{Lerp, Vec2};
+
+#[derive(Lerp)]
+#[lerp(skip)]
+struct Particle {
+    position: Vec2<f32>,
+}
+
+fn main() {}